@@ -15,10 +15,13 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::encoding::{encode_bytes, encode_long};
 use crate::schema::{Attributes, ComplexType, PrimitiveType, Record, Schema, TypeName};
 use arrow_schema::{
-    ArrowError, DataType, Field, FieldRef, IntervalUnit, SchemaBuilder, SchemaRef, TimeUnit,
+    ArrowError, DataType, Field, FieldRef, Fields, IntervalUnit, SchemaBuilder, SchemaRef,
+    TimeUnit, DECIMAL128_MAX_PRECISION, DECIMAL256_MAX_PRECISION,
 };
+use serde_json::json;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -28,8 +31,8 @@ use std::sync::Arc;
 ///
 /// To accommodate this we special case two-variant unions where one of the
 /// variants is the null type, and use this to derive arrow's notion of nullability
-#[derive(Debug, Copy, Clone)]
-enum Nulls {
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Nulls {
     /// The nulls are encoded as the first union variant
     NullFirst,
     /// The nulls are encoded as the second union variant
@@ -42,6 +45,10 @@ pub struct AvroDataType {
     nulls: Option<Nulls>,
     metadata: HashMap<String, String>,
     codec: Codec,
+    /// The name and aliases of a record, enum or fixed type
+    named: Option<Arc<NamedType>>,
+    /// How to read data encoded with a different writer schema, see [`AvroField::resolve`]
+    resolution: Option<Resolution>,
 }
 
 impl AvroDataType {
@@ -50,6 +57,85 @@ impl AvroDataType {
         let d = self.codec.data_type();
         Field::new(name, d, self.nulls.is_some()).with_metadata(self.metadata.clone())
     }
+
+    /// Returns the [`Codec`]
+    pub fn codec(&self) -> &Codec {
+        &self.codec
+    }
+
+    /// Returns how nulls are encoded for this type, if it is nullable
+    pub(crate) fn nulls(&self) -> Option<Nulls> {
+        self.nulls
+    }
+
+    /// Returns how nulls are encoded by the writer of this type, if it is nullable
+    ///
+    /// This differs from [`Self::nulls`] only if this type was resolved against a writer schema
+    pub(crate) fn writer_nulls(&self) -> Option<Nulls> {
+        match &self.resolution {
+            Some(r) => r.writer_nulls,
+            None => self.nulls,
+        }
+    }
+
+    /// Returns how the writer encoding of this type must be resolved, if at all
+    pub(crate) fn resolution(&self) -> Option<&ResolutionKind> {
+        self.resolution.as_ref().and_then(|r| r.kind.as_ref())
+    }
+}
+
+/// Describes how data encoded with a writer schema is read as an [`AvroDataType`]
+///
+/// <https://avro.apache.org/docs/1.11.1/specification/#schema-resolution>
+#[derive(Debug, Clone)]
+pub(crate) struct Resolution {
+    /// How nulls are encoded by the writer
+    writer_nulls: Option<Nulls>,
+    /// How non-null values encoded by the writer must be resolved, if at all
+    kind: Option<ResolutionKind>,
+}
+
+/// How a non-null value encoded by the writer is resolved to the reader type
+#[derive(Debug, Clone)]
+pub(crate) enum ResolutionKind {
+    /// The writer type is promoted to the reader type
+    Promotion(Promotion),
+    /// The writer enum symbols differ from those of the reader
+    ///
+    /// Maps the index of each writer symbol to the index of the reader symbol, if any
+    EnumMapping(Arc<[Option<i32>]>),
+    /// The writer record fields differ from those of the reader
+    Record(Arc<RecordResolution>),
+}
+
+/// A promotion of a writer primitive type to a reader primitive type
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Promotion {
+    IntToLong,
+    IntToFloat,
+    IntToDouble,
+    LongToFloat,
+    LongToDouble,
+    FloatToDouble,
+}
+
+/// Describes how a record encoded with a writer schema is read as a reader record
+#[derive(Debug)]
+pub(crate) struct RecordResolution {
+    /// For each writer field, in writer order, how it should be handled
+    pub(crate) writer_fields: Vec<WriterField>,
+    /// The index of each reader field absent from the writer schema, along with
+    /// the Avro binary encoding of its default value
+    pub(crate) defaults: Vec<(usize, Vec<u8>)>,
+}
+
+/// How a field of a writer record is handled
+#[derive(Debug)]
+pub(crate) enum WriterField {
+    /// The field is read into the reader field with the given index
+    Read(usize),
+    /// The field is absent from the reader schema, and is skipped
+    Skip(AvroDataType),
 }
 
 /// A named [`AvroDataType`]
@@ -57,6 +143,40 @@ impl AvroDataType {
 pub struct AvroField {
     name: String,
     data_type: AvroDataType,
+    /// The default value of this field, if any
+    default: Option<serde_json::Value>,
+    /// Alternative names of this field, used to match writer fields
+    aliases: Vec<String>,
+}
+
+/// The name and aliases of a named Avro type, used to match writer and reader types
+#[derive(Debug)]
+struct NamedType {
+    name: String,
+    aliases: Vec<String>,
+    /// The default symbol of an enum, used for writer symbols absent from the reader
+    default_symbol: Option<String>,
+}
+
+impl NamedType {
+    fn new(name: &str, aliases: &[&str], default_symbol: Option<&str>) -> Arc<Self> {
+        Arc::new(Self {
+            name: name.to_string(),
+            aliases: aliases.iter().map(|x| x.to_string()).collect(),
+            default_symbol: default_symbol.map(|x| x.to_string()),
+        })
+    }
+
+    /// Returns true if this reader type matches the `writer` type by its unqualified
+    /// name, or one of its aliases
+    ///
+    /// <https://avro.apache.org/docs/1.11.1/specification/#aliases>
+    fn matches(&self, writer: &Self) -> bool {
+        let unqualified = |name: &str| name.rsplit('.').next().unwrap_or(name).to_string();
+        let writer_name = unqualified(&writer.name);
+        unqualified(&self.name) == writer_name
+            || self.aliases.iter().any(|a| unqualified(a) == writer_name)
+    }
 }
 
 impl AvroField {
@@ -69,6 +189,16 @@ impl AvroField {
     pub fn codec(&self) -> &Codec {
         &self.data_type.codec
     }
+
+    /// Returns the name of this field
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the [`AvroDataType`] of this field
+    pub fn data_type(&self) -> &AvroDataType {
+        &self.data_type
+    }
 }
 
 impl<'a> TryFrom<&Schema<'a>> for AvroField {
@@ -82,6 +212,8 @@ impl<'a> TryFrom<&Schema<'a>> for AvroField {
                 Ok(AvroField {
                     data_type,
                     name: r.name.to_string(),
+                    default: None,
+                    aliases: vec![],
                 })
             }
             _ => Err(ArrowError::ParseError(format!(
@@ -91,6 +223,74 @@ impl<'a> TryFrom<&Schema<'a>> for AvroField {
     }
 }
 
+impl AvroField {
+    /// Creates an [`AvroField`] that reads data encoded with the `writer` schema into
+    /// the arrow representation of the `reader` schema
+    ///
+    /// Both schemas must be records, and are resolved according to the rules of the
+    /// [Avro specification], in particular:
+    ///
+    /// * Records, enums and fixed types are matched by their unqualified name, or the
+    ///   aliases of the reader type
+    /// * Fields are matched by name, or the aliases of the reader field, and so may be
+    ///   reordered
+    /// * Writer fields absent from the reader schema are skipped
+    /// * Reader fields absent from the writer schema take their `default` value
+    /// * `int`, `long` and `float` may be promoted to `long`, `float` or `double`
+    /// * `string` and `bytes` may be read as one another
+    /// * Enum symbols are matched by name, with writer symbols absent from the reader
+    ///   taking the `default` symbol of the reader enum, if any
+    ///
+    /// [Avro specification]: https://avro.apache.org/docs/1.11.1/specification/#schema-resolution
+    pub fn resolve(writer: &Schema<'_>, reader: &Schema<'_>) -> Result<Self, ArrowError> {
+        let writer = AvroField::try_from(writer)?;
+        let reader = AvroField::try_from(reader)?;
+        Ok(AvroField {
+            data_type: resolve_data_type(&writer.data_type, &reader.data_type)?,
+            name: reader.name,
+            default: None,
+            aliases: vec![],
+        })
+    }
+
+    /// Creates an [`AvroField`] for a record named `name` with the provided arrow [`Fields`]
+    ///
+    /// This is the inverse of the mapping performed by [`AvroField::field`], with arrow types
+    /// lacking an exact Avro equivalent mapped to the closest Avro type, e.g. `Int8`
+    /// is mapped to `int`, and `LargeUtf8` to `string`
+    pub fn try_from_arrow(name: &str, fields: &Fields) -> Result<Self, ArrowError> {
+        let fields = fields
+            .iter()
+            .map(|f| {
+                Ok(AvroField {
+                    name: f.name().clone(),
+                    data_type: arrow_data_type(f)?,
+                    default: None,
+                    aliases: vec![],
+                })
+            })
+            .collect::<Result<_, ArrowError>>()?;
+
+        Ok(AvroField {
+            name: name.to_string(),
+            data_type: AvroDataType {
+                nulls: None,
+                metadata: Default::default(),
+                codec: Codec::Struct(fields),
+                named: None,
+                resolution: None,
+            },
+            default: None,
+            aliases: vec![],
+        })
+    }
+
+    /// Returns the JSON encoded Avro schema of this field
+    pub fn avro_schema(&self) -> serde_json::Value {
+        avro_schema(&self.data_type, &self.name, "")
+    }
+}
+
 /// An Avro encoding
 ///
 /// <https://avro.apache.org/docs/1.11.1/specification/#encodings>
@@ -112,7 +312,17 @@ pub enum Codec {
     /// TimestampMicros(is_utc)
     TimestampMicros(bool),
     Fixed(i32),
+    /// Decimal(precision, scale, fixed_size)
+    ///
+    /// Decimals are encoded either as variable length bytes, or as a fixed of `fixed_size`
+    Decimal(usize, usize, Option<usize>),
+    /// A string annotated with the uuid logical type
+    Uuid,
+    /// An enumeration with the provided symbols
+    Enum(Arc<[String]>),
     List(Arc<AvroDataType>),
+    /// A map with string keys and values of the provided type
+    Map(Arc<AvroDataType>),
     Struct(Arc<[AvroField]>),
     Duration,
 }
@@ -139,12 +349,71 @@ impl Codec {
             }
             Self::Duration => DataType::Interval(IntervalUnit::MonthDayNano),
             Self::Fixed(size) => DataType::FixedSizeBinary(*size),
+            Self::Decimal(precision, scale, _) => {
+                if *precision <= DECIMAL128_MAX_PRECISION as usize {
+                    DataType::Decimal128(*precision as u8, *scale as i8)
+                } else {
+                    DataType::Decimal256(*precision as u8, *scale as i8)
+                }
+            }
+            Self::Uuid => DataType::FixedSizeBinary(16),
+            Self::Enum(_) => {
+                DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+            }
             Self::List(f) => DataType::List(Arc::new(f.field_with_name("item"))),
+            Self::Map(f) => DataType::Map(Arc::new(map_entries_field(f)), false),
             Self::Struct(f) => DataType::Struct(f.iter().map(|x| x.field()).collect()),
         }
     }
 }
 
+/// The metadata key used to store the symbols of an Avro enum on the corresponding arrow [`Field`]
+pub const ENUM_SYMBOLS_METADATA_KEY: &str = "avro.enum.symbols";
+
+/// The metadata key and value identifying the canonical arrow UUID extension type
+const EXTENSION_NAME_METADATA_KEY: &str = "ARROW:extension:name";
+const UUID_EXTENSION_NAME: &str = "arrow.uuid";
+
+/// Returns the entries [`Field`] of an arrow map with values of type `values`
+fn map_entries_field(values: &AvroDataType) -> Field {
+    let fields = Fields::from(vec![
+        Field::new("key", DataType::Utf8, false),
+        values.field_with_name("value"),
+    ]);
+    Field::new("entries", DataType::Struct(fields), false)
+}
+
+/// The attributes of a decimal logical type, these are not preserved in the field metadata
+const DECIMAL_ATTRIBUTES: [&str; 2] = ["precision", "scale"];
+
+/// Parses the `precision` and `scale` attributes of a decimal logical type
+///
+/// <https://avro.apache.org/docs/1.11.1/specification/#decimal>
+fn decimal_precision_scale(attributes: &Attributes<'_>) -> Result<(usize, usize), ArrowError> {
+    let get = |name: &str| match attributes.additional.get(name) {
+        None => Ok(None),
+        Some(v) => v.as_u64().map(|x| Some(x as usize)).ok_or_else(|| {
+            ArrowError::ParseError(format!("Invalid decimal {name}, expected integer got {v}"))
+        }),
+    };
+
+    let precision = get("precision")?
+        .ok_or_else(|| ArrowError::ParseError("Decimal requires precision".to_string()))?;
+    let scale = get("scale")?.unwrap_or(0);
+
+    if precision == 0 || precision > DECIMAL256_MAX_PRECISION as usize {
+        return Err(ArrowError::ParseError(format!(
+            "Unsupported decimal precision {precision}"
+        )));
+    }
+    if scale > precision {
+        return Err(ArrowError::ParseError(format!(
+            "Decimal scale {scale} exceeds precision {precision}"
+        )));
+    }
+    Ok((precision, scale))
+}
+
 impl From<PrimitiveType> for Codec {
     fn from(value: PrimitiveType) -> Self {
         match value {
@@ -201,6 +470,8 @@ fn make_data_type<'a>(
             nulls: None,
             metadata: Default::default(),
             codec: (*p).into(),
+            named: None,
+            resolution: None,
         }),
         Schema::TypeName(TypeName::Ref(name)) => resolver.resolve(name, namespace),
         Schema::Union(f) => {
@@ -234,6 +505,8 @@ fn make_data_type<'a>(
                         Ok(AvroField {
                             name: field.name.to_string(),
                             data_type: make_data_type(&field.r#type, namespace, resolver)?,
+                            default: field.default.clone(),
+                            aliases: field.aliases.iter().map(|x| x.to_string()).collect(),
                         })
                     })
                     .collect::<Result<_, ArrowError>>()?;
@@ -242,6 +515,8 @@ fn make_data_type<'a>(
                     nulls: None,
                     codec: Codec::Struct(fields),
                     metadata: r.attributes.field_metadata(),
+                    named: Some(NamedType::new(r.name, &r.aliases, None)),
+                    resolution: None,
                 };
                 resolver.register(r.name, namespace, field.clone());
                 Ok(field)
@@ -252,6 +527,8 @@ fn make_data_type<'a>(
                     nulls: None,
                     metadata: a.attributes.field_metadata(),
                     codec: Codec::List(Arc::new(field)),
+                    named: None,
+                    resolution: None,
                 })
             }
             ComplexType::Fixed(f) => {
@@ -259,20 +536,58 @@ fn make_data_type<'a>(
                     ArrowError::ParseError(format!("Overflow converting size to i32: {e}"))
                 })?;
 
+                let codec = match (f.attributes.logical_type, f.size) {
+                    (Some("decimal"), _) => {
+                        let (precision, scale) = decimal_precision_scale(&f.attributes)?;
+                        Codec::Decimal(precision, scale, Some(f.size))
+                    }
+                    (Some("duration"), 12) => Codec::Duration,
+                    _ => Codec::Fixed(size),
+                };
+
+                let mut metadata = f.attributes.field_metadata();
+                if matches!(codec, Codec::Decimal(_, _, _)) {
+                    metadata.retain(|k, _| !DECIMAL_ATTRIBUTES.contains(&k.as_str()));
+                }
+
                 let field = AvroDataType {
                     nulls: None,
-                    metadata: f.attributes.field_metadata(),
-                    codec: Codec::Fixed(size),
+                    metadata,
+                    codec,
+                    named: Some(NamedType::new(f.name, &f.aliases, None)),
+                    resolution: None,
                 };
                 resolver.register(f.name, namespace, field.clone());
                 Ok(field)
             }
-            ComplexType::Enum(e) => Err(ArrowError::NotYetImplemented(format!(
-                "Enum of {e:?} not currently supported"
-            ))),
-            ComplexType::Map(m) => Err(ArrowError::NotYetImplemented(format!(
-                "Map of {m:?} not currently supported"
-            ))),
+            ComplexType::Enum(e) => {
+                let symbols: Arc<[String]> = e.symbols.iter().map(|s| s.to_string()).collect();
+                let mut metadata = e.attributes.field_metadata();
+                metadata.insert(
+                    ENUM_SYMBOLS_METADATA_KEY.to_string(),
+                    serde_json::to_string(&e.symbols).unwrap(),
+                );
+
+                let field = AvroDataType {
+                    nulls: None,
+                    metadata,
+                    codec: Codec::Enum(symbols),
+                    named: Some(NamedType::new(e.name, &e.aliases, e.default)),
+                    resolution: None,
+                };
+                resolver.register(e.name, namespace, field.clone());
+                Ok(field)
+            }
+            ComplexType::Map(m) => {
+                let values = make_data_type(m.values.as_ref(), namespace, resolver)?;
+                Ok(AvroDataType {
+                    nulls: None,
+                    metadata: m.attributes.field_metadata(),
+                    codec: Codec::Map(Arc::new(values)),
+                    named: None,
+                    resolution: None,
+                })
+            }
         },
         Schema::Type(t) => {
             let mut field =
@@ -280,10 +595,16 @@ fn make_data_type<'a>(
 
             // https://avro.apache.org/docs/1.11.1/specification/#logical-types
             match (t.attributes.logical_type, &mut field.codec) {
-                (Some("decimal"), c @ Codec::Fixed(_)) => {
-                    return Err(ArrowError::NotYetImplemented(
-                        "Decimals are not currently supported".to_string(),
-                    ))
+                (Some("decimal"), c @ Codec::Binary) => {
+                    let (precision, scale) = decimal_precision_scale(&t.attributes)?;
+                    *c = Codec::Decimal(precision, scale, None)
+                }
+                (Some("uuid"), c @ Codec::Utf8) => {
+                    *c = Codec::Uuid;
+                    field.metadata.insert(
+                        EXTENSION_NAME_METADATA_KEY.to_string(),
+                        UUID_EXTENSION_NAME.to_string(),
+                    );
                 }
                 (Some("date"), c @ Codec::Int32) => *c = Codec::Date32,
                 (Some("time-millis"), c @ Codec::Int32) => *c = Codec::TimeMillis,
@@ -305,11 +626,493 @@ fn make_data_type<'a>(
             }
 
             if !t.attributes.additional.is_empty() {
+                let is_decimal = matches!(field.codec, Codec::Decimal(_, _, _));
                 for (k, v) in &t.attributes.additional {
-                    field.metadata.insert(k.to_string(), v.to_string());
+                    if !(is_decimal && DECIMAL_ATTRIBUTES.contains(k)) {
+                        field.metadata.insert(k.to_string(), v.to_string());
+                    }
                 }
             }
             Ok(field)
         }
     }
 }
+
+/// Returns the [`AvroDataType`] for the provided arrow [`Field`]
+fn arrow_data_type(field: &Field) -> Result<AvroDataType, ArrowError> {
+    let metadata = field.metadata();
+    let codec = match field.data_type() {
+        // The null type cannot be further wrapped in a nullable union
+        DataType::Null => {
+            return Ok(AvroDataType {
+                nulls: None,
+                metadata: metadata.clone(),
+                codec: Codec::Null,
+                named: None,
+                resolution: None,
+            })
+        }
+        DataType::Boolean => Codec::Boolean,
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::UInt8 | DataType::UInt16 => {
+            Codec::Int32
+        }
+        DataType::Int64 | DataType::UInt32 => Codec::Int64,
+        DataType::Float16 | DataType::Float32 => Codec::Float32,
+        DataType::Float64 => Codec::Float64,
+        DataType::Binary | DataType::LargeBinary | DataType::BinaryView => Codec::Binary,
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => Codec::Utf8,
+        DataType::Date32 | DataType::Date64 => Codec::Date32,
+        DataType::Time32(TimeUnit::Millisecond) => Codec::TimeMillis,
+        DataType::Time64(TimeUnit::Microsecond) => Codec::TimeMicros,
+        DataType::Timestamp(TimeUnit::Second | TimeUnit::Millisecond, tz) => {
+            Codec::TimestampMillis(tz.is_some())
+        }
+        DataType::Timestamp(TimeUnit::Microsecond | TimeUnit::Nanosecond, tz) => {
+            Codec::TimestampMicros(tz.is_some())
+        }
+        DataType::FixedSizeBinary(16)
+            if metadata
+                .get(EXTENSION_NAME_METADATA_KEY)
+                .map(|x| x.as_str())
+                == Some(UUID_EXTENSION_NAME) =>
+        {
+            Codec::Uuid
+        }
+        DataType::FixedSizeBinary(size) => Codec::Fixed(*size),
+        DataType::Decimal128(precision, scale) | DataType::Decimal256(precision, scale) => {
+            let scale = (*scale).try_into().map_err(|_| {
+                ArrowError::NotYetImplemented(format!(
+                    "Decimal with negative scale {scale} not supported"
+                ))
+            })?;
+            Codec::Decimal(*precision as usize, scale, None)
+        }
+        DataType::Interval(IntervalUnit::MonthDayNano) => Codec::Duration,
+        DataType::Dictionary(_, v) => match metadata.get(ENUM_SYMBOLS_METADATA_KEY) {
+            Some(symbols) if matches!(v.as_ref(), DataType::Utf8 | DataType::LargeUtf8) => {
+                let symbols: Vec<String> = serde_json::from_str(symbols).map_err(|e| {
+                    ArrowError::ParseError(format!("Invalid enum symbols {symbols}: {e}"))
+                })?;
+                Codec::Enum(symbols.into())
+            }
+            _ => {
+                let values = Field::new(field.name(), v.as_ref().clone(), field.is_nullable())
+                    .with_metadata(metadata.clone());
+                return arrow_data_type(&values);
+            }
+        },
+        DataType::List(f) | DataType::LargeList(f) | DataType::FixedSizeList(f, _) => {
+            Codec::List(Arc::new(arrow_data_type(f)?))
+        }
+        DataType::Map(entries, _) => match entries.data_type() {
+            DataType::Struct(f)
+                if f.len() == 2
+                    && matches!(
+                        f[0].data_type(),
+                        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+                    ) =>
+            {
+                Codec::Map(Arc::new(arrow_data_type(&f[1])?))
+            }
+            d => {
+                return Err(ArrowError::NotYetImplemented(format!(
+                    "Avro maps require string keys, got {d}"
+                )))
+            }
+        },
+        DataType::Struct(fields) => {
+            let fields = fields
+                .iter()
+                .map(|f| {
+                    Ok(AvroField {
+                        name: f.name().clone(),
+                        data_type: arrow_data_type(f)?,
+                        default: None,
+                        aliases: vec![],
+                    })
+                })
+                .collect::<Result<_, ArrowError>>()?;
+            Codec::Struct(fields)
+        }
+        d => {
+            return Err(ArrowError::NotYetImplemented(format!(
+                "Writing {d} to Avro not currently supported"
+            )))
+        }
+    };
+
+    Ok(AvroDataType {
+        nulls: field.is_nullable().then_some(Nulls::NullFirst),
+        metadata: metadata.clone(),
+        codec,
+        named: None,
+        resolution: None,
+    })
+}
+
+/// Returns the JSON encoded Avro schema for `data_type`
+///
+/// `name` and `namespace` are used to name any named types, i.e. records, enums and fixed
+fn avro_schema(data_type: &AvroDataType, name: &str, namespace: &str) -> serde_json::Value {
+    let named = |mut value: serde_json::Value| {
+        value["name"] = name.into();
+        if !namespace.is_empty() {
+            value["namespace"] = namespace.into();
+        }
+        value
+    };
+    let child_namespace = || match namespace.is_empty() {
+        true => name.to_string(),
+        false => format!("{namespace}.{name}"),
+    };
+
+    let schema = match &data_type.codec {
+        Codec::Null => json!("null"),
+        Codec::Boolean => json!("boolean"),
+        Codec::Int32 => json!("int"),
+        Codec::Int64 => json!("long"),
+        Codec::Float32 => json!("float"),
+        Codec::Float64 => json!("double"),
+        Codec::Binary => json!("bytes"),
+        Codec::Utf8 => json!("string"),
+        Codec::Date32 => json!({"type": "int", "logicalType": "date"}),
+        Codec::TimeMillis => json!({"type": "int", "logicalType": "time-millis"}),
+        Codec::TimeMicros => json!({"type": "long", "logicalType": "time-micros"}),
+        Codec::TimestampMillis(true) => json!({"type": "long", "logicalType": "timestamp-millis"}),
+        Codec::TimestampMillis(false) => {
+            json!({"type": "long", "logicalType": "local-timestamp-millis"})
+        }
+        Codec::TimestampMicros(true) => json!({"type": "long", "logicalType": "timestamp-micros"}),
+        Codec::TimestampMicros(false) => {
+            json!({"type": "long", "logicalType": "local-timestamp-micros"})
+        }
+        Codec::Fixed(size) => named(json!({"type": "fixed", "size": size})),
+        Codec::Decimal(precision, scale, None) => json!({
+            "type": "bytes",
+            "logicalType": "decimal",
+            "precision": precision,
+            "scale": scale
+        }),
+        Codec::Decimal(precision, scale, Some(size)) => named(json!({
+            "type": "fixed",
+            "size": size,
+            "logicalType": "decimal",
+            "precision": precision,
+            "scale": scale
+        })),
+        Codec::Uuid => json!({"type": "string", "logicalType": "uuid"}),
+        Codec::Enum(symbols) => named(json!({"type": "enum", "symbols": symbols.as_ref()})),
+        Codec::List(items) => json!({
+            "type": "array",
+            "items": avro_schema(items, "item", &child_namespace())
+        }),
+        Codec::Map(values) => json!({
+            "type": "map",
+            "values": avro_schema(values, "value", &child_namespace())
+        }),
+        Codec::Struct(fields) => {
+            let namespace = child_namespace();
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|f| json!({"name": f.name, "type": avro_schema(&f.data_type, &f.name, &namespace)}))
+                .collect();
+            named(json!({"type": "record", "fields": fields}))
+        }
+        Codec::Duration => named(json!({"type": "fixed", "size": 12, "logicalType": "duration"})),
+    };
+
+    match data_type.nulls {
+        Some(Nulls::NullFirst) => json!(["null", schema]),
+        Some(Nulls::NullSecond) => json!([schema, "null"]),
+        None => schema,
+    }
+}
+
+/// The Avro encoding of a [`Codec`], ignoring any logical type
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Encoding {
+    Null,
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Bytes,
+    Fixed(usize),
+    Complex,
+}
+
+impl Codec {
+    fn encoding(&self) -> Encoding {
+        match self {
+            Self::Null => Encoding::Null,
+            Self::Boolean => Encoding::Boolean,
+            Self::Int32 | Self::Date32 | Self::TimeMillis => Encoding::Int,
+            Self::Int64
+            | Self::TimeMicros
+            | Self::TimestampMillis(_)
+            | Self::TimestampMicros(_) => Encoding::Long,
+            Self::Float32 => Encoding::Float,
+            Self::Float64 => Encoding::Double,
+            Self::Binary | Self::Utf8 | Self::Uuid | Self::Decimal(_, _, None) => Encoding::Bytes,
+            Self::Fixed(size) => Encoding::Fixed(*size as usize),
+            Self::Decimal(_, _, Some(size)) => Encoding::Fixed(*size),
+            Self::Duration => Encoding::Fixed(12),
+            Self::Enum(_) | Self::List(_) | Self::Map(_) | Self::Struct(_) => Encoding::Complex,
+        }
+    }
+}
+
+/// Resolves the `writer` [`AvroDataType`] against the `reader` [`AvroDataType`]
+///
+/// Returns a copy of `reader` annotated with the [`Resolution`] necessary
+/// to decode data encoded by `writer`
+fn resolve_data_type(
+    writer: &AvroDataType,
+    reader: &AvroDataType,
+) -> Result<AvroDataType, ArrowError> {
+    let incompatible = || {
+        ArrowError::SchemaError(format!(
+            "Cannot resolve writer type {:?} to reader type {:?}",
+            writer.codec, reader.codec
+        ))
+    };
+
+    // Named types must match by name or alias
+    if let (Some(w), Some(r)) = (&writer.named, &reader.named) {
+        if !r.matches(w) {
+            return Err(ArrowError::SchemaError(format!(
+                "Cannot resolve writer type '{}' to reader type '{}'",
+                w.name, r.name
+            )));
+        }
+    }
+
+    let (codec, kind) = match (&writer.codec, &reader.codec) {
+        (Codec::List(w), Codec::List(r)) => (Codec::List(Arc::new(resolve_data_type(w, r)?)), None),
+        (Codec::Map(w), Codec::Map(r)) => (Codec::Map(Arc::new(resolve_data_type(w, r)?)), None),
+        (Codec::Enum(w), Codec::Enum(r)) => {
+            // Writer symbols absent from the reader take the reader's default, if any
+            let default = reader
+                .named
+                .as_ref()
+                .and_then(|n| n.default_symbol.as_ref())
+                .and_then(|d| r.iter().position(|x| x == d));
+            let mapping: Arc<[Option<i32>]> = w
+                .iter()
+                .map(|s| r.iter().position(|x| x == s).or(default).map(|x| x as i32))
+                .collect();
+            let identity = w.len() == r.len() && w.iter().zip(r.iter()).all(|(a, b)| a == b);
+            let kind = (!identity).then_some(ResolutionKind::EnumMapping(mapping));
+            (reader.codec.clone(), kind)
+        }
+        (Codec::Struct(w), Codec::Struct(r)) => {
+            let mut reader_fields: Vec<_> = r.iter().cloned().collect();
+            let mut matched = vec![false; r.len()];
+            let mut writer_fields = Vec::with_capacity(w.len());
+            for writer_field in w.iter() {
+                // Match by name, and then by the aliases of the reader fields
+                let idx = r
+                    .iter()
+                    .position(|x| x.name == writer_field.name)
+                    .or_else(|| {
+                        r.iter()
+                            .position(|x| x.aliases.contains(&writer_field.name))
+                    });
+                match idx {
+                    Some(idx) => {
+                        matched[idx] = true;
+                        reader_fields[idx].data_type =
+                            resolve_data_type(&writer_field.data_type, &r[idx].data_type)?;
+                        writer_fields.push(WriterField::Read(idx));
+                    }
+                    None => writer_fields.push(WriterField::Skip(writer_field.data_type.clone())),
+                }
+            }
+
+            let defaults = r
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| !matched[*idx])
+                .map(|(idx, field)| {
+                    let default = field.default.as_ref().ok_or_else(|| {
+                        ArrowError::SchemaError(format!(
+                            "Reader field '{}' not present in writer schema and has no default",
+                            field.name
+                        ))
+                    })?;
+                    let mut encoded = Vec::new();
+                    encode_default(default, &field.data_type, &mut encoded)?;
+                    Ok((idx, encoded))
+                })
+                .collect::<Result<Vec<_>, ArrowError>>()?;
+
+            let resolution = RecordResolution {
+                writer_fields,
+                defaults,
+            };
+            let codec = Codec::Struct(reader_fields.into());
+            (codec, Some(ResolutionKind::Record(Arc::new(resolution))))
+        }
+        (Codec::Decimal(wp, ws, wsize), Codec::Decimal(rp, rs, rsize)) => {
+            match wp == rp && ws == rs && wsize == rsize {
+                true => (reader.codec.clone(), None),
+                false => return Err(incompatible()),
+            }
+        }
+        (w, r) => {
+            let promotion = match (w.encoding(), r.encoding()) {
+                (Encoding::Complex, _) | (_, Encoding::Complex) => return Err(incompatible()),
+                (we, re) if we == re => {
+                    // Types sharing an encoding must also share a logical type, except
+                    // for strings and bytes which may be read as one another
+                    let compatible = std::mem::discriminant(w) == std::mem::discriminant(r)
+                        || matches!(
+                            (w, r),
+                            (Codec::Binary | Codec::Utf8, Codec::Binary | Codec::Utf8)
+                        );
+                    if !compatible {
+                        return Err(incompatible());
+                    }
+                    None
+                }
+                (Encoding::Int, Encoding::Long) => Some(Promotion::IntToLong),
+                (Encoding::Int, Encoding::Float) => Some(Promotion::IntToFloat),
+                (Encoding::Int, Encoding::Double) => Some(Promotion::IntToDouble),
+                (Encoding::Long, Encoding::Float) => Some(Promotion::LongToFloat),
+                (Encoding::Long, Encoding::Double) => Some(Promotion::LongToDouble),
+                (Encoding::Float, Encoding::Double) => Some(Promotion::FloatToDouble),
+                _ => return Err(incompatible()),
+            };
+
+            // Promotion is only supported to the plain primitive types
+            if promotion.is_some() && !matches!(r, Codec::Int64 | Codec::Float32 | Codec::Float64) {
+                return Err(incompatible());
+            }
+            (r.clone(), promotion.map(ResolutionKind::Promotion))
+        }
+    };
+
+    Ok(AvroDataType {
+        nulls: reader.nulls,
+        metadata: reader.metadata.clone(),
+        codec,
+        named: reader.named.clone(),
+        resolution: Some(Resolution {
+            writer_nulls: writer.nulls,
+            kind,
+        }),
+    })
+}
+
+/// Encodes the JSON `default` value of a field of type `data_type` using the Avro binary encoding
+///
+/// <https://avro.apache.org/docs/1.11.1/specification/#schema-record>
+fn encode_default(
+    default: &serde_json::Value,
+    data_type: &AvroDataType,
+    out: &mut Vec<u8>,
+) -> Result<(), ArrowError> {
+    use serde_json::Value;
+
+    let invalid = || {
+        ArrowError::SchemaError(format!(
+            "Invalid default value {default} for {:?}",
+            data_type.codec
+        ))
+    };
+
+    // The default of a union corresponds to its first variant, however, for
+    // compatibility with other implementations we accept either variant here
+    match (data_type.nulls, default.is_null()) {
+        (Some(Nulls::NullFirst), true) => {
+            encode_long(0, out);
+            return Ok(());
+        }
+        (Some(Nulls::NullSecond), true) => {
+            encode_long(1, out);
+            return Ok(());
+        }
+        (Some(Nulls::NullFirst), false) => encode_long(1, out),
+        (Some(Nulls::NullSecond), false) => encode_long(0, out),
+        (None, _) => {}
+    }
+
+    // Bytes and fixed defaults are encoded as strings with codepoints 0-255 mapping to bytes
+    let bytes = |value: &Value| -> Result<Vec<u8>, ArrowError> {
+        let s = value.as_str().ok_or_else(invalid)?;
+        s.chars()
+            .map(|c| u8::try_from(c as u32).map_err(|_| invalid()))
+            .collect()
+    };
+
+    match &data_type.codec {
+        Codec::Null if default.is_null() => {}
+        Codec::Null => return Err(invalid()),
+        Codec::Boolean => out.push(default.as_bool().ok_or_else(invalid)? as u8),
+        Codec::Int32 | Codec::Date32 | Codec::TimeMillis => {
+            let v = default.as_i64().ok_or_else(invalid)?;
+            let v = i32::try_from(v).map_err(|_| invalid())?;
+            encode_long(v as i64, out)
+        }
+        Codec::Int64
+        | Codec::TimeMicros
+        | Codec::TimestampMillis(_)
+        | Codec::TimestampMicros(_) => encode_long(default.as_i64().ok_or_else(invalid)?, out),
+        Codec::Float32 => {
+            let v = default.as_f64().ok_or_else(invalid)? as f32;
+            out.extend_from_slice(&v.to_le_bytes())
+        }
+        Codec::Float64 => {
+            let v = default.as_f64().ok_or_else(invalid)?;
+            out.extend_from_slice(&v.to_le_bytes())
+        }
+        Codec::Utf8 | Codec::Uuid => {
+            encode_bytes(default.as_str().ok_or_else(invalid)?.as_bytes(), out)
+        }
+        Codec::Binary | Codec::Decimal(_, _, None) => encode_bytes(&bytes(default)?, out),
+        Codec::Fixed(_) | Codec::Decimal(_, _, Some(_)) | Codec::Duration => {
+            let v = bytes(default)?;
+            match data_type.codec.encoding() {
+                Encoding::Fixed(size) if size == v.len() => out.extend_from_slice(&v),
+                _ => return Err(invalid()),
+            }
+        }
+        Codec::Enum(symbols) => {
+            let v = default.as_str().ok_or_else(invalid)?;
+            let idx = symbols.iter().position(|s| s == v).ok_or_else(invalid)?;
+            encode_long(idx as i64, out)
+        }
+        Codec::List(items) => {
+            let v = default.as_array().ok_or_else(invalid)?;
+            if !v.is_empty() {
+                encode_long(v.len() as i64, out);
+                for item in v {
+                    encode_default(item, items, out)?;
+                }
+            }
+            encode_long(0, out)
+        }
+        Codec::Map(values) => {
+            let v = default.as_object().ok_or_else(invalid)?;
+            if !v.is_empty() {
+                encode_long(v.len() as i64, out);
+                for (key, value) in v {
+                    encode_bytes(key.as_bytes(), out);
+                    encode_default(value, values, out)?;
+                }
+            }
+            encode_long(0, out)
+        }
+        Codec::Struct(fields) => {
+            let v = default.as_object().ok_or_else(invalid)?;
+            for field in fields.iter() {
+                match v.get(&field.name).or(field.default.as_ref()) {
+                    Some(value) => encode_default(value, &field.data_type, out)?,
+                    None => return Err(invalid()),
+                }
+            }
+        }
+    }
+    Ok(())
+}
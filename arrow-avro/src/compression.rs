@@ -15,22 +15,82 @@
 // specific language governing permissions and limitations
 // under the License.
 
+//! Block compression codecs for Avro Object Container Files
+//!
+//! <https://avro.apache.org/docs/1.11.1/specification/#required-codecs>
+
 use arrow_schema::ArrowError;
-use flate2::read;
+#[cfg(feature = "deflate")]
+use flate2::{read, write};
 use std::io;
-use std::io::Read;
+use std::io::{Read, Write};
 
 /// The metadata key used for storing the JSON encoded [`CompressionCodec`]
 pub const CODEC_METADATA_KEY: &str = "avro.codec";
 
+/// A compression codec applied to the blocks of an Avro Object Container File
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum CompressionCodec {
+    /// The deflate codec, as specified in RFC 1951
     Deflate,
+    /// The snappy codec, with each block followed by a CRC32 checksum
     Snappy,
+    /// The Zstandard codec
     ZStandard,
 }
 
 impl CompressionCodec {
+    /// Returns the name of this codec as stored in the [`CODEC_METADATA_KEY`] header
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            CompressionCodec::Deflate => "deflate",
+            CompressionCodec::Snappy => "snappy",
+            CompressionCodec::ZStandard => "zstandard",
+        }
+    }
+
+    pub(crate) fn compress(&self, block: &[u8]) -> Result<Vec<u8>, ArrowError> {
+        match self {
+            #[cfg(feature = "deflate")]
+            CompressionCodec::Deflate => {
+                let mut encoder =
+                    write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(block)?;
+                Ok(encoder.finish()?)
+            }
+            #[cfg(not(feature = "deflate"))]
+            CompressionCodec::Deflate => Err(ArrowError::ParseError(
+                "Deflate codec requires deflate feature".to_string(),
+            )),
+            #[cfg(feature = "snappy")]
+            CompressionCodec::Snappy => {
+                let mut encoder = snap::raw::Encoder::new();
+                let mut out = encoder
+                    .compress_vec(block)
+                    .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
+
+                let checksum = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC).checksum(block);
+                out.extend_from_slice(&checksum.to_be_bytes());
+                Ok(out)
+            }
+            #[cfg(not(feature = "snappy"))]
+            CompressionCodec::Snappy => Err(ArrowError::ParseError(
+                "Snappy codec requires snappy feature".to_string(),
+            )),
+
+            #[cfg(feature = "zstd")]
+            CompressionCodec::ZStandard => {
+                let mut encoder = zstd::Encoder::new(Vec::new(), 0)?;
+                encoder.write_all(block)?;
+                Ok(encoder.finish()?)
+            }
+            #[cfg(not(feature = "zstd"))]
+            CompressionCodec::ZStandard => Err(ArrowError::ParseError(
+                "ZStandard codec requires zstd feature".to_string(),
+            )),
+        }
+    }
+
     pub(crate) fn decompress(&self, block: &[u8]) -> Result<Vec<u8>, ArrowError> {
         match self {
             #[cfg(feature = "deflate")]
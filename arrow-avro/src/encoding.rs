@@ -0,0 +1,38 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Primitives of the Avro binary encoding, shared by the writer and schema resolution
+//!
+//! <https://avro.apache.org/docs/1.11.1/specification/#binary-encoding>
+
+/// Encode a zig-zag encoded variable length `long` to `out`
+#[inline]
+pub(crate) fn encode_long(v: i64, out: &mut Vec<u8>) {
+    let mut v = ((v << 1) ^ (v >> 63)) as u64;
+    while v >= 0x80 {
+        out.push(v as u8 | 0x80);
+        v >>= 7;
+    }
+    out.push(v as u8);
+}
+
+/// Encode length prefixed `bytes` to `out`
+#[inline]
+pub(crate) fn encode_bytes(v: &[u8], out: &mut Vec<u8>) {
+    encode_long(v.len() as i64, out);
+    out.extend_from_slice(v);
+}
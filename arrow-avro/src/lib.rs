@@ -24,12 +24,16 @@
 #![allow(unused)] // Temporary
 
 pub mod reader;
+pub mod writer;
+
 mod schema;
 
-mod compression;
+pub mod compression;
 
 mod codec;
 
+mod encoding;
+
 #[cfg(test)]
 mod test_util {
     pub fn arrow_test_data(path: &str) -> String {
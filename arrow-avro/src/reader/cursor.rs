@@ -0,0 +1,143 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow_schema::ArrowError;
+
+/// A wrapper around a byte slice, providing low-level decoding for Avro
+///
+/// <https://avro.apache.org/docs/1.11.1/specification/#encodings>
+#[derive(Debug)]
+pub(crate) struct AvroCursor<'a> {
+    buf: &'a [u8],
+    start_len: usize,
+}
+
+impl<'a> AvroCursor<'a> {
+    pub(crate) fn new(buf: &'a [u8]) -> Self {
+        Self {
+            buf,
+            start_len: buf.len(),
+        }
+    }
+
+    /// Returns the current cursor position
+    #[inline]
+    pub(crate) fn position(&self) -> usize {
+        self.start_len - self.buf.len()
+    }
+
+    /// Read a single `u8`
+    #[inline]
+    pub(crate) fn get_u8(&mut self) -> Result<u8, ArrowError> {
+        match self.buf.first().copied() {
+            Some(x) => {
+                self.buf = &self.buf[1..];
+                Ok(x)
+            }
+            None => Err(ArrowError::ParseError("Unexpected EOF".to_string())),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn get_bool(&mut self) -> Result<bool, ArrowError> {
+        Ok(self.get_u8()? != 0)
+    }
+
+    /// Read a variable length unsigned integer
+    pub(crate) fn read_vlq(&mut self) -> Result<u64, ArrowError> {
+        let mut in_progress = 0_u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.get_u8()?;
+            in_progress |= ((byte & 0x7F) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(in_progress);
+            }
+        }
+        Err(ArrowError::ParseError("Varint overflow".to_string()))
+    }
+
+    /// Read a zig-zag encoded `int`
+    #[inline]
+    pub(crate) fn get_int(&mut self) -> Result<i32, ArrowError> {
+        let varint = self.read_vlq()?;
+        let val: u32 = varint
+            .try_into()
+            .map_err(|_| ArrowError::ParseError("Varint overflow".to_string()))?;
+        Ok((val >> 1) as i32 ^ -((val & 1) as i32))
+    }
+
+    /// Read a zig-zag encoded `long`
+    #[inline]
+    pub(crate) fn get_long(&mut self) -> Result<i64, ArrowError> {
+        let val = self.read_vlq()?;
+        Ok((val >> 1) as i64 ^ -((val & 1) as i64))
+    }
+
+    /// Read a length prefixed `bytes` or `string`
+    pub(crate) fn get_bytes(&mut self) -> Result<&'a [u8], ArrowError> {
+        let len: usize = self.get_long()?.try_into().map_err(|_| {
+            ArrowError::ParseError("offset overflow reading avro bytes".to_string())
+        })?;
+        self.get_fixed(len)
+    }
+
+    /// Read exactly `n` bytes
+    pub(crate) fn get_fixed(&mut self, n: usize) -> Result<&'a [u8], ArrowError> {
+        if self.buf.len() < n {
+            return Err(ArrowError::ParseError(
+                "Unexpected EOF reading bytes".to_string(),
+            ));
+        }
+        let ret = &self.buf[..n];
+        self.buf = &self.buf[n..];
+        Ok(ret)
+    }
+
+    #[inline]
+    pub(crate) fn get_float(&mut self) -> Result<f32, ArrowError> {
+        let bytes = self.get_fixed(4)?;
+        Ok(f32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    #[inline]
+    pub(crate) fn get_double(&mut self) -> Result<f64, ArrowError> {
+        let bytes = self.get_fixed(8)?;
+        Ok(f64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor() {
+        let buf = [0x00, 0x01, 0x02, 0x03, 0xAC, 0x02, 0x04, b'a', b'b', 0x01];
+        let mut cursor = AvroCursor::new(&buf);
+        assert_eq!(cursor.get_long().unwrap(), 0);
+        assert_eq!(cursor.get_long().unwrap(), -1);
+        assert_eq!(cursor.get_int().unwrap(), 1);
+        assert_eq!(cursor.get_long().unwrap(), -2);
+        assert_eq!(cursor.get_long().unwrap(), 150);
+        assert_eq!(cursor.get_bytes().unwrap(), b"ab");
+        assert_eq!(cursor.position(), 9);
+        assert!(cursor.get_bool().unwrap());
+
+        let err = cursor.get_u8().unwrap_err().to_string();
+        assert_eq!(err, "Parser error: Unexpected EOF");
+    }
+}
@@ -16,16 +16,42 @@
 // under the License.
 
 //! Read Avro data to Arrow
+//!
+//! ```
+//! # use std::io::Cursor;
+//! # use arrow_avro::reader::ReaderBuilder;
+//! # fn read(data: &[u8]) -> Result<(), arrow_schema::ArrowError> {
+//! let reader = ReaderBuilder::new()
+//!     .with_batch_size(1024)
+//!     .build(Cursor::new(data))?;
+//!
+//! for batch in reader {
+//!     let batch = batch?;
+//!     println!("read {} rows", batch.num_rows());
+//! }
+//! # Ok(())
+//! # }
+//! ```
 
+use crate::codec::AvroField;
+use crate::compression::CompressionCodec;
 use crate::reader::block::{Block, BlockDecoder};
 use crate::reader::header::{Header, HeaderDecoder};
-use arrow_schema::ArrowError;
+use crate::reader::record::RecordDecoder;
+use crate::schema::Schema;
+use arrow_array::{RecordBatch, RecordBatchReader};
+use arrow_schema::{ArrowError, SchemaRef};
+use std::collections::HashMap;
 use std::io::BufRead;
 
+pub use crate::schema::{Fingerprint, SchemaStore};
+
 mod header;
 
 mod block;
 
+mod cursor;
+mod record;
 mod vlq;
 
 /// Read a [`Header`] from the provided [`BufRead`]
@@ -71,13 +97,395 @@ fn read_blocks<R: BufRead>(mut reader: R) -> impl Iterator<Item = Result<Block,
     std::iter::from_fn(move || try_next().transpose())
 }
 
+/// A builder for [`Reader`]
+#[derive(Debug)]
+pub struct ReaderBuilder {
+    batch_size: usize,
+    reader_schema: Option<String>,
+}
+
+impl Default for ReaderBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReaderBuilder {
+    /// Create a new [`ReaderBuilder`]
+    ///
+    /// The schema of the returned [`RecordBatch`] is derived from the Avro schema
+    /// embedded in the header of the Object Container File
+    pub fn new() -> Self {
+        Self {
+            batch_size: 1024,
+            reader_schema: None,
+        }
+    }
+
+    /// Sets the batch size in rows to read
+    pub fn with_batch_size(self, batch_size: usize) -> Self {
+        Self { batch_size, ..self }
+    }
+
+    /// Sets the JSON encoded Avro schema to read data as
+    ///
+    /// The schema embedded in the file header will be resolved against this schema, using
+    /// the [schema resolution] rules of the Avro specification, and the returned
+    /// [`RecordBatch`] will have the schema derived from it
+    ///
+    /// [schema resolution]: https://avro.apache.org/docs/1.11.1/specification/#schema-resolution
+    pub fn with_reader_schema(self, schema: impl Into<String>) -> Self {
+        Self {
+            reader_schema: Some(schema.into()),
+            ..self
+        }
+    }
+
+    /// Create a [`Reader`] with the provided [`BufRead`]
+    ///
+    /// This will eagerly read the header of the Object Container File
+    pub fn build<R: BufRead>(self, mut reader: R) -> Result<Reader<R>, ArrowError> {
+        let header = read_header(&mut reader)?;
+        let compression = header.compression()?;
+        let decoder = {
+            let schema = header.schema()?.ok_or_else(|| {
+                ArrowError::ParseError("No Avro schema present in file header".to_string())
+            })?;
+            make_record_decoder(&schema, self.reader_schema.as_deref())?
+        };
+
+        Ok(Reader {
+            reader,
+            header,
+            compression,
+            decoder,
+            block_decoder: BlockDecoder::default(),
+            block_data: Vec::new(),
+            block_offset: 0,
+            block_remaining: 0,
+            batch_size: self.batch_size,
+        })
+    }
+
+    /// Create a [`Decoder`] for messages with schemas contained in `schemas`
+    ///
+    /// If no reader schema was provided with [`Self::with_reader_schema`], the writer schema
+    /// of the first message decoded is used as the reader schema
+    pub fn build_decoder(self, schemas: SchemaStore) -> Result<Decoder, ArrowError> {
+        if let Some(reader_schema) = &self.reader_schema {
+            parse_schema(reader_schema)?;
+        }
+
+        Ok(Decoder {
+            schemas,
+            reader_schema: self.reader_schema,
+            decoders: HashMap::new(),
+            active: None,
+            batch_size: self.batch_size,
+        })
+    }
+}
+
+/// Parse a JSON encoded Avro [`Schema`]
+fn parse_schema(schema: &str) -> Result<Schema<'_>, ArrowError> {
+    serde_json::from_str(schema)
+        .map_err(|e| ArrowError::ParseError(format!("Invalid Avro schema: {e}")))
+}
+
+/// Create a [`RecordDecoder`] for data encoded with the `writer` schema, resolving
+/// it against the JSON encoded `reader` schema if provided
+fn make_record_decoder(
+    writer: &Schema<'_>,
+    reader: Option<&str>,
+) -> Result<RecordDecoder, ArrowError> {
+    let root = match reader {
+        Some(reader) => AvroField::resolve(writer, &parse_schema(reader)?)?,
+        None => AvroField::try_from(writer)?,
+    };
+    RecordDecoder::try_new(root.data_type())
+}
+
+/// The magic bytes prefixing a message using the [single object encoding]
+///
+/// [single object encoding]: https://avro.apache.org/docs/1.11.1/specification/#single-object-encoding
+const SINGLE_OBJECT_MAGIC: [u8; 2] = [0xC3, 0x01];
+
+/// The magic byte prefixing a message using the [Confluent wire format]
+///
+/// [Confluent wire format]: https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format
+const CONFLUENT_MAGIC: u8 = 0x00;
+
+/// Read the prefix of an encoded message, returning the [`Fingerprint`] of
+/// its schema and the length of the prefix in bytes
+fn read_prefix(buf: &[u8]) -> Result<(Fingerprint, usize), ArrowError> {
+    let eof = || ArrowError::ParseError("Unexpected EOF".to_string());
+    match buf {
+        [] | [0xC3] => Err(eof()),
+        [a, b, rest @ ..] if [*a, *b] == SINGLE_OBJECT_MAGIC => {
+            let fingerprint = rest.get(..8).ok_or_else(eof)?;
+            let fingerprint = u64::from_le_bytes(fingerprint.try_into().unwrap());
+            Ok((Fingerprint::Rabin(fingerprint), 10))
+        }
+        [CONFLUENT_MAGIC, rest @ ..] => {
+            let id = rest.get(..4).ok_or_else(eof)?;
+            let id = u32::from_be_bytes(id.try_into().unwrap());
+            Ok((Fingerprint::Id(id), 5))
+        }
+        _ => Err(ArrowError::ParseError(
+            "Expected single object encoding or Confluent wire format message".to_string(),
+        )),
+    }
+}
+
+/// A low-level interface for decoding individually encoded Avro messages
+///
+/// Each message is prefixed with the [`Fingerprint`] of the schema used to encode it, either
+/// using the [single object encoding] or the [Confluent wire format], which is used to look
+/// up its schema in the [`SchemaStore`] provided to [`ReaderBuilder::build_decoder`]
+///
+/// As Avro is not self-delimiting, each call to [`Self::decode`] must be provided whole
+/// messages, however, it may be provided any number of concatenated messages
+///
+/// ```
+/// # use arrow_array::RecordBatch;
+/// # use arrow_avro::reader::{ReaderBuilder, SchemaStore};
+/// # use arrow_schema::ArrowError;
+/// fn read_messages(
+///     messages: &[&[u8]],
+///     schemas: SchemaStore,
+/// ) -> Result<Vec<RecordBatch>, ArrowError> {
+///     let mut decoder = ReaderBuilder::new().build_decoder(schemas)?;
+///     let mut batches = vec![];
+///     for message in messages {
+///         let mut buf = *message;
+///         while !buf.is_empty() {
+///             let decoded = decoder.decode(buf)?;
+///             buf = &buf[decoded..];
+///             if !buf.is_empty() {
+///                 // Batch is full, or the next message has a different schema
+///                 batches.extend(decoder.flush()?);
+///             }
+///         }
+///     }
+///     batches.extend(decoder.flush()?);
+///     Ok(batches)
+/// }
+/// ```
+///
+/// [single object encoding]: https://avro.apache.org/docs/1.11.1/specification/#single-object-encoding
+/// [Confluent wire format]: https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format
+#[derive(Debug)]
+pub struct Decoder {
+    schemas: SchemaStore,
+    reader_schema: Option<String>,
+    /// A [`RecordDecoder`] for each writer schema encountered
+    decoders: HashMap<Fingerprint, RecordDecoder>,
+    /// The [`Fingerprint`] of the writer schema of the buffered rows
+    active: Option<Fingerprint>,
+    batch_size: usize,
+}
+
+impl Decoder {
+    /// Decode messages from `buf`, returning the number of bytes read
+    ///
+    /// This method returns once `batch_size` rows have been buffered, or the next
+    /// message has a different schema to the buffered rows. Subsequent calls will
+    /// not read any further data until [`Self::flush`] is called
+    ///
+    /// If a message fails to decode after preceding messages in `buf` were decoded,
+    /// this returns the number of bytes of those messages, with the error instead
+    /// returned by the next call provided the failing message
+    pub fn decode(&mut self, buf: &[u8]) -> Result<usize, ArrowError> {
+        let mut offset = 0;
+        while offset < buf.len() && self.num_rows() < self.batch_size {
+            match self.decode_message(&buf[offset..]) {
+                Ok(Some(read)) => offset += read,
+                Ok(None) => break,
+                Err(_) if offset != 0 => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(offset)
+    }
+
+    /// Decode a single message from `buf`, returning the number of bytes read
+    ///
+    /// Returns `Ok(None)` if the message has a different schema to the buffered rows
+    fn decode_message(&mut self, buf: &[u8]) -> Result<Option<usize>, ArrowError> {
+        let (fingerprint, prefix) = read_prefix(buf)?;
+        if self.active != Some(fingerprint) {
+            if self.num_rows() != 0 {
+                return Ok(None);
+            }
+            self.activate(fingerprint)?;
+        }
+        let decoder = self.decoders.get_mut(&fingerprint).unwrap();
+        let read = decoder.decode(&buf[prefix..], 1)?;
+        Ok(Some(prefix + read))
+    }
+
+    /// Returns the number of rows buffered
+    fn num_rows(&self) -> usize {
+        self.active
+            .and_then(|x| self.decoders.get(&x))
+            .map(|x| x.num_rows())
+            .unwrap_or_default()
+    }
+
+    /// Make the writer schema with the given [`Fingerprint`] the active schema
+    fn activate(&mut self, fingerprint: Fingerprint) -> Result<(), ArrowError> {
+        if !self.decoders.contains_key(&fingerprint) {
+            let writer = self.schemas.lookup(&fingerprint).ok_or_else(|| {
+                ArrowError::ParseError(format!("Unknown schema fingerprint {fingerprint:?}"))
+            })?;
+            let decoder =
+                make_record_decoder(&parse_schema(writer)?, self.reader_schema.as_deref())?;
+            if self.reader_schema.is_none() {
+                self.reader_schema = Some(writer.to_string());
+            }
+            self.decoders.insert(fingerprint, decoder);
+        }
+        self.active = Some(fingerprint);
+        Ok(())
+    }
+
+    /// Flushes the currently buffered data to a [`RecordBatch`]
+    ///
+    /// Returns `Ok(None)` if no buffered data
+    pub fn flush(&mut self) -> Result<Option<RecordBatch>, ArrowError> {
+        match self.active.and_then(|x| self.decoders.get_mut(&x)) {
+            Some(decoder) if decoder.num_rows() != 0 => decoder.flush().map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Reads Avro [Object Container Files] into arrow [`RecordBatch`]
+///
+/// If a record fails to decode, the error is returned and the remainder of its block
+/// is skipped, with reading continuing from the next block
+///
+/// [Object Container Files]: https://avro.apache.org/docs/1.11.1/specification/#object-container-files
+pub struct Reader<R> {
+    reader: R,
+    header: Header,
+    compression: Option<CompressionCodec>,
+    decoder: RecordDecoder,
+    block_decoder: BlockDecoder,
+    /// The decompressed data of the current block
+    block_data: Vec<u8>,
+    /// The offset of the next record in `block_data`
+    block_offset: usize,
+    /// The number of records remaining in the current block
+    block_remaining: usize,
+    batch_size: usize,
+}
+
+impl<R> std::fmt::Debug for Reader<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reader")
+            .field("header", &self.header)
+            .field("compression", &self.compression)
+            .field("decoder", &self.decoder)
+            .finish()
+    }
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Reads the next [`Block`] into `block_data`, returning `false` if EOF
+    fn read_block(&mut self) -> Result<bool, ArrowError> {
+        loop {
+            let buf = self.reader.fill_buf()?;
+            if buf.is_empty() {
+                break;
+            }
+            let read = buf.len();
+            let decoded = self.block_decoder.decode(buf)?;
+            self.reader.consume(decoded);
+            if decoded != read {
+                break;
+            }
+        }
+
+        let block = match self.block_decoder.flush() {
+            Some(block) => block,
+            None => return Ok(false),
+        };
+
+        if block.sync != self.header.sync() {
+            return Err(ArrowError::ParseError(
+                "Block sync marker does not match header".to_string(),
+            ));
+        }
+
+        self.block_data = match self.compression {
+            Some(c) => c.decompress(&block.data)?,
+            None => block.data,
+        };
+        self.block_offset = 0;
+        self.block_remaining = block.count;
+        Ok(true)
+    }
+
+    /// Reads the next [`RecordBatch`] returning `Ok(None)` if EOF
+    fn read(&mut self) -> Result<Option<RecordBatch>, ArrowError> {
+        while self.decoder.num_rows() < self.batch_size {
+            if self.block_remaining == 0 && !self.read_block()? {
+                break;
+            }
+            let to_read = self
+                .block_remaining
+                .min(self.batch_size - self.decoder.num_rows());
+
+            let data = &self.block_data[self.block_offset..];
+            match self.decoder.decode(data, to_read) {
+                Ok(read) => self.block_offset += read,
+                Err(e) => {
+                    // Records are not self-delimiting, and so the remainder of the block
+                    // cannot be decoded. The records decoded before the failing record
+                    // remain buffered, and are returned by the next call
+                    self.block_remaining = 0;
+                    return Err(e);
+                }
+            }
+            self.block_remaining -= to_read;
+        }
+
+        match self.decoder.num_rows() {
+            0 => Ok(None),
+            _ => self.decoder.flush().map(Some),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read().transpose()
+    }
+}
+
+impl<R: BufRead> RecordBatchReader for Reader<R> {
+    fn schema(&self) -> SchemaRef {
+        self.decoder.schema().clone()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::compression::CompressionCodec;
-    use crate::reader::{read_blocks, read_header};
+    use crate::encoding::{encode_bytes, encode_long};
+    use crate::reader::{read_blocks, read_header, Fingerprint, ReaderBuilder, SchemaStore};
     use crate::test_util::arrow_test_data;
+    use crate::writer::WriterBuilder;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Decimal128Type, Int64Type};
+    use arrow_array::*;
+    use arrow_schema::DataType;
     use std::fs::File;
-    use std::io::BufReader;
+    use std::io::{BufReader, Cursor};
+    use std::sync::Arc;
 
     #[test]
     fn test_mux() {
@@ -104,4 +512,315 @@ mod test {
             }
         }
     }
+
+    fn read_file(file: &str, batch_size: usize) -> Vec<RecordBatch> {
+        let file = File::open(file).unwrap();
+        let reader = ReaderBuilder::new()
+            .with_batch_size(batch_size)
+            .build(BufReader::new(file))
+            .unwrap();
+        reader.collect::<Result<Vec<_>, _>>().unwrap()
+    }
+
+    #[test]
+    fn test_alltypes() {
+        let files = [
+            "avro/alltypes_plain.avro",
+            "avro/alltypes_plain.snappy.avro",
+            "avro/alltypes_plain.zstandard.avro",
+        ];
+
+        let expected = RecordBatch::try_from_iter_with_nullable([
+            (
+                "id",
+                Arc::new(Int32Array::from(vec![4, 5, 6, 7, 2, 3, 0, 1])) as _,
+                true,
+            ),
+            (
+                "bool_col",
+                Arc::new(BooleanArray::from_iter((0..8).map(|x| Some(x % 2 == 0)))) as _,
+                true,
+            ),
+            (
+                "tinyint_col",
+                Arc::new(Int32Array::from_iter_values((0..8).map(|x| x % 2))) as _,
+                true,
+            ),
+            (
+                "smallint_col",
+                Arc::new(Int32Array::from_iter_values((0..8).map(|x| x % 2))) as _,
+                true,
+            ),
+            (
+                "int_col",
+                Arc::new(Int32Array::from_iter_values((0..8).map(|x| x % 2))) as _,
+                true,
+            ),
+            (
+                "bigint_col",
+                Arc::new(Int64Array::from_iter_values((0..8).map(|x| (x % 2) * 10))) as _,
+                true,
+            ),
+            (
+                "float_col",
+                Arc::new(Float32Array::from_iter_values(
+                    (0..8).map(|x| (x % 2) as f32 * 1.1),
+                )) as _,
+                true,
+            ),
+            (
+                "double_col",
+                Arc::new(Float64Array::from_iter_values(
+                    (0..8).map(|x| (x % 2) as f64 * 10.1),
+                )) as _,
+                true,
+            ),
+            (
+                "date_string_col",
+                Arc::new(BinaryArray::from_iter_values([
+                    "03/01/09", "03/01/09", "04/01/09", "04/01/09", "02/01/09", "02/01/09",
+                    "01/01/09", "01/01/09",
+                ])) as _,
+                true,
+            ),
+            (
+                "string_col",
+                Arc::new(BinaryArray::from_iter_values((0..8).map(|x| [48 + x % 2]))) as _,
+                true,
+            ),
+            (
+                "timestamp_col",
+                Arc::new(
+                    TimestampMicrosecondArray::from_iter_values([
+                        1235865600000000, // 2009-03-01T00:00:00.000
+                        1235865660000000, // 2009-03-01T00:01:00.000
+                        1238544000000000, // 2009-04-01T00:00:00.000
+                        1238544060000000, // 2009-04-01T00:01:00.000
+                        1233446400000000, // 2009-02-01T00:00:00.000
+                        1233446460000000, // 2009-02-01T00:01:00.000
+                        1230768000000000, // 2009-01-01T00:00:00.000
+                        1230768060000000, // 2009-01-01T00:01:00.000
+                    ])
+                    .with_timezone("+00:00"),
+                ) as _,
+                true,
+            ),
+        ])
+        .unwrap();
+
+        for file in files {
+            let file = arrow_test_data(file);
+            assert_eq!(read_file(&file, 8), vec![expected.clone()]);
+
+            let batches = read_file(&file, 3);
+            let rows: Vec<_> = batches.iter().map(|b| b.num_rows()).collect();
+            assert_eq!(rows, &[3, 3, 2]);
+            assert_eq!(batches[0], expected.slice(0, 3));
+            assert_eq!(batches[1], expected.slice(3, 3));
+            assert_eq!(batches[2], expected.slice(6, 2));
+        }
+    }
+
+    #[test]
+    fn test_fixed_length_decimal() {
+        let file = arrow_test_data("avro/fixed_length_decimal.avro");
+        let batches = read_file(&file, 7);
+        assert_eq!(batches.len(), 4);
+
+        let mut values = vec![];
+        for batch in &batches {
+            assert_eq!(
+                batch.schema().field(0).data_type(),
+                &DataType::Decimal128(25, 2)
+            );
+            values.extend(batch.column(0).as_primitive::<Decimal128Type>().iter());
+        }
+        let expected: Vec<_> = (1..=24).map(|x| Some(x * 100)).collect();
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn test_decoder() {
+        let mut schemas = SchemaStore::new();
+        let v1 = r#"{"type":"record","name":"test","fields":[{"name":"a","type":"long"}]}"#;
+        let fingerprint = match schemas.register(v1).unwrap() {
+            Fingerprint::Rabin(x) => x,
+            _ => unreachable!(),
+        };
+        let v2 = r#"{
+            "type":"record",
+            "name":"test",
+            "fields":[{"name":"b","type":"string"},{"name":"a","type":"int"}]
+        }"#;
+        schemas.register_id(7, v2).unwrap();
+
+        let mut buf = vec![];
+        for a in [1, 2, 3] {
+            buf.extend_from_slice(&[0xC3, 0x01]);
+            buf.extend_from_slice(&fingerprint.to_le_bytes());
+            encode_long(a, &mut buf);
+        }
+        let v1_len = buf.len();
+        buf.extend_from_slice(&[0x00, 0x00, 0x00, 0x00, 0x07]);
+        encode_bytes(b"skipped", &mut buf);
+        encode_long(4, &mut buf);
+
+        let mut decoder = ReaderBuilder::new()
+            .with_batch_size(2)
+            .build_decoder(schemas)
+            .unwrap();
+
+        let mut batches = vec![];
+        let mut offset = 0;
+        while offset != buf.len() {
+            offset += decoder.decode(&buf[offset..]).unwrap();
+            batches.extend(decoder.flush().unwrap());
+        }
+        assert!(decoder.flush().unwrap().is_none());
+
+        let values: Vec<_> = batches
+            .iter()
+            .map(|b| b.column(0).as_primitive::<Int64Type>().values().to_vec())
+            .collect();
+        assert_eq!(values, vec![vec![1, 2], vec![3], vec![4]]);
+
+        let err = decoder.decode(&[0xC3, 0x01, 0, 0]).unwrap_err();
+        assert_eq!(err.to_string(), "Parser error: Unexpected EOF");
+
+        let err = decoder.decode(&[0x00, 0x00, 0x00, 0x00, 0x08]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parser error: Unknown schema fingerprint Id(8)"
+        );
+
+        let err = decoder.decode(&buf[v1_len - 1..]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parser error: Expected single object encoding or Confluent wire format message"
+        );
+    }
+
+    #[test]
+    fn test_decode_error() {
+        let values = ["r1", "r2", "r3", "r4", "r5", "r6"];
+        let batch =
+            RecordBatch::try_from_iter([("a", Arc::new(StringArray::from(values.to_vec())) as _)])
+                .unwrap();
+
+        let mut writer = WriterBuilder::new()
+            .with_rows_per_block(3)
+            .build(Vec::new(), batch.schema().as_ref())
+            .unwrap();
+        writer.write(&batch).unwrap();
+        let mut buf = writer.into_inner().unwrap();
+
+        // Corrupt the length of "r2" so that it reads past the end of the first block
+        let idx = buf
+            .windows(3)
+            .position(|w| w == [0x04, b'r', b'2'])
+            .unwrap();
+        buf[idx] = 0x7E;
+
+        let mut reader = ReaderBuilder::new()
+            .with_batch_size(10)
+            .build(Cursor::new(buf))
+            .unwrap();
+
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parser error: Unexpected EOF reading bytes"
+        );
+
+        let batch = reader.next().unwrap().unwrap();
+        let values: Vec<_> = batch
+            .column(0)
+            .as_string::<i32>()
+            .iter()
+            .flatten()
+            .collect();
+        assert_eq!(values, ["r1", "r4", "r5", "r6"]);
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_decoder_error() {
+        let mut schemas = SchemaStore::new();
+        let schema = r#"{"type":"record","name":"test","fields":[{"name":"a","type":"string"}]}"#;
+        schemas.register_id(1, schema).unwrap();
+
+        let mut buf = vec![];
+        for a in ["x", "y", "z"] {
+            buf.extend_from_slice(&[0x00, 0x00, 0x00, 0x00, 0x01]);
+            encode_bytes(a.as_bytes(), &mut buf);
+        }
+        // Corrupt the length of the second message so that it reads past the end
+        let bad = 5 + 2;
+        buf[bad + 5] = 0x7E;
+
+        let mut decoder = ReaderBuilder::new().build_decoder(schemas).unwrap();
+        assert_eq!(decoder.decode(&buf).unwrap(), bad);
+        let batch = decoder.flush().unwrap().unwrap();
+        let values: Vec<_> = batch
+            .column(0)
+            .as_string::<i32>()
+            .iter()
+            .flatten()
+            .collect();
+        assert_eq!(values, ["x"]);
+
+        // The error is returned once provided the failing message
+        let err = decoder.decode(&buf[bad..]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parser error: Unexpected EOF reading bytes"
+        );
+        assert!(decoder.flush().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_reader_schema() {
+        let batch = RecordBatch::try_from_iter([
+            ("a", Arc::new(Int32Array::from(vec![1, 2, 3])) as _),
+            ("b", Arc::new(StringArray::from(vec!["x", "y", "z"])) as _),
+        ])
+        .unwrap();
+
+        let mut writer = WriterBuilder::new()
+            .build(Vec::new(), batch.schema().as_ref())
+            .unwrap();
+        writer.write(&batch).unwrap();
+        let buf = writer.into_inner().unwrap();
+
+        let reader_schema = r#"{
+            "type":"record",
+            "name":"topLevelRecord",
+            "fields":[
+                {"name":"c","type":"string","default":"none"},
+                {"name":"a","type":"double"}
+            ]
+        }"#;
+        let batches = ReaderBuilder::new()
+            .with_reader_schema(reader_schema)
+            .build(Cursor::new(buf))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(batches.len(), 1);
+
+        let expected = RecordBatch::try_from_iter_with_nullable([
+            (
+                "c",
+                Arc::new(StringArray::from(vec!["none"; 3])) as _,
+                false,
+            ),
+            (
+                "a",
+                Arc::new(Float64Array::from(vec![1., 2., 3.])) as _,
+                false,
+            ),
+        ])
+        .unwrap();
+        assert_eq!(batches[0], expected);
+    }
 }
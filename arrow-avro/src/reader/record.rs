@@ -0,0 +1,1384 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Decoding of Avro records into Arrow arrays
+
+use crate::codec::{AvroDataType, Codec, Nulls, Promotion, ResolutionKind, WriterField};
+use crate::reader::cursor::AvroCursor;
+use arrow_array::types::{
+    ArrowPrimitiveType, Date32Type, Decimal128Type, Decimal256Type, Float32Type, Float64Type,
+    Int32Type, Int64Type, IntervalMonthDayNanoType, Time32MillisecondType, Time64MicrosecondType,
+    TimestampMicrosecondType, TimestampMillisecondType,
+};
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, DictionaryArray, FixedSizeBinaryArray, ListArray,
+    MapArray, NullArray, PrimitiveArray, RecordBatch, RecordBatchOptions, StringArray, StructArray,
+};
+use arrow_buffer::{
+    i256, BooleanBufferBuilder, IntervalMonthDayNano, NullBuffer, NullBufferBuilder, OffsetBuffer,
+    OffsetBufferBuilder,
+};
+use arrow_schema::{
+    ArrowError, DataType, FieldRef, Fields, Schema as ArrowSchema, SchemaRef,
+    DECIMAL128_MAX_PRECISION,
+};
+use std::sync::Arc;
+
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// Decodes avro encoded data into [`RecordBatch`]
+#[derive(Debug)]
+pub struct RecordDecoder {
+    schema: SchemaRef,
+    fields: Vec<Decoder>,
+    projector: Option<Projector>,
+    num_rows: usize,
+}
+
+impl RecordDecoder {
+    /// Create a new [`RecordDecoder`] from the provided [`AvroDataType`]
+    ///
+    /// Returns an error if `data_type` is not a record
+    pub fn try_new(data_type: &AvroDataType) -> Result<Self, ArrowError> {
+        match Decoder::try_new(data_type)? {
+            Decoder::Record(fields, encodings, projector) => Ok(Self {
+                schema: Arc::new(ArrowSchema::new(fields)),
+                fields: encodings,
+                projector,
+                num_rows: 0,
+            }),
+            encoding => Err(ArrowError::ParseError(format!(
+                "Expected record got {encoding:?}"
+            ))),
+        }
+    }
+
+    /// Returns the [`SchemaRef`] of the [`RecordBatch`] produced by this decoder
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// Decode `count` records from `buf`, returning the number of bytes read
+    ///
+    /// On error, the record that failed to decode is discarded, with the records
+    /// decoded before it retained until the next call to [`Self::flush`]
+    pub fn decode(&mut self, buf: &[u8], count: usize) -> Result<usize, ArrowError> {
+        let mut cursor = AvroCursor::new(buf);
+        for _ in 0..count {
+            if let Err(e) = decode_record(&mut self.fields, self.projector.as_ref(), &mut cursor) {
+                // A partially decoded record leaves the fields with differing lengths
+                for field in &mut self.fields {
+                    field.truncate(self.num_rows);
+                }
+                return Err(e);
+            }
+            self.num_rows += 1;
+        }
+        Ok(cursor.position())
+    }
+
+    /// Returns the number of records decoded but not yet flushed
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Flush the decoded records into a [`RecordBatch`]
+    pub fn flush(&mut self) -> Result<RecordBatch, ArrowError> {
+        let arrays = self
+            .fields
+            .iter_mut()
+            .map(|x| x.flush(None))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let options = RecordBatchOptions::new().with_row_count(Some(self.num_rows));
+        self.num_rows = 0;
+        RecordBatch::try_new_with_options(self.schema.clone(), arrays, &options)
+    }
+}
+
+/// A decoder for a single avro type, accumulating the decoded values
+#[derive(Debug)]
+enum Decoder {
+    Null(usize),
+    Boolean(BooleanBufferBuilder),
+    Int32(Vec<i32>),
+    Int64(Vec<i64>),
+    Float32(Vec<f32>),
+    Float64(Vec<f64>),
+    Int32ToInt64(Vec<i64>),
+    Int32ToFloat32(Vec<f32>),
+    Int32ToFloat64(Vec<f64>),
+    Int64ToFloat32(Vec<f32>),
+    Int64ToFloat64(Vec<f64>),
+    Float32ToFloat64(Vec<f64>),
+    Date32(Vec<i32>),
+    TimeMillis(Vec<i32>),
+    TimeMicros(Vec<i64>),
+    TimestampMillis(bool, Vec<i64>),
+    TimestampMicros(bool, Vec<i64>),
+    Binary(OffsetBufferBuilder<i32>, Vec<u8>),
+    String(OffsetBufferBuilder<i32>, Vec<u8>),
+    Fixed(i32, Vec<u8>),
+    /// A uuid string, decoded into its 16 byte binary representation
+    Uuid(Vec<u8>),
+    /// Decimal128(precision, scale, fixed_size, values)
+    Decimal128(u8, i8, Option<usize>, Vec<i128>),
+    /// Decimal256(precision, scale, fixed_size, values)
+    Decimal256(u8, i8, Option<usize>, Vec<i256>),
+    Duration(Vec<IntervalMonthDayNano>),
+    /// Enum(symbols, mapping, values)
+    ///
+    /// If the writer symbols differ from `symbols`, `mapping` maps writer indices to reader indices
+    Enum(Arc<[String]>, Option<Arc<[Option<i32>]>>, Vec<i32>),
+    List(FieldRef, OffsetBufferBuilder<i32>, Box<Decoder>),
+    Map(
+        FieldRef,
+        OffsetBufferBuilder<i32>,
+        OffsetBufferBuilder<i32>,
+        Vec<u8>,
+        Box<Decoder>,
+    ),
+    Record(Fields, Vec<Decoder>, Option<Projector>),
+    /// A nullable reader type, with the null encoding of the writer, if any
+    Nullable(Option<Nulls>, NullBufferBuilder, Box<Decoder>),
+    /// A non-nullable reader type, written as a nullable type
+    NonNullable(Nulls, Box<Decoder>),
+}
+
+/// Reads a writer record into the fields of a reader record
+#[derive(Debug)]
+struct Projector {
+    /// How to handle each writer field, in writer order
+    writer_fields: Vec<ProjectedField>,
+    /// The index of each reader field not present in the writer schema,
+    /// along with the Avro encoding of its default value
+    defaults: Vec<(usize, Vec<u8>)>,
+}
+
+impl Projector {
+    fn try_new(
+        writer_fields: &[WriterField],
+        defaults: &[(usize, Vec<u8>)],
+    ) -> Result<Self, ArrowError> {
+        let writer_fields = writer_fields
+            .iter()
+            .map(|x| match x {
+                WriterField::Read(idx) => Ok(ProjectedField::Read(*idx)),
+                WriterField::Skip(data_type) => {
+                    Skipper::try_new(data_type).map(ProjectedField::Skip)
+                }
+            })
+            .collect::<Result<_, ArrowError>>()?;
+
+        Ok(Self {
+            writer_fields,
+            defaults: defaults.to_vec(),
+        })
+    }
+}
+
+/// A field of a writer record
+#[derive(Debug)]
+enum ProjectedField {
+    /// Decode into the reader field with the given index
+    Read(usize),
+    /// Skip as it is not present in the reader schema
+    Skip(Skipper),
+}
+
+/// Skips over a value of a writer type not present in the reader schema
+#[derive(Debug)]
+enum Skipper {
+    Null,
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Bytes,
+    Fixed(usize),
+    List(Box<Skipper>),
+    Map(Box<Skipper>),
+    Record(Vec<Skipper>),
+    Nullable(Nulls, Box<Skipper>),
+}
+
+impl Skipper {
+    fn try_new(data_type: &AvroDataType) -> Result<Self, ArrowError> {
+        let skipper = match data_type.codec() {
+            Codec::Null => Self::Null,
+            Codec::Boolean => Self::Boolean,
+            Codec::Int32 | Codec::Date32 | Codec::TimeMillis | Codec::Enum(_) => Self::Int,
+            Codec::Int64
+            | Codec::TimeMicros
+            | Codec::TimestampMillis(_)
+            | Codec::TimestampMicros(_) => Self::Long,
+            Codec::Float32 => Self::Float,
+            Codec::Float64 => Self::Double,
+            Codec::Binary | Codec::Utf8 | Codec::Uuid | Codec::Decimal(_, _, None) => Self::Bytes,
+            Codec::Fixed(size) => Self::Fixed(*size as usize),
+            Codec::Decimal(_, _, Some(size)) => Self::Fixed(*size),
+            Codec::Duration => Self::Fixed(12),
+            Codec::List(item) => Self::List(Box::new(Self::try_new(item)?)),
+            Codec::Map(values) => Self::Map(Box::new(Self::try_new(values)?)),
+            Codec::Struct(fields) => Self::Record(
+                fields
+                    .iter()
+                    .map(|x| Self::try_new(x.data_type()))
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+
+        Ok(match data_type.nulls() {
+            Some(nulls) => Self::Nullable(nulls, Box::new(skipper)),
+            None => skipper,
+        })
+    }
+
+    /// Skip over a single value in `buf`
+    fn skip(&self, buf: &mut AvroCursor<'_>) -> Result<(), ArrowError> {
+        match self {
+            Self::Null => {}
+            Self::Boolean => {
+                buf.get_bool()?;
+            }
+            Self::Int => {
+                buf.get_int()?;
+            }
+            Self::Long => {
+                buf.get_long()?;
+            }
+            Self::Float => {
+                buf.get_float()?;
+            }
+            Self::Double => {
+                buf.get_double()?;
+            }
+            Self::Bytes => {
+                buf.get_bytes()?;
+            }
+            Self::Fixed(size) => {
+                buf.get_fixed(*size)?;
+            }
+            Self::List(item) => {
+                read_blocks(buf, |buf| item.skip(buf))?;
+            }
+            Self::Map(values) => {
+                read_blocks(buf, |buf| {
+                    buf.get_bytes()?;
+                    values.skip(buf)
+                })?;
+            }
+            Self::Record(fields) => {
+                for field in fields {
+                    field.skip(buf)?;
+                }
+            }
+            Self::Nullable(nulls, skipper) => {
+                if read_union_is_valid(buf, *nulls)? {
+                    skipper.skip(buf)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Decoder {
+    fn try_new(data_type: &AvroDataType) -> Result<Self, ArrowError> {
+        let decoder = match (data_type.codec(), data_type.resolution()) {
+            (_, Some(ResolutionKind::Promotion(promotion))) => match promotion {
+                Promotion::IntToLong => Self::Int32ToInt64(Vec::with_capacity(DEFAULT_CAPACITY)),
+                Promotion::IntToFloat => Self::Int32ToFloat32(Vec::with_capacity(DEFAULT_CAPACITY)),
+                Promotion::IntToDouble => {
+                    Self::Int32ToFloat64(Vec::with_capacity(DEFAULT_CAPACITY))
+                }
+                Promotion::LongToFloat => {
+                    Self::Int64ToFloat32(Vec::with_capacity(DEFAULT_CAPACITY))
+                }
+                Promotion::LongToDouble => {
+                    Self::Int64ToFloat64(Vec::with_capacity(DEFAULT_CAPACITY))
+                }
+                Promotion::FloatToDouble => {
+                    Self::Float32ToFloat64(Vec::with_capacity(DEFAULT_CAPACITY))
+                }
+            },
+            (codec, _) => Self::from_codec(data_type, codec)?,
+        };
+
+        Ok(match (data_type.nulls(), data_type.writer_nulls()) {
+            (Some(_), writer_nulls) => Self::Nullable(
+                writer_nulls,
+                NullBufferBuilder::new(DEFAULT_CAPACITY),
+                Box::new(decoder),
+            ),
+            (None, Some(writer_nulls)) => Self::NonNullable(writer_nulls, Box::new(decoder)),
+            (None, None) => decoder,
+        })
+    }
+
+    fn from_codec(data_type: &AvroDataType, codec: &Codec) -> Result<Self, ArrowError> {
+        Ok(match codec {
+            Codec::Null => Self::Null(0),
+            Codec::Boolean => Self::Boolean(BooleanBufferBuilder::new(DEFAULT_CAPACITY)),
+            Codec::Int32 => Self::Int32(Vec::with_capacity(DEFAULT_CAPACITY)),
+            Codec::Int64 => Self::Int64(Vec::with_capacity(DEFAULT_CAPACITY)),
+            Codec::Float32 => Self::Float32(Vec::with_capacity(DEFAULT_CAPACITY)),
+            Codec::Float64 => Self::Float64(Vec::with_capacity(DEFAULT_CAPACITY)),
+            Codec::Binary => Self::Binary(
+                OffsetBufferBuilder::new(DEFAULT_CAPACITY),
+                Vec::with_capacity(DEFAULT_CAPACITY),
+            ),
+            Codec::Utf8 => Self::String(
+                OffsetBufferBuilder::new(DEFAULT_CAPACITY),
+                Vec::with_capacity(DEFAULT_CAPACITY),
+            ),
+            Codec::Date32 => Self::Date32(Vec::with_capacity(DEFAULT_CAPACITY)),
+            Codec::TimeMillis => Self::TimeMillis(Vec::with_capacity(DEFAULT_CAPACITY)),
+            Codec::TimeMicros => Self::TimeMicros(Vec::with_capacity(DEFAULT_CAPACITY)),
+            Codec::TimestampMillis(is_utc) => {
+                Self::TimestampMillis(*is_utc, Vec::with_capacity(DEFAULT_CAPACITY))
+            }
+            Codec::TimestampMicros(is_utc) => {
+                Self::TimestampMicros(*is_utc, Vec::with_capacity(DEFAULT_CAPACITY))
+            }
+            Codec::Fixed(size) => Self::Fixed(*size, Vec::with_capacity(DEFAULT_CAPACITY)),
+            Codec::Decimal(precision, scale, size) => match size {
+                Some(s) if *s > 32 => {
+                    return Err(ArrowError::ParseError(format!(
+                        "Decimal fixed size of {s} bytes exceeds maximum of 32"
+                    )))
+                }
+                _ if *precision <= DECIMAL128_MAX_PRECISION as usize => Self::Decimal128(
+                    *precision as _,
+                    *scale as _,
+                    *size,
+                    Vec::with_capacity(DEFAULT_CAPACITY),
+                ),
+                _ => Self::Decimal256(
+                    *precision as _,
+                    *scale as _,
+                    *size,
+                    Vec::with_capacity(DEFAULT_CAPACITY),
+                ),
+            },
+            Codec::Uuid => Self::Uuid(Vec::with_capacity(DEFAULT_CAPACITY)),
+            Codec::Duration => Self::Duration(Vec::with_capacity(DEFAULT_CAPACITY)),
+            Codec::Enum(symbols) => {
+                let mapping = match data_type.resolution() {
+                    Some(ResolutionKind::EnumMapping(mapping)) => Some(mapping.clone()),
+                    _ => None,
+                };
+                Self::Enum(
+                    symbols.clone(),
+                    mapping,
+                    Vec::with_capacity(DEFAULT_CAPACITY),
+                )
+            }
+            Codec::List(item) => {
+                let decoder = Self::try_new(item)?;
+                Self::List(
+                    Arc::new(item.field_with_name("item")),
+                    OffsetBufferBuilder::new(DEFAULT_CAPACITY),
+                    Box::new(decoder),
+                )
+            }
+            Codec::Map(values) => {
+                let field = match data_type.field_with_name("map").data_type() {
+                    DataType::Map(f, _) => f.clone(),
+                    _ => unreachable!(),
+                };
+                Self::Map(
+                    field,
+                    OffsetBufferBuilder::new(DEFAULT_CAPACITY),
+                    OffsetBufferBuilder::new(DEFAULT_CAPACITY),
+                    Vec::with_capacity(DEFAULT_CAPACITY),
+                    Box::new(Self::try_new(values)?),
+                )
+            }
+            Codec::Struct(fields) => {
+                let mut arrow_fields = Vec::with_capacity(fields.len());
+                let mut encodings = Vec::with_capacity(fields.len());
+                for avro_field in fields.iter() {
+                    let encoding = Self::try_new(avro_field.data_type())?;
+                    arrow_fields.push(avro_field.field());
+                    encodings.push(encoding);
+                }
+                let projector = match data_type.resolution() {
+                    Some(ResolutionKind::Record(r)) => {
+                        Some(Projector::try_new(&r.writer_fields, &r.defaults)?)
+                    }
+                    _ => None,
+                };
+                Self::Record(arrow_fields.into(), encodings, projector)
+            }
+        })
+    }
+
+    /// Append a null record
+    fn append_null(&mut self) {
+        match self {
+            Self::Null(count) => *count += 1,
+            Self::Boolean(b) => b.append(false),
+            Self::Int32(v) | Self::Date32(v) | Self::TimeMillis(v) | Self::Enum(_, _, v) => {
+                v.push(0)
+            }
+            Self::Int64(v)
+            | Self::Int32ToInt64(v)
+            | Self::TimeMicros(v)
+            | Self::TimestampMillis(_, v)
+            | Self::TimestampMicros(_, v) => v.push(0),
+            Self::Float32(v) | Self::Int32ToFloat32(v) | Self::Int64ToFloat32(v) => v.push(0.),
+            Self::Float64(v)
+            | Self::Int32ToFloat64(v)
+            | Self::Int64ToFloat64(v)
+            | Self::Float32ToFloat64(v) => v.push(0.),
+            Self::Binary(offsets, _) | Self::String(offsets, _) => offsets.push_length(0),
+            Self::Fixed(size, v) => v.resize(v.len() + *size as usize, 0),
+            Self::Uuid(v) => v.resize(v.len() + 16, 0),
+            Self::Decimal128(_, _, _, v) => v.push(0),
+            Self::Decimal256(_, _, _, v) => v.push(i256::ZERO),
+            Self::Duration(v) => v.push(IntervalMonthDayNano::ZERO),
+            Self::List(_, offsets, _) => offsets.push_length(0),
+            Self::Map(_, offsets, _, _, _) => offsets.push_length(0),
+            Self::Record(_, encodings, _) => encodings.iter_mut().for_each(|x| x.append_null()),
+            Self::Nullable(_, nulls, e) => {
+                nulls.append_null();
+                e.append_null()
+            }
+            Self::NonNullable(_, e) => e.append_null(),
+        }
+    }
+
+    /// Truncate to the first `len` records, discarding any partially decoded record
+    fn truncate(&mut self, len: usize) {
+        match self {
+            Self::Null(count) => *count = len,
+            Self::Boolean(b) => b.truncate(len),
+            Self::Int32(v) | Self::Date32(v) | Self::TimeMillis(v) | Self::Enum(_, _, v) => {
+                v.truncate(len)
+            }
+            Self::Int64(v)
+            | Self::Int32ToInt64(v)
+            | Self::TimeMicros(v)
+            | Self::TimestampMillis(_, v)
+            | Self::TimestampMicros(_, v) => v.truncate(len),
+            Self::Float32(v) | Self::Int32ToFloat32(v) | Self::Int64ToFloat32(v) => v.truncate(len),
+            Self::Float64(v)
+            | Self::Int32ToFloat64(v)
+            | Self::Int64ToFloat64(v)
+            | Self::Float32ToFloat64(v) => v.truncate(len),
+            Self::Binary(offsets, values) | Self::String(offsets, values) => {
+                let end = truncate_offsets(offsets, len);
+                values.truncate(end);
+            }
+            Self::Fixed(size, v) => v.truncate(len * *size as usize),
+            Self::Uuid(v) => v.truncate(len * 16),
+            Self::Decimal128(_, _, _, v) => v.truncate(len),
+            Self::Decimal256(_, _, _, v) => v.truncate(len),
+            Self::Duration(v) => v.truncate(len),
+            Self::List(_, offsets, e) => {
+                let end = truncate_offsets(offsets, len);
+                e.truncate(end);
+            }
+            Self::Map(_, offsets, key_offsets, key_values, e) => {
+                let end = truncate_offsets(offsets, len);
+                let key_end = truncate_offsets(key_offsets, end);
+                key_values.truncate(key_end);
+                e.truncate(end);
+            }
+            Self::Record(_, encodings, _) => encodings.iter_mut().for_each(|x| x.truncate(len)),
+            Self::Nullable(_, nulls, e) => {
+                let mut truncated = NullBufferBuilder::new(len);
+                match nulls.finish_cloned() {
+                    Some(n) => n.iter().take(len).for_each(|x| truncated.append(x)),
+                    None => truncated.append_n_non_nulls(len),
+                }
+                *nulls = truncated;
+                e.truncate(len)
+            }
+            Self::NonNullable(_, e) => e.truncate(len),
+        }
+    }
+
+    /// Decode a single record from `buf`
+    fn decode(&mut self, buf: &mut AvroCursor<'_>) -> Result<(), ArrowError> {
+        match self {
+            Self::Null(x) => *x += 1,
+            Self::Boolean(values) => values.append(buf.get_bool()?),
+            Self::Int32(values) | Self::Date32(values) | Self::TimeMillis(values) => {
+                values.push(buf.get_int()?)
+            }
+            Self::Int64(values)
+            | Self::TimeMicros(values)
+            | Self::TimestampMillis(_, values)
+            | Self::TimestampMicros(_, values) => values.push(buf.get_long()?),
+            Self::Float32(values) => values.push(buf.get_float()?),
+            Self::Float64(values) => values.push(buf.get_double()?),
+            Self::Int32ToInt64(values) => values.push(buf.get_int()? as i64),
+            Self::Int32ToFloat32(values) => values.push(buf.get_int()? as f32),
+            Self::Int32ToFloat64(values) => values.push(buf.get_int()? as f64),
+            Self::Int64ToFloat32(values) => values.push(buf.get_long()? as f32),
+            Self::Int64ToFloat64(values) => values.push(buf.get_long()? as f64),
+            Self::Float32ToFloat64(values) => values.push(buf.get_float()? as f64),
+            Self::Binary(offsets, values) | Self::String(offsets, values) => {
+                let data = buf.get_bytes()?;
+                offsets.push_length(data.len());
+                values.extend_from_slice(data);
+            }
+            Self::Fixed(size, values) => values.extend_from_slice(buf.get_fixed(*size as _)?),
+            Self::Uuid(values) => {
+                let data = buf.get_bytes()?;
+                values.extend_from_slice(&parse_uuid(data)?);
+            }
+            Self::Decimal128(_, _, size, values) => {
+                let data = decimal_bytes(buf, *size)?;
+                let value = match data.len() {
+                    0..=16 => i128::from_be_bytes(sign_extend(data)?),
+                    // A fixed wider than 16 bytes may still contain a value within range
+                    _ => i256::from_be_bytes(sign_extend(data)?)
+                        .to_i128()
+                        .ok_or_else(|| {
+                            ArrowError::ParseError(format!(
+                                "Decimal of {} bytes overflows Decimal128",
+                                data.len()
+                            ))
+                        })?,
+                };
+                values.push(value);
+            }
+            Self::Decimal256(_, _, size, values) => {
+                let data = decimal_bytes(buf, *size)?;
+                values.push(i256::from_be_bytes(sign_extend(data)?));
+            }
+            Self::Duration(values) => {
+                // A fixed of 12 bytes containing three little-endian unsigned integers,
+                // representing months, days and milliseconds respectively
+                let data = buf.get_fixed(12)?;
+                let months = u32::from_le_bytes(data[0..4].try_into().unwrap());
+                let days = u32::from_le_bytes(data[4..8].try_into().unwrap());
+                let millis = u32::from_le_bytes(data[8..12].try_into().unwrap());
+                values.push(IntervalMonthDayNano::new(
+                    months as i32,
+                    days as i32,
+                    millis as i64 * 1_000_000,
+                ));
+            }
+            Self::Enum(symbols, mapping, values) => {
+                let idx = buf.get_int()?;
+                let len = mapping.as_ref().map(|m| m.len()).unwrap_or(symbols.len());
+                if idx < 0 || idx as usize >= len {
+                    return Err(ArrowError::ParseError(format!(
+                        "Enum index {idx} out of bounds for {len} symbols"
+                    )));
+                }
+                match mapping {
+                    Some(mapping) => match mapping[idx as usize] {
+                        Some(idx) => values.push(idx),
+                        None => {
+                            return Err(ArrowError::ParseError(format!(
+                                "Enum index {idx} has no corresponding symbol in the reader schema"
+                            )))
+                        }
+                    },
+                    None => values.push(idx),
+                }
+            }
+            Self::List(_, offsets, e) => {
+                let len = read_blocks(buf, |buf| e.decode(buf))?;
+                offsets.push_length(len);
+            }
+            Self::Map(_, offsets, key_offsets, key_values, e) => {
+                let len = read_blocks(buf, |buf| {
+                    let key = buf.get_bytes()?;
+                    key_offsets.push_length(key.len());
+                    key_values.extend_from_slice(key);
+                    e.decode(buf)
+                })?;
+                offsets.push_length(len);
+            }
+            Self::Record(_, encodings, projector) => {
+                decode_record(encodings, projector.as_ref(), buf)?
+            }
+            Self::Nullable(nulls, nb, e) => {
+                let is_valid = match nulls {
+                    Some(nulls) => read_union_is_valid(buf, *nulls)?,
+                    None => true,
+                };
+                nb.append(is_valid);
+                match is_valid {
+                    true => e.decode(buf)?,
+                    false => e.append_null(),
+                }
+            }
+            Self::NonNullable(nulls, e) => match read_union_is_valid(buf, *nulls)? {
+                true => e.decode(buf)?,
+                false => {
+                    return Err(ArrowError::ParseError(
+                        "Found null value for non-nullable type".to_string(),
+                    ))
+                }
+            },
+        }
+        Ok(())
+    }
+
+    /// Flush decoded records to an [`ArrayRef`]
+    fn flush(&mut self, nulls: Option<NullBuffer>) -> Result<ArrayRef, ArrowError> {
+        Ok(match self {
+            Self::Nullable(_, n, e) => e.flush(n.finish())?,
+            Self::NonNullable(_, e) => e.flush(nulls)?,
+            Self::Null(size) => Arc::new(NullArray::new(std::mem::replace(size, 0))),
+            Self::Boolean(b) => Arc::new(BooleanArray::new(b.finish(), nulls)),
+            Self::Int32(values) => Arc::new(flush_primitive::<Int32Type>(values, nulls)),
+            Self::Date32(values) => Arc::new(flush_primitive::<Date32Type>(values, nulls)),
+            Self::Int64(values) | Self::Int32ToInt64(values) => {
+                Arc::new(flush_primitive::<Int64Type>(values, nulls))
+            }
+            Self::TimeMillis(values) => {
+                Arc::new(flush_primitive::<Time32MillisecondType>(values, nulls))
+            }
+            Self::TimeMicros(values) => {
+                Arc::new(flush_primitive::<Time64MicrosecondType>(values, nulls))
+            }
+            Self::TimestampMillis(is_utc, values) => Arc::new(
+                flush_primitive::<TimestampMillisecondType>(values, nulls)
+                    .with_timezone_opt(is_utc.then_some("+00:00")),
+            ),
+            Self::TimestampMicros(is_utc, values) => Arc::new(
+                flush_primitive::<TimestampMicrosecondType>(values, nulls)
+                    .with_timezone_opt(is_utc.then_some("+00:00")),
+            ),
+            Self::Float32(values) | Self::Int32ToFloat32(values) | Self::Int64ToFloat32(values) => {
+                Arc::new(flush_primitive::<Float32Type>(values, nulls))
+            }
+            Self::Float64(values)
+            | Self::Int32ToFloat64(values)
+            | Self::Int64ToFloat64(values)
+            | Self::Float32ToFloat64(values) => {
+                Arc::new(flush_primitive::<Float64Type>(values, nulls))
+            }
+            Self::Binary(offsets, values) => {
+                let offsets = flush_offsets(offsets);
+                let values = flush_values(values).into();
+                Arc::new(BinaryArray::new(offsets, values, nulls))
+            }
+            Self::String(offsets, values) => {
+                let offsets = flush_offsets(offsets);
+                let values = flush_values(values).into();
+                Arc::new(StringArray::try_new(offsets, values, nulls)?)
+            }
+            Self::Fixed(size, values) => {
+                let values = flush_values(values).into();
+                Arc::new(FixedSizeBinaryArray::try_new(*size, values, nulls)?)
+            }
+            Self::Uuid(values) => {
+                let values = flush_values(values).into();
+                Arc::new(FixedSizeBinaryArray::try_new(16, values, nulls)?)
+            }
+            Self::Decimal128(precision, scale, _, values) => Arc::new(
+                flush_primitive::<Decimal128Type>(values, nulls)
+                    .with_precision_and_scale(*precision, *scale)?,
+            ),
+            Self::Decimal256(precision, scale, _, values) => Arc::new(
+                flush_primitive::<Decimal256Type>(values, nulls)
+                    .with_precision_and_scale(*precision, *scale)?,
+            ),
+            Self::Duration(values) => {
+                Arc::new(flush_primitive::<IntervalMonthDayNanoType>(values, nulls))
+            }
+            Self::Enum(symbols, _, values) => {
+                let keys = flush_primitive::<Int32Type>(values, nulls);
+                let values = Arc::new(StringArray::from_iter_values(symbols.iter()));
+                Arc::new(DictionaryArray::try_new(keys, values)?)
+            }
+            Self::List(field, offsets, values) => {
+                let values = values.flush(None)?;
+                let offsets = flush_offsets(offsets);
+                Arc::new(ListArray::try_new(field.clone(), offsets, values, nulls)?)
+            }
+            Self::Map(field, offsets, key_offsets, key_values, values) => {
+                let fields = match field.data_type() {
+                    DataType::Struct(f) => f.clone(),
+                    _ => unreachable!(),
+                };
+                let keys = StringArray::try_new(
+                    flush_offsets(key_offsets),
+                    flush_values(key_values).into(),
+                    None,
+                )?;
+                let values = values.flush(None)?;
+                let entries = StructArray::try_new(fields, vec![Arc::new(keys), values], None)?;
+                let offsets = flush_offsets(offsets);
+                Arc::new(MapArray::try_new(
+                    field.clone(),
+                    offsets,
+                    entries,
+                    nulls,
+                    false,
+                )?)
+            }
+            Self::Record(fields, encodings, _) => {
+                let arrays = encodings
+                    .iter_mut()
+                    .map(|x| x.flush(None))
+                    .collect::<Result<Vec<_>, _>>()?;
+                match fields.is_empty() {
+                    true => {
+                        let len = nulls.as_ref().map(|n| n.len()).unwrap_or_default();
+                        Arc::new(StructArray::new_empty_fields(len, nulls))
+                    }
+                    false => Arc::new(StructArray::try_new(fields.clone(), arrays, nulls)?),
+                }
+            }
+        })
+    }
+}
+
+/// Decode a single record into `encodings`, using `projector` to map writer fields
+/// to reader fields if the writer and reader schemas differ
+fn decode_record(
+    encodings: &mut [Decoder],
+    projector: Option<&Projector>,
+    buf: &mut AvroCursor<'_>,
+) -> Result<(), ArrowError> {
+    let projector = match projector {
+        Some(projector) => projector,
+        None => {
+            for encoding in encodings {
+                encoding.decode(buf)?;
+            }
+            return Ok(());
+        }
+    };
+
+    for field in &projector.writer_fields {
+        match field {
+            ProjectedField::Read(idx) => encodings[*idx].decode(buf)?,
+            ProjectedField::Skip(skipper) => skipper.skip(buf)?,
+        }
+    }
+    for (idx, default) in &projector.defaults {
+        encodings[*idx].decode(&mut AvroCursor::new(default))?;
+    }
+    Ok(())
+}
+
+/// Read the branch index of a union of null and another type, returning `true`
+/// if the value is not null
+#[inline]
+fn read_union_is_valid(buf: &mut AvroCursor<'_>, nulls: Nulls) -> Result<bool, ArrowError> {
+    match (buf.get_long()?, nulls) {
+        (0, Nulls::NullFirst) | (1, Nulls::NullSecond) => Ok(false),
+        (1, Nulls::NullFirst) | (0, Nulls::NullSecond) => Ok(true),
+        (idx, _) => Err(ArrowError::ParseError(format!(
+            "Invalid union index {idx} for nullable type"
+        ))),
+    }
+}
+
+/// Decode a series of blocks as used to encode arrays and maps, invoking `decode_item`
+/// for each item, and returning the total number of items
+///
+/// <https://avro.apache.org/docs/1.11.1/specification/#arrays-1>
+fn read_blocks(
+    buf: &mut AvroCursor<'_>,
+    mut decode_item: impl FnMut(&mut AvroCursor<'_>) -> Result<(), ArrowError>,
+) -> Result<usize, ArrowError> {
+    let mut total = 0;
+    loop {
+        // If a block's count is negative its absolute value is used,
+        // and the count is followed by the number of bytes in the block
+        let count = match buf.get_long()? {
+            0 => return Ok(total),
+            c if c < 0 => {
+                buf.get_long()?;
+                c.unsigned_abs() as usize
+            }
+            c => c as usize,
+        };
+        for _ in 0..count {
+            decode_item(buf)?;
+        }
+        total += count;
+    }
+}
+
+/// Read the big-endian two's complement bytes of a decimal
+#[inline]
+fn decimal_bytes<'a>(
+    buf: &mut AvroCursor<'a>,
+    size: Option<usize>,
+) -> Result<&'a [u8], ArrowError> {
+    match size {
+        Some(size) => buf.get_fixed(size),
+        None => buf.get_bytes(),
+    }
+}
+
+/// Sign extend the big-endian two's complement `data` to `N` bytes
+#[inline]
+fn sign_extend<const N: usize>(data: &[u8]) -> Result<[u8; N], ArrowError> {
+    if data.len() > N {
+        return Err(ArrowError::ParseError(format!(
+            "Decimal of {} bytes exceeds maximum of {N}",
+            data.len()
+        )));
+    }
+    let fill = match data.first() {
+        Some(x) if *x & 0x80 != 0 => 0xFF,
+        _ => 0x00,
+    };
+    let mut out = [fill; N];
+    out[N - data.len()..].copy_from_slice(data);
+    Ok(out)
+}
+
+/// Parse the canonical string representation of a uuid into its 16 byte representation
+fn parse_uuid(data: &[u8]) -> Result<[u8; 16], ArrowError> {
+    let err = || ArrowError::ParseError(format!("Invalid uuid: {}", String::from_utf8_lossy(data)));
+
+    let mut out = [0_u8; 16];
+    let mut digits = data.iter().filter(|x| **x != b'-');
+    for byte in out.iter_mut() {
+        let mut next = || {
+            digits
+                .next()
+                .and_then(|x| (*x as char).to_digit(16))
+                .ok_or_else(err)
+        };
+        let high = next()?;
+        let low = next()?;
+        *byte = ((high << 4) | low) as u8;
+    }
+
+    match data.len() == 36 && digits.next().is_none() {
+        true => Ok(out),
+        false => Err(err()),
+    }
+}
+
+#[inline]
+fn flush_values<T>(values: &mut Vec<T>) -> Vec<T> {
+    std::mem::replace(values, Vec::with_capacity(DEFAULT_CAPACITY))
+}
+
+#[inline]
+/// Truncate `offsets` to the first `len` lengths, returning the end offset
+///
+/// This rebuilds the offsets, and so should only be used when discarding a record
+fn truncate_offsets(offsets: &mut OffsetBufferBuilder<i32>, len: usize) -> usize {
+    let current = offsets.finish_cloned();
+    let mut truncated = OffsetBufferBuilder::new(len);
+    for w in current.windows(2).take(len) {
+        truncated.push_length((w[1] - w[0]) as usize);
+    }
+    *offsets = truncated;
+    current[len.min(current.len() - 1)] as usize
+}
+
+fn flush_offsets(offsets: &mut OffsetBufferBuilder<i32>) -> OffsetBuffer<i32> {
+    std::mem::replace(offsets, OffsetBufferBuilder::new(DEFAULT_CAPACITY)).finish()
+}
+
+#[inline]
+fn flush_primitive<T: ArrowPrimitiveType>(
+    values: &mut Vec<T::Native>,
+    nulls: Option<NullBuffer>,
+) -> PrimitiveArray<T> {
+    PrimitiveArray::new(flush_values(values).into(), nulls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codec::AvroField;
+    use crate::encoding::{encode_bytes, encode_long};
+    use crate::schema::Schema;
+    use arrow_array::cast::AsArray;
+    use arrow_array::Array;
+
+    #[test]
+    fn test_decode() {
+        let schema: Schema = serde_json::from_str(
+            r#"{
+                "type":"record",
+                "name":"test",
+                "fields":[
+                    {"name":"a","type":["null","int"]},
+                    {"name":"b","type":"string"},
+                    {"name":"c","type":{"type":"array","items":"long"}},
+                    {"name":"d","type":{"type":"map","values":["double","null"]}},
+                    {"name":"e","type":{"type":"enum","name":"suit","symbols":["SPADES","HEARTS"]}},
+                    {"name":"f","type":{"type":"bytes","logicalType":"decimal","precision":10,"scale":2}},
+                    {"name":"g","type":["null",{"type":"record","name":"inner","fields":[{"name":"x","type":"boolean"}]}]},
+                    {"name":"h","type":{"type":"string","logicalType":"uuid"}}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let field = AvroField::try_from(&schema).unwrap();
+        let mut decoder = RecordDecoder::try_new(field.data_type()).unwrap();
+
+        let uuid = b"123e4567-e89b-12d3-a456-426614174000";
+        let mut buf = vec![];
+
+        // Row 0
+        encode_long(0, &mut buf); // a: null
+        encode_bytes(b"hi", &mut buf); // b
+        encode_long(2, &mut buf); // c: block of 2 items
+        encode_long(1, &mut buf);
+        encode_long(2, &mut buf);
+        encode_long(0, &mut buf);
+        encode_long(1, &mut buf); // d: block of 1 entry
+        encode_bytes(b"k", &mut buf);
+        encode_long(0, &mut buf);
+        buf.extend_from_slice(&1.5_f64.to_le_bytes());
+        encode_long(0, &mut buf);
+        encode_long(1, &mut buf); // e: HEARTS
+        encode_bytes(&[0x04, 0xD2], &mut buf); // f: 12.34
+        encode_long(1, &mut buf); // g: valid
+        buf.push(1);
+        encode_bytes(uuid, &mut buf); // h
+
+        // Row 1
+        encode_long(1, &mut buf); // a: 5
+        encode_long(5, &mut buf);
+        encode_bytes(b"", &mut buf); // b
+        encode_long(-1, &mut buf); // c: block of 1 item with byte size
+        encode_long(1, &mut buf);
+        encode_long(-3, &mut buf);
+        encode_long(0, &mut buf);
+        encode_long(0, &mut buf); // d: empty
+        encode_long(0, &mut buf); // e: SPADES
+        encode_bytes(&[0xFF], &mut buf); // f: -0.01
+        encode_long(0, &mut buf); // g: null
+        encode_bytes(uuid, &mut buf); // h
+
+        assert_eq!(decoder.decode(&buf, 2).unwrap(), buf.len());
+        let batch = decoder.flush().unwrap();
+        assert_eq!(batch.num_rows(), 2);
+
+        let a = batch.column(0).as_primitive::<Int32Type>();
+        assert_eq!(a.iter().collect::<Vec<_>>(), &[None, Some(5)]);
+
+        let b = batch.column(1).as_string::<i32>();
+        assert_eq!(b.iter().collect::<Vec<_>>(), &[Some("hi"), Some("")]);
+
+        let c = batch.column(2).as_list::<i32>();
+        assert_eq!(c.value_offsets(), &[0, 2, 3]);
+        let c_values = c.values().as_primitive::<Int64Type>();
+        assert_eq!(c_values.values(), &[1, 2, -3]);
+
+        let d = batch.column(3).as_map();
+        assert_eq!(d.value_offsets(), &[0, 1, 1]);
+        assert_eq!(d.keys().as_string::<i32>().value(0), "k");
+        assert_eq!(d.values().as_primitive::<Float64Type>().value(0), 1.5);
+
+        let e = batch.column(4).as_dictionary::<Int32Type>();
+        assert_eq!(e.keys().values(), &[1, 0]);
+        let symbols = e.values().as_string::<i32>();
+        assert_eq!(
+            symbols.iter().collect::<Vec<_>>(),
+            &[Some("SPADES"), Some("HEARTS")]
+        );
+
+        let f = batch.column(5).as_primitive::<Decimal128Type>();
+        assert_eq!(f.data_type(), &DataType::Decimal128(10, 2));
+        assert_eq!(f.values(), &[1234, -1]);
+
+        let g = batch.column(6).as_struct();
+        assert_eq!(
+            g.nulls().unwrap().iter().collect::<Vec<_>>(),
+            &[true, false]
+        );
+        assert!(g.column(0).as_boolean().value(0));
+
+        let h = batch.column(7).as_fixed_size_binary();
+        let expected = [
+            0x12, 0x3e, 0x45, 0x67, 0xe8, 0x9b, 0x12, 0xd3, 0xa4, 0x56, 0x42, 0x66, 0x14, 0x17,
+            0x40, 0x00,
+        ];
+        assert_eq!(h.value(0), &expected);
+        assert_eq!(h.value(1), &expected);
+
+        // Decoder should be reusable after flush
+        assert_eq!(decoder.decode(&[], 0).unwrap(), 0);
+        assert_eq!(decoder.flush().unwrap().num_rows(), 0);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let schema: Schema = serde_json::from_str(
+            r#"{"type":"record","name":"test","fields":[{"name":"a","type":["null","int"]}]}"#,
+        )
+        .unwrap();
+        let field = AvroField::try_from(&schema).unwrap();
+        let mut decoder = RecordDecoder::try_new(field.data_type()).unwrap();
+
+        let err = decoder.decode(&[0x04], 1).unwrap_err().to_string();
+        assert_eq!(err, "Parser error: Invalid union index 2 for nullable type");
+
+        let err = parse_uuid(b"not-a-uuid").unwrap_err().to_string();
+        assert_eq!(err, "Parser error: Invalid uuid: not-a-uuid");
+    }
+
+    #[test]
+    fn test_decode_error_reset() {
+        let schema: Schema = serde_json::from_str(
+            r#"{
+                "type":"record",
+                "name":"test",
+                "fields":[
+                    {"name":"a","type":"int"},
+                    {"name":"b","type":{"type":"array","items":"string"}},
+                    {"name":"c","type":["null","long"]}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let field = AvroField::try_from(&schema).unwrap();
+        let mut decoder = RecordDecoder::try_new(field.data_type()).unwrap();
+
+        let mut valid = vec![];
+        encode_long(1, &mut valid); // a
+        encode_long(1, &mut valid); // b: block of 1 item
+        encode_bytes(b"x", &mut valid);
+        encode_long(0, &mut valid);
+        encode_long(1, &mut valid); // c: 2
+        encode_long(2, &mut valid);
+
+        // Fails after decoding a and an item of b
+        let mut invalid = vec![];
+        encode_long(3, &mut invalid); // a
+        encode_long(2, &mut invalid); // b: block of 2 items
+        encode_bytes(b"y", &mut invalid);
+
+        assert_eq!(decoder.decode(&valid, 1).unwrap(), valid.len());
+        let err = decoder.decode(&invalid, 1).unwrap_err().to_string();
+        assert_eq!(err, "Parser error: Unexpected EOF");
+        // Only the partially decoded record is discarded
+        assert_eq!(decoder.num_rows(), 1);
+
+        // The decoder remains consistent and usable
+        assert_eq!(decoder.decode(&valid, 1).unwrap(), valid.len());
+        let batch = decoder.flush().unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        let a = batch.column(0).as_primitive::<Int32Type>();
+        assert_eq!(a.values(), &[1, 1]);
+        let b = batch.column(1).as_list::<i32>();
+        assert_eq!(b.value_offsets(), &[0, 1, 2]);
+        let items = b.values().as_string::<i32>();
+        assert_eq!(items.iter().collect::<Vec<_>>(), &[Some("x"), Some("x")]);
+        let c = batch.column(2).as_primitive::<Int64Type>();
+        assert_eq!(c.iter().collect::<Vec<_>>(), &[Some(2), Some(2)]);
+    }
+
+    #[test]
+    fn test_decode_wide_fixed_decimal() {
+        let schema: Schema = serde_json::from_str(
+            r#"{
+                "type":"record",
+                "name":"test",
+                "fields":[
+                    {"name":"a","type":{"type":"fixed","name":"a","size":20,"logicalType":"decimal","precision":20,"scale":0}},
+                    {"name":"b","type":{"type":"fixed","name":"b","size":20,"logicalType":"decimal","precision":40,"scale":0}}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let field = AvroField::try_from(&schema).unwrap();
+        let mut decoder = RecordDecoder::try_new(field.data_type()).unwrap();
+
+        let mut buf = [0xFF; 20].to_vec();
+        buf[19] = 0xFB; // a: -5
+        buf.extend_from_slice(&[0; 20]);
+        buf[20] = 0x01; // b: 2^152
+        assert_eq!(decoder.decode(&buf, 1).unwrap(), 40);
+
+        let batch = decoder.flush().unwrap();
+        let a = batch.column(0).as_primitive::<Decimal128Type>();
+        assert_eq!(a.value(0), -5);
+        let b = batch.column(1).as_primitive::<Decimal256Type>();
+        assert_eq!(
+            b.value(0),
+            i256::from_i128(1 << 76).wrapping_mul(i256::from_i128(1 << 76))
+        );
+
+        // A value of `a` exceeding the range of Decimal128
+        let mut buf = [0; 40].to_vec();
+        buf[0] = 0x01;
+        let err = decoder.decode(&buf, 1).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "Parser error: Decimal of 20 bytes overflows Decimal128"
+        );
+    }
+
+    #[test]
+    fn test_schema_resolution() {
+        let writer: Schema = serde_json::from_str(
+            r#"{
+                "type":"record",
+                "name":"test",
+                "fields":[
+                    {"name":"a","type":"int"},
+                    {"name":"b","type":{"type":"array","items":["null","string"]}},
+                    {"name":"c","type":"float"},
+                    {"name":"d","type":{"type":"enum","name":"suit","symbols":["SPADES","HEARTS","CLUBS"]}},
+                    {"name":"e","type":["null","long"]},
+                    {"name":"f","type":"bytes"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let reader: Schema = serde_json::from_str(
+            r#"{
+                "type":"record",
+                "name":"test",
+                "fields":[
+                    {"name":"d","type":{"type":"enum","name":"suit","symbols":["CLUBS","SPADES"]}},
+                    {"name":"c","type":"double"},
+                    {"name":"a","type":"long"},
+                    {"name":"g","type":["null","string"],"default":null},
+                    {"name":"h","type":{"type":"array","items":"int"},"default":[1,2]},
+                    {"name":"e","type":"double"},
+                    {"name":"f","type":"string"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let field = AvroField::resolve(&writer, &reader).unwrap();
+        let mut decoder = RecordDecoder::try_new(field.data_type()).unwrap();
+
+        let fields = decoder.schema().fields();
+        let names: Vec<_> = fields.iter().map(|x| x.name().as_str()).collect();
+        assert_eq!(names, &["d", "c", "a", "g", "h", "e", "f"]);
+        assert_eq!(fields[1].data_type(), &DataType::Float64);
+        assert_eq!(fields[2].data_type(), &DataType::Int64);
+        assert!(fields[3].is_nullable());
+        assert!(!fields[5].is_nullable());
+
+        let mut buf = vec![];
+        encode_long(-4, &mut buf); // a
+        encode_long(2, &mut buf); // b: block of 2 items
+        encode_long(0, &mut buf);
+        encode_long(1, &mut buf);
+        encode_bytes(b"skipped", &mut buf);
+        encode_long(0, &mut buf);
+        buf.extend_from_slice(&1.5_f32.to_le_bytes()); // c
+        encode_long(2, &mut buf); // d: CLUBS
+        encode_long(1, &mut buf); // e: 7
+        encode_long(7, &mut buf);
+        encode_bytes(b"hello", &mut buf); // f
+
+        assert_eq!(decoder.decode(&buf, 1).unwrap(), buf.len());
+        let batch = decoder.flush().unwrap();
+
+        let d = batch.column(0).as_dictionary::<Int32Type>();
+        assert_eq!(d.keys().values(), &[0]);
+        let symbols = d.values().as_string::<i32>();
+        assert_eq!(
+            symbols.iter().collect::<Vec<_>>(),
+            &[Some("CLUBS"), Some("SPADES")]
+        );
+
+        assert_eq!(
+            batch.column(1).as_primitive::<Float64Type>().values(),
+            &[1.5]
+        );
+        assert_eq!(batch.column(2).as_primitive::<Int64Type>().values(), &[-4]);
+        assert!(batch.column(3).is_null(0));
+
+        let h = batch.column(4).as_list::<i32>();
+        assert_eq!(h.value_offsets(), &[0, 2]);
+        assert_eq!(h.values().as_primitive::<Int32Type>().values(), &[1, 2]);
+
+        assert_eq!(
+            batch.column(5).as_primitive::<Float64Type>().values(),
+            &[7.]
+        );
+        assert_eq!(batch.column(6).as_string::<i32>().value(0), "hello");
+
+        // HEARTS is not present in the reader schema
+        let err = decoder.decode(&[0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02], 1);
+        let err = err.unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "Parser error: Enum index 1 has no corresponding symbol in the reader schema"
+        );
+    }
+
+    #[test]
+    fn test_schema_resolution_aliases() {
+        let writer: Schema = serde_json::from_str(
+            r#"{
+                "type":"record",
+                "name":"ns.old",
+                "fields":[
+                    {"name":"a_old","type":"int"},
+                    {"name":"e","type":{"type":"enum","name":"suit","symbols":["SPADES","HEARTS"]}}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let reader: Schema = serde_json::from_str(
+            r#"{
+                "type":"record",
+                "name":"test",
+                "aliases":["other.old"],
+                "fields":[
+                    {"name":"a","type":"int","aliases":["a_old"]},
+                    {"name":"e","type":{
+                        "type":"enum",
+                        "name":"card",
+                        "aliases":["suit"],
+                        "symbols":["SPADES","UNKNOWN"],
+                        "default":"UNKNOWN"
+                    }}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let field = AvroField::resolve(&writer, &reader).unwrap();
+        let mut decoder = RecordDecoder::try_new(field.data_type()).unwrap();
+
+        let mut buf = vec![];
+        encode_long(3, &mut buf); // a_old
+        encode_long(1, &mut buf); // e: HEARTS
+        encode_long(4, &mut buf); // a_old
+        encode_long(0, &mut buf); // e: SPADES
+
+        assert_eq!(decoder.decode(&buf, 2).unwrap(), buf.len());
+        let batch = decoder.flush().unwrap();
+        assert_eq!(batch.schema().field(0).name(), "a");
+        assert_eq!(
+            batch.column(0).as_primitive::<Int32Type>().values(),
+            &[3, 4]
+        );
+
+        // HEARTS is read as the default symbol of the reader
+        let e = batch.column(1).as_dictionary::<Int32Type>();
+        assert_eq!(e.keys().values(), &[1, 0]);
+    }
+
+    #[test]
+    fn test_schema_resolution_errors() {
+        let resolve = |writer: &str, reader: &str| {
+            let writer: Schema = serde_json::from_str(writer).unwrap();
+            let reader: Schema = serde_json::from_str(reader).unwrap();
+            AvroField::resolve(&writer, &reader)
+        };
+
+        let err = resolve(
+            r#"{"type":"record","name":"test","fields":[{"name":"a","type":"long"}]}"#,
+            r#"{"type":"record","name":"test","fields":[{"name":"a","type":"int"}]}"#,
+        )
+        .unwrap_err()
+        .to_string();
+        assert_eq!(
+            err,
+            "Schema error: Cannot resolve writer type Int64 to reader type Int32"
+        );
+
+        let err = resolve(
+            r#"{"type":"record","name":"test","fields":[{"name":"a","type":"long"}]}"#,
+            r#"{"type":"record","name":"test","fields":[{"name":"b","type":"long"}]}"#,
+        )
+        .unwrap_err()
+        .to_string();
+        assert_eq!(
+            err,
+            "Schema error: Reader field 'b' not present in writer schema and has no default"
+        );
+
+        let err = resolve(
+            r#"{"type":"record","name":"test","fields":[]}"#,
+            r#"{"type":"record","name":"test","fields":[{"name":"b","type":"long","default":"x"}]}"#,
+        )
+        .unwrap_err()
+        .to_string();
+        assert_eq!(err, "Schema error: Invalid default value \"x\" for Int64");
+
+        let err = resolve(
+            r#"{"type":"record","name":"a","fields":[]}"#,
+            r#"{"type":"record","name":"b","aliases":["c"],"fields":[]}"#,
+        )
+        .unwrap_err()
+        .to_string();
+        assert_eq!(
+            err,
+            "Schema error: Cannot resolve writer type 'a' to reader type 'b'"
+        );
+
+        let err = resolve(
+            r#"{"type":"record","name":"test","fields":[
+                {"name":"f","type":{"type":"fixed","name":"md5","size":16}}
+            ]}"#,
+            r#"{"type":"record","name":"test","fields":[
+                {"name":"f","type":{"type":"fixed","name":"sha","size":16}}
+            ]}"#,
+        )
+        .unwrap_err()
+        .to_string();
+        assert_eq!(
+            err,
+            "Schema error: Cannot resolve writer type 'md5' to reader type 'sha'"
+        );
+
+        // Types sharing an encoding must share a logical type
+        let record = |t: &str| {
+            format!(r#"{{"type":"record","name":"test","fields":[{{"name":"a","type":{t}}}]}}"#)
+        };
+        let decimal = r#"{"type":"bytes","logicalType":"decimal","precision":10,"scale":2}"#;
+        let uuid = r#"{"type":"string","logicalType":"uuid"}"#;
+        let date = r#"{"type":"int","logicalType":"date"}"#;
+        for (writer, reader, expected) in [
+            (
+                r#""string""#,
+                decimal,
+                "Utf8 to reader type Decimal(10, 2, None)",
+            ),
+            (
+                r#""bytes""#,
+                decimal,
+                "Binary to reader type Decimal(10, 2, None)",
+            ),
+            (r#""bytes""#, uuid, "Binary to reader type Uuid"),
+            (r#""int""#, date, "Int32 to reader type Date32"),
+            (date, r#""int""#, "Date32 to reader type Int32"),
+        ] {
+            let err = resolve(&record(writer), &record(reader)).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("Schema error: Cannot resolve writer type {expected}")
+            );
+        }
+        resolve(&record(r#""string""#), &record(r#""bytes""#)).unwrap();
+        resolve(&record(r#""bytes""#), &record(r#""string""#)).unwrap();
+
+        // A nullable writer field can be read as non-nullable, erroring on null
+        let field = resolve(
+            r#"{"type":"record","name":"test","fields":[{"name":"a","type":["null","int"]}]}"#,
+            r#"{"type":"record","name":"test","fields":[{"name":"a","type":"int"}]}"#,
+        )
+        .unwrap();
+        let mut decoder = RecordDecoder::try_new(field.data_type()).unwrap();
+        let err = decoder.decode(&[0x00], 1).unwrap_err().to_string();
+        assert_eq!(err, "Parser error: Found null value for non-nullable type");
+    }
+}
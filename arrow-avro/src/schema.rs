@@ -15,7 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use serde::{Deserialize, Serialize};
+use arrow_schema::ArrowError;
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 
 /// The metadata key used for storing the JSON encoded [`Schema`]
@@ -144,8 +145,25 @@ pub struct Field<'a> {
     pub doc: Option<&'a str>,
     #[serde(borrow)]
     pub r#type: Schema<'a>,
+    /// The default value of this field, used when resolving a writer schema lacking this field
+    ///
+    /// <https://avro.apache.org/docs/1.11.1/specification/#schema-resolution>
+    #[serde(
+        default,
+        deserialize_with = "deserialize_some",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default: Option<serde_json::Value>,
     #[serde(borrow, default)]
-    pub default: Option<&'a str>,
+    pub aliases: Vec<&'a str>,
+}
+
+/// Deserializes a present value as `Some`, distinguishing an explicit `null` from a missing value
+fn deserialize_some<'de, D>(deserializer: D) -> Result<Option<serde_json::Value>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    serde_json::Value::deserialize(deserializer).map(Some)
 }
 
 /// An enumeration
@@ -207,6 +225,212 @@ pub struct Fixed<'a> {
     pub attributes: Attributes<'a>,
 }
 
+/// The identifier of an Avro schema, as found in the prefix of an encoded message
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Fingerprint {
+    /// The 64-bit Rabin fingerprint of the [Parsing Canonical Form] of the schema,
+    /// as used by the [single object encoding]
+    ///
+    /// [Parsing Canonical Form]: https://avro.apache.org/docs/1.11.1/specification/#parsing-canonical-form-for-schemas
+    /// [single object encoding]: https://avro.apache.org/docs/1.11.1/specification/#single-object-encoding
+    Rabin(u64),
+    /// A schema id assigned by a schema registry, as used by the [Confluent wire format]
+    ///
+    /// [Confluent wire format]: https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format
+    Id(u32),
+}
+
+/// An in-memory mapping from [`Fingerprint`] to JSON encoded Avro schema
+#[derive(Debug, Clone, Default)]
+pub struct SchemaStore {
+    schemas: HashMap<Fingerprint, String>,
+}
+
+impl SchemaStore {
+    /// Create an empty [`SchemaStore`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a JSON encoded Avro schema by its [`Fingerprint::Rabin`], returning the fingerprint
+    pub fn register(&mut self, schema: impl Into<String>) -> Result<Fingerprint, ArrowError> {
+        let schema = schema.into();
+        let value: serde_json::Value = serde_json::from_str(&schema)
+            .map_err(|e| ArrowError::ParseError(format!("Invalid Avro schema: {e}")))?;
+        let fingerprint = Fingerprint::Rabin(rabin_fingerprint(canonical_form(&value)?.as_bytes()));
+        self.schemas.insert(fingerprint, schema);
+        Ok(fingerprint)
+    }
+
+    /// Register a JSON encoded Avro schema with the provided [`Fingerprint::Id`]
+    pub fn register_id(&mut self, id: u32, schema: impl Into<String>) -> Result<(), ArrowError> {
+        let schema = schema.into();
+        serde_json::from_str::<Schema<'_>>(&schema)
+            .map_err(|e| ArrowError::ParseError(format!("Invalid Avro schema: {e}")))?;
+        self.schemas.insert(Fingerprint::Id(id), schema);
+        Ok(())
+    }
+
+    /// Returns the JSON encoded Avro schema with the given [`Fingerprint`], if any
+    pub fn lookup(&self, fingerprint: &Fingerprint) -> Option<&str> {
+        self.schemas.get(fingerprint).map(|x| x.as_str())
+    }
+}
+
+/// Computes the 64-bit Rabin fingerprint, also known as CRC-64-AVRO, of `data`
+///
+/// <https://avro.apache.org/docs/1.11.1/specification/#schema-fingerprints>
+pub(crate) fn rabin_fingerprint(data: &[u8]) -> u64 {
+    const EMPTY: u64 = 0xc15d213aa4d7a795;
+    const TABLE: [u64; 256] = {
+        let mut table = [0_u64; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut fp = i as u64;
+            let mut j = 0;
+            while j < 8 {
+                fp = (fp >> 1) ^ (EMPTY & (fp & 1).wrapping_neg());
+                j += 1;
+            }
+            table[i] = fp;
+            i += 1;
+        }
+        table
+    };
+
+    data.iter().fold(EMPTY, |fp, b| {
+        (fp >> 8) ^ TABLE[((fp ^ *b as u64) & 0xff) as usize]
+    })
+}
+
+/// Returns the [Parsing Canonical Form] of the JSON encoded Avro `schema`
+///
+/// [Parsing Canonical Form]: https://avro.apache.org/docs/1.11.1/specification/#parsing-canonical-form-for-schemas
+pub(crate) fn canonical_form(schema: &serde_json::Value) -> Result<String, ArrowError> {
+    let mut out = String::new();
+    write_canonical(schema, "", &mut out)?;
+    Ok(out)
+}
+
+const PRIMITIVE_TYPES: [&str; 8] = [
+    "null", "boolean", "int", "long", "float", "double", "bytes", "string",
+];
+
+/// Returns the full name and namespace of a name defined or referenced within `namespace`
+fn full_name(name: &str, namespace: &str) -> (String, String) {
+    match name.rsplit_once('.') {
+        Some((ns, _)) => (name.to_string(), ns.to_string()),
+        None if namespace.is_empty() => (name.to_string(), String::new()),
+        None => (format!("{namespace}.{name}"), namespace.to_string()),
+    }
+}
+
+fn write_canonical(
+    schema: &serde_json::Value,
+    namespace: &str,
+    out: &mut String,
+) -> Result<(), ArrowError> {
+    use serde_json::Value;
+    use std::fmt::Write;
+
+    let invalid = || ArrowError::ParseError(format!("Invalid Avro schema: {schema}"));
+    let quote = |s: &str| serde_json::to_string(s).unwrap();
+
+    let object = match schema {
+        Value::String(s) if PRIMITIVE_TYPES.contains(&s.as_str()) => {
+            out.push_str(&quote(s));
+            return Ok(());
+        }
+        Value::String(s) => {
+            out.push_str(&quote(&full_name(s, namespace).0));
+            return Ok(());
+        }
+        Value::Array(variants) => {
+            out.push('[');
+            for (idx, variant) in variants.iter().enumerate() {
+                if idx != 0 {
+                    out.push(',');
+                }
+                write_canonical(variant, namespace, out)?;
+            }
+            out.push(']');
+            return Ok(());
+        }
+        Value::Object(object) => object,
+        _ => return Err(invalid()),
+    };
+
+    let type_name = match object.get("type").ok_or_else(invalid)? {
+        Value::String(s) => s.as_str(),
+        nested => return write_canonical(nested, namespace, out),
+    };
+
+    match type_name {
+        "record" | "error" | "enum" | "fixed" => {
+            let name = object
+                .get("name")
+                .and_then(|x| x.as_str())
+                .ok_or_else(invalid)?;
+            let namespace = match object.get("namespace").and_then(|x| x.as_str()) {
+                Some(ns) if !name.contains('.') => ns,
+                _ => namespace,
+            };
+            let (name, namespace) = full_name(name, namespace);
+            write!(
+                out,
+                "{{\"name\":{},\"type\":{}",
+                quote(&name),
+                quote(type_name)
+            )
+            .unwrap();
+            match type_name {
+                "enum" => {
+                    let symbols = object.get("symbols").ok_or_else(invalid)?;
+                    write!(out, ",\"symbols\":{symbols}").unwrap();
+                }
+                "fixed" => {
+                    let size = object.get("size").and_then(|x| x.as_u64());
+                    write!(out, ",\"size\":{}", size.ok_or_else(invalid)?).unwrap();
+                }
+                _ => {
+                    let fields = object.get("fields").and_then(|x| x.as_array());
+                    out.push_str(",\"fields\":[");
+                    for (idx, field) in fields.ok_or_else(invalid)?.iter().enumerate() {
+                        if idx != 0 {
+                            out.push(',');
+                        }
+                        let name = field.get("name").and_then(|x| x.as_str());
+                        let field_type = field.get("type").ok_or_else(invalid)?;
+                        write!(
+                            out,
+                            "{{\"name\":{},\"type\":",
+                            quote(name.ok_or_else(invalid)?)
+                        )
+                        .unwrap();
+                        write_canonical(field_type, &namespace, out)?;
+                        out.push('}');
+                    }
+                    out.push(']');
+                }
+            }
+            out.push('}');
+        }
+        "array" => {
+            out.push_str("{\"type\":\"array\",\"items\":");
+            write_canonical(object.get("items").ok_or_else(invalid)?, namespace, out)?;
+            out.push('}');
+        }
+        "map" => {
+            out.push_str("{\"type\":\"map\",\"values\":");
+            write_canonical(object.get("values").ok_or_else(invalid)?, namespace, out)?;
+            out.push('}');
+        }
+        // A primitive type, possibly annotated with a logical type, or a named type reference
+        name => write_canonical(&Value::String(name.to_string()), namespace, out)?,
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -317,6 +541,7 @@ mod tests {
                         Schema::TypeName(TypeName::Primitive(PrimitiveType::Null)),
                     ]),
                     default: None,
+                    aliases: vec![],
                 },],
                 attributes: Default::default(),
             }))
@@ -348,6 +573,7 @@ mod tests {
                         doc: None,
                         r#type: Schema::TypeName(TypeName::Primitive(PrimitiveType::Long)),
                         default: None,
+                        aliases: vec![],
                     },
                     Field {
                         name: "next",
@@ -357,6 +583,7 @@ mod tests {
                             Schema::TypeName(TypeName::Ref("LongList")),
                         ]),
                         default: None,
+                        aliases: vec![],
                     }
                 ],
                 attributes: Attributes::default(),
@@ -410,6 +637,7 @@ mod tests {
                             Schema::TypeName(TypeName::Primitive(PrimitiveType::Null)),
                         ]),
                         default: None,
+                        aliases: vec![],
                     },
                     Field {
                         name: "timestamp_col",
@@ -419,6 +647,7 @@ mod tests {
                             Schema::TypeName(TypeName::Primitive(PrimitiveType::Null)),
                         ]),
                         default: None,
+                        aliases: vec![],
                     }
                 ],
                 attributes: Default::default(),
@@ -474,6 +703,7 @@ mod tests {
                             attributes: Default::default(),
                         })),
                         default: None,
+                        aliases: vec![],
                     },
                     Field {
                         name: "clientProtocol",
@@ -483,12 +713,14 @@ mod tests {
                             Schema::TypeName(TypeName::Primitive(PrimitiveType::String)),
                         ]),
                         default: None,
+                        aliases: vec![],
                     },
                     Field {
                         name: "serverHash",
                         doc: None,
                         r#type: Schema::TypeName(TypeName::Ref("MD5")),
                         default: None,
+                        aliases: vec![],
                     },
                     Field {
                         name: "meta",
@@ -503,10 +735,67 @@ mod tests {
                             })),
                         ]),
                         default: None,
+                        aliases: vec![],
                     }
                 ],
                 attributes: Default::default(),
             }))
         );
     }
+
+    #[test]
+    fn test_canonical_form() {
+        let schema = json!({
+            "type": "record",
+            "name": "test",
+            "namespace": "com.example",
+            "doc": "A test record",
+            "fields": [
+                {"name": "a", "type": {"type": "int"}, "default": 1},
+                {"name": "b", "type": ["null", {"type": "long", "logicalType": "timestamp-micros"}]},
+                {"name": "c", "type": {"type": "enum", "name": "suit", "symbols": ["SPADES", "HEARTS"]}},
+                {"name": "d", "type": {"type": "array", "items": "suit"}},
+                {"name": "e", "type": {"type": "fixed", "name": "other.md5", "size": 16}},
+                {"name": "f", "type": {"type": "map", "values": "other.md5"}}
+            ]
+        });
+        assert_eq!(
+            canonical_form(&schema).unwrap(),
+            concat!(
+                r#"{"name":"com.example.test","type":"record","fields":["#,
+                r#"{"name":"a","type":"int"},"#,
+                r#"{"name":"b","type":["null","long"]},"#,
+                r#"{"name":"c","type":{"name":"com.example.suit","type":"enum","symbols":["SPADES","HEARTS"]}},"#,
+                r#"{"name":"d","type":{"type":"array","items":"com.example.suit"}},"#,
+                r#"{"name":"e","type":{"name":"other.md5","type":"fixed","size":16}},"#,
+                r#"{"name":"f","type":{"type":"map","values":"other.md5"}}]}"#,
+            )
+        );
+
+        let err = canonical_form(&json!({"type": "record"})).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"Parser error: Invalid Avro schema: {"type":"record"}"#
+        );
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        // Test vectors from the Avro specification test suite
+        assert_eq!(rabin_fingerprint(br#""null""#), 7195948357588979594);
+        assert_eq!(rabin_fingerprint(br#""int""#), 8247732601305521295);
+        assert_eq!(
+            rabin_fingerprint(br#""boolean""#) as i64,
+            -6970731678124411036
+        );
+
+        let mut store = SchemaStore::new();
+        let fingerprint = store.register(r#"{"type": "null"}"#).unwrap();
+        assert_eq!(fingerprint, Fingerprint::Rabin(7195948357588979594));
+        assert_eq!(store.lookup(&fingerprint), Some(r#"{"type": "null"}"#));
+
+        store.register_id(1, r#""int""#).unwrap();
+        assert_eq!(store.lookup(&Fingerprint::Id(1)), Some(r#""int""#));
+        assert_eq!(store.lookup(&Fingerprint::Id(2)), None);
+    }
 }
@@ -0,0 +1,393 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Encoding of Arrow arrays into the Avro binary encoding
+//!
+//! <https://avro.apache.org/docs/1.11.1/specification/#binary-encoding>
+
+use crate::codec::{AvroDataType, Codec, Nulls};
+use crate::encoding::{encode_bytes, encode_long};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    ByteArrayType, Date32Type, Float32Type, Float64Type, Int32Type, Int64Type,
+    IntervalMonthDayNanoType, Time32MillisecondType, Time64MicrosecondType,
+    TimestampMicrosecondType, TimestampMillisecondType,
+};
+use arrow_array::{
+    Array, BooleanArray, FixedSizeBinaryArray, GenericByteArray, MapArray, StructArray,
+};
+use arrow_buffer::{i256, NullBuffer, OffsetBuffer};
+use arrow_schema::{ArrowError, DataType};
+use std::collections::HashMap;
+
+pub(crate) trait Encoder {
+    /// Encode the value at index `idx` to `out`
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>);
+}
+
+/// Create an [`Encoder`] for `array` which must be of the arrow type corresponding
+/// to `data_type`, as returned by [`AvroDataType::field_with_name`]
+pub(crate) fn make_encoder<'a>(
+    data_type: &AvroDataType,
+    array: &'a dyn Array,
+) -> Result<Box<dyn Encoder + 'a>, ArrowError> {
+    let encoder: Box<dyn Encoder + 'a> = match data_type.codec() {
+        Codec::Null => Box::new(NullEncoder),
+        Codec::Boolean => Box::new(BooleanEncoder(array.as_boolean())),
+        Codec::Int32 => Box::new(IntEncoder(&array.as_primitive::<Int32Type>().values()[..])),
+        Codec::Date32 => Box::new(IntEncoder(&array.as_primitive::<Date32Type>().values()[..])),
+        Codec::TimeMillis => Box::new(IntEncoder(
+            &array.as_primitive::<Time32MillisecondType>().values()[..],
+        )),
+        Codec::Int64 => Box::new(IntEncoder(&array.as_primitive::<Int64Type>().values()[..])),
+        Codec::TimeMicros => Box::new(IntEncoder(
+            &array.as_primitive::<Time64MicrosecondType>().values()[..],
+        )),
+        Codec::TimestampMillis(_) => Box::new(IntEncoder(
+            &array.as_primitive::<TimestampMillisecondType>().values()[..],
+        )),
+        Codec::TimestampMicros(_) => Box::new(IntEncoder(
+            &array.as_primitive::<TimestampMicrosecondType>().values()[..],
+        )),
+        Codec::Float32 => Box::new(Float32Encoder(array.as_primitive::<Float32Type>().values())),
+        Codec::Float64 => Box::new(Float64Encoder(array.as_primitive::<Float64Type>().values())),
+        Codec::Binary => Box::new(BytesEncoder(array.as_binary::<i32>())),
+        Codec::Utf8 => Box::new(BytesEncoder(array.as_string::<i32>())),
+        Codec::Fixed(_) => Box::new(FixedEncoder(array.as_fixed_size_binary())),
+        Codec::Uuid => Box::new(UuidEncoder(array.as_fixed_size_binary())),
+        Codec::Decimal(_, _, size) => match array.data_type() {
+            DataType::Decimal128(_, _) => Box::new(Decimal128Encoder {
+                values: array
+                    .as_primitive::<arrow_array::types::Decimal128Type>()
+                    .values(),
+                size: *size,
+            }),
+            DataType::Decimal256(_, _) => Box::new(Decimal256Encoder {
+                values: array
+                    .as_primitive::<arrow_array::types::Decimal256Type>()
+                    .values(),
+                size: *size,
+            }),
+            d => {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Expected decimal array got {d}"
+                )))
+            }
+        },
+        Codec::Duration => {
+            let array = array.as_primitive::<IntervalMonthDayNanoType>();
+            // Avro durations are unsigned and have millisecond precision
+            for (idx, v) in array.values().iter().enumerate() {
+                let valid = v.months >= 0
+                    && v.days >= 0
+                    && v.nanoseconds >= 0
+                    && v.nanoseconds % 1_000_000 == 0
+                    && v.nanoseconds / 1_000_000 <= u32::MAX as i64;
+                if !valid && array.is_valid(idx) {
+                    return Err(ArrowError::InvalidArgumentError(format!(
+                        "Interval {v:?} cannot be represented as an Avro duration"
+                    )));
+                }
+            }
+            Box::new(DurationEncoder(array.values()))
+        }
+        Codec::Enum(symbols) => {
+            let array = array.as_dictionary::<Int32Type>();
+            let lookup: HashMap<&str, i32> = symbols
+                .iter()
+                .enumerate()
+                .map(|(idx, s)| (s.as_str(), idx as i32))
+                .collect();
+
+            let values = array.values().as_string::<i32>();
+            let mapping = values
+                .iter()
+                .map(|v| match v.and_then(|v| lookup.get(v)) {
+                    Some(idx) => Ok(*idx),
+                    None => Err(ArrowError::InvalidArgumentError(format!(
+                        "Dictionary value {v:?} is not a symbol of enum {symbols:?}"
+                    ))),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // The keys of null slots are not validated by arrow, and are encoded as the
+            // first symbol if not masked by a nullable encoding
+            let symbols = array
+                .keys()
+                .iter()
+                .map(|key| match key {
+                    Some(key) => usize::try_from(key)
+                        .ok()
+                        .and_then(|k| mapping.get(k).copied())
+                        .ok_or_else(|| {
+                            ArrowError::InvalidArgumentError(format!(
+                                "Dictionary key {key} out of bounds for {} values",
+                                mapping.len()
+                            ))
+                        }),
+                    None => Ok(0),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Box::new(EnumEncoder(symbols))
+        }
+        Codec::List(item) => {
+            let array = array.as_list::<i32>();
+            Box::new(ListEncoder {
+                offsets: array.offsets(),
+                values: make_encoder(item, array.values().as_ref())?,
+            })
+        }
+        Codec::Map(values) => {
+            let array = array.as_map();
+            Box::new(MapEncoder {
+                offsets: array.offsets(),
+                keys: array.keys().as_string::<i32>(),
+                values: make_encoder(values, array.values().as_ref())?,
+            })
+        }
+        Codec::Struct(fields) => {
+            let array = array.as_struct();
+            let encoders = fields
+                .iter()
+                .zip(array.columns())
+                .map(|(field, array)| make_encoder(field.data_type(), array.as_ref()))
+                .collect::<Result<Vec<_>, _>>()?;
+            Box::new(StructEncoder(encoders))
+        }
+    };
+
+    Ok(match data_type.nulls() {
+        Some(nulls) => Box::new(NullableEncoder {
+            nulls,
+            null_buffer: array.logical_nulls(),
+            encoder,
+        }),
+        None => encoder,
+    })
+}
+
+/// Encodes a nullable value as a union of null and the value type
+struct NullableEncoder<'a> {
+    nulls: Nulls,
+    null_buffer: Option<NullBuffer>,
+    encoder: Box<dyn Encoder + 'a>,
+}
+
+impl Encoder for NullableEncoder<'_> {
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
+        let is_null = self.null_buffer.as_ref().map(|n| n.is_null(idx)) == Some(true);
+        let union_idx = match (self.nulls, is_null) {
+            (Nulls::NullFirst, true) | (Nulls::NullSecond, false) => 0,
+            (Nulls::NullFirst, false) | (Nulls::NullSecond, true) => 1,
+        };
+        encode_long(union_idx, out);
+        if !is_null {
+            self.encoder.encode(idx, out)
+        }
+    }
+}
+
+struct NullEncoder;
+
+impl Encoder for NullEncoder {
+    fn encode(&mut self, _idx: usize, _out: &mut Vec<u8>) {}
+}
+
+struct BooleanEncoder<'a>(&'a BooleanArray);
+
+impl Encoder for BooleanEncoder<'_> {
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
+        out.push(self.0.value(idx) as u8)
+    }
+}
+
+struct IntEncoder<'a, T>(&'a [T]);
+
+impl<T: Copy + Into<i64>> Encoder for IntEncoder<'_, T> {
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
+        encode_long(self.0[idx].into(), out)
+    }
+}
+
+struct Float32Encoder<'a>(&'a [f32]);
+
+impl Encoder for Float32Encoder<'_> {
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.0[idx].to_le_bytes())
+    }
+}
+
+struct Float64Encoder<'a>(&'a [f64]);
+
+impl Encoder for Float64Encoder<'_> {
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.0[idx].to_le_bytes())
+    }
+}
+
+struct BytesEncoder<'a, T: ByteArrayType>(&'a GenericByteArray<T>);
+
+impl<T: ByteArrayType> Encoder for BytesEncoder<'_, T> {
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
+        let value: &[u8] = self.0.value(idx).as_ref();
+        encode_bytes(value, out)
+    }
+}
+
+struct FixedEncoder<'a>(&'a FixedSizeBinaryArray);
+
+impl Encoder for FixedEncoder<'_> {
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.0.value(idx))
+    }
+}
+
+/// Encodes 16 byte binary values as the canonical string representation of a uuid
+struct UuidEncoder<'a>(&'a FixedSizeBinaryArray);
+
+impl Encoder for UuidEncoder<'_> {
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+        encode_long(36, out);
+        for (i, b) in self.0.value(idx).iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                out.push(b'-');
+            }
+            out.push(HEX[(b >> 4) as usize]);
+            out.push(HEX[(b & 0xF) as usize]);
+        }
+    }
+}
+
+/// Encode the big-endian two's complement representation of a decimal in `bytes`
+///
+/// If `size` is `None`, encodes as a variable length `bytes` using the minimal number
+/// of bytes, otherwise as a fixed of `size` bytes
+fn encode_decimal(bytes: &[u8], size: Option<usize>, out: &mut Vec<u8>) {
+    match size {
+        Some(size) if size >= bytes.len() => {
+            let fill = if bytes[0] & 0x80 != 0 { 0xFF } else { 0x00 };
+            out.resize(out.len() + size - bytes.len(), fill);
+            out.extend_from_slice(bytes)
+        }
+        Some(size) => out.extend_from_slice(&bytes[bytes.len() - size..]),
+        None => {
+            // Strip redundant sign extension bytes
+            let mut start = 0;
+            while start + 1 < bytes.len()
+                && ((bytes[start] == 0x00 && bytes[start + 1] & 0x80 == 0)
+                    || (bytes[start] == 0xFF && bytes[start + 1] & 0x80 != 0))
+            {
+                start += 1;
+            }
+            encode_bytes(&bytes[start..], out)
+        }
+    }
+}
+
+struct Decimal128Encoder<'a> {
+    values: &'a [i128],
+    size: Option<usize>,
+}
+
+impl Encoder for Decimal128Encoder<'_> {
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
+        encode_decimal(&self.values[idx].to_be_bytes(), self.size, out)
+    }
+}
+
+struct Decimal256Encoder<'a> {
+    values: &'a [i256],
+    size: Option<usize>,
+}
+
+impl Encoder for Decimal256Encoder<'_> {
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
+        encode_decimal(&self.values[idx].to_be_bytes(), self.size, out)
+    }
+}
+
+struct DurationEncoder<'a>(&'a [arrow_buffer::IntervalMonthDayNano]);
+
+impl Encoder for DurationEncoder<'_> {
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
+        let v = self.0[idx];
+        let millis = (v.nanoseconds / 1_000_000) as u32;
+        out.extend_from_slice(&(v.months as u32).to_le_bytes());
+        out.extend_from_slice(&(v.days as u32).to_le_bytes());
+        out.extend_from_slice(&millis.to_le_bytes());
+    }
+}
+
+/// The index of the enum symbol of each row
+struct EnumEncoder(Vec<i32>);
+
+impl Encoder for EnumEncoder {
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
+        encode_long(self.0[idx] as i64, out)
+    }
+}
+
+struct ListEncoder<'a> {
+    offsets: &'a OffsetBuffer<i32>,
+    values: Box<dyn Encoder + 'a>,
+}
+
+impl Encoder for ListEncoder<'_> {
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
+        let start = self.offsets[idx] as usize;
+        let end = self.offsets[idx + 1] as usize;
+        if end > start {
+            encode_long((end - start) as i64, out);
+            for i in start..end {
+                self.values.encode(i, out);
+            }
+        }
+        encode_long(0, out);
+    }
+}
+
+struct MapEncoder<'a> {
+    offsets: &'a OffsetBuffer<i32>,
+    keys: &'a GenericByteArray<arrow_array::types::Utf8Type>,
+    values: Box<dyn Encoder + 'a>,
+}
+
+impl Encoder for MapEncoder<'_> {
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
+        let start = self.offsets[idx] as usize;
+        let end = self.offsets[idx + 1] as usize;
+        if end > start {
+            encode_long((end - start) as i64, out);
+            for i in start..end {
+                encode_bytes(self.keys.value(i).as_bytes(), out);
+                self.values.encode(i, out);
+            }
+        }
+        encode_long(0, out);
+    }
+}
+
+struct StructEncoder<'a>(Vec<Box<dyn Encoder + 'a>>);
+
+impl Encoder for StructEncoder<'_> {
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
+        for encoder in &mut self.0 {
+            encoder.encode(idx, out)
+        }
+    }
+}
@@ -0,0 +1,489 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Write Arrow data to Avro [Object Container Files]
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use arrow_array::{Int32Array, RecordBatch};
+//! # use arrow_avro::compression::CompressionCodec;
+//! # use arrow_avro::writer::WriterBuilder;
+//! let a = Int32Array::from(vec![1, 2, 3]);
+//! let batch = RecordBatch::try_from_iter([("a", Arc::new(a) as _)]).unwrap();
+//!
+//! let mut writer = WriterBuilder::new()
+//!     .with_compression(Some(CompressionCodec::Deflate))
+//!     .build(Vec::new(), batch.schema().as_ref())
+//!     .unwrap();
+//! writer.write(&batch).unwrap();
+//! let buf = writer.into_inner().unwrap();
+//! ```
+//!
+//! [Object Container Files]: https://avro.apache.org/docs/1.11.1/specification/#object-container-files
+
+use crate::codec::{AvroField, Codec};
+use crate::compression::{CompressionCodec, CODEC_METADATA_KEY};
+use crate::encoding::{encode_bytes, encode_long};
+use crate::schema::SCHEMA_METADATA_KEY;
+use crate::writer::encoder::make_encoder;
+use arrow_array::{RecordBatch, RecordBatchWriter};
+use arrow_cast::cast;
+use arrow_schema::{ArrowError, Schema};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io::Write;
+
+pub(crate) mod encoder;
+
+/// The default name of the top-level Avro record
+const DEFAULT_RECORD_NAME: &str = "topLevelRecord";
+
+/// A builder for [`Writer`]
+#[derive(Debug, Clone)]
+pub struct WriterBuilder {
+    compression: Option<CompressionCodec>,
+    rows_per_block: usize,
+    record_name: String,
+}
+
+impl Default for WriterBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WriterBuilder {
+    /// Create a new [`WriterBuilder`] with no compression and 1024 rows per block
+    pub fn new() -> Self {
+        Self {
+            compression: None,
+            rows_per_block: 1024,
+            record_name: DEFAULT_RECORD_NAME.to_string(),
+        }
+    }
+
+    /// Sets the [`CompressionCodec`] used to compress each block, defaults to `None`
+    pub fn with_compression(self, compression: Option<CompressionCodec>) -> Self {
+        Self {
+            compression,
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of rows written to each block, defaults to `1024`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rows_per_block` is `0`
+    pub fn with_rows_per_block(self, rows_per_block: usize) -> Self {
+        assert!(rows_per_block > 0, "rows_per_block must be greater than 0");
+        Self {
+            rows_per_block,
+            ..self
+        }
+    }
+
+    /// Sets the name of the top-level Avro record, defaults to `topLevelRecord`
+    pub fn with_record_name(self, record_name: impl Into<String>) -> Self {
+        Self {
+            record_name: record_name.into(),
+            ..self
+        }
+    }
+
+    /// Create a [`Writer`] that writes [`RecordBatch`] with the provided [`Schema`] to `writer`
+    ///
+    /// This will eagerly write the file header to `writer`
+    pub fn build<W: Write>(self, writer: W, schema: &Schema) -> Result<Writer<W>, ArrowError> {
+        let root = AvroField::try_from_arrow(&self.record_name, schema.fields())?;
+        let mut writer = Writer {
+            writer,
+            root,
+            compression: self.compression,
+            rows_per_block: self.rows_per_block,
+            sync: make_sync_marker(),
+            block: Vec::new(),
+            block_rows: 0,
+        };
+        writer.write_header()?;
+        Ok(writer)
+    }
+}
+
+/// Writes arrow [`RecordBatch`] to an Avro [Object Container File]
+///
+/// [Object Container File]: https://avro.apache.org/docs/1.11.1/specification/#object-container-files
+#[derive(Debug)]
+pub struct Writer<W: Write> {
+    writer: W,
+    root: AvroField,
+    compression: Option<CompressionCodec>,
+    rows_per_block: usize,
+    sync: [u8; 16],
+    /// The encoded, uncompressed data of the current block
+    block: Vec<u8>,
+    /// The number of rows in the current block
+    block_rows: usize,
+}
+
+impl<W: Write> Writer<W> {
+    /// Create a new [`Writer`] with the default [`WriterBuilder`] options
+    pub fn try_new(writer: W, schema: &Schema) -> Result<Self, ArrowError> {
+        WriterBuilder::new().build(writer, schema)
+    }
+
+    /// Returns the JSON encoded Avro schema written to the file header
+    pub fn avro_schema(&self) -> String {
+        self.root.avro_schema().to_string()
+    }
+
+    fn write_header(&mut self) -> Result<(), ArrowError> {
+        let mut out = Vec::with_capacity(1024);
+        out.extend_from_slice(b"Obj\x01");
+
+        let schema = self.avro_schema();
+        let codec = self.compression.map(|c| c.name()).unwrap_or("null");
+        encode_long(2, &mut out);
+        encode_bytes(SCHEMA_METADATA_KEY.as_bytes(), &mut out);
+        encode_bytes(schema.as_bytes(), &mut out);
+        encode_bytes(CODEC_METADATA_KEY.as_bytes(), &mut out);
+        encode_bytes(codec.as_bytes(), &mut out);
+        encode_long(0, &mut out);
+
+        out.extend_from_slice(&self.sync);
+        self.writer.write_all(&out)?;
+        Ok(())
+    }
+
+    /// Write a single [`RecordBatch`]
+    ///
+    /// Rows are buffered until `rows_per_block` rows have been written, or [`Self::flush`]
+    /// is called, at which point they are written to the underlying writer as a single block
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<(), ArrowError> {
+        let fields = match self.root.codec() {
+            Codec::Struct(fields) => fields.clone(),
+            _ => unreachable!(),
+        };
+
+        if fields.len() != batch.num_columns() {
+            return Err(ArrowError::SchemaError(format!(
+                "Expected {} columns got {}",
+                fields.len(),
+                batch.num_columns()
+            )));
+        }
+
+        // Cast columns to the arrow type corresponding to the Avro type
+        let columns = fields
+            .iter()
+            .zip(batch.columns())
+            .map(|(field, column)| {
+                let field = field.field();
+                match field.data_type().equals_datatype(column.data_type()) {
+                    true => Ok(column.clone()),
+                    false => cast(column, field.data_type()),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut encoders = fields
+            .iter()
+            .zip(&columns)
+            .map(|(field, column)| make_encoder(field.data_type(), column.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for idx in 0..batch.num_rows() {
+            for encoder in &mut encoders {
+                encoder.encode(idx, &mut self.block);
+            }
+            self.block_rows += 1;
+            if self.block_rows == self.rows_per_block {
+                self.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write any buffered rows to the underlying writer as a single block
+    pub fn flush(&mut self) -> Result<(), ArrowError> {
+        if self.block_rows == 0 {
+            return Ok(());
+        }
+
+        let data = match self.compression {
+            Some(c) => c.compress(&self.block)?,
+            None => std::mem::take(&mut self.block),
+        };
+
+        let mut out = Vec::with_capacity(data.len() + 36);
+        encode_long(self.block_rows as i64, &mut out);
+        encode_long(data.len() as i64, &mut out);
+        out.extend_from_slice(&data);
+        out.extend_from_slice(&self.sync);
+        self.writer.write_all(&out)?;
+
+        self.block.clear();
+        self.block_rows = 0;
+        Ok(())
+    }
+
+    /// Flushes any buffered rows and returns the underlying writer
+    pub fn into_inner(mut self) -> Result<W, ArrowError> {
+        self.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> RecordBatchWriter for Writer<W> {
+    fn write(&mut self, batch: &RecordBatch) -> Result<(), ArrowError> {
+        self.write(batch)
+    }
+
+    fn close(mut self) -> Result<(), ArrowError> {
+        self.flush()?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Generate a pseudo-random 16 byte sync marker
+fn make_sync_marker() -> [u8; 16] {
+    let state = RandomState::new();
+    let mut out = [0; 16];
+    for (idx, chunk) in out.chunks_exact_mut(8).enumerate() {
+        let mut hasher = state.build_hasher();
+        hasher.write_usize(idx);
+        chunk.copy_from_slice(&hasher.finish().to_le_bytes());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::ReaderBuilder;
+    use arrow_array::builder::{Int32Builder, MapBuilder, StringBuilder};
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Int32Type;
+    use arrow_array::*;
+    use arrow_schema::{DataType, Field, Fields};
+    use std::io::Cursor;
+    use std::sync::Arc;
+
+    fn round_trip(batch: &RecordBatch, builder: WriterBuilder) -> Vec<RecordBatch> {
+        let mut writer = builder.build(Vec::new(), batch.schema().as_ref()).unwrap();
+        writer.write(batch).unwrap();
+        writer.write(batch).unwrap();
+        let buf = writer.into_inner().unwrap();
+
+        ReaderBuilder::new()
+            .with_batch_size(batch.num_rows())
+            .build(Cursor::new(buf))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let struct_fields = Fields::from(vec![
+            Field::new("x", DataType::Boolean, false),
+            Field::new("y", DataType::Utf8, true),
+        ]);
+        let list = ListArray::from_iter_primitive::<Int32Type, _, _>([
+            Some(vec![Some(1), Some(2)]),
+            None,
+            Some(vec![]),
+        ]);
+        let structs = StructArray::new(
+            struct_fields.clone(),
+            vec![
+                Arc::new(BooleanArray::from(vec![true, false, true])),
+                Arc::new(StringArray::from(vec![Some("a"), None, Some("c")])),
+            ],
+            None,
+        );
+
+        let batch = RecordBatch::try_from_iter_with_nullable([
+            (
+                "int",
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(-3)])) as _,
+                true,
+            ),
+            (
+                "long",
+                Arc::new(Int64Array::from(vec![i64::MIN, 0, i64::MAX])) as _,
+                false,
+            ),
+            (
+                "double",
+                Arc::new(Float64Array::from(vec![1.5, f64::NAN, -0.0])) as _,
+                false,
+            ),
+            (
+                "string",
+                Arc::new(StringArray::from(vec![Some("hello"), Some(""), None])) as _,
+                true,
+            ),
+            (
+                "binary",
+                Arc::new(BinaryArray::from_iter_values([b"a", b"b", b"c"])) as _,
+                false,
+            ),
+            (
+                "date",
+                Arc::new(Date32Array::from(vec![0, 19000, -1])) as _,
+                false,
+            ),
+            (
+                "timestamp",
+                Arc::new(
+                    TimestampMicrosecondArray::from(vec![0, 1, 1700000000000000])
+                        .with_timezone("+00:00"),
+                ) as _,
+                true,
+            ),
+            (
+                "decimal",
+                Arc::new(
+                    Decimal128Array::from(vec![Some(1234), Some(-1), None])
+                        .with_precision_and_scale(10, 2)
+                        .unwrap(),
+                ) as _,
+                true,
+            ),
+            ("list", Arc::new(list) as _, true),
+            ("struct", Arc::new(structs) as _, false),
+        ])
+        .unwrap();
+
+        let codecs = [
+            None,
+            Some(CompressionCodec::Deflate),
+            Some(CompressionCodec::Snappy),
+            Some(CompressionCodec::ZStandard),
+        ];
+
+        for codec in codecs {
+            for rows_per_block in [1, 2, 1024] {
+                let builder = WriterBuilder::new()
+                    .with_compression(codec)
+                    .with_rows_per_block(rows_per_block);
+                let batches = round_trip(&batch, builder);
+                assert_eq!(batches.len(), 2);
+                for read in batches {
+                    assert_eq!(read.schema(), batch.schema());
+                    for (a, b) in read.columns().iter().zip(batch.columns()) {
+                        // NaN != NaN, so compare the underlying data
+                        assert_eq!(a.to_data(), b.to_data());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_schema() {
+        let mut map = MapBuilder::new(None, StringBuilder::new(), Int32Builder::new());
+        map.keys().append_value("a");
+        map.values().append_value(1);
+        map.append(true).unwrap();
+        map.append(false).unwrap();
+        map.append(false).unwrap();
+        let map = map.finish();
+
+        let enum_field = Field::new(
+            "suit",
+            DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8)),
+            false,
+        )
+        .with_metadata(
+            [(
+                crate::codec::ENUM_SYMBOLS_METADATA_KEY.to_string(),
+                r#"["SPADES","HEARTS"]"#.to_string(),
+            )]
+            .into(),
+        );
+        let suits: DictionaryArray<arrow_array::types::Int8Type> =
+            vec!["HEARTS", "HEARTS", "SPADES"].into_iter().collect();
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("map", map.data_type().clone(), true),
+            enum_field,
+            Field::new("small", DataType::Int16, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(map),
+                Arc::new(suits),
+                Arc::new(Int16Array::from(vec![1, 2, 3])),
+            ],
+        )
+        .unwrap();
+
+        let writer = WriterBuilder::new()
+            .with_record_name("test")
+            .build(Vec::new(), &schema)
+            .unwrap();
+        assert_eq!(
+            writer.avro_schema(),
+            r#"{"fields":[{"name":"map","type":["null",{"type":"map","values":["null","int"]}]},{"name":"suit","type":{"name":"suit","namespace":"test","symbols":["SPADES","HEARTS"],"type":"enum"}},{"name":"small","type":"int"}],"name":"test","type":"record"}"#
+        );
+
+        let batches = round_trip(&batch, WriterBuilder::new());
+        let read = &batches[0];
+
+        let map = read.column(0).as_map();
+        assert_eq!(map.value_offsets(), &[0, 1, 1, 1]);
+        assert_eq!(map.nulls().unwrap().null_count(), 2);
+
+        let suits = read.column(1).as_dictionary::<Int32Type>();
+        assert_eq!(suits.keys().values(), &[1, 1, 0]);
+
+        let small = read.column(2).as_primitive::<Int32Type>();
+        assert_eq!(small.values(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_enum_invalid_key() {
+        let field = Field::new(
+            "suit",
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            false,
+        )
+        .with_metadata(
+            [(
+                crate::codec::ENUM_SYMBOLS_METADATA_KEY.to_string(),
+                r#"["SPADES","HEARTS"]"#.to_string(),
+            )]
+            .into(),
+        );
+        let values = Arc::new(StringArray::from(vec!["HEARTS"]));
+        // Safety: deliberately invalid to test the error
+        let suits = unsafe { Int32DictionaryArray::new_unchecked(vec![0, 3].into(), values) };
+        let schema = Arc::new(Schema::new(vec![field]));
+        let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(suits)]).unwrap();
+
+        let mut writer = WriterBuilder::new().build(Vec::new(), &schema).unwrap();
+        let err = writer.write(&batch).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "Invalid argument error: Dictionary key 3 out of bounds for 1 values"
+        );
+    }
+}
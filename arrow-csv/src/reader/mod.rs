@@ -129,6 +129,7 @@ use arrow_array::builder::{NullBuilder, PrimitiveBuilder};
 use arrow_array::types::*;
 use arrow_array::*;
 use arrow_cast::parse::{parse_decimal, string_to_datetime, Parser};
+use arrow_data::transform::MutableArrayData;
 use arrow_schema::*;
 use chrono::{TimeZone, Utc};
 use csv::StringRecord;
@@ -155,8 +156,15 @@ lazy_static! {
         r"^\d{4}-\d\d-\d\d[T ]\d\d:\d\d:\d\d\.\d{1,6}(?:[^\d].*)?$", //Timestamp(Microsecond)
         r"^\d{4}-\d\d-\d\d[T ]\d\d:\d\d:\d\d\.\d{1,9}(?:[^\d].*)?$", //Timestamp(Nanosecond)
     ]).unwrap();
+
+    /// Matches the remainder of a timestamp after the seconds that specifies a UTC offset
+    static ref OFFSET_REGEX: Regex =
+        Regex::new(r"^(?:\.\d+)?\s?(?:[zZ]|[+-]\d\d(?::?\d\d)?)$").unwrap();
 }
 
+/// The maximum number of conflicting values reported by [`ColumnInference::conflicts`]
+const MAX_CONFLICTS: usize = 5;
+
 /// A wrapper over `Option<Regex>` to check if the value is `NULL`.
 #[derive(Debug, Clone, Default)]
 struct NullRegex(Option<Regex>);
@@ -173,6 +181,28 @@ impl NullRegex {
     }
 }
 
+/// Additional case-insensitive spellings of boolean values, on top of `true` and `false`
+#[derive(Debug, Clone, Default)]
+struct BooleanValues {
+    true_values: Vec<String>,
+    false_values: Vec<String>,
+}
+
+impl BooleanValues {
+    /// Parses `s` as a boolean, returning `None` if it is not a recognised spelling
+    fn parse(&self, s: &str) -> Option<bool> {
+        parse_bool(s).or_else(|| {
+            if self.true_values.iter().any(|v| v.eq_ignore_ascii_case(s)) {
+                Some(true)
+            } else if self.false_values.iter().any(|v| v.eq_ignore_ascii_case(s)) {
+                Some(false)
+            } else {
+                None
+            }
+        })
+    }
+}
+
 #[derive(Default, Copy, Clone)]
 struct InferredDataType {
     /// Packed booleans indicating type
@@ -186,47 +216,213 @@ struct InferredDataType {
     /// 6 - Timestamp(Microsecond)
     /// 7 - Timestamp(Nanosecond)
     /// 8 - Utf8
+    /// 9 - Timestamp with a UTC offset, see [`Format::with_timezone_inference`]
+    /// 10 - Timestamp without a UTC offset, see [`Format::with_timezone_inference`]
+    /// 11 - Decimal128, see [`Format::with_decimal_inference`]
     packed: u16,
+    /// The maximum number of integer digits, used to derive the precision of a Decimal128
+    integer_digits: usize,
+    /// The maximum number of fractional digits, used to derive the scale of a Decimal128
+    scale: usize,
 }
 
 impl InferredDataType {
+    const OFFSET: u16 = 1 << 9;
+    const NO_OFFSET: u16 = 1 << 10;
+    const DECIMAL: u16 = 1 << 11;
+
     /// Returns the inferred data type
     fn get(&self) -> DataType {
-        match self.packed {
+        let offsets = self.packed & (Self::OFFSET | Self::NO_OFFSET);
+        match self.packed & !offsets {
             0 => DataType::Null,
             1 => DataType::Boolean,
             2 => DataType::Int64,
             4 | 6 => DataType::Float64, // Promote Int64 to Float64
-            b if b != 0 && (b & !0b11111000) == 0 => match b.leading_zeros() {
-                // Promote to highest precision temporal type
-                8 => DataType::Timestamp(TimeUnit::Nanosecond, None),
-                9 => DataType::Timestamp(TimeUnit::Microsecond, None),
-                10 => DataType::Timestamp(TimeUnit::Millisecond, None),
-                11 => DataType::Timestamp(TimeUnit::Second, None),
-                12 => DataType::Date32,
-                _ => unreachable!(),
-            },
+            b if b & Self::DECIMAL != 0 && (b & !(Self::DECIMAL | 0b110)) == 0 => {
+                match b & 0b100 {
+                    0 => self.decimal(), // Promote Int64 to Decimal128
+                    _ => DataType::Float64,
+                }
+            }
+            b if b != 0 && (b & !0b11111000) == 0 => {
+                let tz = match offsets {
+                    0 | Self::NO_OFFSET => None,
+                    Self::OFFSET => Some("+00:00".into()),
+                    _ => return DataType::Utf8, // Mix of local and UTC timestamps
+                };
+                match b.leading_zeros() {
+                    // Promote to highest precision temporal type
+                    8 => DataType::Timestamp(TimeUnit::Nanosecond, tz),
+                    9 => DataType::Timestamp(TimeUnit::Microsecond, tz),
+                    10 => DataType::Timestamp(TimeUnit::Millisecond, tz),
+                    11 => DataType::Timestamp(TimeUnit::Second, tz),
+                    12 => DataType::Date32,
+                    _ => unreachable!(),
+                }
+            }
             _ => DataType::Utf8,
         }
     }
 
-    /// Updates the [`InferredDataType`] with the given string
-    fn update(&mut self, string: &str) {
-        self.packed |= if string.starts_with('"') {
+    /// Returns a Decimal128 able to represent all the values seen, falling back
+    /// to Float64 if this would exceed [`DECIMAL128_MAX_PRECISION`]
+    fn decimal(&self) -> DataType {
+        let precision = (self.integer_digits + self.scale).max(1);
+        match precision <= DECIMAL128_MAX_PRECISION as usize {
+            true => DataType::Decimal128(precision as u8, self.scale as i8),
+            false => DataType::Float64,
+        }
+    }
+
+    /// Infers the [`InferredDataType`] of a single value
+    fn infer(string: &str, format: &Format) -> Self {
+        let mut inferred = Self::default();
+        inferred.packed = if string.starts_with('"') {
             1 << 8 // Utf8
+        } else if format.boolean_values.parse(string).is_some() {
+            1 // Boolean
         } else if let Some(m) = REGEX_SET.matches(string).into_iter().next() {
-            if m == 1 && string.len() >= 19 && string.parse::<i64>().is_err() {
+            match m {
                 // if overflow i64, fallback to utf8
-                1 << 8
-            } else {
-                1 << m
+                1 if string.len() >= 19 && string.parse::<i64>().is_err() => 1 << 8,
+                1 => {
+                    inferred.integer_digits = integer_digits(string);
+                    1 << 1
+                }
+                2 if format.infer_decimal && !string.contains(['e', 'E']) => {
+                    let (integer, fraction) = string.split_once('.').unwrap();
+                    inferred.integer_digits = integer_digits(integer);
+                    inferred.scale = fraction.len();
+                    Self::DECIMAL
+                }
+                4..=7 if format.infer_timezone => match OFFSET_REGEX.is_match(&string[19..]) {
+                    true => 1 << m | Self::OFFSET,
+                    false => 1 << m | Self::NO_OFFSET,
+                },
+                _ => 1 << m,
             }
         } else {
             1 << 8 // Utf8
+        };
+        inferred
+    }
+
+    /// Updates the [`InferredDataType`] with the given string
+    fn update(&mut self, string: &str, format: &Format) {
+        self.merge(Self::infer(string, format))
+    }
+
+    /// Merges the [`InferredDataType`] of another set of values
+    fn merge(&mut self, other: Self) {
+        self.packed |= other.packed;
+        self.integer_digits = self.integer_digits.max(other.integer_digits);
+        self.scale = self.scale.max(other.scale);
+    }
+}
+
+/// Returns the number of significant digits in the integer `s`
+fn integer_digits(s: &str) -> usize {
+    s.trim_start_matches('-').trim_start_matches('0').len()
+}
+
+/// Tracks the values of a column for [`Format::infer_schema_with_report`]
+#[derive(Default)]
+struct ColumnStatistics {
+    inferred: InferredDataType,
+    null_count: usize,
+    value_count: usize,
+    /// The packed [`InferredDataType`], count and a sample of each kind of value seen
+    kinds: Vec<(u16, usize, Vec<String>)>,
+}
+
+impl ColumnStatistics {
+    fn update(&mut self, string: &str, format: &Format) {
+        let inferred = InferredDataType::infer(string, format);
+        self.inferred.merge(inferred);
+        self.value_count += 1;
+
+        match self
+            .kinds
+            .iter_mut()
+            .find(|(k, _, _)| *k == inferred.packed)
+        {
+            Some((_, count, samples)) => {
+                *count += 1;
+                if samples.len() < MAX_CONFLICTS {
+                    samples.push(string.to_string());
+                }
+            }
+            None => self
+                .kinds
+                .push((inferred.packed, 1, vec![string.to_string()])),
+        }
+    }
+
+    fn finish(self, name: &str) -> ColumnInference {
+        // Values conflict if they cannot be represented by the most common kind of value
+        let dominant = self.kinds.iter().max_by_key(|(_, count, _)| *count);
+        let conflicting = |packed: u16| match dominant {
+            Some((d, _, _)) => {
+                let with = InferredDataType {
+                    packed: d | packed,
+                    ..Default::default()
+                };
+                let without = InferredDataType {
+                    packed: *d,
+                    ..Default::default()
+                };
+                with.get() == DataType::Utf8 && without.get() != DataType::Utf8
+            }
+            None => false,
+        };
+
+        let mut conflict_count = 0;
+        let mut conflicts = vec![];
+        for (packed, count, samples) in &self.kinds {
+            if conflicting(*packed) {
+                conflict_count += count;
+                conflicts.extend(samples.iter().cloned());
+            }
+        }
+        conflicts.truncate(MAX_CONFLICTS);
+
+        let confidence = match self.value_count {
+            0 => 1.,
+            n => (n - conflict_count) as f64 / n as f64,
+        };
+
+        ColumnInference {
+            name: name.to_string(),
+            data_type: self.inferred.get(),
+            null_count: self.null_count,
+            value_count: self.value_count,
+            confidence,
+            conflicts,
         }
     }
 }
 
+/// The result of inferring the type of a column with [`Format::infer_schema_with_report`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnInference {
+    /// The name of the column
+    pub name: String,
+    /// The inferred data type of the column
+    pub data_type: DataType,
+    /// The number of null values read
+    pub null_count: usize,
+    /// The number of non-null values read
+    pub value_count: usize,
+    /// The fraction of non-null values consistent with the most common kind of value
+    ///
+    /// A confidence below `1.0` indicates the column was inferred as [`DataType::Utf8`]
+    /// because of the values in [`Self::conflicts`]
+    pub confidence: f64,
+    /// A sample of up to five values that conflict with the most common kind of value
+    pub conflicts: Vec<String>,
+}
+
 /// The format specification for the CSV file
 #[derive(Debug, Clone, Default)]
 pub struct Format {
@@ -237,7 +433,10 @@ pub struct Format {
     terminator: Option<u8>,
     comment: Option<u8>,
     null_regex: NullRegex,
+    boolean_values: BooleanValues,
     truncated_rows: bool,
+    infer_decimal: bool,
+    infer_timezone: bool,
 }
 
 impl Format {
@@ -287,6 +486,54 @@ impl Format {
         self
     }
 
+    /// Provide a list of tokens to treat as null values, in addition to the empty string
+    ///
+    /// This overrides any regex provided to [`Self::with_null_regex`]
+    pub fn with_null_values<I, S>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.null_regex = NullRegex(Some(null_values_regex(values)));
+        self
+    }
+
+    /// Provide additional case-insensitive spellings of `true` and `false`, such as
+    /// `yes` / `no` or `Y` / `N`, used both when inferring and parsing boolean columns
+    pub fn with_boolean_values<T, F, S>(mut self, true_values: T, false_values: F) -> Self
+    where
+        T: IntoIterator<Item = S>,
+        F: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.boolean_values = BooleanValues {
+            true_values: true_values.into_iter().map(Into::into).collect(),
+            false_values: false_values.into_iter().map(Into::into).collect(),
+        };
+        self
+    }
+
+    /// Whether to infer [`DataType::Decimal128`] for numbers with a decimal point and
+    /// no exponent, defaults to `false`
+    ///
+    /// The precision and scale are derived from the largest number of integer and fractional
+    /// digits seen. Falls back to [`DataType::Float64`] if this would exceed
+    /// [`DECIMAL128_MAX_PRECISION`], or if any number has an exponent
+    pub fn with_decimal_inference(mut self, infer: bool) -> Self {
+        self.infer_decimal = infer;
+        self
+    }
+
+    /// Whether to infer a timezone for timestamps with a UTC offset, defaults to `false`
+    ///
+    /// When enabled, columns where every timestamp has a UTC offset, e.g. `2021-01-01T00:00:00Z`
+    /// or `2021-01-01 00:00:00+01:00`, are inferred as timestamps with a `+00:00` timezone.
+    /// Columns mixing timestamps with and without a UTC offset are inferred as [`DataType::Utf8`]
+    pub fn with_timezone_inference(mut self, infer: bool) -> Self {
+        self.infer_timezone = infer;
+        self
+    }
+
     /// Whether to allow truncated rows when parsing.
     ///
     /// By default this is set to `false` and will error if the CSV rows have different lengths.
@@ -310,18 +557,7 @@ impl Format {
         max_records: Option<usize>,
     ) -> Result<(Schema, usize), ArrowError> {
         let mut csv_reader = self.build_reader(reader);
-
-        // get or create header names
-        // when has_header is false, creates default column names with column_ prefix
-        let headers: Vec<String> = if self.header {
-            let headers = &csv_reader.headers().map_err(map_csv_error)?.clone();
-            headers.iter().map(|s| s.to_string()).collect()
-        } else {
-            let first_record_count = &csv_reader.headers().map_err(map_csv_error)?.len();
-            (0..*first_record_count)
-                .map(|i| format!("column_{}", i + 1))
-                .collect()
-        };
+        let headers = self.read_headers(&mut csv_reader)?;
 
         let header_length = headers.len();
         // keep track of inferred field types
@@ -342,7 +578,7 @@ impl Format {
             for (i, column_type) in column_types.iter_mut().enumerate().take(header_length) {
                 if let Some(string) = record.get(i) {
                     if !self.null_regex.is_null(string) {
-                        column_type.update(string)
+                        column_type.update(string, self)
                     }
                 }
             }
@@ -358,6 +594,72 @@ impl Format {
         Ok((Schema::new(fields), records_count))
     }
 
+    /// Infer schema of CSV records from the provided `reader`, as [`Self::infer_schema`],
+    /// additionally returning a [`ColumnInference`] for each column describing the values read
+    ///
+    /// This can be used to identify columns that were inferred as [`DataType::Utf8`] because
+    /// of a small number of conflicting values, e.g. an unrecognised null token
+    pub fn infer_schema_with_report<R: Read>(
+        &self,
+        reader: R,
+        max_records: Option<usize>,
+    ) -> Result<(Schema, Vec<ColumnInference>), ArrowError> {
+        let mut csv_reader = self.build_reader(reader);
+        let headers = self.read_headers(&mut csv_reader)?;
+
+        let mut columns: Vec<ColumnStatistics> = Vec::with_capacity(headers.len());
+        columns.resize_with(headers.len(), Default::default);
+
+        let mut records_count = 0;
+
+        let mut record = StringRecord::new();
+        let max_records = max_records.unwrap_or(usize::MAX);
+        while records_count < max_records {
+            if !csv_reader.read_record(&mut record).map_err(map_csv_error)? {
+                break;
+            }
+            records_count += 1;
+
+            for (i, column) in columns.iter_mut().enumerate() {
+                match record.get(i) {
+                    Some(string) if !self.null_regex.is_null(string) => column.update(string, self),
+                    _ => column.null_count += 1,
+                }
+            }
+        }
+
+        let columns: Vec<_> = columns
+            .into_iter()
+            .zip(&headers)
+            .map(|(column, name)| column.finish(name))
+            .collect();
+
+        let fields: Fields = columns
+            .iter()
+            .map(|c| Field::new(&c.name, c.data_type.clone(), true))
+            .collect();
+
+        Ok((Schema::new(fields), columns))
+    }
+
+    /// Get or create header names
+    ///
+    /// When `header` is false, creates default column names with column_ prefix
+    fn read_headers<R: Read>(
+        &self,
+        csv_reader: &mut csv::Reader<R>,
+    ) -> Result<Vec<String>, ArrowError> {
+        Ok(if self.header {
+            let headers = csv_reader.headers().map_err(map_csv_error)?;
+            headers.iter().map(|s| s.to_string()).collect()
+        } else {
+            let first_record_count = csv_reader.headers().map_err(map_csv_error)?.len();
+            (0..first_record_count)
+                .map(|i| format!("column_{}", i + 1))
+                .collect()
+        })
+    }
+
     /// Build a [`csv::Reader`] for this [`Format`]
     fn build_reader<R: Read>(&self, reader: R) -> csv::Reader<R> {
         let mut builder = csv::ReaderBuilder::new();
@@ -399,6 +701,21 @@ impl Format {
     }
 }
 
+/// Returns a [`Regex`] matching the empty string or any of `values`
+fn null_values_regex<I, S>(values: I) -> Regex
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut pattern = String::from("^(?:");
+    for value in values {
+        pattern.push('|');
+        pattern.push_str(&regex::escape(value.as_ref()));
+    }
+    pattern.push_str(")$");
+    Regex::new(&pattern).unwrap()
+}
+
 /// Infer the schema of a CSV file by reading through the first n records of the file,
 /// with `max_read_records` controlling the maximum number of records to read.
 ///
@@ -483,6 +800,31 @@ pub fn infer_schema_from_files(
 // optional bounds of the reader, of the form (min line, max line).
 type Bounds = Option<(usize, usize)>;
 
+/// How values that cannot be parsed are handled, see [`ReaderBuilder::with_error_mode`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorMode {
+    /// Return an error, aborting the decode
+    #[default]
+    Fail,
+    /// Replace values that cannot be parsed with nulls, requires the columns to be nullable
+    Null,
+    /// Omit rows containing values that cannot be parsed from the output
+    Skip,
+}
+
+/// A value that could not be parsed, see [`ErrorMode`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// The line number of the record, as reported by parse errors
+    pub line: usize,
+    /// The index of the column in the schema
+    pub column: usize,
+    /// The raw text of the field
+    pub value: String,
+    /// The reason the value could not be parsed
+    pub reason: String,
+}
+
 /// CSV file reader using [`std::io::BufReader`]
 pub type Reader<R> = BufReader<StdBufReader<R>>;
 
@@ -522,6 +864,13 @@ impl<R: Read> Reader<R> {
 }
 
 impl<R: BufRead> BufReader<R> {
+    /// Returns the values that could not be parsed since the last call to this method
+    ///
+    /// See [`Decoder::take_errors`]
+    pub fn take_errors(&mut self) -> Vec<FieldError> {
+        self.decoder.take_errors()
+    }
+
     fn read(&mut self) -> Result<Option<RecordBatch>, ArrowError> {
         loop {
             let buf = self.reader.fill_buf()?;
@@ -619,6 +968,15 @@ pub struct Decoder {
 
     /// Check if the string matches this pattern for `NULL`.
     null_regex: NullRegex,
+
+    /// Additional spellings of boolean values
+    boolean_values: BooleanValues,
+
+    /// How values that cannot be parsed are handled
+    error_mode: ErrorMode,
+
+    /// Values that could not be parsed since the last call to [`Self::take_errors`]
+    errors: Vec<FieldError>,
 }
 
 impl Decoder {
@@ -658,238 +1016,353 @@ impl Decoder {
         }
 
         let rows = self.record_decoder.flush()?;
-        let batch = parse(
-            &rows,
-            self.schema.fields(),
-            Some(self.schema.metadata.clone()),
-            self.projection.as_ref(),
-            self.line_number,
-            &self.null_regex,
-        )?;
+        let options = ParseOptions {
+            fields: self.schema.fields(),
+            metadata: &self.schema.metadata,
+            projection: self.projection.as_ref(),
+            null_regex: &self.null_regex,
+            boolean_values: &self.boolean_values,
+            error_mode: self.error_mode,
+        };
+        let batch = parse(&rows, self.line_number, &options, &mut self.errors)?;
         self.line_number += rows.len();
         Ok(Some(batch))
     }
 
+    /// Returns the values that could not be parsed since the last call to this method,
+    /// ordered by line and column
+    ///
+    /// Always empty for [`ErrorMode::Fail`]
+    pub fn take_errors(&mut self) -> Vec<FieldError> {
+        std::mem::take(&mut self.errors)
+    }
+
     /// Returns the number of records that can be read before requiring a call to [`Self::flush`]
     pub fn capacity(&self) -> usize {
         self.batch_size - self.record_decoder.len()
     }
 }
 
+/// Options for [`parse`], borrowed from the [`Decoder`]
+struct ParseOptions<'a> {
+    /// The fields of the schema
+    fields: &'a Fields,
+    /// The metadata of the output schema
+    metadata: &'a std::collections::HashMap<String, String>,
+    /// Optional projection for which columns to load (zero-based column indices)
+    projection: Option<&'a Vec<usize>>,
+    /// Check if the string matches this pattern for `NULL`
+    null_regex: &'a NullRegex,
+    /// Additional spellings of boolean values
+    boolean_values: &'a BooleanValues,
+    /// How values that cannot be parsed are handled
+    error_mode: ErrorMode,
+}
+
+/// Records values of a column that cannot be parsed according to an [`ErrorMode`]
+struct ErrorHandler<'a> {
+    mode: ErrorMode,
+    column: usize,
+    line_number: usize,
+    errors: &'a mut Vec<FieldError>,
+}
+
+impl ErrorHandler<'_> {
+    /// Returns `e` for [`ErrorMode::Fail`], otherwise records it against the value `s`
+    /// of `row`, which is then read as null
+    fn handle(&mut self, row: usize, s: &str, e: ArrowError) -> Result<(), ArrowError> {
+        if self.mode == ErrorMode::Fail {
+            return Err(e);
+        }
+        self.errors.push(FieldError {
+            line: self.line_number + row,
+            column: self.column,
+            value: s.to_string(),
+            reason: e.to_string(),
+        });
+        Ok(())
+    }
+}
+
 /// Parses a slice of [`StringRecords`] into a [RecordBatch]
+///
+/// If the error mode is not [`ErrorMode::Fail`], values that cannot be parsed are recorded
+/// in `errors`, and either replaced with nulls or their rows omitted from the output
 fn parse(
     rows: &StringRecords<'_>,
-    fields: &Fields,
-    metadata: Option<std::collections::HashMap<String, String>>,
-    projection: Option<&Vec<usize>>,
     line_number: usize,
-    null_regex: &NullRegex,
+    options: &ParseOptions<'_>,
+    errors: &mut Vec<FieldError>,
 ) -> Result<RecordBatch, ArrowError> {
-    let projection: Vec<usize> = match projection {
+    let fields = options.fields;
+    let projection: Vec<usize> = match options.projection {
         Some(v) => v.clone(),
         None => fields.iter().enumerate().map(|(i, _)| i).collect(),
     };
 
-    let arrays: Result<Vec<ArrayRef>, _> = projection
-        .iter()
-        .map(|i| {
-            let i = *i;
-            let field = &fields[i];
-            match field.data_type() {
-                DataType::Boolean => build_boolean_array(line_number, rows, i, null_regex),
-                DataType::Decimal128(precision, scale) => build_decimal_array::<Decimal128Type>(
-                    line_number,
-                    rows,
-                    i,
-                    *precision,
-                    *scale,
-                    null_regex,
-                ),
-                DataType::Decimal256(precision, scale) => build_decimal_array::<Decimal256Type>(
-                    line_number,
-                    rows,
-                    i,
-                    *precision,
-                    *scale,
-                    null_regex,
-                ),
-                DataType::Int8 => {
-                    build_primitive_array::<Int8Type>(line_number, rows, i, null_regex)
-                }
-                DataType::Int16 => {
-                    build_primitive_array::<Int16Type>(line_number, rows, i, null_regex)
-                }
-                DataType::Int32 => {
-                    build_primitive_array::<Int32Type>(line_number, rows, i, null_regex)
-                }
-                DataType::Int64 => {
-                    build_primitive_array::<Int64Type>(line_number, rows, i, null_regex)
-                }
-                DataType::UInt8 => {
-                    build_primitive_array::<UInt8Type>(line_number, rows, i, null_regex)
-                }
-                DataType::UInt16 => {
-                    build_primitive_array::<UInt16Type>(line_number, rows, i, null_regex)
-                }
-                DataType::UInt32 => {
-                    build_primitive_array::<UInt32Type>(line_number, rows, i, null_regex)
-                }
-                DataType::UInt64 => {
-                    build_primitive_array::<UInt64Type>(line_number, rows, i, null_regex)
-                }
-                DataType::Float32 => {
-                    build_primitive_array::<Float32Type>(line_number, rows, i, null_regex)
-                }
-                DataType::Float64 => {
-                    build_primitive_array::<Float64Type>(line_number, rows, i, null_regex)
-                }
-                DataType::Date32 => {
-                    build_primitive_array::<Date32Type>(line_number, rows, i, null_regex)
-                }
-                DataType::Date64 => {
-                    build_primitive_array::<Date64Type>(line_number, rows, i, null_regex)
-                }
-                DataType::Time32(TimeUnit::Second) => {
-                    build_primitive_array::<Time32SecondType>(line_number, rows, i, null_regex)
-                }
-                DataType::Time32(TimeUnit::Millisecond) => {
-                    build_primitive_array::<Time32MillisecondType>(line_number, rows, i, null_regex)
-                }
-                DataType::Time64(TimeUnit::Microsecond) => {
-                    build_primitive_array::<Time64MicrosecondType>(line_number, rows, i, null_regex)
-                }
-                DataType::Time64(TimeUnit::Nanosecond) => {
-                    build_primitive_array::<Time64NanosecondType>(line_number, rows, i, null_regex)
-                }
-                DataType::Timestamp(TimeUnit::Second, tz) => {
-                    build_timestamp_array::<TimestampSecondType>(
-                        line_number,
-                        rows,
-                        i,
-                        tz.as_deref(),
-                        null_regex,
-                    )
-                }
-                DataType::Timestamp(TimeUnit::Millisecond, tz) => {
-                    build_timestamp_array::<TimestampMillisecondType>(
-                        line_number,
-                        rows,
-                        i,
-                        tz.as_deref(),
-                        null_regex,
-                    )
-                }
-                DataType::Timestamp(TimeUnit::Microsecond, tz) => {
-                    build_timestamp_array::<TimestampMicrosecondType>(
-                        line_number,
-                        rows,
-                        i,
-                        tz.as_deref(),
-                        null_regex,
-                    )
-                }
-                DataType::Timestamp(TimeUnit::Nanosecond, tz) => {
-                    build_timestamp_array::<TimestampNanosecondType>(
-                        line_number,
-                        rows,
-                        i,
-                        tz.as_deref(),
-                        null_regex,
-                    )
+    let errors_start = errors.len();
+    let mut arrays = Vec::with_capacity(projection.len());
+    for i in projection.iter().copied() {
+        let mut handler = ErrorHandler {
+            mode: options.error_mode,
+            column: i,
+            line_number,
+            errors,
+        };
+        arrays.push(build_array(
+            rows,
+            &fields[i],
+            i,
+            line_number,
+            options,
+            &mut handler,
+        )?);
+    }
+
+    let new_errors = &mut errors[errors_start..];
+    new_errors.sort_by_key(|e| (e.line, e.column));
+
+    let mut num_rows = rows.len();
+    if options.error_mode == ErrorMode::Skip && !new_errors.is_empty() {
+        let mut invalid = new_errors.iter().map(|e| e.line - line_number).peekable();
+        let valid: Vec<_> = (0..rows.len())
+            .filter(|row| {
+                let mut skip = false;
+                while invalid.next_if(|x| x == row).is_some() {
+                    skip = true;
                 }
-                DataType::Null => Ok(Arc::new({
-                    let mut builder = NullBuilder::new();
-                    builder.append_nulls(rows.len());
-                    builder.finish()
-                }) as ArrayRef),
-                DataType::Utf8 => Ok(Arc::new(
+                !skip
+            })
+            .collect();
+
+        arrays = arrays
+            .iter()
+            .map(|array| select_rows(array.as_ref(), &valid))
+            .collect();
+        num_rows = valid.len();
+    }
+
+    let projected_fields: Fields = projection.iter().map(|i| fields[*i].clone()).collect();
+
+    let projected_schema = Arc::new(Schema::new_with_metadata(
+        projected_fields,
+        options.metadata.clone(),
+    ));
+
+    RecordBatch::try_new_with_options(
+        projected_schema,
+        arrays,
+        &RecordBatchOptions::new()
+            .with_match_field_names(true)
+            .with_row_count(Some(num_rows)),
+    )
+}
+
+/// Returns the values of `array` at the ascending indices `rows`
+fn select_rows(array: &dyn Array, rows: &[usize]) -> ArrayRef {
+    let data = array.to_data();
+    let mut mutable = MutableArrayData::new(vec![&data], false, rows.len());
+    for row in rows {
+        mutable.extend(0, *row, row + 1);
+    }
+    make_array(mutable.freeze())
+}
+
+/// Parses the column `i` of `rows` into an array of `field`
+fn build_array(
+    rows: &StringRecords<'_>,
+    field: &Field,
+    i: usize,
+    line_number: usize,
+    options: &ParseOptions<'_>,
+    errors: &mut ErrorHandler<'_>,
+) -> Result<ArrayRef, ArrowError> {
+    let null_regex = options.null_regex;
+    match field.data_type() {
+        DataType::Boolean => build_boolean_array(
+            line_number,
+            rows,
+            i,
+            null_regex,
+            options.boolean_values,
+            errors,
+        ),
+        DataType::Decimal128(precision, scale) => build_decimal_array::<Decimal128Type>(
+            line_number,
+            rows,
+            i,
+            *precision,
+            *scale,
+            null_regex,
+            errors,
+        ),
+        DataType::Decimal256(precision, scale) => build_decimal_array::<Decimal256Type>(
+            line_number,
+            rows,
+            i,
+            *precision,
+            *scale,
+            null_regex,
+            errors,
+        ),
+        DataType::Int8 => {
+            build_primitive_array::<Int8Type>(line_number, rows, i, null_regex, errors)
+        }
+        DataType::Int16 => {
+            build_primitive_array::<Int16Type>(line_number, rows, i, null_regex, errors)
+        }
+        DataType::Int32 => {
+            build_primitive_array::<Int32Type>(line_number, rows, i, null_regex, errors)
+        }
+        DataType::Int64 => {
+            build_primitive_array::<Int64Type>(line_number, rows, i, null_regex, errors)
+        }
+        DataType::UInt8 => {
+            build_primitive_array::<UInt8Type>(line_number, rows, i, null_regex, errors)
+        }
+        DataType::UInt16 => {
+            build_primitive_array::<UInt16Type>(line_number, rows, i, null_regex, errors)
+        }
+        DataType::UInt32 => {
+            build_primitive_array::<UInt32Type>(line_number, rows, i, null_regex, errors)
+        }
+        DataType::UInt64 => {
+            build_primitive_array::<UInt64Type>(line_number, rows, i, null_regex, errors)
+        }
+        DataType::Float32 => {
+            build_primitive_array::<Float32Type>(line_number, rows, i, null_regex, errors)
+        }
+        DataType::Float64 => {
+            build_primitive_array::<Float64Type>(line_number, rows, i, null_regex, errors)
+        }
+        DataType::Date32 => {
+            build_primitive_array::<Date32Type>(line_number, rows, i, null_regex, errors)
+        }
+        DataType::Date64 => {
+            build_primitive_array::<Date64Type>(line_number, rows, i, null_regex, errors)
+        }
+        DataType::Time32(TimeUnit::Second) => {
+            build_primitive_array::<Time32SecondType>(line_number, rows, i, null_regex, errors)
+        }
+        DataType::Time32(TimeUnit::Millisecond) => {
+            build_primitive_array::<Time32MillisecondType>(line_number, rows, i, null_regex, errors)
+        }
+        DataType::Time64(TimeUnit::Microsecond) => {
+            build_primitive_array::<Time64MicrosecondType>(line_number, rows, i, null_regex, errors)
+        }
+        DataType::Time64(TimeUnit::Nanosecond) => {
+            build_primitive_array::<Time64NanosecondType>(line_number, rows, i, null_regex, errors)
+        }
+        DataType::Timestamp(TimeUnit::Second, tz) => build_timestamp_array::<TimestampSecondType>(
+            line_number,
+            rows,
+            i,
+            tz.as_deref(),
+            null_regex,
+            errors,
+        ),
+        DataType::Timestamp(TimeUnit::Millisecond, tz) => {
+            build_timestamp_array::<TimestampMillisecondType>(
+                line_number,
+                rows,
+                i,
+                tz.as_deref(),
+                null_regex,
+                errors,
+            )
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, tz) => {
+            build_timestamp_array::<TimestampMicrosecondType>(
+                line_number,
+                rows,
+                i,
+                tz.as_deref(),
+                null_regex,
+                errors,
+            )
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, tz) => {
+            build_timestamp_array::<TimestampNanosecondType>(
+                line_number,
+                rows,
+                i,
+                tz.as_deref(),
+                null_regex,
+                errors,
+            )
+        }
+        DataType::Null => Ok(Arc::new({
+            let mut builder = NullBuilder::new();
+            builder.append_nulls(rows.len());
+            builder.finish()
+        }) as ArrayRef),
+        DataType::Utf8 => Ok(Arc::new(
+            rows.iter()
+                .map(|row| {
+                    let s = row.get(i);
+                    (!null_regex.is_null(s)).then_some(s)
+                })
+                .collect::<StringArray>(),
+        ) as ArrayRef),
+        DataType::Utf8View => Ok(Arc::new(
+            rows.iter()
+                .map(|row| {
+                    let s = row.get(i);
+                    (!null_regex.is_null(s)).then_some(s)
+                })
+                .collect::<StringViewArray>(),
+        ) as ArrayRef),
+        DataType::Dictionary(key_type, value_type) if value_type.as_ref() == &DataType::Utf8 => {
+            match key_type.as_ref() {
+                DataType::Int8 => Ok(Arc::new(
                     rows.iter()
-                        .map(|row| {
-                            let s = row.get(i);
-                            (!null_regex.is_null(s)).then_some(s)
-                        })
-                        .collect::<StringArray>(),
+                        .map(|row| row.get(i))
+                        .collect::<DictionaryArray<Int8Type>>(),
                 ) as ArrayRef),
-                DataType::Utf8View => Ok(Arc::new(
+                DataType::Int16 => Ok(Arc::new(
                     rows.iter()
-                        .map(|row| {
-                            let s = row.get(i);
-                            (!null_regex.is_null(s)).then_some(s)
-                        })
-                        .collect::<StringViewArray>(),
+                        .map(|row| row.get(i))
+                        .collect::<DictionaryArray<Int16Type>>(),
                 ) as ArrayRef),
-                DataType::Dictionary(key_type, value_type)
-                    if value_type.as_ref() == &DataType::Utf8 =>
-                {
-                    match key_type.as_ref() {
-                        DataType::Int8 => Ok(Arc::new(
-                            rows.iter()
-                                .map(|row| row.get(i))
-                                .collect::<DictionaryArray<Int8Type>>(),
-                        ) as ArrayRef),
-                        DataType::Int16 => Ok(Arc::new(
-                            rows.iter()
-                                .map(|row| row.get(i))
-                                .collect::<DictionaryArray<Int16Type>>(),
-                        ) as ArrayRef),
-                        DataType::Int32 => Ok(Arc::new(
-                            rows.iter()
-                                .map(|row| row.get(i))
-                                .collect::<DictionaryArray<Int32Type>>(),
-                        ) as ArrayRef),
-                        DataType::Int64 => Ok(Arc::new(
-                            rows.iter()
-                                .map(|row| row.get(i))
-                                .collect::<DictionaryArray<Int64Type>>(),
-                        ) as ArrayRef),
-                        DataType::UInt8 => Ok(Arc::new(
-                            rows.iter()
-                                .map(|row| row.get(i))
-                                .collect::<DictionaryArray<UInt8Type>>(),
-                        ) as ArrayRef),
-                        DataType::UInt16 => Ok(Arc::new(
-                            rows.iter()
-                                .map(|row| row.get(i))
-                                .collect::<DictionaryArray<UInt16Type>>(),
-                        ) as ArrayRef),
-                        DataType::UInt32 => Ok(Arc::new(
-                            rows.iter()
-                                .map(|row| row.get(i))
-                                .collect::<DictionaryArray<UInt32Type>>(),
-                        ) as ArrayRef),
-                        DataType::UInt64 => Ok(Arc::new(
-                            rows.iter()
-                                .map(|row| row.get(i))
-                                .collect::<DictionaryArray<UInt64Type>>(),
-                        ) as ArrayRef),
-                        _ => Err(ArrowError::ParseError(format!(
-                            "Unsupported dictionary key type {key_type:?}"
-                        ))),
-                    }
-                }
-                other => Err(ArrowError::ParseError(format!(
-                    "Unsupported data type {other:?}"
+                DataType::Int32 => Ok(Arc::new(
+                    rows.iter()
+                        .map(|row| row.get(i))
+                        .collect::<DictionaryArray<Int32Type>>(),
+                ) as ArrayRef),
+                DataType::Int64 => Ok(Arc::new(
+                    rows.iter()
+                        .map(|row| row.get(i))
+                        .collect::<DictionaryArray<Int64Type>>(),
+                ) as ArrayRef),
+                DataType::UInt8 => Ok(Arc::new(
+                    rows.iter()
+                        .map(|row| row.get(i))
+                        .collect::<DictionaryArray<UInt8Type>>(),
+                ) as ArrayRef),
+                DataType::UInt16 => Ok(Arc::new(
+                    rows.iter()
+                        .map(|row| row.get(i))
+                        .collect::<DictionaryArray<UInt16Type>>(),
+                ) as ArrayRef),
+                DataType::UInt32 => Ok(Arc::new(
+                    rows.iter()
+                        .map(|row| row.get(i))
+                        .collect::<DictionaryArray<UInt32Type>>(),
+                ) as ArrayRef),
+                DataType::UInt64 => Ok(Arc::new(
+                    rows.iter()
+                        .map(|row| row.get(i))
+                        .collect::<DictionaryArray<UInt64Type>>(),
+                ) as ArrayRef),
+                _ => Err(ArrowError::ParseError(format!(
+                    "Unsupported dictionary key type {key_type:?}"
                 ))),
             }
-        })
-        .collect();
-
-    let projected_fields: Fields = projection.iter().map(|i| fields[*i].clone()).collect();
-
-    let projected_schema = Arc::new(match metadata {
-        None => Schema::new(projected_fields),
-        Some(metadata) => Schema::new_with_metadata(projected_fields, metadata),
-    });
-
-    arrays.and_then(|arr| {
-        RecordBatch::try_new_with_options(
-            projected_schema,
-            arr,
-            &RecordBatchOptions::new()
-                .with_match_field_names(true)
-                .with_row_count(Some(rows.len())),
-        )
-    })
+        }
+        other => Err(ArrowError::ParseError(format!(
+            "Unsupported data type {other:?}"
+        ))),
+    }
 }
 
 fn parse_bool(string: &str) -> Option<bool> {
@@ -910,9 +1383,10 @@ fn build_decimal_array<T: DecimalType>(
     precision: u8,
     scale: i8,
     null_regex: &NullRegex,
+    errors: &mut ErrorHandler<'_>,
 ) -> Result<ArrayRef, ArrowError> {
     let mut decimal_builder = PrimitiveBuilder::<T>::with_capacity(rows.len());
-    for row in rows.iter() {
+    for (row_index, row) in rows.iter().enumerate() {
         let s = row.get(col_idx);
         if null_regex.is_null(s) {
             // append null
@@ -924,7 +1398,8 @@ fn build_decimal_array<T: DecimalType>(
                     decimal_builder.append_value(v);
                 }
                 Err(e) => {
-                    return Err(e);
+                    errors.handle(row_index, s, e)?;
+                    decimal_builder.append_null();
                 }
             }
         }
@@ -942,6 +1417,7 @@ fn build_primitive_array<T: ArrowPrimitiveType + Parser>(
     rows: &StringRecords<'_>,
     col_idx: usize,
     null_regex: &NullRegex,
+    errors: &mut ErrorHandler<'_>,
 ) -> Result<ArrayRef, ArrowError> {
     rows.iter()
         .enumerate()
@@ -953,13 +1429,17 @@ fn build_primitive_array<T: ArrowPrimitiveType + Parser>(
 
             match T::parse(s) {
                 Some(e) => Ok(Some(e)),
-                None => Err(ArrowError::ParseError(format!(
-                    // TODO: we should surface the underlying error here.
-                    "Error while parsing value {} for column {} at line {}",
-                    s,
-                    col_idx,
-                    line_number + row_index
-                ))),
+                None => {
+                    let e = ArrowError::ParseError(format!(
+                        // TODO: we should surface the underlying error here.
+                        "Error while parsing value {} for column {} at line {}",
+                        s,
+                        col_idx,
+                        line_number + row_index
+                    ));
+                    errors.handle(row_index, s, e)?;
+                    Ok(None)
+                }
             }
         })
         .collect::<Result<PrimitiveArray<T>, ArrowError>>()
@@ -972,14 +1452,22 @@ fn build_timestamp_array<T: ArrowTimestampType>(
     col_idx: usize,
     timezone: Option<&str>,
     null_regex: &NullRegex,
+    errors: &mut ErrorHandler<'_>,
 ) -> Result<ArrayRef, ArrowError> {
     Ok(Arc::new(match timezone {
         Some(timezone) => {
             let tz: Tz = timezone.parse()?;
-            build_timestamp_array_impl::<T, _>(line_number, rows, col_idx, &tz, null_regex)?
+            build_timestamp_array_impl::<T, _>(line_number, rows, col_idx, &tz, null_regex, errors)?
                 .with_timezone(timezone)
         }
-        None => build_timestamp_array_impl::<T, _>(line_number, rows, col_idx, &Utc, null_regex)?,
+        None => build_timestamp_array_impl::<T, _>(
+            line_number,
+            rows,
+            col_idx,
+            &Utc,
+            null_regex,
+            errors,
+        )?,
     }))
 }
 
@@ -989,6 +1477,7 @@ fn build_timestamp_array_impl<T: ArrowTimestampType, Tz: TimeZone>(
     col_idx: usize,
     timezone: &Tz,
     null_regex: &NullRegex,
+    errors: &mut ErrorHandler<'_>,
 ) -> Result<PrimitiveArray<T>, ArrowError> {
     rows.iter()
         .enumerate()
@@ -1016,8 +1505,14 @@ fn build_timestamp_array_impl<T: ArrowTimestampType, Tz: TimeZone>(
                         line_number + row_index,
                         e
                     ))
-                })?;
-            Ok(Some(date))
+                });
+            match date {
+                Ok(date) => Ok(Some(date)),
+                Err(e) => {
+                    errors.handle(row_index, s, e)?;
+                    Ok(None)
+                }
+            }
         })
         .collect()
 }
@@ -1028,6 +1523,8 @@ fn build_boolean_array(
     rows: &StringRecords<'_>,
     col_idx: usize,
     null_regex: &NullRegex,
+    boolean_values: &BooleanValues,
+    errors: &mut ErrorHandler<'_>,
 ) -> Result<ArrayRef, ArrowError> {
     rows.iter()
         .enumerate()
@@ -1036,16 +1533,20 @@ fn build_boolean_array(
             if null_regex.is_null(s) {
                 return Ok(None);
             }
-            let parsed = parse_bool(s);
+            let parsed = boolean_values.parse(s);
             match parsed {
                 Some(e) => Ok(Some(e)),
-                None => Err(ArrowError::ParseError(format!(
-                    // TODO: we should surface the underlying error here.
-                    "Error while parsing value {} for column {} at line {}",
-                    s,
-                    col_idx,
-                    line_number + row_index
-                ))),
+                None => {
+                    let e = ArrowError::ParseError(format!(
+                        // TODO: we should surface the underlying error here.
+                        "Error while parsing value {} for column {} at line {}",
+                        s,
+                        col_idx,
+                        line_number + row_index
+                    ));
+                    errors.handle(row_index, s, e)?;
+                    Ok(None)
+                }
             }
         })
         .collect::<Result<BooleanArray, _>>()
@@ -1067,6 +1568,8 @@ pub struct ReaderBuilder {
     bounds: Bounds,
     /// Optional projection for which columns to load (zero-based column indices)
     projection: Option<Vec<usize>>,
+    /// How values that cannot be parsed are handled
+    error_mode: ErrorMode,
 }
 
 impl ReaderBuilder {
@@ -1098,6 +1601,7 @@ impl ReaderBuilder {
             batch_size: 1024,
             bounds: None,
             projection: None,
+            error_mode: ErrorMode::Fail,
         }
     }
 
@@ -1157,6 +1661,31 @@ impl ReaderBuilder {
         self
     }
 
+    /// Provide a list of tokens to treat as null values, in addition to the empty string
+    ///
+    /// See [`Format::with_null_values`]
+    pub fn with_null_values<I, S>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.format = self.format.with_null_values(values);
+        self
+    }
+
+    /// Provide additional case-insensitive spellings of `true` and `false`
+    ///
+    /// See [`Format::with_boolean_values`]
+    pub fn with_boolean_values<T, F, S>(mut self, true_values: T, false_values: F) -> Self
+    where
+        T: IntoIterator<Item = S>,
+        F: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.format = self.format.with_boolean_values(true_values, false_values);
+        self
+    }
+
     /// Set the batch size (number of records to load at one time)
     pub fn with_batch_size(mut self, batch_size: usize) -> Self {
         self.batch_size = batch_size;
@@ -1187,6 +1716,16 @@ impl ReaderBuilder {
         self
     }
 
+    /// Sets how values that cannot be parsed are handled, defaults to [`ErrorMode::Fail`]
+    ///
+    /// Any mode other than [`ErrorMode::Fail`] reports the values through
+    /// [`Decoder::take_errors`]. As with [`Self::with_truncated_rows`], a batch
+    /// containing nulls in a non-nullable column will still return an error
+    pub fn with_error_mode(mut self, error_mode: ErrorMode) -> Self {
+        self.error_mode = error_mode;
+        self
+    }
+
     /// Create a new `Reader` from a non-buffered reader
     ///
     /// If `R: BufRead` consider using [`Self::build_buffered`] to avoid unnecessary additional
@@ -1228,6 +1767,9 @@ impl ReaderBuilder {
             projection: self.projection,
             batch_size: self.batch_size,
             null_regex: self.format.null_regex,
+            boolean_values: self.format.boolean_values,
+            error_mode: self.error_mode,
+            errors: vec![],
         }
     }
 }
@@ -1696,6 +2238,143 @@ mod tests {
         assert!(!batch.column(1).is_null(4));
     }
 
+    #[test]
+    fn test_infer_schema_options() {
+        let data = "id,amount,paid,at,day,note
+1,12.50,yes,2021-01-01T00:00:00Z,2021-01-01,a
+2,-3.125,N,2021-01-01T01:00:00.5+01:00,2021-01-02,NA
+3,,Y,2021-01-01 02:00:00+01:00,n/a,b
+";
+
+        let format = Format::default().with_header(true);
+        let (schema, _) = format.infer_schema(data.as_bytes(), None).unwrap();
+        let types: Vec<_> = schema.fields().iter().map(|f| f.data_type()).collect();
+        assert_eq!(
+            types,
+            vec![
+                &DataType::Int64,
+                &DataType::Float64,
+                &DataType::Utf8,
+                &DataType::Timestamp(TimeUnit::Millisecond, None),
+                &DataType::Utf8,
+                &DataType::Utf8,
+            ]
+        );
+
+        let format = format
+            .with_null_values(["NA", "n/a"])
+            .with_boolean_values(["yes", "y"], ["no", "n"])
+            .with_decimal_inference(true)
+            .with_timezone_inference(true);
+        let (schema, _) = format.infer_schema(data.as_bytes(), None).unwrap();
+        let types: Vec<_> = schema.fields().iter().map(|f| f.data_type()).collect();
+        assert_eq!(
+            types,
+            vec![
+                &DataType::Int64,
+                &DataType::Decimal128(5, 3),
+                &DataType::Boolean,
+                &DataType::Timestamp(TimeUnit::Millisecond, Some("+00:00".into())),
+                &DataType::Date32,
+                &DataType::Utf8,
+            ]
+        );
+
+        let mut reader = ReaderBuilder::new(Arc::new(schema))
+            .with_format(format)
+            .build_buffered(data.as_bytes())
+            .unwrap();
+        let batch = reader.next().unwrap().unwrap();
+
+        let amount = batch.column(1).as_primitive::<Decimal128Type>();
+        assert_eq!(amount.value(0), 12500);
+        assert_eq!(amount.value(1), -3125);
+        assert!(amount.is_null(2));
+
+        let paid = batch.column(2).as_boolean();
+        assert_eq!(paid, &BooleanArray::from(vec![true, false, true]));
+
+        let at = batch.column(3).as_primitive::<TimestampMillisecondType>();
+        assert_eq!(
+            at.values().to_vec(),
+            vec![1609459200000, 1609459200500, 1609462800000]
+        );
+
+        assert!(batch.column(4).is_null(2));
+        let note = batch.column(5).as_string::<i32>();
+        assert_eq!(note, &StringArray::from(vec![Some("a"), None, Some("b")]));
+
+        // Exponents and mixed offsets are not representable
+        let data = "a,b\n1.5,2021-01-01T00:00:00Z\n1e3,2021-01-01T00:00:00\n";
+        let format = Format::default()
+            .with_header(true)
+            .with_decimal_inference(true)
+            .with_timezone_inference(true);
+        let (schema, _) = format.infer_schema(data.as_bytes(), None).unwrap();
+        assert_eq!(schema.field(0).data_type(), &DataType::Float64);
+        assert_eq!(schema.field(1).data_type(), &DataType::Utf8);
+    }
+
+    #[test]
+    fn test_infer_schema_with_report() {
+        let data = "a,b,c
+1,x,2021-01-01
+2,y,2021-01-02
+3,z,unknown
+n/a,w,2021-01-04
+,v,
+";
+
+        let format = Format::default().with_header(true);
+        let (schema, report) = format
+            .infer_schema_with_report(data.as_bytes(), None)
+            .unwrap();
+        assert_eq!(
+            schema,
+            format.infer_schema(data.as_bytes(), None).unwrap().0
+        );
+
+        assert_eq!(
+            report,
+            vec![
+                ColumnInference {
+                    name: "a".to_string(),
+                    data_type: DataType::Utf8,
+                    null_count: 1,
+                    value_count: 4,
+                    confidence: 0.75,
+                    conflicts: vec!["n/a".to_string()],
+                },
+                ColumnInference {
+                    name: "b".to_string(),
+                    data_type: DataType::Utf8,
+                    null_count: 0,
+                    value_count: 5,
+                    confidence: 1.,
+                    conflicts: vec![],
+                },
+                ColumnInference {
+                    name: "c".to_string(),
+                    data_type: DataType::Utf8,
+                    null_count: 1,
+                    value_count: 4,
+                    confidence: 0.75,
+                    conflicts: vec!["unknown".to_string()],
+                },
+            ]
+        );
+
+        let format = format.with_null_values(["n/a", "unknown"]);
+        let (_, report) = format
+            .infer_schema_with_report(data.as_bytes(), None)
+            .unwrap();
+        assert_eq!(report[0].data_type, DataType::Int64);
+        assert_eq!(report[0].null_count, 2);
+        assert_eq!(report[0].confidence, 1.);
+        assert_eq!(report[2].data_type, DataType::Date32);
+        assert!(report[2].conflicts.is_empty());
+    }
+
     #[test]
     fn test_custom_nulls_with_inference() {
         let mut file = File::open("test/data/custom_null_test.csv").unwrap();
@@ -1786,7 +2465,7 @@ mod tests {
     /// Infer the data type of a record
     fn infer_field_schema(string: &str) -> DataType {
         let mut v = InferredDataType::default();
-        v.update(string);
+        v.update(string, &Format::default());
         v.get()
     }
 
@@ -2270,6 +2949,70 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_error_mode() {
+        let data = "a,b,c\n1,x,2021-01-01\nfoo,y,2021-01-02\n3,z,bad\n4,w,2021-01-04\n";
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+            Field::new("c", DataType::Date32, true),
+        ]));
+        let builder = || ReaderBuilder::new(schema.clone()).with_header(true);
+
+        let mut reader = builder().build_buffered(data.as_bytes()).unwrap();
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parser error: Error while parsing value foo for column 0 at line 2"
+        );
+
+        let expected_errors = |errors: Vec<FieldError>| {
+            let errors: Vec<_> = errors
+                .into_iter()
+                .map(|e| (e.line, e.column, e.value))
+                .collect();
+            assert_eq!(
+                errors,
+                vec![(2, 0, "foo".to_string()), (3, 2, "bad".to_string())]
+            );
+        };
+
+        let mut reader = builder()
+            .with_error_mode(ErrorMode::Null)
+            .build_buffered(data.as_bytes())
+            .unwrap();
+        let batch = reader.next().unwrap().unwrap();
+        assert_eq!(batch.num_rows(), 4);
+        let a = batch.column(0).as_primitive::<Int32Type>();
+        assert_eq!(a, &Int32Array::from(vec![Some(1), None, Some(3), Some(4)]));
+        let b = batch.column(1).as_string::<i32>();
+        assert_eq!(b, &StringArray::from(vec!["x", "y", "z", "w"]));
+        let c = batch.column(2).as_primitive::<Date32Type>();
+        assert_eq!(c.null_count(), 1);
+        assert!(c.is_null(2));
+        assert!(reader.next().is_none());
+        expected_errors(reader.take_errors());
+        assert!(reader.take_errors().is_empty());
+
+        let mut reader = builder()
+            .with_error_mode(ErrorMode::Skip)
+            .with_batch_size(2)
+            .build_buffered(data.as_bytes())
+            .unwrap();
+        let batches: Vec<_> = reader.by_ref().collect::<Result<_, _>>().unwrap();
+        let a: Vec<_> = batches
+            .iter()
+            .flat_map(|b| b.column(0).as_primitive::<Int32Type>().values().to_vec())
+            .collect();
+        assert_eq!(a, vec![1, 4]);
+        let b: Vec<_> = batches
+            .iter()
+            .flat_map(|b| b.column(1).as_string::<i32>().iter().flatten())
+            .collect();
+        assert_eq!(b, vec!["x", "w"]);
+        expected_errors(reader.take_errors());
+    }
+
     #[test]
     fn test_truncated_rows_csv() {
         let file = File::open("test/data/truncated_rows.csv").unwrap();
@@ -2585,7 +3328,7 @@ mod tests {
         for (values, expected) in cases {
             let mut t = InferredDataType::default();
             for v in *values {
-                t.update(v)
+                t.update(v, &Format::default())
             }
             assert_eq!(&t.get(), expected, "{values:?}")
         }
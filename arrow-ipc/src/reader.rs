@@ -127,6 +127,22 @@ fn create_array(
                 require_alignment,
             )
         }
+        ListView(ref list_field) | LargeListView(ref list_field) => {
+            let list_node = reader.next_node(field)?;
+            let list_buffers = [
+                reader.next_buffer()?,
+                reader.next_buffer()?,
+                reader.next_buffer()?,
+            ];
+            let values = create_array(reader, list_field, variadic_counts, require_alignment)?;
+            create_list_array(
+                list_node,
+                data_type,
+                &list_buffers,
+                values,
+                require_alignment,
+            )
+        }
         FixedSizeList(ref list_field, _) => {
             let list_node = reader.next_node(field)?;
             let list_buffers = [reader.next_buffer()?];
@@ -338,6 +354,13 @@ fn create_list_array(
             .add_child_data(child_data)
             .null_bit_buffer(null_buffer),
 
+        ListView(_) | LargeListView(_) => ArrayData::builder(data_type.clone())
+            .len(length)
+            .add_buffer(buffers[1].clone())
+            .add_buffer(buffers[2].clone())
+            .add_child_data(child_data)
+            .null_bit_buffer(null_buffer),
+
         FixedSizeList(_, _) => ArrayData::builder(data_type.clone())
             .len(length)
             .add_child_data(child_data)
@@ -452,6 +475,12 @@ impl<'a> ArrayReader<'a> {
                 self.skip_buffer();
                 self.skip_field(list_field, variadic_count)?;
             }
+            ListView(list_field) | LargeListView(list_field) => {
+                self.skip_buffer();
+                self.skip_buffer();
+                self.skip_buffer();
+                self.skip_field(list_field, variadic_count)?;
+            }
             FixedSizeList(list_field, _) => {
                 self.skip_buffer();
                 self.skip_field(list_field, variadic_count)?;
@@ -2019,6 +2048,68 @@ mod tests {
         assert_eq!(sliced_batch, roundtrip_ipc_stream(&sliced_batch));
     }
 
+    #[test]
+    fn test_roundtrip_list_view() {
+        let field = Arc::new(Field::new_list_field(DataType::Int32, true));
+        let values: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(1),
+            None,
+            Some(3),
+            Some(4),
+            Some(5),
+        ]));
+        let nulls = Some(NullBuffer::from(vec![true, false, true, true]));
+        // Views overlap and are out of order
+        let list_view = ListViewArray::new(
+            field.clone(),
+            ScalarBuffer::from(vec![3, 0, 0, 1]),
+            ScalarBuffer::from(vec![2, 0, 3, 4]),
+            values.clone(),
+            nulls.clone(),
+        );
+        let large_list_view = LargeListViewArray::new(
+            field.clone(),
+            ScalarBuffer::from(vec![3, 0, 0, 1]),
+            ScalarBuffer::from(vec![2, 0, 3, 4]),
+            values,
+            nulls,
+        );
+        let record_batch = RecordBatch::try_from_iter(vec![
+            ("list_view", Arc::new(list_view) as ArrayRef),
+            ("large_list_view", Arc::new(large_list_view) as ArrayRef),
+        ])
+        .unwrap();
+
+        // ArrayData equality is not implemented for list views, compare the views instead
+        fn assert_views_eq<O: OffsetSizeTrait>(expected: &ArrayRef, actual: &ArrayRef) {
+            let expected = expected
+                .as_any()
+                .downcast_ref::<GenericListViewArray<O>>()
+                .unwrap();
+            let actual = actual
+                .as_any()
+                .downcast_ref::<GenericListViewArray<O>>()
+                .unwrap();
+            assert_eq!(expected.len(), actual.len());
+            assert_eq!(expected.nulls(), actual.nulls());
+            for i in 0..expected.len() {
+                assert_eq!(&expected.value(i), &actual.value(i), "mismatch at {i}");
+            }
+        }
+        fn assert_batch_eq(expected: &RecordBatch, actual: &RecordBatch) {
+            assert_eq!(expected.schema(), actual.schema());
+            assert_views_eq::<i32>(expected.column(0), actual.column(0));
+            assert_views_eq::<i64>(expected.column(1), actual.column(1));
+        }
+
+        assert_batch_eq(&record_batch, &roundtrip_ipc(&record_batch));
+        assert_batch_eq(&record_batch, &roundtrip_ipc_stream(&record_batch));
+
+        let sliced_batch = record_batch.slice(1, 2);
+        assert_batch_eq(&sliced_batch, &roundtrip_ipc(&sliced_batch));
+        assert_batch_eq(&sliced_batch, &roundtrip_ipc_stream(&sliced_batch));
+    }
+
     #[test]
     fn test_roundtrip_view_types_nested_dict() {
         let bin_values: Vec<Option<&[u8]>> = vec![
@@ -330,6 +330,34 @@ impl IpcDataGenerator {
                     dict_id,
                 )?;
             }
+            DataType::ListView(field) => {
+                let list = column
+                    .as_any()
+                    .downcast_ref::<ListViewArray>()
+                    .expect("Unable to downcast to list view array");
+                self.encode_dictionaries(
+                    field,
+                    list.values(),
+                    encoded_dictionaries,
+                    dictionary_tracker,
+                    write_options,
+                    dict_id,
+                )?;
+            }
+            DataType::LargeListView(field) => {
+                let list = column
+                    .as_any()
+                    .downcast_ref::<LargeListViewArray>()
+                    .expect("Unable to downcast to large list view array");
+                self.encode_dictionaries(
+                    field,
+                    list.values(),
+                    encoded_dictionaries,
+                    dictionary_tracker,
+                    write_options,
+                    dict_id,
+                )?;
+            }
             DataType::FixedSizeList(field, _) => {
                 let list = column
                     .as_any()
@@ -1633,6 +1661,31 @@ fn write_array_data(
             write_options,
         )?;
         return Ok(offset);
+    } else if matches!(
+        data_type,
+        DataType::ListView(_) | DataType::LargeListView(_)
+    ) {
+        assert_eq!(array_data.buffers().len(), 2);
+        assert_eq!(array_data.child_data().len(), 1);
+
+        // Truncate offsets and sizes, the child data is written in full as
+        // views may reference it in any order
+        let byte_width = match data_type {
+            DataType::ListView(_) => 4,
+            _ => 8,
+        };
+        let start = array_data.offset() * byte_width;
+        let end = start + array_data.len() * byte_width;
+        for buffer in array_data.buffers() {
+            offset = write_buffer(
+                &buffer.as_slice()[start..end],
+                buffers,
+                arrow_data,
+                offset,
+                compression_codec,
+                write_options.alignment,
+            )?;
+        }
     } else {
         for buffer in array_data.buffers() {
             offset = write_buffer(
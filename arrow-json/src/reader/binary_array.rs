@@ -0,0 +1,139 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::marker::PhantomData;
+
+use arrow_array::builder::FixedSizeBinaryBuilder;
+use arrow_array::{Array, GenericBinaryArray, OffsetSizeTrait};
+use arrow_buffer::{BooleanBufferBuilder, Buffer, NullBuffer};
+use arrow_cast::base64::{Engine, BASE64_STANDARD};
+use arrow_data::{ArrayData, ArrayDataBuilder};
+use arrow_schema::ArrowError;
+
+use crate::reader::tape::{Tape, TapeElement};
+use crate::reader::{ArrayDecoder, BinaryEncoding};
+
+/// Decode the binary string `s` encoded with `encoding`, appending the result to `out`
+fn decode_binary(encoding: BinaryEncoding, s: &str, out: &mut Vec<u8>) -> Result<(), ArrowError> {
+    let err = || ArrowError::JsonError(format!("failed to decode \"{s}\" as {encoding:?} binary"));
+    match encoding {
+        BinaryEncoding::Hex => {
+            if s.len() % 2 != 0 {
+                return Err(err());
+            }
+            for chunk in s.as_bytes().chunks_exact(2) {
+                let high = (chunk[0] as char).to_digit(16).ok_or_else(err)?;
+                let low = (chunk[1] as char).to_digit(16).ok_or_else(err)?;
+                out.push(((high << 4) | low) as u8);
+            }
+            Ok(())
+        }
+        BinaryEncoding::Base64 => BASE64_STANDARD.decode_vec(s, out).map_err(|_| err()),
+    }
+}
+
+pub struct BinaryArrayDecoder<O: OffsetSizeTrait> {
+    encoding: BinaryEncoding,
+    phantom: PhantomData<O>,
+}
+
+impl<O: OffsetSizeTrait> BinaryArrayDecoder<O> {
+    pub fn new(encoding: BinaryEncoding) -> Self {
+        Self {
+            encoding,
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<O: OffsetSizeTrait> ArrayDecoder for BinaryArrayDecoder<O> {
+    fn decode(&mut self, tape: &Tape<'_>, pos: &[u32]) -> Result<ArrayData, ArrowError> {
+        let data_type = GenericBinaryArray::<O>::DATA_TYPE;
+
+        let mut values = Vec::new();
+        let mut offsets = Vec::with_capacity(pos.len() + 1);
+        let mut nulls = BooleanBufferBuilder::new(pos.len());
+        offsets.push(O::usize_as(0));
+
+        for p in pos {
+            match tape.get(*p) {
+                TapeElement::String(idx) => {
+                    decode_binary(self.encoding, tape.get_string(idx), &mut values)?;
+                    nulls.append(true);
+                }
+                TapeElement::Null => nulls.append(false),
+                _ => return Err(tape.error(*p, "binary string")),
+            }
+            let offset = O::from_usize(values.len()).ok_or_else(|| {
+                ArrowError::JsonError(format!("offset overflow decoding {data_type}"))
+            })?;
+            offsets.push(offset);
+        }
+
+        let nulls = NullBuffer::new(nulls.finish());
+        let data = ArrayDataBuilder::new(data_type)
+            .len(pos.len())
+            .nulls(Some(nulls).filter(|n| n.null_count() > 0))
+            .add_buffer(Buffer::from_vec(offsets))
+            .add_buffer(Buffer::from_vec(values));
+
+        // Safety
+        // Offsets are monotonically increasing and within bounds by construction
+        Ok(unsafe { data.build_unchecked() })
+    }
+}
+
+pub struct FixedSizeBinaryArrayDecoder {
+    size: i32,
+    encoding: BinaryEncoding,
+}
+
+impl FixedSizeBinaryArrayDecoder {
+    pub fn new(size: i32, encoding: BinaryEncoding) -> Self {
+        Self { size, encoding }
+    }
+}
+
+impl ArrayDecoder for FixedSizeBinaryArrayDecoder {
+    fn decode(&mut self, tape: &Tape<'_>, pos: &[u32]) -> Result<ArrayData, ArrowError> {
+        let mut builder = FixedSizeBinaryBuilder::with_capacity(pos.len(), self.size);
+        let mut scratch = Vec::with_capacity(self.size as usize);
+
+        for p in pos {
+            match tape.get(*p) {
+                TapeElement::String(idx) => {
+                    let s = tape.get_string(idx);
+                    scratch.clear();
+                    decode_binary(self.encoding, s, &mut scratch)?;
+                    if scratch.len() != self.size as usize {
+                        return Err(ArrowError::JsonError(format!(
+                            "expected {} bytes decoding \"{s}\" as FixedSizeBinary({}) got {}",
+                            self.size,
+                            self.size,
+                            scratch.len()
+                        )));
+                    }
+                    builder.append_value(&scratch)?;
+                }
+                TapeElement::Null => builder.append_null(),
+                _ => return Err(tape.error(*p, "binary string")),
+            }
+        }
+
+        Ok(builder.finish().into_data())
+    }
+}
@@ -0,0 +1,140 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::marker::PhantomData;
+
+use arrow_array::builder::GenericByteDictionaryBuilder;
+use arrow_array::types::*;
+use arrow_array::{make_array, Array, OffsetSizeTrait};
+use arrow_cast::cast;
+use arrow_data::ArrayData;
+use arrow_schema::{ArrowError, DataType};
+
+use crate::reader::string_array::coerce_string;
+use crate::reader::tape::Tape;
+use crate::reader::{make_decoder, ArrayDecoder, BinaryEncoding};
+
+/// Create an [`ArrayDecoder`] for the [`DataType::Dictionary`] `data_type`
+pub fn make_dictionary_decoder(
+    data_type: DataType,
+    coerce_primitive: bool,
+    strict_mode: bool,
+    binary_encoding: BinaryEncoding,
+    union_discriminator: Option<&str>,
+    is_nullable: bool,
+) -> Result<Box<dyn ArrayDecoder>, ArrowError> {
+    let (key_type, value_type) = match &data_type {
+        DataType::Dictionary(k, v) => (k.as_ref(), v.as_ref()),
+        _ => unreachable!(),
+    };
+
+    macro_rules! dictionary_decoder {
+        ($k:ty) => {
+            match value_type {
+                DataType::Utf8 => Ok(Box::new(StringDictionaryArrayDecoder::<$k, i32>::new(
+                    data_type,
+                    coerce_primitive,
+                ))),
+                DataType::LargeUtf8 => Ok(Box::new(StringDictionaryArrayDecoder::<$k, i64>::new(
+                    data_type,
+                    coerce_primitive,
+                ))),
+                _ => {
+                    let decoder = make_decoder(
+                        value_type.clone(),
+                        coerce_primitive,
+                        strict_mode,
+                        binary_encoding,
+                        union_discriminator,
+                        is_nullable,
+                    )?;
+                    Ok(Box::new(CastDictionaryArrayDecoder { data_type, decoder }))
+                }
+            }
+        };
+    }
+
+    match key_type {
+        DataType::Int8 => dictionary_decoder!(Int8Type),
+        DataType::Int16 => dictionary_decoder!(Int16Type),
+        DataType::Int32 => dictionary_decoder!(Int32Type),
+        DataType::Int64 => dictionary_decoder!(Int64Type),
+        DataType::UInt8 => dictionary_decoder!(UInt8Type),
+        DataType::UInt16 => dictionary_decoder!(UInt16Type),
+        DataType::UInt32 => dictionary_decoder!(UInt32Type),
+        DataType::UInt64 => dictionary_decoder!(UInt64Type),
+        _ => Err(ArrowError::JsonError(format!(
+            "{data_type} is not a valid dictionary type"
+        ))),
+    }
+}
+
+/// A dictionary of strings, deduplicating values as they are decoded
+pub struct StringDictionaryArrayDecoder<K: ArrowDictionaryKeyType, O: OffsetSizeTrait> {
+    data_type: DataType,
+    coerce_primitive: bool,
+    phantom: PhantomData<fn(K, O)>,
+}
+
+impl<K: ArrowDictionaryKeyType, O: OffsetSizeTrait> StringDictionaryArrayDecoder<K, O> {
+    pub fn new(data_type: DataType, coerce_primitive: bool) -> Self {
+        Self {
+            data_type,
+            coerce_primitive,
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<K: ArrowDictionaryKeyType, O: OffsetSizeTrait> ArrayDecoder
+    for StringDictionaryArrayDecoder<K, O>
+{
+    fn decode(&mut self, tape: &Tape<'_>, pos: &[u32]) -> Result<ArrayData, ArrowError> {
+        let mut builder = GenericByteDictionaryBuilder::<K, GenericStringType<O>>::with_capacity(
+            pos.len(),
+            pos.len(),
+            0,
+        );
+
+        let overflow =
+            |_| ArrowError::JsonError(format!("key overflow decoding {}", self.data_type));
+
+        for p in pos {
+            match coerce_string(tape, *p, self.coerce_primitive)? {
+                Some(s) => {
+                    builder.append(s).map_err(overflow)?;
+                }
+                None => builder.append_null(),
+            }
+        }
+
+        Ok(builder.finish().into_data())
+    }
+}
+
+/// A dictionary of non-string values, decoded and then cast to a dictionary
+pub struct CastDictionaryArrayDecoder {
+    data_type: DataType,
+    decoder: Box<dyn ArrayDecoder>,
+}
+
+impl ArrayDecoder for CastDictionaryArrayDecoder {
+    fn decode(&mut self, tape: &Tape<'_>, pos: &[u32]) -> Result<ArrayData, ArrowError> {
+        let values = make_array(self.decoder.decode(tape, pos)?);
+        Ok(cast(&values, &self.data_type)?.into_data())
+    }
+}
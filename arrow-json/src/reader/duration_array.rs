@@ -0,0 +1,222 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::marker::PhantomData;
+
+use arrow_array::builder::PrimitiveBuilder;
+use arrow_array::types::{
+    ArrowPrimitiveType, DurationMicrosecondType, DurationMillisecondType, DurationNanosecondType,
+    DurationSecondType,
+};
+use arrow_array::Array;
+use arrow_data::ArrayData;
+use arrow_schema::ArrowError;
+
+use crate::reader::tape::{Tape, TapeElement};
+use crate::reader::ArrayDecoder;
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+const NANOS_PER_MINUTE: i128 = 60 * NANOS_PER_SECOND;
+const NANOS_PER_HOUR: i128 = 60 * NANOS_PER_MINUTE;
+const NANOS_PER_DAY: i128 = 24 * NANOS_PER_HOUR;
+const NANOS_PER_WEEK: i128 = 7 * NANOS_PER_DAY;
+
+/// A duration type
+pub trait DurationType: ArrowPrimitiveType<Native = i64> {
+    /// The number of nanoseconds in one unit of this type
+    const NANOS: i128;
+}
+
+impl DurationType for DurationSecondType {
+    const NANOS: i128 = NANOS_PER_SECOND;
+}
+
+impl DurationType for DurationMillisecondType {
+    const NANOS: i128 = 1_000_000;
+}
+
+impl DurationType for DurationMicrosecondType {
+    const NANOS: i128 = 1_000;
+}
+
+impl DurationType for DurationNanosecondType {
+    const NANOS: i128 = 1;
+}
+
+/// A specialized [`ArrayDecoder`] for durations
+///
+/// Accepts either integers, interpreted as a number of the duration's [`TimeUnit`],
+/// or ISO 8601 duration strings, e.g. `"PT1.5S"` or `"P1DT2H"`
+pub struct DurationArrayDecoder<P: DurationType> {
+    // Invariant and Send
+    phantom: PhantomData<fn(P) -> P>,
+}
+
+impl<P: DurationType> DurationArrayDecoder<P> {
+    pub fn new() -> Self {
+        Self {
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<P: DurationType> ArrayDecoder for DurationArrayDecoder<P> {
+    fn decode(&mut self, tape: &Tape<'_>, pos: &[u32]) -> Result<ArrayData, ArrowError> {
+        let mut builder = PrimitiveBuilder::<P>::with_capacity(pos.len());
+        let d = &P::DATA_TYPE;
+
+        for p in pos {
+            match tape.get(*p) {
+                TapeElement::Null => builder.append_null(),
+                TapeElement::String(idx) => {
+                    let s = tape.get_string(idx);
+                    let value = parse_iso8601_duration(s)
+                        .filter(|nanos| nanos % P::NANOS == 0)
+                        .and_then(|nanos| i64::try_from(nanos / P::NANOS).ok())
+                        .ok_or_else(|| {
+                            ArrowError::JsonError(format!("failed to parse \"{s}\" as {d}"))
+                        })?;
+                    builder.append_value(value)
+                }
+                TapeElement::Number(idx) => {
+                    let s = tape.get_string(idx);
+                    let value = s.parse::<i64>().map_err(|_| {
+                        ArrowError::JsonError(format!("failed to parse {s} as {d}"))
+                    })?;
+                    builder.append_value(value)
+                }
+                TapeElement::I32(v) => builder.append_value(v as i64),
+                TapeElement::I64(high) => match tape.get(p + 1) {
+                    TapeElement::I32(low) => {
+                        builder.append_value((high as i64) << 32 | (low as u32) as i64)
+                    }
+                    _ => unreachable!(),
+                },
+                _ => return Err(tape.error(*p, "duration")),
+            }
+        }
+
+        Ok(builder.finish().into_data())
+    }
+}
+
+/// Parse an ISO 8601 duration such as `P1DT2H3M4.5S`, returning the number of nanoseconds
+///
+/// As they do not have a fixed length, years and months are not supported
+fn parse_iso8601_duration(s: &str) -> Option<i128> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+    let s = s.strip_prefix('P')?;
+    let (date, time) = match s.split_once('T') {
+        Some((date, time)) if !time.is_empty() => (date, time),
+        Some(_) => return None,
+        None if !s.is_empty() => (s, ""),
+        None => return None,
+    };
+
+    let date = parse_components(date, &[(b'W', NANOS_PER_WEEK), (b'D', NANOS_PER_DAY)])?;
+    let time = parse_components(
+        time,
+        &[
+            (b'H', NANOS_PER_HOUR),
+            (b'M', NANOS_PER_MINUTE),
+            (b'S', NANOS_PER_SECOND),
+        ],
+    )?;
+    let nanos = date.checked_add(time)?;
+    Some(if negative { -nanos } else { nanos })
+}
+
+/// Parse a sequence of `<number><designator>` components, where `units` lists
+/// the permitted designators, in order, along with their length in nanoseconds
+fn parse_components(mut s: &str, units: &[(u8, i128)]) -> Option<i128> {
+    let mut units = units.iter();
+    let mut total: i128 = 0;
+    while !s.is_empty() {
+        let end = s.find(|c: char| c.is_ascii_alphabetic())?;
+        let designator = s.as_bytes()[end];
+        let (_, nanos) = units.find(|(d, _)| *d == designator)?;
+        total = total.checked_add(parse_decimal(&s[..end], *nanos)?)?;
+        s = &s[end + 1..];
+    }
+    Some(total)
+}
+
+/// Parse the unsigned decimal number `s`, multiplying it by `scale`
+///
+/// Returns `None` if the result is not an integer
+fn parse_decimal(s: &str, scale: i128) -> Option<i128> {
+    let (integer, fraction) = s.split_once('.').unwrap_or((s, ""));
+    let is_digits = |x: &str| x.bytes().all(|b| b.is_ascii_digit());
+    if integer.is_empty() || !is_digits(integer) || !is_digits(fraction) || fraction.len() > 18 {
+        return None;
+    }
+
+    let integer = integer.parse::<i128>().ok()?.checked_mul(scale)?;
+    if fraction.is_empty() {
+        return Some(integer);
+    }
+
+    let divisor = 10_i128.pow(fraction.len() as u32);
+    let fraction = fraction.parse::<i128>().ok()? * scale;
+    match fraction % divisor {
+        0 => integer.checked_add(fraction / divisor),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_iso8601_duration() {
+        let cases = [
+            ("PT120S", Some(120 * NANOS_PER_SECOND)),
+            ("PT0.12S", Some(120_000_000)),
+            ("PT0.00000012S", Some(120)),
+            ("P0D", Some(0)),
+            ("-PT1.5S", Some(-1_500_000_000)),
+            (
+                "P1DT2H3M4S",
+                Some(
+                    NANOS_PER_DAY
+                        + 2 * NANOS_PER_HOUR
+                        + 3 * NANOS_PER_MINUTE
+                        + 4 * NANOS_PER_SECOND,
+                ),
+            ),
+            ("P2W", Some(2 * NANOS_PER_WEEK)),
+            ("PT0.5M", Some(30 * NANOS_PER_SECOND)),
+            ("P", None),
+            ("PT", None),
+            ("P1Y", None),
+            ("P1M", None),
+            ("PT1S2M", None),
+            ("PT.5S", None),
+            ("PT0.0000000001S", None),
+            ("1S", None),
+            ("P1D1D", None),
+        ];
+
+        for (s, expected) in cases {
+            assert_eq!(parse_iso8601_duration(s), expected, "{s}");
+        }
+    }
+}
@@ -0,0 +1,118 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::reader::tape::{Tape, TapeElement};
+use crate::reader::{make_decoder, ArrayDecoder, BinaryEncoding};
+use arrow_array::builder::BooleanBufferBuilder;
+use arrow_buffer::buffer::NullBuffer;
+use arrow_data::{ArrayData, ArrayDataBuilder};
+use arrow_schema::{ArrowError, DataType};
+
+pub struct FixedSizeListArrayDecoder {
+    data_type: DataType,
+    size: usize,
+    decoder: Box<dyn ArrayDecoder>,
+    is_nullable: bool,
+}
+
+impl FixedSizeListArrayDecoder {
+    pub fn new(
+        data_type: DataType,
+        coerce_primitive: bool,
+        strict_mode: bool,
+        binary_encoding: BinaryEncoding,
+        union_discriminator: Option<&str>,
+        is_nullable: bool,
+    ) -> Result<Self, ArrowError> {
+        let (field, size) = match &data_type {
+            DataType::FixedSizeList(f, size) => (f, *size as usize),
+            _ => unreachable!(),
+        };
+        // A null list is represented by `size` null child values
+        let decoder = make_decoder(
+            field.data_type().clone(),
+            coerce_primitive,
+            strict_mode,
+            binary_encoding,
+            union_discriminator,
+            field.is_nullable() || is_nullable,
+        )?;
+
+        Ok(Self {
+            data_type,
+            size,
+            decoder,
+            is_nullable,
+        })
+    }
+}
+
+impl ArrayDecoder for FixedSizeListArrayDecoder {
+    fn decode(&mut self, tape: &Tape<'_>, pos: &[u32]) -> Result<ArrayData, ArrowError> {
+        let mut child_pos = Vec::with_capacity(pos.len() * self.size);
+
+        let mut nulls = self
+            .is_nullable
+            .then(|| BooleanBufferBuilder::new(pos.len()));
+
+        for p in pos {
+            let end_idx = match (tape.get(*p), nulls.as_mut()) {
+                (TapeElement::StartList(end_idx), None) => end_idx,
+                (TapeElement::StartList(end_idx), Some(nulls)) => {
+                    nulls.append(true);
+                    end_idx
+                }
+                (TapeElement::Null, Some(nulls)) => {
+                    nulls.append(false);
+                    // Decode the null itself as each of the child values
+                    child_pos.resize(child_pos.len() + self.size, *p);
+                    continue;
+                }
+                _ => return Err(tape.error(*p, "[")),
+            };
+
+            let start = child_pos.len();
+            let mut cur_idx = *p + 1;
+            while cur_idx < end_idx {
+                child_pos.push(cur_idx);
+
+                // Advance to next field
+                cur_idx = tape.next(cur_idx, "list value")?;
+            }
+
+            let len = child_pos.len() - start;
+            if len != self.size {
+                return Err(ArrowError::JsonError(format!(
+                    "expected {} values for fixed size list got {len}",
+                    self.size
+                )));
+            }
+        }
+
+        let child_data = self.decoder.decode(tape, &child_pos)?;
+        let nulls = nulls.as_mut().map(|x| NullBuffer::new(x.finish()));
+
+        let data = ArrayDataBuilder::new(self.data_type.clone())
+            .len(pos.len())
+            .nulls(nulls)
+            .child_data(vec![child_data]);
+
+        // Safety
+        // Validated lengths above
+        Ok(unsafe { data.build_unchecked() })
+    }
+}
@@ -0,0 +1,66 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow_array::builder::PrimitiveBuilder;
+use arrow_array::types::ArrowPrimitiveType;
+use arrow_array::Array;
+use arrow_data::ArrayData;
+use arrow_schema::ArrowError;
+
+use crate::reader::tape::{Tape, TapeElement};
+use crate::reader::ArrayDecoder;
+
+/// A specialized [`ArrayDecoder`] for intervals
+///
+/// Accepts strings in the human-readable format produced by [`Writer`](crate::Writer),
+/// e.g. `"1 mons 2 days 3 secs"`
+pub struct IntervalArrayDecoder<P: ArrowPrimitiveType> {
+    parse: fn(&str) -> Result<P::Native, ArrowError>,
+}
+
+impl<P: ArrowPrimitiveType> IntervalArrayDecoder<P> {
+    pub fn new(parse: fn(&str) -> Result<P::Native, ArrowError>) -> Self {
+        Self { parse }
+    }
+}
+
+impl<P: ArrowPrimitiveType> ArrayDecoder for IntervalArrayDecoder<P> {
+    fn decode(&mut self, tape: &Tape<'_>, pos: &[u32]) -> Result<ArrayData, ArrowError> {
+        let mut builder = PrimitiveBuilder::<P>::with_capacity(pos.len());
+        let d = &P::DATA_TYPE;
+
+        for p in pos {
+            match tape.get(*p) {
+                TapeElement::Null => builder.append_null(),
+                // A zero interval is formatted as an empty string
+                TapeElement::String(idx) if tape.get_string(idx).is_empty() => {
+                    builder.append_value(P::Native::default())
+                }
+                TapeElement::String(idx) => {
+                    let s = tape.get_string(idx);
+                    let value = (self.parse)(s).map_err(|e| {
+                        ArrowError::JsonError(format!("failed to parse \"{s}\" as {d}: {e}"))
+                    })?;
+                    builder.append_value(value)
+                }
+                _ => return Err(tape.error(*p, "interval")),
+            }
+        }
+
+        Ok(builder.finish().into_data())
+    }
+}
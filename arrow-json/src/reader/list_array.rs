@@ -16,7 +16,7 @@
 // under the License.
 
 use crate::reader::tape::{Tape, TapeElement};
-use crate::reader::{make_decoder, ArrayDecoder};
+use crate::reader::{make_decoder, ArrayDecoder, BinaryEncoding};
 use arrow_array::builder::{BooleanBufferBuilder, BufferBuilder};
 use arrow_array::OffsetSizeTrait;
 use arrow_buffer::buffer::NullBuffer;
@@ -36,6 +36,8 @@ impl<O: OffsetSizeTrait> ListArrayDecoder<O> {
         data_type: DataType,
         coerce_primitive: bool,
         strict_mode: bool,
+        binary_encoding: BinaryEncoding,
+        union_discriminator: Option<&str>,
         is_nullable: bool,
     ) -> Result<Self, ArrowError> {
         let field = match &data_type {
@@ -47,6 +49,8 @@ impl<O: OffsetSizeTrait> ListArrayDecoder<O> {
             field.data_type().clone(),
             coerce_primitive,
             strict_mode,
+            binary_encoding,
+            union_discriminator,
             field.is_nullable(),
         )?;
 
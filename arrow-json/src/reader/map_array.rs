@@ -16,7 +16,7 @@
 // under the License.
 
 use crate::reader::tape::{Tape, TapeElement};
-use crate::reader::{make_decoder, ArrayDecoder};
+use crate::reader::{make_decoder, ArrayDecoder, BinaryEncoding};
 use arrow_array::builder::{BooleanBufferBuilder, BufferBuilder};
 use arrow_buffer::buffer::NullBuffer;
 use arrow_buffer::ArrowNativeType;
@@ -35,6 +35,8 @@ impl MapArrayDecoder {
         data_type: DataType,
         coerce_primitive: bool,
         strict_mode: bool,
+        binary_encoding: BinaryEncoding,
+        union_discriminator: Option<&str>,
         is_nullable: bool,
     ) -> Result<Self, ArrowError> {
         let fields = match &data_type {
@@ -58,12 +60,16 @@ impl MapArrayDecoder {
             fields[0].data_type().clone(),
             coerce_primitive,
             strict_mode,
+            binary_encoding,
+            union_discriminator,
             fields[0].is_nullable(),
         )?;
         let values = make_decoder(
             fields[1].data_type().clone(),
             coerce_primitive,
             strict_mode,
+            binary_encoding,
+            union_discriminator,
             fields[1].is_nullable(),
         )?;
 
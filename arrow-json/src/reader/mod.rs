@@ -141,34 +141,57 @@ use serde::Serialize;
 
 use arrow_array::timezone::Tz;
 use arrow_array::types::*;
-use arrow_array::{downcast_integer, make_array, RecordBatch, RecordBatchReader, StructArray};
+use arrow_array::{
+    downcast_integer, make_array, RecordBatch, RecordBatchOptions, RecordBatchReader, StructArray,
+};
+use arrow_cast::parse::{
+    parse_interval_day_time, parse_interval_month_day_nano, parse_interval_year_month,
+};
 use arrow_data::ArrayData;
-use arrow_schema::{ArrowError, DataType, FieldRef, Schema, SchemaRef, TimeUnit};
+use arrow_schema::{ArrowError, DataType, FieldRef, IntervalUnit, Schema, SchemaRef, TimeUnit};
 pub use schema::*;
 
+use crate::reader::binary_array::{BinaryArrayDecoder, FixedSizeBinaryArrayDecoder};
 use crate::reader::boolean_array::BooleanArrayDecoder;
 use crate::reader::decimal_array::DecimalArrayDecoder;
+use crate::reader::dictionary_array::make_dictionary_decoder;
+use crate::reader::duration_array::DurationArrayDecoder;
+use crate::reader::fixed_size_list_array::FixedSizeListArrayDecoder;
+use crate::reader::interval_array::IntervalArrayDecoder;
 use crate::reader::list_array::ListArrayDecoder;
 use crate::reader::map_array::MapArrayDecoder;
 use crate::reader::null_array::NullArrayDecoder;
+use crate::reader::path::{parse_json_path, PathTree, ProjectionDecoder};
 use crate::reader::primitive_array::PrimitiveArrayDecoder;
+use crate::reader::run_end_array::make_run_end_decoder;
 use crate::reader::string_array::StringArrayDecoder;
+use crate::reader::string_view_array::StringViewArrayDecoder;
 use crate::reader::struct_array::StructArrayDecoder;
-use crate::reader::tape::{Tape, TapeDecoder};
+use crate::reader::tape::{Tape, TapeDecoder, TapeElement};
 use crate::reader::timestamp_array::TimestampArrayDecoder;
+use crate::reader::union_array::UnionArrayDecoder;
 
+mod binary_array;
 mod boolean_array;
 mod decimal_array;
+mod dictionary_array;
+mod duration_array;
+mod fixed_size_list_array;
+mod interval_array;
 mod list_array;
 mod map_array;
 mod null_array;
+mod path;
 mod primitive_array;
+mod run_end_array;
 mod schema;
 mod serializer;
 mod string_array;
+mod string_view_array;
 mod struct_array;
 mod tape;
 mod timestamp_array;
+mod union_array;
 
 /// A builder for [`Reader`] and [`Decoder`]
 pub struct ReaderBuilder {
@@ -176,6 +199,10 @@ pub struct ReaderBuilder {
     coerce_primitive: bool,
     strict_mode: bool,
     is_field: bool,
+    binary_encoding: BinaryEncoding,
+    union_discriminator: Option<String>,
+    json_paths: Vec<(String, String)>,
+    error_mode: ErrorMode,
 
     schema: SchemaRef,
 }
@@ -195,6 +222,10 @@ impl ReaderBuilder {
             coerce_primitive: false,
             strict_mode: false,
             is_field: false,
+            binary_encoding: BinaryEncoding::default(),
+            union_discriminator: None,
+            json_paths: vec![],
+            error_mode: ErrorMode::default(),
             schema,
         }
     }
@@ -235,6 +266,10 @@ impl ReaderBuilder {
             coerce_primitive: false,
             strict_mode: false,
             is_field: true,
+            binary_encoding: BinaryEncoding::default(),
+            union_discriminator: None,
+            json_paths: vec![],
+            error_mode: ErrorMode::default(),
             schema: Arc::new(Schema::new([field.into()])),
         }
     }
@@ -269,6 +304,78 @@ impl ReaderBuilder {
         }
     }
 
+    /// Sets the [`BinaryEncoding`] used to decode JSON strings into binary columns,
+    /// defaults to [`BinaryEncoding::Hex`]
+    pub fn with_binary_encoding(self, binary_encoding: BinaryEncoding) -> Self {
+        Self {
+            binary_encoding,
+            ..self
+        }
+    }
+
+    /// Sets the name of a field used to select the child of a [`DataType::Union`]
+    ///
+    /// By default each JSON value is decoded into the first child of the union, in
+    /// declaration order, that can decode it. If a discriminator is set, JSON objects
+    /// containing a string field of this name are instead decoded into the child with
+    /// a matching field name, any other values fall back to the first child able to
+    /// decode them.
+    ///
+    /// The discriminator field is only decoded by struct children that declare it, and
+    /// is otherwise skipped, even in strict mode
+    pub fn with_union_discriminator(self, union_discriminator: impl Into<String>) -> Self {
+        Self {
+            union_discriminator: Some(union_discriminator.into()),
+            ..self
+        }
+    }
+
+    /// Decode the column named `column` from the value at the JSON path `path`
+    ///
+    /// Paths are relative to the root object of each row and consist of a sequence
+    /// of field names, e.g. `$.payload.user.id` or `$['payload']['user.id']`. Missing
+    /// values, including those whose parent is not an object, are decoded as null.
+    ///
+    /// Any column without an explicit path is decoded from the field with its name,
+    /// i.e. `$.<column>`, and fields not referenced by any path are skipped without
+    /// being decoded or validated, which can be significantly faster when only a
+    /// small part of each row is needed.
+    ///
+    /// JSON paths cannot be used with [`Self::new_with_field`] or strict mode
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use arrow_array::cast::AsArray;
+    /// # use arrow_array::types::Int64Type;
+    /// # use arrow_json::ReaderBuilder;
+    /// # use arrow_schema::{DataType, Field, Schema};
+    /// let data = r#"{"payload": {"user": {"id": 1, "name": "a"}}, "level": "info"}"#;
+    /// let schema = Arc::new(Schema::new(vec![
+    ///     Field::new("user_id", DataType::Int64, true),
+    ///     Field::new("level", DataType::Utf8, true),
+    /// ]));
+    /// let mut reader = ReaderBuilder::new(schema)
+    ///     .with_json_path("user_id", "$.payload.user.id")
+    ///     .build(data.as_bytes())
+    ///     .unwrap();
+    /// let batch = reader.next().unwrap().unwrap();
+    /// assert_eq!(batch.column(0).as_primitive::<Int64Type>().value(0), 1);
+    /// assert_eq!(batch.column(1).as_string::<i32>().value(0), "info");
+    /// ```
+    pub fn with_json_path(mut self, column: impl Into<String>, path: impl Into<String>) -> Self {
+        self.json_paths.push((column.into(), path.into()));
+        self
+    }
+
+    /// Sets how records that fail to decode are handled, defaults to [`ErrorMode::Fail`]
+    ///
+    /// Any mode other than [`ErrorMode::Fail`] reports failed records through
+    /// [`Decoder::take_errors`], and assumes records are newline-delimited, resuming
+    /// after the next newline following malformed JSON
+    pub fn with_error_mode(self, error_mode: ErrorMode) -> Self {
+        Self { error_mode, ..self }
+    }
+
     /// Create a [`Reader`] with the provided [`BufRead`]
     pub fn build<R: BufRead>(self, reader: R) -> Result<Reader<R>, ArrowError> {
         Ok(Reader {
@@ -279,28 +386,118 @@ impl ReaderBuilder {
 
     /// Create a [`Decoder`]
     pub fn build_decoder(self) -> Result<Decoder, ArrowError> {
+        let null_rows = self.error_mode == ErrorMode::Null;
+        if null_rows {
+            if let Some(f) = self.schema.fields.iter().find(|f| !f.is_nullable()) {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "ErrorMode::Null requires nullable columns, but '{}' is not nullable",
+                    f.name()
+                )));
+            }
+        }
+
         let (data_type, nullable) = match self.is_field {
-            false => (DataType::Struct(self.schema.fields.clone()), false),
+            false => (DataType::Struct(self.schema.fields.clone()), null_rows),
             true => {
                 let field = &self.schema.fields[0];
                 (field.data_type().clone(), field.is_nullable())
             }
         };
 
-        let decoder = make_decoder(data_type, self.coerce_primitive, self.strict_mode, nullable)?;
-
         let num_fields = self.schema.flattened_fields().len();
+        let mut tape_decoder =
+            TapeDecoder::new(self.batch_size, num_fields).with_error_mode(self.error_mode);
+
+        let decoder: Box<dyn ArrayDecoder> = match self.json_paths.is_empty() {
+            true => make_decoder(
+                data_type,
+                self.coerce_primitive,
+                self.strict_mode,
+                self.binary_encoding,
+                self.union_discriminator.as_deref(),
+                nullable,
+            )?,
+            false => {
+                if self.is_field || self.strict_mode {
+                    return Err(ArrowError::InvalidArgumentError(
+                        "JSON paths cannot be used with new_with_field or strict mode".to_string(),
+                    ));
+                }
+
+                let fields = &self.schema.fields;
+                let mut paths: Vec<_> = fields.iter().map(|f| vec![f.name().clone()]).collect();
+                for (column, path) in &self.json_paths {
+                    paths[self.schema.index_of(column)?] = parse_json_path(path)?;
+                }
+
+                let projection = PathTree::new(paths.iter().map(|x| x.as_slice()));
+                tape_decoder = tape_decoder.with_projection(projection);
+                Box::new(ProjectionDecoder::new(
+                    fields.clone(),
+                    paths,
+                    self.coerce_primitive,
+                    self.binary_encoding,
+                    self.union_discriminator.as_deref(),
+                    null_rows,
+                )?)
+            }
+        };
 
         Ok(Decoder {
             decoder,
             is_field: self.is_field,
-            tape_decoder: TapeDecoder::new(self.batch_size, num_fields),
+            tape_decoder,
             batch_size: self.batch_size,
             schema: self.schema,
+            error_mode: self.error_mode,
+            errors: vec![],
         })
     }
 }
 
+/// The encoding of binary data, i.e. [`DataType::Binary`], [`DataType::LargeBinary`]
+/// and [`DataType::FixedSizeBinary`], within JSON strings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BinaryEncoding {
+    /// Hexadecimal encoding, as produced by [`Writer`](crate::Writer)
+    #[default]
+    Hex,
+    /// Standard base64 encoding, with padding
+    Base64,
+}
+
+/// How records that fail to decode are handled, see [`ReaderBuilder::with_error_mode`]
+///
+/// This includes both malformed JSON, and records that cannot be decoded into
+/// the schema, e.g. because of a type mismatch or an unknown column in strict mode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorMode {
+    /// Return an error, aborting the decode
+    #[default]
+    Fail,
+    /// Omit the record from the output
+    Skip,
+    /// Output a row of nulls in place of the record, requires all columns to be nullable
+    Null,
+    /// Omit the record from the output, retaining its raw bytes in [`RowError::record`]
+    Quarantine,
+}
+
+/// A record that failed to decode, see [`ErrorMode`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowError {
+    /// The index of the record in the input, counting from zero
+    pub row: usize,
+    /// The byte offset of the start of the record in the input
+    pub offset: usize,
+    /// The reason the record failed to decode
+    pub reason: String,
+    /// The raw bytes of the record, if [`ErrorMode::Quarantine`]
+    ///
+    /// For malformed JSON this includes the remainder of the line
+    pub record: Option<Vec<u8>>,
+}
+
 /// Reads JSON data with a known schema directly into arrow [`RecordBatch`]
 ///
 /// Lines consisting solely of ASCII whitespace are ignored
@@ -318,6 +515,13 @@ impl<R> std::fmt::Debug for Reader<R> {
 }
 
 impl<R: BufRead> Reader<R> {
+    /// Returns the records that failed to decode since the last call to this method
+    ///
+    /// See [`Decoder::take_errors`]
+    pub fn take_errors(&mut self) -> Vec<RowError> {
+        self.decoder.take_errors()
+    }
+
     /// Reads the next [`RecordBatch`] returning `Ok(None)` if EOF
     fn read(&mut self) -> Result<Option<RecordBatch>, ArrowError> {
         loop {
@@ -397,6 +601,8 @@ pub struct Decoder {
     batch_size: usize,
     is_field: bool,
     schema: SchemaRef,
+    error_mode: ErrorMode,
+    errors: Vec<RowError>,
 }
 
 impl std::fmt::Debug for Decoder {
@@ -599,12 +805,27 @@ impl Decoder {
         self.tape_decoder.serialize(rows)
     }
 
+    /// Returns the records that failed to decode since the last call to this method
+    ///
+    /// Always empty for [`ErrorMode::Fail`]
+    pub fn take_errors(&mut self) -> Vec<RowError> {
+        let mut errors = std::mem::take(&mut self.errors);
+        errors.extend(self.tape_decoder.take_errors());
+        errors.sort_by_key(|e| e.row);
+        errors
+    }
+
     /// Flushes the currently buffered data to a [`RecordBatch`]
     ///
     /// Returns `Ok(None)` if no buffered data
     ///
-    /// Note: if called part way through decoding a record, this will return an error
+    /// Note: if called part way through decoding a record, this will return an error,
+    /// unless the [`ErrorMode`] is not [`ErrorMode::Fail`], in which case the partial
+    /// record is treated as truncated
     pub fn flush(&mut self) -> Result<Option<RecordBatch>, ArrowError> {
+        if self.error_mode != ErrorMode::Fail {
+            self.tape_decoder.finish_row();
+        }
         let tape = self.tape_decoder.finish()?;
 
         if tape.num_rows() == 0 {
@@ -620,13 +841,61 @@ impl Decoder {
             })
             .collect();
 
-        let decoded = self.decoder.decode(&tape, &pos)?;
+        // With ErrorMode::Null the decoder accepts null rows, but a null record is not an object,
+        // unless it replaced a row that failed to parse, and has therefore already been reported
+        let reject_null = self.error_mode == ErrorMode::Null && !self.is_field;
+        let failed: Vec<u32> = match reject_null {
+            true => self
+                .tape_decoder
+                .failed_rows()
+                .iter()
+                .map(|i| pos[*i])
+                .collect(),
+            false => vec![],
+        };
+        let mut decode = |pos: &[u32]| {
+            let null = pos.iter().find(|p| {
+                reject_null
+                    && matches!(tape.get(**p), TapeElement::Null)
+                    && failed.binary_search(p).is_err()
+            });
+            match null {
+                Some(p) => Err(tape.error(*p, "{")),
+                None => self.decoder.decode(&tape, pos),
+            }
+        };
+
+        let decoded = match decode(&pos) {
+            Ok(decoded) => decoded,
+            Err(e) if self.error_mode == ErrorMode::Fail => return Err(e),
+            Err(_) => {
+                // Decode rows individually to identify those that failed
+                let mut valid = Vec::with_capacity(pos.len());
+                for (idx, p) in pos.iter().enumerate() {
+                    match decode(&[*p]) {
+                        Ok(_) => valid.push(*p),
+                        Err(e) => {
+                            self.errors.push(self.tape_decoder.row_error(idx, e));
+                            if self.error_mode == ErrorMode::Null {
+                                // Position of null sentinel
+                                valid.push(0);
+                            }
+                        }
+                    }
+                }
+                self.decoder.decode(&tape, &valid)?
+            }
+        };
         self.tape_decoder.clear();
 
         let batch = match self.is_field {
             true => RecordBatch::try_new(self.schema.clone(), vec![make_array(decoded)])?,
             false => {
-                RecordBatch::from(StructArray::from(decoded)).with_schema(self.schema.clone())?
+                // Rows of ErrorMode::Null are null in the StructArray and all its columns
+                let len = decoded.len();
+                let (_, columns, _) = StructArray::from(decoded).into_parts();
+                let options = RecordBatchOptions::new().with_row_count(Some(len));
+                RecordBatch::try_new_with_options(self.schema.clone(), columns, &options)?
             }
         };
 
@@ -649,6 +918,8 @@ fn make_decoder(
     data_type: DataType,
     coerce_primitive: bool,
     strict_mode: bool,
+    binary_encoding: BinaryEncoding,
+    union_discriminator: Option<&str>,
     is_nullable: bool,
 ) -> Result<Box<dyn ArrayDecoder>, ArrowError> {
     downcast_integer! {
@@ -696,13 +967,31 @@ fn make_decoder(
         DataType::Boolean => Ok(Box::<BooleanArrayDecoder>::default()),
         DataType::Utf8 => Ok(Box::new(StringArrayDecoder::<i32>::new(coerce_primitive))),
         DataType::LargeUtf8 => Ok(Box::new(StringArrayDecoder::<i64>::new(coerce_primitive))),
-        DataType::List(_) => Ok(Box::new(ListArrayDecoder::<i32>::new(data_type, coerce_primitive, strict_mode, is_nullable)?)),
-        DataType::LargeList(_) => Ok(Box::new(ListArrayDecoder::<i64>::new(data_type, coerce_primitive, strict_mode, is_nullable)?)),
-        DataType::Struct(_) => Ok(Box::new(StructArrayDecoder::new(data_type, coerce_primitive, strict_mode, is_nullable)?)),
-        DataType::Binary | DataType::LargeBinary | DataType::FixedSizeBinary(_) => {
-            Err(ArrowError::JsonError(format!("{data_type} is not supported by JSON")))
+        DataType::Utf8View => Ok(Box::new(StringViewArrayDecoder::new(coerce_primitive))),
+        DataType::Duration(TimeUnit::Second) => Ok(Box::new(DurationArrayDecoder::<DurationSecondType>::new())),
+        DataType::Duration(TimeUnit::Millisecond) => Ok(Box::new(DurationArrayDecoder::<DurationMillisecondType>::new())),
+        DataType::Duration(TimeUnit::Microsecond) => Ok(Box::new(DurationArrayDecoder::<DurationMicrosecondType>::new())),
+        DataType::Duration(TimeUnit::Nanosecond) => Ok(Box::new(DurationArrayDecoder::<DurationNanosecondType>::new())),
+        DataType::Interval(IntervalUnit::YearMonth) => {
+            Ok(Box::new(IntervalArrayDecoder::<IntervalYearMonthType>::new(parse_interval_year_month)))
+        }
+        DataType::Interval(IntervalUnit::DayTime) => {
+            Ok(Box::new(IntervalArrayDecoder::<IntervalDayTimeType>::new(parse_interval_day_time)))
+        }
+        DataType::Interval(IntervalUnit::MonthDayNano) => {
+            Ok(Box::new(IntervalArrayDecoder::<IntervalMonthDayNanoType>::new(parse_interval_month_day_nano)))
         }
-        DataType::Map(_, _) => Ok(Box::new(MapArrayDecoder::new(data_type, coerce_primitive, strict_mode, is_nullable)?)),
+        DataType::List(_) => Ok(Box::new(ListArrayDecoder::<i32>::new(data_type, coerce_primitive, strict_mode, binary_encoding, union_discriminator, is_nullable)?)),
+        DataType::LargeList(_) => Ok(Box::new(ListArrayDecoder::<i64>::new(data_type, coerce_primitive, strict_mode, binary_encoding, union_discriminator, is_nullable)?)),
+        DataType::FixedSizeList(_, _) => Ok(Box::new(FixedSizeListArrayDecoder::new(data_type, coerce_primitive, strict_mode, binary_encoding, union_discriminator, is_nullable)?)),
+        DataType::Struct(_) => Ok(Box::new(StructArrayDecoder::new(data_type, coerce_primitive, strict_mode, binary_encoding, union_discriminator, is_nullable)?)),
+        DataType::Binary => Ok(Box::new(BinaryArrayDecoder::<i32>::new(binary_encoding))),
+        DataType::LargeBinary => Ok(Box::new(BinaryArrayDecoder::<i64>::new(binary_encoding))),
+        DataType::FixedSizeBinary(size) => Ok(Box::new(FixedSizeBinaryArrayDecoder::new(size, binary_encoding))),
+        DataType::Dictionary(_, _) => make_dictionary_decoder(data_type, coerce_primitive, strict_mode, binary_encoding, union_discriminator, is_nullable),
+        DataType::RunEndEncoded(_, _) => make_run_end_decoder(data_type, coerce_primitive, strict_mode, binary_encoding, union_discriminator),
+        DataType::Map(_, _) => Ok(Box::new(MapArrayDecoder::new(data_type, coerce_primitive, strict_mode, binary_encoding, union_discriminator, is_nullable)?)),
+        DataType::Union(_, _) => Ok(Box::new(UnionArrayDecoder::new(data_type, coerce_primitive, strict_mode, binary_encoding, union_discriminator)?)),
         d => Err(ArrowError::NotYetImplemented(format!("Support for {d} in JSON reader")))
     }
 }
@@ -713,12 +1002,16 @@ mod tests {
     use std::fs::File;
     use std::io::{BufReader, Cursor, Seek};
 
-    use arrow_array::cast::AsArray;
-    use arrow_array::{Array, BooleanArray, Float64Array, ListArray, StringArray};
+    use arrow_array::cast::{as_run_array, AsArray};
+    use arrow_array::{
+        Array, BinaryArray, BooleanArray, DictionaryArray, DurationMicrosecondArray,
+        FixedSizeBinaryArray, FixedSizeListArray, Float64Array, Int64Array, ListArray, StringArray,
+        StringViewArray,
+    };
     use arrow_buffer::{ArrowNativeType, Buffer};
     use arrow_cast::display::{ArrayFormatter, FormatOptions};
     use arrow_data::ArrayDataBuilder;
-    use arrow_schema::Field;
+    use arrow_schema::{Field, UnionFields, UnionMode};
 
     use super::*;
 
@@ -2316,4 +2609,748 @@ mod tests {
             .unwrap()
         );
     }
+
+    #[test]
+    fn test_binary() {
+        let buf = r#"
+        {"a": "00ff", "b": "", "c": "0102"}
+        {"a": null, "b": "DEADbeef", "c": null}
+        "#;
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Binary, true),
+            Field::new("b", DataType::LargeBinary, true),
+            Field::new("c", DataType::FixedSizeBinary(2), true),
+        ]));
+
+        let batches = do_read(buf, 1024, false, false, schema.clone());
+        assert_eq!(batches.len(), 1);
+
+        let a = batches[0].column(0).as_binary::<i32>();
+        assert_eq!(a.iter().collect::<Vec<_>>(), &[Some(&[0, 255][..]), None]);
+        let b = batches[0].column(1).as_binary::<i64>();
+        assert_eq!(
+            b.iter().collect::<Vec<_>>(),
+            &[Some(&[][..]), Some(&[0xDE, 0xAD, 0xBE, 0xEF][..])]
+        );
+        let c = batches[0].column(2).as_fixed_size_binary();
+        assert_eq!(c.iter().collect::<Vec<_>>(), &[Some(&[1, 2][..]), None]);
+
+        let buf = r#"{"a": "AP8=", "b": "", "c": "AQI="}"#;
+        let batch = ReaderBuilder::new(schema.clone())
+            .with_binary_encoding(BinaryEncoding::Base64)
+            .build(Cursor::new(buf.as_bytes()))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(batch.column(0).as_binary::<i32>().value(0), &[0, 255]);
+        assert_eq!(batch.column(1).as_binary::<i64>().value(0), b"");
+        assert_eq!(batch.column(2).as_fixed_size_binary().value(0), &[1, 2]);
+
+        for (buf, expected) in [
+            (
+                r#"{"a": "0"}"#,
+                "Json error: whilst decoding field 'a': failed to decode \"0\" as Hex binary",
+            ),
+            (
+                r#"{"a": "zz"}"#,
+                "Json error: whilst decoding field 'a': failed to decode \"zz\" as Hex binary",
+            ),
+            (
+                r#"{"c": "010203"}"#,
+                "Json error: whilst decoding field 'c': expected 2 bytes decoding \"010203\" as FixedSizeBinary(2) got 3",
+            ),
+            (r#"{"a": 1}"#, "Json error: whilst decoding field 'a': expected binary string got 1"),
+        ] {
+            let err = ReaderBuilder::new(schema.clone())
+                .build(Cursor::new(buf.as_bytes()))
+                .unwrap()
+                .next()
+                .unwrap()
+                .unwrap_err();
+            assert_eq!(err.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_string_view() {
+        let buf = r#"
+        {"a": "hello", "b": "a string longer than twelve bytes"}
+        {"a": null, "b": 1}
+        {"a": "", "b": true}
+        "#;
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Utf8View, true),
+            Field::new("b", DataType::Utf8View, true),
+        ]));
+
+        let batches = do_read(buf, 1024, true, false, schema);
+        assert_eq!(batches.len(), 1);
+
+        let a = batches[0].column(0).as_string_view();
+        assert_eq!(
+            a.iter().collect::<Vec<_>>(),
+            &[Some("hello"), None, Some("")]
+        );
+        let b = batches[0].column(1).as_string_view();
+        assert_eq!(
+            b.iter().collect::<Vec<_>>(),
+            &[
+                Some("a string longer than twelve bytes"),
+                Some("1"),
+                Some("true")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dictionary() {
+        let buf = r#"
+        {"a": "foo", "b": 1}
+        {"a": "bar", "b": 2}
+        {"a": "foo", "b": 1}
+        {"a": null, "b": null}
+        "#;
+        let schema = Arc::new(Schema::new(vec![
+            Field::new_dictionary("a", DataType::Int16, DataType::Utf8, true),
+            Field::new_dictionary("b", DataType::UInt8, DataType::Int64, true),
+        ]));
+
+        let batches = do_read(buf, 1024, false, false, schema.clone());
+        assert_eq!(batches.len(), 1);
+
+        let a = batches[0].column(0).as_dictionary::<Int16Type>();
+        assert_eq!(
+            a.keys().iter().collect::<Vec<_>>(),
+            &[Some(0), Some(1), Some(0), None]
+        );
+        let a_values = a.values().as_string::<i32>();
+        assert_eq!(
+            a_values.iter().collect::<Vec<_>>(),
+            &[Some("foo"), Some("bar")]
+        );
+
+        let b = batches[0].column(1).as_dictionary::<UInt8Type>();
+        assert_eq!(b.data_type(), schema.field(1).data_type());
+        assert_eq!(b.values().len(), 2);
+        let b = b.downcast_dict::<Int64Array>().unwrap();
+        assert_eq!(
+            b.into_iter().collect::<Vec<_>>(),
+            &[Some(1), Some(2), Some(1), None]
+        );
+
+        // Key overflow
+        let buf = (0..200)
+            .map(|x| format!(r#"{{"a": "{x}"}}"#))
+            .collect::<String>();
+        let schema = Arc::new(Schema::new(vec![Field::new_dictionary(
+            "a",
+            DataType::Int8,
+            DataType::Utf8,
+            true,
+        )]));
+        let err = ReaderBuilder::new(schema)
+            .build(Cursor::new(buf.as_bytes()))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Json error: whilst decoding field 'a': key overflow decoding Dictionary(Int8, Utf8)"
+        );
+    }
+
+    #[test]
+    fn test_duration_interval() {
+        let buf = r#"
+        {"a": "PT120S", "b": "PT0.12S", "c": 5, "d": "1 mons 2 days 0.000000003 secs", "e": "1 years 2 mons"}
+        {"a": null, "b": "-P1DT1H", "c": "PT0.000001S", "d": "", "e": null}
+        "#;
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Duration(TimeUnit::Second), true),
+            Field::new("b", DataType::Duration(TimeUnit::Millisecond), true),
+            Field::new("c", DataType::Duration(TimeUnit::Nanosecond), true),
+            Field::new("d", DataType::Interval(IntervalUnit::MonthDayNano), true),
+            Field::new("e", DataType::Interval(IntervalUnit::YearMonth), true),
+        ]));
+
+        let batches = do_read(buf, 1024, false, false, schema.clone());
+        assert_eq!(batches.len(), 1);
+
+        let a = batches[0].column(0).as_primitive::<DurationSecondType>();
+        assert_eq!(a.iter().collect::<Vec<_>>(), &[Some(120), None]);
+        let b = batches[0]
+            .column(1)
+            .as_primitive::<DurationMillisecondType>();
+        assert_eq!(b.values(), &[120, -90_000_000]);
+        let c = batches[0]
+            .column(2)
+            .as_primitive::<DurationNanosecondType>();
+        assert_eq!(c.values(), &[5, 1000]);
+        let d = batches[0]
+            .column(3)
+            .as_primitive::<IntervalMonthDayNanoType>();
+        assert_eq!(
+            d.values(),
+            &[
+                IntervalMonthDayNanoType::make_value(1, 2, 3),
+                IntervalMonthDayNanoType::make_value(0, 0, 0)
+            ]
+        );
+        let e = batches[0].column(4).as_primitive::<IntervalYearMonthType>();
+        assert_eq!(e.iter().collect::<Vec<_>>(), &[Some(14), None]);
+
+        let buf = r#"{"a": "PT0.5S"}"#;
+        let err = ReaderBuilder::new(schema)
+            .build(Cursor::new(buf.as_bytes()))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Json error: whilst decoding field 'a': failed to parse \"PT0.5S\" as Duration(Second)"
+        );
+    }
+
+    #[test]
+    fn test_fixed_size_list() {
+        let buf = r#"
+        {"a": [1, 2]}
+        {"a": null}
+        {"a": [3, null]}
+        "#;
+        let field = Arc::new(Field::new("item", DataType::Int32, true));
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "a",
+            DataType::FixedSizeList(field, 2),
+            true,
+        )]));
+
+        let batches = do_read(buf, 1024, false, false, schema.clone());
+        assert_eq!(batches.len(), 1);
+
+        let a = batches[0].column(0).as_fixed_size_list();
+        assert_eq!(
+            a.nulls().unwrap().iter().collect::<Vec<_>>(),
+            &[true, false, true]
+        );
+        let values = a.values().as_primitive::<Int32Type>();
+        assert_eq!(
+            values.iter().collect::<Vec<_>>(),
+            &[Some(1), Some(2), None, None, Some(3), None]
+        );
+
+        let buf = r#"{"a": [1, 2, 3]}"#;
+        let err = ReaderBuilder::new(schema)
+            .build(Cursor::new(buf.as_bytes()))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Json error: whilst decoding field 'a': expected 2 values for fixed size list got 3"
+        );
+    }
+
+    #[test]
+    fn test_run_end_encoded() {
+        let buf = r#"
+        {"a": "x", "b": 1}
+        {"a": "x", "b": 1}
+        {"a": null, "b": 1}
+        {"a": null}
+        {"a": "y", "b": 2}
+        "#;
+        let run_ends = Arc::new(Field::new("run_ends", DataType::Int32, false));
+        let values = Arc::new(Field::new("values", DataType::Utf8, true));
+        let int_run_ends = Arc::new(Field::new("run_ends", DataType::Int16, false));
+        let int_values = Arc::new(Field::new("values", DataType::Int64, true));
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::RunEndEncoded(run_ends, values), false),
+            Field::new("b", DataType::RunEndEncoded(int_run_ends, int_values), true),
+        ]));
+
+        let batches = ReaderBuilder::new(schema)
+            .build(Cursor::new(buf.as_bytes()))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(batches.len(), 1);
+
+        let a = as_run_array::<Int32Type>(batches[0].column(0));
+        assert_eq!(a.len(), 5);
+        assert_eq!(a.run_ends().values(), &[2, 4, 5]);
+        let values = a.values().as_string::<i32>();
+        assert_eq!(
+            values.iter().collect::<Vec<_>>(),
+            &[Some("x"), None, Some("y")]
+        );
+
+        let b = as_run_array::<Int16Type>(batches[0].column(1));
+        assert_eq!(b.run_ends().values(), &[3, 4, 5]);
+        let values = b.values().as_primitive::<Int64Type>();
+        assert_eq!(values.iter().collect::<Vec<_>>(), &[Some(1), None, Some(2)]);
+    }
+
+    #[test]
+    fn test_write_read_round_trip() {
+        let list_field = Arc::new(Field::new("item", DataType::Int64, true));
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("binary", DataType::Binary, true),
+            Field::new("fixed", DataType::FixedSizeBinary(3), true),
+            Field::new("view", DataType::Utf8View, true),
+            Field::new_dictionary("dict", DataType::Int32, DataType::Utf8, true),
+            Field::new("duration", DataType::Duration(TimeUnit::Microsecond), true),
+            Field::new("list", DataType::FixedSizeList(list_field, 2), true),
+        ]));
+
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(BinaryArray::from_opt_vec(vec![
+                    Some(&b"ab"[..]),
+                    None,
+                    Some(&b""[..]),
+                ])),
+                Arc::new(
+                    FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+                        [Some(b"abc"), Some(b"def"), None].into_iter(),
+                        3,
+                    )
+                    .unwrap(),
+                ),
+                Arc::new(StringViewArray::from(vec![Some("a"), None, Some("c")])),
+                Arc::new(DictionaryArray::<Int32Type>::from_iter([
+                    Some("x"),
+                    Some("y"),
+                    None,
+                ])),
+                Arc::new(DurationMicrosecondArray::from(vec![
+                    Some(1),
+                    Some(-86_400_000_000),
+                    None,
+                ])),
+                Arc::new(FixedSizeListArray::from_iter_primitive::<Int64Type, _, _>(
+                    [
+                        Some(vec![Some(1), None]),
+                        None,
+                        Some(vec![Some(3), Some(4)]),
+                    ],
+                    2,
+                )),
+            ],
+        )
+        .unwrap();
+
+        let mut buf = Vec::new();
+        let mut writer = crate::LineDelimitedWriter::new(&mut buf);
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+
+        let read = ReaderBuilder::new(schema)
+            .build(Cursor::new(buf))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+
+        // The JSON writer does not preserve the values of null list entries
+        let (a, b) = (read.column(5), batch.column(5));
+        assert_eq!(a.nulls(), b.nulls());
+        for (a, b) in read.columns().iter().zip(batch.columns()).take(5) {
+            assert_eq!(a, b);
+        }
+    }
+
+    fn read_union(buf: &str, builder: ReaderBuilder) -> Result<RecordBatch, ArrowError> {
+        builder.build(Cursor::new(buf.as_bytes()))?.next().unwrap()
+    }
+
+    #[test]
+    fn test_union() {
+        let buf = r#"
+        {"u": 1}
+        {"u": "x"}
+        {"u": {"a": 2}}
+        {"u": null}
+        {"u": 3}
+        "#;
+        let fields = UnionFields::new(
+            [0, 1, 2],
+            [
+                Field::new("int", DataType::Int64, true),
+                Field::new("str", DataType::Utf8, true),
+                Field::new_struct("obj", vec![Field::new("a", DataType::Int64, true)], true),
+            ],
+        );
+
+        let dense = DataType::Union(fields.clone(), UnionMode::Dense);
+        let schema = Arc::new(Schema::new(vec![Field::new("u", dense, false)]));
+        let batch = read_union(buf, ReaderBuilder::new(schema)).unwrap();
+
+        let u = batch.column(0).as_union();
+        assert_eq!(u.type_ids().as_ref(), &[0, 1, 2, 0, 0]);
+        assert_eq!(u.offsets().unwrap().as_ref(), &[0, 0, 0, 1, 2]);
+        let ints = u.child(0).as_primitive::<Int64Type>();
+        assert_eq!(ints.iter().collect::<Vec<_>>(), &[Some(1), None, Some(3)]);
+        assert_eq!(u.child(1).as_string::<i32>().value(0), "x");
+        let obj = u.child(2).as_struct();
+        assert_eq!(obj.column(0).as_primitive::<Int64Type>().value(0), 2);
+
+        let sparse = DataType::Union(fields, UnionMode::Sparse);
+        let schema = Arc::new(Schema::new(vec![Field::new("u", sparse, false)]));
+        let batch = read_union(buf, ReaderBuilder::new(schema)).unwrap();
+
+        let u = batch.column(0).as_union();
+        assert_eq!(u.type_ids().as_ref(), &[0, 1, 2, 0, 0]);
+        assert!(u.offsets().is_none());
+        let ints = u.child(0).as_primitive::<Int64Type>();
+        assert_eq!(
+            ints.iter().collect::<Vec<_>>(),
+            &[Some(1), None, None, None, Some(3)]
+        );
+        let strings = u.child(1).as_string::<i32>();
+        assert_eq!(
+            strings.iter().collect::<Vec<_>>(),
+            &[None, Some("x"), None, None, None]
+        );
+        assert_eq!(u.child(2).null_count(), 4);
+
+        // Children are tried in declaration order, regardless of the kind of value
+        let fields = UnionFields::new(
+            [0, 1],
+            [
+                Field::new("int", DataType::Int64, true),
+                Field::new("str", DataType::Utf8, true),
+            ],
+        );
+        let union = DataType::Union(fields, UnionMode::Dense);
+        let schema = Arc::new(Schema::new(vec![Field::new("u", union, false)]));
+        let buf = r#"{"u": "5"} {"u": "x"} {"u": 6}"#;
+        let batch = read_union(buf, ReaderBuilder::new(schema)).unwrap();
+        let u = batch.column(0).as_union();
+        assert_eq!(u.type_ids().as_ref(), &[0, 1, 0]);
+        let ints = u.child(0).as_primitive::<Int64Type>();
+        assert_eq!(ints.values(), &[5, 6]);
+
+        let fields = UnionFields::new(
+            [0, 1],
+            [
+                Field::new("int", DataType::Int64, true),
+                Field::new("bool", DataType::Boolean, true),
+            ],
+        );
+        let union = DataType::Union(fields, UnionMode::Dense);
+        let schema = Arc::new(Schema::new(vec![Field::new("u", union, false)]));
+        let err = read_union(r#"{"u": [1]}"#, ReaderBuilder::new(schema)).unwrap_err();
+        assert!(err.to_string().contains("got [1]"), "{err}");
+    }
+
+    #[test]
+    fn test_union_discriminator() {
+        let buf = r#"
+        {"shape": {"type": "square", "side": 2.0}}
+        {"shape": {"type": "circle", "radius": 1.5}}
+        {"shape": {"radius": 3.0}}
+        "#;
+        let fields = UnionFields::new(
+            [0, 1],
+            [
+                Field::new_struct(
+                    "circle",
+                    vec![
+                        Field::new("type", DataType::Utf8, true),
+                        Field::new("radius", DataType::Float64, true),
+                    ],
+                    false,
+                ),
+                Field::new_struct(
+                    "square",
+                    vec![
+                        Field::new("type", DataType::Utf8, true),
+                        Field::new("side", DataType::Float64, true),
+                    ],
+                    false,
+                ),
+            ],
+        );
+        let union = DataType::Union(fields, UnionMode::Dense);
+        let schema = Arc::new(Schema::new(vec![Field::new("shape", union, false)]));
+
+        // Without a discriminator every object is accepted by the first child
+        let batch = read_union(buf, ReaderBuilder::new(schema.clone())).unwrap();
+        assert_eq!(batch.column(0).as_union().type_ids().as_ref(), &[0, 0, 0]);
+
+        let builder = ReaderBuilder::new(schema.clone()).with_union_discriminator("type");
+        let batch = read_union(buf, builder).unwrap();
+        let u = batch.column(0).as_union();
+        assert_eq!(u.type_ids().as_ref(), &[1, 0, 0]);
+        assert_eq!(u.offsets().unwrap().as_ref(), &[0, 0, 1]);
+        let square = u.child(1).as_struct();
+        assert_eq!(square.column(1).as_primitive::<Float64Type>().value(0), 2.0);
+        let circle = u.child(0).as_struct();
+        let radius = circle.column(1).as_primitive::<Float64Type>();
+        assert_eq!(radius.values(), &[1.5, 3.0]);
+
+        let builder = ReaderBuilder::new(schema).with_union_discriminator("type");
+        let buf = r#"{"shape": {"type": "triangle"}}"#;
+        let err = read_union(buf, builder).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unknown union discriminator 'triangle'"),
+            "{err}"
+        );
+
+        // In strict mode the discriminator need not be a field of the children
+        let fields = UnionFields::new(
+            [0, 1],
+            [
+                Field::new_struct(
+                    "circle",
+                    vec![Field::new("radius", DataType::Float64, true)],
+                    false,
+                ),
+                Field::new_struct(
+                    "square",
+                    vec![Field::new("side", DataType::Float64, true)],
+                    false,
+                ),
+            ],
+        );
+        let union = DataType::Union(fields, UnionMode::Dense);
+        let schema = Arc::new(Schema::new(vec![Field::new("shape", union, false)]));
+        let buf = r#"
+        {"shape": {"type": "square", "side": 2.0}}
+        {"shape": {"type": "circle", "radius": 1.5}}
+        "#;
+        let builder = ReaderBuilder::new(schema)
+            .with_strict_mode(true)
+            .with_union_discriminator("type");
+        let batch = read_union(buf, builder).unwrap();
+        let u = batch.column(0).as_union();
+        assert_eq!(u.type_ids().as_ref(), &[1, 0]);
+        let square = u.child(1).as_struct();
+        assert_eq!(square.column(0).as_primitive::<Float64Type>().value(0), 2.0);
+    }
+
+    #[test]
+    fn test_json_paths() {
+        let buf = r#"
+        {"payload": {"user": {"id": 1, "tags": ["a"]}, "body": "{\"x\": 1}"}, "level": "info"}
+        {"level": "warn", "payload": {"user": {"id": 2}, "extra": [[{}], "]"]}}
+        {"payload": {"user": "anonymous"}}
+        {"payload": {"user": {"id": 4, "id": 5}}, "other": {"level": "error"}}
+        "#;
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("level", DataType::Utf8, true),
+            Field::new_list("tags", Field::new("item", DataType::Utf8, true), true),
+        ]));
+
+        let read = |batch_size| {
+            ReaderBuilder::new(schema.clone())
+                .with_batch_size(batch_size)
+                .with_json_path("id", "$.payload.user.id")
+                .with_json_path("tags", "$['payload']['user']['tags']")
+                .build(Cursor::new(buf.as_bytes()))
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        };
+
+        let batches = read(3);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].num_rows(), 3);
+        assert_eq!(batches[1].num_rows(), 1);
+
+        let batches = read(1024);
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+
+        let id = batch.column(0).as_primitive::<Int64Type>();
+        assert_eq!(
+            id.iter().collect::<Vec<_>>(),
+            &[Some(1), Some(2), None, Some(5)]
+        );
+        let level = batch.column(1).as_string::<i32>();
+        assert_eq!(
+            level.iter().collect::<Vec<_>>(),
+            &[Some("info"), Some("warn"), None, None]
+        );
+        let tags = batch.column(2).as_list::<i32>();
+        assert_eq!(tags.null_count(), 3);
+        assert_eq!(tags.value(0).as_string::<i32>().value(0), "a");
+
+        // `$` decodes the entire row, alongside other paths
+        let row = vec![Field::new("level", DataType::Utf8, true)];
+        let root_schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new_struct("row", row, true),
+        ]));
+        let batch = ReaderBuilder::new(root_schema)
+            .with_json_path("id", "$.payload.user.id")
+            .with_json_path("row", "$")
+            .build(Cursor::new(buf.as_bytes()))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let row = batch.column(1).as_struct();
+        assert_eq!(row.null_count(), 0);
+        assert_eq!(
+            row.column(0).as_string::<i32>().iter().collect::<Vec<_>>(),
+            &[Some("info"), Some("warn"), None, None]
+        );
+        let id = batch.column(0).as_primitive::<Int64Type>();
+        assert_eq!(id.value(1), 2);
+
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let err = ReaderBuilder::new(schema.clone())
+            .with_json_path("id", "$.payload.user.id")
+            .build(Cursor::new(buf.as_bytes()))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Json error: Encountered nulls in non-nullable field 'id'"
+        );
+
+        let err = ReaderBuilder::new(schema.clone())
+            .with_json_path("missing", "$.a")
+            .build_decoder()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Schema error: Unable to get field named \"missing\". Valid fields: [\"id\"]"
+        );
+
+        let err = ReaderBuilder::new(schema.clone())
+            .with_json_path("id", "$.a")
+            .with_strict_mode(true)
+            .build_decoder()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: JSON paths cannot be used with new_with_field or strict mode"
+        );
+
+        let err = ReaderBuilder::new(schema)
+            .with_json_path("id", "payload")
+            .build_decoder()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: Invalid JSON path 'payload': expected '$'"
+        );
+    }
+
+    #[test]
+    fn test_error_mode() {
+        let lines = [
+            r#"{"a": 1, "b": "x"}"#,
+            r#"{"a": tru, "b": "y"}"#,
+            r#"{"a": "nope"}"#,
+            r#"not json"#,
+            r#"{"a": 4, "c": 1}"#,
+            r#"null"#,
+            r#"{"a": 5"#,
+        ];
+        let buf = lines.join("\n");
+        let offset = |row: usize| lines[..row].iter().map(|l| l.len() + 1).sum::<usize>();
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        let builder = |mode| {
+            ReaderBuilder::new(schema.clone())
+                .with_strict_mode(true)
+                .with_error_mode(mode)
+        };
+
+        let reasons = [
+            (1, "Encountered unexpected ',' whilst parsing literal"),
+            (
+                2,
+                "whilst decoding field 'a': failed to parse \"nope\" as Int64",
+            ),
+            (3, "Encountered unexpected 'o' whilst parsing literal"),
+            (4, "column 'c' missing from schema"),
+            (5, "expected { got null"),
+            (6, "Truncated record whilst reading number"),
+        ];
+        let expected = |quarantine: bool| {
+            reasons
+                .iter()
+                .map(|(row, reason)| RowError {
+                    row: *row,
+                    offset: offset(*row),
+                    reason: reason.to_string(),
+                    record: quarantine.then(|| lines[*row].as_bytes().to_vec()),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let err = builder(ErrorMode::Fail)
+            .build(Cursor::new(buf.as_bytes()))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Json error: Encountered unexpected ',' whilst parsing literal"
+        );
+
+        let mut reader = builder(ErrorMode::Skip)
+            .build(Cursor::new(buf.as_bytes()))
+            .unwrap();
+        let batch = reader.next().unwrap().unwrap();
+        assert!(reader.next().is_none());
+        assert_eq!(batch.num_rows(), 1);
+        assert_eq!(batch.column(0).as_primitive::<Int64Type>().value(0), 1);
+        assert_eq!(batch.column(1).as_string::<i32>().value(0), "x");
+        assert_eq!(reader.take_errors(), expected(false));
+        assert!(reader.take_errors().is_empty());
+
+        let mut reader = builder(ErrorMode::Null)
+            .build(Cursor::new(buf.as_bytes()))
+            .unwrap();
+        let batch = reader.next().unwrap().unwrap();
+        assert_eq!(batch.num_rows(), 7);
+        let a = batch.column(0).as_primitive::<Int64Type>();
+        assert_eq!(
+            a.iter().collect::<Vec<_>>(),
+            &[Some(1), None, None, None, None, None, None]
+        );
+        assert_eq!(batch.column(1).null_count(), 6);
+        assert_eq!(reader.take_errors(), expected(false));
+
+        // Decode a byte at a time to exercise recovery across buffer boundaries
+        let mut decoder = builder(ErrorMode::Quarantine).build_decoder().unwrap();
+        let mut rows = 0;
+        for b in buf.as_bytes().chunks(1) {
+            assert_eq!(decoder.decode(b).unwrap(), 1);
+        }
+        while let Some(batch) = decoder.flush().unwrap() {
+            rows += batch.num_rows();
+        }
+        assert_eq!(rows, 1);
+        assert_eq!(decoder.take_errors(), expected(true));
+
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)]));
+        let err = ReaderBuilder::new(schema)
+            .with_error_mode(ErrorMode::Null)
+            .build_decoder()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument error: ErrorMode::Null requires nullable columns, but 'a' is not nullable"
+        );
+    }
 }
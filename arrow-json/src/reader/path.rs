@@ -0,0 +1,313 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Support for projecting columns from [JSONPath]-style expressions
+//!
+//! [JSONPath]: https://datatracker.ietf.org/doc/html/rfc9535
+
+use arrow_array::builder::BooleanBufferBuilder;
+use arrow_buffer::buffer::NullBuffer;
+use arrow_data::{ArrayData, ArrayDataBuilder};
+use arrow_schema::{ArrowError, DataType, Fields};
+
+use crate::reader::tape::{Tape, TapeElement};
+use crate::reader::{make_decoder, ArrayDecoder, BinaryEncoding};
+
+/// Parses a path of the form `$.a.b`, or `$['a']['b']`, into its field names
+pub fn parse_json_path(path: &str) -> Result<Vec<String>, ArrowError> {
+    let invalid = |reason: &str| {
+        ArrowError::InvalidArgumentError(format!("Invalid JSON path '{path}': {reason}"))
+    };
+
+    let mut rest = path
+        .strip_prefix('$')
+        .ok_or_else(|| invalid("expected '$'"))?;
+
+    let mut segments = vec![];
+    while !rest.is_empty() {
+        if let Some(r) = rest.strip_prefix('.') {
+            let end = r.find(['.', '[']).unwrap_or(r.len());
+            if end == 0 {
+                return Err(invalid("empty field name"));
+            }
+            segments.push(r[..end].to_string());
+            rest = &r[end..];
+        } else if let Some(r) = rest.strip_prefix('[') {
+            let quote = match r.chars().next() {
+                Some(q @ ('\'' | '"')) => q,
+                _ => return Err(invalid("expected quoted field name")),
+            };
+            let r = &r[1..];
+            let end = r
+                .find(quote)
+                .ok_or_else(|| invalid("unterminated field name"))?;
+            segments.push(r[..end].to_string());
+            rest = r[end + 1..]
+                .strip_prefix(']')
+                .ok_or_else(|| invalid("expected ']'"))?;
+        } else {
+            return Err(invalid("expected '.' or '['"));
+        }
+    }
+    Ok(segments)
+}
+
+/// A tree of the object fields referenced by a set of JSON paths
+///
+/// Used by [`TapeDecoder`](super::tape::TapeDecoder) to skip unreferenced fields
+#[derive(Debug)]
+pub struct PathTree {
+    nodes: Vec<PathNode>,
+}
+
+#[derive(Debug, Default)]
+struct PathNode {
+    /// If the entire value at this node is referenced
+    keep: bool,
+    children: Vec<(String, u32)>,
+}
+
+impl PathTree {
+    /// Create a new [`PathTree`] from the provided paths
+    pub fn new<'a>(paths: impl IntoIterator<Item = &'a [String]>) -> Self {
+        let mut nodes = vec![PathNode::default()];
+        for path in paths {
+            let mut node = 0;
+            for segment in path {
+                if nodes[node].keep {
+                    break;
+                }
+                let child = nodes[node].children.iter().find(|(k, _)| k == segment);
+                node = match child {
+                    Some((_, c)) => *c as usize,
+                    None => {
+                        let c = nodes.len();
+                        nodes.push(PathNode::default());
+                        nodes[node].children.push((segment.clone(), c as u32));
+                        c
+                    }
+                };
+            }
+            nodes[node].keep = true;
+            nodes[node].children.clear();
+        }
+        Self { nodes }
+    }
+
+    /// Returns the root node
+    pub fn root(&self) -> u32 {
+        0
+    }
+
+    /// Returns true if the entire value at `node` is referenced
+    pub fn keep(&self, node: u32) -> bool {
+        self.nodes[node as usize].keep
+    }
+
+    /// Returns the child of `node` for the field `name`, if referenced
+    pub fn child(&self, node: u32, name: &[u8]) -> Option<u32> {
+        let children = &self.nodes[node as usize].children;
+        children
+            .iter()
+            .find(|(k, _)| k.as_bytes() == name)
+            .map(|(_, c)| *c)
+    }
+}
+
+/// Decodes the root object of each row into columns identified by JSON paths
+pub struct ProjectionDecoder {
+    fields: Fields,
+    paths: Vec<Vec<String>>,
+    decoders: Vec<Box<dyn ArrayDecoder>>,
+    is_nullable: bool,
+}
+
+impl ProjectionDecoder {
+    pub fn new(
+        fields: Fields,
+        paths: Vec<Vec<String>>,
+        coerce_primitive: bool,
+        binary_encoding: BinaryEncoding,
+        union_discriminator: Option<&str>,
+        is_nullable: bool,
+    ) -> Result<Self, ArrowError> {
+        let decoders = fields
+            .iter()
+            .map(|f| {
+                make_decoder(
+                    f.data_type().clone(),
+                    coerce_primitive,
+                    false,
+                    binary_encoding,
+                    union_discriminator,
+                    f.is_nullable() || is_nullable,
+                )
+            })
+            .collect::<Result<Vec<_>, ArrowError>>()?;
+
+        Ok(Self {
+            fields,
+            paths,
+            decoders,
+            is_nullable,
+        })
+    }
+}
+
+/// Returns the tape index of the value at `path` relative to `idx`, or `0`,
+/// the index of the null sentinel, if not present
+fn resolve(tape: &Tape<'_>, mut idx: u32, path: &[String]) -> Result<u32, ArrowError> {
+    for segment in path {
+        let end_idx = match tape.get(idx) {
+            TapeElement::StartObject(end_idx) => end_idx,
+            _ => return Ok(0),
+        };
+
+        let mut found = 0;
+        let mut cur_idx = idx + 1;
+        while cur_idx < end_idx {
+            let field_name = match tape.get(cur_idx) {
+                TapeElement::String(s) => tape.get_string(s),
+                _ => return Err(tape.error(cur_idx, "field name")),
+            };
+            if field_name == segment {
+                found = cur_idx + 1;
+            }
+            cur_idx = tape.next(cur_idx + 1, "field value")?;
+        }
+
+        if found == 0 {
+            return Ok(0);
+        }
+        idx = found;
+    }
+    Ok(idx)
+}
+
+impl ArrayDecoder for ProjectionDecoder {
+    fn decode(&mut self, tape: &Tape<'_>, pos: &[u32]) -> Result<ArrayData, ArrowError> {
+        let mut child_pos: Vec<_> = (0..self.fields.len())
+            .map(|_| Vec::with_capacity(pos.len()))
+            .collect();
+
+        let mut nulls = self
+            .is_nullable
+            .then(|| BooleanBufferBuilder::new(pos.len()));
+
+        for p in pos {
+            match (tape.get(*p), nulls.as_mut()) {
+                (TapeElement::StartObject(_), nulls) => {
+                    if let Some(nulls) = nulls {
+                        nulls.append(true);
+                    }
+                    for (path, positions) in self.paths.iter().zip(&mut child_pos) {
+                        positions.push(resolve(tape, *p, path)?);
+                    }
+                }
+                (TapeElement::Null, Some(nulls)) => {
+                    nulls.append(false);
+                    child_pos.iter_mut().for_each(|x| x.push(0));
+                }
+                _ => return Err(tape.error(*p, "{")),
+            }
+        }
+
+        let child_data = self
+            .decoders
+            .iter_mut()
+            .zip(child_pos)
+            .zip(self.fields.iter())
+            .map(|((d, pos), f)| {
+                let data = d.decode(tape, &pos).map_err(|e| match e {
+                    ArrowError::JsonError(s) => {
+                        ArrowError::JsonError(format!("whilst decoding field '{}': {s}", f.name()))
+                    }
+                    e => e,
+                })?;
+                if !f.is_nullable() && !self.is_nullable && data.null_count() != 0 {
+                    return Err(ArrowError::JsonError(format!(
+                        "Encountered nulls in non-nullable field '{}'",
+                        f.name()
+                    )));
+                }
+                Ok(data)
+            })
+            .collect::<Result<Vec<_>, ArrowError>>()?;
+
+        let data = ArrayDataBuilder::new(DataType::Struct(self.fields.clone()))
+            .len(pos.len())
+            .nulls(nulls.as_mut().map(|x| NullBuffer::new(x.finish())))
+            .child_data(child_data);
+
+        // Safety
+        // Validated lengths above
+        Ok(unsafe { data.build_unchecked() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json_path() {
+        assert_eq!(parse_json_path("$").unwrap(), Vec::<String>::new());
+        assert_eq!(parse_json_path("$.a.b").unwrap(), &["a", "b"]);
+        assert_eq!(
+            parse_json_path("$['a.b'][\"c\"].d").unwrap(),
+            &["a.b", "c", "d"]
+        );
+
+        let err = parse_json_path("a.b").unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "Invalid argument error: Invalid JSON path 'a.b': expected '$'"
+        );
+        let err = parse_json_path("$.a..b").unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "Invalid argument error: Invalid JSON path '$.a..b': empty field name"
+        );
+        let err = parse_json_path("$['a'").unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "Invalid argument error: Invalid JSON path '$['a'': expected ']'"
+        );
+    }
+
+    #[test]
+    fn test_path_tree() {
+        let paths = [
+            vec!["a".to_string(), "b".to_string()],
+            vec!["a".to_string(), "c".to_string(), "d".to_string()],
+            vec!["a".to_string(), "c".to_string()],
+            vec!["e".to_string()],
+        ];
+        let tree = PathTree::new(paths.iter().map(|x| x.as_slice()));
+
+        let root = tree.root();
+        assert!(!tree.keep(root));
+        let a = tree.child(root, b"a").unwrap();
+        assert!(!tree.keep(a));
+        assert!(tree.keep(tree.child(a, b"b").unwrap()));
+        let c = tree.child(a, b"c").unwrap();
+        assert!(tree.keep(c));
+        assert!(tree.child(c, b"d").is_none());
+        assert!(tree.keep(tree.child(root, b"e").unwrap()));
+        assert!(tree.child(root, b"f").is_none());
+    }
+}
@@ -0,0 +1,124 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow_array::make_array;
+use arrow_array::types::{Int16Type, Int32Type, Int64Type, RunEndIndexType};
+use arrow_buffer::{ArrowNativeType, ScalarBuffer};
+use arrow_data::transform::MutableArrayData;
+use arrow_data::{ArrayData, ArrayDataBuilder};
+use arrow_ord::partition::partition;
+use arrow_schema::{ArrowError, DataType};
+use std::marker::PhantomData;
+
+use crate::reader::tape::Tape;
+use crate::reader::{make_decoder, ArrayDecoder, BinaryEncoding};
+
+/// Create an [`ArrayDecoder`] for the [`DataType::RunEndEncoded`] `data_type`
+pub fn make_run_end_decoder(
+    data_type: DataType,
+    coerce_primitive: bool,
+    strict_mode: bool,
+    binary_encoding: BinaryEncoding,
+    union_discriminator: Option<&str>,
+) -> Result<Box<dyn ArrayDecoder>, ArrowError> {
+    let (run_ends, values) = match &data_type {
+        DataType::RunEndEncoded(r, v) => (r, v),
+        _ => unreachable!(),
+    };
+
+    // Nulls are encoded in the values
+    let decoder = make_decoder(
+        values.data_type().clone(),
+        coerce_primitive,
+        strict_mode,
+        binary_encoding,
+        union_discriminator,
+        true,
+    )?;
+
+    match run_ends.data_type() {
+        DataType::Int16 => Ok(Box::new(RunEndArrayDecoder::<Int16Type>::new(
+            data_type, decoder,
+        ))),
+        DataType::Int32 => Ok(Box::new(RunEndArrayDecoder::<Int32Type>::new(
+            data_type, decoder,
+        ))),
+        DataType::Int64 => Ok(Box::new(RunEndArrayDecoder::<Int64Type>::new(
+            data_type, decoder,
+        ))),
+        d => Err(ArrowError::JsonError(format!(
+            "{d} is not a valid run end type for {data_type}"
+        ))),
+    }
+}
+
+/// Decodes values and then run-end encodes runs of equal values
+pub struct RunEndArrayDecoder<R: RunEndIndexType> {
+    data_type: DataType,
+    decoder: Box<dyn ArrayDecoder>,
+    phantom: PhantomData<fn() -> R>,
+}
+
+impl<R: RunEndIndexType> RunEndArrayDecoder<R> {
+    fn new(data_type: DataType, decoder: Box<dyn ArrayDecoder>) -> Self {
+        Self {
+            data_type,
+            decoder,
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<R: RunEndIndexType> ArrayDecoder for RunEndArrayDecoder<R> {
+    fn decode(&mut self, tape: &Tape<'_>, pos: &[u32]) -> Result<ArrayData, ArrowError> {
+        let values = self.decoder.decode(tape, pos)?;
+
+        // Nested values cannot be compared by partition, and so each forms its own run
+        let runs = match values.data_type() {
+            d if d.is_nested() || matches!(d, DataType::RunEndEncoded(_, _)) => {
+                (0..values.len()).map(|idx| idx..idx + 1).collect()
+            }
+            _ => partition(&[make_array(values.clone())])?.ranges(),
+        };
+
+        let mut run_ends = Vec::with_capacity(runs.len());
+        let mut run_values = MutableArrayData::new(vec![&values], false, runs.len());
+        for run in runs {
+            let run_end = R::Native::from_usize(run.end).ok_or_else(|| {
+                ArrowError::JsonError(format!("run end overflow decoding {}", self.data_type))
+            })?;
+            run_ends.push(run_end);
+            run_values.extend(0, run.start, run.start + 1);
+        }
+
+        let run_ends = ArrayDataBuilder::new(R::DATA_TYPE)
+            .len(run_ends.len())
+            .add_buffer(ScalarBuffer::from(run_ends).into_inner());
+
+        let data = ArrayDataBuilder::new(self.data_type.clone())
+            .len(pos.len())
+            .child_data(vec![
+                // Safety: run ends are a valid primitive array by construction
+                unsafe { run_ends.build_unchecked() },
+                run_values.freeze(),
+            ]);
+
+        // Safety
+        // Run ends are strictly increasing, and end at `pos.len()`
+        Ok(unsafe { data.build_unchecked() })
+    }
+}
@@ -19,6 +19,7 @@ use arrow_array::builder::GenericStringBuilder;
 use arrow_array::{Array, GenericStringArray, OffsetSizeTrait};
 use arrow_data::ArrayData;
 use arrow_schema::ArrowError;
+use std::borrow::Cow;
 use std::marker::PhantomData;
 
 use crate::reader::tape::{Tape, TapeElement};
@@ -86,44 +87,47 @@ impl<O: OffsetSizeTrait> ArrayDecoder for StringArrayDecoder<O> {
         let mut builder = GenericStringBuilder::<O>::with_capacity(pos.len(), data_capacity);
 
         for p in pos {
-            match tape.get(*p) {
-                TapeElement::String(idx) => {
-                    builder.append_value(tape.get_string(idx));
-                }
-                TapeElement::Null => builder.append_null(),
-                TapeElement::True if coerce_primitive => {
-                    builder.append_value(TRUE);
-                }
-                TapeElement::False if coerce_primitive => {
-                    builder.append_value(FALSE);
-                }
-                TapeElement::Number(idx) if coerce_primitive => {
-                    builder.append_value(tape.get_string(idx));
-                }
-                TapeElement::I64(high) if coerce_primitive => match tape.get(p + 1) {
-                    TapeElement::I32(low) => {
-                        let val = (high as i64) << 32 | (low as u32) as i64;
-                        builder.append_value(val.to_string());
-                    }
-                    _ => unreachable!(),
-                },
-                TapeElement::I32(n) if coerce_primitive => {
-                    builder.append_value(n.to_string());
-                }
-                TapeElement::F32(n) if coerce_primitive => {
-                    builder.append_value(n.to_string());
-                }
-                TapeElement::F64(high) if coerce_primitive => match tape.get(p + 1) {
-                    TapeElement::F32(low) => {
-                        let val = f64::from_bits((high as u64) << 32 | low as u64);
-                        builder.append_value(val.to_string());
-                    }
-                    _ => unreachable!(),
-                },
-                _ => unreachable!(),
+            match coerce_string(tape, *p, coerce_primitive)? {
+                Some(s) => builder.append_value(s),
+                None => builder.append_null(),
             }
         }
 
         Ok(builder.finish().into_data())
     }
 }
+
+/// Returns the string value of the element at `p` in `tape`, or `None` if null
+///
+/// If `coerce_primitive`, numbers and booleans are converted to their string
+/// representation, otherwise only strings are accepted
+pub fn coerce_string<'a>(
+    tape: &Tape<'a>,
+    p: u32,
+    coerce_primitive: bool,
+) -> Result<Option<Cow<'a, str>>, ArrowError> {
+    Ok(Some(match tape.get(p) {
+        TapeElement::String(idx) => Cow::Borrowed(tape.get_string(idx)),
+        TapeElement::Null => return Ok(None),
+        TapeElement::True if coerce_primitive => Cow::Borrowed(TRUE),
+        TapeElement::False if coerce_primitive => Cow::Borrowed(FALSE),
+        TapeElement::Number(idx) if coerce_primitive => Cow::Borrowed(tape.get_string(idx)),
+        TapeElement::I64(high) if coerce_primitive => match tape.get(p + 1) {
+            TapeElement::I32(low) => {
+                let val = (high as i64) << 32 | (low as u32) as i64;
+                Cow::Owned(val.to_string())
+            }
+            _ => unreachable!(),
+        },
+        TapeElement::I32(n) if coerce_primitive => Cow::Owned(n.to_string()),
+        TapeElement::F32(n) if coerce_primitive => Cow::Owned(n.to_string()),
+        TapeElement::F64(high) if coerce_primitive => match tape.get(p + 1) {
+            TapeElement::F32(low) => {
+                let val = f64::from_bits((high as u64) << 32 | low as u64);
+                Cow::Owned(val.to_string())
+            }
+            _ => unreachable!(),
+        },
+        _ => return Err(tape.error(p, "string")),
+    }))
+}
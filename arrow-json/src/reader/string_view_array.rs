@@ -0,0 +1,53 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow_array::builder::StringViewBuilder;
+use arrow_array::Array;
+use arrow_data::ArrayData;
+use arrow_schema::ArrowError;
+
+use crate::reader::string_array::coerce_string;
+use crate::reader::tape::Tape;
+use crate::reader::ArrayDecoder;
+
+/// An [`ArrayDecoder`] for [`DataType::Utf8View`](arrow_schema::DataType::Utf8View)
+///
+/// Views are built directly from the strings in the [`Tape`]
+pub struct StringViewArrayDecoder {
+    coerce_primitive: bool,
+}
+
+impl StringViewArrayDecoder {
+    pub fn new(coerce_primitive: bool) -> Self {
+        Self { coerce_primitive }
+    }
+}
+
+impl ArrayDecoder for StringViewArrayDecoder {
+    fn decode(&mut self, tape: &Tape<'_>, pos: &[u32]) -> Result<ArrayData, ArrowError> {
+        let mut builder = StringViewBuilder::with_capacity(pos.len());
+
+        for p in pos {
+            match coerce_string(tape, *p, self.coerce_primitive)? {
+                Some(s) => builder.append_value(s),
+                None => builder.append_null(),
+            }
+        }
+
+        Ok(builder.finish().into_data())
+    }
+}
@@ -16,7 +16,7 @@
 // under the License.
 
 use crate::reader::tape::{Tape, TapeElement};
-use crate::reader::{make_decoder, ArrayDecoder};
+use crate::reader::{make_decoder, ArrayDecoder, BinaryEncoding};
 use arrow_array::builder::BooleanBufferBuilder;
 use arrow_buffer::buffer::NullBuffer;
 use arrow_data::{ArrayData, ArrayDataBuilder};
@@ -27,6 +27,8 @@ pub struct StructArrayDecoder {
     decoders: Vec<Box<dyn ArrayDecoder>>,
     strict_mode: bool,
     is_nullable: bool,
+    /// A field that is skipped, even in strict mode, if not one of the fields
+    ignored_field: Option<String>,
 }
 
 impl StructArrayDecoder {
@@ -34,6 +36,8 @@ impl StructArrayDecoder {
         data_type: DataType,
         coerce_primitive: bool,
         strict_mode: bool,
+        binary_encoding: BinaryEncoding,
+        union_discriminator: Option<&str>,
         is_nullable: bool,
     ) -> Result<Self, ArrowError> {
         let decoders = struct_fields(&data_type)
@@ -47,6 +51,8 @@ impl StructArrayDecoder {
                     f.data_type().clone(),
                     coerce_primitive,
                     strict_mode,
+                    binary_encoding,
+                    union_discriminator,
                     nullable,
                 )
             })
@@ -57,8 +63,17 @@ impl StructArrayDecoder {
             decoders,
             strict_mode,
             is_nullable,
+            ignored_field: None,
         })
     }
+
+    /// Skip the field named `name`, if not one of the fields of this struct
+    pub fn with_ignored_field(self, name: &str) -> Self {
+        Self {
+            ignored_field: Some(name.to_string()),
+            ..self
+        }
+    }
 }
 
 impl ArrayDecoder for StructArrayDecoder {
@@ -96,7 +111,7 @@ impl ArrayDecoder for StructArrayDecoder {
                 match fields.iter().position(|x| x.name() == field_name) {
                     Some(field_idx) => child_pos[field_idx][row] = cur_idx + 1,
                     None => {
-                        if self.strict_mode {
+                        if self.strict_mode && self.ignored_field.as_deref() != Some(field_name) {
                             return Err(ArrowError::JsonError(format!(
                                 "column '{}' missing from schema",
                                 field_name
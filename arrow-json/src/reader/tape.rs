@@ -15,7 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::reader::path::PathTree;
 use crate::reader::serializer::TapeSerializer;
+use crate::reader::{ErrorMode, RowError};
 use arrow_schema::ArrowError;
 use serde::Serialize;
 use std::fmt::Write;
@@ -236,6 +238,23 @@ enum DecoderState {
     ///
     /// Consists of `(literal, decoded length)`
     Literal(Literal, u8),
+    /// A value to decode according to the contained [`PathTree`] node
+    ProjectedValue(u32),
+    /// Decoding an object whose fields are filtered by a [`PathTree`] node
+    ///
+    /// Contains index of start [`TapeElement::StartObject`]
+    ProjectedObject(u32),
+    /// A field value within a [`DecoderState::ProjectedObject`], the preceding
+    /// element of the tape is the field name
+    Field,
+    /// A value to skip
+    SkipValue,
+    /// Skipping a number or literal
+    SkipScalar,
+    /// Skipping a string, list or object
+    ///
+    /// Consists of `(nesting depth, in string, escaped)`
+    Skip(u32, bool, bool),
 }
 
 impl DecoderState {
@@ -250,6 +269,10 @@ impl DecoderState {
             DecoderState::Escape => "escape",
             DecoderState::Unicode(_, _, _) => "unicode literal",
             DecoderState::Literal(d, _) => d.as_str(),
+            DecoderState::ProjectedValue(_) => "value",
+            DecoderState::ProjectedObject(_) => "object",
+            DecoderState::Field => "value",
+            DecoderState::SkipValue | DecoderState::SkipScalar | DecoderState::Skip(..) => "value",
         }
     }
 }
@@ -314,6 +337,15 @@ pub struct TapeDecoder {
 
     /// A stack of [`DecoderState`]
     stack: Vec<DecoderState>,
+
+    /// The fields to decode, if not all
+    projection: Option<PathTree>,
+
+    /// A stack of [`PathTree`] nodes for each [`DecoderState::ProjectedObject`] in `stack`
+    projection_stack: Vec<u32>,
+
+    /// Recovery state if not [`ErrorMode::Fail`]
+    recovery: Option<Recovery>,
 }
 
 impl TapeDecoder {
@@ -334,48 +366,179 @@ impl TapeDecoder {
             cur_row: 0,
             bytes: Vec::with_capacity(num_fields * 2 * 8),
             stack: Vec::with_capacity(10),
+            projection: None,
+            projection_stack: vec![],
+            recovery: None,
+        }
+    }
+
+    /// Recover from malformed rows according to `mode`, see [`ErrorMode`]
+    pub fn with_error_mode(self, mode: ErrorMode) -> Self {
+        let recovery = (mode != ErrorMode::Fail).then(|| Recovery {
+            mode,
+            ..Default::default()
+        });
+        Self { recovery, ..self }
+    }
+
+    /// Only decode the object fields referenced by `projection`, skipping any others
+    ///
+    /// Skipped values are only scanned for their extent, and are not otherwise validated
+    pub fn with_projection(self, projection: PathTree) -> Self {
+        Self {
+            projection: Some(projection),
+            ..self
         }
     }
 
     pub fn decode(&mut self, buf: &[u8]) -> Result<usize, ArrowError> {
         let mut iter = BufIter::new(buf);
 
-        while !iter.is_empty() {
+        while let Err(e) = self.decode_iter(buf, &mut iter) {
+            // Resume from the next line, unless the error was caused by a newline
+            let read = buf.len() - iter.len();
+            let skip_line = read == 0 || buf[read - 1] != b'\n';
+            self.recover(e, buf, read, skip_line)?;
+        }
+
+        let read = buf.len() - iter.len();
+        if let Some(r) = self.recovery.as_mut() {
+            if r.row_open {
+                r.copy_raw(buf, read);
+            }
+            r.num_bytes += read;
+        }
+        Ok(read)
+    }
+
+    fn decode_iter<'a>(&mut self, buf: &'a [u8], iter: &mut BufIter<'a>) -> Result<(), ArrowError> {
+        while !iter.is_exhausted() {
             let state = match self.stack.last_mut() {
                 Some(l) => l,
                 None => {
+                    if self.recovery.is_some() && !self.resume(buf, iter) {
+                        break;
+                    }
+
                     iter.skip_whitespace();
-                    if iter.is_empty() || self.cur_row >= self.batch_size {
+                    if iter.is_exhausted() || self.cur_row >= self.batch_size {
                         break;
                     }
 
                     // Start of row
-                    self.cur_row += 1;
-                    self.stack.push(DecoderState::Value);
+                    self.start_row(buf.len() - iter.len());
+                    self.stack.push(match &self.projection {
+                        // A projection of the root, `$`, keeps the entire row
+                        Some(p) if !p.keep(p.root()) => DecoderState::ProjectedValue(p.root()),
+                        _ => DecoderState::Value,
+                    });
                     self.stack.last_mut().unwrap()
                 }
             };
 
             match state {
                 // Decoding an object
-                DecoderState::Object(start_idx) => {
+                DecoderState::Object(_) | DecoderState::ProjectedObject(_) => {
+                    let (start_idx, projected) = match *state {
+                        DecoderState::Object(idx) => (idx, false),
+                        DecoderState::ProjectedObject(idx) => (idx, true),
+                        _ => unreachable!(),
+                    };
                     iter.advance_until(|b| !json_whitespace(b) && b != b',');
                     match next!(iter) {
                         b'"' => {
-                            self.stack.push(DecoderState::Value);
+                            self.stack.push(match projected {
+                                true => DecoderState::Field,
+                                false => DecoderState::Value,
+                            });
                             self.stack.push(DecoderState::Colon);
                             self.stack.push(DecoderState::String);
                         }
                         b'}' => {
-                            let start_idx = *start_idx;
                             let end_idx = self.elements.len() as u32;
                             self.elements[start_idx as usize] = TapeElement::StartObject(end_idx);
                             self.elements.push(TapeElement::EndObject(start_idx));
                             self.stack.pop();
+                            if projected {
+                                self.projection_stack.pop();
+                            }
                         }
                         b => return Err(err(b, "parsing object")),
                     }
                 }
+                // A value that may be an object to project
+                DecoderState::ProjectedValue(node) => {
+                    iter.skip_whitespace();
+                    match iter.peek() {
+                        Some(b'{') => {
+                            iter.next();
+                            self.projection_stack.push(*node);
+                            let idx = self.elements.len() as u32;
+                            self.elements.push(TapeElement::StartObject(u32::MAX));
+                            *state = DecoderState::ProjectedObject(idx);
+                        }
+                        Some(_) => *state = DecoderState::Value,
+                        None => break,
+                    }
+                }
+                // The name of a field of a projected object has been decoded
+                DecoderState::Field => {
+                    let projection = self.projection.as_ref().unwrap();
+                    let node = *self.projection_stack.last().unwrap();
+
+                    let start = self.offsets[self.offsets.len() - 2];
+                    let name = &self.bytes[start..];
+                    *state = match projection.child(node, name) {
+                        Some(c) if projection.keep(c) => DecoderState::Value,
+                        Some(c) => DecoderState::ProjectedValue(c),
+                        None => {
+                            // Remove the field name from the tape
+                            self.elements.pop();
+                            self.offsets.pop();
+                            self.bytes.truncate(start);
+                            DecoderState::SkipValue
+                        }
+                    };
+                }
+                DecoderState::SkipValue => {
+                    iter.skip_whitespace();
+                    *state = match next!(iter) {
+                        b'"' => DecoderState::Skip(0, true, false),
+                        b'[' | b'{' => DecoderState::Skip(1, false, false),
+                        b'-' | b'0'..=b'9' | b'n' | b't' | b'f' => DecoderState::SkipScalar,
+                        b => return Err(err(b, "parsing value")),
+                    };
+                }
+                DecoderState::SkipScalar => {
+                    iter.advance_until(|b| json_whitespace(b) || matches!(b, b',' | b']' | b'}'));
+                    if !iter.is_exhausted() {
+                        self.stack.pop();
+                    }
+                }
+                DecoderState::Skip(depth, in_string, escaped) => loop {
+                    if *escaped {
+                        next!(iter);
+                        *escaped = false;
+                    } else if *in_string {
+                        iter.advance_until(|b| matches!(b, b'\\' | b'"'));
+                        match next!(iter) {
+                            b'\\' => *escaped = true,
+                            _ => *in_string = false,
+                        }
+                    } else {
+                        iter.advance_until(|b| matches!(b, b'"' | b'[' | b']' | b'{' | b'}'));
+                        match next!(iter) {
+                            b'"' => *in_string = true,
+                            b'[' | b'{' => *depth += 1,
+                            _ => *depth -= 1,
+                        }
+                    }
+
+                    if *depth == 0 && !*in_string {
+                        self.stack.pop();
+                        break;
+                    }
+                },
                 // Decoding a list
                 DecoderState::List(start_idx) => {
                     iter.advance_until(|b| !json_whitespace(b) && b != b',');
@@ -438,7 +601,7 @@ impl TapeDecoder {
                     });
                     self.bytes.extend_from_slice(s);
 
-                    if !iter.is_empty() {
+                    if !iter.is_exhausted() {
                         self.stack.pop();
                         let idx = self.offsets.len() - 1;
                         self.elements.push(TapeElement::Number(idx as _));
@@ -455,7 +618,7 @@ impl TapeDecoder {
                 DecoderState::Literal(literal, idx) => {
                     let bytes = literal.bytes();
                     let expected = bytes.iter().skip(*idx as usize).copied();
-                    for (expected, b) in expected.zip(&mut iter) {
+                    for (expected, b) in expected.zip(&mut *iter) {
                         match b == expected {
                             true => *idx += 1,
                             false => return Err(err(b, "parsing literal")),
@@ -521,7 +684,171 @@ impl TapeDecoder {
             }
         }
 
-        Ok(buf.len() - iter.len())
+        Ok(())
+    }
+
+    /// Called with an empty stack between rows, finishing the previous row and skipping
+    /// the remainder of the line if recovering from an error
+    ///
+    /// Returns false if `iter` was exhausted whilst skipping
+    fn resume(&mut self, buf: &[u8], iter: &mut BufIter<'_>) -> bool {
+        let r = self.recovery.as_mut().unwrap();
+        if r.skip_line {
+            let skipped = iter.advance_until(|b| b == b'\n');
+            if let Some(record) = r.errors.last_mut().and_then(|e| e.record.as_mut()) {
+                record.extend_from_slice(skipped);
+            }
+            if iter.next().is_none() {
+                return false;
+            }
+            r.skip_line = false;
+        }
+
+        if r.row_open {
+            self.end_row(buf, buf.len() - iter.len());
+        }
+        true
+    }
+
+    /// Starts a new row at position `pos` of the current input
+    fn start_row(&mut self, pos: usize) {
+        self.cur_row += 1;
+        if let Some(r) = self.recovery.as_mut() {
+            r.row_open = true;
+            r.row_offset = r.num_bytes + pos;
+            r.rows.push((r.num_rows, r.row_offset));
+            r.num_rows += 1;
+            r.start = (self.elements.len(), self.bytes.len(), self.offsets.len());
+            if r.mode == ErrorMode::Quarantine {
+                r.raw_offsets.push(r.raw.len());
+            }
+        }
+    }
+
+    /// Finishes a row ending at position `pos` of `buf`, validating its string data
+    fn end_row(&mut self, buf: &[u8], pos: usize) {
+        let r = self.recovery.as_mut().unwrap();
+        r.copy_raw(buf, pos);
+        r.row_open = false;
+
+        let (_, bytes, offsets) = r.start;
+        let valid = check_utf8(&self.bytes[bytes..], &self.offsets[offsets..], bytes).map(|_| ());
+        if let Err(e) = valid {
+            // Cannot fail as recovery is enabled
+            let _ = self.recover(e, buf, pos, false);
+        }
+    }
+
+    /// Removes the current row following the error `e` at position `pos` of `buf`
+    ///
+    /// Returns `e` if not recovering from errors
+    fn recover(
+        &mut self,
+        e: ArrowError,
+        buf: &[u8],
+        pos: usize,
+        skip_line: bool,
+    ) -> Result<(), ArrowError> {
+        let r = match self.recovery.as_mut() {
+            Some(r) => r,
+            None => return Err(e),
+        };
+        if r.row_open {
+            r.copy_raw(buf, pos);
+            r.row_open = false;
+        }
+
+        let (elements, bytes, offsets) = r.start;
+        self.elements.truncate(elements);
+        self.bytes.truncate(bytes);
+        self.offsets.truncate(offsets);
+        self.stack.clear();
+        self.projection_stack.clear();
+        r.skip_line = skip_line;
+
+        let (row, offset) = *r.rows.last().unwrap();
+        let record = match r.mode {
+            ErrorMode::Quarantine => {
+                let start = r.raw_offsets.pop().unwrap();
+                Some(r.raw.split_off(start))
+            }
+            _ => None,
+        };
+        match r.mode {
+            ErrorMode::Null => {
+                r.failed.push(r.rows.len() - 1);
+                self.elements.push(TapeElement::Null);
+            }
+            _ => {
+                r.rows.pop();
+                self.cur_row -= 1;
+            }
+        }
+
+        r.errors.push(RowError {
+            row,
+            offset,
+            reason: error_reason(e),
+            record,
+        });
+        Ok(())
+    }
+
+    /// Finishes any partially decoded row if recovering from errors, treating it as truncated
+    pub fn finish_row(&mut self) {
+        let r = match self.recovery.as_mut() {
+            Some(r) => r,
+            None => return,
+        };
+
+        if let Some(state) = self.stack.last() {
+            let e = ArrowError::JsonError(format!(
+                "Truncated record whilst reading {}",
+                state.as_str()
+            ));
+            // Cannot fail as recovery is enabled
+            let _ = self.recover(e, &[], 0, false);
+        } else if r.row_open {
+            self.end_row(&[], 0);
+        }
+    }
+
+    /// Returns a [`RowError`] for the row at index `idx` of the current tape
+    ///
+    /// # Panics
+    ///
+    /// Panics if not recovering from errors
+    pub fn row_error(&self, idx: usize, e: ArrowError) -> RowError {
+        let r = self.recovery.as_ref().unwrap();
+        let (row, offset) = r.rows[idx];
+        let record = (r.mode == ErrorMode::Quarantine).then(|| {
+            let start = r.raw_offsets[idx];
+            let end = r.raw_offsets.get(idx + 1).copied().unwrap_or(r.raw.len());
+            r.raw[start..end].to_vec()
+        });
+        RowError {
+            row,
+            offset,
+            reason: error_reason(e),
+            record,
+        }
+    }
+
+    /// Returns the indexes of the rows of the current tape that failed to parse,
+    /// and were replaced by null, in ascending order
+    pub fn failed_rows(&self) -> &[usize] {
+        match self.recovery.as_ref() {
+            Some(r) => &r.failed,
+            None => &[],
+        }
+    }
+
+    /// Returns the rows that failed to parse since the last call to this method
+    pub fn take_errors(&mut self) -> Vec<RowError> {
+        match self.recovery.as_mut() {
+            Some(r) => std::mem::take(&mut r.errors),
+            None => vec![],
+        }
     }
 
     /// Writes any type that implements [`Serialize`] into this [`TapeDecoder`]
@@ -541,6 +868,15 @@ impl TapeDecoder {
             .map_err(|e| ArrowError::JsonError(e.to_string()))?;
 
         self.cur_row += rows.len();
+        if let Some(r) = self.recovery.as_mut() {
+            for _ in rows {
+                r.rows.push((r.num_rows, r.num_bytes));
+                r.num_rows += 1;
+                if r.mode == ErrorMode::Quarantine {
+                    r.raw_offsets.push(r.raw.len());
+                }
+            }
+        }
 
         Ok(())
     }
@@ -571,16 +907,7 @@ impl TapeDecoder {
             self.bytes.len()
         );
 
-        let strings = std::str::from_utf8(&self.bytes)
-            .map_err(|_| ArrowError::JsonError("Encountered non-UTF-8 data".to_string()))?;
-
-        for offset in self.offsets.iter().copied() {
-            if !strings.is_char_boundary(offset) {
-                return Err(ArrowError::JsonError(
-                    "Encountered truncated UTF-8 sequence".to_string(),
-                ));
-            }
-        }
+        let strings = check_utf8(&self.bytes, &self.offsets, 0)?;
 
         Ok(Tape {
             strings,
@@ -593,6 +920,7 @@ impl TapeDecoder {
     /// Clears this [`TapeDecoder`] in preparation to read the next batch
     pub fn clear(&mut self) {
         assert!(self.stack.is_empty());
+        assert!(self.projection_stack.is_empty());
 
         self.cur_row = 0;
         self.bytes.clear();
@@ -600,7 +928,76 @@ impl TapeDecoder {
         self.elements.push(TapeElement::Null);
         self.offsets.clear();
         self.offsets.push(0);
+
+        if let Some(r) = self.recovery.as_mut() {
+            r.rows.clear();
+            r.raw.clear();
+            r.raw_offsets.clear();
+            r.failed.clear();
+        }
+    }
+}
+
+/// State for recovering from malformed rows, see [`ErrorMode`]
+#[derive(Debug, Default)]
+struct Recovery {
+    mode: ErrorMode,
+    /// The row number and byte offset within the input of each row in the tape
+    rows: Vec<(usize, usize)>,
+    /// The raw bytes of each row in the tape, if [`ErrorMode::Quarantine`]
+    raw: Vec<u8>,
+    /// The offset of each row within `raw`
+    raw_offsets: Vec<usize>,
+    /// Rows that failed to parse
+    errors: Vec<RowError>,
+    /// The indexes within `rows` of the rows replaced by null, if [`ErrorMode::Null`]
+    failed: Vec<usize>,
+    /// The number of rows started in the input
+    num_rows: usize,
+    /// The number of bytes read from the input before the current call to decode
+    num_bytes: usize,
+    /// The byte offset of the current row within the input
+    row_offset: usize,
+    /// True if a row has started, but not been validated by [`TapeDecoder::end_row`]
+    row_open: bool,
+    /// The lengths of `elements`, `bytes` and `offsets` at the start of the current row
+    start: (usize, usize, usize),
+    /// Skipping the remainder of the line following a malformed row
+    skip_line: bool,
+}
+
+impl Recovery {
+    /// Copies the bytes of the current row up to `pos` in `buf` to `raw`
+    fn copy_raw(&mut self, buf: &[u8], pos: usize) {
+        if self.mode == ErrorMode::Quarantine {
+            let start = self.row_offset.saturating_sub(self.num_bytes);
+            self.raw.extend_from_slice(&buf[start..pos]);
+        }
+    }
+}
+
+/// Returns the message of `e` without its prefix
+fn error_reason(e: ArrowError) -> String {
+    match e {
+        ArrowError::JsonError(s) => s,
+        e => e.to_string(),
+    }
+}
+
+/// Validates that `bytes` is UTF-8, and that `offsets`, relative to `base`,
+/// are on character boundaries
+fn check_utf8<'a>(bytes: &'a [u8], offsets: &[usize], base: usize) -> Result<&'a str, ArrowError> {
+    let strings = std::str::from_utf8(bytes)
+        .map_err(|_| ArrowError::JsonError("Encountered non-UTF-8 data".to_string()))?;
+
+    for offset in offsets.iter().copied() {
+        if !strings.is_char_boundary(offset - base) {
+            return Err(ArrowError::JsonError(
+                "Encountered truncated UTF-8 sequence".to_string(),
+            ));
+        }
     }
+    Ok(strings)
 }
 
 /// A wrapper around a slice iterator that provides some helper functionality
@@ -615,7 +1012,7 @@ impl<'a> BufIter<'a> {
         self.0.as_slice()
     }
 
-    fn is_empty(&self) -> bool {
+    fn is_exhausted(&self) -> bool {
         self.0.len() == 0
     }
 
@@ -823,6 +1220,77 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_projection() {
+        let a = r#"
+        {"x": {"b": "}\"]"}, "a": {"b": 1, "c": [1, {"d": "\\"}]}, "c": [true], "y": -1.5e3}
+        {"c": null, "a": 2, "z": "é"}
+        "#;
+        let paths = [
+            vec!["a".to_string(), "b".to_string()],
+            vec!["c".to_string()],
+        ];
+        let projection = || PathTree::new(paths.iter().map(|x| x.as_slice()));
+
+        let expected = [
+            TapeElement::Null,
+            TapeElement::StartObject(11), // {"a": {"b": 1}, "c": [true]}
+            TapeElement::String(0),       // "a"
+            TapeElement::StartObject(6),  // {"b": 1}
+            TapeElement::String(1),       // "b"
+            TapeElement::Number(2),       // 1
+            TapeElement::EndObject(3),
+            TapeElement::String(3),     // "c"
+            TapeElement::StartList(10), // [true]
+            TapeElement::True,
+            TapeElement::EndList(8),
+            TapeElement::EndObject(1),
+        ];
+
+        // Decode in one go, and then a byte at a time
+        let mut decoder = TapeDecoder::new(16, 2).with_projection(projection());
+        decoder.decode(a.as_bytes()).unwrap();
+        let mut bytewise = TapeDecoder::new(16, 2).with_projection(projection());
+        for b in a.as_bytes().chunks(1) {
+            bytewise.decode(b).unwrap();
+        }
+
+        for decoder in [&decoder, &bytewise] {
+            let finished = decoder.finish().unwrap();
+            assert_eq!(&finished.elements[..expected.len()], &expected);
+            assert_eq!(
+                &finished.elements[expected.len()..],
+                &[
+                    TapeElement::StartObject(17), // {"c": null, "a": 2}
+                    TapeElement::String(4),       // "c"
+                    TapeElement::Null,
+                    TapeElement::String(5), // "a"
+                    TapeElement::Number(6), // 2
+                    TapeElement::EndObject(12),
+                ]
+            );
+            assert_eq!(finished.strings, "ab1cca2");
+        }
+    }
+
+    #[test]
+    fn test_projection_root() {
+        let a = r#"{"a": 1, "b": {"c": [2]}} {"d": null}"#;
+        let paths = [vec![], vec!["a".to_string()]];
+        let projection = PathTree::new(paths.iter().map(|x| x.as_slice()));
+
+        // Projecting `$` keeps every field
+        let mut decoder = TapeDecoder::new(16, 2).with_projection(projection);
+        decoder.decode(a.as_bytes()).unwrap();
+        let mut expected = TapeDecoder::new(16, 2);
+        expected.decode(a.as_bytes()).unwrap();
+
+        let finished = decoder.finish().unwrap();
+        let expected = expected.finish().unwrap();
+        assert_eq!(finished.elements, expected.elements);
+        assert_eq!(finished.strings, expected.strings);
+    }
+
     #[test]
     fn test_invalid() {
         // Test invalid
@@ -0,0 +1,202 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow_buffer::Buffer;
+use arrow_data::{ArrayData, ArrayDataBuilder};
+use arrow_schema::{ArrowError, DataType, UnionMode};
+
+use crate::reader::struct_array::StructArrayDecoder;
+use crate::reader::tape::{Tape, TapeElement};
+use crate::reader::{make_decoder, ArrayDecoder, BinaryEncoding};
+
+/// Decodes a [`DataType::Union`]
+///
+/// Each JSON value is decoded into the first child, in declaration order, that can
+/// decode it, unless a discriminator is configured and the value is an object
+/// containing it
+pub struct UnionArrayDecoder {
+    data_type: DataType,
+    mode: UnionMode,
+    type_ids: Vec<i8>,
+    names: Vec<String>,
+    nullable: Vec<bool>,
+    decoders: Vec<Box<dyn ArrayDecoder>>,
+    discriminator: Option<String>,
+}
+
+impl UnionArrayDecoder {
+    pub fn new(
+        data_type: DataType,
+        coerce_primitive: bool,
+        strict_mode: bool,
+        binary_encoding: BinaryEncoding,
+        union_discriminator: Option<&str>,
+    ) -> Result<Self, ArrowError> {
+        let (fields, mode) = match &data_type {
+            DataType::Union(fields, mode) => (fields, *mode),
+            _ => unreachable!(),
+        };
+
+        let mut type_ids = Vec::with_capacity(fields.len());
+        let mut names = Vec::with_capacity(fields.len());
+        let mut nullables = Vec::with_capacity(fields.len());
+        let mut decoders = Vec::with_capacity(fields.len());
+        for (type_id, field) in fields.iter() {
+            // Sparse children contain a slot for every row, and so must accept nulls
+            let nullable = field.is_nullable() || mode == UnionMode::Sparse;
+            let decoder: Box<dyn ArrayDecoder> = match (field.data_type(), union_discriminator) {
+                // The discriminator is not passed to a struct child that does not declare it
+                (DataType::Struct(_), Some(discriminator)) => Box::new(
+                    StructArrayDecoder::new(
+                        field.data_type().clone(),
+                        coerce_primitive,
+                        strict_mode,
+                        binary_encoding,
+                        union_discriminator,
+                        nullable,
+                    )?
+                    .with_ignored_field(discriminator),
+                ),
+                _ => make_decoder(
+                    field.data_type().clone(),
+                    coerce_primitive,
+                    strict_mode,
+                    binary_encoding,
+                    union_discriminator,
+                    nullable,
+                )?,
+            };
+            decoders.push(decoder);
+            type_ids.push(type_id);
+            names.push(field.name().clone());
+            nullables.push(nullable);
+        }
+
+        Ok(Self {
+            data_type,
+            mode,
+            type_ids,
+            names,
+            nullable: nullables,
+            decoders,
+            discriminator: union_discriminator.map(ToString::to_string),
+        })
+    }
+
+    /// Returns the index of the child selected by the discriminator of the
+    /// object at `p`, if any
+    fn discriminate(&self, tape: &Tape<'_>, p: u32) -> Result<Option<usize>, ArrowError> {
+        let (discriminator, end_idx) = match (&self.discriminator, tape.get(p)) {
+            (Some(d), TapeElement::StartObject(end_idx)) => (d, end_idx),
+            _ => return Ok(None),
+        };
+
+        let mut cur_idx = p + 1;
+        while cur_idx < end_idx {
+            let field_name = match tape.get(cur_idx) {
+                TapeElement::String(s) => tape.get_string(s),
+                _ => return Err(tape.error(cur_idx, "field name")),
+            };
+
+            if field_name == discriminator {
+                if let TapeElement::String(s) = tape.get(cur_idx + 1) {
+                    let name = tape.get_string(s);
+                    return match self.names.iter().position(|x| x == name) {
+                        Some(idx) => Ok(Some(idx)),
+                        None => Err(ArrowError::JsonError(format!(
+                            "unknown union discriminator '{name}' for {}",
+                            self.data_type
+                        ))),
+                    };
+                }
+            }
+            cur_idx = tape.next(cur_idx + 1, "field value")?;
+        }
+        Ok(None)
+    }
+
+    /// Returns the index of the first child that can decode the value at `p`
+    fn select(&mut self, tape: &Tape<'_>, p: u32) -> Result<usize, ArrowError> {
+        if let Some(idx) = self.discriminate(tape, p)? {
+            return Ok(idx);
+        }
+
+        let is_null = matches!(tape.get(p), TapeElement::Null);
+        for (idx, decoder) in self.decoders.iter_mut().enumerate() {
+            let accepted = match is_null {
+                true => self.nullable[idx],
+                false => decoder.decode(tape, &[p]).is_ok(),
+            };
+            if accepted {
+                return Ok(idx);
+            }
+        }
+        Err(tape.error(p, &self.data_type.to_string()))
+    }
+}
+
+impl ArrayDecoder for UnionArrayDecoder {
+    fn decode(&mut self, tape: &Tape<'_>, pos: &[u32]) -> Result<ArrayData, ArrowError> {
+        let mut type_ids = Vec::with_capacity(pos.len());
+        let mut child_pos: Vec<Vec<u32>> = vec![vec![]; self.decoders.len()];
+        let mut offsets = match self.mode {
+            UnionMode::Dense => Some(Vec::with_capacity(pos.len())),
+            UnionMode::Sparse => {
+                child_pos.iter_mut().for_each(|x| x.resize(pos.len(), 0));
+                None
+            }
+        };
+
+        for (row, p) in pos.iter().enumerate() {
+            let idx = self.select(tape, *p)?;
+            type_ids.push(self.type_ids[idx]);
+            match offsets.as_mut() {
+                Some(offsets) => {
+                    let offset = i32::try_from(child_pos[idx].len()).map_err(|_| {
+                        ArrowError::JsonError(format!(
+                            "offset overflow decoding {}",
+                            self.data_type
+                        ))
+                    })?;
+                    offsets.push(offset);
+                    child_pos[idx].push(*p);
+                }
+                None => child_pos[idx][row] = *p,
+            }
+        }
+
+        let child_data = self
+            .decoders
+            .iter_mut()
+            .zip(&child_pos)
+            .map(|(d, pos)| d.decode(tape, pos))
+            .collect::<Result<Vec<_>, ArrowError>>()?;
+
+        let mut data = ArrayDataBuilder::new(self.data_type.clone())
+            .len(pos.len())
+            .add_buffer(Buffer::from_vec(type_ids))
+            .child_data(child_data);
+
+        if let Some(offsets) = offsets {
+            data = data.add_buffer(Buffer::from_vec(offsets));
+        }
+
+        // Safety
+        // Type ids are taken from the union fields and offsets from the child lengths
+        Ok(unsafe { data.build_unchecked() })
+    }
+}
@@ -29,6 +29,7 @@ use std::io::Write;
 #[derive(Debug, Clone, Default)]
 pub struct EncoderOptions {
     pub explicit_nulls: bool,
+    pub union_discriminator: Option<String>,
 }
 
 /// A trait to format array values as JSON values
@@ -122,22 +123,15 @@ fn make_encoder_impl<'a>(
             (Box::new(BinaryEncoder::new(array)) as _, array.nulls().cloned())
         }
 
-        DataType::Struct(fields) => {
+        DataType::Struct(_) => {
             let array = array.as_struct();
-            let encoders = fields.iter().zip(array.columns()).map(|(field, array)| {
-                let (encoder, nulls) = make_encoder_impl(array, options)?;
-                Ok(FieldEncoder{
-                    field: field.clone(),
-                    encoder, nulls
-                })
-            }).collect::<Result<Vec<_>, ArrowError>>()?;
-
-            let encoder = StructArrayEncoder{
-                encoders,
-                explicit_nulls: options.explicit_nulls,
-            };
+            let encoder = StructArrayEncoder::try_new(array, options, None)?;
             (Box::new(encoder) as _, array.nulls().cloned())
         }
+        DataType::Union(_, _) => {
+            let array = array.as_union();
+            (Box::new(UnionEncoder::try_new(array, options)?) as _, array.logical_nulls())
+        }
         DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => {
             let options = FormatOptions::new().with_display_error(true);
             let formatter = ArrayFormatter::try_new(array, &options)?;
@@ -172,6 +166,36 @@ struct FieldEncoder<'a> {
 struct StructArrayEncoder<'a> {
     encoders: Vec<FieldEncoder<'a>>,
     explicit_nulls: bool,
+    /// An encoded key and value written before the fields, see [`UnionEncoder`]
+    tag: Option<Vec<u8>>,
+}
+
+impl<'a> StructArrayEncoder<'a> {
+    fn try_new(
+        array: &'a StructArray,
+        options: &EncoderOptions,
+        tag: Option<Vec<u8>>,
+    ) -> Result<Self, ArrowError> {
+        let encoders = array
+            .fields()
+            .iter()
+            .zip(array.columns())
+            .map(|(field, array)| {
+                let (encoder, nulls) = make_encoder_impl(array, options)?;
+                Ok(FieldEncoder {
+                    field: field.clone(),
+                    encoder,
+                    nulls,
+                })
+            })
+            .collect::<Result<Vec<_>, ArrowError>>()?;
+
+        Ok(Self {
+            encoders,
+            explicit_nulls: options.explicit_nulls,
+            tag,
+        })
+    }
 }
 
 /// This API is only stable since 1.70 so can't use it when current MSRV is lower
@@ -187,6 +211,10 @@ impl Encoder for StructArrayEncoder<'_> {
     fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
         out.push(b'{');
         let mut is_first = true;
+        if let Some(tag) = &self.tag {
+            out.extend_from_slice(tag);
+            is_first = false;
+        }
         for field_encoder in &mut self.encoders {
             let is_null = is_some_and(field_encoder.nulls.as_ref(), |n| n.is_null(idx));
             if is_null && !self.explicit_nulls {
@@ -441,6 +469,54 @@ impl<K: ArrowDictionaryKeyType> Encoder for DictionaryEncoder<'_, K> {
     }
 }
 
+/// Encodes each value of a union as the value of its child
+///
+/// If [`EncoderOptions::union_discriminator`] is set, struct children that do not
+/// declare a field of that name are written with it set to the name of the child
+struct UnionEncoder<'a> {
+    array: &'a UnionArray,
+    /// Child encoders indexed by type id
+    encoders: Vec<Option<Box<dyn Encoder + 'a>>>,
+}
+
+impl<'a> UnionEncoder<'a> {
+    fn try_new(array: &'a UnionArray, options: &EncoderOptions) -> Result<Self, ArrowError> {
+        let fields = match array.data_type() {
+            DataType::Union(fields, _) => fields,
+            _ => unreachable!(),
+        };
+
+        let mut encoders: Vec<_> = (0..128).map(|_| None).collect();
+        for (type_id, field) in fields.iter() {
+            let child = array.child(type_id).as_ref();
+            let encoder = match (child.as_struct_opt(), &options.union_discriminator) {
+                (Some(child), Some(discriminator))
+                    if child.column_by_name(discriminator).is_none() =>
+                {
+                    let mut tag = Vec::new();
+                    encode_string(discriminator, &mut tag);
+                    tag.push(b':');
+                    encode_string(field.name(), &mut tag);
+                    Box::new(StructArrayEncoder::try_new(child, options, Some(tag))?) as _
+                }
+                // Nulls are handled by UnionArray::logical_nulls
+                _ => make_encoder_impl(child, options)?.0,
+            };
+            encoders[type_id as usize] = Some(encoder);
+        }
+        Ok(Self { array, encoders })
+    }
+}
+
+impl Encoder for UnionEncoder<'_> {
+    fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
+        let type_id = self.array.type_id(idx);
+        let offset = self.array.value_offset(idx);
+        let encoder = self.encoders[type_id as usize].as_mut().unwrap();
+        encoder.encode(offset, out)
+    }
+}
+
 impl Encoder for ArrayFormatter<'_> {
     fn encode(&mut self, idx: usize, out: &mut Vec<u8>) {
         out.push(b'"');
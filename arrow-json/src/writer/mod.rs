@@ -253,6 +253,24 @@ impl WriterBuilder {
         self
     }
 
+    /// Returns the name of the field used to identify the child of a [`DataType::Union`]
+    pub fn union_discriminator(&self) -> Option<&str> {
+        self.0.union_discriminator.as_deref()
+    }
+
+    /// Set the name of a field used to identify the child of a [`DataType::Union`]
+    ///
+    /// By default each value of a union is written as the value of its child, and
+    /// the child can only be recovered by reading the JSON value into the first child
+    /// accepting it. If set, struct children are written with a field of this name
+    /// set to the name of the child, unless they declare a field of that name, see
+    /// [`ReaderBuilder::with_union_discriminator`](crate::ReaderBuilder::with_union_discriminator).
+    /// Values of other children are still written without a discriminator.
+    pub fn with_union_discriminator(mut self, union_discriminator: impl Into<String>) -> Self {
+        self.0.union_discriminator = Some(union_discriminator.into());
+        self
+    }
+
     /// Create a new `Writer` with specified `JsonFormat` and builder options.
     pub fn build<W, F>(self, writer: W) -> Writer<W, F>
     where
@@ -1953,4 +1971,116 @@ mod tests {
 "#,
         );
     }
+
+    #[test]
+    fn test_writer_union() {
+        let fields = UnionFields::new(
+            [0, 1],
+            [
+                Field::new("int", DataType::Int64, true),
+                Field::new("str", DataType::Utf8, true),
+            ],
+        );
+        let ints = Int64Array::from(vec![Some(1), None]);
+        let strings = StringArray::from(vec!["x", "y"]);
+        let array = UnionArray::try_new(
+            fields,
+            vec![0, 1, 0, 1].into(),
+            Some(vec![0, 0, 1, 1].into()),
+            vec![Arc::new(ints), Arc::new(strings)],
+        )
+        .unwrap();
+
+        let field = Field::new("u", array.data_type().clone(), false);
+        let schema = Arc::new(Schema::new(vec![field]));
+        let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+
+        let mut buf = Vec::new();
+        {
+            let mut writer = LineDelimitedWriter::new(&mut buf);
+            writer.write_batches(&[&batch]).unwrap();
+        }
+
+        assert_json_eq(
+            &buf,
+            r#"{"u":1}
+{"u":"x"}
+{}
+{"u":"y"}
+"#,
+        );
+
+        let read = ReaderBuilder::new(schema)
+            .build(buf.as_slice())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(read.column(0), batch.column(0));
+    }
+
+    #[test]
+    fn test_writer_union_discriminator() {
+        let fields = UnionFields::new(
+            [0, 1, 2],
+            [
+                Field::new_struct(
+                    "circle",
+                    vec![Field::new("radius", DataType::Float64, true)],
+                    false,
+                ),
+                Field::new_struct(
+                    "square",
+                    vec![Field::new("side", DataType::Float64, true)],
+                    false,
+                ),
+                Field::new("int", DataType::Int64, true),
+            ],
+        );
+        let circles = StructArray::from(vec![(
+            Arc::new(Field::new("radius", DataType::Float64, true)),
+            Arc::new(Float64Array::from(vec![1.5])) as ArrayRef,
+        )]);
+        let squares = StructArray::from(vec![(
+            Arc::new(Field::new("side", DataType::Float64, true)),
+            Arc::new(Float64Array::from(vec![1.5])) as ArrayRef,
+        )]);
+        let ints = Int64Array::from(vec![3]);
+        let array = UnionArray::try_new(
+            fields,
+            vec![1, 0, 2].into(),
+            Some(vec![0, 0, 0].into()),
+            vec![Arc::new(circles), Arc::new(squares), Arc::new(ints)],
+        )
+        .unwrap();
+
+        let field = Field::new("u", array.data_type().clone(), false);
+        let schema = Arc::new(Schema::new(vec![field]));
+        let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap();
+
+        let mut buf = Vec::new();
+        {
+            let builder = WriterBuilder::new().with_union_discriminator("type");
+            let mut writer = builder.build::<_, LineDelimited>(&mut buf);
+            writer.write_batches(&[&batch]).unwrap();
+        }
+
+        assert_json_eq(
+            &buf,
+            r#"{"u":{"type":"square","side":1.5}}
+{"u":{"type":"circle","radius":1.5}}
+{"u":3}
+"#,
+        );
+
+        // Without the discriminator both structs would be read as the first child
+        let read = ReaderBuilder::new(schema)
+            .with_union_discriminator("type")
+            .build(buf.as_slice())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(read.column(0), batch.column(0));
+    }
 }
@@ -29,7 +29,7 @@ use crate::client::list::ListClient;
 use crate::client::retry::RetryExt;
 use crate::client::s3::{
     CompleteMultipartUpload, CompleteMultipartUploadResult, CopyPartResult,
-    InitiateMultipartUploadResult, ListResponse,
+    InitiateMultipartUploadResult, ListResponse, ListVersionsResponse,
 };
 use crate::client::GetOptionsExt;
 use crate::multipart::PartId;
@@ -554,7 +554,21 @@ impl S3Client {
 
     /// Make an S3 Copy request <https://docs.aws.amazon.com/AmazonS3/latest/API/API_CopyObject.html>
     pub(crate) fn copy_request<'a>(&'a self, from: &Path, to: &'a Path) -> Request<'a> {
-        let source = format!("{}/{}", self.config.bucket, encode_path(from));
+        self.copy_version_request(from, None, to)
+    }
+
+    /// Make an S3 Copy request, optionally copying a specific `version` of `from`
+    pub(crate) fn copy_version_request<'a>(
+        &'a self,
+        from: &Path,
+        version: Option<&str>,
+        to: &'a Path,
+    ) -> Request<'a> {
+        let mut source = format!("{}/{}", self.config.bucket, encode_path(from));
+        if let Some(version) = version {
+            let version = utf8_percent_encode(version, &STRICT_PATH_ENCODE_SET);
+            source.push_str(&format!("?versionId={version}"));
+        }
 
         let mut copy_source_encryption_headers = HeaderMap::new();
         if let Some(customer_algorithm) = self
@@ -749,6 +763,48 @@ impl S3Client {
         })
     }
 
+    /// Make an S3 ListObjectVersions request <https://docs.aws.amazon.com/AmazonS3/latest/API/API_ListObjectVersions.html>
+    pub(crate) async fn list_versions_request(
+        &self,
+        prefix: Option<&str>,
+        key_marker: Option<&str>,
+        version_id_marker: Option<&str>,
+    ) -> Result<ListVersionsResponse> {
+        let credential = self.config.get_session_credential().await?;
+        let url = self.config.bucket_endpoint.clone();
+
+        let mut query = Vec::with_capacity(4);
+        query.push(("versions", ""));
+
+        if let Some(prefix) = prefix {
+            query.push(("prefix", prefix))
+        }
+
+        if let Some(key_marker) = key_marker {
+            query.push(("key-marker", key_marker))
+        }
+
+        if let Some(version_id_marker) = version_id_marker {
+            query.push(("version-id-marker", version_id_marker))
+        }
+
+        let response = self
+            .client
+            .request(Method::GET, &url)
+            .query(&query)
+            .with_aws_sigv4(credential.authorizer(), None)
+            .send_retry(&self.config.retry_config)
+            .await
+            .context(ListRequestSnafu)?
+            .bytes()
+            .await
+            .context(ListResponseBodySnafu)?;
+
+        let response =
+            quick_xml::de::from_reader(response.reader()).context(InvalidListResponseSnafu)?;
+        Ok(response)
+    }
+
     #[cfg(test)]
     pub(crate) async fn get_object_tagging(&self, path: &Path) -> Result<Response> {
         let credential = self.config.get_session_credential().await?;
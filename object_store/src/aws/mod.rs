@@ -39,14 +39,16 @@ use url::Url;
 use crate::aws::client::{CompleteMultipartMode, PutPartPayload, RequestError, S3Client};
 use crate::client::get::GetClientExt;
 use crate::client::list::ListClientExt;
+use crate::client::pagination::stream_paginated;
 use crate::client::CredentialProvider;
 use crate::multipart::{MultipartStore, PartId};
+use crate::path::DELIMITER;
 use crate::signer::Signer;
 use crate::util::STRICT_ENCODE_SET;
 use crate::{
-    Error, GetOptions, GetResult, ListResult, MultipartId, MultipartUpload, ObjectMeta,
-    ObjectStore, Path, PutMode, PutMultipartOpts, PutOptions, PutPayload, PutResult, Result,
-    UploadPart,
+    CopyOptions, DeleteOptions, Error, GetOptions, GetResult, ListResult, MultipartId,
+    MultipartUpload, ObjectMeta, ObjectStore, ObjectVersion, Path, PutMode, PutMultipartOpts,
+    PutOptions, PutPayload, PutResult, Result, UploadPart,
 };
 
 static TAGS_HEADER: HeaderName = HeaderName::from_static("x-amz-tagging");
@@ -238,6 +240,15 @@ impl ObjectStore for AmazonS3 {
         Ok(())
     }
 
+    async fn delete_opts(&self, location: &Path, opts: DeleteOptions) -> Result<()> {
+        let mut request = self.client.request(Method::DELETE, location);
+        if let Some(version) = &opts.version {
+            request = request.query(&[("versionId", version)]);
+        }
+        request.send().await?;
+        Ok(())
+    }
+
     fn delete_stream<'a>(
         &'a self,
         locations: BoxStream<'a, Result<Path>>,
@@ -284,6 +295,41 @@ impl ObjectStore for AmazonS3 {
         self.client.list_with_delimiter(prefix).await
     }
 
+    fn list_versions(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectVersion>> {
+        let prefix = prefix
+            .filter(|x| !x.as_ref().is_empty())
+            .map(|p| format!("{}{}", p.as_ref(), DELIMITER));
+
+        // ListObjectVersions is paginated by a key marker and a version id marker,
+        // the latter is threaded through as part of the pagination state
+        stream_paginated(
+            (prefix, None::<String>),
+            move |(prefix, version_id_marker), key_marker| async move {
+                let mut response = self
+                    .client
+                    .list_versions_request(
+                        prefix.as_deref(),
+                        key_marker.as_deref(),
+                        version_id_marker.as_deref(),
+                    )
+                    .await?;
+
+                let (key_marker, version_id_marker) = match response.is_truncated {
+                    true => (
+                        response.next_key_marker.take(),
+                        response.next_version_id_marker.take(),
+                    ),
+                    false => (None, None),
+                };
+                let objects = response.into_objects()?;
+                Ok((objects, (prefix, version_id_marker), key_marker))
+            },
+        )
+        .map_ok(|objects| futures::stream::iter(objects.into_iter().map(Ok)))
+        .try_flatten()
+        .boxed()
+    }
+
     async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
         self.client
             .copy_request(from, to)
@@ -293,6 +339,15 @@ impl ObjectStore for AmazonS3 {
         Ok(())
     }
 
+    async fn copy_opts(&self, from: &Path, to: &Path, opts: CopyOptions) -> Result<()> {
+        self.client
+            .copy_version_request(from, opts.version.as_deref(), to)
+            .idempotent(true)
+            .send()
+            .await?;
+        Ok(())
+    }
+
     async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
         let (k, v, status) = match &self.client.config.copy_if_not_exists {
             Some(S3CopyIfNotExists::Header(k, v)) => (k, v, StatusCode::PRECONDITION_FAILED),
@@ -535,6 +590,15 @@ mod tests {
             Some(S3CopyIfNotExists::Dynamo(d)) => dynamo::integration_test(&integration, d).await,
             _ => eprintln!("Skipping dynamo integration test - dynamo not configured"),
         };
+
+        // Versioning must be enabled on the bucket
+        match std::env::var("AWS_VERSIONED_BUCKET") {
+            Ok(bucket) => {
+                let builder = AmazonS3Builder::from_env().with_bucket_name(bucket);
+                versioning(&builder.build().unwrap()).await;
+            }
+            Err(_) => eprintln!("Skipping versioning test - AWS_VERSIONED_BUCKET not set"),
+        };
     }
 
     #[tokio::test]
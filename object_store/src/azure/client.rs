@@ -352,22 +352,23 @@ impl AzureClient {
     }
 
     /// Make an Azure Delete request <https://docs.microsoft.com/en-us/rest/api/storageservices/delete-blob>
-    pub(crate) async fn delete_request<T: Serialize + ?Sized + Sync>(
-        &self,
-        path: &Path,
-        query: &T,
-    ) -> Result<()> {
+    ///
+    /// If `version` is provided, permanently deletes that version of the blob
+    pub(crate) async fn delete_request(&self, path: &Path, version: Option<&str>) -> Result<()> {
         let credential = self.get_credential().await?;
         let url = self.config.path_url(path);
 
+        let mut builder = self.client.request(Method::DELETE, url);
+        builder = match version {
+            Some(version) => builder.query(&[("versionid", version)]),
+            None => builder.header(&DELETE_SNAPSHOTS, "include"),
+        };
+
         let sensitive = credential
             .as_deref()
             .map(|c| c.sensitive_request())
             .unwrap_or_default();
-        self.client
-            .request(Method::DELETE, url)
-            .query(query)
-            .header(&DELETE_SNAPSHOTS, "include")
+        builder
             .with_azure_authorization(&credential, &self.config.account)
             .retryable(&self.config.retry_config)
             .sensitive(sensitive)
@@ -381,11 +382,23 @@ impl AzureClient {
     }
 
     /// Make an Azure Copy request <https://docs.microsoft.com/en-us/rest/api/storageservices/copy-blob>
-    pub(crate) async fn copy_request(&self, from: &Path, to: &Path, overwrite: bool) -> Result<()> {
+    ///
+    /// If `version` is provided, copies that version of `from`
+    pub(crate) async fn copy_request(
+        &self,
+        from: &Path,
+        version: Option<&str>,
+        to: &Path,
+        overwrite: bool,
+    ) -> Result<()> {
         let credential = self.get_credential().await?;
         let url = self.config.path_url(to);
         let mut source = self.config.path_url(from);
 
+        if let Some(version) = version {
+            source.query_pairs_mut().append_pair("versionid", version);
+        }
+
         // If using SAS authorization must include the headers in the URL
         // <https://docs.microsoft.com/en-us/rest/api/storageservices/copy-blob#request-headers>
         if let Some(AzureCredential::SASToken(pairs)) = credential.as_deref() {
@@ -591,22 +604,21 @@ impl GetClient for AzureClient {
     }
 }
 
-#[async_trait]
-impl ListClient for AzureClient {
+impl AzureClient {
     /// Make an Azure List request <https://docs.microsoft.com/en-us/rest/api/storageservices/list-blobs>
-    async fn list_request(
+    ///
+    /// If `versions` is true, all versions of each blob are listed
+    async fn list_blobs(
         &self,
         prefix: Option<&str>,
         delimiter: bool,
         token: Option<&str>,
-        offset: Option<&str>,
+        versions: bool,
     ) -> Result<(ListResult, Option<String>)> {
-        assert!(offset.is_none()); // Not yet supported
-
         let credential = self.get_credential().await?;
         let url = self.config.path_url(&Path::default());
 
-        let mut query = Vec::with_capacity(5);
+        let mut query = Vec::with_capacity(6);
         query.push(("restype", "container"));
         query.push(("comp", "list"));
 
@@ -622,6 +634,10 @@ impl ListClient for AzureClient {
             query.push(("marker", token))
         }
 
+        if versions {
+            query.push(("include", "versions"))
+        }
+
         let sensitive = credential
             .as_deref()
             .map(|c| c.sensitive_request())
@@ -644,7 +660,34 @@ impl ListClient for AzureClient {
             quick_xml::de::from_reader(response.reader()).context(InvalidListResponseSnafu)?;
         let token = response.next_marker.take();
 
-        Ok((to_list_result(response, prefix)?, token))
+        Ok((to_list_result(response, prefix, versions)?, token))
+    }
+
+    /// Make an Azure List request including all versions of each blob
+    ///
+    /// The version id of each blob is returned as [`ObjectMeta::version`]
+    pub(crate) async fn list_versions_request(
+        &self,
+        prefix: Option<&str>,
+        token: Option<&str>,
+    ) -> Result<(Vec<ObjectMeta>, Option<String>)> {
+        let (result, token) = self.list_blobs(prefix, false, token, true).await?;
+        Ok((result.objects, token))
+    }
+}
+
+#[async_trait]
+impl ListClient for AzureClient {
+    /// Make an Azure List request <https://docs.microsoft.com/en-us/rest/api/storageservices/list-blobs>
+    async fn list_request(
+        &self,
+        prefix: Option<&str>,
+        delimiter: bool,
+        token: Option<&str>,
+        offset: Option<&str>,
+    ) -> Result<(ListResult, Option<String>)> {
+        assert!(offset.is_none()); // Not yet supported
+        self.list_blobs(prefix, delimiter, token, false).await
     }
 }
 
@@ -659,7 +702,11 @@ struct ListResultInternal {
     pub blobs: Blobs,
 }
 
-fn to_list_result(value: ListResultInternal, prefix: Option<&str>) -> Result<ListResult> {
+fn to_list_result(
+    value: ListResultInternal,
+    prefix: Option<&str>,
+    versions: bool,
+) -> Result<ListResult> {
     let prefix = prefix.unwrap_or_default();
     let common_prefixes = value
         .blobs
@@ -679,7 +726,13 @@ fn to_list_result(value: ListResultInternal, prefix: Option<&str>) -> Result<Lis
             !matches!(blob.properties.resource_type.as_ref(), Some(typ) if typ == "directory")
                 && blob.name.len() > prefix.len()
         })
-        .map(ObjectMeta::try_from)
+        .map(|blob| {
+            let version = blob.version_id.clone().filter(|_| versions);
+            Ok(ObjectMeta {
+                version,
+                ..ObjectMeta::try_from(blob)?
+            })
+        })
         .collect::<Result<_>>()?;
 
     Ok(ListResult {
@@ -1005,4 +1058,58 @@ mod tests {
         let _delegated_key_response_internal: UserDelegationKey =
             quick_xml::de::from_str(S).unwrap();
     }
+
+    #[test]
+    fn test_list_versions_response() {
+        const S: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="http://127.0.0.1:10000/devstoreaccount1" ContainerName="test">
+    <Prefix>dir/</Prefix>
+    <Blobs>
+        <Blob>
+            <Name>dir/file</Name>
+            <VersionId>2024-01-01T00:00:00.0000000Z</VersionId>
+            <Properties>
+                <Last-Modified>Mon, 01 Jan 2024 00:00:00 GMT</Last-Modified>
+                <Etag>0x8DC0A4C5F7B2A11</Etag>
+                <Content-Length>1</Content-Length>
+                <Content-Type>application/octet-stream</Content-Type>
+            </Properties>
+        </Blob>
+        <Blob>
+            <Name>dir/file</Name>
+            <VersionId>2024-01-02T00:00:00.0000000Z</VersionId>
+            <IsCurrentVersion>true</IsCurrentVersion>
+            <Properties>
+                <Last-Modified>Tue, 02 Jan 2024 00:00:00 GMT</Last-Modified>
+                <Etag>0x8DC0A4C5F7B2A22</Etag>
+                <Content-Length>2</Content-Length>
+                <Content-Type>application/octet-stream</Content-Type>
+            </Properties>
+        </Blob>
+    </Blobs>
+    <NextMarker>marker</NextMarker>
+</EnumerationResults>"#;
+
+        let response: ListResultInternal = quick_xml::de::from_str(S).unwrap();
+        assert_eq!(response.next_marker.as_deref(), Some("marker"));
+
+        let result = to_list_result(response.clone(), Some("dir/"), true).unwrap();
+        assert!(result.common_prefixes.is_empty());
+        let versions: Vec<_> = result
+            .objects
+            .iter()
+            .map(|o| (o.location.as_ref(), o.size, o.version.as_deref()))
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                ("dir/file", 1, Some("2024-01-01T00:00:00.0000000Z")),
+                ("dir/file", 2, Some("2024-01-02T00:00:00.0000000Z")),
+            ]
+        );
+
+        // The version is only returned when listing versions
+        let result = to_list_result(response, Some("dir/"), false).unwrap();
+        assert!(result.objects.iter().all(|o| o.version.is_none()));
+    }
 }
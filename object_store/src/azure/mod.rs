@@ -24,13 +24,15 @@
 //! Unused blocks will automatically be dropped after 7 days.
 use crate::{
     multipart::{MultipartStore, PartId},
-    path::Path,
+    path::{Path, DELIMITER},
     signer::Signer,
-    GetOptions, GetResult, ListResult, MultipartId, MultipartUpload, ObjectMeta, ObjectStore,
-    PutMultipartOpts, PutOptions, PutPayload, PutResult, Result, UploadPart,
+    CopyOptions, DeleteOptions, GetOptions, GetResult, ListResult, MultipartId, MultipartUpload,
+    ObjectMeta, ObjectStore, ObjectVersion, PutMultipartOpts, PutOptions, PutPayload, PutResult,
+    Result, UploadPart,
 };
 use async_trait::async_trait;
 use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
 use reqwest::Method;
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -39,6 +41,7 @@ use url::Url;
 
 use crate::client::get::GetClientExt;
 use crate::client::list::ListClientExt;
+use crate::client::pagination::stream_paginated;
 use crate::client::CredentialProvider;
 pub use credential::{authority_hosts, AzureAccessKey, AzureAuthorizer};
 
@@ -116,7 +119,12 @@ impl ObjectStore for MicrosoftAzure {
     }
 
     async fn delete(&self, location: &Path) -> Result<()> {
-        self.client.delete_request(location, &()).await
+        self.client.delete_request(location, None).await
+    }
+
+    async fn delete_opts(&self, location: &Path, opts: DeleteOptions) -> Result<()> {
+        let version = opts.version.as_deref();
+        self.client.delete_request(location, version).await
     }
 
     fn list(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectMeta>> {
@@ -127,12 +135,45 @@ impl ObjectStore for MicrosoftAzure {
         self.client.list_with_delimiter(prefix).await
     }
 
+    /// Lists all versions of the blobs with the given prefix
+    ///
+    /// Azure does not have delete markers, instead deleting a blob in an account with
+    /// blob versioning enabled leaves only previous versions, none of which are current
+    fn list_versions(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectVersion>> {
+        let prefix = prefix
+            .filter(|x| !x.as_ref().is_empty())
+            .map(|p| format!("{}{}", p.as_ref(), DELIMITER));
+
+        stream_paginated(prefix, move |prefix, token| async move {
+            let (objects, token) = self
+                .client
+                .list_versions_request(prefix.as_deref(), token.as_deref())
+                .await?;
+            Ok((objects, prefix, token))
+        })
+        .map_ok(|objects| {
+            futures::stream::iter(objects.into_iter().map(|meta| {
+                Ok(ObjectVersion {
+                    meta,
+                    delete_marker: false,
+                })
+            }))
+        })
+        .try_flatten()
+        .boxed()
+    }
+
     async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
-        self.client.copy_request(from, to, true).await
+        self.client.copy_request(from, None, to, true).await
+    }
+
+    async fn copy_opts(&self, from: &Path, to: &Path, opts: CopyOptions) -> Result<()> {
+        let version = opts.version.as_deref();
+        self.client.copy_request(from, version, to, true).await
     }
 
     async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
-        self.client.copy_request(from, to, false).await
+        self.client.copy_request(from, None, to, false).await
     }
 }
 
@@ -0,0 +1,873 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A read-through [`CachingStore`] that caches object ranges in memory and on disk
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use parking_lot::Mutex;
+use tracing::warn;
+
+use crate::local::LocalFileSystem;
+use crate::path::Path;
+use crate::{
+    CopyOptions, DeleteOptions, Error, GetOptions, GetResult, ListResult, MultipartUpload,
+    ObjectMeta, ObjectStore, ObjectVersion, PutMultipartOpts, PutOptions, PutPayload, PutResult,
+    Result, UploadPart,
+};
+
+/// The default size of the blocks cached ranges are aligned to
+const DEFAULT_BLOCK_SIZE: usize = 1024 * 1024;
+
+/// The default capacity of the in-memory tier in bytes
+const DEFAULT_MEMORY_CAPACITY: usize = 128 * 1024 * 1024;
+
+/// The default duration for which [`ObjectMeta`] is cached
+const DEFAULT_METADATA_TTL: Duration = Duration::from_secs(60);
+
+/// The maximum number of concurrent requests made to fetch missing blocks
+const MAX_CONCURRENT_FETCHES: usize = 10;
+
+/// Store wrapper that caches the results of [`ObjectStore::get_range`],
+/// [`ObjectStore::get_ranges`] and [`ObjectStore::head`]
+///
+/// Requested ranges are aligned to blocks of a configurable size, with each block
+/// keyed by the object's path, its [ETag] and the offset of the block. Blocks are
+/// first looked up in a bounded in-memory LRU cache, then in an optional on-disk
+/// cache backed by a [`LocalFileSystem`], and only the missing blocks are fetched
+/// from the wrapped store, with contiguous missing blocks fetched in a single request.
+///
+/// The [`ObjectMeta`] of an object, and therefore its ETag, is cached for a
+/// configurable duration, see [`CachingStore::with_metadata_ttl`]. Block fetches are
+/// made conditional on this ETag, and if the object has since changed all cached data
+/// for the object is discarded. Cached data is also discarded when the object is
+/// written, deleted, copied to or renamed through this store.
+///
+/// Objects without an ETag are never cached.
+///
+/// ```
+/// # use object_store::memory::InMemory;
+/// # use object_store::cache::CachingStore;
+///
+/// // Cache 1 MiB blocks of an in-memory store in up to 64 MiB of memory
+/// let store = CachingStore::new(InMemory::new())
+///     .with_block_size(1024 * 1024)
+///     .with_memory_capacity(64 * 1024 * 1024);
+/// ```
+///
+/// [ETag]: https://datatracker.ietf.org/doc/html/rfc9110#name-etag
+#[derive(Debug)]
+pub struct CachingStore<T: ObjectStore> {
+    inner: T,
+    block_size: usize,
+    metadata_ttl: Duration,
+    cache: Arc<Cache>,
+}
+
+impl<T: ObjectStore> CachingStore<T> {
+    /// Create a new [`CachingStore`] wrapping `inner` with an in-memory cache
+    /// of the default capacity
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            block_size: DEFAULT_BLOCK_SIZE,
+            metadata_ttl: DEFAULT_METADATA_TTL,
+            cache: Arc::new(Cache::new(DEFAULT_MEMORY_CAPACITY, None)),
+        }
+    }
+
+    /// Set the size of the blocks requested ranges are aligned to, defaults to 1 MiB
+    pub fn with_block_size(mut self, block_size: usize) -> Self {
+        assert!(block_size > 0, "block size must be non-zero");
+        self.block_size = block_size;
+        self
+    }
+
+    /// Set the maximum number of bytes cached in memory, defaults to 128 MiB
+    pub fn with_memory_capacity(mut self, capacity: usize) -> Self {
+        self.cache = Arc::new(Cache::new(capacity, self.cache.disk.clone()));
+        self
+    }
+
+    /// Additionally cache blocks on disk in `disk`
+    ///
+    /// Blocks are stored in `disk` with a path derived from the object's path, its
+    /// ETag and the block size, and so the same `disk` can be shared across stores
+    /// and process restarts. Blocks cached for other ETags of an object are removed
+    /// once a new ETag is observed, but the on-disk tier is otherwise not bounded in size.
+    pub fn with_disk_cache(mut self, disk: LocalFileSystem) -> Self {
+        let capacity = self.cache.memory.lock().capacity;
+        self.cache = Arc::new(Cache::new(capacity, Some(Arc::new(disk))));
+        self
+    }
+
+    /// Set the duration for which the [`ObjectMeta`] of an object is cached,
+    /// defaults to 60 seconds
+    ///
+    /// Changes made to an object other than through this store may not be
+    /// observed until this duration has elapsed.
+    pub fn with_metadata_ttl(mut self, ttl: Duration) -> Self {
+        self.metadata_ttl = ttl;
+        self
+    }
+
+    /// Returns the [`ObjectMeta`] for `location`, from the cache if it is not
+    /// older than the configured metadata TTL
+    async fn meta(&self, location: &Path) -> Result<ObjectMeta> {
+        let cached = self.cache.memory.lock().meta(location, self.metadata_ttl);
+        if let Some(meta) = cached {
+            return Ok(meta);
+        }
+        let meta = self.inner.head(location).await?;
+        self.update_meta(&meta).await;
+        Ok(meta)
+    }
+
+    /// Records `meta`, discarding any cached data for other ETags of the object
+    async fn update_meta(&self, meta: &ObjectMeta) {
+        let ttl = self.metadata_ttl;
+        let changed = self.cache.memory.lock().set_meta(meta.clone(), ttl);
+        if changed {
+            // The disk may hold blocks of prior versions no longer tracked in memory
+            let e_tag = meta.e_tag.as_deref();
+            self.cache.invalidate_disk(&meta.location, e_tag).await;
+        }
+    }
+
+    /// Returns the blocks covering `ranges` of the object described by `meta`
+    async fn get_blocks(
+        &self,
+        meta: &ObjectMeta,
+        e_tag: &str,
+        ranges: &[Range<usize>],
+    ) -> Result<HashMap<usize, Bytes>> {
+        let block_size = self.block_size;
+        let location = &meta.location;
+        let block_len = |idx: usize| block_size.min(meta.size - idx * block_size);
+
+        let mut indices: Vec<_> = ranges
+            .iter()
+            .flat_map(|r| r.start / block_size..(r.end + block_size - 1) / block_size)
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+
+        let mut blocks = HashMap::with_capacity(indices.len());
+        let mut missing = Vec::new();
+        {
+            let mut memory = self.cache.memory.lock();
+            for idx in indices {
+                match memory.get(location, e_tag, idx) {
+                    Some(data) => {
+                        blocks.insert(idx, data);
+                    }
+                    None => missing.push(idx),
+                }
+            }
+        }
+
+        if let Some(disk) = &self.cache.disk {
+            let mut remaining = Vec::with_capacity(missing.len());
+            for idx in missing {
+                let key = disk_key(location, e_tag, block_size, idx);
+                match read_disk(disk, &key, block_len(idx)).await {
+                    Some(data) => {
+                        self.cache.memory.lock().insert(
+                            location,
+                            e_tag,
+                            idx,
+                            data.clone(),
+                            self.metadata_ttl,
+                        );
+                        blocks.insert(idx, data);
+                    }
+                    None => remaining.push(idx),
+                }
+            }
+            missing = remaining;
+        }
+
+        // Group missing blocks into contiguous runs
+        let mut runs: Vec<Range<usize>> = Vec::new();
+        for idx in missing {
+            match runs.last_mut() {
+                Some(run) if run.end == idx => run.end += 1,
+                _ => runs.push(idx..idx + 1),
+            }
+        }
+
+        let fetched: Vec<_> = futures::stream::iter(runs)
+            .map(|run| async move {
+                let range = run.start * block_size..(run.end * block_size).min(meta.size);
+                let options = GetOptions {
+                    if_match: Some(e_tag.to_string()),
+                    range: Some(range.clone().into()),
+                    ..Default::default()
+                };
+                let data = self
+                    .inner
+                    .get_opts(location, options)
+                    .await?
+                    .bytes()
+                    .await?;
+                if data.len() != range.len() {
+                    return Err(Error::Generic {
+                        store: "CachingStore",
+                        source: format!(
+                            "expected {} bytes for range {range:?} of {location}, got {}",
+                            range.len(),
+                            data.len()
+                        )
+                        .into(),
+                    });
+                }
+                Ok((run, data))
+            })
+            .buffered(MAX_CONCURRENT_FETCHES)
+            .try_collect()
+            .await?;
+
+        for (run, data) in fetched {
+            for idx in run.clone() {
+                let start = (idx - run.start) * block_size;
+                let block = if run.len() == 1 {
+                    data.clone()
+                } else {
+                    // Copy so that evicting a block releases its memory
+                    Bytes::copy_from_slice(&data[start..start + block_len(idx)])
+                };
+
+                if let Some(disk) = &self.cache.disk {
+                    let key = disk_key(location, e_tag, block_size, idx);
+                    if let Err(e) = disk.put(&key, block.clone().into()).await {
+                        warn!("failed to write {key} to disk cache: {e}");
+                    }
+                }
+                self.cache.memory.lock().insert(
+                    location,
+                    e_tag,
+                    idx,
+                    block.clone(),
+                    self.metadata_ttl,
+                );
+                blocks.insert(idx, block);
+            }
+        }
+        Ok(blocks)
+    }
+}
+
+impl<T: ObjectStore> Display for CachingStore<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CachingStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl<T: ObjectStore> ObjectStore for CachingStore<T> {
+    async fn put(&self, location: &Path, payload: PutPayload) -> Result<PutResult> {
+        let r = self.inner.put(location, payload).await;
+        self.cache.invalidate(location).await;
+        r
+    }
+
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> Result<PutResult> {
+        let r = self.inner.put_opts(location, payload, opts).await;
+        self.cache.invalidate(location).await;
+        r
+    }
+
+    async fn put_multipart(&self, location: &Path) -> Result<Box<dyn MultipartUpload>> {
+        let upload = self.inner.put_multipart(location).await?;
+        Ok(Box::new(CachingUpload {
+            upload,
+            location: location.clone(),
+            cache: Arc::clone(&self.cache),
+        }))
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> Result<Box<dyn MultipartUpload>> {
+        let upload = self.inner.put_multipart_opts(location, opts).await?;
+        Ok(Box::new(CachingUpload {
+            upload,
+            location: location.clone(),
+            cache: Arc::clone(&self.cache),
+        }))
+    }
+
+    async fn get_opts(&self, location: &Path, options: GetOptions) -> Result<GetResult> {
+        let latest = options.version.is_none();
+        let r = self.inner.get_opts(location, options).await?;
+        if latest {
+            self.update_meta(&r.meta).await;
+        }
+        Ok(r)
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> Result<Bytes> {
+        let mut r = self.get_ranges(location, &[range]).await?;
+        Ok(r.pop().unwrap())
+    }
+
+    async fn get_ranges(&self, location: &Path, ranges: &[Range<usize>]) -> Result<Vec<Bytes>> {
+        let meta = self.meta(location).await?;
+        let e_tag = match meta.e_tag.as_deref() {
+            Some(e_tag) if !e_tag.is_empty() => e_tag,
+            _ => return self.inner.get_ranges(location, ranges).await,
+        };
+
+        // Defer to the wrapped store for the handling of invalid ranges
+        if ranges
+            .iter()
+            .any(|r| r.start >= r.end || r.start >= meta.size)
+        {
+            return self.inner.get_ranges(location, ranges).await;
+        }
+
+        // Ranges that extend beyond the end of the object return the remainder
+        let clamped: Vec<_> = ranges
+            .iter()
+            .map(|r| r.start..r.end.min(meta.size))
+            .collect();
+
+        let blocks = match self.get_blocks(&meta, e_tag, &clamped).await {
+            Ok(blocks) => blocks,
+            Err(Error::Precondition { .. }) => {
+                // The object has changed since its metadata was cached
+                self.cache.invalidate(location).await;
+                return self.inner.get_ranges(location, ranges).await;
+            }
+            Err(e) => return Err(e),
+        };
+
+        Ok(clamped
+            .iter()
+            .map(|r| assemble(&blocks, r, self.block_size))
+            .collect())
+    }
+
+    async fn head(&self, location: &Path) -> Result<ObjectMeta> {
+        self.meta(location).await
+    }
+
+    async fn delete(&self, location: &Path) -> Result<()> {
+        let r = self.inner.delete(location).await;
+        self.cache.invalidate(location).await;
+        r
+    }
+
+    async fn delete_opts(&self, location: &Path, opts: DeleteOptions) -> Result<()> {
+        // Deleting the latest version will make the prior version current
+        let r = self.inner.delete_opts(location, opts).await;
+        self.cache.invalidate(location).await;
+        r
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, Result<Path>>,
+    ) -> BoxStream<'a, Result<Path>> {
+        self.inner
+            .delete_stream(locations)
+            .then(move |r| async move {
+                if let Ok(location) = &r {
+                    self.cache.invalidate(location).await;
+                }
+                r
+            })
+            .boxed()
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectMeta>> {
+        self.inner.list(prefix)
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'_, Result<ObjectMeta>> {
+        self.inner.list_with_offset(prefix, offset)
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> Result<ListResult> {
+        self.inner.list_with_delimiter(prefix).await
+    }
+
+    fn list_versions(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectVersion>> {
+        self.inner.list_versions(prefix)
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
+        let r = self.inner.copy(from, to).await;
+        self.cache.invalidate(to).await;
+        r
+    }
+
+    async fn copy_opts(&self, from: &Path, to: &Path, opts: CopyOptions) -> Result<()> {
+        let r = self.inner.copy_opts(from, to, opts).await;
+        self.cache.invalidate(to).await;
+        r
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        let r = self.inner.rename(from, to).await;
+        self.cache.invalidate(from).await;
+        self.cache.invalidate(to).await;
+        r
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        let r = self.inner.copy_if_not_exists(from, to).await;
+        self.cache.invalidate(to).await;
+        r
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        let r = self.inner.rename_if_not_exists(from, to).await;
+        self.cache.invalidate(from).await;
+        self.cache.invalidate(to).await;
+        r
+    }
+}
+
+/// Returns the bytes of `range` from `blocks`
+fn assemble(blocks: &HashMap<usize, Bytes>, range: &Range<usize>, block_size: usize) -> Bytes {
+    let first = range.start / block_size;
+    let last = (range.end - 1) / block_size;
+    if first == last {
+        let offset = first * block_size;
+        return blocks[&first].slice(range.start - offset..range.end - offset);
+    }
+
+    let mut out = BytesMut::with_capacity(range.len());
+    for idx in first..=last {
+        let offset = idx * block_size;
+        let block = &blocks[&idx];
+        let start = range.start.saturating_sub(offset);
+        let end = (range.end - offset).min(block.len());
+        out.extend_from_slice(&block[start..end]);
+    }
+    out.freeze()
+}
+
+/// Returns the path of the block `idx` of `location` in the on-disk cache
+///
+/// Each component is encoded as a single [`PathPart`](crate::path::PathPart),
+/// ensuring distinct objects never map to the same path
+fn disk_key(location: &Path, e_tag: &str, block_size: usize, idx: usize) -> Path {
+    let block = format!("{block_size}-{idx}");
+    Path::from_iter([location.as_ref(), e_tag, block.as_str()])
+}
+
+/// Returns the prefix of all blocks of `location` in the on-disk cache
+fn disk_prefix(location: &Path) -> Path {
+    Path::from_iter([location.as_ref()])
+}
+
+/// Reads the block at `key` from `disk`, returning `None` if it is not present
+/// or is not of the expected length
+async fn read_disk(disk: &LocalFileSystem, key: &Path, len: usize) -> Option<Bytes> {
+    let r = match disk.get(key).await {
+        Ok(r) => r.bytes().await,
+        Err(e) => Err(e),
+    };
+    match r {
+        Ok(data) if data.len() == len => Some(data),
+        Ok(_) | Err(Error::NotFound { .. }) => None,
+        Err(e) => {
+            warn!("failed to read {key} from disk cache: {e}");
+            None
+        }
+    }
+}
+
+/// The cached state shared by a [`CachingStore`] and its [`CachingUpload`]
+#[derive(Debug)]
+struct Cache {
+    memory: Mutex<MemoryCache>,
+    disk: Option<Arc<LocalFileSystem>>,
+}
+
+impl Cache {
+    fn new(capacity: usize, disk: Option<Arc<LocalFileSystem>>) -> Self {
+        Self {
+            memory: Mutex::new(MemoryCache::new(capacity)),
+            disk,
+        }
+    }
+
+    /// Discards all cached data for `location`
+    async fn invalidate(&self, location: &Path) {
+        self.memory.lock().remove(location);
+        self.invalidate_disk(location, None).await
+    }
+
+    /// Discards all blocks of `location` cached on disk, other than those for `e_tag`
+    async fn invalidate_disk(&self, location: &Path, e_tag: Option<&str>) {
+        let disk = match &self.disk {
+            Some(disk) => disk,
+            None => return,
+        };
+        let prefix = disk_prefix(location);
+        let keep = e_tag.map(|e_tag| Path::from_iter([location.as_ref(), e_tag]));
+        let paths = disk
+            .list(Some(&prefix))
+            .map_ok(|m| m.location)
+            .try_filter(move |path| {
+                let discard = match &keep {
+                    Some(keep) => path.prefix_match(keep).is_none(),
+                    None => true,
+                };
+                futures::future::ready(discard)
+            })
+            .boxed();
+        if let Err(e) = disk.delete_stream(paths).try_collect::<Vec<_>>().await {
+            warn!("failed to remove {location} from disk cache: {e}");
+        }
+    }
+}
+
+/// A bounded in-memory cache of blocks, evicted in least recently used order
+///
+/// The [`ObjectMeta`] of objects is not counted towards the capacity, instead objects
+/// without cached blocks are discarded once their metadata has expired
+#[derive(Debug)]
+struct MemoryCache {
+    capacity: usize,
+    size: usize,
+    tick: u64,
+    /// The number of objects following the last removal of expired objects
+    swept: usize,
+    objects: HashMap<Path, CachedObject>,
+    /// The location and index of each cached block, keyed by last access
+    lru: BTreeMap<u64, (Path, usize)>,
+}
+
+#[derive(Debug)]
+struct CachedObject {
+    meta: ObjectMeta,
+    fetched: Instant,
+    /// The data and last access of each cached block
+    blocks: HashMap<usize, (Bytes, u64)>,
+}
+
+impl MemoryCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            size: 0,
+            tick: 0,
+            swept: 0,
+            objects: HashMap::new(),
+            lru: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the cached [`ObjectMeta`] for `location` if not older than `ttl`
+    fn meta(&self, location: &Path, ttl: Duration) -> Option<ObjectMeta> {
+        let object = self.objects.get(location)?;
+        (object.fetched.elapsed() <= ttl).then(|| object.meta.clone())
+    }
+
+    /// Records `meta`, returning true if its ETag was not already cached
+    fn set_meta(&mut self, meta: ObjectMeta, ttl: Duration) -> bool {
+        if let Some(object) = self.objects.get_mut(&meta.location) {
+            if object.meta.e_tag == meta.e_tag {
+                object.meta = meta;
+                object.fetched = Instant::now();
+                return false;
+            }
+        }
+
+        self.remove(&meta.location);
+
+        // Discard expired objects each time their number doubles, amortizing the cost
+        if self.objects.len() >= 2 * self.swept.max(64) {
+            self.objects
+                .retain(|_, o| !o.blocks.is_empty() || o.fetched.elapsed() <= ttl);
+            self.swept = self.objects.len();
+        }
+
+        let object = CachedObject {
+            meta,
+            fetched: Instant::now(),
+            blocks: HashMap::new(),
+        };
+        self.objects.insert(object.meta.location.clone(), object);
+        true
+    }
+
+    /// Returns the cached block `idx` of `location` if it has ETag `e_tag`
+    fn get(&mut self, location: &Path, e_tag: &str, idx: usize) -> Option<Bytes> {
+        let object = self.objects.get_mut(location)?;
+        if object.meta.e_tag.as_deref() != Some(e_tag) {
+            return None;
+        }
+        let (data, last_access) = object.blocks.get_mut(&idx)?;
+
+        self.tick += 1;
+        let key = self.lru.remove(last_access).unwrap();
+        self.lru.insert(self.tick, key);
+        *last_access = self.tick;
+        Some(data.clone())
+    }
+
+    /// Caches block `idx` of `location` if its cached ETag is `e_tag`, evicting
+    /// the least recently used blocks to stay within capacity
+    ///
+    /// Objects whose last block is evicted are discarded if older than `ttl`
+    fn insert(&mut self, location: &Path, e_tag: &str, idx: usize, data: Bytes, ttl: Duration) {
+        if data.len() > self.capacity {
+            return;
+        }
+        let object = match self.objects.get_mut(location) {
+            Some(object) => object,
+            None => return,
+        };
+        if object.meta.e_tag.as_deref() != Some(e_tag) {
+            return;
+        }
+
+        self.tick += 1;
+        self.size += data.len();
+        if let Some((old, last_access)) = object.blocks.insert(idx, (data, self.tick)) {
+            self.size -= old.len();
+            self.lru.remove(&last_access);
+        }
+        self.lru.insert(self.tick, (location.clone(), idx));
+
+        while self.size > self.capacity {
+            let oldest = *self.lru.keys().next().unwrap();
+            let (location, idx) = self.lru.remove(&oldest).unwrap();
+            let object = self.objects.get_mut(&location).unwrap();
+            let (data, _) = object.blocks.remove(&idx).unwrap();
+            self.size -= data.len();
+            if object.blocks.is_empty() && object.fetched.elapsed() > ttl {
+                self.objects.remove(&location);
+            }
+        }
+    }
+
+    /// Removes `location` and all its cached blocks
+    fn remove(&mut self, location: &Path) -> Option<CachedObject> {
+        let object = self.objects.remove(location)?;
+        for (data, last_access) in object.blocks.values() {
+            self.size -= data.len();
+            self.lru.remove(last_access);
+        }
+        Some(object)
+    }
+}
+
+/// A [`MultipartUpload`] wrapper that discards cached data for the
+/// destination once the upload completes
+#[derive(Debug)]
+struct CachingUpload {
+    upload: Box<dyn MultipartUpload>,
+    location: Path,
+    cache: Arc<Cache>,
+}
+
+#[async_trait]
+impl MultipartUpload for CachingUpload {
+    fn put_part(&mut self, data: PutPayload) -> UploadPart {
+        self.upload.put_part(data)
+    }
+
+    async fn complete(&mut self) -> Result<PutResult> {
+        let r = self.upload.complete().await;
+        self.cache.invalidate(&self.location).await;
+        r
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.upload.abort().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::*;
+    use crate::memory::InMemory;
+
+    #[tokio::test]
+    async fn caching_test() {
+        let temporary = tempfile::tempdir().unwrap();
+        let disk = LocalFileSystem::new_with_prefix(temporary.path()).unwrap();
+        let integration = CachingStore::new(InMemory::new())
+            .with_block_size(7)
+            .with_disk_cache(disk);
+
+        put_get_delete_list(&integration).await;
+        get_opts(&integration).await;
+        put_opts(&integration, true).await;
+        list_uses_directories_correctly(&integration).await;
+        list_with_delimiter(&integration).await;
+        rename_and_copy(&integration).await;
+        copy_if_not_exists(&integration).await;
+        stream_get(&integration).await;
+    }
+
+    #[tokio::test]
+    async fn caching_invalidation() {
+        let inner = Arc::new(InMemory::new());
+        let store =
+            CachingStore::new(Arc::clone(&inner) as Arc<dyn ObjectStore>).with_block_size(4);
+
+        let location = Path::from("data");
+        store.put(&location, "0123456789".into()).await.unwrap();
+
+        let ranges = store
+            .get_ranges(&location, &[1..3, 2..9, 8..20])
+            .await
+            .unwrap();
+        assert_eq!(ranges, vec!["12", "2345678", "89"]);
+
+        // Changes not made through the store are not observed whilst cached
+        inner.put(&location, "abcdefghij".into()).await.unwrap();
+        let r = store.get_range(&location, 0..4).await.unwrap();
+        assert_eq!(r, "0123");
+
+        // Once expired the ETag of the object is revalidated
+        let store = store.with_metadata_ttl(Duration::ZERO);
+        let r = store.get_range(&location, 0..4).await.unwrap();
+        assert_eq!(r, "abcd");
+
+        // Fetches of uncached blocks detect that the object has changed
+        let store = store.with_metadata_ttl(Duration::from_secs(60));
+        inner.put(&location, "klmnopqrst".into()).await.unwrap();
+        let r = store.get_range(&location, 8..10).await.unwrap();
+        assert_eq!(r, "st");
+        let r = store.get_range(&location, 0..4).await.unwrap();
+        assert_eq!(r, "klmn");
+
+        // Writes through the store are observed immediately
+        store.put(&location, "uvwxyz".into()).await.unwrap();
+        let r = store.get_range(&location, 2..10).await.unwrap();
+        assert_eq!(r, "wxyz");
+        assert_eq!(store.head(&location).await.unwrap().size, 6);
+    }
+
+    #[tokio::test]
+    async fn caching_disk() {
+        let temporary = tempfile::tempdir().unwrap();
+        let inner = Arc::new(InMemory::new());
+        let disk = LocalFileSystem::new_with_prefix(temporary.path()).unwrap();
+        let store = CachingStore::new(Arc::clone(&inner) as Arc<dyn ObjectStore>)
+            .with_block_size(4)
+            .with_memory_capacity(0)
+            .with_disk_cache(disk);
+
+        let location = Path::from("a/b");
+        store.put(&location, "0123456789".into()).await.unwrap();
+        let r = store.get_range(&location, 2..6).await.unwrap();
+        assert_eq!(r, "2345");
+
+        // Blocks are served from disk
+        inner.put(&location, "abcdefghij".into()).await.unwrap();
+        let r = store.get_range(&location, 2..6).await.unwrap();
+        assert_eq!(r, "2345");
+
+        let disk = LocalFileSystem::new_with_prefix(temporary.path()).unwrap();
+        let files: Vec<_> = disk.list(None).try_collect().await.unwrap();
+        assert_eq!(files.len(), 2);
+
+        store.delete(&location).await.unwrap();
+        let files: Vec<_> = disk.list(None).try_collect().await.unwrap();
+        assert!(files.is_empty());
+    }
+
+    #[tokio::test]
+    async fn caching_disk_e_tag() {
+        let temporary = tempfile::tempdir().unwrap();
+        let inner = Arc::new(InMemory::new());
+        let disk = || LocalFileSystem::new_with_prefix(temporary.path()).unwrap();
+        let store = || {
+            CachingStore::new(Arc::clone(&inner) as Arc<dyn ObjectStore>)
+                .with_block_size(4)
+                .with_disk_cache(disk())
+        };
+
+        let location = Path::from("a/b");
+        inner.put(&location, "0123456789".into()).await.unwrap();
+        let r = store().get_range(&location, 2..6).await.unwrap();
+        assert_eq!(r, "2345");
+
+        let files: Vec<_> = disk().list(None).try_collect().await.unwrap();
+        assert_eq!(files.len(), 2);
+
+        // Blocks of the prior ETag are discarded even if not cached in memory
+        inner.put(&location, "abcdefghij".into()).await.unwrap();
+        let r = store().get_range(&location, 0..2).await.unwrap();
+        assert_eq!(r, "ab");
+
+        let files: Vec<_> = disk().list(None).try_collect().await.unwrap();
+        assert_eq!(files.len(), 1);
+    }
+
+    #[test]
+    fn memory_cache_expiry() {
+        let ttl = Duration::from_millis(100);
+        let meta = |i: usize| ObjectMeta {
+            location: Path::from(i.to_string()),
+            last_modified: Default::default(),
+            size: 4,
+            e_tag: Some("0".to_string()),
+            version: None,
+        };
+
+        let mut cache = MemoryCache::new(8);
+        for i in 0..100 {
+            assert!(cache.set_meta(meta(i), ttl));
+        }
+        assert!(!cache.set_meta(meta(0), ttl));
+        cache.insert(&Path::from("0"), "0", 0, "abcd".into(), ttl);
+
+        // Expired objects without cached blocks are discarded
+        std::thread::sleep(2 * ttl);
+        for i in 100..200 {
+            assert!(cache.set_meta(meta(i), ttl));
+        }
+        assert_eq!(cache.objects.len(), 101);
+        assert!(cache.get(&Path::from("0"), "0", 0).is_some());
+
+        // Expired objects are discarded along with their last block
+        cache.insert(&Path::from("100"), "0", 0, "efgh".into(), ttl);
+        cache.insert(&Path::from("101"), "0", 0, "ijkl".into(), ttl);
+        assert!(!cache.objects.contains_key(&Path::from("0")));
+        assert_eq!(cache.objects.len(), 100);
+    }
+}
@@ -17,8 +17,6 @@
 
 //! Generic utilities reqwest based ObjectStore implementations
 
-pub(crate) mod backoff;
-
 #[cfg(test)]
 pub(crate) mod mock_server;
 
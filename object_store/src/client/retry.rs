@@ -17,8 +17,8 @@
 
 //! A shared HTTP client implementation incorporating retries
 
-use crate::client::backoff::{Backoff, BackoffConfig};
-use crate::PutPayload;
+use crate::backoff::Backoff;
+use crate::{PutPayload, RetryConfig};
 use futures::future::BoxFuture;
 use reqwest::header::LOCATION;
 use reqwest::{Client, Request, Response, StatusCode};
@@ -139,53 +139,6 @@ impl From<Error> for std::io::Error {
 
 pub(crate) type Result<T, E = Error> = std::result::Result<T, E>;
 
-/// The configuration for how to respond to request errors
-///
-/// The following categories of error will be retried:
-///
-/// * 5xx server errors
-/// * Connection errors
-/// * Dropped connections
-/// * Timeouts for [safe] / read-only requests
-///
-/// Requests will be retried up to some limit, using exponential
-/// backoff with jitter. See [`BackoffConfig`] for more information
-///
-/// [safe]: https://datatracker.ietf.org/doc/html/rfc7231#section-4.2.1
-#[derive(Debug, Clone)]
-pub struct RetryConfig {
-    /// The backoff configuration
-    pub backoff: BackoffConfig,
-
-    /// The maximum number of times to retry a request
-    ///
-    /// Set to 0 to disable retries
-    pub max_retries: usize,
-
-    /// The maximum length of time from the initial request
-    /// after which no further retries will be attempted
-    ///
-    /// This not only bounds the length of time before a server
-    /// error will be surfaced to the application, but also bounds
-    /// the length of time a request's credentials must remain valid.
-    ///
-    /// As requests are retried without renewing credentials or
-    /// regenerating request payloads, this number should be kept
-    /// below 5 minutes to avoid errors due to expired credentials
-    /// and/or request payloads
-    pub retry_timeout: Duration,
-}
-
-impl Default for RetryConfig {
-    fn default() -> Self {
-        Self {
-            backoff: Default::default(),
-            max_retries: 10,
-            retry_timeout: Duration::from_secs(3 * 60),
-        }
-    }
-}
-
 fn body_contains_error(response_body: &str) -> bool {
     response_body.contains("InternalError") || response_body.contains("SlowDown")
 }
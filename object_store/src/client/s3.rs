@@ -18,6 +18,8 @@
 
 use crate::multipart::PartId;
 use crate::path::Path;
+#[cfg(feature = "aws")]
+use crate::ObjectVersion;
 use crate::{ListResult, ObjectMeta, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -70,6 +72,8 @@ pub struct ListContents {
     pub last_modified: DateTime<Utc>,
     #[serde(rename = "ETag")]
     pub e_tag: Option<String>,
+    /// The GCS object generation, only returned when listing versions
+    pub generation: Option<String>,
 }
 
 impl TryFrom<ListContents> for ObjectMeta {
@@ -86,6 +90,77 @@ impl TryFrom<ListContents> for ObjectMeta {
     }
 }
 
+#[cfg(feature = "aws")]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct ListVersionsResponse {
+    #[serde(default)]
+    pub version: Vec<ListVersion>,
+    #[serde(default)]
+    pub delete_marker: Vec<ListDeleteMarker>,
+    #[serde(default)]
+    pub is_truncated: bool,
+    pub next_key_marker: Option<String>,
+    pub next_version_id_marker: Option<String>,
+}
+
+#[cfg(feature = "aws")]
+impl ListVersionsResponse {
+    /// Returns the object versions and delete markers in this response
+    pub(crate) fn into_objects(self) -> Result<Vec<ObjectVersion>> {
+        let versions = self.version.into_iter().map(|v| {
+            let meta = ObjectMeta {
+                location: Path::parse(v.key)?,
+                last_modified: v.last_modified,
+                size: v.size,
+                e_tag: v.e_tag,
+                version: Some(v.version_id),
+            };
+            Ok(ObjectVersion {
+                meta,
+                delete_marker: false,
+            })
+        });
+
+        let markers = self.delete_marker.into_iter().map(|m| {
+            let meta = ObjectMeta {
+                location: Path::parse(m.key)?,
+                last_modified: m.last_modified,
+                size: 0,
+                e_tag: None,
+                version: Some(m.version_id),
+            };
+            Ok(ObjectVersion {
+                meta,
+                delete_marker: true,
+            })
+        });
+
+        versions.chain(markers).collect()
+    }
+}
+
+#[cfg(feature = "aws")]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct ListVersion {
+    pub key: String,
+    pub version_id: String,
+    pub size: usize,
+    pub last_modified: DateTime<Utc>,
+    #[serde(rename = "ETag")]
+    pub e_tag: Option<String>,
+}
+
+#[cfg(feature = "aws")]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct ListDeleteMarker {
+    pub key: String,
+    pub version_id: String,
+    pub last_modified: DateTime<Utc>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub(crate) struct InitiateMultipartUploadResult {
@@ -134,3 +209,141 @@ pub(crate) struct CompleteMultipartUploadResult {
     #[serde(rename = "ETag")]
     pub e_tag: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_generations_response() {
+        const S: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://doc.s3.amazonaws.com/2006-03-01">
+    <Name>bucket</Name>
+    <Prefix>dir/</Prefix>
+    <Contents>
+        <Key>dir/file</Key>
+        <Generation>1704067200000000</Generation>
+        <MetaGeneration>1</MetaGeneration>
+        <LastModified>2024-01-01T00:00:00.000Z</LastModified>
+        <ETag>"0cc175b9c0f1b6a831c399e269772661"</ETag>
+        <Size>1</Size>
+    </Contents>
+    <Contents>
+        <Key>dir/file</Key>
+        <Generation>1704153600000000</Generation>
+        <MetaGeneration>1</MetaGeneration>
+        <LastModified>2024-01-02T00:00:00.000Z</LastModified>
+        <ETag>"92eb5ffee6ae2fec3ad71c777531578f"</ETag>
+        <Size>2</Size>
+    </Contents>
+    <NextContinuationToken>token</NextContinuationToken>
+</ListBucketResult>"#;
+
+        let response: ListResponse = quick_xml::de::from_str(S).unwrap();
+        assert_eq!(response.next_continuation_token.as_deref(), Some("token"));
+        let generations: Vec<_> = response
+            .contents
+            .iter()
+            .map(|c| (c.key.as_str(), c.size, c.generation.as_deref()))
+            .collect();
+        assert_eq!(
+            generations,
+            vec![
+                ("dir/file", 1, Some("1704067200000000")),
+                ("dir/file", 2, Some("1704153600000000")),
+            ]
+        );
+
+        // The generation is not returned as the version of a regular listing
+        let result = ListResult::try_from(response).unwrap();
+        assert!(result.objects.iter().all(|o| o.version.is_none()));
+    }
+
+    #[cfg(feature = "aws")]
+    #[test]
+    fn test_list_versions_response() {
+        const S: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListVersionsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+    <Name>bucket</Name>
+    <Prefix>dir/</Prefix>
+    <KeyMarker/>
+    <VersionIdMarker/>
+    <NextKeyMarker>dir/b</NextKeyMarker>
+    <NextVersionIdMarker>v3</NextVersionIdMarker>
+    <MaxKeys>3</MaxKeys>
+    <IsTruncated>true</IsTruncated>
+    <Version>
+        <Key>dir/a</Key>
+        <VersionId>v1</VersionId>
+        <IsLatest>true</IsLatest>
+        <LastModified>2024-01-01T00:00:00.000Z</LastModified>
+        <ETag>"0cc175b9c0f1b6a831c399e269772661"</ETag>
+        <Size>1</Size>
+        <StorageClass>STANDARD</StorageClass>
+    </Version>
+    <DeleteMarker>
+        <Key>dir/b</Key>
+        <VersionId>v2</VersionId>
+        <IsLatest>true</IsLatest>
+        <LastModified>2024-01-03T00:00:00.000Z</LastModified>
+    </DeleteMarker>
+    <Version>
+        <Key>dir/b</Key>
+        <VersionId>v3</VersionId>
+        <IsLatest>false</IsLatest>
+        <LastModified>2024-01-02T00:00:00.000Z</LastModified>
+        <ETag>"92eb5ffee6ae2fec3ad71c777531578f"</ETag>
+        <Size>2</Size>
+        <StorageClass>STANDARD</StorageClass>
+    </Version>
+</ListVersionsResult>"#;
+
+        let response: ListVersionsResponse = quick_xml::de::from_str(S).unwrap();
+        assert!(response.is_truncated);
+        assert_eq!(response.next_key_marker.as_deref(), Some("dir/b"));
+        assert_eq!(response.next_version_id_marker.as_deref(), Some("v3"));
+
+        let objects = response.into_objects().unwrap();
+        let versions: Vec<_> = objects
+            .iter()
+            .map(|o| {
+                let location = o.meta.location.as_ref();
+                (
+                    location,
+                    o.meta.size,
+                    o.meta.version.as_deref(),
+                    o.delete_marker,
+                )
+            })
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                ("dir/a", 1, Some("v1"), false),
+                ("dir/b", 2, Some("v3"), false),
+                ("dir/b", 0, Some("v2"), true),
+            ]
+        );
+        assert_eq!(
+            objects[0].meta.e_tag.as_deref(),
+            Some("\"0cc175b9c0f1b6a831c399e269772661\"")
+        );
+        assert_eq!(objects[2].meta.e_tag, None);
+
+        const FINAL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListVersionsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+    <Name>bucket</Name>
+    <Prefix>dir/</Prefix>
+    <KeyMarker>dir/b</KeyMarker>
+    <VersionIdMarker>v3</VersionIdMarker>
+    <MaxKeys>3</MaxKeys>
+    <IsTruncated>false</IsTruncated>
+</ListVersionsResult>"#;
+
+        let response: ListVersionsResponse = quick_xml::de::from_str(FINAL).unwrap();
+        assert!(!response.is_truncated);
+        assert_eq!(response.next_key_marker, None);
+        assert_eq!(response.next_version_id_marker, None);
+        assert!(response.into_objects().unwrap().is_empty());
+    }
+}
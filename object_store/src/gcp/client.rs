@@ -29,7 +29,7 @@ use crate::multipart::PartId;
 use crate::path::{Path, DELIMITER};
 use crate::util::hex_encode;
 use crate::{
-    Attribute, Attributes, ClientOptions, GetOptions, ListResult, MultipartId, PutMode,
+    Attribute, Attributes, ClientOptions, GetOptions, ListResult, MultipartId, ObjectMeta, PutMode,
     PutMultipartOpts, PutOptions, PutPayload, PutResult, Result, RetryConfig,
 };
 use async_trait::async_trait;
@@ -533,15 +533,24 @@ impl GoogleCloudStorageClient {
     }
 
     /// Perform a delete request <https://cloud.google.com/storage/docs/xml-api/delete-object>
-    pub(crate) async fn delete_request(&self, path: &Path) -> Result<()> {
-        self.request(Method::DELETE, path).send().await?;
+    ///
+    /// If `generation` is provided, permanently deletes that generation of the object
+    pub(crate) async fn delete_request(&self, path: &Path, generation: Option<&str>) -> Result<()> {
+        let mut request = self.request(Method::DELETE, path);
+        if let Some(generation) = generation {
+            request = request.query(&[("generation", generation)]);
+        }
+        request.send().await?;
         Ok(())
     }
 
     /// Perform a copy request <https://cloud.google.com/storage/docs/xml-api/put-object-copy>
+    ///
+    /// If `generation` is provided, copies that generation of `from`
     pub(crate) async fn copy_request(
         &self,
         from: &Path,
+        generation: Option<&str>,
         to: &Path,
         if_not_exists: bool,
     ) -> Result<()> {
@@ -556,6 +565,10 @@ impl GoogleCloudStorageClient {
             .request(Method::PUT, url)
             .header("x-goog-copy-source", source);
 
+        if let Some(generation) = generation {
+            builder = builder.header("x-goog-copy-source-generation", generation);
+        }
+
         if if_not_exists {
             builder = builder.header(&VERSION_MATCH, 0);
         }
@@ -623,20 +636,22 @@ impl GetClient for GoogleCloudStorageClient {
     }
 }
 
-#[async_trait]
-impl ListClient for GoogleCloudStorageClient {
+impl GoogleCloudStorageClient {
     /// Perform a list request <https://cloud.google.com/storage/docs/xml-api/get-bucket-list>
-    async fn list_request(
+    ///
+    /// If `versions` is true, all generations of each object are listed
+    async fn list_objects(
         &self,
         prefix: Option<&str>,
         delimiter: bool,
         page_token: Option<&str>,
         offset: Option<&str>,
-    ) -> Result<(ListResult, Option<String>)> {
+        versions: bool,
+    ) -> Result<ListResponse> {
         let credential = self.get_credential().await?;
         let url = format!("{}/{}", self.config.base_url, self.bucket_name_encoded);
 
-        let mut query = Vec::with_capacity(5);
+        let mut query = Vec::with_capacity(6);
         query.push(("list-type", "2"));
         if delimiter {
             query.push(("delimiter", DELIMITER))
@@ -658,6 +673,10 @@ impl ListClient for GoogleCloudStorageClient {
             query.push(("start-after", offset))
         }
 
+        if versions {
+            query.push(("versions", "true"))
+        }
+
         let response = self
             .client
             .request(Method::GET, url)
@@ -670,8 +689,52 @@ impl ListClient for GoogleCloudStorageClient {
             .await
             .context(ListResponseBodySnafu)?;
 
-        let mut response: ListResponse =
+        let response =
             quick_xml::de::from_reader(response.reader()).context(InvalidListResponseSnafu)?;
+        Ok(response)
+    }
+
+    /// Perform a list request including all generations of each object
+    ///
+    /// The generation of each object is returned as [`ObjectMeta::version`]
+    pub(crate) async fn list_versions_request(
+        &self,
+        prefix: Option<&str>,
+        page_token: Option<&str>,
+    ) -> Result<(Vec<ObjectMeta>, Option<String>)> {
+        let mut response = self
+            .list_objects(prefix, false, page_token, None, true)
+            .await?;
+
+        let token = response.next_continuation_token.take();
+        let objects = response
+            .contents
+            .into_iter()
+            .map(|c| {
+                let version = c.generation.clone();
+                Ok(ObjectMeta {
+                    version,
+                    ..ObjectMeta::try_from(c)?
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok((objects, token))
+    }
+}
+
+#[async_trait]
+impl ListClient for GoogleCloudStorageClient {
+    /// Perform a list request <https://cloud.google.com/storage/docs/xml-api/get-bucket-list>
+    async fn list_request(
+        &self,
+        prefix: Option<&str>,
+        delimiter: bool,
+        page_token: Option<&str>,
+        offset: Option<&str>,
+    ) -> Result<(ListResult, Option<String>)> {
+        let mut response = self
+            .list_objects(prefix, delimiter, page_token, offset, false)
+            .await?;
 
         let token = response.next_continuation_token.take();
         Ok((response.try_into()?, token))
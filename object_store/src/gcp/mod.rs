@@ -41,18 +41,20 @@ use crate::client::CredentialProvider;
 use crate::gcp::credential::GCSAuthorizer;
 use crate::signer::Signer;
 use crate::{
-    multipart::PartId, path::Path, GetOptions, GetResult, ListResult, MultipartId, MultipartUpload,
-    ObjectMeta, ObjectStore, PutMultipartOpts, PutOptions, PutPayload, PutResult, Result,
-    UploadPart,
+    multipart::PartId, path::Path, path::DELIMITER, CopyOptions, DeleteOptions, GetOptions,
+    GetResult, ListResult, MultipartId, MultipartUpload, ObjectMeta, ObjectStore, ObjectVersion,
+    PutMultipartOpts, PutOptions, PutPayload, PutResult, Result, UploadPart,
 };
 use async_trait::async_trait;
 use client::GoogleCloudStorageClient;
 use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
 use hyper::Method;
 use url::Url;
 
 use crate::client::get::GetClientExt;
 use crate::client::list::ListClientExt;
+use crate::client::pagination::stream_paginated;
 use crate::client::parts::Parts;
 use crate::multipart::MultipartStore;
 pub use builder::{GoogleCloudStorageBuilder, GoogleConfigKey};
@@ -180,7 +182,12 @@ impl ObjectStore for GoogleCloudStorage {
     }
 
     async fn delete(&self, location: &Path) -> Result<()> {
-        self.client.delete_request(location).await
+        self.client.delete_request(location, None).await
+    }
+
+    async fn delete_opts(&self, location: &Path, opts: DeleteOptions) -> Result<()> {
+        let generation = opts.version.as_deref();
+        self.client.delete_request(location, generation).await
     }
 
     fn list(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectMeta>> {
@@ -199,12 +206,45 @@ impl ObjectStore for GoogleCloudStorage {
         self.client.list_with_delimiter(prefix).await
     }
 
+    /// Lists all generations of the objects with the given prefix
+    ///
+    /// GCS does not have delete markers, instead deleting an object in a bucket with
+    /// object versioning enabled makes the live generation noncurrent
+    fn list_versions(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectVersion>> {
+        let prefix = prefix
+            .filter(|x| !x.as_ref().is_empty())
+            .map(|p| format!("{}{}", p.as_ref(), DELIMITER));
+
+        stream_paginated(prefix, move |prefix, token| async move {
+            let (objects, token) = self
+                .client
+                .list_versions_request(prefix.as_deref(), token.as_deref())
+                .await?;
+            Ok((objects, prefix, token))
+        })
+        .map_ok(|objects| {
+            futures::stream::iter(objects.into_iter().map(|meta| {
+                Ok(ObjectVersion {
+                    meta,
+                    delete_marker: false,
+                })
+            }))
+        })
+        .try_flatten()
+        .boxed()
+    }
+
     async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
-        self.client.copy_request(from, to, false).await
+        self.client.copy_request(from, None, to, false).await
+    }
+
+    async fn copy_opts(&self, from: &Path, to: &Path, opts: CopyOptions) -> Result<()> {
+        let generation = opts.version.as_deref();
+        self.client.copy_request(from, generation, to, false).await
     }
 
     async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
-        self.client.copy_request(from, to, true).await
+        self.client.copy_request(from, None, to, true).await
     }
 }
 
@@ -17,11 +17,13 @@
 
 use crate::client::get::GetClient;
 use crate::client::header::HeaderConfig;
-use crate::client::retry::{self, RetryConfig, RetryExt};
+use crate::client::retry::{self, RetryExt};
 use crate::client::GetOptionsExt;
 use crate::path::{Path, DELIMITER};
 use crate::util::deserialize_rfc1123;
-use crate::{Attribute, Attributes, ClientOptions, GetOptions, ObjectMeta, PutPayload, Result};
+use crate::{
+    Attribute, Attributes, ClientOptions, GetOptions, ObjectMeta, PutPayload, Result, RetryConfig,
+};
 use async_trait::async_trait;
 use bytes::Buf;
 use chrono::{DateTime, Utc};
@@ -27,8 +27,8 @@
 use crate::multipart::MultipartStore;
 use crate::path::Path;
 use crate::{
-    Attribute, Attributes, DynObjectStore, Error, GetOptions, GetRange, ObjectStore, PutMode,
-    PutPayload, UpdateVersion, WriteMultipart,
+    Attribute, Attributes, CopyOptions, DeleteOptions, DynObjectStore, Error, GetOptions, GetRange,
+    ObjectStore, ObjectVersion, PutMode, PutPayload, UpdateVersion, WriteMultipart,
 };
 use bytes::Bytes;
 use futures::stream::FuturesUnordered;
@@ -1060,6 +1060,95 @@ pub async fn copy_rename_nonexistent_object(storage: &DynObjectStore) {
     storage.delete(&path2).await.unwrap();
 }
 
+/// Tests versioned objects, requires the store to have versioning enabled
+pub async fn versioning(storage: &DynObjectStore) {
+    let prefix = Path::from("versioning");
+    let path = prefix.child("file");
+
+    let v1 = storage.put(&path, "a".into()).await.unwrap();
+    let v2 = storage.put(&path, "b".into()).await.unwrap();
+    let version1 = v1.version.unwrap();
+    let version2 = v2.version.unwrap();
+    assert_ne!(version1, version2);
+
+    // Can read a previous version
+    let options = GetOptions {
+        version: Some(version1.clone()),
+        ..Default::default()
+    };
+    let r = storage.get_opts(&path, options).await.unwrap();
+    assert_eq!(r.meta.version.as_ref(), Some(&version1));
+    assert_eq!(r.bytes().await.unwrap().as_ref(), b"a");
+
+    // Deleting the object retains previous versions
+    storage.delete(&path).await.unwrap();
+    let err = storage.head(&path).await.unwrap_err();
+    assert!(matches!(err, Error::NotFound { .. }), "{err}");
+
+    let versions: Vec<_> = storage
+        .list_versions(Some(&prefix))
+        .try_collect()
+        .await
+        .unwrap();
+    let mut listed: Vec<_> = versions
+        .iter()
+        .filter(|v| !v.delete_marker)
+        .map(|v| {
+            assert_eq!(v.meta.location, path);
+            v.meta.version.clone().unwrap()
+        })
+        .collect();
+    listed.sort_unstable();
+    let mut expected = vec![version1.clone(), version2.clone()];
+    expected.sort_unstable();
+    assert_eq!(listed, expected);
+
+    // Can restore a previous version
+    let opts = CopyOptions {
+        version: Some(version1.clone()),
+    };
+    storage.copy_opts(&path, &path, opts).await.unwrap();
+    let b = storage.get(&path).await.unwrap().bytes().await.unwrap();
+    assert_eq!(b.as_ref(), b"a");
+
+    // Can permanently delete a version
+    let opts = DeleteOptions {
+        version: Some(version2.clone()),
+    };
+    storage.delete_opts(&path, opts).await.unwrap();
+    let options = GetOptions {
+        version: Some(version2.clone()),
+        ..Default::default()
+    };
+    let err = storage.get_opts(&path, options).await.unwrap_err();
+    assert!(matches!(err, Error::NotFound { .. }), "{err}");
+
+    // Clean up all versions, including any delete markers
+    let versions: Vec<_> = storage
+        .list_versions(Some(&prefix))
+        .try_collect()
+        .await
+        .unwrap();
+    assert!(versions
+        .iter()
+        .all(|v| v.meta.version != Some(version2.clone())));
+    for ObjectVersion { meta, .. } in versions {
+        let opts = DeleteOptions {
+            version: meta.version,
+        };
+        storage.delete_opts(&meta.location, opts).await.unwrap();
+    }
+
+    let versions: Vec<_> = storage
+        .list_versions(Some(&prefix))
+        .try_collect()
+        .await
+        .unwrap();
+    assert!(versions.is_empty(), "{versions:?}");
+    let err = storage.head(&path).await.unwrap_err();
+    assert!(matches!(err, Error::NotFound { .. }), "{err}");
+}
+
 /// Tests [`MultipartStore`]
 pub async fn multipart(storage: &dyn ObjectStore, multipart: &dyn MultipartStore) {
     let path = Path::from("test_multipart");
@@ -115,6 +115,12 @@
 //!
 //! * Rate Throttling: [`ThrottleConfig`](throttle::ThrottleConfig)
 //! * Concurrent Request Limit: [`LimitStore`](limit::LimitStore)
+//! * Read-Through Caching: [`CachingStore`](cache::CachingStore)
+#![cfg_attr(
+    feature = "retry",
+    doc = "* Retries and Hedged Requests: [`RetryStore`](retry::RetryStore)"
+)]
+//! * Metrics and Tracing: [`InstrumentedStore`](metrics::InstrumentedStore)
 //!
 //! # Configuration System
 //!
@@ -506,6 +512,8 @@ pub mod aws;
 pub mod azure;
 pub mod buffered;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod cache;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod chunked;
 pub mod delimited;
 #[cfg(feature = "gcp")]
@@ -516,8 +524,11 @@ pub mod limit;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod local;
 pub mod memory;
+pub mod metrics;
 pub mod path;
 pub mod prefix;
+#[cfg(feature = "retry")]
+pub mod retry;
 #[cfg(feature = "cloud")]
 pub mod signer;
 pub mod throttle;
@@ -527,10 +538,17 @@ mod client;
 
 #[cfg(feature = "cloud")]
 pub use client::{
-    backoff::BackoffConfig, retry::RetryConfig, Certificate, ClientConfigKey, ClientOptions,
-    CredentialProvider, StaticCredentialProvider,
+    Certificate, ClientConfigKey, ClientOptions, CredentialProvider, StaticCredentialProvider,
 };
 
+#[cfg(feature = "retry")]
+mod backoff;
+
+#[cfg(feature = "retry")]
+pub use backoff::BackoffConfig;
+#[cfg(feature = "retry")]
+pub use retry::RetryConfig;
+
 #[cfg(feature = "cloud")]
 mod config;
 
@@ -659,6 +677,19 @@ pub trait ObjectStore: std::fmt::Display + Send + Sync + Debug + 'static {
     /// Delete the object at the specified location.
     async fn delete(&self, location: &Path) -> Result<()>;
 
+    /// Delete the object at the specified location with the given options
+    ///
+    /// If [`DeleteOptions::version`] is provided, that version of the object is
+    /// permanently removed, as opposed to [`ObjectStore::delete`] which on a versioned
+    /// store will typically retain the existing versions. Stores that do not support
+    /// versioning return [`Error::NotImplemented`] for versioned deletes
+    async fn delete_opts(&self, location: &Path, opts: DeleteOptions) -> Result<()> {
+        match opts.version {
+            Some(_) => Err(Error::NotImplemented),
+            None => self.delete(location).await,
+        }
+    }
+
     /// Delete all the objects at the specified locations
     ///
     /// When supported, this method will use bulk operations that delete more
@@ -747,11 +778,39 @@ pub trait ObjectStore: std::fmt::Display + Send + Sync + Debug + 'static {
     /// `foo/bar_baz/x`. List is not recursive, i.e. `foo/bar/more/x` will not be included.
     async fn list_with_delimiter(&self, prefix: Option<&Path>) -> Result<ListResult>;
 
+    /// List all versions of the objects with the given prefix, including delete markers
+    ///
+    /// Each version is returned as a separate [`ObjectVersion`] with [`ObjectMeta::version`]
+    /// set, which can then be provided to [`GetOptions::version`], [`DeleteOptions::version`]
+    /// or [`CopyOptions::version`] to access that specific version of the object.
+    ///
+    /// Prefixes are evaluated on a path segment basis, as for [`ObjectStore::list`].
+    /// Stores that do not support versioning return [`Error::NotImplemented`]
+    ///
+    /// Note: the order of returned [`ObjectVersion`] is not guaranteed
+    fn list_versions(&self, _prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectVersion>> {
+        futures::stream::once(futures::future::ready(Err(Error::NotImplemented))).boxed()
+    }
+
     /// Copy an object from one path to another in the same object store.
     ///
     /// If there exists an object at the destination, it will be overwritten.
     async fn copy(&self, from: &Path, to: &Path) -> Result<()>;
 
+    /// Copy an object from one path to another with the given options
+    ///
+    /// If [`CopyOptions::version`] is provided, that version of the source object is
+    /// copied, which can be used to restore a previous version of an object. Stores
+    /// that do not support versioning return [`Error::NotImplemented`] for versioned copies
+    ///
+    /// If there exists an object at the destination, it will be overwritten.
+    async fn copy_opts(&self, from: &Path, to: &Path, opts: CopyOptions) -> Result<()> {
+        match opts.version {
+            Some(_) => Err(Error::NotImplemented),
+            None => self.copy(from, to).await,
+        }
+    }
+
     /// Move an object from one path to another in the same object store.
     ///
     /// By default, this is implemented as a copy and then delete source. It may not
@@ -838,6 +897,10 @@ macro_rules! as_ref_impl {
                 self.as_ref().delete(location).await
             }
 
+            async fn delete_opts(&self, location: &Path, opts: DeleteOptions) -> Result<()> {
+                self.as_ref().delete_opts(location, opts).await
+            }
+
             fn delete_stream<'a>(
                 &'a self,
                 locations: BoxStream<'a, Result<Path>>,
@@ -861,10 +924,18 @@ macro_rules! as_ref_impl {
                 self.as_ref().list_with_delimiter(prefix).await
             }
 
+            fn list_versions(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectVersion>> {
+                self.as_ref().list_versions(prefix)
+            }
+
             async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
                 self.as_ref().copy(from, to).await
             }
 
+            async fn copy_opts(&self, from: &Path, to: &Path, opts: CopyOptions) -> Result<()> {
+                self.as_ref().copy_opts(from, to, opts).await
+            }
+
             async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
                 self.as_ref().rename(from, to).await
             }
@@ -911,6 +982,18 @@ pub struct ObjectMeta {
     pub version: Option<String>,
 }
 
+/// A version of an object returned by [`ObjectStore::list_versions`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectVersion {
+    /// The metadata of this version
+    pub meta: ObjectMeta,
+    /// Whether this version is a delete marker
+    ///
+    /// Delete markers record that the object was deleted at `last_modified` on stores
+    /// that support them, such as S3. A delete marker has no content, and `size` will be `0`
+    pub delete_marker: bool,
+}
+
 /// Options for a get request, such as range
 #[derive(Debug, Default, Clone)]
 pub struct GetOptions {
@@ -1209,6 +1292,24 @@ impl From<Attributes> for PutMultipartOpts {
     }
 }
 
+/// Options for [`ObjectStore::delete_opts`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DeleteOptions {
+    /// Permanently delete a particular object version
+    ///
+    /// See [`ObjectStore::list_versions`]
+    pub version: Option<String>,
+}
+
+/// Options for [`ObjectStore::copy_opts`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CopyOptions {
+    /// Copy a particular version of the source object
+    ///
+    /// See [`ObjectStore::list_versions`]
+    pub version: Option<String>,
+}
+
 /// Result for a put request
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PutResult {
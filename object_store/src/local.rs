@@ -42,6 +42,9 @@ use crate::{
     ObjectStore, PutMode, PutMultipartOpts, PutOptions, PutPayload, PutResult, Result, UploadPart,
 };
 
+#[cfg(target_family = "unix")]
+use crate::UpdateVersion;
+
 /// A specialized `Error` for filesystem object store-related errors
 #[derive(Debug, Snafu)]
 pub(crate) enum Error {
@@ -155,6 +158,20 @@ pub(crate) enum Error {
 
     #[snafu(display("Upload aborted"))]
     Aborted,
+
+    #[snafu(display("Unable to lock file {}: {}", path.display(), source))]
+    UnableToLockFile {
+        source: io::Error,
+        path: PathBuf,
+    },
+
+    #[snafu(display("ETag required for conditional update"))]
+    MissingETag,
+
+    Precondition {
+        path: String,
+        message: String,
+    },
 }
 
 impl From<Error> for super::Error {
@@ -168,6 +185,10 @@ impl From<Error> for super::Error {
                 path,
                 source: source.into(),
             },
+            Error::Precondition { path, message } => Self::Precondition {
+                path,
+                source: message.into(),
+            },
             _ => Self::Generic {
                 store: "LocalFileSystem",
                 source: Box::new(source),
@@ -211,6 +232,20 @@ impl From<Error> for super::Error {
 /// by [`LocalFileSystem`] as they are used to provide atomic writes. Such files will be ignored
 /// for listing operations, and attempting to address such a file will error.
 ///
+/// # Conditional Updates
+///
+/// On Unix platforms [`PutMode::Update`] is supported, comparing the provided ETag against
+/// that derived from the inode, modification time and size of the existing file. Concurrent
+/// updates of the same file, whether from the same or different processes, are serialized
+/// using an advisory [`flock`] on a sidecar file `{filename}#0`, which is retained after the
+/// update and removed when the file is deleted. As with other advisory locks, correctness on
+/// network filesystems such as NFS depends on the filesystem's support for locking.
+///
+/// Only conditional updates take this lock, and so these are not serialized with respect to
+/// concurrent [`PutMode::Overwrite`] writes or deletes of the same file.
+///
+/// [`flock`]: https://man7.org/linux/man-pages/man2/flock.2.html
+///
 /// # Tokio Compatibility
 ///
 /// Tokio discourages performing blocking IO on a tokio worker thread, however,
@@ -366,6 +401,7 @@ impl ObjectStore for LocalFileSystem {
         payload: PutPayload,
         opts: PutOptions,
     ) -> Result<PutResult> {
+        #[cfg(not(target_family = "unix"))]
         if matches!(opts.mode, PutMode::Update(_)) {
             return Err(crate::Error::NotImplemented);
         }
@@ -409,6 +445,12 @@ impl ObjectStore for LocalFileSystem {
                                 _ => Some(Error::UnableToRenameFile { source }),
                             },
                         },
+                        #[cfg(target_family = "unix")]
+                        PutMode::Update(v) => {
+                            std::mem::drop(file);
+                            update_file(&staging_path, &path, v).err()
+                        }
+                        #[cfg(not(target_family = "unix"))]
                         PutMode::Update(_) => unreachable!(),
                     }
                 }
@@ -494,11 +536,17 @@ impl ObjectStore for LocalFileSystem {
         let automactic_cleanup = self.automatic_cleanup;
         maybe_spawn_blocking(move || {
             if let Err(e) = std::fs::remove_file(&path) {
-                Err(match e.kind() {
+                return Err(match e.kind() {
                     ErrorKind::NotFound => Error::NotFound { path, source: e }.into(),
                     _ => Error::UnableToDeleteFile { path, source: e }.into(),
-                })
-            } else if automactic_cleanup {
+                });
+            }
+
+            // Remove the lock file of any prior conditional update, if present
+            #[cfg(target_family = "unix")]
+            let _ = std::fs::remove_file(staged_upload_path(&path, LOCK_SUFFIX));
+
+            if automactic_cleanup {
                 let root = &config.root;
                 let root = root
                     .to_file_path()
@@ -514,11 +562,8 @@ impl ObjectStore for LocalFileSystem {
                         break;
                     }
                 }
-
-                Ok(())
-            } else {
-                Ok(())
             }
+            Ok(())
         })
         .await
     }
@@ -645,7 +690,8 @@ impl ObjectStore for LocalFileSystem {
     async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
         let from = self.path_to_filesystem(from)?;
         let to = self.path_to_filesystem(to)?;
-        let mut id = 0;
+        // Start at 1 to skip over the lock file of PutMode::Update
+        let mut id = 1;
         // In order to make this atomic we:
         //
         // - hard link to a hidden temporary file
@@ -750,6 +796,96 @@ fn new_staged_upload(base: &std::path::Path) -> Result<(File, PathBuf)> {
     }
 }
 
+/// The suffix of the file used to lock an object for [`PutMode::Update`]
+///
+/// Uses the `#\d+` suffix reserved for staged uploads so that it is ignored by listing
+/// operations, [`new_staged_upload`] and `copy` start from `1` so never use this path
+#[cfg(target_family = "unix")]
+const LOCK_SUFFIX: &str = "0";
+
+/// Replaces `path` with `staged` if the ETag of `path` matches `expected`
+///
+/// Concurrent updates, including from other processes, are serialized by an exclusive
+/// advisory lock on a sidecar lock file. This file is not removed after the update,
+/// as doing so would allow a concurrent update to lock a different file, but is
+/// instead removed when the object is deleted
+#[cfg(target_family = "unix")]
+fn update_file(
+    staged: &std::path::Path,
+    path: &std::path::Path,
+    expected: UpdateVersion,
+) -> Result<(), Error> {
+    use std::os::unix::io::AsRawFd;
+
+    let expected = expected.e_tag.context(MissingETagSnafu)?;
+    let not_found = || Error::Precondition {
+        path: path.to_string_lossy().to_string(),
+        message: format!("Object at location {} not found", path.display()),
+    };
+
+    // Avoid creating a lock file for an object that doesn't exist
+    if !path.exists() {
+        return Err(not_found());
+    }
+
+    let lock_path = staged_upload_path(path, LOCK_SUFFIX);
+    let mut options = OpenOptions::new();
+    let lock_file = match options
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&lock_path)
+    {
+        Ok(f) => f,
+        Err(source) => match source.kind() {
+            ErrorKind::NotFound => return Err(not_found()),
+            _ => {
+                return Err(Error::UnableToOpenFile {
+                    source,
+                    path: lock_path,
+                })
+            }
+        },
+    };
+
+    // The lock is released when `lock_file` is closed
+    loop {
+        // SAFETY: `lock_file` is an open file descriptor for the duration of the call
+        if unsafe { libc::flock(lock_file.as_raw_fd(), libc::LOCK_EX) } == 0 {
+            break;
+        }
+        let source = io::Error::last_os_error();
+        if source.kind() != ErrorKind::Interrupted {
+            return Err(Error::UnableToLockFile {
+                source,
+                path: lock_path,
+            });
+        }
+    }
+
+    let existing = match metadata(path) {
+        Ok(m) => get_etag(&m),
+        Err(source) => match source.kind() {
+            ErrorKind::NotFound => return Err(not_found()),
+            _ => {
+                return Err(Error::Metadata {
+                    source: source.into(),
+                    path: path.to_string_lossy().to_string(),
+                })
+            }
+        },
+    };
+
+    if existing != expected {
+        return Err(Error::Precondition {
+            path: path.to_string_lossy().to_string(),
+            message: format!("{existing} does not match {expected}"),
+        });
+    }
+
+    std::fs::rename(staged, path).map_err(|source| Error::UnableToRenameFile { source })
+}
+
 /// Returns the unique upload for the given path and suffix
 fn staged_upload_path(dest: &std::path::Path, suffix: &str) -> PathBuf {
     let mut staging_path = dest.as_os_str().to_owned();
@@ -1080,7 +1216,46 @@ mod tests {
         copy_if_not_exists(&integration).await;
         copy_rename_nonexistent_object(&integration).await;
         stream_get(&integration).await;
-        put_opts(&integration, false).await;
+        put_opts(&integration, true).await;
+    }
+
+    #[tokio::test]
+    #[cfg(target_family = "unix")]
+    async fn test_update_lock_file() {
+        let root = TempDir::new().unwrap();
+        let integration = LocalFileSystem::new_with_prefix(root.path()).unwrap();
+
+        let location = Path::from("nested/file");
+        let v1 = integration.put(&location, "a".into()).await.unwrap();
+        let mode = PutMode::Update(v1.into());
+        integration
+            .put_opts(&location, "b".into(), mode.into())
+            .await
+            .unwrap();
+
+        // The lock file is retained, but not listed
+        assert!(root.path().join("nested/file#0").exists());
+        let listed: Vec<_> = integration.list(None).try_collect().await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].location, location);
+
+        // Copies skip over the lock file
+        let other = Path::from("other");
+        integration.put(&other, "c".into()).await.unwrap();
+        integration.copy(&other, &location).await.unwrap();
+        let b = integration
+            .get(&location)
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        assert_eq!(b.as_ref(), b"c");
+        assert!(!root.path().join("nested/file#1").exists());
+
+        // The lock file is removed with the object
+        integration.delete(&location).await.unwrap();
+        assert!(!root.path().join("nested/file#0").exists());
     }
 
     #[test]
@@ -30,9 +30,9 @@ use snafu::{OptionExt, ResultExt, Snafu};
 use crate::multipart::{MultipartStore, PartId};
 use crate::util::InvalidGetRange;
 use crate::{
-    path::Path, Attributes, GetRange, GetResult, GetResultPayload, ListResult, MultipartId,
-    MultipartUpload, ObjectMeta, ObjectStore, PutMode, PutMultipartOpts, PutOptions, PutResult,
-    Result, UpdateVersion, UploadPart,
+    path::Path, Attributes, CopyOptions, DeleteOptions, GetRange, GetResult, GetResultPayload,
+    ListResult, MultipartId, MultipartUpload, ObjectMeta, ObjectStore, ObjectVersion, PutMode,
+    PutMultipartOpts, PutOptions, PutResult, Result, UpdateVersion, UploadPart,
 };
 use crate::{GetOptions, PutPayload};
 
@@ -42,6 +42,9 @@ enum Error {
     #[snafu(display("No data in memory found. Location: {path}"))]
     NoDataInMemory { path: String },
 
+    #[snafu(display("Version {version} not found. Location: {path}"))]
+    VersionNotFound { path: String, version: String },
+
     #[snafu(display("Invalid range: {source}"))]
     Range { source: InvalidGetRange },
 
@@ -61,10 +64,12 @@ enum Error {
 impl From<Error> for super::Error {
     fn from(source: Error) -> Self {
         match source {
-            Error::NoDataInMemory { ref path } => Self::NotFound {
-                path: path.into(),
-                source: source.into(),
-            },
+            Error::NoDataInMemory { ref path } | Error::VersionNotFound { ref path, .. } => {
+                Self::NotFound {
+                    path: path.into(),
+                    source: source.into(),
+                }
+            }
             Error::AlreadyExists { ref path } => Self::AlreadyExists {
                 path: path.into(),
                 source: source.into(),
@@ -79,6 +84,20 @@ impl From<Error> for super::Error {
 
 /// In-memory storage suitable for testing or for opting out of using a cloud
 /// storage provider.
+///
+/// # Versioning
+///
+/// A store created with [`InMemory::new_versioned`] emulates a bucket with object
+/// versioning enabled, retaining every version of each object, similar to S3:
+///
+/// * Each write creates a new version, identified by its e-tag, and reported as
+///   [`ObjectMeta::version`] and [`PutResult::version`]
+/// * [`ObjectStore::delete`] creates a delete marker, with previous versions still
+///   accessible via [`GetOptions::version`]
+/// * [`DeleteOptions::version`] permanently removes a version, with the prior version
+///   becoming current if it was the latest
+///
+/// As no versions are ever discarded, this is only suitable for testing
 #[derive(Debug, Default)]
 pub struct InMemory {
     storage: SharedStorage,
@@ -93,6 +112,16 @@ struct Entry {
 }
 
 impl Entry {
+    fn meta(&self, location: &Path, versioned: bool) -> ObjectMeta {
+        ObjectMeta {
+            location: location.clone(),
+            last_modified: self.last_modified,
+            size: self.data.len(),
+            e_tag: Some(self.e_tag.to_string()),
+            version: versioned.then(|| self.e_tag.to_string()),
+        }
+    }
+
     fn new(
         data: Bytes,
         last_modified: DateTime<Utc>,
@@ -108,11 +137,56 @@ impl Entry {
     }
 }
 
+/// A version of an object in a versioned [`InMemory`]
+#[derive(Debug, Clone)]
+enum Version {
+    Object(Entry),
+    DeleteMarker {
+        version: usize,
+        last_modified: DateTime<Utc>,
+    },
+}
+
+impl Version {
+    fn version(&self) -> usize {
+        match self {
+            Self::Object(e) => e.e_tag,
+            Self::DeleteMarker { version, .. } => *version,
+        }
+    }
+
+    fn object_version(&self, location: &Path) -> ObjectVersion {
+        match self {
+            Self::Object(e) => ObjectVersion {
+                meta: e.meta(location, true),
+                delete_marker: false,
+            },
+            Self::DeleteMarker {
+                version,
+                last_modified,
+            } => ObjectVersion {
+                meta: ObjectMeta {
+                    location: location.clone(),
+                    last_modified: *last_modified,
+                    size: 0,
+                    e_tag: None,
+                    version: Some(version.to_string()),
+                },
+                delete_marker: true,
+            },
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 struct Storage {
     next_etag: usize,
     map: BTreeMap<Path, Entry>,
     uploads: HashMap<usize, PartStorage>,
+    /// The versions of each object, oldest first, if versioning is enabled
+    ///
+    /// The last version, if an [`Version::Object`], is the one stored in `map`
+    versions: Option<BTreeMap<Path, Vec<Version>>>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -133,6 +207,7 @@ impl Storage {
 
     fn overwrite(&mut self, location: &Path, entry: Entry) {
         self.map.insert(location.clone(), entry);
+        self.push_version(location);
     }
 
     fn create(&mut self, location: &Path, entry: Entry) -> Result<()> {
@@ -144,6 +219,7 @@ impl Storage {
             .into()),
             btree_map::Entry::Vacant(v) => {
                 v.insert(entry);
+                self.push_version(location);
                 Ok(())
             }
         }
@@ -161,6 +237,7 @@ impl Storage {
                 let expected = v.e_tag.context(MissingETagSnafu)?;
                 if existing == expected {
                     *e = entry;
+                    self.push_version(location);
                     Ok(())
                 } else {
                     Err(crate::Error::Precondition {
@@ -172,6 +249,71 @@ impl Storage {
         }
     }
 
+    fn remove(&mut self, location: &Path) {
+        self.map.remove(location);
+        if let Some(versions) = &mut self.versions {
+            let marker = Version::DeleteMarker {
+                version: self.next_etag,
+                last_modified: Utc::now(),
+            };
+            self.next_etag += 1;
+            versions.entry(location.clone()).or_default().push(marker);
+        }
+    }
+
+    /// Records the current entry of `location` as its latest version, if versioning is enabled
+    fn push_version(&mut self, location: &Path) {
+        if let (Some(versions), Some(entry)) = (&mut self.versions, self.map.get(location)) {
+            let version = Version::Object(entry.clone());
+            versions.entry(location.clone()).or_default().push(version);
+        }
+    }
+
+    fn get_version(&self, location: &Path, version: &str) -> Result<Entry> {
+        let found = self
+            .versions
+            .as_ref()
+            .and_then(|v| v.get(location))
+            .and_then(|v| v.iter().find(|v| v.version().to_string() == version));
+
+        match found {
+            Some(Version::Object(e)) => Ok(e.clone()),
+            _ => Err(Error::VersionNotFound {
+                path: location.to_string(),
+                version: version.to_string(),
+            }
+            .into()),
+        }
+    }
+
+    fn remove_version(&mut self, location: &Path, version: &str) -> Result<()> {
+        let versions = self.versions.as_mut().and_then(|v| v.get_mut(location));
+        let idx = versions
+            .as_ref()
+            .and_then(|v| v.iter().position(|v| v.version().to_string() == version));
+
+        let (versions, idx) = match (versions, idx) {
+            (Some(versions), Some(idx)) => (versions, idx),
+            _ => {
+                return Err(Error::VersionNotFound {
+                    path: location.to_string(),
+                    version: version.to_string(),
+                }
+                .into())
+            }
+        };
+
+        versions.remove(idx);
+        if idx == versions.len() {
+            // Removed the latest version, the prior version becomes current
+            match versions.last() {
+                Some(Version::Object(e)) => self.map.insert(location.clone(), e.clone()),
+                _ => self.map.remove(location),
+            };
+        }
+        Ok(())
+    }
+
     fn upload_mut(&mut self, id: &MultipartId) -> Result<&mut PartStorage> {
         let parts = id
             .parse()
@@ -218,7 +360,7 @@ impl ObjectStore for InMemory {
 
         Ok(PutResult {
             e_tag: Some(etag.to_string()),
-            version: None,
+            version: storage.versions.is_some().then(|| etag.to_string()),
         })
     }
 
@@ -236,16 +378,11 @@ impl ObjectStore for InMemory {
     }
 
     async fn get_opts(&self, location: &Path, options: GetOptions) -> Result<GetResult> {
-        let entry = self.entry(location).await?;
-        let e_tag = entry.e_tag.to_string();
+        let entry = self
+            .version_entry(location, options.version.as_deref())
+            .await?;
 
-        let meta = ObjectMeta {
-            location: location.clone(),
-            last_modified: entry.last_modified,
-            size: entry.data.len(),
-            e_tag: Some(e_tag),
-            version: None,
-        };
+        let meta = entry.meta(location, self.is_versioned());
         options.check_preconditions(&meta)?;
 
         let (range, data) = match options.range {
@@ -281,26 +418,32 @@ impl ObjectStore for InMemory {
 
     async fn head(&self, location: &Path) -> Result<ObjectMeta> {
         let entry = self.entry(location).await?;
-
-        Ok(ObjectMeta {
-            location: location.clone(),
-            last_modified: entry.last_modified,
-            size: entry.data.len(),
-            e_tag: Some(entry.e_tag.to_string()),
-            version: None,
-        })
+        Ok(entry.meta(location, self.is_versioned()))
     }
 
     async fn delete(&self, location: &Path) -> Result<()> {
-        self.storage.write().map.remove(location);
+        self.storage.write().remove(location);
         Ok(())
     }
 
+    async fn delete_opts(&self, location: &Path, opts: DeleteOptions) -> Result<()> {
+        let mut storage = self.storage.write();
+        match opts.version {
+            Some(_) if storage.versions.is_none() => Err(crate::Error::NotImplemented),
+            Some(version) => storage.remove_version(location, &version),
+            None => {
+                storage.remove(location);
+                Ok(())
+            }
+        }
+    }
+
     fn list(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectMeta>> {
         let root = Path::default();
         let prefix = prefix.unwrap_or(&root);
 
         let storage = self.storage.read();
+        let versioned = storage.versions.is_some();
         let values: Vec<_> = storage
             .map
             .range((prefix)..)
@@ -311,15 +454,7 @@ impl ObjectStore for InMemory {
                     .map(|mut x| x.next().is_some())
                     .unwrap_or(false)
             })
-            .map(|(key, value)| {
-                Ok(ObjectMeta {
-                    location: key.clone(),
-                    last_modified: value.last_modified,
-                    size: value.data.len(),
-                    e_tag: Some(value.e_tag.to_string()),
-                    version: None,
-                })
-            })
+            .map(|(key, value)| Ok(value.meta(key, versioned)))
             .collect();
 
         futures::stream::iter(values).boxed()
@@ -337,7 +472,9 @@ impl ObjectStore for InMemory {
         // Only objects in this base level should be returned in the
         // response. Otherwise, we just collect the common prefixes.
         let mut objects = vec![];
-        for (k, v) in self.storage.read().map.range((prefix)..) {
+        let storage = self.storage.read();
+        let versioned = storage.versions.is_some();
+        for (k, v) in storage.map.range((prefix)..) {
             if !k.as_ref().starts_with(prefix.as_ref()) {
                 break;
             }
@@ -357,14 +494,7 @@ impl ObjectStore for InMemory {
             if parts.next().is_some() {
                 common_prefixes.insert(prefix.child(common_prefix));
             } else {
-                let object = ObjectMeta {
-                    location: k.clone(),
-                    last_modified: v.last_modified,
-                    size: v.data.len(),
-                    e_tag: Some(v.e_tag.to_string()),
-                    version: None,
-                };
-                objects.push(object);
+                objects.push(v.meta(k, versioned));
             }
         }
 
@@ -374,6 +504,41 @@ impl ObjectStore for InMemory {
         })
     }
 
+    /// Lists all versions of the objects with the given prefix, newest first
+    ///
+    /// Returns [`crate::Error::NotImplemented`] unless created with [`InMemory::new_versioned`]
+    fn list_versions(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectVersion>> {
+        let root = Path::default();
+        let prefix = prefix.unwrap_or(&root);
+
+        let storage = self.storage.read();
+        let versions = match &storage.versions {
+            Some(versions) => versions,
+            None => {
+                return futures::stream::once(async { Err(crate::Error::NotImplemented) }).boxed()
+            }
+        };
+
+        let values: Vec<_> = versions
+            .range((prefix)..)
+            .take_while(|(key, _)| key.as_ref().starts_with(prefix.as_ref()))
+            .filter(|(key, _)| {
+                // Don't return for exact prefix match
+                key.prefix_match(prefix)
+                    .map(|mut x| x.next().is_some())
+                    .unwrap_or(false)
+            })
+            .flat_map(|(key, versions)| {
+                versions
+                    .iter()
+                    .rev()
+                    .map(move |v| Ok(v.object_version(key)))
+            })
+            .collect();
+
+        futures::stream::iter(values).boxed()
+    }
+
     async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
         let entry = self.entry(from).await?;
         self.storage
@@ -382,6 +547,18 @@ impl ObjectStore for InMemory {
         Ok(())
     }
 
+    async fn copy_opts(&self, from: &Path, to: &Path, opts: CopyOptions) -> Result<()> {
+        if opts.version.is_some() && !self.is_versioned() {
+            return Err(crate::Error::NotImplemented);
+        }
+
+        let entry = self.version_entry(from, opts.version.as_deref()).await?;
+        self.storage
+            .write()
+            .insert(to, entry.data, entry.attributes);
+        Ok(())
+    }
+
     async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
         let entry = self.entry(from).await?;
         let mut storage = self.storage.write();
@@ -444,7 +621,7 @@ impl MultipartStore for InMemory {
         let etag = storage.insert(path, buf.into(), Default::default());
         Ok(PutResult {
             e_tag: Some(etag.to_string()),
-            version: None,
+            version: storage.versions.is_some().then(|| etag.to_string()),
         })
     }
 
@@ -460,6 +637,19 @@ impl InMemory {
         Self::default()
     }
 
+    /// Create new in-memory storage with object versioning enabled
+    ///
+    /// See [`InMemory#versioning`]
+    pub fn new_versioned() -> Self {
+        let storage = Storage {
+            versions: Some(Default::default()),
+            ..Default::default()
+        };
+        Self {
+            storage: Arc::new(RwLock::new(storage)),
+        }
+    }
+
     /// Creates a fork of the store, with the current content copied into the
     /// new store.
     pub fn fork(&self) -> Self {
@@ -486,6 +676,22 @@ impl InMemory {
 
         Ok(value)
     }
+
+    /// Returns the entry for `version` of `location`, or the current entry if `None`
+    ///
+    /// Stores without versioning ignore `version`
+    async fn version_entry(&self, location: &Path, version: Option<&str>) -> Result<Entry> {
+        match version {
+            Some(version) if self.is_versioned() => {
+                self.storage.read().get_version(location, version)
+            }
+            _ => self.entry(location).await,
+        }
+    }
+
+    fn is_versioned(&self) -> bool {
+        self.storage.read().versions.is_some()
+    }
 }
 
 #[derive(Debug)]
@@ -508,7 +714,8 @@ impl MultipartUpload for InMemoryUpload {
         let mut buf = Vec::with_capacity(cap);
         let parts = self.parts.iter().flatten();
         parts.for_each(|x| buf.extend_from_slice(x));
-        let etag = self.storage.write().insert(
+        let mut storage = self.storage.write();
+        let etag = storage.insert(
             &self.location,
             buf.into(),
             std::mem::take(&mut self.attributes),
@@ -516,7 +723,7 @@ impl MultipartUpload for InMemoryUpload {
 
         Ok(PutResult {
             e_tag: Some(etag.to_string()),
-            version: None,
+            version: storage.versions.is_some().then(|| etag.to_string()),
         })
     }
 
@@ -528,6 +735,7 @@ impl MultipartUpload for InMemoryUpload {
 #[cfg(test)]
 mod tests {
     use crate::integration::*;
+    use futures::TryStreamExt;
 
     use super::*;
 
@@ -547,6 +755,47 @@ mod tests {
         put_get_attributes(&integration).await;
     }
 
+    #[tokio::test]
+    async fn in_memory_versioned_test() {
+        let integration = InMemory::new_versioned();
+
+        put_get_delete_list(&integration).await;
+        get_opts(&integration).await;
+        list_uses_directories_correctly(&integration).await;
+        list_with_delimiter(&integration).await;
+        rename_and_copy(&integration).await;
+        copy_if_not_exists(&integration).await;
+        stream_get(&integration).await;
+        put_opts(&integration, true).await;
+        versioning(&integration).await;
+    }
+
+    #[tokio::test]
+    async fn delete_marker() {
+        let integration = InMemory::new_versioned();
+        let path = Path::from("file");
+        integration.put(&path, "a".into()).await.unwrap();
+        integration.delete(&path).await.unwrap();
+
+        let versions: Vec<_> = integration.list_versions(None).try_collect().await.unwrap();
+        assert_eq!(versions.len(), 2);
+        assert!(versions[0].delete_marker);
+        assert_eq!(versions[0].meta.size, 0);
+        assert!(!versions[1].delete_marker);
+
+        // Removing the delete marker makes the prior version current
+        let opts = DeleteOptions {
+            version: versions[0].meta.version.clone(),
+        };
+        integration.delete_opts(&path, opts).await.unwrap();
+        let b = integration.get(&path).await.unwrap().bytes().await.unwrap();
+        assert_eq!(b.as_ref(), b"a");
+
+        let integration = InMemory::new();
+        let err = integration.list_versions(None).next().await.unwrap();
+        assert!(matches!(err, Err(crate::Error::NotImplemented)), "{err:?}");
+    }
+
     #[tokio::test]
     async fn box_test() {
         let integration: Box<dyn ObjectStore> = Box::new(InMemory::new());
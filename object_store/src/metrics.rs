@@ -0,0 +1,752 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! An object store wrapper that records metrics for each operation
+//!
+//! [`InstrumentedStore`] reports an [`OperationMetrics`] for every operation
+//! to a [`MetricsSink`], such as [`TracingSink`], from which per-operation
+//! counters, byte counts and latency histograms can be derived.
+
+use std::fmt::{Debug, Display, Formatter};
+use std::future::Future;
+use std::ops::Range;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt};
+use tracing::field::Empty;
+use tracing::{info_span, Instrument, Span};
+
+use crate::path::Path;
+use crate::{
+    CopyOptions, DeleteOptions, Error, GetOptions, GetResult, GetResultPayload, ListResult,
+    MultipartUpload, ObjectMeta, ObjectStore, ObjectVersion, PutMultipartOpts, PutOptions,
+    PutPayload, PutResult, Result, UploadPart,
+};
+
+/// The kind of an [`ObjectStore`] operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    /// [`ObjectStore::put_opts`]
+    Put,
+    /// [`ObjectStore::put_multipart_opts`]
+    PutMultipart,
+    /// [`MultipartUpload::put_part`]
+    PutPart,
+    /// [`MultipartUpload::complete`]
+    CompleteMultipart,
+    /// [`MultipartUpload::abort`]
+    AbortMultipart,
+    /// [`ObjectStore::get_opts`], including streaming the response body
+    Get,
+    /// [`ObjectStore::get_range`]
+    GetRange,
+    /// [`ObjectStore::get_ranges`]
+    GetRanges,
+    /// [`ObjectStore::head`], or [`ObjectStore::get_opts`] with [`GetOptions::head`]
+    Head,
+    /// [`ObjectStore::delete`] or [`ObjectStore::delete_opts`]
+    Delete,
+    /// [`ObjectStore::delete_stream`], including consuming the stream
+    DeleteStream,
+    /// [`ObjectStore::list`] or [`ObjectStore::list_with_offset`], including
+    /// consuming the stream
+    List,
+    /// [`ObjectStore::list_with_delimiter`]
+    ListWithDelimiter,
+    /// [`ObjectStore::list_versions`], including consuming the stream
+    ListVersions,
+    /// [`ObjectStore::copy`] or [`ObjectStore::copy_opts`]
+    Copy,
+    /// [`ObjectStore::rename`]
+    Rename,
+    /// [`ObjectStore::copy_if_not_exists`]
+    CopyIfNotExists,
+    /// [`ObjectStore::rename_if_not_exists`]
+    RenameIfNotExists,
+}
+
+impl Operation {
+    /// Returns a string representation of this operation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Put => "put",
+            Self::PutMultipart => "put_multipart",
+            Self::PutPart => "put_part",
+            Self::CompleteMultipart => "complete_multipart",
+            Self::AbortMultipart => "abort_multipart",
+            Self::Get => "get",
+            Self::GetRange => "get_range",
+            Self::GetRanges => "get_ranges",
+            Self::Head => "head",
+            Self::Delete => "delete",
+            Self::DeleteStream => "delete_stream",
+            Self::List => "list",
+            Self::ListWithDelimiter => "list_with_delimiter",
+            Self::ListVersions => "list_versions",
+            Self::Copy => "copy",
+            Self::Rename => "rename",
+            Self::CopyIfNotExists => "copy_if_not_exists",
+            Self::RenameIfNotExists => "rename_if_not_exists",
+        }
+    }
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The outcome of an [`ObjectStore`] operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Status {
+    /// The operation succeeded
+    Success,
+    /// The operation failed with [`Error::NotFound`]
+    NotFound,
+    /// The operation failed with [`Error::AlreadyExists`]
+    AlreadyExists,
+    /// The operation failed with [`Error::Precondition`]
+    Precondition,
+    /// The operation failed with [`Error::NotModified`]
+    NotModified,
+    /// The operation failed with any other error
+    Error,
+    /// The operation was dropped before it completed
+    Cancelled,
+}
+
+impl Status {
+    /// Returns the [`Status`] of an operation that failed with `e`
+    fn from_error(e: &Error) -> Self {
+        match e {
+            Error::NotFound { .. } => Self::NotFound,
+            Error::AlreadyExists { .. } => Self::AlreadyExists,
+            Error::Precondition { .. } => Self::Precondition,
+            Error::NotModified { .. } => Self::NotModified,
+            _ => Self::Error,
+        }
+    }
+
+    /// Returns the [`Status`] of an operation that returned `result`
+    fn from_result<T>(result: &Result<T>) -> Self {
+        match result {
+            Ok(_) => Self::Success,
+            Err(e) => Self::from_error(e),
+        }
+    }
+
+    /// Returns a string representation of this status
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::NotFound => "not_found",
+            Self::AlreadyExists => "already_exists",
+            Self::Precondition => "precondition",
+            Self::NotModified => "not_modified",
+            Self::Error => "error",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The metrics of a completed [`ObjectStore`] operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationMetrics {
+    /// The kind of operation
+    pub operation: Operation,
+    /// The prefix of the location of the operation, see [`InstrumentedStore::with_prefix_depth`]
+    pub prefix: Path,
+    /// The number of bytes of object data transferred
+    pub bytes: usize,
+    /// The outcome of the operation
+    pub status: Status,
+    /// The time from the start of the operation until it completed
+    pub duration: Duration,
+}
+
+/// A destination for the metrics recorded by an [`InstrumentedStore`]
+pub trait MetricsSink: Debug + Send + Sync + 'static {
+    /// Called when an operation on `prefix` starts, returning the [`Span`]
+    /// within which the operation is performed
+    ///
+    /// Defaults to [`Span::none`]
+    fn start(&self, operation: Operation, prefix: &Path) -> Span {
+        let _ = (operation, prefix);
+        Span::none()
+    }
+
+    /// Called when an operation completes, with the [`Span`] returned by [`Self::start`]
+    fn record(&self, span: &Span, metrics: &OperationMetrics);
+}
+
+/// A [`MetricsSink`] that performs each operation within a [`tracing`] span
+///
+/// Each span is named `object_store`, and has fields `operation`, `prefix`,
+/// `bytes`, `status` and `duration_ms`, with the latter three recorded when
+/// the operation completes.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TracingSink;
+
+impl MetricsSink for TracingSink {
+    fn start(&self, operation: Operation, prefix: &Path) -> Span {
+        info_span!(
+            "object_store",
+            operation = operation.as_str(),
+            prefix = %prefix,
+            bytes = Empty,
+            status = Empty,
+            duration_ms = Empty,
+        )
+    }
+
+    fn record(&self, span: &Span, metrics: &OperationMetrics) {
+        span.record("bytes", metrics.bytes as u64);
+        span.record("status", metrics.status.as_str());
+        span.record("duration_ms", metrics.duration.as_secs_f64() * 1000.);
+    }
+}
+
+/// Store wrapper that records [`OperationMetrics`] for every operation of
+/// the wrapped implementation to a [`MetricsSink`]
+///
+/// Streaming operations, such as [`ObjectStore::get_opts`] and [`ObjectStore::list`],
+/// are recorded once the returned stream completes, or with [`Status::Cancelled`]
+/// if it is dropped before completion. The parts of a [`MultipartUpload`] are
+/// recorded individually.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use object_store::memory::InMemory;
+/// # use object_store::metrics::{InstrumentedStore, TracingSink};
+///
+/// // Record each operation as a tracing span, attributed to the first
+/// // two segments of its path
+/// let store = InstrumentedStore::new(InMemory::new(), Arc::new(TracingSink))
+///     .with_prefix_depth(2);
+/// ```
+#[derive(Debug)]
+pub struct InstrumentedStore<T: ObjectStore> {
+    inner: T,
+    sink: Arc<dyn MetricsSink>,
+    prefix_depth: usize,
+}
+
+impl<T: ObjectStore> InstrumentedStore<T> {
+    /// Create a new [`InstrumentedStore`] recording the operations of `inner` to `sink`
+    pub fn new(inner: T, sink: Arc<dyn MetricsSink>) -> Self {
+        Self {
+            inner,
+            sink,
+            prefix_depth: 1,
+        }
+    }
+
+    /// Set the number of leading path segments recorded in [`OperationMetrics::prefix`],
+    /// defaults to 1
+    ///
+    /// The final segment of an object location, the object name, is never recorded
+    ///
+    /// Larger values allow finer attribution, at the cost of greater cardinality
+    pub fn with_prefix_depth(mut self, depth: usize) -> Self {
+        self.prefix_depth = depth;
+        self
+    }
+
+    /// Returns the prefix recorded for an operation on the object at `location`
+    ///
+    /// The final segment is the object name, and is never recorded
+    fn object_prefix(&self, location: &Path) -> Path {
+        let parts: Vec<_> = location.parts().collect();
+        let depth = self.prefix_depth.min(parts.len().saturating_sub(1));
+        parts.into_iter().take(depth).collect()
+    }
+
+    /// Returns the prefix recorded for a listing of `prefix`
+    fn list_prefix(&self, prefix: Option<&Path>) -> Path {
+        match prefix {
+            Some(prefix) => prefix.parts().take(self.prefix_depth).collect(),
+            None => Path::default(),
+        }
+    }
+
+    fn recorder(&self, operation: Operation, prefix: Path) -> Recorder {
+        Recorder::new(&self.sink, operation, prefix)
+    }
+
+    /// Records `operation` on the object at `location`, returning the output of `fut`
+    async fn instrument<F, R>(
+        &self,
+        operation: Operation,
+        location: &Path,
+        bytes: impl FnOnce(&R) -> usize,
+        fut: F,
+    ) -> Result<R>
+    where
+        F: Future<Output = Result<R>>,
+    {
+        let recorder = self.recorder(operation, self.object_prefix(location));
+        self.record(recorder, bytes, fut).await
+    }
+
+    /// Records the output of `fut` to `recorder`
+    async fn record<F, R>(
+        &self,
+        mut recorder: Recorder,
+        bytes: impl FnOnce(&R) -> usize,
+        fut: F,
+    ) -> Result<R>
+    where
+        F: Future<Output = Result<R>>,
+    {
+        let r = fut.instrument(recorder.span.clone()).await;
+        if let Ok(v) = &r {
+            recorder.bytes = bytes(v);
+        }
+        recorder.finish(Status::from_result(&r));
+        r
+    }
+
+    fn instrument_upload(
+        &self,
+        upload: Box<dyn MultipartUpload>,
+        location: &Path,
+    ) -> InstrumentedUpload {
+        InstrumentedUpload {
+            upload,
+            sink: Arc::clone(&self.sink),
+            prefix: self.object_prefix(location),
+        }
+    }
+}
+
+impl<T: ObjectStore> Display for InstrumentedStore<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "InstrumentedStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl<T: ObjectStore> ObjectStore for InstrumentedStore<T> {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> Result<PutResult> {
+        let len = payload.content_length();
+        let fut = self.inner.put_opts(location, payload, opts);
+        self.instrument(Operation::Put, location, |_| len, fut)
+            .await
+    }
+
+    async fn put_multipart(&self, location: &Path) -> Result<Box<dyn MultipartUpload>> {
+        let fut = self.inner.put_multipart(location);
+        let upload = self
+            .instrument(Operation::PutMultipart, location, |_| 0, fut)
+            .await?;
+        Ok(Box::new(self.instrument_upload(upload, location)))
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> Result<Box<dyn MultipartUpload>> {
+        let fut = self.inner.put_multipart_opts(location, opts);
+        let upload = self
+            .instrument(Operation::PutMultipart, location, |_| 0, fut)
+            .await?;
+        Ok(Box::new(self.instrument_upload(upload, location)))
+    }
+
+    async fn get_opts(&self, location: &Path, options: GetOptions) -> Result<GetResult> {
+        if options.head {
+            let fut = self.inner.get_opts(location, options);
+            return self.instrument(Operation::Head, location, |_| 0, fut).await;
+        }
+
+        let mut recorder = self.recorder(Operation::Get, self.object_prefix(location));
+        let span = recorder.span.clone();
+        let r = match self
+            .inner
+            .get_opts(location, options)
+            .instrument(span)
+            .await
+        {
+            Ok(r) => r,
+            Err(e) => {
+                recorder.finish(Status::from_error(&e));
+                return Err(e);
+            }
+        };
+
+        let payload = match r.payload {
+            GetResultPayload::Stream(s) => {
+                GetResultPayload::Stream(InstrumentedStream::new(s, recorder, Bytes::len).boxed())
+            }
+            payload @ GetResultPayload::File(_, _) => {
+                recorder.bytes = r.range.len();
+                recorder.finish(Status::Success);
+                payload
+            }
+        };
+        Ok(GetResult { payload, ..r })
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> Result<Bytes> {
+        let fut = self.inner.get_range(location, range);
+        self.instrument(Operation::GetRange, location, Bytes::len, fut)
+            .await
+    }
+
+    async fn get_ranges(&self, location: &Path, ranges: &[Range<usize>]) -> Result<Vec<Bytes>> {
+        let fut = self.inner.get_ranges(location, ranges);
+        let bytes = |r: &Vec<Bytes>| r.iter().map(Bytes::len).sum();
+        self.instrument(Operation::GetRanges, location, bytes, fut)
+            .await
+    }
+
+    async fn head(&self, location: &Path) -> Result<ObjectMeta> {
+        let fut = self.inner.head(location);
+        self.instrument(Operation::Head, location, |_| 0, fut).await
+    }
+
+    async fn delete(&self, location: &Path) -> Result<()> {
+        let fut = self.inner.delete(location);
+        self.instrument(Operation::Delete, location, |_| 0, fut)
+            .await
+    }
+
+    async fn delete_opts(&self, location: &Path, opts: DeleteOptions) -> Result<()> {
+        let fut = self.inner.delete_opts(location, opts);
+        self.instrument(Operation::Delete, location, |_| 0, fut)
+            .await
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, Result<Path>>,
+    ) -> BoxStream<'a, Result<Path>> {
+        let recorder = self.recorder(Operation::DeleteStream, Path::default());
+        let s = self.inner.delete_stream(locations);
+        InstrumentedStream::new(s, recorder, |_| 0).boxed()
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectMeta>> {
+        let recorder = self.recorder(Operation::List, self.list_prefix(prefix));
+        let s = self.inner.list(prefix);
+        InstrumentedStream::new(s, recorder, |_| 0).boxed()
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'_, Result<ObjectMeta>> {
+        let recorder = self.recorder(Operation::List, self.list_prefix(prefix));
+        let s = self.inner.list_with_offset(prefix, offset);
+        InstrumentedStream::new(s, recorder, |_| 0).boxed()
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> Result<ListResult> {
+        let recorder = self.recorder(Operation::ListWithDelimiter, self.list_prefix(prefix));
+        let fut = self.inner.list_with_delimiter(prefix);
+        self.record(recorder, |_| 0, fut).await
+    }
+
+    fn list_versions(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectVersion>> {
+        let recorder = self.recorder(Operation::ListVersions, self.list_prefix(prefix));
+        let s = self.inner.list_versions(prefix);
+        InstrumentedStream::new(s, recorder, |_| 0).boxed()
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
+        let fut = self.inner.copy(from, to);
+        self.instrument(Operation::Copy, from, |_| 0, fut).await
+    }
+
+    async fn copy_opts(&self, from: &Path, to: &Path, opts: CopyOptions) -> Result<()> {
+        let fut = self.inner.copy_opts(from, to, opts);
+        self.instrument(Operation::Copy, from, |_| 0, fut).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        let fut = self.inner.rename(from, to);
+        self.instrument(Operation::Rename, from, |_| 0, fut).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        let fut = self.inner.copy_if_not_exists(from, to);
+        self.instrument(Operation::CopyIfNotExists, from, |_| 0, fut)
+            .await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        let fut = self.inner.rename_if_not_exists(from, to);
+        self.instrument(Operation::RenameIfNotExists, from, |_| 0, fut)
+            .await
+    }
+}
+
+/// Records the [`OperationMetrics`] of an in-progress operation, recording
+/// [`Status::Cancelled`] if dropped before [`Recorder::finish`] is called
+#[derive(Debug)]
+struct Recorder {
+    sink: Arc<dyn MetricsSink>,
+    span: Span,
+    operation: Operation,
+    prefix: Path,
+    start: Instant,
+    bytes: usize,
+    finished: bool,
+}
+
+impl Recorder {
+    fn new(sink: &Arc<dyn MetricsSink>, operation: Operation, prefix: Path) -> Self {
+        Self {
+            span: sink.start(operation, &prefix),
+            sink: Arc::clone(sink),
+            operation,
+            prefix,
+            start: Instant::now(),
+            bytes: 0,
+            finished: false,
+        }
+    }
+
+    fn finish(&mut self, status: Status) {
+        if std::mem::replace(&mut self.finished, true) {
+            return;
+        }
+        let metrics = OperationMetrics {
+            operation: self.operation,
+            prefix: self.prefix.clone(),
+            bytes: self.bytes,
+            status,
+            duration: self.start.elapsed(),
+        };
+        self.sink.record(&self.span, &metrics);
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        self.finish(Status::Cancelled)
+    }
+}
+
+/// A [`Stream`] that records an operation once the wrapped stream completes
+struct InstrumentedStream<'a, T> {
+    inner: BoxStream<'a, Result<T>>,
+    recorder: Recorder,
+    size: fn(&T) -> usize,
+}
+
+impl<'a, T> InstrumentedStream<'a, T> {
+    fn new(inner: BoxStream<'a, Result<T>>, recorder: Recorder, size: fn(&T) -> usize) -> Self {
+        Self {
+            inner,
+            recorder,
+            size,
+        }
+    }
+}
+
+impl<'a, T> Stream for InstrumentedStream<'a, T> {
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let span = this.recorder.span.clone();
+        let _guard = span.enter();
+        let r = this.inner.poll_next_unpin(cx);
+        match &r {
+            Poll::Ready(Some(Ok(v))) => this.recorder.bytes += (this.size)(v),
+            Poll::Ready(Some(Err(e))) => this.recorder.finish(Status::from_error(e)),
+            Poll::Ready(None) => this.recorder.finish(Status::Success),
+            Poll::Pending => {}
+        }
+        r
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// A [`MultipartUpload`] wrapper that records each operation
+#[derive(Debug)]
+struct InstrumentedUpload {
+    upload: Box<dyn MultipartUpload>,
+    sink: Arc<dyn MetricsSink>,
+    prefix: Path,
+}
+
+#[async_trait]
+impl MultipartUpload for InstrumentedUpload {
+    fn put_part(&mut self, data: PutPayload) -> UploadPart {
+        let mut recorder = Recorder::new(&self.sink, Operation::PutPart, self.prefix.clone());
+        recorder.bytes = data.content_length();
+        let fut = self.upload.put_part(data).instrument(recorder.span.clone());
+        Box::pin(async move {
+            let r = fut.await;
+            recorder.finish(Status::from_result(&r));
+            r
+        })
+    }
+
+    async fn complete(&mut self) -> Result<PutResult> {
+        let mut recorder = Recorder::new(
+            &self.sink,
+            Operation::CompleteMultipart,
+            self.prefix.clone(),
+        );
+        let r = self
+            .upload
+            .complete()
+            .instrument(recorder.span.clone())
+            .await;
+        recorder.finish(Status::from_result(&r));
+        r
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        let mut recorder =
+            Recorder::new(&self.sink, Operation::AbortMultipart, self.prefix.clone());
+        let r = self.upload.abort().instrument(recorder.span.clone()).await;
+        recorder.finish(Status::from_result(&r));
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::*;
+    use crate::memory::InMemory;
+    use futures::TryStreamExt;
+    use parking_lot::Mutex;
+
+    #[derive(Debug, Default)]
+    struct RecordingSink {
+        metrics: Mutex<Vec<OperationMetrics>>,
+    }
+
+    impl RecordingSink {
+        fn take(&self) -> Vec<OperationMetrics> {
+            std::mem::take(&mut *self.metrics.lock())
+        }
+    }
+
+    impl MetricsSink for RecordingSink {
+        fn record(&self, _span: &Span, metrics: &OperationMetrics) {
+            self.metrics.lock().push(metrics.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn instrumented_test() {
+        let integration = InstrumentedStore::new(InMemory::new(), Arc::new(TracingSink));
+
+        put_get_delete_list(&integration).await;
+        get_opts(&integration).await;
+        list_uses_directories_correctly(&integration).await;
+        list_with_delimiter(&integration).await;
+        rename_and_copy(&integration).await;
+        copy_if_not_exists(&integration).await;
+        stream_get(&integration).await;
+    }
+
+    #[tokio::test]
+    async fn instrumented_metrics() {
+        let sink = Arc::new(RecordingSink::default());
+        let store = InstrumentedStore::new(InMemory::new(), Arc::clone(&sink) as _);
+
+        let location = Path::from("table/part/data");
+        store.put(&location, "0123456789".into()).await.unwrap();
+        store.get(&location).await.unwrap().bytes().await.unwrap();
+        store.get_range(&location, 2..5).await.unwrap();
+        store.get_ranges(&location, &[0..2, 4..8]).await.unwrap();
+        store.head(&location).await.unwrap();
+        store.head(&Path::from("missing")).await.unwrap_err();
+
+        let prefix = Path::from("table");
+        let listed: Vec<_> = store.list(Some(&prefix)).try_collect().await.unwrap();
+        assert_eq!(listed.len(), 1);
+
+        // Dropping a stream before it completes is recorded as cancelled
+        drop(store.get(&location).await.unwrap());
+
+        let mut upload = store.put_multipart(&location).await.unwrap();
+        upload.put_part("0123".into()).await.unwrap();
+        upload.put_part("45".into()).await.unwrap();
+        upload.complete().await.unwrap();
+        store.delete(&location).await.unwrap();
+
+        let metrics = sink.take();
+        let actual: Vec<_> = metrics
+            .iter()
+            .map(|m| (m.operation, m.prefix.as_ref(), m.bytes, m.status))
+            .collect();
+
+        let expected = vec![
+            (Operation::Put, "table", 10, Status::Success),
+            (Operation::Get, "table", 10, Status::Success),
+            (Operation::GetRange, "table", 3, Status::Success),
+            (Operation::GetRanges, "table", 6, Status::Success),
+            (Operation::Head, "table", 0, Status::Success),
+            (Operation::Head, "", 0, Status::NotFound),
+            (Operation::List, "table", 0, Status::Success),
+            (Operation::Get, "table", 0, Status::Cancelled),
+            (Operation::PutMultipart, "table", 0, Status::Success),
+            (Operation::PutPart, "table", 4, Status::Success),
+            (Operation::PutPart, "table", 2, Status::Success),
+            (Operation::CompleteMultipart, "table", 0, Status::Success),
+            (Operation::Delete, "table", 0, Status::Success),
+        ];
+        assert_eq!(actual, expected);
+
+        let store = store.with_prefix_depth(2);
+        store
+            .copy(&Path::from("a/b/c"), &location)
+            .await
+            .unwrap_err();
+        let metrics = sink.take();
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].operation, Operation::Copy);
+        assert_eq!(metrics[0].prefix.as_ref(), "a/b");
+        assert_eq!(metrics[0].status, Status::NotFound);
+
+        store.head(&Path::from("a/b")).await.unwrap_err();
+        let metrics = sink.take();
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].prefix.as_ref(), "a");
+    }
+}
@@ -0,0 +1,686 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! An object store wrapper that retries failed requests and hedges slow ones
+
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::future::{select, Either};
+use futures::stream::BoxStream;
+use parking_lot::Mutex;
+use tracing::info;
+
+use crate::backoff::{Backoff, BackoffConfig};
+use crate::path::Path;
+use crate::{
+    CopyOptions, DeleteOptions, Error, GetOptions, GetResult, ListResult, MultipartUpload,
+    ObjectMeta, ObjectStore, ObjectVersion, PutMode, PutMultipartOpts, PutOptions, PutPayload,
+    PutResult, Result,
+};
+
+/// The configuration for how many times and for how long failed requests are retried
+///
+/// Requests will be retried up to some limit, using exponential
+/// backoff with jitter. See [`BackoffConfig`] for more information
+///
+/// This configuration is used by the HTTP-based stores, which retry server errors,
+/// connection errors and timeouts of [safe] requests, and by [`RetryStore`], which
+/// retries the errors identified by [`RetryStore::with_retryable`]
+///
+/// [safe]: https://datatracker.ietf.org/doc/html/rfc7231#section-4.2.1
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    /// The backoff configuration
+    pub backoff: BackoffConfig,
+
+    /// The maximum number of times to retry a request
+    ///
+    /// Set to 0 to disable retries
+    pub max_retries: usize,
+
+    /// The maximum length of time from the initial request
+    /// after which no further retries will be attempted
+    ///
+    /// This not only bounds the length of time before a server
+    /// error will be surfaced to the application, but also bounds
+    /// the length of time a request's credentials must remain valid.
+    ///
+    /// As requests are retried without renewing credentials or
+    /// regenerating request payloads, this number should be kept
+    /// below 5 minutes to avoid errors due to expired credentials
+    /// and/or request payloads
+    pub retry_timeout: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            backoff: Default::default(),
+            max_retries: 10,
+            retry_timeout: Duration::from_secs(3 * 60),
+        }
+    }
+}
+
+/// The configuration for hedged requests, see [`RetryStore::with_hedging`]
+#[derive(Debug, Clone, Copy)]
+pub struct HedgeConfig {
+    /// The percentile of recently observed latencies, between 0 and 1, after
+    /// which a duplicate request is issued
+    pub percentile: f64,
+
+    /// The number of most recently observed latencies to compute the percentile from
+    pub window: usize,
+
+    /// The minimum number of observed latencies before requests are hedged
+    pub min_samples: usize,
+}
+
+impl Default for HedgeConfig {
+    fn default() -> Self {
+        Self {
+            percentile: 0.95,
+            window: 1000,
+            min_samples: 20,
+        }
+    }
+}
+
+/// Store wrapper that retries failed idempotent operations of the wrapped
+/// implementation, and optionally hedges slow reads
+///
+/// Failed requests are retried according to a [`RetryConfig`], using exponential
+/// backoff with jitter. Only operations that can safely be repeated are retried:
+///
+/// * [`ObjectStore::get_opts`], [`ObjectStore::get_range`], [`ObjectStore::get_ranges`]
+///   and [`ObjectStore::head`], with errors whilst streaming the body not retried
+/// * [`ObjectStore::put_opts`] with [`PutMode::Overwrite`]
+/// * [`ObjectStore::delete`], [`ObjectStore::delete_opts`], [`ObjectStore::copy`],
+///   [`ObjectStore::copy_opts`] and [`ObjectStore::list_with_delimiter`]
+///
+/// By default only I/O errors and timeouts are considered transient, see
+/// [`RetryStore::with_retryable`].
+///
+/// The HTTP-based stores already retry requests internally, and so this is
+/// primarily intended for [`LocalFileSystem`], [`InMemory`] and custom
+/// [`ObjectStore`] implementations.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use object_store::memory::InMemory;
+/// # use object_store::retry::{HedgeConfig, RetryStore};
+/// # use object_store::RetryConfig;
+///
+/// // Retry requests for up to 30 seconds, and hedge ranged reads
+/// // slower than 99% of recent requests
+/// let config = RetryConfig {
+///     retry_timeout: Duration::from_secs(30),
+///     ..Default::default()
+/// };
+/// let hedge = HedgeConfig {
+///     percentile: 0.99,
+///     ..Default::default()
+/// };
+/// let store = RetryStore::new(InMemory::new())
+///     .with_config(config)
+///     .with_hedging(hedge);
+/// ```
+///
+/// [`LocalFileSystem`]: crate::local::LocalFileSystem
+/// [`InMemory`]: crate::memory::InMemory
+#[derive(Debug)]
+pub struct RetryStore<T: ObjectStore> {
+    inner: T,
+    config: RetryConfig,
+    retryable: fn(&Error) -> bool,
+    hedge: Option<Hedge>,
+}
+
+impl<T: ObjectStore> RetryStore<T> {
+    /// Create a new [`RetryStore`] wrapping `inner` with the default [`RetryConfig`]
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            config: Default::default(),
+            retryable: is_transient,
+            hedge: None,
+        }
+    }
+
+    /// Set the [`RetryConfig`]
+    pub fn with_config(mut self, config: RetryConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Set the function used to determine if an [`Error`] is transient and the
+    /// operation should be retried
+    ///
+    /// Defaults to [`is_transient`], [`is_generic`] additionally retries any error
+    /// not reported as a more specific [`Error`] variant
+    pub fn with_retryable(mut self, retryable: fn(&Error) -> bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// Hedge [`ObjectStore::get_range`] and [`ObjectStore::get_ranges`] requests
+    ///
+    /// Once a request has been outstanding for longer than the configured percentile
+    /// of recently observed latencies, a duplicate request is issued. Whichever
+    /// request completes successfully first is returned, and the other cancelled.
+    pub fn with_hedging(mut self, config: HedgeConfig) -> Self {
+        self.hedge = Some(Hedge {
+            latencies: Mutex::new(VecDeque::with_capacity(config.window)),
+            config,
+        });
+        self
+    }
+
+    /// Invokes `f` until it succeeds, returns a non-transient error, or the
+    /// configured retry limits are reached
+    async fn retry<F, Fut, R>(&self, mut f: F) -> Result<R>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<R>>,
+    {
+        let mut backoff = Backoff::new(&self.config.backoff);
+        let start = Instant::now();
+        let mut retries = 0;
+        loop {
+            match f().await {
+                Ok(r) => return Ok(r),
+                Err(e) => {
+                    if retries == self.config.max_retries
+                        || start.elapsed() > self.config.retry_timeout
+                        || !(self.retryable)(&e)
+                    {
+                        return Err(e);
+                    }
+                    let sleep = backoff.next();
+                    retries += 1;
+                    info!(
+                        "Encountered transient error, backing off for {} seconds, retry {} of {}: {}",
+                        sleep.as_secs_f32(),
+                        retries,
+                        self.config.max_retries,
+                        e,
+                    );
+                    tokio::time::sleep(sleep).await;
+                }
+            }
+        }
+    }
+
+    /// Invokes `f`, invoking it again if the first request is slower than the
+    /// configured percentile of recent requests
+    async fn hedged<F, Fut, R>(&self, f: F) -> Result<R>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<R>>,
+    {
+        let hedge = match &self.hedge {
+            Some(hedge) => hedge,
+            None => return f().await,
+        };
+
+        let start = Instant::now();
+        let primary = Box::pin(f());
+        let r = match hedge.delay() {
+            None => primary.await,
+            Some(delay) => match select(primary, Box::pin(tokio::time::sleep(delay))).await {
+                Either::Left((r, _)) => r,
+                Either::Right((_, primary)) => {
+                    // Dropping the request that loses cancels it
+                    match select(primary, Box::pin(f())).await {
+                        Either::Left((Ok(r), _)) | Either::Right((Ok(r), _)) => Ok(r),
+                        Either::Left((Err(_), hedged)) => hedged.await,
+                        Either::Right((Err(_), primary)) => primary.await,
+                    }
+                }
+            },
+        };
+
+        if r.is_ok() {
+            hedge.record(start.elapsed());
+        }
+        r
+    }
+}
+
+impl<T: ObjectStore> Display for RetryStore<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RetryStore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl<T: ObjectStore> ObjectStore for RetryStore<T> {
+    async fn put_opts(
+        &self,
+        location: &Path,
+        payload: PutPayload,
+        opts: PutOptions,
+    ) -> Result<PutResult> {
+        match opts.mode {
+            PutMode::Overwrite => {
+                self.retry(|| self.inner.put_opts(location, payload.clone(), opts.clone()))
+                    .await
+            }
+            _ => self.inner.put_opts(location, payload, opts).await,
+        }
+    }
+
+    async fn put_multipart(&self, location: &Path) -> Result<Box<dyn MultipartUpload>> {
+        self.inner.put_multipart(location).await
+    }
+
+    async fn put_multipart_opts(
+        &self,
+        location: &Path,
+        opts: PutMultipartOpts,
+    ) -> Result<Box<dyn MultipartUpload>> {
+        self.inner.put_multipart_opts(location, opts).await
+    }
+
+    async fn get_opts(&self, location: &Path, options: GetOptions) -> Result<GetResult> {
+        self.retry(|| self.inner.get_opts(location, options.clone()))
+            .await
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> Result<Bytes> {
+        let range = &range;
+        self.retry(|| self.hedged(move || self.inner.get_range(location, range.clone())))
+            .await
+    }
+
+    async fn get_ranges(&self, location: &Path, ranges: &[Range<usize>]) -> Result<Vec<Bytes>> {
+        self.retry(|| self.hedged(move || self.inner.get_ranges(location, ranges)))
+            .await
+    }
+
+    async fn head(&self, location: &Path) -> Result<ObjectMeta> {
+        self.retry(|| self.inner.head(location)).await
+    }
+
+    async fn delete(&self, location: &Path) -> Result<()> {
+        self.retry(|| self.inner.delete(location)).await
+    }
+
+    async fn delete_opts(&self, location: &Path, opts: DeleteOptions) -> Result<()> {
+        self.retry(|| self.inner.delete_opts(location, opts.clone()))
+            .await
+    }
+
+    fn delete_stream<'a>(
+        &'a self,
+        locations: BoxStream<'a, Result<Path>>,
+    ) -> BoxStream<'a, Result<Path>> {
+        self.inner.delete_stream(locations)
+    }
+
+    fn list(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectMeta>> {
+        self.inner.list(prefix)
+    }
+
+    fn list_with_offset(
+        &self,
+        prefix: Option<&Path>,
+        offset: &Path,
+    ) -> BoxStream<'_, Result<ObjectMeta>> {
+        self.inner.list_with_offset(prefix, offset)
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> Result<ListResult> {
+        self.retry(|| self.inner.list_with_delimiter(prefix)).await
+    }
+
+    fn list_versions(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectVersion>> {
+        self.inner.list_versions(prefix)
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
+        self.retry(|| self.inner.copy(from, to)).await
+    }
+
+    async fn copy_opts(&self, from: &Path, to: &Path, opts: CopyOptions) -> Result<()> {
+        self.retry(|| self.inner.copy_opts(from, to, opts.clone()))
+            .await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        self.inner.rename(from, to).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        self.inner.copy_if_not_exists(from, to).await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        self.inner.rename_if_not_exists(from, to).await
+    }
+}
+
+/// Returns true if `e` is caused by an I/O error or timeout that may be transient
+///
+/// This is the default for [`RetryStore::with_retryable`]
+pub fn is_transient(e: &Error) -> bool {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = match e {
+        Error::Generic { source, .. } => Some(source.as_ref()),
+        _ => return false,
+    };
+    while let Some(e) = source {
+        if let Some(e) = e.downcast_ref::<std::io::Error>() {
+            if is_transient_io(e.kind()) {
+                return true;
+            }
+        }
+        if e.is::<tokio::time::error::Elapsed>() {
+            return true;
+        }
+        source = e.source();
+    }
+    false
+}
+
+/// Returns true for the kinds of [`std::io::Error`] that may succeed on retry
+fn is_transient_io(kind: std::io::ErrorKind) -> bool {
+    use std::io::ErrorKind;
+    matches!(
+        kind,
+        ErrorKind::TimedOut
+            | ErrorKind::Interrupted
+            | ErrorKind::WouldBlock
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::ConnectionRefused
+            | ErrorKind::NotConnected
+            | ErrorKind::BrokenPipe
+            | ErrorKind::UnexpectedEof
+    )
+}
+
+/// Returns true if `e` is an [`Error::Generic`] or [`Error::JoinError`]
+///
+/// This retries any error not reported as a more specific [`Error`] variant, such as
+/// those of custom [`ObjectStore`] implementations, and can be opted into with
+/// [`RetryStore::with_retryable`]
+pub fn is_generic(e: &Error) -> bool {
+    matches!(e, Error::Generic { .. } | Error::JoinError { .. })
+}
+
+/// The state of [`RetryStore::with_hedging`]
+#[derive(Debug)]
+struct Hedge {
+    config: HedgeConfig,
+    latencies: Mutex<VecDeque<Duration>>,
+}
+
+impl Hedge {
+    /// Returns the delay after which to issue a duplicate request, if any
+    fn delay(&self) -> Option<Duration> {
+        let mut latencies: Vec<_> = {
+            let latencies = self.latencies.lock();
+            if latencies.is_empty() || latencies.len() < self.config.min_samples {
+                return None;
+            }
+            latencies.iter().copied().collect()
+        };
+        latencies.sort_unstable();
+        let percentile = self.config.percentile.clamp(0., 1.);
+        let idx = ((latencies.len() - 1) as f64 * percentile).round() as usize;
+        Some(latencies[idx])
+    }
+
+    /// Records the latency of a successful request
+    fn record(&self, latency: Duration) {
+        let mut latencies = self.latencies.lock();
+        if latencies.len() >= self.config.window.max(1) {
+            latencies.pop_front();
+        }
+        latencies.push_back(latency);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::*;
+    use crate::memory::InMemory;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// An [`ObjectStore`] that fails or delays its first requests to get objects
+    #[derive(Debug, Default)]
+    struct MockStore {
+        inner: InMemory,
+        calls: AtomicUsize,
+        failures: usize,
+        delayed: usize,
+    }
+
+    impl Display for MockStore {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(f, "MockStore")
+        }
+    }
+
+    #[async_trait]
+    impl ObjectStore for MockStore {
+        async fn put_opts(
+            &self,
+            location: &Path,
+            payload: PutPayload,
+            opts: PutOptions,
+        ) -> Result<PutResult> {
+            self.inner.put_opts(location, payload, opts).await
+        }
+
+        async fn put_multipart_opts(
+            &self,
+            location: &Path,
+            opts: PutMultipartOpts,
+        ) -> Result<Box<dyn MultipartUpload>> {
+            self.inner.put_multipart_opts(location, opts).await
+        }
+
+        async fn get_opts(&self, location: &Path, options: GetOptions) -> Result<GetResult> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.failures {
+                return Err(Error::Generic {
+                    store: "MockStore",
+                    source: Box::new(std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        "transient failure",
+                    )),
+                });
+            }
+            if call < self.failures + self.delayed {
+                tokio::time::sleep(Duration::from_secs(30)).await;
+            }
+            self.inner.get_opts(location, options).await
+        }
+
+        async fn delete(&self, location: &Path) -> Result<()> {
+            self.inner.delete(location).await
+        }
+
+        fn list(&self, prefix: Option<&Path>) -> BoxStream<'_, Result<ObjectMeta>> {
+            self.inner.list(prefix)
+        }
+
+        async fn list_with_delimiter(&self, prefix: Option<&Path>) -> Result<ListResult> {
+            self.inner.list_with_delimiter(prefix).await
+        }
+
+        async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
+            self.inner.copy(from, to).await
+        }
+
+        async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+            self.inner.copy_if_not_exists(from, to).await
+        }
+    }
+
+    fn test_config(max_retries: usize) -> RetryConfig {
+        RetryConfig {
+            backoff: BackoffConfig {
+                init_backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(10),
+                base: 2.,
+            },
+            max_retries,
+            retry_timeout: Duration::from_secs(60),
+        }
+    }
+
+    #[tokio::test]
+    async fn retry_test() {
+        let integration = RetryStore::new(InMemory::new()).with_hedging(Default::default());
+
+        put_get_delete_list(&integration).await;
+        get_opts(&integration).await;
+        list_uses_directories_correctly(&integration).await;
+        list_with_delimiter(&integration).await;
+        rename_and_copy(&integration).await;
+        copy_if_not_exists(&integration).await;
+        stream_get(&integration).await;
+    }
+
+    #[tokio::test]
+    async fn retry_transient() {
+        let location = Path::from("data");
+        let mock = MockStore {
+            failures: 3,
+            ..Default::default()
+        };
+        mock.put(&location, "0123456789".into()).await.unwrap();
+
+        let store = RetryStore::new(mock).with_config(test_config(3));
+        let r = store.get_range(&location, 2..4).await.unwrap();
+        assert_eq!(r, "23");
+        assert_eq!(store.inner.calls.load(Ordering::SeqCst), 4);
+
+        // Non-transient errors are not retried
+        let err = store.head(&Path::from("missing")).await.unwrap_err();
+        assert!(matches!(err, Error::NotFound { .. }), "{err}");
+        assert_eq!(store.inner.calls.load(Ordering::SeqCst), 5);
+
+        // Errors are returned once the retry limit is reached
+        let mock = MockStore {
+            failures: 3,
+            ..Default::default()
+        };
+        mock.put(&location, "0123456789".into()).await.unwrap();
+        let store = RetryStore::new(mock).with_config(test_config(2));
+        let err = store.get_range(&location, 2..4).await.unwrap_err();
+        assert!(matches!(err, Error::Generic { .. }), "{err}");
+        assert_eq!(store.inner.calls.load(Ordering::SeqCst), 3);
+
+        // Transient errors can be customised
+        let mock = MockStore {
+            failures: 1,
+            ..Default::default()
+        };
+        mock.put(&location, "0123456789".into()).await.unwrap();
+        let store = RetryStore::new(mock)
+            .with_config(test_config(2))
+            .with_retryable(|_| false);
+        store.get_range(&location, 2..4).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn retry_is_transient() {
+        let generic = |source: Box<dyn std::error::Error + Send + Sync>| Error::Generic {
+            store: "MockStore",
+            source,
+        };
+        let io = |kind| std::io::Error::new(kind, "failure");
+
+        let timeout = generic(Box::new(io(std::io::ErrorKind::TimedOut)));
+        assert!(is_transient(&timeout));
+        assert!(is_generic(&timeout));
+
+        // I/O errors are found when wrapped by other errors
+        let wrapped = generic(Box::new(crate::local::Error::UnableToReadBytes {
+            source: io(std::io::ErrorKind::ConnectionReset),
+            path: "data".into(),
+        }));
+        assert!(is_transient(&wrapped));
+
+        let elapsed = tokio::time::timeout(Duration::from_millis(1), std::future::pending::<()>())
+            .await
+            .unwrap_err();
+        assert!(is_transient(&generic(Box::new(elapsed))));
+
+        let permission = generic(Box::new(io(std::io::ErrorKind::PermissionDenied)));
+        assert!(!is_transient(&permission));
+        assert!(is_generic(&permission));
+
+        let other = generic("failure".into());
+        assert!(!is_transient(&other));
+        assert!(is_generic(&other));
+
+        let not_found = Error::NotFound {
+            path: "data".into(),
+            source: "failure".into(),
+        };
+        assert!(!is_transient(&not_found));
+        assert!(!is_generic(&not_found));
+    }
+
+    #[tokio::test]
+    async fn retry_hedged() {
+        let location = Path::from("data");
+        let mock = MockStore::default();
+        mock.put(&location, "0123456789".into()).await.unwrap();
+
+        let hedge = HedgeConfig {
+            percentile: 0.5,
+            window: 10,
+            min_samples: 3,
+        };
+        let store = RetryStore::new(mock).with_hedging(hedge);
+
+        // Requests are not hedged until sufficient latencies are observed
+        for _ in 0..3 {
+            assert!(store.hedge.as_ref().unwrap().delay().is_none());
+            store.get_range(&location, 0..4).await.unwrap();
+        }
+        assert!(store.hedge.as_ref().unwrap().delay().is_some());
+
+        // Delay the next request, which should be hedged
+        store.inner.calls.store(0, Ordering::SeqCst);
+        let store = RetryStore {
+            inner: MockStore {
+                delayed: 1,
+                ..store.inner
+            },
+            ..store
+        };
+
+        let start = Instant::now();
+        let ranges = store.get_ranges(&location, &[0..2, 6..8]).await.unwrap();
+        assert_eq!(ranges, vec!["01", "67"]);
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert_eq!(store.inner.calls.load(Ordering::SeqCst), 2);
+    }
+}
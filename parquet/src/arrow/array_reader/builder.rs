@@ -24,9 +24,10 @@ use crate::arrow::array_reader::empty_array::make_empty_array_reader;
 use crate::arrow::array_reader::fixed_len_byte_array::make_fixed_len_byte_array_reader;
 use crate::arrow::array_reader::{
     make_byte_array_dictionary_reader, make_byte_array_reader, ArrayReader,
-    FixedSizeListArrayReader, ListArrayReader, MapArrayReader, NullArrayReader,
-    PrimitiveArrayReader, RowGroups, StructArrayReader,
+    FixedSizeListArrayReader, ListArrayReader, ListViewArrayReader, MapArrayReader,
+    NullArrayReader, PrimitiveArrayReader, RowGroups, StructArrayReader, UnionArrayReader,
 };
+use crate::arrow::arrow_reader::DecodeExecutor;
 use crate::arrow::schema::{ParquetField, ParquetFieldType};
 use crate::arrow::ProjectionMask;
 use crate::basic::Type as PhysicalType;
@@ -48,6 +49,30 @@ pub fn build_array_reader(
     Ok(reader)
 }
 
+/// Create array reader as [`build_array_reader`], decoding the top-level columns
+/// concurrently using `executor` if provided
+pub fn build_parallel_array_reader(
+    field: Option<&ParquetField>,
+    mask: &ProjectionMask,
+    row_groups: &dyn RowGroups,
+    executor: Option<&Arc<dyn DecodeExecutor>>,
+) -> Result<Box<dyn ArrayReader>> {
+    let executor = match executor {
+        Some(executor) => executor,
+        None => return build_array_reader(field, mask, row_groups),
+    };
+
+    // The root field is always a struct
+    let reader = field
+        .and_then(|field| make_struct_reader(field, mask, row_groups).transpose())
+        .transpose()?;
+
+    Ok(match reader {
+        Some(reader) => Box::new(reader.with_executor(executor.clone())),
+        None => make_empty_array_reader(row_groups.num_rows()),
+    })
+}
+
 fn build_reader(
     field: &ParquetField,
     mask: &ProjectionMask,
@@ -58,10 +83,15 @@ fn build_reader(
         ParquetFieldType::Group { .. } => match &field.arrow_type {
             DataType::Map(_, _) => build_map_reader(field, mask, row_groups),
             DataType::Struct(_) => build_struct_reader(field, mask, row_groups),
-            DataType::List(_) => build_list_reader(field, mask, false, row_groups),
-            DataType::LargeList(_) => build_list_reader(field, mask, true, row_groups),
+            DataType::List(_) | DataType::ListView(_) => {
+                build_list_reader(field, mask, false, row_groups)
+            }
+            DataType::LargeList(_) | DataType::LargeListView(_) => {
+                build_list_reader(field, mask, true, row_groups)
+            }
             DataType::FixedSizeList(_, _) => build_fixed_size_list_reader(field, mask, row_groups),
-            d => unimplemented!("reading group type {} not implemented", d),
+            DataType::Union(_, _) => build_union_reader(field, mask, row_groups),
+            d => Err(nyi_err!("reading group type {} not implemented", d)),
         },
     }
 }
@@ -131,31 +161,51 @@ fn build_list_reader(
         Some(item_reader) => {
             // Need to retrieve underlying data type to handle projection
             let item_type = item_reader.get_data_type().clone();
-            let data_type = match &field.arrow_type {
-                DataType::List(f) => {
-                    DataType::List(Arc::new(f.as_ref().clone().with_data_type(item_type)))
-                }
-                DataType::LargeList(f) => {
-                    DataType::LargeList(Arc::new(f.as_ref().clone().with_data_type(item_type)))
-                }
+            let (item_field, is_view) = match &field.arrow_type {
+                DataType::List(f) | DataType::LargeList(f) => (f, false),
+                DataType::ListView(f) | DataType::LargeListView(f) => (f, true),
                 _ => unreachable!(),
             };
+            let item_field = Arc::new(item_field.as_ref().clone().with_data_type(item_type));
 
-            let reader = match is_large {
-                false => Box::new(ListArrayReader::<i32>::new(
+            // List views are stored as lists, and converted after decoding
+            let reader = match (is_large, is_view) {
+                (false, false) => Box::new(ListArrayReader::<i32>::new(
                     item_reader,
-                    data_type,
+                    DataType::List(item_field),
                     field.def_level,
                     field.rep_level,
                     field.nullable,
                 )) as _,
-                true => Box::new(ListArrayReader::<i64>::new(
+                (true, false) => Box::new(ListArrayReader::<i64>::new(
                     item_reader,
-                    data_type,
+                    DataType::LargeList(item_field),
                     field.def_level,
                     field.rep_level,
                     field.nullable,
                 )) as _,
+                (false, true) => {
+                    let reader = ListArrayReader::<i32>::new(
+                        item_reader,
+                        DataType::List(item_field.clone()),
+                        field.def_level,
+                        field.rep_level,
+                        field.nullable,
+                    );
+                    let data_type = DataType::ListView(item_field);
+                    Box::new(ListViewArrayReader::new(reader, data_type)) as _
+                }
+                (true, true) => {
+                    let reader = ListArrayReader::<i64>::new(
+                        item_reader,
+                        DataType::LargeList(item_field.clone()),
+                        field.def_level,
+                        field.rep_level,
+                        field.nullable,
+                    );
+                    let data_type = DataType::LargeListView(item_field);
+                    Box::new(ListViewArrayReader::new(reader, data_type)) as _
+                }
             };
             Some(reader)
         }
@@ -192,7 +242,7 @@ fn build_fixed_size_list_reader(
                         field.nullable,
                     )) as _
                 }
-                _ => unimplemented!(),
+                d => return Err(general_err!("expected fixed size list, got {}", d)),
             };
             Some(reader)
         }
@@ -301,6 +351,15 @@ fn build_struct_reader(
     mask: &ProjectionMask,
     row_groups: &dyn RowGroups,
 ) -> Result<Option<Box<dyn ArrayReader>>> {
+    let reader = make_struct_reader(field, mask, row_groups)?;
+    Ok(reader.map(|reader| Box::new(reader) as Box<dyn ArrayReader>))
+}
+
+fn make_struct_reader(
+    field: &ParquetField,
+    mask: &ProjectionMask,
+    row_groups: &dyn RowGroups,
+) -> Result<Option<StructArrayReader>> {
     let arrow_fields = match &field.arrow_type {
         DataType::Struct(children) => children,
         _ => unreachable!(),
@@ -324,12 +383,67 @@ fn build_struct_reader(
         return Ok(None);
     }
 
-    Ok(Some(Box::new(StructArrayReader::new(
+    Ok(Some(StructArrayReader::new(
         DataType::Struct(builder.finish().fields),
         readers,
         field.def_level,
         field.rep_level,
         field.nullable,
+    )))
+}
+
+/// Build array reader for union type.
+fn build_union_reader(
+    field: &ParquetField,
+    mask: &ProjectionMask,
+    row_groups: &dyn RowGroups,
+) -> Result<Option<Box<dyn ArrayReader>>> {
+    let (union_fields, mode) = match &field.arrow_type {
+        DataType::Union(fields, mode) => (fields, *mode),
+        _ => unreachable!(),
+    };
+    let children = field.children().unwrap();
+    assert_eq!(union_fields.len() + 1, children.len());
+
+    let mut readers = Vec::with_capacity(children.len());
+    for child in children {
+        match build_reader(child, mask, row_groups)? {
+            Some(reader) => readers.push(reader),
+            None => {
+                return Err(general_err!(
+                    "partial projection of UnionArray is not supported"
+                ))
+            }
+        }
+    }
+
+    let mut readers = readers.into_iter();
+    let type_id_reader = readers.next().unwrap();
+    let variant_readers: Vec<_> = readers.collect();
+
+    // Need to retrieve underlying data type to handle projection
+    let data_type = DataType::Union(
+        union_fields
+            .iter()
+            .zip(&variant_readers)
+            .map(|((type_id, f), reader)| {
+                let f = f
+                    .as_ref()
+                    .clone()
+                    .with_data_type(reader.get_data_type().clone());
+                (type_id, Arc::new(f))
+            })
+            .collect(),
+        mode,
+    );
+
+    Ok(Some(Box::new(UnionArrayReader::new(
+        type_id_reader,
+        variant_readers,
+        data_type,
+        field.def_level,
+        field.rep_level,
+        field.nullable,
     ))))
 }
 
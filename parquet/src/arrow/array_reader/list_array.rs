@@ -64,7 +64,11 @@ impl<OffsetSize: OffsetSizeTrait> ListArrayReader<OffsetSize> {
     }
 }
 
-/// Implementation of ListArrayReader. Nested lists and lists of structs are not yet supported.
+/// Implementation of ListArrayReader.
+///
+/// The item reader may itself be a list, struct or map reader, allowing arbitrarily
+/// nested types to be reconstructed: levels with a repetition level greater than that
+/// of this list belong to an inner list, and are already handled by the item reader.
 impl<OffsetSize: OffsetSizeTrait> ArrayReader for ListArrayReader<OffsetSize> {
     fn as_any(&self) -> &dyn Any {
         self
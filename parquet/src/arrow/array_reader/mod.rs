@@ -37,15 +37,17 @@ mod empty_array;
 mod fixed_len_byte_array;
 mod fixed_size_list_array;
 mod list_array;
+mod list_view_array;
 mod map_array;
 mod null_array;
 mod primitive_array;
 mod struct_array;
+mod union_array;
 
 #[cfg(test)]
 mod test_util;
 
-pub use builder::build_array_reader;
+pub use builder::{build_array_reader, build_parallel_array_reader};
 pub use byte_array::make_byte_array_reader;
 pub use byte_array_dictionary::make_byte_array_dictionary_reader;
 #[allow(unused_imports)] // Only used for benchmarks
@@ -54,10 +56,12 @@ pub use byte_view_array::make_byte_view_array_reader;
 pub use fixed_len_byte_array::make_fixed_len_byte_array_reader;
 pub use fixed_size_list_array::FixedSizeListArrayReader;
 pub use list_array::ListArrayReader;
+pub use list_view_array::ListViewArrayReader;
 pub use map_array::MapArrayReader;
 pub use null_array::NullArrayReader;
 pub use primitive_array::PrimitiveArrayReader;
 pub use struct_array::StructArrayReader;
+pub use union_array::UnionArrayReader;
 
 /// Array reader reads parquet data into arrow array.
 pub trait ArrayReader: Send {
@@ -16,6 +16,7 @@
 // under the License.
 
 use crate::arrow::array_reader::ArrayReader;
+use crate::arrow::arrow_reader::{DecodeExecutor, DecodeTask};
 use crate::errors::{ParquetError, Result};
 use arrow_array::{builder::BooleanBufferBuilder, Array, ArrayRef, StructArray};
 use arrow_data::{ArrayData, ArrayDataBuilder};
@@ -30,6 +31,7 @@ pub struct StructArrayReader {
     struct_def_level: i16,
     struct_rep_level: i16,
     nullable: bool,
+    executor: Option<Arc<dyn DecodeExecutor>>,
 }
 
 impl StructArrayReader {
@@ -47,8 +49,44 @@ impl StructArrayReader {
             struct_def_level: def_level,
             struct_rep_level: rep_level,
             nullable,
+            executor: None,
         }
     }
+
+    /// Decode the children of this struct concurrently using `executor`
+    pub fn with_executor(self, executor: Arc<dyn DecodeExecutor>) -> Self {
+        Self {
+            executor: Some(executor),
+            ..self
+        }
+    }
+
+    /// Calls `f` for each child, concurrently if an executor has been provided,
+    /// returning the results in the order of the children
+    fn map_children<R: Send>(
+        &mut self,
+        f: impl Fn(&mut Box<dyn ArrayReader>) -> R + Sync,
+    ) -> Vec<R> {
+        let executor = match &self.executor {
+            Some(executor) if self.children.len() > 1 => executor,
+            _ => return self.children.iter_mut().map(f).collect(),
+        };
+
+        let mut results: Vec<Option<R>> = self.children.iter().map(|_| None).collect();
+        let f = &f;
+        let tasks = self
+            .children
+            .iter_mut()
+            .zip(results.iter_mut())
+            .map(|(child, result)| Box::new(move || *result = Some(f(child))) as DecodeTask<'_>)
+            .collect();
+        executor.execute(tasks);
+
+        results
+            .into_iter()
+            .map(|result| result.expect("task should have completed"))
+            .collect()
+    }
 }
 
 impl ArrayReader for StructArrayReader {
@@ -64,8 +102,8 @@ impl ArrayReader for StructArrayReader {
 
     fn read_records(&mut self, batch_size: usize) -> Result<usize> {
         let mut read = None;
-        for child in self.children.iter_mut() {
-            let child_read = child.read_records(batch_size)?;
+        for child_read in self.map_children(|child| child.read_records(batch_size)) {
+            let child_read = child_read?;
             match read {
                 Some(expected) => {
                     if expected != child_read {
@@ -106,9 +144,8 @@ impl ArrayReader for StructArrayReader {
         }
 
         let children_array = self
-            .children
-            .iter_mut()
-            .map(|reader| reader.consume_batch())
+            .map_children(|reader| reader.consume_batch())
+            .into_iter()
             .collect::<Result<Vec<_>>>()?;
 
         // check that array child data has same size
@@ -178,8 +215,8 @@ impl ArrayReader for StructArrayReader {
 
     fn skip_records(&mut self, num_records: usize) -> Result<usize> {
         let mut skipped = None;
-        for child in self.children.iter_mut() {
-            let child_skipped = child.skip_records(num_records)?;
+        for child_skipped in self.map_children(|child| child.skip_records(num_records)) {
+            let child_skipped = child_skipped?;
             match skipped {
                 Some(expected) => {
                     if expected != child_skipped {
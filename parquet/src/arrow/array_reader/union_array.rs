@@ -0,0 +1,234 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::arrow::array_reader::{ArrayReader, StructArrayReader};
+use crate::arrow::schema::UNION_TYPE_ID_FIELD;
+use crate::errors::{ParquetError, Result};
+use arrow_array::cast::AsArray;
+use arrow_array::types::Int8Type;
+use arrow_array::{Array, ArrayRef, BooleanArray, UnionArray};
+use arrow_buffer::ScalarBuffer;
+use arrow_schema::{DataType as ArrowType, Field, Fields, UnionFields, UnionMode};
+use std::any::Any;
+use std::sync::Arc;
+
+/// Implementation of a union array reader
+///
+/// Unions are stored as a group containing the type id of each slot followed by a
+/// nullable column for each variant, see [`ArrowWriter`](crate::arrow::ArrowWriter)
+pub struct UnionArrayReader {
+    data_type: ArrowType,
+    reader: StructArrayReader,
+}
+
+impl UnionArrayReader {
+    /// Creates a new [`UnionArrayReader`] from a reader for the `type_id` column and a
+    /// reader for each variant, with a `def_level`, `rep_level` and `nullable` as defined
+    /// on [`ParquetField`][crate::arrow::schema::ParquetField]
+    pub fn new(
+        type_id_reader: Box<dyn ArrayReader>,
+        variant_readers: Vec<Box<dyn ArrayReader>>,
+        data_type: ArrowType,
+        def_level: i16,
+        rep_level: i16,
+        nullable: bool,
+    ) -> Self {
+        let fields = match &data_type {
+            ArrowType::Union(fields, _) => fields,
+            _ => unreachable!("expected union type"),
+        };
+        assert_eq!(fields.len(), variant_readers.len());
+
+        let struct_fields: Fields = std::iter::once(Field::new(
+            UNION_TYPE_ID_FIELD,
+            type_id_reader.get_data_type().clone(),
+            false,
+        ))
+        .chain(
+            fields
+                .iter()
+                .map(|(_, f)| f.as_ref().clone().with_nullable(true)),
+        )
+        .collect();
+
+        let mut children = Vec::with_capacity(variant_readers.len() + 1);
+        children.push(type_id_reader);
+        children.extend(variant_readers);
+
+        let reader = StructArrayReader::new(
+            ArrowType::Struct(struct_fields),
+            children,
+            def_level,
+            rep_level,
+            nullable,
+        );
+
+        Self { data_type, reader }
+    }
+}
+
+impl ArrayReader for UnionArrayReader {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_data_type(&self) -> &ArrowType {
+        &self.data_type
+    }
+
+    fn read_records(&mut self, batch_size: usize) -> Result<usize> {
+        self.reader.read_records(batch_size)
+    }
+
+    fn consume_batch(&mut self) -> Result<ArrayRef> {
+        let (fields, mode) = match &self.data_type {
+            ArrowType::Union(fields, mode) => (fields, *mode),
+            _ => unreachable!(),
+        };
+
+        let array = self.reader.consume_batch()?;
+        let array = array.as_struct();
+        let type_ids = array.column(0).as_primitive::<Int8Type>();
+        if type_ids.null_count() != 0 {
+            return Err(general_err!("union type_id cannot be null"));
+        }
+        let type_ids = type_ids.values().clone();
+        let variants = &array.columns()[1..];
+
+        let union = match mode {
+            UnionMode::Sparse => {
+                UnionArray::try_new(fields.clone(), type_ids, None, variants.to_vec())?
+            }
+            UnionMode::Dense => {
+                let (offsets, children) = densify(fields, &type_ids, variants)?;
+                UnionArray::try_new(fields.clone(), type_ids, Some(offsets), children)?
+            }
+        };
+        Ok(Arc::new(union))
+    }
+
+    fn skip_records(&mut self, num_records: usize) -> Result<usize> {
+        self.reader.skip_records(num_records)
+    }
+
+    fn get_def_levels(&self) -> Option<&[i16]> {
+        self.reader.get_def_levels()
+    }
+
+    fn get_rep_levels(&self) -> Option<&[i16]> {
+        self.reader.get_rep_levels()
+    }
+}
+
+/// Computes the offsets and children of a dense union from the sparse `variants`,
+/// keeping only the slots selected by `type_ids`
+fn densify(
+    fields: &UnionFields,
+    type_ids: &ScalarBuffer<i8>,
+    variants: &[ArrayRef],
+) -> Result<(ScalarBuffer<i32>, Vec<ArrayRef>)> {
+    let max_id = fields.iter().map(|(id, _)| id).max().unwrap_or_default();
+    let mut counts = vec![0_i32; max_id as usize + 1];
+    let offsets = type_ids
+        .iter()
+        .map(|id| {
+            let count = counts
+                .get_mut(*id as usize)
+                .ok_or_else(|| general_err!("invalid union type_id {}", id))?;
+            *count += 1;
+            Ok(*count - 1)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let children = fields
+        .iter()
+        .zip(variants)
+        .map(|((id, _), variant)| {
+            let predicate = BooleanArray::from_iter(type_ids.iter().map(|t| Some(*t == id)));
+            Ok(arrow_select::filter::filter(variant.as_ref(), &predicate)?)
+        })
+        .collect::<Result<_>>()?;
+
+    Ok((offsets.into(), children))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arrow::arrow_reader::ParquetRecordBatchReader;
+    use crate::arrow::ArrowWriter;
+    use arrow_array::{ArrayRef, Int32Array, RecordBatch, StringArray, UnionArray};
+    use arrow_buffer::ScalarBuffer;
+    use arrow_schema::{DataType, Field, UnionFields};
+    use bytes::Bytes;
+    use std::sync::Arc;
+
+    fn union_fields() -> UnionFields {
+        UnionFields::new(
+            vec![3, 7],
+            vec![
+                Field::new("a", DataType::Int32, false),
+                Field::new("b", DataType::Utf8, true),
+            ],
+        )
+    }
+
+    fn round_trip(union: UnionArray) {
+        let batch = RecordBatch::try_from_iter([("u", Arc::new(union) as ArrayRef)]).unwrap();
+
+        let mut buf = Vec::with_capacity(1024);
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let mut reader = ParquetRecordBatchReader::try_new(Bytes::from(buf), 3).unwrap();
+        let a = reader.next().unwrap().unwrap();
+        let b = reader.next().unwrap().unwrap();
+        assert!(reader.next().is_none());
+        assert_eq!(a.schema(), batch.schema());
+        assert_eq!(a, batch.slice(0, 3));
+        assert_eq!(b, batch.slice(3, 2));
+    }
+
+    #[test]
+    fn test_sparse_union_round_trip() {
+        // Unselected slots are read back as null
+        let a = Int32Array::from(vec![Some(1), None, None, Some(4), None]);
+        let b = StringArray::from(vec![None, Some("x"), None, None, Some("y")]);
+        let union = UnionArray::try_new(
+            union_fields(),
+            ScalarBuffer::from(vec![3, 7, 7, 3, 7]),
+            None,
+            vec![Arc::new(a), Arc::new(b)],
+        )
+        .unwrap();
+        round_trip(union);
+    }
+
+    #[test]
+    fn test_dense_union_round_trip() {
+        let a = Int32Array::from(vec![1, 4]);
+        let b = StringArray::from(vec![Some("x"), None, Some("y")]);
+        let union = UnionArray::try_new(
+            union_fields(),
+            ScalarBuffer::from(vec![3, 7, 7, 3, 7]),
+            Some(ScalarBuffer::from(vec![0, 0, 1, 1, 2])),
+            vec![Arc::new(a), Arc::new(b)],
+        )
+        .unwrap();
+        round_trip(union);
+    }
+}
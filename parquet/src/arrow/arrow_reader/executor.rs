@@ -0,0 +1,139 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Concurrent decoding of columns, see [`DecodeExecutor`]
+
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+/// A task submitted to a [`DecodeExecutor`]
+pub type DecodeTask<'a> = Box<dyn FnOnce() + Send + 'a>;
+
+/// Runs the tasks that decode the columns of a row group concurrently
+///
+/// By default [`ParquetRecordBatchReader`] decodes the projected columns of each batch
+/// serially on the calling thread. Providing a [`DecodeExecutor`] with
+/// [`ArrowReaderBuilder::with_decode_executor`] instead decodes each top-level column
+/// as a separate task, which can significantly reduce the latency of decoding wide tables.
+///
+/// [`ScopedThreadExecutor`] is provided using threads from the standard library, and
+/// with the `rayon` feature [`DecodeExecutor`] is implemented for `rayon::ThreadPool`.
+///
+/// [`ParquetRecordBatchReader`]: crate::arrow::arrow_reader::ParquetRecordBatchReader
+/// [`ArrowReaderBuilder::with_decode_executor`]: crate::arrow::arrow_reader::ArrowReaderBuilder::with_decode_executor
+pub trait DecodeExecutor: Send + Sync {
+    /// Runs `tasks`, returning once all of them have completed
+    ///
+    /// Tasks may be run in any order and on any thread, including the calling thread
+    fn execute<'a>(&self, tasks: Vec<DecodeTask<'a>>);
+}
+
+/// A [`DecodeExecutor`] that runs tasks on up to `num_threads` scoped threads
+///
+/// Threads are spawned for each call to [`DecodeExecutor::execute`], with the calling
+/// thread also running tasks. A long-lived thread pool, such as `rayon::ThreadPool`,
+/// avoids the cost of spawning threads for every batch.
+#[derive(Debug, Clone, Copy)]
+pub struct ScopedThreadExecutor {
+    num_threads: NonZeroUsize,
+}
+
+impl ScopedThreadExecutor {
+    /// Create a new [`ScopedThreadExecutor`] that runs at most `num_threads` tasks
+    /// concurrently
+    pub fn new(num_threads: NonZeroUsize) -> Self {
+        Self { num_threads }
+    }
+
+    /// Returns the maximum number of tasks run concurrently
+    pub fn num_threads(&self) -> NonZeroUsize {
+        self.num_threads
+    }
+}
+
+impl Default for ScopedThreadExecutor {
+    /// Returns a [`ScopedThreadExecutor`] using [`std::thread::available_parallelism`]
+    fn default() -> Self {
+        let num_threads = std::thread::available_parallelism().unwrap_or(NonZeroUsize::MIN);
+        Self::new(num_threads)
+    }
+}
+
+impl DecodeExecutor for ScopedThreadExecutor {
+    fn execute<'a>(&self, tasks: Vec<DecodeTask<'a>>) {
+        let num_threads = self.num_threads.get().min(tasks.len());
+        let queue = Mutex::new(tasks.into_iter());
+        let run = || loop {
+            let task = queue.lock().unwrap().next();
+            match task {
+                Some(task) => task(),
+                None => break,
+            }
+        };
+
+        std::thread::scope(|s| {
+            for _ in 1..num_threads {
+                s.spawn(run);
+            }
+            run()
+        })
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl DecodeExecutor for rayon::ThreadPool {
+    fn execute<'a>(&self, tasks: Vec<DecodeTask<'a>>) {
+        self.scope(|s| {
+            for task in tasks {
+                s.spawn(move |_| task())
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_scoped_thread_executor() {
+        for num_threads in [1, 2, 8] {
+            let executor = ScopedThreadExecutor::new(NonZeroUsize::new(num_threads).unwrap());
+            let mut results = vec![0; 20];
+            let count = AtomicUsize::new(0);
+            let tasks = results
+                .iter_mut()
+                .enumerate()
+                .map(|(idx, result)| {
+                    let count = &count;
+                    Box::new(move || {
+                        *result = idx * 2;
+                        count.fetch_add(1, Ordering::Relaxed);
+                    }) as DecodeTask<'_>
+                })
+                .collect();
+
+            executor.execute(tasks);
+            assert_eq!(count.load(Ordering::Relaxed), 20);
+            assert_eq!(results, (0..20).map(|x| x * 2).collect::<Vec<_>>());
+        }
+
+        // No tasks
+        ScopedThreadExecutor::default().execute(vec![]);
+    }
+}
@@ -25,20 +25,30 @@ use arrow_array::Array;
 use arrow_array::{RecordBatch, RecordBatchReader};
 use arrow_schema::{ArrowError, DataType as ArrowType, Schema, SchemaRef};
 use arrow_select::filter::prep_null_mask_filter;
+pub use executor::{DecodeExecutor, DecodeTask, ScopedThreadExecutor};
 pub use filter::{ArrowPredicate, ArrowPredicateFn, RowFilter};
+pub use page_pruning::{CompareOp, PagePredicate};
+pub use pruning::EqualityPredicate;
+pub(crate) use pruning::RowGroupPruner;
 pub use selection::{RowSelection, RowSelector};
 
 pub use crate::arrow::array_reader::RowGroups;
-use crate::arrow::array_reader::{build_array_reader, ArrayReader};
+use crate::arrow::array_reader::{build_array_reader, build_parallel_array_reader, ArrayReader};
 use crate::arrow::schema::{parquet_to_arrow_schema_and_fields, ParquetField};
 use crate::arrow::{parquet_to_arrow_field_levels, FieldLevels, ProjectionMask};
+use crate::bloom_filter::Sbbf;
 use crate::column::page::{PageIterator, PageReader};
+#[cfg(feature = "encryption")]
+use crate::encryption::decrypt::{CryptoContext, FileDecryptionProperties};
 use crate::errors::{ParquetError, Result};
 use crate::file::metadata::{ParquetMetaData, ParquetMetaDataReader};
 use crate::file::reader::{ChunkReader, SerializedPageReader};
 use crate::schema::types::SchemaDescriptor;
 
+mod executor;
 mod filter;
+mod page_pruning;
+mod pruning;
 mod selection;
 pub mod statistics;
 
@@ -72,6 +82,8 @@ pub struct ArrowReaderBuilder<T> {
     pub(crate) limit: Option<usize>,
 
     pub(crate) offset: Option<usize>,
+
+    pub(crate) executor: Option<Arc<dyn DecodeExecutor>>,
 }
 
 impl<T> ArrowReaderBuilder<T> {
@@ -88,6 +100,7 @@ impl<T> ArrowReaderBuilder<T> {
             selection: None,
             limit: None,
             offset: None,
+            executor: None,
         }
     }
 
@@ -238,6 +251,86 @@ impl<T> ArrowReaderBuilder<T> {
             ..self
         }
     }
+
+    /// Provide a [`DecodeExecutor`] to decode the top-level columns of each batch
+    /// concurrently
+    ///
+    /// This is beneficial for wide tables where decoding is CPU-bound. Batches are
+    /// returned in the same order, and with the same contents, as without an executor,
+    /// and the executor is also used to evaluate any [`RowFilter`]
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use bytes::Bytes;
+    /// # use arrow_array::{Int32Array, RecordBatch};
+    /// # use parquet::arrow::arrow_reader::{ParquetRecordBatchReaderBuilder, ScopedThreadExecutor};
+    /// # use parquet::arrow::ArrowWriter;
+    /// # let a = Arc::new(Int32Array::from_iter_values(0..100)) as _;
+    /// # let b = Arc::new(Int32Array::from_iter_values(100..200)) as _;
+    /// # let batch = RecordBatch::try_from_iter([("a", a), ("b", b)]).unwrap();
+    /// # let mut file: Vec<u8> = Vec::with_capacity(1024);
+    /// # let mut writer = ArrowWriter::try_new(&mut file, batch.schema(), None).unwrap();
+    /// # writer.write(&batch).unwrap();
+    /// # writer.close().unwrap();
+    /// # let file = Bytes::from(file);
+    /// let executor = Arc::new(ScopedThreadExecutor::default());
+    /// let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+    ///     .unwrap()
+    ///     .with_decode_executor(executor)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+    /// assert_eq!(batches, vec![batch]);
+    /// ```
+    pub fn with_decode_executor(self, executor: Arc<dyn DecodeExecutor>) -> Self {
+        Self {
+            executor: Some(executor),
+            ..self
+        }
+    }
+
+    /// Returns a [`RowSelection`] of the rows that may match `predicate`, based on the
+    /// page index
+    ///
+    /// If [`Self::with_row_groups`] has been called only those row groups are considered,
+    /// and the result can be passed to [`Self::with_row_selection`]
+    ///
+    /// This requires the page index to have been read, see [`ArrowReaderOptions::with_page_index`],
+    /// otherwise all rows are selected
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use bytes::Bytes;
+    /// # use arrow_array::{Int64Array, RecordBatch};
+    /// # use arrow_schema::{DataType, Field, Schema};
+    /// # use parquet::arrow::arrow_reader::{ArrowReaderOptions, CompareOp, PagePredicate, ParquetRecordBatchReaderBuilder, RowSelector};
+    /// # use parquet::arrow::ArrowWriter;
+    /// # use parquet::file::properties::WriterProperties;
+    /// # let mut file: Vec<u8> = Vec::with_capacity(1024);
+    /// # let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+    /// # let props = WriterProperties::builder().set_data_page_row_count_limit(100).set_write_batch_size(100).build();
+    /// # let mut writer = ArrowWriter::try_new(&mut file, schema.clone(), Some(props)).unwrap();
+    /// # let batch = RecordBatch::try_new(schema, vec![Arc::new(Int64Array::from_iter_values(0..1000))]).unwrap();
+    /// # writer.write(&batch).unwrap();
+    /// # writer.close().unwrap();
+    /// # let file = Bytes::from(file);
+    /// let options = ArrowReaderOptions::new().with_page_index(true);
+    /// let builder = ParquetRecordBatchReaderBuilder::try_new_with_options(file, options).unwrap();
+    ///
+    /// // Find the pages that may contain ids in 250..420
+    /// let predicate = PagePredicate::compare("id", CompareOp::GtEq, Arc::new(Int64Array::from(vec![250])))
+    ///     .and(PagePredicate::compare("id", CompareOp::Lt, Arc::new(Int64Array::from(vec![420]))));
+    /// let selection = builder.prune_pages(&predicate).unwrap();
+    ///
+    /// let selectors: Vec<RowSelector> = selection.clone().into();
+    /// assert_eq!(selectors, vec![RowSelector::skip(200), RowSelector::select(300), RowSelector::skip(500)]);
+    ///
+    /// let reader = builder.with_row_selection(selection).build().unwrap();
+    /// ```
+    pub fn prune_pages(&self, predicate: &PagePredicate) -> Result<RowSelection> {
+        predicate.row_selection(&self.metadata, self.row_groups.as_deref())
+    }
 }
 
 /// Options that control how metadata is read for a parquet file
@@ -252,6 +345,9 @@ pub struct ArrowReaderOptions {
     supplied_schema: Option<SchemaRef>,
     /// If true, attempt to read `OffsetIndex` and `ColumnIndex`
     pub(crate) page_index: bool,
+    /// If encryption is enabled, the file decryption properties can be provided
+    #[cfg(feature = "encryption")]
+    pub(crate) file_decryption_properties: Option<FileDecryptionProperties>,
 }
 
 impl ArrowReaderOptions {
@@ -342,6 +438,19 @@ impl ArrowReaderOptions {
     pub fn with_page_index(self, page_index: bool) -> Self {
         Self { page_index, ..self }
     }
+
+    /// Provide the [`FileDecryptionProperties`] used to read a file written with
+    /// Parquet modular encryption
+    #[cfg(feature = "encryption")]
+    pub fn with_file_decryption_properties(
+        self,
+        file_decryption_properties: FileDecryptionProperties,
+    ) -> Self {
+        Self {
+            file_decryption_properties: Some(file_decryption_properties),
+            ..self
+        }
+    }
 }
 
 /// The metadata necessary to construct a [`ArrowReaderBuilder`]
@@ -380,9 +489,11 @@ impl ArrowReaderMetadata {
     /// `Self::metadata` is missing the page index, this function will attempt
     /// to load the page index by making an object store request.
     pub fn load<T: ChunkReader>(reader: &T, options: ArrowReaderOptions) -> Result<Self> {
-        let metadata = ParquetMetaDataReader::new()
-            .with_page_indexes(options.page_index)
-            .parse_and_finish(reader)?;
+        let metadata = ParquetMetaDataReader::new().with_page_indexes(options.page_index);
+        #[cfg(feature = "encryption")]
+        let metadata =
+            metadata.with_decryption_properties(options.file_decryption_properties.as_ref());
+        let metadata = metadata.parse_and_finish(reader)?;
         Self::try_new(Arc::new(metadata), options)
     }
 
@@ -572,6 +683,60 @@ impl<T: ChunkReader + 'static> ParquetRecordBatchReaderBuilder<T> {
         Self::new_builder(SyncReader(input), metadata)
     }
 
+    /// Returns the row groups that may contain rows matching all of `predicates`,
+    /// based on their statistics and bloom filters
+    ///
+    /// If [`Self::with_row_groups`] has been called only those row groups are
+    /// considered. Bloom filters are only read for row groups whose statistics do not
+    /// already exclude them, and the result can be passed to [`Self::with_row_groups`]
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use bytes::Bytes;
+    /// # use arrow_array::{Int64Array, RecordBatch};
+    /// # use arrow_schema::{DataType, Field, Schema};
+    /// # use parquet::arrow::arrow_reader::{EqualityPredicate, ParquetRecordBatchReaderBuilder};
+    /// # use parquet::arrow::ArrowWriter;
+    /// # use parquet::file::properties::WriterProperties;
+    /// # let mut file: Vec<u8> = Vec::with_capacity(1024);
+    /// # let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+    /// # let props = WriterProperties::builder().set_max_row_group_size(100).set_bloom_filter_enabled(true).build();
+    /// # let mut writer = ArrowWriter::try_new(&mut file, schema.clone(), Some(props)).unwrap();
+    /// # let batch = RecordBatch::try_new(schema, vec![Arc::new(Int64Array::from_iter_values(0..1000))]).unwrap();
+    /// # writer.write(&batch).unwrap();
+    /// # writer.close().unwrap();
+    /// # let file = Bytes::from(file);
+    /// let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+    ///
+    /// // Find the row groups that may contain id 150 or 420
+    /// let ids = Arc::new(Int64Array::from(vec![150, 420]));
+    /// let predicate = EqualityPredicate::new("id", ids);
+    /// let row_groups = builder.prune_row_groups(&[predicate]).unwrap();
+    /// assert_eq!(row_groups, vec![1, 4]);
+    ///
+    /// let reader = builder.with_row_groups(row_groups).build().unwrap();
+    /// ```
+    pub fn prune_row_groups(&self, predicates: &[EqualityPredicate]) -> Result<Vec<usize>> {
+        let pruner = RowGroupPruner::try_new(self.parquet_schema(), predicates)?;
+        let row_groups = pruner.prune_by_statistics(&self.metadata, self.row_groups.as_deref())?;
+        let columns = pruner.bloom_filter_columns();
+
+        let mut selected = Vec::with_capacity(row_groups.len());
+        'outer: for row_group_idx in row_groups {
+            let row_group = self.metadata.row_group(row_group_idx);
+            for column_idx in &columns {
+                let column = row_group.column(*column_idx);
+                if let Some(filter) = Sbbf::read_from_column_chunk(column, &self.input.0)? {
+                    if !pruner.check_bloom_filter(*column_idx, &filter) {
+                        continue 'outer;
+                    }
+                }
+            }
+            selected.push(row_group_idx);
+        }
+        Ok(selected)
+    }
+
     /// Build a [`ParquetRecordBatchReader`]
     ///
     /// Note: this will eagerly evaluate any `RowFilter` before returning
@@ -600,8 +765,12 @@ impl<T: ChunkReader + 'static> ParquetRecordBatchReaderBuilder<T> {
                     break;
                 }
 
-                let array_reader =
-                    build_array_reader(self.fields.as_deref(), predicate.projection(), &reader)?;
+                let array_reader = build_parallel_array_reader(
+                    self.fields.as_deref(),
+                    predicate.projection(),
+                    &reader,
+                    self.executor.as_ref(),
+                )?;
 
                 selection = Some(evaluate_predicate(
                     batch_size,
@@ -612,7 +781,12 @@ impl<T: ChunkReader + 'static> ParquetRecordBatchReaderBuilder<T> {
             }
         }
 
-        let array_reader = build_array_reader(self.fields.as_deref(), &self.projection, &reader)?;
+        let array_reader = build_parallel_array_reader(
+            self.fields.as_deref(),
+            &self.projection,
+            &reader,
+            self.executor.as_ref(),
+        )?;
 
         // If selection is empty, truncate
         if !selects_any(selection.as_ref()) {
@@ -678,6 +852,11 @@ impl<T: ChunkReader + 'static> Iterator for ReaderPageIterator<T> {
         let reader = self.reader.clone();
 
         let ret = SerializedPageReader::new(reader, meta, total_rows, page_locations);
+        #[cfg(feature = "encryption")]
+        let ret = ret.and_then(|x| {
+            let context = CryptoContext::for_column(&self.metadata, rg_idx, self.column_idx)?;
+            Ok(x.with_crypto_context(context))
+        });
         Some(ret.map(|x| Box::new(x) as _))
     }
 }
@@ -926,7 +1105,8 @@ mod tests {
     use bytes::Bytes;
     use half::f16;
     use num::PrimInt;
-    use rand::{thread_rng, Rng, RngCore};
+    use rand::rngs::StdRng;
+    use rand::{thread_rng, Rng, RngCore, SeedableRng};
     use tempfile::tempfile;
 
     use arrow_array::builder::*;
@@ -944,8 +1124,9 @@ mod tests {
     use arrow_select::concat::concat_batches;
 
     use crate::arrow::arrow_reader::{
-        ArrowPredicateFn, ArrowReaderBuilder, ArrowReaderOptions, ParquetRecordBatchReader,
-        ParquetRecordBatchReaderBuilder, RowFilter, RowSelection, RowSelector,
+        ArrowPredicateFn, ArrowReaderBuilder, ArrowReaderOptions, DecodeExecutor,
+        ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder, RowFilter, RowSelection,
+        RowSelector, ScopedThreadExecutor,
     };
     use crate::arrow::schema::add_encoded_arrow_schema_to_metadata;
     use crate::arrow::{ArrowWriter, ProjectionMask};
@@ -4065,4 +4246,228 @@ mod tests {
             }
         }
     }
+
+    /// Generates a batch of `List<Struct<List<Map<Utf8, List<Int32>>>>>` with nulls
+    /// and empty collections at every level of nesting
+    fn deeply_nested_batch(num_rows: usize) -> RecordBatch {
+        let values = ArrowDataType::List(Arc::new(Field::new("item", ArrowDataType::Int32, true)));
+        let entries = Field::new_struct(
+            "entries",
+            vec![
+                Field::new("keys", ArrowDataType::Utf8, false),
+                Field::new("values", values, true),
+            ],
+            false,
+        );
+        let attrs = Field::new_list(
+            "attrs",
+            Field::new("item", ArrowDataType::Map(Arc::new(entries), false), true),
+            true,
+        );
+        let event = Field::new_struct(
+            "item",
+            vec![Field::new("name", ArrowDataType::Utf8, true), attrs],
+            true,
+        );
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", ArrowDataType::Int32, false),
+            Field::new_list("events", event, true),
+        ]));
+
+        // Returns `value` or, with some probability, null
+        fn maybe(rng: &mut impl Rng, value: serde_json::Value) -> serde_json::Value {
+            match rng.gen_bool(0.15) {
+                true => serde_json::Value::Null,
+                false => value,
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut json = String::new();
+        for id in 0..num_rows {
+            let mut events = vec![];
+            for e in 0..rng.gen_range(0..4) {
+                let mut attrs = vec![];
+                for _ in 0..rng.gen_range(0..3) {
+                    let mut map = serde_json::Map::new();
+                    for k in 0..rng.gen_range(0..3) {
+                        let mut list = vec![];
+                        for _ in 0..rng.gen_range(0..3) {
+                            let v = rng.gen_range(0..100);
+                            list.push(maybe(&mut rng, v.into()));
+                        }
+                        let list = maybe(&mut rng, list.into());
+                        map.insert(format!("k{k}"), list);
+                    }
+                    attrs.push(maybe(&mut rng, map.into()));
+                }
+                let event = serde_json::json!({
+                    "name": maybe(&mut rng, format!("{id}-{e}").into()),
+                    "attrs": maybe(&mut rng, attrs.into()),
+                });
+                events.push(maybe(&mut rng, event));
+            }
+            let row = serde_json::json!({"id": id, "events": maybe(&mut rng, events.into())});
+            json.push_str(&row.to_string());
+            json.push('\n');
+        }
+
+        let batches = arrow::json::ReaderBuilder::new(schema.clone())
+            .with_batch_size(num_rows)
+            .build(json.as_bytes())
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        concat_batches(&schema, &batches).unwrap()
+    }
+
+    #[test]
+    fn test_deeply_nested_round_trip() {
+        let batch = deeply_nested_batch(2048);
+
+        let props = WriterProperties::builder()
+            .set_data_page_row_count_limit(100)
+            .set_write_batch_size(10)
+            .build();
+        let mut buf = Vec::with_capacity(1024);
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let buf = Bytes::from(buf);
+
+        for batch_size in [7, 100, 2048] {
+            // Full scan
+            let reader = ParquetRecordBatchReaderBuilder::try_new(buf.clone())
+                .unwrap()
+                .with_batch_size(batch_size)
+                .build()
+                .unwrap();
+            let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+            assert_eq!(concat_batches(batch.schema_ref(), &batches).unwrap(), batch);
+
+            // Row selection, with and without the page index
+            let selection = RowSelection::from(vec![
+                RowSelector::skip(150),
+                RowSelector::select(333),
+                RowSelector::skip(1),
+                RowSelector::select(1),
+                RowSelector::skip(1000),
+                RowSelector::select(563),
+            ]);
+            for page_index in [false, true] {
+                let options = ArrowReaderOptions::new().with_page_index(page_index);
+                let reader =
+                    ParquetRecordBatchReaderBuilder::try_new_with_options(buf.clone(), options)
+                        .unwrap()
+                        .with_row_selection(selection.clone())
+                        .with_batch_size(batch_size)
+                        .build()
+                        .unwrap();
+                let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+                let actual = concat_batches(batch.schema_ref(), &batches).unwrap();
+                let expected = concat_batches(
+                    batch.schema_ref(),
+                    &[
+                        batch.slice(150, 333),
+                        batch.slice(484, 1),
+                        batch.slice(1485, 563),
+                    ],
+                )
+                .unwrap();
+                assert_eq!(actual, expected);
+            }
+
+            // Row filter on a sibling column
+            let builder = ParquetRecordBatchReaderBuilder::try_new(buf.clone()).unwrap();
+            let mask = ProjectionMask::leaves(builder.parquet_schema(), [0]);
+            let filter = RowFilter::new(vec![Box::new(ArrowPredicateFn::new(mask, |b| {
+                let ids = b.column(0).as_primitive::<arrow_array::types::Int32Type>();
+                Ok(BooleanArray::from_unary(ids, |id| id % 3 == 0))
+            }))]);
+            let reader = builder
+                .with_row_filter(filter)
+                .with_batch_size(batch_size)
+                .build()
+                .unwrap();
+            let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+            let actual = concat_batches(batch.schema_ref(), &batches).unwrap();
+            let ids = batch
+                .column(0)
+                .as_primitive::<arrow_array::types::Int32Type>();
+            let predicate = BooleanArray::from_unary(ids, |id| id % 3 == 0);
+            let expected = arrow_select::filter::filter_record_batch(&batch, &predicate).unwrap();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_decode_executor() {
+        let mut columns: Vec<(String, ArrayRef)> = vec![];
+        for i in 0..20 {
+            let ints =
+                Int32Array::from_iter((0..2000).map(|x: i32| (x % (i + 2) != 0).then_some(x)));
+            let strings = StringArray::from_iter_values((0..2000).map(|x| format!("{i}-{x}")));
+            let lists = ListArray::from_iter_primitive::<arrow_array::types::Int64Type, _, _>(
+                (0..2000_usize).map(|x| (x % 7 != 0).then(|| vec![Some(x as i64); x % 3])),
+            );
+            columns.push((format!("int_{i}"), Arc::new(ints) as ArrayRef));
+            columns.push((format!("string_{i}"), Arc::new(strings) as ArrayRef));
+            columns.push((format!("list_{i}"), Arc::new(lists) as ArrayRef));
+        }
+        let batch = RecordBatch::try_from_iter(columns).unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(700)
+            .set_data_page_row_count_limit(100)
+            .set_write_batch_size(10)
+            .build();
+        let mut buf = Vec::with_capacity(1024);
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let buf = Bytes::from(buf);
+
+        let selection = RowSelection::from(vec![
+            RowSelector::skip(150),
+            RowSelector::select(900),
+            RowSelector::skip(300),
+            RowSelector::select(500),
+        ]);
+
+        let read = |executor: Option<Arc<dyn DecodeExecutor>>| {
+            let options = ArrowReaderOptions::new().with_page_index(true);
+            let builder =
+                ParquetRecordBatchReaderBuilder::try_new_with_options(buf.clone(), options)
+                    .unwrap();
+            let mask = ProjectionMask::leaves(builder.parquet_schema(), [0]);
+            let filter = RowFilter::new(vec![Box::new(ArrowPredicateFn::new(mask, |b| {
+                Ok(BooleanArray::from_iter(
+                    b.column(0)
+                        .as_primitive::<arrow_array::types::Int32Type>()
+                        .iter()
+                        .map(|x| x.map(|x| x % 5 != 0)),
+                ))
+            }))]);
+            let mut builder = builder
+                .with_batch_size(256)
+                .with_row_selection(selection.clone())
+                .with_row_filter(filter);
+            if let Some(executor) = executor {
+                builder = builder.with_decode_executor(executor);
+            }
+            builder
+                .build()
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        };
+
+        let expected = read(None);
+        assert_ne!(expected.iter().map(|b| b.num_rows()).sum::<usize>(), 0);
+        for num_threads in [1, 2, 4, 16] {
+            let num_threads = std::num::NonZeroUsize::new(num_threads).unwrap();
+            let actual = read(Some(Arc::new(ScopedThreadExecutor::new(num_threads))));
+            assert_eq!(actual, expected);
+        }
+    }
 }
@@ -0,0 +1,849 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Page pruning with the page index, see [`PagePredicate`]
+
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use arrow_array::{Array, ArrayRef};
+
+use crate::arrow::arrow_reader::pruning::PhysicalValue;
+use crate::arrow::arrow_reader::{RowSelection, RowSelector};
+use crate::basic::SortOrder;
+use crate::data_type::private::ParquetValueType;
+use crate::errors::{ParquetError, Result};
+use crate::file::metadata::ParquetMetaData;
+use crate::file::page_index::index::{Index, NativeIndex, PageIndex};
+use crate::file::page_index::offset_index::OffsetIndexMetaData;
+use crate::schema::types::{ColumnDescriptor, ColumnPath, SchemaDescriptor};
+
+/// A comparison operator used by [`PagePredicate::Compare`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    /// `=`
+    Eq,
+    /// `!=`
+    NotEq,
+    /// `<`
+    Lt,
+    /// `<=`
+    LtEq,
+    /// `>`
+    Gt,
+    /// `>=`
+    GtEq,
+}
+
+/// A predicate over leaf columns, used to skip pages that can not contain matching
+/// rows based on the page index, see [`ArrowReaderBuilder::prune_pages`]
+///
+/// Predicates follow SQL semantics, in particular a comparison with a null is neither
+/// true nor false, and so never matches even when negated with [`PagePredicate::Not`].
+///
+/// Predicates on repeated columns, such as the elements of a list, match a row if any of
+/// its values match.
+///
+/// Comparisons with a floating point `NaN` are false, except for [`CompareOp::NotEq`].
+/// As `NaN` is excluded from the statistics, pages of floating point columns are only
+/// pruned when the predicate is false for every non-`NaN` value in the page.
+///
+/// Values are cast to the arrow type of the parquet column. A value that can not be
+/// exactly represented in this type, such as `1.5` for an integer column or a timestamp
+/// with a finer unit than the column, is never equal to any value of the column, and
+/// other comparisons with it never prune pages. Predicates on columns whose
+/// physical representation does not permit comparisons, such as decimals, `INT96` and
+/// half-precision floats, never prune pages.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use arrow_array::{Int32Array, StringArray};
+/// # use parquet::arrow::arrow_reader::{CompareOp, PagePredicate};
+/// // a >= 10 AND (b IN ('x', 'y') OR c IS NULL)
+/// let predicate = PagePredicate::compare("a", CompareOp::GtEq, Arc::new(Int32Array::from(vec![10])))
+///     .and(
+///         PagePredicate::in_list("b", Arc::new(StringArray::from(vec!["x", "y"])))
+///             .or(PagePredicate::is_null("c")),
+///     );
+/// ```
+///
+/// [`ArrowReaderBuilder::prune_pages`]: crate::arrow::arrow_reader::ArrowReaderBuilder::prune_pages
+#[derive(Debug, Clone)]
+pub enum PagePredicate {
+    /// `column <op> value`, where `value` is an array containing a single value
+    Compare {
+        /// The path of the leaf column
+        column: ColumnPath,
+        /// The comparison operator
+        op: CompareOp,
+        /// An array containing the value to compare with
+        value: ArrayRef,
+    },
+    /// `column IN (values)`
+    InList {
+        /// The path of the leaf column
+        column: ColumnPath,
+        /// The values to compare with
+        values: ArrayRef,
+    },
+    /// `column IS NULL`
+    IsNull(ColumnPath),
+    /// `column IS NOT NULL`
+    IsNotNull(ColumnPath),
+    /// Both predicates are true
+    And(Box<PagePredicate>, Box<PagePredicate>),
+    /// Either predicate is true
+    Or(Box<PagePredicate>, Box<PagePredicate>),
+    /// The predicate is false
+    Not(Box<PagePredicate>),
+}
+
+impl PagePredicate {
+    /// Create a [`PagePredicate::Compare`]
+    pub fn compare(column: impl Into<ColumnPath>, op: CompareOp, value: ArrayRef) -> Self {
+        Self::Compare {
+            column: column.into(),
+            op,
+            value,
+        }
+    }
+
+    /// Create a [`PagePredicate::InList`]
+    pub fn in_list(column: impl Into<ColumnPath>, values: ArrayRef) -> Self {
+        Self::InList {
+            column: column.into(),
+            values,
+        }
+    }
+
+    /// Create a [`PagePredicate::IsNull`]
+    pub fn is_null(column: impl Into<ColumnPath>) -> Self {
+        Self::IsNull(column.into())
+    }
+
+    /// Create a [`PagePredicate::IsNotNull`]
+    pub fn is_not_null(column: impl Into<ColumnPath>) -> Self {
+        Self::IsNotNull(column.into())
+    }
+
+    /// Combine `self` and `other` with [`PagePredicate::And`]
+    pub fn and(self, other: Self) -> Self {
+        Self::And(Box::new(self), Box::new(other))
+    }
+
+    /// Combine `self` and `other` with [`PagePredicate::Or`]
+    pub fn or(self, other: Self) -> Self {
+        Self::Or(Box::new(self), Box::new(other))
+    }
+
+    /// Negate `self` with [`PagePredicate::Not`]
+    pub fn negate(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+
+    /// Returns a [`RowSelection`] of the rows in `row_groups`, or all row groups if `None`,
+    /// that may match this predicate according to the page index in `metadata`
+    pub(crate) fn row_selection(
+        &self,
+        metadata: &ParquetMetaData,
+        row_groups: Option<&[usize]>,
+    ) -> Result<RowSelection> {
+        let num_row_groups = metadata.num_row_groups();
+        let row_groups = match row_groups {
+            Some(row_groups) => {
+                if let Some(idx) = row_groups.iter().find(|x| **x >= num_row_groups) {
+                    return Err(general_err!(
+                        "row group {} out of bounds 0..{}",
+                        idx,
+                        num_row_groups
+                    ));
+                }
+                row_groups.to_vec()
+            }
+            None => (0..num_row_groups).collect(),
+        };
+
+        let mut selectors: Vec<RowSelector> = vec![];
+        for row_group_idx in row_groups {
+            let ctx = RowGroupContext {
+                schema: metadata.file_metadata().schema_descr(),
+                num_rows: metadata.row_group(row_group_idx).num_rows() as usize,
+                column_index: metadata.column_index().map(|x| x[row_group_idx].as_slice()),
+                offset_index: metadata.offset_index().map(|x| x[row_group_idx].as_slice()),
+            };
+            let outcome = self.evaluate(&ctx)?;
+            selectors.extend(outcome.may_be_true.iter().copied());
+        }
+        Ok(selectors.into())
+    }
+
+    fn evaluate(&self, ctx: &RowGroupContext<'_>) -> Result<Outcome> {
+        Ok(match self {
+            Self::Compare { column, op, value } => {
+                if value.len() != 1 {
+                    return Err(general_err!(
+                        "comparison with column {} must have a single value, got {}",
+                        column,
+                        value.len()
+                    ));
+                }
+                if value.is_null(0) {
+                    // A comparison with null is neither true nor false
+                    return Ok(Outcome::none(ctx.num_rows));
+                }
+                ctx.evaluate_leaf(column, value, |page, values, sort_order| {
+                    compare_page(page, *op, values, sort_order)
+                })?
+            }
+            Self::InList { column, values } => {
+                ctx.evaluate_leaf(column, values, |page, values, sort_order| {
+                    in_list_page(page, values, sort_order)
+                })?
+            }
+            Self::IsNull(column) => ctx.evaluate_nulls(column, false)?,
+            Self::IsNotNull(column) => ctx.evaluate_nulls(column, true)?,
+            Self::And(a, b) => {
+                let (a, b) = (a.evaluate(ctx)?, b.evaluate(ctx)?);
+                Outcome {
+                    may_be_true: a.may_be_true.intersection(&b.may_be_true),
+                    may_be_false: a.may_be_false.union(&b.may_be_false),
+                }
+            }
+            Self::Or(a, b) => {
+                let (a, b) = (a.evaluate(ctx)?, b.evaluate(ctx)?);
+                Outcome {
+                    may_be_true: a.may_be_true.union(&b.may_be_true),
+                    may_be_false: a.may_be_false.intersection(&b.may_be_false),
+                }
+            }
+            Self::Not(a) => {
+                let a = a.evaluate(ctx)?;
+                Outcome {
+                    may_be_true: a.may_be_false,
+                    may_be_false: a.may_be_true,
+                }
+            }
+        })
+    }
+}
+
+/// The rows of a row group for which a predicate may evaluate to true, and those for
+/// which it may evaluate to false
+///
+/// As a predicate involving nulls is neither true nor false, these are tracked
+/// separately to correctly handle [`PagePredicate::Not`]
+struct Outcome {
+    may_be_true: RowSelection,
+    may_be_false: RowSelection,
+}
+
+impl Outcome {
+    /// Returns an [`Outcome`] that may be true or false for any row
+    fn unknown(num_rows: usize) -> Self {
+        let all = RowSelection::from_consecutive_ranges(std::iter::once(0..num_rows), num_rows);
+        Self {
+            may_be_true: all.clone(),
+            may_be_false: all,
+        }
+    }
+
+    /// Returns an [`Outcome`] that is neither true nor false for any row
+    fn none(num_rows: usize) -> Self {
+        let none = RowSelection::from_consecutive_ranges(std::iter::empty(), num_rows);
+        Self {
+            may_be_true: none.clone(),
+            may_be_false: none,
+        }
+    }
+}
+
+/// The page index of a row group
+struct RowGroupContext<'a> {
+    schema: &'a SchemaDescriptor,
+    num_rows: usize,
+    column_index: Option<&'a [Index]>,
+    offset_index: Option<&'a [OffsetIndexMetaData]>,
+}
+
+impl RowGroupContext<'_> {
+    /// Evaluates a comparison with `values` on the leaf `column`, with `f` returning
+    /// whether the comparison may be true and may be false for the non-null values of
+    /// a page
+    fn evaluate_leaf(
+        &self,
+        column: &ColumnPath,
+        values: &ArrayRef,
+        f: impl Fn(&PageBounds, &[PhysicalValue], SortOrder) -> (bool, bool),
+    ) -> Result<Outcome> {
+        let column_idx = self.column_idx(column)?;
+        let descr = self.schema.column(column_idx);
+        let values = match PhysicalValue::try_from_array(values, &descr)? {
+            Some(values) => values,
+            None => return Ok(Outcome::unknown(self.num_rows)),
+        };
+        let sort_order = descr.sort_order();
+
+        self.evaluate_pages(column_idx, &descr, |page| match page.all_null() {
+            // Comparisons with null are neither true nor false
+            true => (false, false),
+            false => f(page, &values, sort_order),
+        })
+    }
+
+    /// Evaluates `column IS NULL`, or `column IS NOT NULL` if `negated`
+    fn evaluate_nulls(&self, column: &ColumnPath, negated: bool) -> Result<Outcome> {
+        let column_idx = self.column_idx(column)?;
+        let descr = self.schema.column(column_idx);
+        self.evaluate_pages(column_idx, &descr, |page| {
+            let may_contain_null = page.null_count != Some(0);
+            let may_contain_value = !page.all_null();
+            match negated {
+                false => (may_contain_null, may_contain_value),
+                true => (may_contain_value, may_contain_null),
+            }
+        })
+    }
+
+    fn column_idx(&self, column: &ColumnPath) -> Result<usize> {
+        self.schema
+            .columns()
+            .iter()
+            .position(|c| c.path() == column)
+            .ok_or_else(|| general_err!("leaf column {} not found", column))
+    }
+
+    /// Computes an [`Outcome`] from `f`, which returns whether a predicate may be true
+    /// and may be false for the rows of a page
+    fn evaluate_pages(
+        &self,
+        column_idx: usize,
+        descr: &ColumnDescriptor,
+        f: impl Fn(&PageBounds) -> (bool, bool),
+    ) -> Result<Outcome> {
+        let (index, locations) = match (self.column_index, self.offset_index) {
+            (Some(index), Some(offsets)) => {
+                (&index[column_idx], &offsets[column_idx].page_locations)
+            }
+            _ => return Ok(Outcome::unknown(self.num_rows)),
+        };
+        let pages = match PageBounds::try_from_index(index) {
+            Some(pages) if pages.len() == locations.len() => pages,
+            _ => return Ok(Outcome::unknown(self.num_rows)),
+        };
+
+        // Pages of repeated columns begin at row boundaries, but a row may contain
+        // a mix of values for which the predicate is true and false
+        let repeated = descr.max_rep_level() > 0;
+
+        let mut may_be_true: Vec<Range<usize>> = vec![];
+        let mut may_be_false: Vec<Range<usize>> = vec![];
+        for (idx, page) in pages.iter().enumerate() {
+            let start = locations[idx].first_row_index as usize;
+            let end = match locations.get(idx + 1) {
+                Some(next) => next.first_row_index as usize,
+                None => self.num_rows,
+            };
+            let (t, f) = f(page);
+            if t {
+                may_be_true.push(start..end);
+            }
+            if f || repeated {
+                may_be_false.push(start..end);
+            }
+        }
+
+        Ok(Outcome {
+            may_be_true: RowSelection::from_consecutive_ranges(
+                may_be_true.into_iter(),
+                self.num_rows,
+            ),
+            may_be_false: RowSelection::from_consecutive_ranges(
+                may_be_false.into_iter(),
+                self.num_rows,
+            ),
+        })
+    }
+}
+
+/// The statistics of a page from the column index
+struct PageBounds {
+    /// The minimum and maximum non-null values, `None` if the page only contains nulls
+    min_max: Option<(PhysicalValue, PhysicalValue)>,
+    null_count: Option<i64>,
+}
+
+impl PageBounds {
+    fn all_null(&self) -> bool {
+        self.min_max.is_none()
+    }
+
+    /// Returns the [`PageBounds`] of the pages in `index`, or `None` if not available
+    fn try_from_index(index: &Index) -> Option<Vec<Self>> {
+        fn convert<T: ParquetValueType>(
+            index: &NativeIndex<T>,
+            f: impl Fn(&T) -> PhysicalValue,
+        ) -> Vec<PageBounds> {
+            index
+                .indexes
+                .iter()
+                .map(|page: &PageIndex<T>| PageBounds {
+                    min_max: page
+                        .min()
+                        .zip(page.max())
+                        .map(|(min, max)| (f(min), f(max))),
+                    null_count: page.null_count(),
+                })
+                .collect()
+        }
+
+        Some(match index {
+            Index::NONE | Index::INT96(_) => return None,
+            Index::BOOLEAN(i) => convert(i, |v| PhysicalValue::Boolean(*v)),
+            Index::INT32(i) => convert(i, |v| PhysicalValue::Int32(*v)),
+            Index::INT64(i) => convert(i, |v| PhysicalValue::Int64(*v)),
+            Index::FLOAT(i) => convert(i, |v| PhysicalValue::Float(*v)),
+            Index::DOUBLE(i) => convert(i, |v| PhysicalValue::Double(*v)),
+            Index::BYTE_ARRAY(i) => convert(i, |v| PhysicalValue::Bytes(v.data().to_vec())),
+            Index::FIXED_LEN_BYTE_ARRAY(i) => {
+                convert(i, |v| PhysicalValue::Bytes(v.data().to_vec()))
+            }
+        })
+    }
+}
+
+/// Returns whether `column <op> values[0]` may be true and may be false for a page
+/// that is not all null
+fn compare_page(
+    page: &PageBounds,
+    op: CompareOp,
+    values: &[PhysicalValue],
+    sort_order: SortOrder,
+) -> (bool, bool) {
+    // A value that can not be represented in the column type never compares equal,
+    // and its ordering is unknown
+    let value = match values.first() {
+        Some(value) => value,
+        None => match op {
+            CompareOp::Eq => return (false, true),
+            CompareOp::NotEq => return (true, false),
+            _ => return (true, true),
+        },
+    };
+    let (min, max) = page.min_max.as_ref().unwrap();
+    let min_cmp = min.compare(value, sort_order);
+    let max_cmp = max.compare(value, sort_order);
+
+    // Returns true if `cmp` is unknown or one of `expected`
+    let maybe = |cmp: Option<Ordering>, expected: &[Ordering]| match cmp {
+        Some(cmp) => expected.contains(&cmp),
+        None => true,
+    };
+    let all_equal = min_cmp == Some(Ordering::Equal) && max_cmp == Some(Ordering::Equal);
+    let within = value.may_be_within(min, max, sort_order);
+
+    use Ordering::*;
+    let (may_be_true, may_be_false) = match op {
+        CompareOp::Eq => (within, !all_equal),
+        CompareOp::NotEq => (!all_equal, within),
+        CompareOp::Lt => (maybe(min_cmp, &[Less]), maybe(max_cmp, &[Greater, Equal])),
+        CompareOp::LtEq => (maybe(min_cmp, &[Less, Equal]), maybe(max_cmp, &[Greater])),
+        CompareOp::Gt => (maybe(max_cmp, &[Greater]), maybe(min_cmp, &[Less, Equal])),
+        CompareOp::GtEq => (maybe(max_cmp, &[Greater, Equal]), maybe(min_cmp, &[Less])),
+    };
+
+    // A NaN, which is not included in the statistics, only satisfies `!=`
+    match may_contain_nan(min) {
+        true if op == CompareOp::NotEq => (true, may_be_false),
+        true => (may_be_true, true),
+        false => (may_be_true, may_be_false),
+    }
+}
+
+/// Returns true if a page with minimum `min` may contain `NaN` values, which are
+/// excluded from the statistics
+fn may_contain_nan(min: &PhysicalValue) -> bool {
+    matches!(min, PhysicalValue::Float(_) | PhysicalValue::Double(_))
+}
+
+/// Returns whether `column IN (values)` may be true and may be false for a page that
+/// is not all null
+fn in_list_page(
+    page: &PageBounds,
+    values: &[PhysicalValue],
+    sort_order: SortOrder,
+) -> (bool, bool) {
+    let (min, max) = page.min_max.as_ref().unwrap();
+    let may_be_true = values.iter().any(|v| v.may_be_within(min, max, sort_order));
+    let single_value = min.compare(max, sort_order) == Some(Ordering::Equal);
+    let all_match = single_value
+        && !may_contain_nan(min)
+        && values
+            .iter()
+            .any(|v| v.compare(min, sort_order) == Some(Ordering::Equal));
+    (may_be_true, !all_match)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrow::arrow_reader::{ArrowReaderOptions, ParquetRecordBatchReaderBuilder};
+    use crate::arrow::ArrowWriter;
+    use crate::file::properties::WriterProperties;
+    use arrow_array::types::Int32Type;
+    use arrow_array::{
+        Float64Array, Int32Array, Int64Array, ListArray, RecordBatch, StringArray,
+        TimestampMillisecondArray, TimestampNanosecondArray,
+    };
+    use bytes::Bytes;
+    use std::sync::Arc;
+
+    /// Returns a file with two row groups of 200 rows, and pages of 100 rows
+    ///
+    /// * `id` contains the row index, and nulls for the last 100 rows
+    /// * `tags` contains `[i, i + 1]` for row `i`
+    fn test_file() -> Bytes {
+        let id = Int32Array::from_iter((0..400).map(|i| (i < 300).then_some(i)));
+        let tags = ListArray::from_iter_primitive::<Int32Type, _, _>(
+            (0..400).map(|i| Some(vec![Some(i), Some(i + 1)])),
+        );
+        let batch = RecordBatch::try_from_iter([
+            ("id", Arc::new(id) as ArrayRef),
+            ("tags", Arc::new(tags) as ArrayRef),
+        ])
+        .unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(200)
+            .set_data_page_row_count_limit(100)
+            .set_write_batch_size(10)
+            .build();
+
+        let mut buf = Vec::with_capacity(1024);
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        Bytes::from(buf)
+    }
+
+    fn builder() -> ParquetRecordBatchReaderBuilder<Bytes> {
+        let options = ArrowReaderOptions::new().with_page_index(true);
+        ParquetRecordBatchReaderBuilder::try_new_with_options(test_file(), options).unwrap()
+    }
+
+    fn id(op: CompareOp, value: i32) -> PagePredicate {
+        PagePredicate::compare("id", op, Arc::new(Int32Array::from(vec![value])))
+    }
+
+    /// Returns the selected pages of 100 rows
+    fn pages(builder: &ParquetRecordBatchReaderBuilder<Bytes>, p: &PagePredicate) -> Vec<usize> {
+        let selection = builder.prune_pages(p).unwrap();
+        let mut pages = vec![];
+        let mut offset = 0;
+        for selector in selection.iter() {
+            assert_eq!(selector.row_count % 100, 0);
+            if !selector.skip {
+                pages.extend(offset / 100..(offset + selector.row_count) / 100);
+            }
+            offset += selector.row_count;
+        }
+        assert_eq!(offset, 400);
+        pages
+    }
+
+    #[test]
+    fn test_page_boundaries() {
+        let builder = builder();
+        let offset_index = builder.metadata().offset_index().unwrap();
+        for row_group in offset_index {
+            for column in row_group {
+                let rows: Vec<_> = column
+                    .page_locations
+                    .iter()
+                    .map(|x| x.first_row_index)
+                    .collect();
+                assert_eq!(rows, vec![0, 100]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let builder = builder();
+        assert_eq!(pages(&builder, &id(CompareOp::Eq, 150)), vec![1]);
+        assert_eq!(
+            pages(&builder, &id(CompareOp::Eq, 1000)),
+            Vec::<usize>::new()
+        );
+        assert_eq!(pages(&builder, &id(CompareOp::NotEq, 5)), vec![0, 1, 2]);
+        assert_eq!(pages(&builder, &id(CompareOp::Lt, 100)), vec![0]);
+        assert_eq!(pages(&builder, &id(CompareOp::LtEq, 100)), vec![0, 1]);
+        assert_eq!(pages(&builder, &id(CompareOp::Gt, 199)), vec![2]);
+        assert_eq!(pages(&builder, &id(CompareOp::GtEq, 199)), vec![1, 2]);
+
+        // Values are cast to the column type
+        let value = Arc::new(Int64Array::from(vec![250]));
+        let p = PagePredicate::compare("id", CompareOp::Eq, value);
+        assert_eq!(pages(&builder, &p), vec![2]);
+
+        // Comparisons with null never match
+        let value = Arc::new(Int32Array::from(vec![None]));
+        let p = PagePredicate::compare("id", CompareOp::NotEq, value);
+        assert_eq!(pages(&builder, &p), Vec::<usize>::new());
+        assert_eq!(pages(&builder, &p.negate()), Vec::<usize>::new());
+
+        let value = Arc::new(Int32Array::from(vec![1, 2]));
+        let err = builder
+            .prune_pages(&PagePredicate::compare("id", CompareOp::Eq, value))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parquet error: comparison with column \"id\" must have a single value, got 2"
+        );
+    }
+
+    #[test]
+    fn test_in_list() {
+        let builder = builder();
+        let values = Arc::new(Int32Array::from(vec![5, 250, 1000]));
+        let p = PagePredicate::in_list("id", values);
+        assert_eq!(pages(&builder, &p), vec![0, 2]);
+
+        // Values that can not be represented in the column type are ignored
+        let values = Arc::new(StringArray::from(vec!["120", "foo"]));
+        let p = PagePredicate::in_list("id", values);
+        assert_eq!(pages(&builder, &p), vec![1]);
+    }
+
+    #[test]
+    fn test_nulls() {
+        let builder = builder();
+        assert_eq!(pages(&builder, &PagePredicate::is_null("id")), vec![3]);
+        let p = PagePredicate::is_not_null("id");
+        assert_eq!(pages(&builder, &p), vec![0, 1, 2]);
+        assert_eq!(pages(&builder, &p.negate()), vec![3]);
+    }
+
+    #[test]
+    fn test_logical() {
+        let builder = builder();
+
+        // Pages that only contain nulls are neither true nor false
+        let p = id(CompareOp::Lt, 200).negate();
+        assert_eq!(pages(&builder, &p), vec![2]);
+
+        let p = id(CompareOp::Lt, 100)
+            .or(id(CompareOp::GtEq, 250))
+            .and(PagePredicate::is_not_null("id"));
+        assert_eq!(pages(&builder, &p), vec![0, 2]);
+
+        let p = id(CompareOp::GtEq, 150).and(id(CompareOp::Lt, 220));
+        assert_eq!(pages(&builder, &p), vec![1, 2]);
+
+        let p = id(CompareOp::Eq, 5).or(PagePredicate::is_null("id"));
+        assert_eq!(pages(&builder, &p), vec![0, 3]);
+        assert_eq!(pages(&builder, &p.negate()), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_nested() {
+        let builder = builder();
+        let value = Arc::new(Int32Array::from(vec![100]));
+        let column = ColumnPath::new(vec!["tags".into(), "list".into(), "item".into()]);
+        let p = PagePredicate::compare(column, CompareOp::Eq, value);
+        assert_eq!(pages(&builder, &p), vec![0, 1]);
+
+        // A row of a repeated column may contain both matching and non-matching values
+        assert_eq!(pages(&builder, &p.negate()), vec![0, 1, 2, 3]);
+
+        let err = builder
+            .prune_pages(&PagePredicate::is_null("tags"))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parquet error: leaf column \"tags\" not found"
+        );
+    }
+
+    #[test]
+    fn test_row_groups() {
+        let reader = builder().with_row_groups(vec![1]);
+        let selection = reader.prune_pages(&id(CompareOp::GtEq, 250)).unwrap();
+        let selectors: Vec<RowSelector> = selection.clone().into();
+        assert_eq!(
+            selectors,
+            vec![RowSelector::select(100), RowSelector::skip(100)]
+        );
+        assert_eq!(selection.row_count(), 100);
+
+        let batches = reader
+            .with_row_selection(selection)
+            .build()
+            .unwrap()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        let ids: Vec<_> = batches
+            .iter()
+            .flat_map(|b| {
+                b.column(0)
+                    .as_any()
+                    .downcast_ref::<Int32Array>()
+                    .unwrap()
+                    .iter()
+            })
+            .collect();
+        let expected: Vec<_> = (200..300).map(Some).collect();
+        assert_eq!(ids, expected);
+
+        let err = builder()
+            .with_row_groups(vec![2])
+            .prune_pages(&PagePredicate::is_null("id"));
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "Parquet error: row group 2 out of bounds 0..2"
+        );
+    }
+
+    #[test]
+    fn test_inexact_values() {
+        // Pages 0 and 1 contain `1`, and pages 2 and 3 contain `2`
+        let x = Int32Array::from_iter_values((0..400).map(|i| 1 + i / 200));
+        let t = TimestampMillisecondArray::from_iter_values((0..400).map(|i| 1000 + i / 200));
+        let batch = RecordBatch::try_from_iter([
+            ("x", Arc::new(x) as ArrayRef),
+            ("t", Arc::new(t) as ArrayRef),
+        ])
+        .unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(200)
+            .set_data_page_row_count_limit(100)
+            .set_write_batch_size(10)
+            .build();
+        let mut buf = Vec::with_capacity(1024);
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let options = ArrowReaderOptions::new().with_page_index(true);
+        let builder =
+            ParquetRecordBatchReaderBuilder::try_new_with_options(Bytes::from(buf), options)
+                .unwrap();
+
+        let all = vec![0, 1, 2, 3];
+        let none = Vec::<usize>::new();
+        let x = |op, v: f64| PagePredicate::compare("x", op, Arc::new(Float64Array::from(vec![v])));
+
+        // Values exactly representable in the column type are cast
+        assert_eq!(pages(&builder, &x(CompareOp::Lt, 2.)), vec![0, 1]);
+        assert_eq!(pages(&builder, &x(CompareOp::Eq, 2.)), vec![2, 3]);
+
+        // `1.5` is never equal to an integer, and its ordering is unknown
+        assert_eq!(pages(&builder, &x(CompareOp::Eq, 1.5)), none);
+        assert_eq!(pages(&builder, &x(CompareOp::Eq, 1.5).negate()), all);
+        assert_eq!(pages(&builder, &x(CompareOp::NotEq, 1.5)), all);
+        assert_eq!(pages(&builder, &x(CompareOp::NotEq, 1.5).negate()), none);
+        assert_eq!(pages(&builder, &x(CompareOp::Lt, 1.5)), all);
+        assert_eq!(pages(&builder, &x(CompareOp::Lt, 1.5).negate()), all);
+        assert_eq!(pages(&builder, &x(CompareOp::GtEq, 1.5)), all);
+
+        let values = Arc::new(Float64Array::from(vec![1.5, 2.]));
+        let p = PagePredicate::in_list("x", values);
+        assert_eq!(pages(&builder, &p), vec![2, 3]);
+        assert_eq!(pages(&builder, &p.negate()), vec![0, 1]);
+
+        let p = PagePredicate::in_list("x", Arc::new(Float64Array::from(vec![1.5])));
+        assert_eq!(pages(&builder, &p), none);
+        assert_eq!(pages(&builder, &p.negate()), all);
+
+        // A timestamp with a finer unit than the column, 1000.5 milliseconds
+        let t = |op, v: i64| {
+            let value = TimestampNanosecondArray::from(vec![v]);
+            PagePredicate::compare("t", op, Arc::new(value))
+        };
+        assert_eq!(
+            pages(&builder, &t(CompareOp::Eq, 1_001_000_000)),
+            vec![2, 3]
+        );
+        assert_eq!(
+            pages(&builder, &t(CompareOp::Lt, 1_001_000_000)),
+            vec![0, 1]
+        );
+        assert_eq!(pages(&builder, &t(CompareOp::Eq, 1_000_500_000)), none);
+        assert_eq!(
+            pages(&builder, &t(CompareOp::Eq, 1_000_500_000).negate()),
+            all
+        );
+        assert_eq!(pages(&builder, &t(CompareOp::Lt, 1_000_500_000)), all);
+        assert_eq!(pages(&builder, &t(CompareOp::Gt, 1_000_500_000)), all);
+
+        let values = TimestampNanosecondArray::from(vec![1_000_500_000]);
+        let p = PagePredicate::in_list("t", Arc::new(values));
+        assert_eq!(pages(&builder, &p), none);
+        assert_eq!(pages(&builder, &p.negate()), all);
+    }
+
+    #[test]
+    fn test_nan() {
+        // The first page contains `NaN`, which is excluded from its statistics of `1..=1`
+        let values = (0..400).map(|i| match i {
+            99 => f64::NAN,
+            0..=199 => 1.,
+            _ => 2.,
+        });
+        let batch = RecordBatch::try_from_iter([(
+            "v",
+            Arc::new(Float64Array::from_iter_values(values)) as ArrayRef,
+        )])
+        .unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(200)
+            .set_data_page_row_count_limit(100)
+            .set_write_batch_size(10)
+            .build();
+        let mut buf = Vec::with_capacity(1024);
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let options = ArrowReaderOptions::new().with_page_index(true);
+        let builder =
+            ParquetRecordBatchReaderBuilder::try_new_with_options(Bytes::from(buf), options)
+                .unwrap();
+
+        let v = |op| PagePredicate::compare("v", op, Arc::new(Float64Array::from(vec![1.])));
+        assert_eq!(pages(&builder, &v(CompareOp::Eq)), vec![0, 1]);
+        assert_eq!(pages(&builder, &v(CompareOp::NotEq).negate()), vec![0, 1]);
+        assert_eq!(pages(&builder, &v(CompareOp::Gt)), vec![2, 3]);
+
+        // Any page of a floating point column may contain `NaN`
+        let all = vec![0, 1, 2, 3];
+        assert_eq!(pages(&builder, &v(CompareOp::NotEq)), all);
+        assert_eq!(pages(&builder, &v(CompareOp::Eq).negate()), all);
+        assert_eq!(pages(&builder, &v(CompareOp::LtEq).negate()), all);
+
+        let p = PagePredicate::in_list("v", Arc::new(Float64Array::from(vec![1., 3.])));
+        assert_eq!(pages(&builder, &p), vec![0, 1]);
+        assert_eq!(pages(&builder, &p.negate()), all);
+    }
+
+    #[test]
+    fn test_without_page_index() {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(test_file()).unwrap();
+        assert_eq!(pages(&builder, &id(CompareOp::Eq, 150)), vec![0, 1, 2, 3]);
+    }
+}
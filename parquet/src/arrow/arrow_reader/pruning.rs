@@ -0,0 +1,572 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Row group pruning with equality predicates, see [`EqualityPredicate`]
+
+use std::cmp::Ordering;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float32Type, Float64Type, Int32Type, Int64Type, UInt32Type, UInt64Type};
+use arrow_array::{Array, ArrayRef, BooleanArray};
+use arrow_cast::{can_cast_types, cast};
+use arrow_schema::DataType;
+use arrow_select::nullif::nullif;
+
+use crate::arrow::schema::parquet_to_arrow_field;
+use crate::basic::{SortOrder, Type as PhysicalType};
+use crate::bloom_filter::Sbbf;
+use crate::errors::{ParquetError, Result};
+use crate::file::metadata::{ColumnChunkMetaData, ParquetMetaData};
+use crate::file::statistics::Statistics;
+use crate::schema::types::{ColumnDescriptor, ColumnPath, SchemaDescriptor};
+
+/// An equality predicate on a leaf column, matching rows where the column is equal
+/// to any of a list of values
+///
+/// A single value corresponds to `column = value`, and multiple values to
+/// `column IN (values)`. Null values never compare equal and are ignored.
+///
+/// Equality predicates can be used to skip row groups that cannot contain any
+/// matching rows, based on their statistics and bloom filters, see
+/// [`ParquetRecordBatchReaderBuilder::prune_row_groups`] and
+/// [`ParquetRecordBatchStreamBuilder::prune_row_groups`]
+///
+/// [`ParquetRecordBatchReaderBuilder::prune_row_groups`]: crate::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::prune_row_groups
+/// [`ParquetRecordBatchStreamBuilder::prune_row_groups`]: crate::arrow::async_reader::ParquetRecordBatchStreamBuilder::prune_row_groups
+#[derive(Debug, Clone)]
+pub struct EqualityPredicate {
+    column: ColumnPath,
+    values: ArrayRef,
+}
+
+impl EqualityPredicate {
+    /// Create a new [`EqualityPredicate`] matching rows where the leaf column at
+    /// `column` is equal to any of `values`
+    ///
+    /// `values` are cast to the arrow type of the parquet column, values that can not
+    /// be exactly represented in this type can not match any row and are ignored.
+    ///
+    /// Predicates on columns whose physical representation does not permit equality
+    /// comparisons, such as decimals, `INT96` and half-precision floats, are accepted
+    /// but never prune row groups
+    pub fn new(column: impl Into<ColumnPath>, values: ArrayRef) -> Self {
+        Self {
+            column: column.into(),
+            values,
+        }
+    }
+
+    /// Returns the path of the leaf column this predicate applies to
+    pub fn column(&self) -> &ColumnPath {
+        &self.column
+    }
+
+    /// Returns the values this predicate matches
+    pub fn values(&self) -> &ArrayRef {
+        &self.values
+    }
+}
+
+/// A non-null value in the physical representation of a parquet column
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum PhysicalValue {
+    Boolean(bool),
+    Int32(i32),
+    Int64(i64),
+    Float(f32),
+    Double(f64),
+    Bytes(Vec<u8>),
+}
+
+impl PhysicalValue {
+    /// Converts the non-null `values` to the physical representation of the column
+    /// described by `descr`, returning `None` if this is not supported
+    ///
+    /// `values` are first cast to the arrow type of the column, values that can not
+    /// be exactly represented in this type, such as `1.5` for an integer column, are
+    /// ignored
+    pub(super) fn try_from_array(
+        values: &ArrayRef,
+        descr: &ColumnDescriptor,
+    ) -> Result<Option<Vec<Self>>> {
+        let field = parquet_to_arrow_field(descr)?;
+        let unsigned = match field.data_type() {
+            DataType::Boolean
+            | DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Date32
+            | DataType::Time32(_)
+            | DataType::Time64(_)
+            | DataType::Timestamp(_, _)
+            | DataType::Utf8
+            | DataType::Binary
+            | DataType::FixedSizeBinary(_) => false,
+            DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => true,
+            _ => return Ok(None),
+        };
+
+        let values = cast_exact(values, field.data_type())?;
+        Ok(Some(match descr.physical_type() {
+            PhysicalType::BOOLEAN => values
+                .as_boolean()
+                .iter()
+                .flatten()
+                .map(Self::Boolean)
+                .collect(),
+            PhysicalType::INT32 if unsigned => {
+                let values = cast(&values, &DataType::UInt32)?;
+                let values = values.as_primitive::<UInt32Type>().iter().flatten();
+                values.map(|v| Self::Int32(v as i32)).collect()
+            }
+            PhysicalType::INT32 => {
+                let values = cast(&values, &DataType::Int32)?;
+                let values = values.as_primitive::<Int32Type>().iter().flatten();
+                values.map(Self::Int32).collect()
+            }
+            PhysicalType::INT64 if unsigned => {
+                let values = cast(&values, &DataType::UInt64)?;
+                let values = values.as_primitive::<UInt64Type>().iter().flatten();
+                values.map(|v| Self::Int64(v as i64)).collect()
+            }
+            PhysicalType::INT64 => {
+                let values = cast(&values, &DataType::Int64)?;
+                let values = values.as_primitive::<Int64Type>().iter().flatten();
+                values.map(Self::Int64).collect()
+            }
+            PhysicalType::FLOAT => {
+                let values = values.as_primitive::<Float32Type>().iter().flatten();
+                values.map(Self::Float).collect()
+            }
+            PhysicalType::DOUBLE => {
+                let values = values.as_primitive::<Float64Type>().iter().flatten();
+                values.map(Self::Double).collect()
+            }
+            PhysicalType::BYTE_ARRAY => {
+                let values = cast(&values, &DataType::Binary)?;
+                let values = values.as_binary::<i32>().iter().flatten();
+                values.map(|v| Self::Bytes(v.to_vec())).collect()
+            }
+            PhysicalType::FIXED_LEN_BYTE_ARRAY => {
+                let values = values.as_fixed_size_binary().iter().flatten();
+                values.map(|v| Self::Bytes(v.to_vec())).collect()
+            }
+            PhysicalType::INT96 => return Ok(None),
+        }))
+    }
+
+    /// Returns the minimum and maximum of `stats`, if present and usable with
+    /// `sort_order`
+    fn try_from_statistics(stats: &Statistics, sort_order: SortOrder) -> Option<(Self, Self)> {
+        // The deprecated min and max fields were written with signed comparison
+        // regardless of the sort order of the column
+        if stats.is_min_max_deprecated() && sort_order != SortOrder::SIGNED {
+            return None;
+        }
+        match stats {
+            Statistics::Boolean(s) => {
+                Some((Self::Boolean(*s.min_opt()?), Self::Boolean(*s.max_opt()?)))
+            }
+            Statistics::Int32(s) => Some((Self::Int32(*s.min_opt()?), Self::Int32(*s.max_opt()?))),
+            Statistics::Int64(s) => Some((Self::Int64(*s.min_opt()?), Self::Int64(*s.max_opt()?))),
+            Statistics::Float(s) => Some((Self::Float(*s.min_opt()?), Self::Float(*s.max_opt()?))),
+            Statistics::Double(s) => {
+                Some((Self::Double(*s.min_opt()?), Self::Double(*s.max_opt()?)))
+            }
+            Statistics::ByteArray(s) => Some((
+                Self::Bytes(s.min_opt()?.data().to_vec()),
+                Self::Bytes(s.max_opt()?.data().to_vec()),
+            )),
+            Statistics::FixedLenByteArray(s) => Some((
+                Self::Bytes(s.min_opt()?.data().to_vec()),
+                Self::Bytes(s.max_opt()?.data().to_vec()),
+            )),
+            Statistics::Int96(_) => None,
+        }
+    }
+
+    /// Compares `self` to `other` using `sort_order`, returning `None` if the values
+    /// have different physical types or can not be compared, such as `NaN`
+    pub(super) fn compare(&self, other: &Self, sort_order: SortOrder) -> Option<Ordering> {
+        let unsigned = match sort_order {
+            SortOrder::SIGNED => false,
+            SortOrder::UNSIGNED => true,
+            SortOrder::UNDEFINED => return None,
+        };
+        match (self, other) {
+            (Self::Boolean(a), Self::Boolean(b)) => a.partial_cmp(b),
+            (Self::Int32(a), Self::Int32(b)) if unsigned => (*a as u32).partial_cmp(&(*b as u32)),
+            (Self::Int32(a), Self::Int32(b)) => a.partial_cmp(b),
+            (Self::Int64(a), Self::Int64(b)) if unsigned => (*a as u64).partial_cmp(&(*b as u64)),
+            (Self::Int64(a), Self::Int64(b)) => a.partial_cmp(b),
+            (Self::Float(a), Self::Float(b)) => a.partial_cmp(b),
+            (Self::Double(a), Self::Double(b)) => a.partial_cmp(b),
+            (Self::Bytes(a), Self::Bytes(b)) if unsigned => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+
+    /// Returns false if `self` is definitely not within `min..=max`
+    pub(super) fn may_be_within(&self, min: &Self, max: &Self, sort_order: SortOrder) -> bool {
+        !matches!(self.compare(min, sort_order), Some(Ordering::Less))
+            && !matches!(self.compare(max, sort_order), Some(Ordering::Greater))
+    }
+
+    /// Returns false if `bloom_filter` shows that `self` is not present
+    fn check_bloom_filter(&self, bloom_filter: &Sbbf) -> bool {
+        match self {
+            Self::Boolean(v) => bloom_filter.check(v),
+            Self::Int32(v) => bloom_filter.check(v),
+            Self::Int64(v) => bloom_filter.check(v),
+            // Zeros of either sign are equal but hash differently
+            Self::Float(v) if *v == 0. => bloom_filter.check(&0_f32) || bloom_filter.check(&-0_f32),
+            Self::Float(v) => bloom_filter.check(v),
+            Self::Double(v) if *v == 0. => {
+                bloom_filter.check(&0_f64) || bloom_filter.check(&-0_f64)
+            }
+            Self::Double(v) => bloom_filter.check(v),
+            Self::Bytes(v) => bloom_filter.check(v),
+        }
+    }
+}
+
+/// Casts `values` to `to_type`, with values that can not be exactly represented in
+/// `to_type` set to null
+///
+/// A value is exact if casting it back to its original type yields the same value.
+/// Strings are parsed, and so are not checked, as formatting the parsed value need not
+/// produce the original string
+fn cast_exact(values: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
+    let cast_values = cast(values, to_type)?;
+    let from_type = values.data_type();
+    let parsed = matches!(
+        from_type,
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+    );
+    if parsed || from_type == to_type || !can_cast_types(to_type, from_type) {
+        return Ok(cast_values);
+    }
+
+    let round_trip = cast(&cast_values, from_type)?.to_data();
+    let original = values.to_data();
+    let inexact: BooleanArray = (0..values.len())
+        .map(|i| Some(original.slice(i, 1) != round_trip.slice(i, 1)))
+        .collect();
+    Ok(nullif(&cast_values, &inexact)?)
+}
+
+/// An [`EqualityPredicate`] resolved against the schema of a parquet file
+#[derive(Debug)]
+struct ResolvedPredicate {
+    /// The index of the leaf column
+    column_idx: usize,
+    /// The sort order of the column's statistics
+    sort_order: SortOrder,
+    /// The non-null values to match
+    values: Vec<PhysicalValue>,
+}
+
+impl ResolvedPredicate {
+    /// Returns false if the statistics of `column` show that it does not contain any
+    /// of the values
+    fn check_statistics(&self, column: &ColumnChunkMetaData) -> bool {
+        if self.values.is_empty() {
+            return false;
+        }
+
+        let stats = match column.statistics() {
+            Some(stats) => stats,
+            None => return true,
+        };
+
+        // A column chunk containing only nulls can not match
+        if stats.null_count_opt() == Some(column.num_values() as u64) {
+            return false;
+        }
+
+        match PhysicalValue::try_from_statistics(stats, self.sort_order) {
+            Some((min, max)) => self
+                .values
+                .iter()
+                .any(|v| v.may_be_within(&min, &max, self.sort_order)),
+            None => true,
+        }
+    }
+
+    /// Returns false if `bloom_filter` shows that the column does not contain any
+    /// of the values
+    fn check_bloom_filter(&self, bloom_filter: &Sbbf) -> bool {
+        self.values
+            .iter()
+            .any(|v| v.check_bloom_filter(bloom_filter))
+    }
+}
+
+/// Prunes row groups using a conjunction of [`EqualityPredicate`]s
+///
+/// Statistics are stored in the [`ParquetMetaData`] and are checked first, with bloom
+/// filters, which require additional IO, then checked for the remaining row groups
+#[derive(Debug)]
+pub(crate) struct RowGroupPruner {
+    predicates: Vec<ResolvedPredicate>,
+}
+
+impl RowGroupPruner {
+    /// Resolve `predicates` against `schema`
+    pub(crate) fn try_new(
+        schema: &SchemaDescriptor,
+        predicates: &[EqualityPredicate],
+    ) -> Result<Self> {
+        let mut resolved = Vec::with_capacity(predicates.len());
+        for predicate in predicates {
+            let column_idx = schema
+                .columns()
+                .iter()
+                .position(|c| c.path() == predicate.column())
+                .ok_or_else(|| general_err!("leaf column {} not found", predicate.column()))?;
+
+            let descr = schema.column(column_idx);
+            if let Some(values) = PhysicalValue::try_from_array(predicate.values(), &descr)? {
+                resolved.push(ResolvedPredicate {
+                    column_idx,
+                    sort_order: descr.sort_order(),
+                    values,
+                });
+            }
+        }
+        Ok(Self {
+            predicates: resolved,
+        })
+    }
+
+    /// Returns the subset of `row_groups` whose statistics show they may contain rows
+    /// matching all the predicates, or all row groups if `row_groups` is `None`
+    pub(crate) fn prune_by_statistics(
+        &self,
+        metadata: &ParquetMetaData,
+        row_groups: Option<&[usize]>,
+    ) -> Result<Vec<usize>> {
+        let num_row_groups = metadata.num_row_groups();
+        let row_groups = match row_groups {
+            Some(row_groups) => {
+                if let Some(idx) = row_groups.iter().find(|x| **x >= num_row_groups) {
+                    return Err(general_err!(
+                        "row group {} out of bounds 0..{}",
+                        idx,
+                        num_row_groups
+                    ));
+                }
+                row_groups.to_vec()
+            }
+            None => (0..num_row_groups).collect(),
+        };
+
+        Ok(row_groups
+            .into_iter()
+            .filter(|idx| {
+                let row_group = metadata.row_group(*idx);
+                self.predicates
+                    .iter()
+                    .all(|p| p.check_statistics(row_group.column(p.column_idx)))
+            })
+            .collect())
+    }
+
+    /// Returns the leaf columns whose bloom filters should be checked with
+    /// [`Self::check_bloom_filter`]
+    pub(crate) fn bloom_filter_columns(&self) -> Vec<usize> {
+        let mut columns: Vec<_> = self.predicates.iter().map(|p| p.column_idx).collect();
+        columns.sort_unstable();
+        columns.dedup();
+        columns
+    }
+
+    /// Returns false if `bloom_filter`, the bloom filter for the leaf column `column_idx`
+    /// within a row group, shows that the row group contains no matching rows
+    pub(crate) fn check_bloom_filter(&self, column_idx: usize, bloom_filter: &Sbbf) -> bool {
+        self.predicates
+            .iter()
+            .filter(|p| p.column_idx == column_idx)
+            .all(|p| p.check_bloom_filter(bloom_filter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use crate::arrow::ArrowWriter;
+    use crate::file::properties::WriterProperties;
+    use arrow_array::{
+        Float64Array, Int32Array, Int64Array, RecordBatch, StringArray, UInt32Array,
+    };
+    use bytes::Bytes;
+    use std::sync::Arc;
+
+    /// Returns a file with 4 row groups of 100 rows, with `id` and `u` increasing, and
+    /// `name` a permutation of unique values spread across all row groups
+    fn test_file() -> Bytes {
+        let id = Int32Array::from_iter_values(0..400);
+        let u = UInt32Array::from_iter_values((0..400).map(|i| i * 10_000_000));
+        let name =
+            StringArray::from_iter_values((0..400).map(|i| format!("name-{}", (i * 7919) % 400)));
+        let batch = RecordBatch::try_from_iter([
+            ("id", Arc::new(id) as ArrayRef),
+            ("u", Arc::new(u) as ArrayRef),
+            ("name", Arc::new(name) as ArrayRef),
+        ])
+        .unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(100)
+            .set_bloom_filter_enabled(true)
+            .set_bloom_filter_ndv(100)
+            .set_bloom_filter_fpp(0.001)
+            .build();
+
+        let mut buf = Vec::with_capacity(1024);
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        Bytes::from(buf)
+    }
+
+    fn prune(
+        builder: &ParquetRecordBatchReaderBuilder<Bytes>,
+        predicates: &[EqualityPredicate],
+    ) -> Vec<usize> {
+        builder.prune_row_groups(predicates).unwrap()
+    }
+
+    #[test]
+    fn test_prune_statistics() {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(test_file()).unwrap();
+        assert_eq!(builder.metadata().num_row_groups(), 4);
+
+        // Values are cast to the column type
+        let ids = Arc::new(Int64Array::from(vec![5, 250]));
+        assert_eq!(
+            prune(&builder, &[EqualityPredicate::new("id", ids)]),
+            vec![0, 2]
+        );
+
+        let ids = Arc::new(Int32Array::from(vec![1000, -1]));
+        assert!(prune(&builder, &[EqualityPredicate::new("id", ids)]).is_empty());
+
+        // Values that are not exactly representable in the column type never match
+        let ids = Arc::new(Float64Array::from(vec![5.5, 250.]));
+        assert_eq!(
+            prune(&builder, &[EqualityPredicate::new("id", ids)]),
+            vec![2]
+        );
+
+        // Nulls never match
+        let ids = Arc::new(Int32Array::from(vec![None]));
+        assert!(prune(&builder, &[EqualityPredicate::new("id", ids)]).is_empty());
+
+        // Unsigned values are compared with unsigned ordering
+        let u = Arc::new(UInt32Array::from(vec![2_100_000_000, 3_990_000_000]));
+        assert_eq!(
+            prune(&builder, &[EqualityPredicate::new("u", u)]),
+            vec![2, 3]
+        );
+
+        // Only row groups already selected are considered
+        let ids = Arc::new(Int32Array::from(vec![5, 250]));
+        let builder = builder.with_row_groups(vec![1, 2]);
+        assert_eq!(
+            prune(&builder, &[EqualityPredicate::new("id", ids)]),
+            vec![2]
+        );
+    }
+
+    #[test]
+    fn test_prune_bloom_filter() {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(test_file()).unwrap();
+
+        // Statistics for name overlap, only the bloom filters can exclude row groups
+        let names = Arc::new(StringArray::from(vec!["name-150"]));
+        let predicate = EqualityPredicate::new("name", names);
+        assert_eq!(prune(&builder, std::slice::from_ref(&predicate)), vec![2]);
+
+        let names = Arc::new(StringArray::from(vec!["name-150", "name-400"]));
+        assert_eq!(
+            prune(&builder, &[EqualityPredicate::new("name", names)]),
+            vec![2]
+        );
+
+        // Predicates are combined with AND
+        let ids = Arc::new(Int32Array::from(vec![5, 250]));
+        let predicates = [EqualityPredicate::new("id", ids), predicate.clone()];
+        assert_eq!(prune(&builder, &predicates), vec![2]);
+
+        let ids = Arc::new(Int32Array::from(vec![5]));
+        let predicates = [EqualityPredicate::new("id", ids), predicate];
+        assert!(prune(&builder, &predicates).is_empty());
+
+        let row_groups = prune(&builder, &predicates[1..]);
+        let batches = builder
+            .with_row_groups(row_groups)
+            .build()
+            .unwrap()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        let ids = batches[0].column(0).as_primitive::<Int32Type>();
+        assert_eq!(ids.value(0), 200);
+        assert_eq!(ids.len(), 100);
+    }
+
+    #[test]
+    fn test_prune_float_zero() {
+        let f = Float64Array::from(vec![-1., -0., 1., -1., 0., 1.]);
+        let batch = RecordBatch::try_from_iter([("f", Arc::new(f) as ArrayRef)]).unwrap();
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(3)
+            .set_bloom_filter_enabled(true)
+            .build();
+        let mut buf = Vec::with_capacity(1024);
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        // Zeros match regardless of sign
+        let builder = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(buf)).unwrap();
+        for zero in [0., -0.] {
+            let values = Arc::new(Float64Array::from(vec![zero]));
+            assert_eq!(
+                prune(&builder, &[EqualityPredicate::new("f", values)]),
+                vec![0, 1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_prune_unknown_column() {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(test_file()).unwrap();
+        let values = Arc::new(Int32Array::from(vec![1]));
+        let err = builder
+            .prune_row_groups(&[EqualityPredicate::new("missing", values)])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parquet error: leaf column \"missing\" not found"
+        );
+    }
+}
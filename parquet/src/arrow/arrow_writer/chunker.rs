@@ -0,0 +1,324 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Content-defined chunking of data pages, see [`CdcOptions`]
+
+use arrow_array::cast::AsArray;
+use arrow_array::{Array, ArrayRef, BinaryViewArray, FixedSizeBinaryArray};
+use arrow_buffer::{BooleanBuffer, Buffer, OffsetBuffer};
+use arrow_schema::DataType;
+
+use crate::arrow::arrow_writer::levels::ArrayLevels;
+use crate::errors::{ParquetError, Result};
+use crate::file::properties::CdcOptions;
+
+/// The table of the gear hash, generated with splitmix64
+///
+/// This must not change, as it determines the boundaries of chunks
+const GEAR: [u64; 256] = {
+    let mut table = [0; 256];
+    let mut state: u64 = 0;
+    let mut i = 0;
+    while i < 256 {
+        state = state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+};
+
+/// The position of a chunk boundary within an [`ArrayLevels`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ChunkBoundary {
+    /// The offset into the levels
+    pub levels: usize,
+    /// The offset into the non-null indices
+    pub values: usize,
+}
+
+/// Finds content-defined chunk boundaries in the values written to a column
+///
+/// A gear hash is computed over the repetition levels, definition levels and bytes of
+/// the non-null values. Once a chunk contains at least `min_chunk_size` bytes, a
+/// boundary is placed after the first value containing a byte at which the hash matches
+/// a mask, or when `max_chunk_size` is reached. Boundaries are only placed at the start
+/// of a row, so that pages begin at row boundaries.
+///
+/// As the hash only depends on the preceding 64 bytes, the boundaries following a
+/// modification realign with those of the unmodified data.
+#[derive(Debug)]
+pub(crate) struct ContentDefinedChunker {
+    min_chunk_size: usize,
+    max_chunk_size: usize,
+    mask: u64,
+    hash: u64,
+    chunk_size: usize,
+    /// The current chunk should end at the start of the next row
+    pending: bool,
+}
+
+impl ContentDefinedChunker {
+    pub fn new(options: &CdcOptions) -> Self {
+        let min_chunk_size = options.min_chunk_size;
+        let max_chunk_size = options.max_chunk_size.max(min_chunk_size + 1);
+
+        // A boundary is expected every 2^bits bytes after min_chunk_size
+        let target = ((max_chunk_size - min_chunk_size) / 2).max(2);
+        let bits = (target.ilog2() as i64 - options.norm_level as i64).clamp(1, 63);
+        let mask = u64::MAX << (64 - bits);
+
+        Self {
+            min_chunk_size,
+            max_chunk_size,
+            mask,
+            hash: 0,
+            chunk_size: 0,
+            pending: false,
+        }
+    }
+
+    /// Returns the chunk boundaries within `levels`, continuing from the previous call
+    pub fn boundaries(&mut self, levels: &ArrayLevels) -> Result<Vec<ChunkBoundary>> {
+        let values = ValueBytes::try_new(levels.array())?;
+        let def_levels = levels.def_levels();
+        let rep_levels = levels.rep_levels();
+        let max_def_level = levels.max_def_level();
+        let non_null_indices = levels.non_null_indices();
+
+        let mut boundaries = vec![];
+        let mut value_idx = 0;
+        for level_idx in 0..levels.num_levels() {
+            let rep_level = rep_levels.map(|x| x[level_idx]).unwrap_or(0);
+            if self.pending && rep_level == 0 {
+                boundaries.push(ChunkBoundary {
+                    levels: level_idx,
+                    values: value_idx,
+                });
+                self.hash = 0;
+                self.chunk_size = 0;
+                self.pending = false;
+            }
+
+            if rep_levels.is_some() {
+                self.roll(&rep_level.to_le_bytes());
+            }
+            let def_level = match def_levels {
+                Some(def_levels) => {
+                    self.roll(&def_levels[level_idx].to_le_bytes());
+                    def_levels[level_idx]
+                }
+                None => max_def_level,
+            };
+            if def_level == max_def_level {
+                values.with_value(non_null_indices[value_idx], |bytes| self.roll(bytes));
+                value_idx += 1;
+            }
+
+            if self.chunk_size >= self.max_chunk_size {
+                self.pending = true;
+            }
+        }
+        Ok(boundaries)
+    }
+
+    /// Rolls `bytes` into the hash, marking the chunk as complete if the hash matches
+    /// the mask at any byte past `min_chunk_size`
+    fn roll(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.hash = (self.hash << 1).wrapping_add(GEAR[*b as usize]);
+            self.chunk_size += 1;
+            if self.chunk_size >= self.min_chunk_size && self.hash & self.mask == 0 {
+                self.pending = true;
+            }
+        }
+    }
+}
+
+/// Provides the bytes of the logical values of an array
+enum ValueBytes {
+    Null,
+    Boolean(BooleanBuffer),
+    FixedWidth {
+        buffer: Buffer,
+        width: usize,
+    },
+    FixedSizeBinary(FixedSizeBinaryArray),
+    Offsets32 {
+        offsets: OffsetBuffer<i32>,
+        values: Buffer,
+    },
+    Offsets64 {
+        offsets: OffsetBuffer<i64>,
+        values: Buffer,
+    },
+    View(BinaryViewArray),
+    Dictionary {
+        keys: Vec<usize>,
+        values: Box<ValueBytes>,
+    },
+}
+
+impl ValueBytes {
+    fn try_new(array: &ArrayRef) -> Result<Self> {
+        Ok(match array.data_type() {
+            DataType::Null => Self::Null,
+            DataType::Boolean => Self::Boolean(array.as_boolean().values().clone()),
+            DataType::Utf8 => {
+                let array = array.as_string::<i32>();
+                Self::Offsets32 {
+                    offsets: array.offsets().clone(),
+                    values: array.values().clone(),
+                }
+            }
+            DataType::Binary => {
+                let array = array.as_binary::<i32>();
+                Self::Offsets32 {
+                    offsets: array.offsets().clone(),
+                    values: array.values().clone(),
+                }
+            }
+            DataType::LargeUtf8 => {
+                let array = array.as_string::<i64>();
+                Self::Offsets64 {
+                    offsets: array.offsets().clone(),
+                    values: array.values().clone(),
+                }
+            }
+            DataType::LargeBinary => {
+                let array = array.as_binary::<i64>();
+                Self::Offsets64 {
+                    offsets: array.offsets().clone(),
+                    values: array.values().clone(),
+                }
+            }
+            DataType::Utf8View => Self::View(array.as_string_view().clone().to_binary_view()),
+            DataType::BinaryView => Self::View(array.as_binary_view().clone()),
+            DataType::FixedSizeBinary(_) => {
+                Self::FixedSizeBinary(array.as_fixed_size_binary().clone())
+            }
+            DataType::Dictionary(_, _) => {
+                let dictionary = array.as_any_dictionary();
+                Self::Dictionary {
+                    keys: dictionary.normalized_keys(),
+                    values: Box::new(Self::try_new(dictionary.values())?),
+                }
+            }
+            d => match d.primitive_width() {
+                Some(width) => {
+                    let data = array.to_data();
+                    let buffer = data.buffers()[0]
+                        .slice_with_length(data.offset() * width, data.len() * width);
+                    Self::FixedWidth { buffer, width }
+                }
+                None => {
+                    return Err(nyi_err!(
+                        "content-defined chunking of {} columns is not supported",
+                        d
+                    ))
+                }
+            },
+        })
+    }
+
+    /// Calls `f` with the bytes of the value at `idx`
+    fn with_value(&self, idx: usize, f: impl FnOnce(&[u8])) {
+        match self {
+            Self::Null => f(&[]),
+            Self::Boolean(values) => f(&[values.value(idx) as u8]),
+            Self::FixedWidth { buffer, width } => f(&buffer[idx * width..(idx + 1) * width]),
+            Self::FixedSizeBinary(array) => f(array.value(idx)),
+            Self::Offsets32 { offsets, values } => {
+                let (start, end) = (offsets[idx] as usize, offsets[idx + 1] as usize);
+                f(&values[start..end])
+            }
+            Self::Offsets64 { offsets, values } => {
+                let (start, end) = (offsets[idx] as usize, offsets[idx + 1] as usize);
+                f(&values[start..end])
+            }
+            Self::View(array) => f(array.value(idx)),
+            Self::Dictionary { keys, values } => values.with_value(keys[idx], f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrow::arrow_writer::levels::calculate_array_levels;
+    use arrow_array::types::Int32Type;
+    use arrow_array::ListArray;
+    use arrow_schema::Field;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_boundaries() {
+        let list = ListArray::from_iter_primitive::<Int32Type, _, _>(
+            (0..1000)
+                .map(|i| (i % 9 != 0).then(|| (0..i % 5).map(move |j| (j != 2).then_some(i * j)))),
+        );
+        let field = Field::new("list", list.data_type().clone(), true);
+        let list = Arc::new(list) as ArrayRef;
+        let options = CdcOptions {
+            min_chunk_size: 64,
+            max_chunk_size: 256,
+            norm_level: 0,
+        };
+
+        let levels = calculate_array_levels(&list, &field).unwrap().remove(0);
+        let boundaries = ContentDefinedChunker::new(&options)
+            .boundaries(&levels)
+            .unwrap();
+        assert!(boundaries.len() > 10);
+
+        // Boundaries are at the start of a row
+        let rep_levels = levels.rep_levels().unwrap();
+        let offsets = list.as_list::<i32>().value_offsets();
+        for b in &boundaries {
+            assert_eq!(rep_levels[b.levels], 0);
+            let row = rep_levels[..b.levels].iter().filter(|r| **r == 0).count();
+            let prev = levels.non_null_indices()[..b.values].last().copied();
+            assert!(prev.map_or(true, |x| x < offsets[row] as usize));
+            let next = levels.non_null_indices().get(b.values).copied();
+            assert!(next.map_or(true, |x| x >= offsets[row] as usize));
+        }
+
+        // Boundaries do not depend on how the data is split into batches
+        let mut chunker = ContentDefinedChunker::new(&options);
+        let mut split = vec![];
+        let (mut num_levels, mut num_values) = (0, 0);
+        for offset in (0..1000).step_by(70) {
+            let slice = list.slice(offset, 70.min(1000 - offset));
+            let levels = calculate_array_levels(&slice, &field).unwrap().remove(0);
+            split.extend(
+                chunker
+                    .boundaries(&levels)
+                    .unwrap()
+                    .into_iter()
+                    .map(|b| ChunkBoundary {
+                        levels: b.levels + num_levels,
+                        values: b.values + num_values,
+                    }),
+            );
+            num_levels += levels.num_levels();
+            num_values += levels.non_null_indices().len();
+        }
+        assert_eq!(split, boundaries);
+    }
+}
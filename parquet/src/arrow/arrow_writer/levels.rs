@@ -40,11 +40,15 @@
 //!
 //! \[1\] [parquet-format#nested-encoding](https://github.com/apache/parquet-format#nested-encoding)
 
+use crate::arrow::schema::UNION_TYPE_ID_FIELD;
 use crate::errors::{ParquetError, Result};
 use arrow_array::cast::AsArray;
-use arrow_array::{Array, ArrayRef, OffsetSizeTrait};
-use arrow_buffer::{NullBuffer, OffsetBuffer};
-use arrow_schema::{DataType, Field};
+use arrow_array::{
+    Array, ArrayRef, GenericListArray, GenericListViewArray, Int8Array, OffsetSizeTrait,
+    StructArray, UInt32Array, UInt64Array, UnionArray,
+};
+use arrow_buffer::{NullBuffer, OffsetBuffer, ScalarBuffer};
+use arrow_schema::{DataType, Field, FieldRef, Fields, UnionMode};
 use std::ops::Range;
 use std::sync::Arc;
 
@@ -56,6 +60,87 @@ pub(crate) fn calculate_array_levels(array: &ArrayRef, field: &Field) -> Result<
     Ok(builder.finish())
 }
 
+/// Converts a list view to the equivalent list, copying each view's values so that
+/// they are contiguous and in order
+fn list_view_to_list<O: OffsetSizeTrait>(
+    array: &GenericListViewArray<O>,
+    field: &FieldRef,
+) -> Result<GenericListArray<O>> {
+    let mut indices = Vec::with_capacity(array.values().len());
+    let mut lengths = Vec::with_capacity(array.len());
+    for idx in 0..array.len() {
+        if array.is_null(idx) {
+            lengths.push(0);
+            continue;
+        }
+        let offset = array.value_offsets()[idx].as_usize();
+        let size = array.value_sizes()[idx].as_usize();
+        indices.extend((offset..offset + size).map(|x| x as u64));
+        lengths.push(size);
+    }
+    // Offsets of a large list view may not fit in u32
+    let indices: ArrayRef = if O::IS_LARGE {
+        Arc::new(UInt64Array::from(indices))
+    } else {
+        Arc::new(UInt32Array::from_iter_values(
+            indices.into_iter().map(|x| x as u32),
+        ))
+    };
+    let values = arrow_select::take::take(array.values().as_ref(), &indices, None)?;
+    let offsets = OffsetBuffer::from_lengths(lengths);
+    let nulls = array.nulls().cloned();
+    Ok(GenericListArray::try_new(
+        field.clone(),
+        offsets,
+        values,
+        nulls,
+    )?)
+}
+
+/// Converts a union to the struct it is written as, containing the type id of each
+/// slot followed by one nullable child per variant, only valid where it is selected
+fn union_to_struct(array: &UnionArray) -> Result<StructArray> {
+    let (fields, mode) = match array.data_type() {
+        DataType::Union(fields, mode) => (fields, mode),
+        _ => unreachable!(),
+    };
+    let type_ids = array.type_ids();
+
+    let mut struct_fields = Vec::with_capacity(fields.len() + 1);
+    let mut columns = Vec::with_capacity(fields.len() + 1);
+    struct_fields.push(Arc::new(Field::new(
+        UNION_TYPE_ID_FIELD,
+        DataType::Int8,
+        false,
+    )));
+    columns.push(Arc::new(Int8Array::new(type_ids.clone(), None)) as ArrayRef);
+
+    for (type_id, field) in fields.iter() {
+        let indices: UInt32Array = match mode {
+            UnionMode::Sparse => (0..array.len())
+                .map(|idx| (type_ids[idx] == type_id).then_some(idx as u32))
+                .collect(),
+            UnionMode::Dense => {
+                let offsets: &ScalarBuffer<i32> = array.offsets().unwrap();
+                type_ids
+                    .iter()
+                    .zip(offsets.iter())
+                    .map(|(t, o)| (*t == type_id).then_some(*o as u32))
+                    .collect()
+            }
+        };
+        let child = arrow_select::take::take(array.child(type_id).as_ref(), &indices, None)?;
+        struct_fields.push(Arc::new(field.as_ref().clone().with_nullable(true)));
+        columns.push(child);
+    }
+
+    Ok(StructArray::try_new(
+        Fields::from(struct_fields),
+        columns,
+        None,
+    )?)
+}
+
 /// Returns true if the DataType can be represented as a primitive parquet column,
 /// i.e. a leaf array with no children
 fn is_leaf(data_type: &DataType) -> bool {
@@ -219,6 +304,29 @@ impl LevelInfoBuilder {
                     _ => unreachable!(),
                 })
             }
+            DataType::ListView(child) => {
+                let list = array
+                    .as_any()
+                    .downcast_ref::<GenericListViewArray<i32>>()
+                    .unwrap();
+                let list: ArrayRef = Arc::new(list_view_to_list(list, child)?);
+                let field = field.clone().with_data_type(list.data_type().clone());
+                Self::try_new(&field, parent_ctx, &list)
+            }
+            DataType::LargeListView(child) => {
+                let list = array
+                    .as_any()
+                    .downcast_ref::<GenericListViewArray<i64>>()
+                    .unwrap();
+                let list: ArrayRef = Arc::new(list_view_to_list(list, child)?);
+                let field = field.clone().with_data_type(list.data_type().clone());
+                Self::try_new(&field, parent_ctx, &list)
+            }
+            DataType::Union(_, _) => {
+                let array: ArrayRef = Arc::new(union_to_struct(array.as_union())?);
+                let field = field.clone().with_data_type(array.data_type().clone());
+                Self::try_new(&field, parent_ctx, &array)
+            }
             d => Err(nyi_err!("Datatype {} is not yet supported", d)),
         }
     }
@@ -613,6 +721,33 @@ impl ArrayLevels {
     pub fn non_null_indices(&self) -> &[usize] {
         &self.non_null_indices
     }
+
+    pub fn max_def_level(&self) -> i16 {
+        self.max_def_level
+    }
+
+    /// Returns the number of levels, i.e. the number of values including nulls
+    pub fn num_levels(&self) -> usize {
+        match &self.def_levels {
+            Some(def_levels) => def_levels.len(),
+            None => self.non_null_indices.len(),
+        }
+    }
+
+    /// Returns the levels at `levels`, whose non-null values are those at `values`
+    /// within [`Self::non_null_indices`]
+    ///
+    /// The returned [`ArrayLevels`] references the same underlying array
+    pub fn slice(&self, levels: Range<usize>, values: Range<usize>) -> Self {
+        Self {
+            def_levels: self.def_levels.as_ref().map(|x| x[levels.clone()].to_vec()),
+            rep_levels: self.rep_levels.as_ref().map(|x| x[levels].to_vec()),
+            non_null_indices: self.non_null_indices[values].to_vec(),
+            max_def_level: self.max_def_level,
+            max_rep_level: self.max_rep_level,
+            array: self.array.clone(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -36,22 +36,28 @@ use super::schema::{
 };
 
 use crate::arrow::arrow_writer::byte_array::ByteArrayEncoder;
+use crate::bloom_filter::Sbbf;
 use crate::column::page::{CompressedPage, PageWriteSpec, PageWriter};
 use crate::column::writer::encoder::ColumnValueEncoder;
 use crate::column::writer::{
     get_column_writer, ColumnCloseResult, ColumnWriter, GenericColumnWriter,
 };
 use crate::data_type::{ByteArray, FixedLenByteArray};
+#[cfg(feature = "encryption")]
+use crate::encryption::encrypt::{FileEncryptor, PageEncryptor};
 use crate::errors::{ParquetError, Result};
 use crate::file::metadata::{KeyValue, RowGroupMetaData};
+use crate::file::page_index::offset_index::OffsetIndexMetaData;
 use crate::file::properties::{WriterProperties, WriterPropertiesPtr};
 use crate::file::reader::{ChunkReader, Length};
 use crate::file::writer::{SerializedFileWriter, SerializedRowGroupWriter};
 use crate::schema::types::{ColumnDescPtr, SchemaDescriptor};
 use crate::thrift::TSerializable;
+use chunker::{ChunkBoundary, ContentDefinedChunker};
 use levels::{calculate_array_levels, ArrayLevels};
 
 mod byte_array;
+mod chunker;
 mod levels;
 
 /// Encodes [`RecordBatch`] to parquet
@@ -117,6 +123,15 @@ mod levels;
 /// The writer supports writing all Arrow [`DataType`]s that have a direct mapping to
 /// Parquet types including  [`StructArray`] and [`ListArray`].
 ///
+/// Some Arrow types have no Parquet equivalent and are stored using a normalized
+/// layout, which is converted back to the original type when read with the embedded
+/// Arrow schema (see [`ArrowWriterOptions::with_skip_arrow_metadata`]):
+///
+/// * [`ListViewArray`] and [`LargeListViewArray`] are written as lists
+/// * [`UnionArray`] is written as a group containing a required `type_id` column,
+///   followed by an optional column for each variant, only the variant selected by
+///   `type_id` being non-null in each row
+///
 /// The following are not supported:
 ///
 /// * [`IntervalMonthDayNanoArray`]: Parquet does not [support nanosecond intervals].
@@ -124,6 +139,9 @@ mod levels;
 /// [`DataType`]: https://docs.rs/arrow/latest/arrow/datatypes/enum.DataType.html
 /// [`StructArray`]: https://docs.rs/arrow/latest/arrow/array/struct.StructArray.html
 /// [`ListArray`]: https://docs.rs/arrow/latest/arrow/array/type.ListArray.html
+/// [`ListViewArray`]: https://docs.rs/arrow/latest/arrow/array/type.ListViewArray.html
+/// [`LargeListViewArray`]: https://docs.rs/arrow/latest/arrow/array/type.LargeListViewArray.html
+/// [`UnionArray`]: https://docs.rs/arrow/latest/arrow/array/struct.UnionArray.html
 /// [`IntervalMonthDayNanoArray`]: https://docs.rs/arrow/latest/arrow/array/type.IntervalMonthDayNanoArray.html
 /// [support nanosecond intervals]: https://github.com/apache/parquet-format/blob/master/LogicalTypes.md#interval
 pub struct ArrowWriter<W: Write> {
@@ -261,12 +279,14 @@ impl<W: Write + Send> ArrowWriter<W> {
             return Ok(());
         }
 
+        let factory = self.column_writer_factory();
         let in_progress = match &mut self.in_progress {
             Some(in_progress) => in_progress,
             x => x.insert(ArrowRowGroupWriter::new(
                 self.writer.schema_descr(),
                 self.writer.properties(),
                 &self.arrow_schema,
+                &factory,
             )?),
         };
 
@@ -296,12 +316,64 @@ impl<W: Write + Send> ArrowWriter<W> {
 
         let mut row_group_writer = self.writer.next_row_group()?;
         for chunk in in_progress.close()? {
-            chunk.append_to_row_group(&mut row_group_writer)?;
+            // The chunk was encoded for this row group, so is already encrypted if needed
+            row_group_writer.splice_column(&chunk.data, chunk.close)?;
+        }
+        row_group_writer.close()?;
+        Ok(())
+    }
+
+    /// Flushes any buffered rows, and then appends the already encoded row group
+    /// described by `metadata`, reading its column chunks from `reader`
+    ///
+    /// The column chunks are copied verbatim, along with their bloom filters and, if
+    /// `offset_index` is provided, their offset indexes. Column indexes are not copied.
+    ///
+    /// Returns an error if the row group does not have the same parquet schema as this writer
+    pub fn append_row_group<R: ChunkReader>(
+        &mut self,
+        reader: &R,
+        metadata: &RowGroupMetaData,
+        offset_index: Option<&[OffsetIndexMetaData]>,
+    ) -> Result<()> {
+        self.flush()?;
+
+        let mut row_group_writer = self.writer.next_row_group()?;
+        for (idx, column) in metadata.columns().iter().enumerate() {
+            let close = ColumnCloseResult {
+                bytes_written: column.compressed_size() as _,
+                rows_written: metadata.num_rows() as _,
+                metadata: column.clone(),
+                bloom_filter: Sbbf::read_from_column_chunk(column, reader)?,
+                column_index: None,
+                offset_index: offset_index.map(|index| index[idx].to_thrift()),
+            };
+            row_group_writer.append_column(reader, close)?;
         }
         row_group_writer.close()?;
         Ok(())
     }
 
+    /// Returns the parquet schema of the file being written
+    pub(crate) fn parquet_schema(&self) -> &SchemaDescriptor {
+        self.writer.schema_descr()
+    }
+
+    /// Returns the [`ArrowColumnWriterFactory`] for the next row group
+    #[cfg(feature = "encryption")]
+    fn column_writer_factory(&self) -> ArrowColumnWriterFactory {
+        ArrowColumnWriterFactory::new().with_file_encryptor(
+            self.writer.flushed_row_groups().len(),
+            self.writer.file_encryptor(),
+        )
+    }
+
+    /// Returns the [`ArrowColumnWriterFactory`] for the next row group
+    #[cfg(not(feature = "encryption"))]
+    fn column_writer_factory(&self) -> ArrowColumnWriterFactory {
+        ArrowColumnWriterFactory::new()
+    }
+
     /// Additional [`KeyValue`] metadata to be written in addition to those from [`WriterProperties`]
     ///
     /// This method provide a way to append kv_metadata after write RecordBatch
@@ -458,11 +530,19 @@ type SharedColumnChunk = Arc<Mutex<ArrowColumnChunkData>>;
 #[derive(Default)]
 struct ArrowPageWriter {
     buffer: SharedColumnChunk,
+    #[cfg(feature = "encryption")]
+    page_encryptor: Option<PageEncryptor>,
 }
 
-impl PageWriter for ArrowPageWriter {
-    fn write_page(&mut self, page: CompressedPage) -> Result<PageWriteSpec> {
-        let mut buf = self.buffer.try_lock().unwrap();
+impl ArrowPageWriter {
+    /// Returns the header and data of `page`, encrypting them if configured
+    fn encode_page(&mut self, page: &CompressedPage) -> Result<(Bytes, Bytes)> {
+        #[cfg(feature = "encryption")]
+        if let Some(page_encryptor) = self.page_encryptor.as_mut() {
+            let (header, data) = page_encryptor.encrypt_compressed_page(page)?;
+            return Ok((Bytes::from(header), Bytes::from(data)));
+        }
+
         let page_header = page.to_thrift_header();
         let header = {
             let mut header = Vec::with_capacity(1024);
@@ -470,8 +550,14 @@ impl PageWriter for ArrowPageWriter {
             page_header.write_to_out_protocol(&mut protocol)?;
             Bytes::from(header)
         };
+        Ok((header, page.compressed_page().buffer().clone()))
+    }
+}
 
-        let data = page.compressed_page().buffer().clone();
+impl PageWriter for ArrowPageWriter {
+    fn write_page(&mut self, page: CompressedPage) -> Result<PageWriteSpec> {
+        let (header, data) = self.encode_page(&page)?;
+        let mut buf = self.buffer.try_lock().unwrap();
         let compressed_size = data.len() + header.len();
 
         let mut spec = PageWriteSpec::new();
@@ -607,6 +693,7 @@ impl ArrowColumnChunk {
 pub struct ArrowColumnWriter {
     writer: ArrowColumnWriterImpl,
     chunk: SharedColumnChunk,
+    chunker: Option<ContentDefinedChunker>,
 }
 
 impl std::fmt::Debug for ArrowColumnWriter {
@@ -620,20 +707,58 @@ enum ArrowColumnWriterImpl {
     Column(ColumnWriter<'static>),
 }
 
-impl ArrowColumnWriter {
-    /// Write an [`ArrowLeafColumn`]
-    pub fn write(&mut self, col: &ArrowLeafColumn) -> Result<()> {
-        match &mut self.writer {
+impl ArrowColumnWriterImpl {
+    fn write(&mut self, levels: &ArrayLevels) -> Result<()> {
+        match self {
             ArrowColumnWriterImpl::Column(c) => {
-                write_leaf(c, &col.0)?;
+                write_leaf(c, levels)?;
             }
             ArrowColumnWriterImpl::ByteArray(c) => {
-                write_primitive(c, col.0.array().as_ref(), &col.0)?;
+                write_primitive(c, levels.array().as_ref(), levels)?;
             }
         }
         Ok(())
     }
 
+    fn finish_data_page(&mut self) -> Result<()> {
+        match self {
+            ArrowColumnWriterImpl::Column(c) => c.finish_data_page(),
+            ArrowColumnWriterImpl::ByteArray(c) => c.finish_data_page(),
+        }
+    }
+}
+
+impl ArrowColumnWriter {
+    /// Write an [`ArrowLeafColumn`]
+    pub fn write(&mut self, col: &ArrowLeafColumn) -> Result<()> {
+        let levels = &col.0;
+        let boundaries = match self.chunker.as_mut() {
+            Some(chunker) => chunker.boundaries(levels)?,
+            None => return self.writer.write(levels),
+        };
+
+        // End a data page at each content-defined boundary
+        let mut start = ChunkBoundary::default();
+        for end in boundaries {
+            if end.levels > start.levels {
+                let slice = levels.slice(start.levels..end.levels, start.values..end.values);
+                self.writer.write(&slice)?;
+            }
+            self.writer.finish_data_page()?;
+            start = end;
+        }
+
+        let (num_levels, num_values) = (levels.num_levels(), levels.non_null_indices().len());
+        match start.levels {
+            0 => self.writer.write(levels),
+            l if l < num_levels => {
+                let slice = levels.slice(l..num_levels, start.values..num_values);
+                self.writer.write(&slice)
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Close this column returning the written [`ArrowColumnChunk`]
     pub fn close(self) -> Result<ArrowColumnChunk> {
         let close = match self.writer {
@@ -689,8 +814,9 @@ impl ArrowRowGroupWriter {
         parquet: &SchemaDescriptor,
         props: &WriterPropertiesPtr,
         arrow: &SchemaRef,
+        factory: &ArrowColumnWriterFactory,
     ) -> Result<Self> {
-        let writers = get_column_writers(parquet, props, arrow)?;
+        let writers = factory.create_column_writers(parquet, props, arrow)?;
         Ok(Self {
             writers,
             schema: arrow.clone(),
@@ -718,43 +844,120 @@ impl ArrowRowGroupWriter {
 }
 
 /// Returns the [`ArrowColumnWriter`] for a given schema
+///
+/// The returned writers do not encrypt their pages, and so the resulting
+/// [`ArrowColumnChunk`] cannot be appended to an encrypted column
 pub fn get_column_writers(
     parquet: &SchemaDescriptor,
     props: &WriterPropertiesPtr,
     arrow: &SchemaRef,
 ) -> Result<Vec<ArrowColumnWriter>> {
-    let mut writers = Vec::with_capacity(arrow.fields.len());
-    let mut leaves = parquet.columns().iter();
-    for field in &arrow.fields {
-        get_arrow_column_writer(field.data_type(), props, &mut leaves, &mut writers)?;
+    ArrowColumnWriterFactory::new().create_column_writers(parquet, props, arrow)
+}
+
+/// Creates the [`ArrowColumnWriter`]s of a row group, encrypting their pages if needed
+struct ArrowColumnWriterFactory {
+    #[cfg(feature = "encryption")]
+    row_group_index: usize,
+    #[cfg(feature = "encryption")]
+    file_encryptor: Option<Arc<FileEncryptor>>,
+}
+
+impl ArrowColumnWriterFactory {
+    fn new() -> Self {
+        Self {
+            #[cfg(feature = "encryption")]
+            row_group_index: 0,
+            #[cfg(feature = "encryption")]
+            file_encryptor: None,
+        }
+    }
+
+    /// Encrypts the pages of the row group at `row_group_index` with `file_encryptor`
+    #[cfg(feature = "encryption")]
+    fn with_file_encryptor(
+        mut self,
+        row_group_index: usize,
+        file_encryptor: Option<Arc<FileEncryptor>>,
+    ) -> Self {
+        self.row_group_index = row_group_index;
+        self.file_encryptor = file_encryptor;
+        self
+    }
+
+    fn create_column_writers(
+        &self,
+        parquet: &SchemaDescriptor,
+        props: &WriterPropertiesPtr,
+        arrow: &SchemaRef,
+    ) -> Result<Vec<ArrowColumnWriter>> {
+        let mut writers = Vec::with_capacity(arrow.fields.len());
+        let mut leaves = parquet.columns().iter();
+        for field in &arrow.fields {
+            get_arrow_column_writer(field.data_type(), props, self, &mut leaves, &mut writers)?;
+        }
+        Ok(writers)
+    }
+
+    /// Creates the page writer for the column `desc` at `column_ordinal`
+    #[cfg(feature = "encryption")]
+    fn create_page_writer(
+        &self,
+        desc: &ColumnDescPtr,
+        column_ordinal: usize,
+    ) -> Box<ArrowPageWriter> {
+        let page_encryptor = self.file_encryptor.as_ref().and_then(|e| {
+            e.page_encryptor(&desc.path().string(), self.row_group_index, column_ordinal)
+        });
+        Box::new(ArrowPageWriter {
+            buffer: Default::default(),
+            page_encryptor,
+        })
+    }
+
+    /// Creates the page writer for the column `_desc` at `_column_ordinal`
+    #[cfg(not(feature = "encryption"))]
+    fn create_page_writer(
+        &self,
+        _desc: &ColumnDescPtr,
+        _column_ordinal: usize,
+    ) -> Box<ArrowPageWriter> {
+        Box::<ArrowPageWriter>::default()
     }
-    Ok(writers)
 }
 
 /// Gets the [`ArrowColumnWriter`] for the given `data_type`
 fn get_arrow_column_writer(
     data_type: &ArrowDataType,
     props: &WriterPropertiesPtr,
+    factory: &ArrowColumnWriterFactory,
     leaves: &mut Iter<'_, ColumnDescPtr>,
     out: &mut Vec<ArrowColumnWriter>,
 ) -> Result<()> {
+    let column_ordinal = out.len();
     let col = |desc: &ColumnDescPtr| {
-        let page_writer = Box::<ArrowPageWriter>::default();
+        let page_writer = factory.create_page_writer(desc, column_ordinal);
         let chunk = page_writer.buffer.clone();
         let writer = get_column_writer(desc.clone(), props.clone(), page_writer);
         ArrowColumnWriter {
             chunk,
             writer: ArrowColumnWriterImpl::Column(writer),
+            chunker: props
+                .content_defined_chunking()
+                .map(ContentDefinedChunker::new),
         }
     };
 
     let bytes = |desc: &ColumnDescPtr| {
-        let page_writer = Box::<ArrowPageWriter>::default();
+        let page_writer = factory.create_page_writer(desc, column_ordinal);
         let chunk = page_writer.buffer.clone();
         let writer = GenericColumnWriter::new(desc.clone(), props.clone(), page_writer);
         ArrowColumnWriter {
             chunk,
             writer: ArrowColumnWriterImpl::ByteArray(writer),
+            chunker: props
+                .content_defined_chunking()
+                .map(ContentDefinedChunker::new),
         }
     };
 
@@ -771,18 +974,27 @@ fn get_arrow_column_writer(
         }
         ArrowDataType::List(f)
         | ArrowDataType::LargeList(f)
+        | ArrowDataType::ListView(f)
+        | ArrowDataType::LargeListView(f)
         | ArrowDataType::FixedSizeList(f, _) => {
-            get_arrow_column_writer(f.data_type(), props, leaves, out)?
+            get_arrow_column_writer(f.data_type(), props, factory, leaves, out)?
+        }
+        ArrowDataType::Union(fields, _) => {
+            // type_id column followed by one column per variant
+            out.push(col(leaves.next().unwrap()));
+            for (_, field) in fields.iter() {
+                get_arrow_column_writer(field.data_type(), props, factory, leaves, out)?
+            }
         }
         ArrowDataType::Struct(fields) => {
             for field in fields {
-                get_arrow_column_writer(field.data_type(), props, leaves, out)?
+                get_arrow_column_writer(field.data_type(), props, factory, leaves, out)?
             }
         }
         ArrowDataType::Map(f, _) => match f.data_type() {
             ArrowDataType::Struct(f) => {
-                get_arrow_column_writer(f[0].data_type(), props, leaves, out)?;
-                get_arrow_column_writer(f[1].data_type(), props, leaves, out)?
+                get_arrow_column_writer(f[0].data_type(), props, factory, leaves, out)?;
+                get_arrow_column_writer(f[1].data_type(), props, factory, leaves, out)?
             }
             _ => unreachable!("invalid map type"),
         }
@@ -1077,7 +1289,9 @@ mod tests {
 
     use std::fs::File;
 
-    use crate::arrow::arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder};
+    use crate::arrow::arrow_reader::{
+        ArrowReaderOptions, ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder,
+    };
     use crate::arrow::ARROW_SCHEMA_META_KEY;
     use arrow::datatypes::ToByteSlice;
     use arrow::datatypes::{DataType, Schema};
@@ -1089,11 +1303,11 @@ mod tests {
 
     use crate::basic::Encoding;
     use crate::data_type::AsBytes;
-    use crate::file::metadata::ParquetMetaData;
+    use crate::file::metadata::{LevelHistogram, ParquetMetaData};
     use crate::file::page_index::index::Index;
     use crate::file::page_index::index_reader::read_offset_indexes;
     use crate::file::properties::{
-        BloomFilterPosition, EnabledStatistics, ReaderProperties, WriterVersion,
+        BloomFilterPosition, CdcOptions, EnabledStatistics, ReaderProperties, WriterVersion,
     };
     use crate::file::serialized_reader::ReadOptionsBuilder;
     use crate::file::{
@@ -3256,6 +3470,186 @@ mod tests {
         assert!(matches!(b_idx, Index::NONE), "{b_idx:?}");
     }
 
+    #[test]
+    fn test_arrow_writer_size_statistics() {
+        let names: StringArray = (0..100)
+            .map(|i| (i % 4 != 0).then(|| "x".repeat(i % 5)))
+            .collect();
+        let dict: DictionaryArray<arrow::datatypes::Int32Type> =
+            (0..100).map(|i| ["ab", "cde", "f"][i % 3]).collect();
+        let views: StringViewArray = (0..100).map(|i| Some("y".repeat(i % 20))).collect();
+        let lists = ListArray::from_iter_primitive::<arrow::datatypes::Int32Type, _, _>(
+            (0..100).map(|i| (i % 10 != 0).then(|| (0..i % 3).map(|j| (j != 1).then_some(j)))),
+        );
+
+        let batch = RecordBatch::try_from_iter([
+            ("name", Arc::new(names.clone()) as ArrayRef),
+            ("dict", Arc::new(dict) as ArrayRef),
+            ("view", Arc::new(views.clone()) as ArrayRef),
+            ("list", Arc::new(lists.clone()) as ArrayRef),
+        ])
+        .unwrap();
+
+        let props = WriterProperties::builder()
+            .set_statistics_enabled(EnabledStatistics::Page)
+            .set_column_statistics_enabled("view".into(), EnabledStatistics::None)
+            .set_data_page_row_count_limit(25)
+            .set_write_batch_size(1)
+            .build();
+        let mut buf = Vec::with_capacity(1024);
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let options = ReadOptionsBuilder::new().with_page_index().build();
+        let reader = SerializedFileReader::new_with_options(Bytes::from(buf), options).unwrap();
+        let metadata = reader.metadata();
+        let columns = metadata.row_group(0).columns();
+        let column_index = &metadata.column_index().unwrap()[0];
+        let offset_index = &metadata.offset_index().unwrap()[0];
+
+        // Unencoded sizes are reported per chunk and per page, even without statistics
+        let name_bytes: i64 = names.iter().flatten().map(|x| x.len() as i64).sum();
+        let dict_bytes: i64 = (0..100).map(|i| [2, 3, 1][i % 3]).sum();
+        let view_bytes: i64 = views.iter().flatten().map(|x| x.len() as i64).sum();
+        for (idx, expected) in [(0, name_bytes), (1, dict_bytes), (2, view_bytes)] {
+            assert_eq!(
+                columns[idx].unencoded_byte_array_data_bytes(),
+                Some(expected)
+            );
+            let pages = offset_index[idx].unencoded_byte_array_data_bytes().unwrap();
+            assert_eq!(pages.len(), offset_index[idx].page_locations().len());
+            assert_eq!(pages.iter().sum::<i64>(), expected);
+        }
+        assert_eq!(columns[3].unencoded_byte_array_data_bytes(), None);
+        assert_eq!(offset_index[3].unencoded_byte_array_data_bytes(), None);
+
+        // Level histograms are only reported with statistics enabled
+        assert!(columns[2].definition_level_histogram().is_none());
+        assert!(matches!(column_index[2], Index::NONE));
+
+        assert!(columns[0].repetition_level_histogram().is_none());
+        let def = columns[0].definition_level_histogram().unwrap();
+        assert_eq!(def.values(), &[25, 75]);
+
+        let mut expected_def = vec![0; 4];
+        let mut expected_rep = vec![0; 2];
+        for list in lists.iter() {
+            expected_rep[0] += 1;
+            match list {
+                None => expected_def[0] += 1,
+                Some(list) if list.is_empty() => expected_def[1] += 1,
+                Some(list) => {
+                    expected_rep[1] += list.len() as i64 - 1;
+                    expected_def[2] += list.null_count() as i64;
+                    expected_def[3] += (list.len() - list.null_count()) as i64;
+                }
+            }
+        }
+        let rep = columns[3].repetition_level_histogram().unwrap();
+        let def = columns[3].definition_level_histogram().unwrap();
+        assert_eq!(rep.values(), &expected_rep);
+        assert_eq!(def.values(), &expected_def);
+        assert_eq!(rep.get(0), Some(100));
+
+        // The page histograms of the column index sum to the chunk histograms
+        let pages = match &column_index[3] {
+            Index::INT32(index) => &index.indexes,
+            _ => unreachable!(),
+        };
+        assert_eq!(pages.len(), 4);
+        let mut page_rep = LevelHistogram::try_new(1).unwrap();
+        let mut page_def = LevelHistogram::try_new(3).unwrap();
+        for page in pages {
+            page_rep.add(page.repetition_level_histogram().unwrap());
+            page_def.add(page.definition_level_histogram().unwrap());
+            assert_eq!(page.repetition_level_histogram().unwrap().get(0), Some(25));
+        }
+        assert_eq!(&page_rep, rep);
+        assert_eq!(&page_def, def);
+    }
+
+    #[test]
+    fn test_arrow_writer_content_defined_chunking() {
+        fn make_batch(ids: impl Iterator<Item = i64> + Clone) -> RecordBatch {
+            let names: StringArray = ids
+                .clone()
+                .map(|i| (i % 7 != 0).then(|| format!("name-{}", i * 31 % 97)))
+                .collect();
+            let lists = ListArray::from_iter_primitive::<arrow::datatypes::Int32Type, _, _>(
+                ids.clone()
+                    .map(|i| Some((0..i % 4).map(move |j| Some((i + j) as i32)))),
+            );
+            let ids = Int64Array::from_iter_values(ids);
+            RecordBatch::try_from_iter([
+                ("id", Arc::new(ids) as ArrayRef),
+                ("name", Arc::new(names) as ArrayRef),
+                ("list", Arc::new(lists) as ArrayRef),
+            ])
+            .unwrap()
+        }
+
+        fn write_pages(batch: &RecordBatch) -> Vec<Vec<Bytes>> {
+            let props = WriterProperties::builder()
+                .set_dictionary_enabled(false)
+                .set_content_defined_chunking(Some(CdcOptions {
+                    min_chunk_size: 256,
+                    max_chunk_size: 1024,
+                    norm_level: 0,
+                }))
+                .build();
+            let mut buf = Vec::with_capacity(1024);
+            let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+            for offset in (0..batch.num_rows()).step_by(300) {
+                let len = 300.min(batch.num_rows() - offset);
+                writer.write(&batch.slice(offset, len)).unwrap();
+            }
+            writer.close().unwrap();
+
+            let data = Bytes::from(buf);
+            let options = ArrowReaderOptions::new().with_page_index(true);
+            let builder =
+                ParquetRecordBatchReaderBuilder::try_new_with_options(data.clone(), options)
+                    .unwrap();
+            let offset_index = &builder.metadata().offset_index().unwrap()[0];
+            let pages = offset_index
+                .iter()
+                .map(|column| {
+                    let locations = column.page_locations();
+                    assert!(locations.len() > 4, "{locations:?}");
+                    locations
+                        .iter()
+                        .map(|l| {
+                            let start = l.offset as usize;
+                            data.slice(start..start + l.compressed_page_size as usize)
+                        })
+                        .collect()
+                })
+                .collect();
+
+            let read: Vec<_> = builder.build().unwrap().collect::<Result<_, _>>().unwrap();
+            assert_eq!(
+                &arrow::compute::concat_batches(&batch.schema(), &read).unwrap(),
+                batch
+            );
+            pages
+        }
+
+        let original = write_pages(&make_batch(0..3000));
+        let modified = write_pages(&make_batch((0..1500).chain(5000..5010).chain(1500..3000)));
+
+        // Only the pages around the inserted rows should differ, the inserted rows
+        // may introduce an additional boundary within them
+        for (original, modified) in original.iter().zip(&modified) {
+            let changed = modified.iter().filter(|p| !original.contains(p)).count();
+            assert!(
+                changed <= 3,
+                "{changed} of {} pages changed",
+                modified.len()
+            );
+        }
+    }
+
     #[test]
     fn test_arrow_writer_skip_metadata() {
         let batch_schema = Schema::new(vec![Field::new("int32", DataType::Int32, false)]);
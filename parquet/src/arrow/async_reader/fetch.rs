@@ -0,0 +1,335 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Planning of the I/O performed by [`ParquetRecordBatchStream`]
+//!
+//! [`ParquetRecordBatchStream`]: super::ParquetRecordBatchStream
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::future::{BoxFuture, FutureExt};
+
+use crate::arrow::async_reader::AsyncFileReader;
+use crate::errors::{ParquetError, Result};
+use crate::file::metadata::ParquetMetaData;
+
+/// Configures how a [`ParquetRecordBatchStream`] fetches data from its [`AsyncFileReader`]
+///
+/// By default, the column chunks of a row group are fetched with a single call to
+/// [`AsyncFileReader::get_byte_ranges`] once the previous row group has been decoded.
+/// When reading from storage where each request has a high latency, such as object
+/// storage, throughput can be improved by coalescing nearby byte ranges into fewer
+/// requests, and by fetching the next row groups whilst the current one is decoded.
+///
+/// ```
+/// # use parquet::arrow::async_reader::FetchOptions;
+/// let options = FetchOptions::new()
+///     // Merge ranges less than 1 MiB apart
+///     .with_coalesce_gap(1024 * 1024)
+///     // Fetch up to 2 row groups ahead, buffering at most 256 MiB
+///     .with_prefetch_row_groups(2)
+///     .with_max_prefetch_bytes(256 * 1024 * 1024);
+/// ```
+///
+/// [`ParquetRecordBatchStream`]: super::ParquetRecordBatchStream
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FetchOptions {
+    coalesce_gap: Option<usize>,
+    prefetch_row_groups: usize,
+    max_prefetch_bytes: Option<usize>,
+}
+
+impl FetchOptions {
+    /// Create a new [`FetchOptions`] with the default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merge byte ranges separated by at most `max_gap` bytes into a single request
+    ///
+    /// The bytes between the merged ranges are fetched and discarded, trading additional
+    /// bandwidth for fewer requests.
+    pub fn with_coalesce_gap(self, max_gap: usize) -> Self {
+        Self {
+            coalesce_gap: Some(max_gap),
+            ..self
+        }
+    }
+
+    /// Fetch the data of up to `row_groups` row groups ahead of the row group being decoded
+    ///
+    /// Data is fetched for the columns of the projection and of any [`RowFilter`]
+    /// predicates. If an offset index is loaded, only the pages selected by the
+    /// [`RowSelection`] are fetched. Prefetching is driven by polling the stream, and so
+    /// progresses whilst batches of the current row group are being returned.
+    ///
+    /// As predicates and limits are only evaluated once a row group is read, the
+    /// prefetched data may include pages that are subsequently not decoded.
+    ///
+    /// [`RowFilter`]: crate::arrow::arrow_reader::RowFilter
+    /// [`RowSelection`]: crate::arrow::arrow_reader::RowSelection
+    pub fn with_prefetch_row_groups(self, row_groups: usize) -> Self {
+        Self {
+            prefetch_row_groups: row_groups,
+            ..self
+        }
+    }
+
+    /// Limit the total size of prefetched data that is buffered or in flight to `bytes`
+    ///
+    /// A row group is not prefetched if doing so would exceed this limit, and is instead
+    /// fetched once it is read. The bytes fetched between coalesced byte ranges count
+    /// towards this limit, as they are buffered along with the ranges.
+    pub fn with_max_prefetch_bytes(self, bytes: usize) -> Self {
+        Self {
+            max_prefetch_bytes: Some(bytes),
+            ..self
+        }
+    }
+
+    /// Returns the maximum gap between byte ranges that are merged, if any
+    pub fn coalesce_gap(&self) -> Option<usize> {
+        self.coalesce_gap
+    }
+
+    /// Returns the number of row groups to prefetch
+    pub fn prefetch_row_groups(&self) -> usize {
+        self.prefetch_row_groups
+    }
+
+    /// Returns the maximum number of prefetched bytes, if any
+    pub fn max_prefetch_bytes(&self) -> Option<usize> {
+        self.max_prefetch_bytes
+    }
+}
+
+/// Merges `ranges` separated by at most `gap` bytes, returning the merged ranges
+/// ordered by their start offset
+fn merge_ranges(ranges: &[Range<usize>], gap: usize) -> Vec<Range<usize>> {
+    let mut sorted = ranges.to_vec();
+    sorted.sort_unstable_by_key(|r| r.start);
+
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(sorted.len());
+    for range in sorted {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end.saturating_add(gap) => {
+                last.end = last.end.max(range.end)
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Returns the ranges to fetch for `ranges`, merging those separated by at most
+/// `coalesce_gap` bytes
+pub(crate) fn coalesce_ranges(
+    ranges: &[Range<usize>],
+    coalesce_gap: Option<usize>,
+) -> Vec<Range<usize>> {
+    match coalesce_gap {
+        Some(gap) => merge_ranges(ranges, gap),
+        None => ranges.to_vec(),
+    }
+}
+
+/// Fetches `ranges` from `input`, merging ranges separated by at most `coalesce_gap` bytes
+pub(crate) async fn get_byte_ranges<T: AsyncFileReader>(
+    input: &mut T,
+    ranges: Vec<Range<usize>>,
+    coalesce_gap: Option<usize>,
+) -> Result<Vec<Bytes>> {
+    let gap = match coalesce_gap {
+        Some(gap) => gap,
+        None => return input.get_byte_ranges(ranges).await,
+    };
+
+    let merged = merge_ranges(&ranges, gap);
+    let fetched = input.get_byte_ranges(merged.clone()).await?;
+    if fetched.len() != merged.len() {
+        return Err(general_err!(
+            "expected {} byte ranges but got {}",
+            merged.len(),
+            fetched.len()
+        ));
+    }
+
+    ranges
+        .iter()
+        .map(|range| {
+            let idx = merged.partition_point(|m| m.start <= range.start) - 1;
+            let (start, data) = (merged[idx].start, &fetched[idx]);
+            if range.end - start > data.len() {
+                return Err(general_err!(
+                    "expected {} bytes for range {:?} but got {}",
+                    merged[idx].len(),
+                    merged[idx],
+                    data.len()
+                ));
+            }
+            Ok(data.slice(range.start - start..range.end - start))
+        })
+        .collect()
+}
+
+/// The data of a row group fetched ahead of it being read
+#[derive(Debug)]
+pub(crate) struct PrefetchedRowGroup {
+    /// The index of the row group
+    pub row_group_idx: usize,
+    /// The fetched byte ranges, after any coalescing, such that their size is that of
+    /// the buffered data
+    pub data: Vec<(Range<usize>, Bytes)>,
+}
+
+impl PrefetchedRowGroup {
+    /// Returns the total size of the fetched data
+    pub fn size(&self) -> usize {
+        self.data.iter().map(|(_, b)| b.len()).sum()
+    }
+
+    /// Returns the data for `range` if it is contained within a fetched range
+    fn get(&self, range: &Range<usize>) -> Option<Bytes> {
+        self.data.iter().find_map(|(r, data)| {
+            (r.start <= range.start && range.end <= r.end)
+                .then(|| data.slice(range.start - r.start..range.end - r.start))
+        })
+    }
+}
+
+/// An [`AsyncFileReader`] that reads from a [`PrefetchedRowGroup`] where possible,
+/// fetching any other ranges from the underlying reader
+pub(crate) struct PrefetchReader<'a, T> {
+    input: &'a mut T,
+    prefetched: Option<&'a PrefetchedRowGroup>,
+    coalesce_gap: Option<usize>,
+}
+
+impl<'a, T> PrefetchReader<'a, T> {
+    pub fn new(
+        input: &'a mut T,
+        prefetched: Option<&'a PrefetchedRowGroup>,
+        coalesce_gap: Option<usize>,
+    ) -> Self {
+        Self {
+            input,
+            prefetched,
+            coalesce_gap,
+        }
+    }
+}
+
+impl<T: AsyncFileReader> AsyncFileReader for PrefetchReader<'_, T> {
+    fn get_bytes(&mut self, range: Range<usize>) -> BoxFuture<'_, Result<Bytes>> {
+        match self.prefetched.and_then(|p| p.get(&range)) {
+            Some(data) => futures::future::ready(Ok(data)).boxed(),
+            None => self.input.get_bytes(range),
+        }
+    }
+
+    fn get_byte_ranges(&mut self, ranges: Vec<Range<usize>>) -> BoxFuture<'_, Result<Vec<Bytes>>> {
+        async move {
+            let mut result: Vec<_> = ranges
+                .iter()
+                .map(|r| self.prefetched.and_then(|p| p.get(r)))
+                .collect();
+
+            let missing: Vec<_> = ranges
+                .iter()
+                .zip(&result)
+                .filter(|(_, data)| data.is_none())
+                .map(|(range, _)| range.clone())
+                .collect();
+
+            if !missing.is_empty() {
+                let mut fetched = get_byte_ranges(&mut *self.input, missing, self.coalesce_gap)
+                    .await?
+                    .into_iter();
+                for data in result.iter_mut().filter(|data| data.is_none()) {
+                    *data = fetched.next();
+                }
+            }
+
+            result
+                .into_iter()
+                .map(|data| data.ok_or_else(|| general_err!("missing byte range")))
+                .collect()
+        }
+        .boxed()
+    }
+
+    fn get_metadata(&mut self) -> BoxFuture<'_, Result<Arc<ParquetMetaData>>> {
+        self.input.get_metadata()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_ranges() {
+        let ranges = vec![10..20, 0..5, 22..30, 50..60, 25..28, 61..61];
+        assert_eq!(
+            merge_ranges(&ranges, 0),
+            vec![0..5, 10..20, 22..30, 50..60, 61..61]
+        );
+        assert_eq!(merge_ranges(&ranges, 2), vec![0..5, 10..30, 50..61]);
+        assert_eq!(merge_ranges(&ranges, 5), vec![0..30, 50..61]);
+        assert_eq!(merge_ranges(&ranges, usize::MAX), vec![0..61]);
+        assert!(merge_ranges(&[], 10).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_reader() {
+        struct Reader {
+            data: Bytes,
+            requests: Vec<Range<usize>>,
+        }
+
+        impl AsyncFileReader for Reader {
+            fn get_bytes(&mut self, range: Range<usize>) -> BoxFuture<'_, Result<Bytes>> {
+                self.requests.push(range.clone());
+                futures::future::ready(Ok(self.data.slice(range))).boxed()
+            }
+
+            fn get_metadata(&mut self) -> BoxFuture<'_, Result<Arc<ParquetMetaData>>> {
+                unimplemented!()
+            }
+        }
+
+        let data = Bytes::from_iter(0..=255_u8);
+        let mut input = Reader {
+            data: data.clone(),
+            requests: vec![],
+        };
+        let prefetched = PrefetchedRowGroup {
+            row_group_idx: 0,
+            data: vec![(100..150, data.slice(100..150))],
+        };
+
+        let ranges = vec![120..130, 10..20, 140..160, 25..30, 100..150];
+        let mut reader = PrefetchReader::new(&mut input, Some(&prefetched), Some(10));
+        let result = reader.get_byte_ranges(ranges.clone()).await.unwrap();
+        for (range, result) in ranges.iter().zip(result) {
+            assert_eq!(result, data.slice(range.clone()));
+        }
+        assert_eq!(input.requests, vec![10..30, 140..160]);
+    }
+}
@@ -92,17 +92,22 @@ use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
 use arrow_array::RecordBatch;
 use arrow_schema::{DataType, Fields, Schema, SchemaRef};
 
-use crate::arrow::array_reader::{build_array_reader, RowGroups};
+use crate::arrow::array_reader::{build_parallel_array_reader, RowGroups};
 use crate::arrow::arrow_reader::{
     apply_range, evaluate_predicate, selects_any, ArrowReaderBuilder, ArrowReaderMetadata,
-    ArrowReaderOptions, ParquetRecordBatchReader, RowFilter, RowSelection,
+    ArrowReaderOptions, DecodeExecutor, EqualityPredicate, ParquetRecordBatchReader, RowFilter,
+    RowGroupPruner, RowSelection,
 };
 use crate::arrow::ProjectionMask;
 
+#[cfg(feature = "encryption")]
+use crate::bloom_filter::encrypted_bloom_filter_range;
 use crate::bloom_filter::{
     chunk_read_bloom_filter_header_and_offset, Sbbf, SBBF_HEADER_SIZE_ESTIMATE,
 };
 use crate::column::page::{PageIterator, PageReader};
+#[cfg(feature = "encryption")]
+use crate::encryption::decrypt::CryptoContext;
 use crate::errors::{ParquetError, Result};
 use crate::file::metadata::{ParquetMetaData, ParquetMetaDataReader, RowGroupMetaData};
 use crate::file::page_index::offset_index::OffsetIndexMetaData;
@@ -110,6 +115,10 @@ use crate::file::reader::{ChunkReader, Length, SerializedPageReader};
 use crate::file::FOOTER_SIZE;
 use crate::format::{BloomFilterAlgorithm, BloomFilterCompression, BloomFilterHash};
 
+mod fetch;
+pub use fetch::FetchOptions;
+use fetch::{PrefetchReader, PrefetchedRowGroup};
+
 mod metadata;
 pub use metadata::*;
 
@@ -156,6 +165,19 @@ pub trait AsyncFileReader: Send {
     /// allowing fine-grained control over how metadata is sourced, in particular allowing
     /// for caching, pre-fetching, catalog metadata, etc...
     fn get_metadata(&mut self) -> BoxFuture<'_, Result<Arc<ParquetMetaData>>>;
+
+    /// Provides asynchronous access to the [`ParquetMetaData`] of a parquet file, taking
+    /// into account the provided [`ArrowReaderOptions`], for example to decrypt the
+    /// metadata of a file written with Parquet modular encryption.
+    ///
+    /// The default implementation ignores `options` and calls [`Self::get_metadata`]
+    fn get_metadata_with_options<'a>(
+        &'a mut self,
+        options: &'a ArrowReaderOptions,
+    ) -> BoxFuture<'a, Result<Arc<ParquetMetaData>>> {
+        let _ = options;
+        self.get_metadata()
+    }
 }
 
 impl AsyncFileReader for Box<dyn AsyncFileReader> {
@@ -170,6 +192,13 @@ impl AsyncFileReader for Box<dyn AsyncFileReader> {
     fn get_metadata(&mut self) -> BoxFuture<'_, Result<Arc<ParquetMetaData>>> {
         self.as_mut().get_metadata()
     }
+
+    fn get_metadata_with_options<'a>(
+        &'a mut self,
+        options: &'a ArrowReaderOptions,
+    ) -> BoxFuture<'a, Result<Arc<ParquetMetaData>>> {
+        self.as_mut().get_metadata_with_options(options)
+    }
 }
 
 impl<T: AsyncRead + AsyncSeek + Unpin + Send> AsyncFileReader for T {
@@ -208,6 +237,22 @@ impl<T: AsyncRead + AsyncSeek + Unpin + Send> AsyncFileReader for T {
         }
         .boxed()
     }
+
+    #[cfg(feature = "encryption")]
+    fn get_metadata_with_options<'a>(
+        &'a mut self,
+        options: &'a ArrowReaderOptions,
+    ) -> BoxFuture<'a, Result<Arc<ParquetMetaData>>> {
+        async move {
+            let file_size = self.seek(SeekFrom::End(0)).await? as usize;
+            let metadata = ParquetMetaDataReader::new()
+                .with_decryption_properties(options.file_decryption_properties.as_ref())
+                .load_and_finish(self, file_size)
+                .await?;
+            Ok(Arc::new(metadata))
+        }
+        .boxed()
+    }
 }
 
 impl ArrowReaderMetadata {
@@ -226,7 +271,7 @@ impl ArrowReaderMetadata {
     ) -> Result<Self> {
         // TODO: this is all rather awkward. It would be nice if AsyncFileReader::get_metadata
         // took an argument to fetch the page indexes.
-        let mut metadata = input.get_metadata().await?;
+        let mut metadata = input.get_metadata_with_options(&options).await?;
 
         if options.page_index
             && metadata.column_index().is_none()
@@ -246,7 +291,7 @@ impl ArrowReaderMetadata {
 ///
 /// Allows sharing the same builder for both the sync and async versions, whilst also not
 /// breaking the pre-existing ParquetRecordBatchStreamBuilder API
-pub struct AsyncReader<T>(T);
+pub struct AsyncReader<T>(T, FetchOptions);
 
 /// A builder used to construct a [`ParquetRecordBatchStream`] for `async` reading of a parquet file
 ///
@@ -265,7 +310,6 @@ impl<T: AsyncFileReader + Send + 'static> ParquetRecordBatchStreamBuilder<T> {
     /// ```
     /// # use std::fs::metadata;
     /// # use std::sync::Arc;
-    /// # use bytes::Bytes;
     /// # use arrow_array::{Int32Array, RecordBatch};
     /// # use arrow_schema::{DataType, Field, Schema};
     /// # use parquet::arrow::arrow_reader::ArrowReaderMetadata;
@@ -311,7 +355,6 @@ impl<T: AsyncFileReader + Send + 'static> ParquetRecordBatchStreamBuilder<T> {
     /// ```
     /// # use std::fs::metadata;
     /// # use std::sync::Arc;
-    /// # use bytes::Bytes;
     /// # use arrow_array::{Int32Array, RecordBatch};
     /// # use arrow_schema::{DataType, Field, Schema};
     /// # use parquet::arrow::arrow_reader::ArrowReaderMetadata;
@@ -347,7 +390,47 @@ impl<T: AsyncFileReader + Send + 'static> ParquetRecordBatchStreamBuilder<T> {
     /// # }
     /// ```
     pub fn new_with_metadata(input: T, metadata: ArrowReaderMetadata) -> Self {
-        Self::new_builder(AsyncReader(input), metadata)
+        Self::new_builder(AsyncReader(input, FetchOptions::default()), metadata)
+    }
+
+    /// Configures how the [`ParquetRecordBatchStream`] fetches data, see [`FetchOptions`]
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use arrow_array::{Int32Array, RecordBatch};
+    /// # use arrow_schema::{DataType, Field, Schema};
+    /// # use futures::TryStreamExt;
+    /// # use parquet::arrow::{ArrowWriter, ParquetRecordBatchStreamBuilder};
+    /// # use parquet::arrow::async_reader::FetchOptions;
+    /// # use parquet::file::properties::WriterProperties;
+    /// # #[tokio::main(flavor="current_thread")]
+    /// # async fn main() {
+    /// # let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+    /// # let mut file = tempfile::tempfile().unwrap();
+    /// # let props = WriterProperties::builder().set_max_row_group_size(10).build();
+    /// # let mut writer = ArrowWriter::try_new(&mut file, schema.clone(), Some(props)).unwrap();
+    /// # let batch = RecordBatch::try_new(schema, vec![Arc::new(Int32Array::from_iter_values(0..100))]).unwrap();
+    /// # writer.write(&batch).unwrap();
+    /// # writer.close().unwrap();
+    /// # let file = tokio::fs::File::from_std(file);
+    /// let options = FetchOptions::new()
+    ///     .with_coalesce_gap(1024 * 1024)
+    ///     .with_prefetch_row_groups(2);
+    ///
+    /// let stream = ParquetRecordBatchStreamBuilder::new(file)
+    ///     .await
+    ///     .unwrap()
+    ///     .with_fetch_options(options)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let batches: Vec<_> = stream.try_collect().await.unwrap();
+    /// assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 100);
+    /// # }
+    /// ```
+    pub fn with_fetch_options(mut self, options: FetchOptions) -> Self {
+        self.input.1 = options;
+        self
     }
 
     /// Read bloom filter for a column in a row group
@@ -362,6 +445,17 @@ impl<T: AsyncFileReader + Send + 'static> ParquetRecordBatchStreamBuilder<T> {
         let metadata = self.metadata.row_group(row_group_idx);
         let column_metadata = metadata.column(column_idx);
 
+        #[cfg(feature = "encryption")]
+        if let Some(context) = CryptoContext::for_column(&self.metadata, row_group_idx, column_idx)?
+        {
+            let range = match encrypted_bloom_filter_range(column_metadata)? {
+                Some(range) => range.start as usize..range.end as usize,
+                None => return Ok(None),
+            };
+            let buffer = self.input.0.get_bytes(range).await?;
+            return Sbbf::decrypt(&buffer, &context).map(Some);
+        }
+
         let offset: usize = if let Some(offset) = column_metadata.bloom_filter_offset() {
             offset
                 .try_into()
@@ -413,6 +507,37 @@ impl<T: AsyncFileReader + Send + 'static> ParquetRecordBatchStreamBuilder<T> {
         Ok(Some(Sbbf::new(&bitset)))
     }
 
+    /// Returns the row groups that may contain rows matching all of `predicates`,
+    /// based on their statistics and bloom filters
+    ///
+    /// See [`ParquetRecordBatchReaderBuilder::prune_row_groups`] for more details
+    ///
+    /// [`ParquetRecordBatchReaderBuilder::prune_row_groups`]: crate::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::prune_row_groups
+    pub async fn prune_row_groups(
+        &mut self,
+        predicates: &[EqualityPredicate],
+    ) -> Result<Vec<usize>> {
+        let pruner = RowGroupPruner::try_new(self.parquet_schema(), predicates)?;
+        let row_groups = pruner.prune_by_statistics(&self.metadata, self.row_groups.as_deref())?;
+        let columns = pruner.bloom_filter_columns();
+
+        let mut selected = Vec::with_capacity(row_groups.len());
+        'outer: for row_group_idx in row_groups {
+            for column_idx in &columns {
+                let filter = self
+                    .get_row_group_column_bloom_filter(row_group_idx, *column_idx)
+                    .await?;
+                if let Some(filter) = filter {
+                    if !pruner.check_bloom_filter(*column_idx, &filter) {
+                        continue 'outer;
+                    }
+                }
+            }
+            selected.push(row_group_idx);
+        }
+        Ok(selected)
+    }
+
     /// Build a new [`ParquetRecordBatchStream`]
     pub fn build(self) -> Result<ParquetRecordBatchStream<T>> {
         let num_row_groups = self.metadata.row_groups().len();
@@ -442,6 +567,9 @@ impl<T: AsyncFileReader + Send + 'static> ParquetRecordBatchStreamBuilder<T> {
             fields: self.fields,
             limit: self.limit,
             offset: self.offset,
+            executor: self.executor,
+            fetch_options: self.input.1,
+            prefetched: VecDeque::new(),
         };
 
         // Ensure schema of ParquetRecordBatchStream respects projection, and does
@@ -463,6 +591,7 @@ impl<T: AsyncFileReader + Send + 'static> ParquetRecordBatchStreamBuilder<T> {
             selection: self.selection,
             schema,
             reader: Some(reader),
+            prefetch: None,
             state: StreamState::Init,
         })
     }
@@ -484,6 +613,13 @@ struct ReaderFactory<T> {
     limit: Option<usize>,
 
     offset: Option<usize>,
+
+    executor: Option<Arc<dyn DecodeExecutor>>,
+
+    fetch_options: FetchOptions,
+
+    /// Data fetched ahead of reading upcoming row groups, in the order they will be read
+    prefetched: VecDeque<PrefetchedRowGroup>,
 }
 
 impl<T> ReaderFactory<T>
@@ -502,21 +638,13 @@ where
     ) -> ReadResult<T> {
         // TODO: calling build_array multiple times is wasteful
 
-        let meta = self.metadata.row_group(row_group_idx);
-        let offset_index = self
-            .metadata
-            .offset_index()
-            // filter out empty offset indexes (old versions specified Some(vec![]) when no present)
-            .filter(|index| !index.is_empty())
-            .map(|x| x[row_group_idx].as_slice());
+        let mut row_group = InMemoryRowGroup::new(&self.metadata, row_group_idx);
 
-        let mut row_group = InMemoryRowGroup {
-            metadata: meta,
-            // schema: meta.schema_descr_ptr(),
-            row_count: meta.num_rows() as usize,
-            column_chunks: vec![None; meta.columns().len()],
-            offset_index,
+        let prefetched = match self.prefetched.front() {
+            Some(p) if p.row_group_idx == row_group_idx => self.prefetched.pop_front(),
+            _ => None,
         };
+        let coalesce_gap = self.fetch_options.coalesce_gap();
 
         if let Some(filter) = self.filter.as_mut() {
             for predicate in filter.predicates.iter_mut() {
@@ -525,12 +653,18 @@ where
                 }
 
                 let predicate_projection = predicate.projection();
+                let mut input =
+                    PrefetchReader::new(&mut self.input, prefetched.as_ref(), coalesce_gap);
                 row_group
-                    .fetch(&mut self.input, predicate_projection, selection.as_ref())
+                    .fetch(&mut input, predicate_projection, selection.as_ref())
                     .await?;
 
-                let array_reader =
-                    build_array_reader(self.fields.as_deref(), predicate_projection, &row_group)?;
+                let array_reader = build_parallel_array_reader(
+                    self.fields.as_deref(),
+                    predicate_projection,
+                    &row_group,
+                    self.executor.as_ref(),
+                )?;
 
                 selection = Some(evaluate_predicate(
                     batch_size,
@@ -574,18 +708,124 @@ where
             *limit -= rows_after;
         }
 
+        let mut input = PrefetchReader::new(&mut self.input, prefetched.as_ref(), coalesce_gap);
         row_group
-            .fetch(&mut self.input, &projection, selection.as_ref())
+            .fetch(&mut input, &projection, selection.as_ref())
             .await?;
 
         let reader = ParquetRecordBatchReader::new(
             batch_size,
-            build_array_reader(self.fields.as_deref(), &projection, &row_group)?,
+            build_parallel_array_reader(
+                self.fields.as_deref(),
+                &projection,
+                &row_group,
+                self.executor.as_ref(),
+            )?,
             selection,
         );
 
         Ok((self, Some(reader)))
     }
+
+    /// Fetches the data of the upcoming `row_groups`, with their corresponding selections,
+    /// ahead of them being read by [`Self::read_row_group`]
+    ///
+    /// Row groups are fetched in order until the total size of the prefetched data would
+    /// exceed [`FetchOptions::max_prefetch_bytes`]
+    async fn prefetch(
+        mut self,
+        row_groups: Vec<(usize, Option<RowSelection>)>,
+        projection: ProjectionMask,
+    ) -> Result<Self> {
+        if self.limit == Some(0) {
+            return Ok(self);
+        }
+
+        // Fetch the columns of the projection and of any predicates
+        let schema = self.metadata.file_metadata().schema_descr();
+        let included = (0..schema.num_columns()).filter(|idx| {
+            projection.leaf_included(*idx)
+                || self.filter.as_ref().is_some_and(|filter| {
+                    let mut predicates = filter.predicates.iter();
+                    predicates.any(|p| p.projection().leaf_included(*idx))
+                })
+        });
+        let projection = ProjectionMask::leaves(schema, included);
+
+        let coalesce_gap = self.fetch_options.coalesce_gap();
+        let mut buffered: usize = self.prefetched.iter().map(|p| p.size()).sum();
+        let mut planned = vec![];
+        for (row_group_idx, selection) in row_groups {
+            if self
+                .prefetched
+                .iter()
+                .any(|p| p.row_group_idx == row_group_idx)
+            {
+                continue;
+            }
+            if !selects_any(selection.as_ref()) {
+                continue;
+            }
+
+            let row_group = InMemoryRowGroup::new(&self.metadata, row_group_idx);
+            let ranges: Vec<_> = row_group
+                .fetch_ranges(&projection, selection.as_ref())
+                .into_iter()
+                .flat_map(|(_, ranges)| ranges)
+                .collect();
+
+            // Coalesce the ranges of each row group separately, such that the bytes
+            // between them are counted towards the limit and no buffer is shared with
+            // another row group
+            let ranges = fetch::coalesce_ranges(&ranges, coalesce_gap);
+            let size = ranges.iter().map(|r| r.len()).sum::<usize>();
+            match self.fetch_options.max_prefetch_bytes() {
+                Some(max) if buffered + size > max => break,
+                _ => buffered += size,
+            }
+            planned.push((row_group_idx, ranges));
+        }
+
+        if planned.is_empty() {
+            return Ok(self);
+        }
+
+        let ranges: Vec<_> = planned
+            .iter()
+            .flat_map(|(_, r)| r.iter().cloned())
+            .collect();
+        let num_ranges = ranges.len();
+        let fetched = self.input.get_byte_ranges(ranges).await?;
+        if fetched.len() != num_ranges {
+            return Err(general_err!(
+                "expected {} byte ranges but got {}",
+                num_ranges,
+                fetched.len()
+            ));
+        }
+
+        let mut fetched = fetched.into_iter();
+        for (row_group_idx, ranges) in planned {
+            let data = ranges
+                .into_iter()
+                .zip(fetched.by_ref())
+                .map(|(range, data)| match data.len() == range.len() {
+                    true => Ok((range, data)),
+                    false => Err(general_err!(
+                        "expected {} bytes for range {:?} but got {}",
+                        range.len(),
+                        range,
+                        data.len()
+                    )),
+                })
+                .collect::<Result<_>>()?;
+            self.prefetched.push_back(PrefetchedRowGroup {
+                row_group_idx,
+                data,
+            });
+        }
+        Ok(self)
+    }
 }
 
 enum StreamState<T> {
@@ -628,6 +868,10 @@ pub struct ParquetRecordBatchStream<T> {
     /// This is an option so it can be moved into a future
     reader: Option<ReaderFactory<T>>,
 
+    /// Fetches upcoming row groups whilst the current row group is decoded, taking
+    /// ownership of the [`ReaderFactory`] until complete
+    prefetch: Option<BoxFuture<'static, Result<ReaderFactory<T>>>>,
+
     state: StreamState<T>,
 }
 
@@ -653,6 +897,28 @@ impl<T> ParquetRecordBatchStream<T> {
     }
 }
 
+impl<T> ParquetRecordBatchStream<T>
+where
+    T: AsyncFileReader + Unpin + Send + 'static,
+{
+    /// Starts fetching the upcoming row groups, if enabled by [`FetchOptions`]
+    fn start_prefetch(&mut self, reader: ReaderFactory<T>) {
+        let num_row_groups = reader.fetch_options.prefetch_row_groups();
+        if num_row_groups == 0 || self.row_groups.is_empty() {
+            self.reader = Some(reader);
+            return;
+        }
+
+        let mut selection = self.selection.clone();
+        let row_groups = self.row_groups.iter().take(num_row_groups).map(|idx| {
+            let row_count = self.metadata.row_group(*idx).num_rows() as usize;
+            (*idx, selection.as_mut().map(|s| s.split_off(row_count)))
+        });
+        let fut = reader.prefetch(row_groups.collect(), self.projection.clone());
+        self.prefetch = Some(fut.boxed());
+    }
+}
+
 impl<T> Stream for ParquetRecordBatchStream<T>
 where
     T: AsyncFileReader + Unpin + Send + 'static,
@@ -661,6 +927,21 @@ where
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         loop {
+            // Make progress on any prefetch whilst decoding
+            if let StreamState::Decoding(_) = &self.state {
+                if let Some(Poll::Ready(result)) = self.prefetch.as_mut().map(|f| f.poll_unpin(cx))
+                {
+                    self.prefetch = None;
+                    match result {
+                        Ok(reader_factory) => self.reader = Some(reader_factory),
+                        Err(e) => {
+                            self.state = StreamState::Error;
+                            return Poll::Ready(Some(Err(e)));
+                        }
+                    }
+                }
+            }
+
             match &mut self.state {
                 StreamState::Decoding(batch_reader) => match batch_reader.next() {
                     Some(Ok(batch)) => {
@@ -678,33 +959,41 @@ where
                         None => return Poll::Ready(None),
                     };
 
-                    let reader = self.reader.take().expect("lost reader");
-
                     let row_count = self.metadata.row_group(row_group_idx).num_rows() as usize;
 
                     let selection = self.selection.as_mut().map(|s| s.split_off(row_count));
-
-                    let fut = reader
-                        .read_row_group(
-                            row_group_idx,
-                            selection,
-                            self.projection.clone(),
-                            self.batch_size,
-                        )
-                        .boxed();
+                    let projection = self.projection.clone();
+                    let batch_size = self.batch_size;
+
+                    let fut = match (self.reader.take(), self.prefetch.take()) {
+                        (Some(reader), _) => reader
+                            .read_row_group(row_group_idx, selection, projection, batch_size)
+                            .boxed(),
+                        (None, Some(prefetch)) => async move {
+                            prefetch
+                                .await?
+                                .read_row_group(row_group_idx, selection, projection, batch_size)
+                                .await
+                        }
+                        .boxed(),
+                        (None, None) => panic!("lost reader"),
+                    };
 
                     self.state = StreamState::Reading(fut)
                 }
                 StreamState::Reading(f) => match ready!(f.poll_unpin(cx)) {
-                    Ok((reader_factory, maybe_reader)) => {
-                        self.reader = Some(reader_factory);
-                        match maybe_reader {
-                            // Read records from [`ParquetRecordBatchReader`]
-                            Some(reader) => self.state = StreamState::Decoding(reader),
-                            // All rows skipped, read next row group
-                            None => self.state = StreamState::Init,
+                    Ok((reader_factory, maybe_reader)) => match maybe_reader {
+                        // Read records from [`ParquetRecordBatchReader`]
+                        Some(reader) => {
+                            self.start_prefetch(reader_factory);
+                            self.state = StreamState::Decoding(reader)
                         }
-                    }
+                        // All rows skipped, read next row group
+                        None => {
+                            self.reader = Some(reader_factory);
+                            self.state = StreamState::Init
+                        }
+                    },
                     Err(e) => {
                         self.state = StreamState::Error;
                         return Poll::Ready(Some(Err(e)));
@@ -722,9 +1011,77 @@ struct InMemoryRowGroup<'a> {
     offset_index: Option<&'a [OffsetIndexMetaData]>,
     column_chunks: Vec<Option<Arc<ColumnChunkData>>>,
     row_count: usize,
+    /// The metadata of the file, used to decrypt the pages of encrypted columns
+    #[cfg(feature = "encryption")]
+    parquet_metadata: &'a ParquetMetaData,
+    /// The index of this row group in `parquet_metadata`
+    #[cfg(feature = "encryption")]
+    row_group_idx: usize,
 }
 
 impl<'a> InMemoryRowGroup<'a> {
+    /// Creates an [`InMemoryRowGroup`] for `row_group_idx` with no column chunks fetched
+    fn new(metadata: &'a ParquetMetaData, row_group_idx: usize) -> Self {
+        let meta = metadata.row_group(row_group_idx);
+        let offset_index = metadata
+            .offset_index()
+            // filter out empty offset indexes (old versions specified Some(vec![]) when no present)
+            .filter(|index| !index.is_empty())
+            .map(|x| x[row_group_idx].as_slice());
+
+        Self {
+            metadata: meta,
+            row_count: meta.num_rows() as usize,
+            column_chunks: vec![None; meta.columns().len()],
+            offset_index,
+            #[cfg(feature = "encryption")]
+            parquet_metadata: metadata,
+            #[cfg(feature = "encryption")]
+            row_group_idx,
+        }
+    }
+
+    /// Returns the byte ranges to fetch for each column in `projection` that has not
+    /// already been fetched
+    fn fetch_ranges(
+        &self,
+        projection: &ProjectionMask,
+        selection: Option<&RowSelection>,
+    ) -> Vec<(usize, Vec<Range<usize>>)> {
+        self.column_chunks
+            .iter()
+            .zip(self.metadata.columns())
+            .enumerate()
+            .filter(|&(idx, (chunk, _chunk_meta))| chunk.is_none() && projection.leaf_included(idx))
+            .map(|(idx, (_chunk, chunk_meta))| {
+                let (start, length) = chunk_meta.byte_range();
+                let ranges = match selection.zip(self.offset_index) {
+                    // If we have a `RowSelection` and an `OffsetIndex` then only fetch pages required
+                    // for the `RowSelection`
+                    Some((selection, offset_index)) => {
+                        // If the first page does not start at the beginning of the column,
+                        // then we need to also fetch a dictionary page.
+                        let mut ranges = vec![];
+                        match offset_index[idx].page_locations.first() {
+                            Some(first) if first.offset as u64 != start => {
+                                ranges.push(start as usize..first.offset as usize);
+                            }
+                            _ => (),
+                        }
+
+                        ranges.extend(selection.scan_ranges(&offset_index[idx].page_locations));
+                        ranges
+                    }
+                    None => {
+                        let range = start as usize..(start + length) as usize;
+                        vec![range]
+                    }
+                };
+                (idx, ranges)
+            })
+            .collect()
+    }
+
     /// Fetches the necessary column data into memory
     async fn fetch<T: AsyncFileReader + Send>(
         &mut self,
@@ -732,85 +1089,30 @@ impl<'a> InMemoryRowGroup<'a> {
         projection: &ProjectionMask,
         selection: Option<&RowSelection>,
     ) -> Result<()> {
-        if let Some((selection, offset_index)) = selection.zip(self.offset_index) {
-            // If we have a `RowSelection` and an `OffsetIndex` then only fetch pages required for the
-            // `RowSelection`
-            let mut page_start_offsets: Vec<Vec<usize>> = vec![];
-
-            let fetch_ranges = self
-                .column_chunks
-                .iter()
-                .zip(self.metadata.columns())
-                .enumerate()
-                .filter(|&(idx, (chunk, _chunk_meta))| {
-                    chunk.is_none() && projection.leaf_included(idx)
-                })
-                .flat_map(|(idx, (_chunk, chunk_meta))| {
-                    // If the first page does not start at the beginning of the column,
-                    // then we need to also fetch a dictionary page.
-                    let mut ranges = vec![];
-                    let (start, _len) = chunk_meta.byte_range();
-                    match offset_index[idx].page_locations.first() {
-                        Some(first) if first.offset as u64 != start => {
-                            ranges.push(start as usize..first.offset as usize);
-                        }
-                        _ => (),
-                    }
-
-                    ranges.extend(selection.scan_ranges(&offset_index[idx].page_locations));
-                    page_start_offsets.push(ranges.iter().map(|range| range.start).collect());
-
-                    ranges
-                })
-                .collect();
-
-            let mut chunk_data = input.get_byte_ranges(fetch_ranges).await?.into_iter();
-            let mut page_start_offsets = page_start_offsets.into_iter();
-
-            for (idx, chunk) in self.column_chunks.iter_mut().enumerate() {
-                if chunk.is_some() || !projection.leaf_included(idx) {
-                    continue;
-                }
-
-                if let Some(offsets) = page_start_offsets.next() {
-                    let mut chunks = Vec::with_capacity(offsets.len());
-                    for _ in 0..offsets.len() {
-                        chunks.push(chunk_data.next().unwrap());
-                    }
-
-                    *chunk = Some(Arc::new(ColumnChunkData::Sparse {
-                        length: self.metadata.column(idx).byte_range().1 as usize,
-                        data: offsets.into_iter().zip(chunks.into_iter()).collect(),
-                    }))
-                }
-            }
-        } else {
-            let fetch_ranges = self
-                .column_chunks
-                .iter()
-                .enumerate()
-                .filter(|&(idx, chunk)| chunk.is_none() && projection.leaf_included(idx))
-                .map(|(idx, _chunk)| {
-                    let column = self.metadata.column(idx);
-                    let (start, length) = column.byte_range();
-                    start as usize..(start + length) as usize
-                })
-                .collect();
-
-            let mut chunk_data = input.get_byte_ranges(fetch_ranges).await?.into_iter();
-
-            for (idx, chunk) in self.column_chunks.iter_mut().enumerate() {
-                if chunk.is_some() || !projection.leaf_included(idx) {
-                    continue;
-                }
-
-                if let Some(data) = chunk_data.next() {
-                    *chunk = Some(Arc::new(ColumnChunkData::Dense {
-                        offset: self.metadata.column(idx).byte_range().0 as usize,
+        let sparse = selection.is_some() && self.offset_index.is_some();
+        let fetch_ranges = self.fetch_ranges(projection, selection);
+
+        let ranges = fetch_ranges.iter().flat_map(|(_, r)| r.iter().cloned());
+        let mut chunk_data = input.get_byte_ranges(ranges.collect()).await?.into_iter();
+
+        for (idx, ranges) in fetch_ranges {
+            let chunk = match sparse {
+                true => ColumnChunkData::Sparse {
+                    length: self.metadata.column(idx).byte_range().1 as usize,
+                    data: ranges
+                        .iter()
+                        .map(|range| (range.start, chunk_data.next().unwrap()))
+                        .collect(),
+                },
+                false => match chunk_data.next() {
+                    Some(data) => ColumnChunkData::Dense {
+                        offset: ranges[0].start,
                         data,
-                    }));
-                }
-            }
+                    },
+                    None => continue,
+                },
+            };
+            self.column_chunks[idx] = Some(Arc::new(chunk));
         }
 
         Ok(())
@@ -833,12 +1135,19 @@ impl RowGroups for InMemoryRowGroup<'_> {
                     // filter out empty offset indexes (old versions specified Some(vec![]) when no present)
                     .filter(|index| !index.is_empty())
                     .map(|index| index[i].page_locations.clone());
-                let page_reader: Box<dyn PageReader> = Box::new(SerializedPageReader::new(
+                let page_reader = SerializedPageReader::new(
                     data.clone(),
                     self.metadata.column(i),
                     self.row_count,
                     page_locations,
+                )?;
+                #[cfg(feature = "encryption")]
+                let page_reader = page_reader.with_crypto_context(CryptoContext::for_column(
+                    self.parquet_metadata,
+                    self.row_group_idx,
+                    i,
                 )?);
+                let page_reader: Box<dyn PageReader> = Box::new(page_reader);
 
                 Ok(Box::new(ColumnChunkIterator {
                     reader: Some(Ok(page_reader)),
@@ -922,26 +1231,28 @@ impl PageIterator for ColumnChunkIterator {}
 mod tests {
     use super::*;
     use crate::arrow::arrow_reader::{
-        ArrowPredicateFn, ParquetRecordBatchReaderBuilder, RowSelector,
+        ArrowPredicateFn, ParquetRecordBatchReaderBuilder, RowSelector, ScopedThreadExecutor,
     };
     use crate::arrow::schema::parquet_to_arrow_schema_and_fields;
     use crate::arrow::ArrowWriter;
     use crate::file::metadata::ParquetMetaDataReader;
     use crate::file::page_index::index_reader;
     use crate::file::properties::WriterProperties;
+    use arrow::compute::concat_batches;
     use arrow::compute::kernels::cmp::eq;
     use arrow::error::Result as ArrowResult;
     use arrow_array::builder::{ListBuilder, StringBuilder};
     use arrow_array::cast::AsArray;
-    use arrow_array::types::Int32Type;
+    use arrow_array::types::{Int32Type, Int64Type};
     use arrow_array::{
-        Array, ArrayRef, Int32Array, Int8Array, RecordBatchReader, Scalar, StringArray,
+        Array, ArrayRef, Int32Array, Int64Array, Int8Array, RecordBatchReader, Scalar, StringArray,
         StructArray, UInt64Array,
     };
     use arrow_schema::{DataType, Field, Schema};
     use futures::{StreamExt, TryStreamExt};
     use rand::{thread_rng, Rng};
     use std::collections::HashMap;
+    use std::num::NonZeroUsize;
     use std::sync::{Arc, Mutex};
     use tempfile::tempfile;
 
@@ -1614,6 +1925,9 @@ mod tests {
             filter: None,
             limit: None,
             offset: None,
+            executor: None,
+            fetch_options: FetchOptions::default(),
+            prefetched: VecDeque::new(),
         };
 
         let mut skip = true;
@@ -1866,6 +2180,296 @@ mod tests {
         assert!(!sbbf.check(&"Hello_Not_Exists"));
     }
 
+    #[tokio::test]
+    async fn test_prune_row_groups() {
+        let ids = Int64Array::from_iter_values(0..1000);
+        let names = StringArray::from_iter_values((0..1000).map(|i| format!("{}", i % 7)));
+        let batch = RecordBatch::try_from_iter([
+            ("id", Arc::new(ids) as ArrayRef),
+            ("name", Arc::new(names) as ArrayRef),
+        ])
+        .unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(250)
+            .set_column_bloom_filter_enabled("id".into(), true)
+            .build();
+        let mut buf = Vec::with_capacity(1024);
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let data: Bytes = buf.into();
+        let metadata = ParquetMetaDataReader::new()
+            .parse_and_finish(&data)
+            .unwrap();
+        let async_reader = TestReader {
+            data,
+            metadata: Arc::new(metadata),
+            requests: Default::default(),
+        };
+        let requests = async_reader.requests.clone();
+
+        let mut builder = ParquetRecordBatchStreamBuilder::new(async_reader)
+            .await
+            .unwrap();
+
+        // Statistics exclude all but row group 2 without any IO
+        let values = Arc::new(Int64Array::from(vec![600]));
+        let predicates = [EqualityPredicate::new("id", values)];
+        requests.lock().unwrap().clear();
+        let row_groups = builder.prune_row_groups(&predicates).await.unwrap();
+        assert_eq!(row_groups, vec![2]);
+        // Only the bloom filter for row group 2 is fetched
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        // Statistics for name can not exclude any row groups, and there is no bloom filter
+        let values = Arc::new(StringArray::from(vec!["3"]));
+        let predicates = [EqualityPredicate::new("name", values)];
+        let row_groups = builder.prune_row_groups(&predicates).await.unwrap();
+        assert_eq!(row_groups, vec![0, 1, 2, 3]);
+
+        let stream = builder.with_row_groups(vec![2]).build().unwrap();
+        let batches: Vec<_> = stream.try_collect().await.unwrap();
+        let ids = batches[0].column(0).as_primitive::<Int64Type>();
+        assert_eq!(ids.value(0), 500);
+    }
+
+    #[tokio::test]
+    async fn test_decode_executor() {
+        let ids = Int64Array::from_iter_values(0..1000);
+        let names = StringArray::from_iter_values((0..1000).map(|i| format!("{}", i % 7)));
+        let values = Int32Array::from_iter((0..1000).map(|i| (i % 3 != 0).then_some(i)));
+        let batch = RecordBatch::try_from_iter([
+            ("id", Arc::new(ids) as ArrayRef),
+            ("name", Arc::new(names) as ArrayRef),
+            ("value", Arc::new(values) as ArrayRef),
+        ])
+        .unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(300)
+            .build();
+        let mut buf = Vec::with_capacity(1024);
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let data: Bytes = buf.into();
+
+        let selection = RowSelection::from(vec![
+            RowSelector::skip(100),
+            RowSelector::select(600),
+            RowSelector::skip(50),
+            RowSelector::select(250),
+        ]);
+        let metadata = ParquetMetaDataReader::new()
+            .parse_and_finish(&data)
+            .unwrap();
+        let async_reader = TestReader {
+            data: data.clone(),
+            metadata: Arc::new(metadata),
+            requests: Default::default(),
+        };
+
+        let executor = Arc::new(ScopedThreadExecutor::new(NonZeroUsize::new(3).unwrap()));
+        let builder = ParquetRecordBatchStreamBuilder::new(async_reader)
+            .await
+            .unwrap();
+        let mask = ProjectionMask::leaves(builder.parquet_schema(), [1]);
+        let filter = RowFilter::new(vec![Box::new(ArrowPredicateFn::new(mask, |batch| {
+            eq(batch.column(0), &Scalar::new(StringArray::from(vec!["2"])))
+        }))]);
+        let stream = builder
+            .with_batch_size(64)
+            .with_row_selection(selection.clone())
+            .with_row_filter(filter)
+            .with_decode_executor(executor)
+            .build()
+            .unwrap();
+        let actual: Vec<_> = stream.try_collect().await.unwrap();
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(data).unwrap();
+        let mask = ProjectionMask::leaves(builder.parquet_schema(), [1]);
+        let filter = RowFilter::new(vec![Box::new(ArrowPredicateFn::new(mask, |batch| {
+            eq(batch.column(0), &Scalar::new(StringArray::from(vec!["2"])))
+        }))]);
+        let expected = builder
+            .with_batch_size(64)
+            .with_row_selection(selection)
+            .with_row_filter(filter)
+            .build()
+            .unwrap()
+            .collect::<ArrowResult<Vec<_>>>()
+            .unwrap();
+
+        // The batches may be split differently when decoding in parallel
+        assert!(!expected.is_empty());
+        let schema = expected[0].schema();
+        assert_eq!(
+            concat_batches(&schema, &actual).unwrap(),
+            concat_batches(&schema, &expected).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_options() {
+        let ids = Int64Array::from_iter_values(0..1000);
+        let names = StringArray::from_iter_values((0..1000).map(|i| format!("{}", i % 7)));
+        let values = Int32Array::from_iter((0..1000).map(|i| (i % 3 != 0).then_some(i)));
+        let batch = RecordBatch::try_from_iter([
+            ("id", Arc::new(ids) as ArrayRef),
+            ("name", Arc::new(names) as ArrayRef),
+            ("value", Arc::new(values) as ArrayRef),
+        ])
+        .unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(300)
+            .set_data_page_row_count_limit(50)
+            .set_write_batch_size(50)
+            .build();
+        let mut buf = Vec::with_capacity(1024);
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let data: Bytes = buf.into();
+
+        let metadata = ParquetMetaDataReader::new()
+            .with_page_indexes(true)
+            .parse_and_finish(&data)
+            .unwrap();
+        let metadata = Arc::new(metadata);
+        assert_eq!(metadata.num_row_groups(), 4);
+
+        // The row group containing each byte range
+        let row_group_of = |range: &Range<usize>| {
+            metadata
+                .row_groups()
+                .iter()
+                .position(|rg| {
+                    let columns = rg.columns().iter().map(|c| c.byte_range());
+                    let start = columns.clone().map(|(s, _)| s).min().unwrap() as usize;
+                    let end = columns.map(|(s, l)| s + l).max().unwrap() as usize;
+                    start <= range.start && range.end <= end
+                })
+                .unwrap()
+        };
+
+        let selection = RowSelection::from(vec![
+            RowSelector::skip(100),
+            RowSelector::select(600),
+            RowSelector::skip(50),
+            RowSelector::select(250),
+        ]);
+        let predicate = || {
+            let mask = ProjectionMask::leaves(metadata.file_metadata().schema_descr(), [1]);
+            RowFilter::new(vec![Box::new(ArrowPredicateFn::new(mask, |batch| {
+                eq(batch.column(0), &Scalar::new(StringArray::from(vec!["2"])))
+            }))])
+        };
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(data.clone()).unwrap();
+        let expected = builder
+            .with_batch_size(64)
+            .with_row_selection(selection.clone())
+            .with_row_filter(predicate())
+            .build()
+            .unwrap()
+            .collect::<ArrowResult<Vec<_>>>()
+            .unwrap();
+        let schema = expected[0].schema();
+        let expected = concat_batches(&schema, &expected).unwrap();
+
+        let read = |options: FetchOptions| {
+            let async_reader = TestReader {
+                data: data.clone(),
+                metadata: metadata.clone(),
+                requests: Default::default(),
+            };
+            let requests = async_reader.requests.clone();
+            let reader_options = ArrowReaderOptions::new().with_page_index(true);
+            let builder =
+                ParquetRecordBatchStreamBuilder::new_with_options(async_reader, reader_options);
+            let filter = predicate();
+            let selection = selection.clone();
+            let schema = schema.clone();
+            async move {
+                let mut stream = builder
+                    .await
+                    .unwrap()
+                    .with_batch_size(64)
+                    .with_row_selection(selection)
+                    .with_row_filter(filter)
+                    .with_fetch_options(options)
+                    .build()
+                    .unwrap();
+
+                let first = stream.next().await.unwrap().unwrap();
+                let after_first: Vec<_> = requests.lock().unwrap().clone();
+                let mut batches = vec![first];
+                batches.extend(stream.try_collect::<Vec<_>>().await.unwrap());
+                let all: Vec<_> = requests.lock().unwrap().clone();
+                // Prefetching may change how the batches are split
+                let batches = concat_batches(&schema, &batches).unwrap();
+                (batches, after_first, all)
+            }
+        };
+
+        // Without prefetching, only the first row group is fetched before the first batch
+        let (batches, after_first, all) = read(FetchOptions::new()).await;
+        assert_eq!(batches, expected);
+        assert!(after_first.iter().all(|r| row_group_of(r) == 0));
+        let num_requests = all.len();
+
+        // Coalescing merges the pages of each fetch into a single request
+        let options = FetchOptions::new().with_coalesce_gap(usize::MAX);
+        let (batches, _, all) = read(options).await;
+        assert_eq!(batches, expected);
+        // One request for the predicate and one for the projection of each row group
+        assert_eq!(all.len(), 8);
+        assert!(all.len() < num_requests);
+
+        // The next two row groups are fetched whilst decoding the first
+        let options = FetchOptions::new().with_prefetch_row_groups(2);
+        let (batches, after_first, all) = read(options).await;
+        assert_eq!(batches, expected);
+        let row_groups: Vec<_> = after_first.iter().map(row_group_of).collect();
+        assert!(row_groups.contains(&1) && row_groups.contains(&2));
+        assert!(!row_groups.contains(&3));
+        // Prefetched data is not fetched again
+        assert_eq!(all.len(), num_requests);
+
+        // Row groups are not prefetched beyond the limit on buffered bytes
+        let options = FetchOptions::new()
+            .with_prefetch_row_groups(2)
+            .with_max_prefetch_bytes(1);
+        let (batches, after_first, all) = read(options).await;
+        assert_eq!(batches, expected);
+        assert!(after_first.iter().all(|r| row_group_of(r) == 0));
+        assert_eq!(all.len(), num_requests);
+
+        // The size of the pages prefetched for the second and third row groups
+        let options = FetchOptions::new().with_prefetch_row_groups(2);
+        let (_, after_first, _) = read(options).await;
+        let prefetched: usize = after_first
+            .iter()
+            .filter(|r| row_group_of(r) != 0)
+            .map(|r| r.len())
+            .sum();
+
+        // The bytes between coalesced ranges count towards the limit, with the pages
+        // skipped in the third row group exceeding it
+        let options = FetchOptions::new()
+            .with_coalesce_gap(usize::MAX)
+            .with_prefetch_row_groups(2)
+            .with_max_prefetch_bytes(prefetched);
+        let (batches, after_first, _) = read(options).await;
+        assert_eq!(batches, expected);
+        let row_groups: Vec<_> = after_first.iter().map(row_group_of).collect();
+        assert!(row_groups.contains(&1));
+        assert!(!row_groups.contains(&2));
+    }
+
     #[tokio::test]
     async fn test_nested_skip() {
         let schema = Arc::new(Schema::new(vec![
@@ -22,6 +22,8 @@ use futures::{future::BoxFuture, FutureExt, TryFutureExt};
 use object_store::{path::Path, ObjectMeta, ObjectStore};
 use tokio::runtime::Handle;
 
+#[cfg(feature = "encryption")]
+use crate::arrow::arrow_reader::ArrowReaderOptions;
 use crate::arrow::async_reader::AsyncFileReader;
 use crate::errors::{ParquetError, Result};
 use crate::file::metadata::{ParquetMetaData, ParquetMetaDataReader};
@@ -175,6 +177,24 @@ impl AsyncFileReader for ParquetObjectReader {
             Ok(Arc::new(metadata))
         })
     }
+
+    #[cfg(feature = "encryption")]
+    fn get_metadata_with_options<'a>(
+        &'a mut self,
+        options: &'a ArrowReaderOptions,
+    ) -> BoxFuture<'a, Result<Arc<ParquetMetaData>>> {
+        Box::pin(async move {
+            let file_size = self.meta.size;
+            let metadata = ParquetMetaDataReader::new()
+                .with_column_indexes(self.preload_column_index)
+                .with_offset_indexes(self.preload_offset_index)
+                .with_prefetch_hint(self.metadata_size_hint)
+                .with_decryption_properties(options.file_decryption_properties.as_ref())
+                .load_and_finish(self, file_size)
+                .await?;
+            Ok(Arc::new(metadata))
+        })
+    }
 }
 
 #[cfg(test)]
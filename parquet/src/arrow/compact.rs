@@ -0,0 +1,855 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Merging of parquet files into well-sized, optionally sorted, row groups,
+//! see [`Compactor`]
+
+use std::cmp::Ordering;
+use std::collections::binary_heap::{BinaryHeap, PeekMut};
+use std::io::Write;
+use std::sync::Arc;
+
+use arrow_array::{new_null_array, Array, ArrayRef, RecordBatch, UInt32Array};
+use arrow_row::{OwnedRow, RowConverter, Rows, SortField};
+use arrow_schema::{SchemaRef, SortOptions};
+use arrow_select::concat::{concat, concat_batches};
+use arrow_select::interleave::interleave;
+use arrow_select::take::take_record_batch;
+use bytes::Bytes;
+
+use crate::arrow::arrow_reader::statistics::StatisticsConverter;
+use crate::arrow::arrow_reader::{
+    ArrowReaderMetadata, ArrowReaderOptions, ParquetRecordBatchReader,
+    ParquetRecordBatchReaderBuilder,
+};
+use crate::arrow::ArrowWriter;
+use crate::errors::{ParquetError, Result};
+use crate::file::properties::WriterProperties;
+use crate::file::reader::{ChunkReader, Length};
+use crate::format::{FileMetaData, SortingColumn};
+
+/// Merges the row groups of one or more parquet files into a single file with
+/// well-sized row groups, optionally sorted by the [`SortingColumn`]s of its
+/// [`WriterProperties`]
+///
+/// Inputs are added with [`Self::add_input`], and the output is written by
+/// [`Self::close`]. Only the metadata of the inputs is held in memory, with their
+/// data streamed from the inputs as it is written.
+///
+/// A row group is copied verbatim, without decoding, if it:
+///
+/// * has between [`Self::with_min_copy_rows`] and [`WriterProperties::max_row_group_size`] rows
+/// * has the same parquet schema as the output
+/// * when sorting, declares the same [`SortingColumn`]s as the output in its metadata,
+///   and does not overlap any other row group according to the statistics of the
+///   first sorting column, which is never known for floating point columns as their
+///   statistics exclude `NaN`
+///
+/// Other row groups are decoded and re-encoded into row groups of up to
+/// [`WriterProperties::max_row_group_size`] rows. When sorting, overlapping row groups
+/// are merged, such that the output is sorted and its row groups declare the
+/// [`SortingColumn`]s in their metadata. Otherwise the rows are written in the order
+/// of the inputs.
+///
+/// Row groups that do not declare the [`SortingColumn`]s are first sorted in memory,
+/// one at a time, and buffered in encoded form until merged. Memory usage is therefore
+/// proportional to the largest such row group, and the encoded size of those that
+/// overlap one another, which if the sort keys of any row group are unknown is all
+/// such row groups.
+///
+/// Sorting is only supported on top-level columns, that is columns not nested within
+/// a struct or list.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use arrow_array::{ArrayRef, Int32Array, RecordBatch};
+/// # use bytes::Bytes;
+/// # use parquet::arrow::ArrowWriter;
+/// # use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+/// # use parquet::arrow::compact::Compactor;
+/// # use parquet::file::properties::WriterProperties;
+/// # use parquet::format::SortingColumn;
+/// # fn write(values: Vec<i32>) -> Bytes {
+/// #     let a = Arc::new(Int32Array::from(values)) as ArrayRef;
+/// #     let batch = RecordBatch::try_from_iter([("a", a)]).unwrap();
+/// #     let mut buf = vec![];
+/// #     let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), None).unwrap();
+/// #     writer.write(&batch).unwrap();
+/// #     writer.close().unwrap();
+/// #     buf.into()
+/// # }
+/// let a = write(vec![5, 1, 3]);
+/// let b = write(vec![4, 2, 6]);
+/// # let schema = ParquetRecordBatchReaderBuilder::try_new(a.clone()).unwrap().schema().clone();
+///
+/// // Sort by the first column
+/// let props = WriterProperties::builder()
+///     .set_sorting_columns(Some(vec![SortingColumn::new(0, false, true)]))
+///     .build();
+///
+/// let mut output = vec![];
+/// let mut compactor = Compactor::try_new(&mut output, schema, Some(props)).unwrap();
+/// compactor.add_input(a).unwrap();
+/// compactor.add_input(b).unwrap();
+/// compactor.close().unwrap();
+///
+/// let reader = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(output)).unwrap();
+/// let row_group = &reader.metadata().row_groups()[0];
+/// assert_eq!(row_group.sorting_columns().unwrap()[0].column_idx, 0);
+///
+/// let batch = reader.build().unwrap().next().unwrap().unwrap();
+/// let expected = Arc::new(Int32Array::from(vec![1, 2, 3, 4, 5, 6])) as ArrayRef;
+/// assert_eq!(batch.column(0), &expected);
+/// ```
+pub struct Compactor<W: Write + Send, R> {
+    writer: ArrowWriter<W>,
+    schema: SchemaRef,
+    inputs: Vec<Input<R>>,
+    sort: Option<SortKey>,
+    max_row_group_size: usize,
+    min_copy_rows: usize,
+    copy_row_groups: bool,
+    batch_size: usize,
+}
+
+impl<W: Write + Send, R> std::fmt::Debug for Compactor<W, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Compactor")
+            .field("schema", &self.schema)
+            .field("inputs", &self.inputs.len())
+            .field("max_row_group_size", &self.max_row_group_size)
+            .field("min_copy_rows", &self.min_copy_rows)
+            .field("copy_row_groups", &self.copy_row_groups)
+            .field("batch_size", &self.batch_size)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An input file of a [`Compactor`]
+struct Input<R> {
+    reader: SharedReader<R>,
+    metadata: ArrowReaderMetadata,
+    /// Whether this input has the same parquet schema as the output
+    same_schema: bool,
+}
+
+/// A [`ChunkReader`] that can be shared between multiple readers of the same input
+struct SharedReader<R>(Arc<R>);
+
+impl<R> Clone for SharedReader<R> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<R: ChunkReader> Length for SharedReader<R> {
+    fn len(&self) -> u64 {
+        self.0.len()
+    }
+}
+
+impl<R: ChunkReader> ChunkReader for SharedReader<R> {
+    type T = R::T;
+
+    fn get_read(&self, start: u64) -> Result<Self::T> {
+        self.0.get_read(start)
+    }
+
+    fn get_bytes(&self, start: u64, length: usize) -> Result<Bytes> {
+        self.0.get_bytes(start, length)
+    }
+}
+
+/// The columns by which the output of a [`Compactor`] is sorted
+struct SortKey {
+    sorting_columns: Vec<SortingColumn>,
+    /// The index of each sorting column in the arrow schema
+    columns: Vec<usize>,
+    converter: RowConverter,
+}
+
+impl SortKey {
+    fn convert(&self, batch: &RecordBatch) -> Result<Rows> {
+        let columns: Vec<_> = self
+            .columns
+            .iter()
+            .map(|i| batch.column(*i).clone())
+            .collect();
+        Ok(self.converter.convert_columns(&columns)?)
+    }
+
+    /// Sorts `batch`, preserving the order of equal rows
+    fn sort(&self, batch: &RecordBatch) -> Result<RecordBatch> {
+        let rows = self.convert(batch)?;
+        let mut indices: Vec<u32> = (0..batch.num_rows() as u32).collect();
+        indices.sort_by(|a, b| rows.row(*a as usize).cmp(&rows.row(*b as usize)));
+        Ok(take_record_batch(batch, &UInt32Array::from(indices))?)
+    }
+}
+
+/// A row group of an input
+struct Run {
+    input: usize,
+    row_group: usize,
+    /// Whether this row group can be copied verbatim, if it does not overlap another
+    copy: bool,
+    /// The lowest and highest sort key of the first sorting column, if known
+    bounds: Option<(OwnedRow, OwnedRow)>,
+}
+
+impl<W: Write + Send, R: ChunkReader + 'static> Compactor<W, R> {
+    /// Create a new [`Compactor`] writing to `writer`, with the provided arrow `schema`
+    /// and [`WriterProperties`]
+    ///
+    /// The output is sorted if [`WriterProperties::sorting_columns`] is set
+    pub fn try_new(writer: W, schema: SchemaRef, props: Option<WriterProperties>) -> Result<Self> {
+        let props = props.unwrap_or_default();
+        let sorting_columns = props.sorting_columns().cloned();
+        let max_row_group_size = props.max_row_group_size();
+        let batch_size = props.write_batch_size();
+        let writer = ArrowWriter::try_new(writer, schema.clone(), Some(props))?;
+
+        let sort = match sorting_columns {
+            Some(sorting_columns) if !sorting_columns.is_empty() => {
+                let parquet_schema = writer.parquet_schema();
+                let mut columns = Vec::with_capacity(sorting_columns.len());
+                let mut fields = Vec::with_capacity(sorting_columns.len());
+                for sorting_column in &sorting_columns {
+                    let idx = sorting_column.column_idx as usize;
+                    if idx >= parquet_schema.num_columns() {
+                        return Err(general_err!(
+                            "sorting column {} out of bounds 0..{}",
+                            idx,
+                            parquet_schema.num_columns()
+                        ));
+                    }
+                    let path = parquet_schema.column(idx).path().clone();
+                    let column = match path.parts() {
+                        [name] => schema.index_of(name)?,
+                        _ => return Err(nyi_err!("sorting by nested column {}", path)),
+                    };
+                    let options = SortOptions {
+                        descending: sorting_column.descending,
+                        nulls_first: sorting_column.nulls_first,
+                    };
+                    let data_type = schema.field(column).data_type().clone();
+                    fields.push(SortField::new_with_options(data_type, options));
+                    columns.push(column);
+                }
+                Some(SortKey {
+                    sorting_columns,
+                    columns,
+                    converter: RowConverter::new(fields)?,
+                })
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            writer,
+            schema,
+            inputs: vec![],
+            sort,
+            max_row_group_size,
+            min_copy_rows: max_row_group_size / 2,
+            copy_row_groups: true,
+            batch_size,
+        })
+    }
+
+    /// Only copy row groups verbatim if they have at least `rows` rows, defaults to
+    /// half of [`WriterProperties::max_row_group_size`]
+    ///
+    /// Smaller row groups are re-encoded, merging them into larger row groups
+    pub fn with_min_copy_rows(self, rows: usize) -> Self {
+        Self {
+            min_copy_rows: rows,
+            ..self
+        }
+    }
+
+    /// Whether to copy row groups verbatim where possible, defaults to `true`
+    ///
+    /// If `false`, all row groups are decoded and re-encoded, for example to apply
+    /// different [`WriterProperties`]
+    pub fn with_copy_row_groups(self, copy_row_groups: bool) -> Self {
+        Self {
+            copy_row_groups,
+            ..self
+        }
+    }
+
+    /// Set the number of rows decoded and encoded at a time, defaults to
+    /// [`WriterProperties::write_batch_size`]
+    pub fn with_batch_size(self, batch_size: usize) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            ..self
+        }
+    }
+
+    /// Add an input file
+    ///
+    /// Returns an error if the file cannot be read with the schema of the output
+    pub fn add_input(&mut self, reader: R) -> Result<()> {
+        let options = ArrowReaderOptions::new()
+            .with_page_index(true)
+            .with_schema(self.schema.clone());
+        let metadata = ArrowReaderMetadata::load(&reader, options)?;
+        let same_schema =
+            metadata.parquet_schema().root_schema() == self.writer.parquet_schema().root_schema();
+
+        self.inputs.push(Input {
+            reader: SharedReader(Arc::new(reader)),
+            metadata,
+            same_schema,
+        });
+        Ok(())
+    }
+
+    /// Writes the row groups of the inputs, and then the footer, returning the
+    /// metadata of the written file
+    pub fn close(mut self) -> Result<FileMetaData> {
+        for cluster in self.clusters()? {
+            match cluster.as_slice() {
+                [run] if run.copy => {
+                    let input = &self.inputs[run.input];
+                    let metadata = input.metadata.metadata();
+                    let row_group = metadata.row_group(run.row_group);
+                    let offset_index = metadata
+                        .offset_index()
+                        .and_then(|x| x.get(run.row_group))
+                        .filter(|x| x.len() == row_group.num_columns());
+                    self.writer.append_row_group(
+                        &input.reader,
+                        row_group,
+                        offset_index.map(|x| x.as_slice()),
+                    )?;
+                }
+                runs => self.merge(runs)?,
+            }
+        }
+        self.writer.close()
+    }
+
+    /// Returns the row groups of all inputs, grouped such that the row groups of
+    /// each group only need to be merged with each other
+    ///
+    /// When sorting, these are row groups with overlapping sort keys, with all row
+    /// groups in a single group if the sort keys of any are unknown
+    fn clusters(&self) -> Result<Vec<Vec<Run>>> {
+        let mut runs = vec![];
+        let mut bounds_converter = None;
+        for (input_idx, input) in self.inputs.iter().enumerate() {
+            let metadata = input.metadata.metadata();
+            let bounds = match &self.sort {
+                Some(sort) => self.bounds(input, sort, &mut bounds_converter)?,
+                None => vec![None; metadata.num_row_groups()],
+            };
+
+            for (idx, (row_group, bounds)) in metadata.row_groups().iter().zip(bounds).enumerate() {
+                let num_rows = row_group.num_rows() as usize;
+                let sorted = match &self.sort {
+                    Some(sort) => row_group.sorting_columns() == Some(&sort.sorting_columns),
+                    None => true,
+                };
+                let copy = self.copy_row_groups
+                    && input.same_schema
+                    && sorted
+                    && (self.min_copy_rows..=self.max_row_group_size).contains(&num_rows);
+
+                runs.push(Run {
+                    input: input_idx,
+                    row_group: idx,
+                    copy,
+                    bounds,
+                });
+            }
+        }
+
+        if self.sort.is_none() {
+            return Ok(runs.into_iter().map(|run| vec![run]).collect());
+        }
+        if runs.iter().any(|run| run.bounds.is_none()) {
+            return Ok(vec![runs]);
+        }
+
+        // Group row groups with overlapping bounds
+        runs.sort_by(|a, b| {
+            a.bounds
+                .as_ref()
+                .unwrap()
+                .0
+                .cmp(&b.bounds.as_ref().unwrap().0)
+        });
+        let mut clusters: Vec<Vec<Run>> = vec![];
+        let mut high: Option<OwnedRow> = None;
+        for run in runs {
+            let (low, run_high) = run.bounds.clone().unwrap();
+            match high.as_mut() {
+                Some(high) if low <= *high => {
+                    if run_high > *high {
+                        *high = run_high;
+                    }
+                    clusters.last_mut().unwrap().push(run);
+                }
+                _ => {
+                    high = Some(run_high);
+                    clusters.push(vec![run]);
+                }
+            }
+        }
+        Ok(clusters)
+    }
+
+    /// Returns the bounds of the first sorting column for each row group of `input`,
+    /// based on their statistics
+    fn bounds(
+        &self,
+        input: &Input<R>,
+        sort: &SortKey,
+        converter: &mut Option<RowConverter>,
+    ) -> Result<Vec<Option<(OwnedRow, OwnedRow)>>> {
+        let metadata = input.metadata.metadata();
+        let row_groups = metadata.row_groups();
+        let field = self.schema.field(sort.columns[0]);
+        let statistics = StatisticsConverter::try_new(
+            field.name(),
+            input.metadata.schema(),
+            input.metadata.parquet_schema(),
+        )?;
+        let mins = statistics.row_group_mins(row_groups)?;
+        let maxes = statistics.row_group_maxes(row_groups)?;
+        let null_counts = statistics.row_group_null_counts(row_groups)?;
+
+        // The statistics of floating point columns exclude NaN, which may sort before
+        // or after all other values depending on its sign
+        if mins.data_type().is_floating() {
+            return Ok(vec![None; row_groups.len()]);
+        }
+
+        let converter = match converter {
+            Some(converter) => converter,
+            None => {
+                let options = SortOptions {
+                    descending: sort.sorting_columns[0].descending,
+                    nulls_first: sort.sorting_columns[0].nulls_first,
+                };
+                let field = SortField::new_with_options(mins.data_type().clone(), options);
+                converter.insert(RowConverter::new(vec![field])?)
+            }
+        };
+
+        let nulls = new_null_array(mins.data_type(), 1);
+        (0..row_groups.len())
+            .map(|idx| {
+                if mins.is_null(idx) || maxes.is_null(idx) {
+                    return Ok(None);
+                }
+                let mut values: Vec<ArrayRef> = vec![mins.slice(idx, 1), maxes.slice(idx, 1)];
+                if null_counts.is_null(idx) || null_counts.value(idx) > 0 {
+                    values.push(nulls.clone());
+                }
+                let values: Vec<_> = values.iter().map(|x| x.as_ref()).collect();
+                let rows = converter.convert_columns(&[concat(&values)?])?;
+                let low = rows.iter().min().unwrap().owned();
+                let high = rows.iter().max().unwrap().owned();
+                Ok(Some((low, high)))
+            })
+            .collect()
+    }
+
+    /// Decodes the row groups of `runs` and writes them to the output, merging them
+    /// if sorting
+    fn merge(&mut self, runs: &[Run]) -> Result<()> {
+        let Self {
+            writer,
+            schema,
+            inputs,
+            sort,
+            batch_size,
+            ..
+        } = self;
+        let batch_size = *batch_size;
+
+        let sort = match sort {
+            Some(sort) => sort,
+            None => {
+                for run in runs {
+                    for batch in read_run(inputs, run, batch_size)? {
+                        writer.write(&batch?)?;
+                    }
+                }
+                return Ok(());
+            }
+        };
+
+        let mut cursors = BinaryHeap::with_capacity(runs.len());
+        for (index, run) in runs.iter().enumerate() {
+            let reader = read_run(inputs, run, batch_size)?;
+            let input = &inputs[run.input];
+            let row_group = input.metadata.metadata().row_group(run.row_group);
+            let batches: Box<dyn Iterator<Item = Result<RecordBatch>>> =
+                match row_group.sorting_columns() == Some(&sort.sorting_columns) {
+                    true => Box::new(reader.map(|b| b.map_err(ParquetError::from))),
+                    false => {
+                        let sorted = sort_run(schema, sort, reader)?;
+                        let reader = ParquetRecordBatchReaderBuilder::try_new(sorted)?
+                            .with_batch_size(batch_size)
+                            .build()?;
+                        Box::new(reader.map(|b| b.map_err(ParquetError::from)))
+                    }
+                };
+            if let Some(cursor) = Cursor::try_new(batches, sort, index)? {
+                cursors.push(cursor);
+            }
+        }
+
+        while !cursors.is_empty() {
+            let mut batches = Vec::with_capacity(cursors.len());
+            let mut current = std::mem::take(&mut cursors).into_vec();
+            for cursor in current.iter_mut() {
+                cursor.slot = batches.len();
+                batches.push(cursor.batch.clone());
+            }
+            cursors = BinaryHeap::from(current);
+
+            let mut indices = Vec::with_capacity(batch_size);
+            while indices.len() < batch_size {
+                // Take the lowest row, preferring earlier row groups for equal rows
+                let mut cursor = match cursors.peek_mut() {
+                    Some(cursor) => cursor,
+                    None => break,
+                };
+                indices.push((cursor.slot, cursor.offset));
+                if !cursor.advance(sort)? {
+                    PeekMut::pop(cursor);
+                } else if cursor.offset == 0 {
+                    cursor.slot = batches.len();
+                    batches.push(cursor.batch.clone());
+                }
+            }
+
+            let columns = (0..schema.fields().len())
+                .map(|col| {
+                    let arrays: Vec<_> = batches.iter().map(|b| b.column(col).as_ref()).collect();
+                    interleave(&arrays, &indices)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            writer.write(&RecordBatch::try_new(schema.clone(), columns)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns a reader of the row group of `run`
+fn read_run<R: ChunkReader + 'static>(
+    inputs: &[Input<R>],
+    run: &Run,
+    batch_size: usize,
+) -> Result<ParquetRecordBatchReader> {
+    let input = &inputs[run.input];
+    ParquetRecordBatchReaderBuilder::new_with_metadata(input.reader.clone(), input.metadata.clone())
+        .with_row_groups(vec![run.row_group])
+        .with_batch_size(batch_size)
+        .build()
+}
+
+/// Sorts the rows of `reader`, returning them encoded as an in-memory parquet file
+///
+/// This ensures only a single unsorted row group is decoded at a time, with the
+/// others held in their encoded form until merged
+fn sort_run(schema: &SchemaRef, sort: &SortKey, reader: ParquetRecordBatchReader) -> Result<Bytes> {
+    let batches = reader.collect::<Result<Vec<_>, _>>()?;
+    let batch = sort.sort(&concat_batches(schema, &batches)?)?;
+    drop(batches);
+
+    let mut buf = vec![];
+    let mut writer = ArrowWriter::try_new(&mut buf, schema.clone(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(buf.into())
+}
+
+/// The position within a sorted stream of batches being merged
+struct Cursor {
+    batches: Box<dyn Iterator<Item = Result<RecordBatch>>>,
+    batch: RecordBatch,
+    rows: Rows,
+    offset: usize,
+    /// The index of `batch` within the batches of the output being built
+    slot: usize,
+    /// The index of the run being read, ordering equal rows
+    index: usize,
+}
+
+impl Cursor {
+    /// Returns a [`Cursor`] at the first row of `batches`, or `None` if empty
+    fn try_new(
+        mut batches: Box<dyn Iterator<Item = Result<RecordBatch>>>,
+        sort: &SortKey,
+        index: usize,
+    ) -> Result<Option<Self>> {
+        loop {
+            let batch = match batches.next() {
+                Some(batch) => batch?,
+                None => return Ok(None),
+            };
+            if batch.num_rows() > 0 {
+                let rows = sort.convert(&batch)?;
+                return Ok(Some(Self {
+                    batches,
+                    batch,
+                    rows,
+                    offset: 0,
+                    slot: 0,
+                    index,
+                }));
+            }
+        }
+    }
+
+    /// Advances to the next row, returning `false` if there are no more rows
+    fn advance(&mut self, sort: &SortKey) -> Result<bool> {
+        self.offset += 1;
+        if self.offset < self.batch.num_rows() {
+            return Ok(true);
+        }
+        for batch in self.batches.by_ref() {
+            let batch = batch?;
+            if batch.num_rows() > 0 {
+                self.rows = sort.convert(&batch)?;
+                self.batch = batch;
+                self.offset = 0;
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+impl PartialEq for Cursor {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Cursor {}
+
+impl PartialOrd for Cursor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cursor {
+    /// Orders by the current row and then the index of the run, reversed such that
+    /// a [`BinaryHeap`] yields the lowest row of the earliest run first
+    fn cmp(&self, other: &Self) -> Ordering {
+        let this = self.rows.row(self.offset);
+        let that = other.rows.row(other.offset);
+        this.cmp(&that)
+            .then_with(|| self.index.cmp(&other.index))
+            .reverse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::metadata::ParquetMetaData;
+    use arrow_array::{Float64Array, Int32Array, StringArray, StructArray};
+    use arrow_schema::{DataType, Field};
+
+    fn write(batch: &RecordBatch, props: WriterProperties) -> Bytes {
+        let mut buf = vec![];
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(batch).unwrap();
+        writer.close().unwrap();
+        buf.into()
+    }
+
+    fn make_batch(values: impl IntoIterator<Item = i32>) -> RecordBatch {
+        let a = Int32Array::from_iter_values(values);
+        let b = StringArray::from_iter_values(a.values().iter().map(|x| format!("v{x}")));
+        RecordBatch::try_from_iter([
+            ("a", Arc::new(a) as ArrayRef),
+            ("b", Arc::new(b) as ArrayRef),
+        ])
+        .unwrap()
+    }
+
+    fn read(data: Vec<u8>) -> (Vec<i64>, Vec<RecordBatch>, Arc<ParquetMetaData>) {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(data)).unwrap();
+        let metadata = builder.metadata().clone();
+        let rows = metadata.row_groups().iter().map(|x| x.num_rows()).collect();
+        let batches = builder.build().unwrap().collect::<Result<_, _>>().unwrap();
+        (rows, batches, metadata)
+    }
+
+    #[test]
+    fn test_compact() {
+        let props = || {
+            WriterProperties::builder()
+                .set_max_row_group_size(100)
+                .build()
+        };
+        let a = make_batch(0..130);
+        let b = make_batch(130..200);
+        let input_a = write(&a, props());
+        let input_b = write(
+            &b,
+            WriterProperties::builder()
+                .set_max_row_group_size(60)
+                .build(),
+        );
+
+        let mut output = vec![];
+        let mut compactor = Compactor::try_new(&mut output, a.schema(), Some(props()))
+            .unwrap()
+            .with_min_copy_rows(50);
+        compactor.add_input(input_a.clone()).unwrap();
+        compactor.add_input(input_b.clone()).unwrap();
+        compactor.close().unwrap();
+
+        // The row groups of 100 and 60 rows are copied
+        let (rows, batches, metadata) = read(output);
+        assert_eq!(rows, vec![100, 30, 60, 10]);
+        let input = ParquetRecordBatchReaderBuilder::try_new(input_b).unwrap();
+        let copied = &input.metadata().row_group(0).columns()[1];
+        let column = &metadata.row_group(2).columns()[1];
+        assert_eq!(column.compressed_size(), copied.compressed_size());
+
+        let batch = concat_batches(&a.schema(), &batches).unwrap();
+        assert_eq!(batch, make_batch(0..200));
+
+        // Without copying, rows are written to row groups of the maximum size
+        let mut output = vec![];
+        let mut compactor = Compactor::try_new(&mut output, a.schema(), Some(props()))
+            .unwrap()
+            .with_copy_row_groups(false);
+        compactor.add_input(input_a).unwrap();
+        compactor.add_input(write(&b, props())).unwrap();
+        compactor.close().unwrap();
+
+        let (rows, batches, _) = read(output);
+        assert_eq!(rows, vec![100, 100]);
+        let batch = concat_batches(&a.schema(), &batches).unwrap();
+        assert_eq!(batch, make_batch(0..200));
+    }
+
+    #[test]
+    fn test_compact_sorted() {
+        let sorting_columns = vec![SortingColumn::new(0, true, false)];
+        let sorted = || {
+            WriterProperties::builder()
+                .set_max_row_group_size(50)
+                .set_sorting_columns(Some(sorting_columns.clone()))
+                .build()
+        };
+
+        // Sorted row groups of 199..150 and 149..100
+        let input_a = write(&make_batch((100..200).rev()), sorted());
+        // Unsorted 150..175, overlapping the first row group of `input_a`
+        let values = (0..25).map(|x| x * 7 % 25 + 150);
+        let input_b = write(&make_batch(values), WriterProperties::default());
+        // Sorted even and odd values, overlapping each other
+        let input_c = write(&make_batch((0..50).rev().map(|x| x * 2)), sorted());
+        let input_d = write(&make_batch((1..50).rev().step_by(2)), sorted());
+
+        let schema = make_batch([]).schema();
+        let mut output = vec![];
+        let mut compactor = Compactor::try_new(&mut output, schema.clone(), Some(sorted()))
+            .unwrap()
+            .with_batch_size(16)
+            .with_min_copy_rows(25);
+        for input in [input_a.clone(), input_b, input_c, input_d] {
+            compactor.add_input(input).unwrap();
+        }
+        compactor.close().unwrap();
+
+        let (rows, batches, metadata) = read(output);
+        let mut expected: Vec<_> = (100..200)
+            .chain(150..175)
+            .chain((0..100).step_by(2))
+            .chain((1..50).step_by(2))
+            .collect();
+        expected.sort_unstable_by(|a, b| b.cmp(a));
+        let batch = concat_batches(&schema, &batches).unwrap();
+        assert_eq!(batch, make_batch(expected));
+
+        // Only the row group of 149..100 does not overlap another, and is copied
+        assert_eq!(rows, vec![50, 25, 50, 50, 25]);
+        let input = ParquetRecordBatchReaderBuilder::try_new(input_a).unwrap();
+        let copied = &input.metadata().row_group(1).columns()[1];
+        let column = &metadata.row_group(2).columns()[1];
+        assert_eq!(column.compressed_size(), copied.compressed_size());
+
+        for row_group in metadata.row_groups() {
+            assert_eq!(row_group.sorting_columns(), Some(&sorting_columns));
+        }
+    }
+
+    #[test]
+    fn test_compact_sorted_nan() {
+        let props = WriterProperties::builder()
+            .set_sorting_columns(Some(vec![SortingColumn::new(0, false, false)]))
+            .build();
+        let batch = |values: Vec<f64>| {
+            let a = Arc::new(Float64Array::from(values)) as ArrayRef;
+            RecordBatch::try_from_iter([("a", a)]).unwrap()
+        };
+
+        // The statistics of the first row group are `1..=1`, excluding NaN
+        let input_a = write(&batch(vec![1., f64::NAN]), props.clone());
+        let input_b = write(&batch(vec![2., 3.]), props.clone());
+
+        let schema = batch(vec![]).schema();
+        let mut output = vec![];
+        let mut compactor = Compactor::try_new(&mut output, schema.clone(), Some(props))
+            .unwrap()
+            .with_min_copy_rows(1);
+        compactor.add_input(input_a).unwrap();
+        compactor.add_input(input_b).unwrap();
+        compactor.close().unwrap();
+
+        let (rows, batches, _) = read(output);
+        assert_eq!(rows, vec![4]);
+        let batch = concat_batches(&schema, &batches).unwrap();
+        let a = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(a.values()[..3], [1., 2., 3.]);
+        assert!(a.value(3).is_nan());
+    }
+
+    #[test]
+    fn test_compact_nested_sort() {
+        let a = Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef;
+        let fields = vec![Field::new("a", DataType::Int32, false)];
+        let s = StructArray::new(fields.into(), vec![a], None);
+        let batch = RecordBatch::try_from_iter([("s", Arc::new(s) as ArrayRef)]).unwrap();
+
+        let props = WriterProperties::builder()
+            .set_sorting_columns(Some(vec![SortingColumn::new(0, false, false)]))
+            .build();
+        let err = Compactor::<_, Bytes>::try_new(vec![], batch.schema(), Some(props)).unwrap_err();
+        assert_eq!(err.to_string(), "NYI: sorting by nested column \"s.a\"");
+    }
+}
@@ -70,7 +70,8 @@
 //! #     Field::new("id", DataType::Int32, false),
 //! # ]));
 //! #
-//! # let file = File::create("data.parquet").unwrap();
+//! # let path = tempfile::NamedTempFile::new().unwrap().into_temp_path();
+//! # let file = File::create(&path).unwrap();
 //! #
 //! # let batch = RecordBatch::try_new(Arc::clone(&schema), vec![Arc::new(ids)]).unwrap();
 //! # let batches = vec![batch];
@@ -82,7 +83,7 @@
 //! # }
 //! # writer.close().unwrap();
 //! #
-//! let file = File::open("data.parquet").unwrap();
+//! let file = File::open(&path).unwrap();
 //!
 //! let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
 //! println!("Converted arrow schema is: {}", builder.schema());
@@ -98,6 +99,7 @@ experimental!(mod array_reader);
 pub mod arrow_reader;
 pub mod arrow_writer;
 mod buffer;
+pub mod compact;
 mod decoder;
 
 #[cfg(feature = "async")]
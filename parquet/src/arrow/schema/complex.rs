@@ -19,12 +19,13 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::arrow::schema::primitive::convert_primitive;
+use crate::arrow::schema::UNION_TYPE_ID_FIELD;
 use crate::arrow::{ProjectionMask, PARQUET_FIELD_ID_META_KEY};
 use crate::basic::{ConvertedType, Repetition};
 use crate::errors::ParquetError;
 use crate::errors::Result;
 use crate::schema::types::{SchemaDescriptor, Type, TypePtr};
-use arrow_schema::{DataType, Field, Fields, SchemaBuilder};
+use arrow_schema::{DataType, Field, Fields, SchemaBuilder, UnionFields};
 
 fn get_repetition(t: &Type) -> Repetition {
     let info = t.get_basic_info();
@@ -416,6 +417,8 @@ impl Visitor {
             Some(DataType::List(f)) => Some(f.as_ref()),
             Some(DataType::LargeList(f)) => Some(f.as_ref()),
             Some(DataType::FixedSizeList(f, _)) => Some(f.as_ref()),
+            Some(DataType::ListView(f)) => Some(f.as_ref()),
+            Some(DataType::LargeListView(f)) => Some(f.as_ref()),
             Some(d) => {
                 return Err(arrow_err!(
                     "incompatible arrow schema, expected list got {}",
@@ -450,11 +453,14 @@ impl Visitor {
 
         let items = repeated_field.get_fields();
         if items.len() != 1
+            || get_repetition(&items[0]) == Repetition::REPEATED
             || repeated_field.name() == "array"
             || repeated_field.name() == format!("{}_tuple", list_type.name())
         {
             // If the repeated field is a group with multiple fields, then its type is the element type and elements are required.
             //
+            // If the repeated field is a group with one repeated field, then its type is the element type and elements are required.
+            //
             // If the repeated field is a group with one field and is named either array or uses the LIST-annotated group's name
             // with _tuple appended then the repeated type is the element type and elements are required.
             let context = VisitorContext {
@@ -493,6 +499,8 @@ impl Visitor {
                     Some(DataType::FixedSizeList(_, len)) => {
                         DataType::FixedSizeList(item_field, len)
                     }
+                    Some(DataType::ListView(_)) => DataType::ListView(item_field),
+                    Some(DataType::LargeListView(_)) => DataType::LargeListView(item_field),
                     _ => DataType::List(item_field),
                 };
 
@@ -510,6 +518,63 @@ impl Visitor {
         }
     }
 
+    /// Visits a group written from an arrow union, consisting of a `type_id` column
+    /// followed by a nullable column for each variant
+    ///
+    /// If any of the columns are not projected the group is instead returned as a struct
+    fn visit_union(
+        &mut self,
+        union_type: &TypePtr,
+        context: VisitorContext,
+    ) -> Result<Option<ParquetField>> {
+        let (union_fields, mode) = match &context.data_type {
+            Some(DataType::Union(fields, mode)) => (fields.clone(), *mode),
+            _ => unreachable!(),
+        };
+
+        let struct_fields: Fields =
+            std::iter::once(Field::new(UNION_TYPE_ID_FIELD, DataType::Int8, false))
+                .chain(
+                    union_fields
+                        .iter()
+                        .map(|(_, f)| f.as_ref().clone().with_nullable(true)),
+                )
+                .collect();
+
+        let context = VisitorContext {
+            rep_level: context.rep_level,
+            def_level: context.def_level,
+            data_type: Some(DataType::Struct(struct_fields)),
+        };
+
+        let mut field = match self.visit_struct(union_type, context)? {
+            Some(field) => field,
+            None => return Ok(None),
+        };
+
+        let projected = match &field.arrow_type {
+            DataType::Struct(fields) => fields.clone(),
+            _ => return Ok(Some(field)),
+        };
+        if projected.len() != union_fields.len() + 1 {
+            return Ok(Some(field));
+        }
+
+        // Restore the variants, using the child types in case they differ from the hint
+        field.arrow_type = DataType::Union(
+            union_fields
+                .iter()
+                .zip(projected.iter().skip(1))
+                .map(|((type_id, f), child)| {
+                    let f = f.as_ref().clone().with_data_type(child.data_type().clone());
+                    (type_id, Arc::new(f))
+                })
+                .collect::<UnionFields>(),
+            mode,
+        );
+        Ok(Some(field))
+    }
+
     fn dispatch(
         &mut self,
         cur_type: &TypePtr,
@@ -517,6 +582,8 @@ impl Visitor {
     ) -> Result<Option<ParquetField>> {
         if cur_type.is_primitive() {
             self.visit_primitive(cur_type, context)
+        } else if matches!(context.data_type, Some(DataType::Union(_, _))) {
+            self.visit_union(cur_type, context)
         } else {
             match cur_type.get_basic_info().converted_type() {
                 ConvertedType::LIST => self.visit_list(cur_type, context),
@@ -298,6 +298,15 @@ pub fn decimal_length_from_precision(precision: u8) -> usize {
 }
 
 /// Convert an arrow field to a parquet `Type`
+///
+/// Arrow types without a parquet equivalent are mapped as follows, with the
+/// embedded arrow schema used to restore the original type on read:
+///
+/// * `ListView` and `LargeListView` are written as a standard `LIST`
+/// * `Union` is written as a group whose first child is a required `type_id`
+///   column (`INT32` annotated as `INT(8, true)`), followed by every variant
+///   as an optional child. Only the variant selected by `type_id` is non-null
+///   in a given slot
 fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
     let name = field.name().as_str();
     let repetition = if field.is_nullable() {
@@ -514,22 +523,21 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
             .with_repetition(repetition)
             .with_id(id)
             .build(),
-        DataType::List(f) | DataType::FixedSizeList(f, _) | DataType::LargeList(f) => {
-            Type::group_type_builder(name)
-                .with_fields(vec![Arc::new(
-                    Type::group_type_builder("list")
-                        .with_fields(vec![Arc::new(arrow_to_parquet_type(f)?)])
-                        .with_repetition(Repetition::REPEATED)
-                        .build()?,
-                )])
-                .with_logical_type(Some(LogicalType::List))
-                .with_repetition(repetition)
-                .with_id(id)
-                .build()
-        }
-        DataType::ListView(_) | DataType::LargeListView(_) => {
-            unimplemented!("ListView/LargeListView not implemented")
-        }
+        DataType::List(f)
+        | DataType::FixedSizeList(f, _)
+        | DataType::LargeList(f)
+        | DataType::ListView(f)
+        | DataType::LargeListView(f) => Type::group_type_builder(name)
+            .with_fields(vec![Arc::new(
+                Type::group_type_builder("list")
+                    .with_fields(vec![Arc::new(arrow_to_parquet_type(f)?)])
+                    .with_repetition(Repetition::REPEATED)
+                    .build()?,
+            )])
+            .with_logical_type(Some(LogicalType::List))
+            .with_repetition(repetition)
+            .with_id(id)
+            .build(),
         DataType::Struct(fields) => {
             if fields.is_empty() {
                 return Err(arrow_err!("Parquet does not support writing empty structs",));
@@ -567,7 +575,36 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
                 ))
             }
         }
-        DataType::Union(_, _) => unimplemented!("See ARROW-8817."),
+        DataType::Union(fields, _) => {
+            // A union is stored as a group containing the 8-bit type id of each
+            // slot followed by one optional child per variant, of which only the
+            // child selected by the type id is non-null
+            let type_id = Type::primitive_type_builder(UNION_TYPE_ID_FIELD, PhysicalType::INT32)
+                .with_logical_type(Some(LogicalType::Integer {
+                    bit_width: 8,
+                    is_signed: true,
+                }))
+                .with_repetition(Repetition::REQUIRED)
+                .build()?;
+
+            let mut children = Vec::with_capacity(fields.len() + 1);
+            children.push(Arc::new(type_id));
+            for (_, f) in fields.iter() {
+                if f.name() == UNION_TYPE_ID_FIELD {
+                    return Err(arrow_err!(
+                        "Union child field name \"{}\" is reserved",
+                        UNION_TYPE_ID_FIELD
+                    ));
+                }
+                let child = f.as_ref().clone().with_nullable(true);
+                children.push(Arc::new(arrow_to_parquet_type(&child)?));
+            }
+            Type::group_type_builder(name)
+                .with_fields(children)
+                .with_repetition(repetition)
+                .with_id(id)
+                .build()
+        }
         DataType::Dictionary(_, ref value) => {
             // Dictionary encoding not handled at the schema level
             let dict_field = field.clone().with_data_type(value.as_ref().clone());
@@ -579,6 +616,11 @@ fn arrow_to_parquet_type(field: &Field) -> Result<Type> {
     }
 }
 
+/// Name of the column holding the type id of each slot of an arrow union
+///
+/// See [`arrow_to_parquet_type`] for the layout used to store unions
+pub(crate) const UNION_TYPE_ID_FIELD: &str = "type_id";
+
 fn field_id(field: &Field) -> Option<i32> {
     let value = field.metadata().get(super::PARQUET_FIELD_ID_META_KEY)?;
     value.parse().ok() // Fail quietly if not a valid integer
@@ -590,7 +632,7 @@ mod tests {
 
     use std::{collections::HashMap, sync::Arc};
 
-    use arrow::datatypes::{DataType, Field, IntervalUnit, TimeUnit};
+    use arrow::datatypes::{DataType, Field, IntervalUnit, TimeUnit, UnionFields, UnionMode};
 
     use crate::arrow::PARQUET_FIELD_ID_META_KEY;
     use crate::file::metadata::KeyValue;
@@ -772,6 +814,11 @@ mod tests {
               REQUIRED BINARY str (UTF8);
             }
           }
+          OPTIONAL GROUP my_list (LIST) {
+            REPEATED GROUP list {
+              REPEATED INT32 element;
+            }
+          }
           REPEATED INT32 name;
         }
         ";
@@ -902,6 +949,26 @@ mod tests {
             ));
         }
 
+        // // List<OneTuple<List<Integer>>> (nullable list, non-null elements)
+        // optional group my_list (LIST) {
+        //   repeated group list {
+        //     repeated int32 element;
+        //   };
+        // }
+        // Special case: group with a single repeated field
+        {
+            let element = Field::new_list(
+                "element",
+                Field::new("element", DataType::Int32, false),
+                false,
+            );
+            arrow_fields.push(Field::new_list(
+                "my_list",
+                Field::new_struct("list", vec![element], false),
+                true,
+            ));
+        }
+
         // One-level encoding: Only allows required lists with required cells
         //   repeated value_type name
         {
@@ -1928,6 +1995,104 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_arrow_schema_roundtrip_union_and_list_view() -> Result<()> {
+        let union_fields = UnionFields::new(
+            vec![1, 5],
+            vec![
+                Field::new("a", DataType::Int32, false),
+                Field::new_list("b", Field::new("item", DataType::Utf8, true), true),
+            ],
+        );
+        let item = Arc::new(Field::new("item", DataType::Float64, true));
+        let schema = Schema::new(vec![
+            Field::new(
+                "u",
+                DataType::Union(union_fields.clone(), UnionMode::Dense),
+                true,
+            ),
+            Field::new("s", DataType::Union(union_fields, UnionMode::Sparse), false),
+            Field::new("lv", DataType::ListView(item.clone()), true),
+            Field::new("llv", DataType::LargeListView(item), false),
+        ]);
+
+        let parquet_schema = arrow_to_parquet_schema(&schema)?;
+        let paths: Vec<_> = parquet_schema
+            .columns()
+            .iter()
+            .map(|c| c.path().string())
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "u.type_id",
+                "u.a",
+                "u.b.list.item",
+                "s.type_id",
+                "s.a",
+                "s.b.list.item",
+                "lv.list.item",
+                "llv.list.item"
+            ]
+        );
+        let type_id = parquet_schema.column(0);
+        assert_eq!(type_id.physical_type(), PhysicalType::INT32);
+        assert_eq!(
+            type_id.logical_type(),
+            Some(LogicalType::Integer {
+                bit_width: 8,
+                is_signed: true
+            })
+        );
+        assert_eq!(type_id.max_def_level(), 1);
+        // Variants are always optional
+        assert_eq!(parquet_schema.column(4).max_def_level(), 1);
+
+        // Without the embedded arrow schema the union is read as a struct
+        let arrow_schema = parquet_to_arrow_schema(&parquet_schema, None)?;
+        let expected = Fields::from(vec![
+            Field::new("type_id", DataType::Int8, false),
+            Field::new("a", DataType::Int32, true),
+            Field::new_list("b", Field::new("item", DataType::Utf8, true), true),
+        ]);
+        assert_eq!(
+            arrow_schema.field(0).data_type(),
+            &DataType::Struct(expected)
+        );
+        assert_eq!(
+            arrow_schema.field(2).data_type(),
+            &DataType::List(Arc::new(Field::new("item", DataType::Float64, true)))
+        );
+
+        // With the embedded arrow schema the original types are restored
+        let file = tempfile::tempfile().unwrap();
+        let writer =
+            ArrowWriter::try_new(file.try_clone().unwrap(), Arc::new(schema.clone()), None)?;
+        writer.close()?;
+
+        let arrow_reader = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+        let read_schema = arrow_reader.schema();
+        assert_eq!(&schema, read_schema.as_ref());
+        Ok(())
+    }
+
+    #[test]
+    fn test_union_reserved_field_name() {
+        let union_fields =
+            UnionFields::new(vec![0], vec![Field::new("type_id", DataType::Int32, false)]);
+        let schema = Schema::new(vec![Field::new(
+            "u",
+            DataType::Union(union_fields, UnionMode::Sparse),
+            false,
+        )]);
+        let err = arrow_to_parquet_schema(&schema).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Union child field name \"type_id\" is reserved"),
+            "{err}"
+        );
+    }
+
     #[test]
     fn test_get_arrow_schema_from_metadata() {
         assert!(get_arrow_schema_from_metadata("").is_err());
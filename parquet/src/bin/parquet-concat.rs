@@ -33,13 +33,21 @@
 //! cargo run --features=cli --bin parquet-concat out.parquet a.parquet b.parquet
 //! ```
 //!
+//! Row groups are copied without being decoded, unless `--min-row-group-size` or
+//! `--max-row-group-size` is set, in which case smaller row groups are merged into
+//! row groups of up to `--max-row-group-size` rows, see [`Compactor`]
+//!
 //! Note: this does not currently support preserving the page index or bloom filters
+//! of row groups copied without being decoded
 //!
+//! [`Compactor`]: parquet::arrow::compact::Compactor
 
 use clap::Parser;
+use parquet::arrow::arrow_reader::ArrowReaderMetadata;
+use parquet::arrow::compact::Compactor;
 use parquet::column::writer::ColumnCloseResult;
 use parquet::errors::{ParquetError, Result};
-use parquet::file::metadata::ParquetMetaDataReader;
+use parquet::file::metadata::{ParquetMetaData, ParquetMetaDataReader};
 use parquet::file::properties::WriterProperties;
 use parquet::file::writer::SerializedFileWriter;
 use std::fs::File;
@@ -54,6 +62,14 @@ struct Args {
 
     /// Path to input files
     input: Vec<String>,
+
+    /// Merges row groups with fewer rows than this, defaults to half the maximum
+    #[clap(long)]
+    min_row_group_size: Option<usize>,
+
+    /// Sets maximum number of rows in a merged row group
+    #[clap(long)]
+    max_row_group_size: Option<usize>,
 }
 
 impl Args {
@@ -86,34 +102,108 @@ impl Args {
             }
         }
 
-        let props = Arc::new(WriterProperties::builder().build());
-        let schema = inputs[0].1.file_metadata().schema_descr().root_schema_ptr();
-        let mut writer = SerializedFileWriter::new(output, schema, props)?;
-
-        for (input, metadata) in inputs {
-            for rg in metadata.row_groups() {
-                let mut rg_out = writer.next_row_group()?;
-                for column in rg.columns() {
-                    let result = ColumnCloseResult {
-                        bytes_written: column.compressed_size() as _,
-                        rows_written: rg.num_rows() as _,
-                        metadata: column.clone(),
-                        bloom_filter: None,
-                        column_index: None,
-                        offset_index: None,
-                    };
-                    rg_out.append_column(&input, result)?;
-                }
-                rg_out.close()?;
-            }
+        if self.min_row_group_size.is_none() && self.max_row_group_size.is_none() {
+            return copy_row_groups(output, inputs);
         }
 
-        writer.close()?;
+        let metadata = ArrowReaderMetadata::load(&inputs[0].0, Default::default())?;
+        let schema = metadata.schema().clone();
+
+        let mut props = WriterProperties::builder();
+        if let Some(value) = self.max_row_group_size {
+            props = props.set_max_row_group_size(value);
+        }
+
+        let mut compactor = Compactor::try_new(output, schema, Some(props.build()))?;
+        if let Some(value) = self.min_row_group_size {
+            compactor = compactor.with_min_copy_rows(value);
+        }
+        for (input, _) in inputs {
+            compactor.add_input(input)?;
+        }
+        compactor.close()?;
 
         Ok(())
     }
 }
 
+/// Copies the row groups of `inputs` to `output` without decoding them
+fn copy_row_groups(output: File, inputs: Vec<(File, ParquetMetaData)>) -> Result<()> {
+    let props = Arc::new(WriterProperties::builder().build());
+    let schema = inputs[0].1.file_metadata().schema_descr().root_schema_ptr();
+    let mut writer = SerializedFileWriter::new(output, schema, props)?;
+
+    for (input, metadata) in inputs {
+        for rg in metadata.row_groups() {
+            let mut rg_out = writer.next_row_group()?;
+            for column in rg.columns() {
+                let result = ColumnCloseResult {
+                    bytes_written: column.compressed_size() as _,
+                    rows_written: rg.num_rows() as _,
+                    metadata: column.clone(),
+                    bloom_filter: None,
+                    column_index: None,
+                    offset_index: None,
+                };
+                rg_out.append_column(&input, result)?;
+            }
+            rg_out.close()?;
+        }
+    }
+
+    writer.close()?;
+    Ok(())
+}
+
 fn main() -> Result<()> {
     Args::parse().run()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{ArrayRef, Int32Array, RecordBatch};
+    use parquet::arrow::ArrowWriter;
+    use tempfile::NamedTempFile;
+
+    /// Returns the number of rows in each row group of the file at `path`
+    fn row_group_sizes(path: &str) -> Vec<i64> {
+        let file = File::open(path).unwrap();
+        let metadata = ParquetMetaDataReader::new()
+            .parse_and_finish(&file)
+            .unwrap();
+        metadata.row_groups().iter().map(|x| x.num_rows()).collect()
+    }
+
+    #[test]
+    fn test_max_row_group_size() {
+        let input = NamedTempFile::new().unwrap();
+        let values = Arc::new(Int32Array::from_iter_values(0..30)) as ArrayRef;
+        let batch = RecordBatch::try_from_iter([("a", values)]).unwrap();
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(10)
+            .build();
+        let mut writer =
+            ArrowWriter::try_new(input.reopen().unwrap(), batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let input = input.path().to_str().unwrap().to_string();
+        let output = NamedTempFile::new().unwrap();
+        let output = output.path().to_str().unwrap().to_string();
+
+        let args = Args::parse_from(["parquet-concat", &output, &input]);
+        args.run().unwrap();
+        assert_eq!(row_group_sizes(&output), vec![10, 10, 10]);
+
+        let args = Args::parse_from([
+            "parquet-concat",
+            "--max-row-group-size",
+            "30",
+            &output,
+            &input,
+        ]);
+        args.run().unwrap();
+        assert_eq!(row_group_sizes(&output), vec![30]);
+    }
+}
@@ -32,19 +32,20 @@
 //! ```
 //! cargo run --features=cli --bin parquet-rewrite -- -i XYZ.parquet -o XYZ2.parquet
 //! ```
+//!
+//! The output can be sorted by one or more top-level columns with `--sort-by`,
+//! see [`Compactor`] for more details.
+//!
+//! [`Compactor`]: parquet::arrow::compact::Compactor
 
 use std::fs::File;
 
-use arrow_array::RecordBatchReader;
 use clap::{builder::PossibleValue, Parser, ValueEnum};
 use parquet::{
-    arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter},
+    arrow::{arrow_reader::ArrowReaderMetadata, compact::Compactor},
     basic::Compression,
-    file::{
-        properties::{EnabledStatistics, WriterProperties, WriterVersion},
-        reader::FileReader,
-        serialized_reader::SerializedFileReader,
-    },
+    file::properties::{EnabledStatistics, WriterProperties, WriterVersion},
+    format::SortingColumn,
 };
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
@@ -199,29 +200,25 @@ struct Args {
     /// Sets writer version.
     #[clap(long)]
     writer_version: Option<WriterVersionArgs>,
+
+    /// Sorts the output by these top-level columns, ascending with nulls first.
+    #[clap(long)]
+    sort_by: Vec<String>,
 }
 
 fn main() {
     let args = Args::parse();
 
+    let input = File::open(&args.input).expect("Unable to open input file");
+    let metadata = ArrowReaderMetadata::load(&input, Default::default()).expect("parquet open");
+
     // read key-value metadata
-    let parquet_reader =
-        SerializedFileReader::new(File::open(&args.input).expect("Unable to open input file"))
-            .expect("Failed to create reader");
-    let kv_md = parquet_reader
+    let kv_md = metadata
         .metadata()
         .file_metadata()
         .key_value_metadata()
         .cloned();
 
-    // create actual parquet reader
-    let parquet_reader = ParquetRecordBatchReaderBuilder::try_new(
-        File::open(args.input).expect("Unable to open input file"),
-    )
-    .expect("parquet open")
-    .build()
-    .expect("parquet open");
-
     let mut writer_properties_builder = WriterProperties::builder().set_key_value_metadata(kv_md);
     if let Some(value) = args.compression {
         writer_properties_builder = writer_properties_builder.set_compression(value.into());
@@ -262,18 +259,32 @@ fn main() {
     if let Some(value) = args.writer_version {
         writer_properties_builder = writer_properties_builder.set_writer_version(value.into());
     }
+    if !args.sort_by.is_empty() {
+        let schema_descr = metadata.metadata().file_metadata().schema_descr();
+        let sorting_columns = args
+            .sort_by
+            .iter()
+            .map(|name| {
+                let column_idx = schema_descr
+                    .columns()
+                    .iter()
+                    .position(|c| &c.path().string() == name)
+                    .unwrap_or_else(|| panic!("Unknown sort column {name}"));
+                SortingColumn::new(column_idx as i32, false, true)
+            })
+            .collect();
+        writer_properties_builder =
+            writer_properties_builder.set_sorting_columns(Some(sorting_columns));
+    }
     let writer_properties = writer_properties_builder.build();
-    let mut parquet_writer = ArrowWriter::try_new(
+    let mut compactor = Compactor::try_new(
         File::create(&args.output).expect("Unable to open output file"),
-        parquet_reader.schema(),
+        metadata.schema().clone(),
         Some(writer_properties),
     )
-    .expect("create arrow writer");
-
-    for maybe_batch in parquet_reader {
-        let batch = maybe_batch.expect("reading batch");
-        parquet_writer.write(&batch).expect("writing data");
-    }
+    .expect("create compactor")
+    .with_copy_row_groups(false);
 
-    parquet_writer.close().expect("finalizing file");
+    compactor.add_input(input).expect("parquet open");
+    compactor.close().expect("finalizing file");
 }
@@ -73,6 +73,8 @@
 //! [bf-formulae]: http://tfk.mit.edu/pdf/bloom.pdf
 
 use crate::data_type::AsBytes;
+#[cfg(feature = "encryption")]
+use crate::encryption::decrypt::CryptoContext;
 use crate::errors::ParquetError;
 use crate::file::metadata::ColumnChunkMetaData;
 use crate::file::reader::ChunkReader;
@@ -84,7 +86,6 @@ use crate::thrift::{TCompactSliceInputProtocol, TSerializable};
 use bytes::Bytes;
 use std::hash::Hasher;
 use std::io::Write;
-use std::sync::Arc;
 use thrift::protocol::{TCompactOutputProtocol, TOutputProtocol};
 use twox_hash::XxHash64;
 
@@ -213,6 +214,34 @@ pub(crate) fn read_bloom_filter_header_and_length(
     Ok((header, (total_length - prot.as_slice().len()) as u64))
 }
 
+/// Returns the byte range of the bloom filter of an encrypted column chunk, if any
+///
+/// Unlike plaintext bloom filters, the length is required as the encrypted header
+/// cannot be read without it
+#[cfg(feature = "encryption")]
+pub(crate) fn encrypted_bloom_filter_range(
+    column_metadata: &ColumnChunkMetaData,
+) -> Result<Option<std::ops::Range<u64>>, ParquetError> {
+    let (offset, length) = match (
+        column_metadata.bloom_filter_offset(),
+        column_metadata.bloom_filter_length(),
+    ) {
+        (Some(offset), Some(length)) => (offset, length),
+        (None, _) => return Ok(None),
+        (Some(_), None) => {
+            return Err(ParquetError::General(
+                "Encrypted bloom filter length is missing".to_string(),
+            ))
+        }
+    };
+    match (u64::try_from(offset), u64::try_from(length)) {
+        (Ok(offset), Ok(length)) => Ok(Some(offset..offset + length)),
+        _ => Err(ParquetError::General(
+            "Bloom filter offset or length is invalid".to_string(),
+        )),
+    }
+}
+
 pub(crate) const BITSET_MIN_LENGTH: usize = 32;
 pub(crate) const BITSET_MAX_LENGTH: usize = 128 * 1024 * 1024;
 
@@ -282,6 +311,15 @@ impl Sbbf {
         Ok(())
     }
 
+    /// Returns the bitset in serialized form
+    #[cfg(feature = "encryption")]
+    pub(crate) fn bitset(&self) -> Vec<u8> {
+        self.0
+            .iter()
+            .flat_map(|block| block.to_le_bytes())
+            .collect()
+    }
+
     /// Write the bitset in serialized form to the writer.
     fn write_bitset<W: Write>(&self, mut writer: W) -> Result<(), ParquetError> {
         for block in &self.0 {
@@ -295,7 +333,7 @@ impl Sbbf {
     }
 
     /// Create and populate [`BloomFilterHeader`] from this bitset for writing to serialized form
-    fn header(&self) -> BloomFilterHeader {
+    pub(crate) fn header(&self) -> BloomFilterHeader {
         BloomFilterHeader {
             // 8 i32 per block, 4 bytes per i32
             num_bytes: self.0.len() as i32 * 4 * 8,
@@ -308,8 +346,14 @@ impl Sbbf {
     /// Read a new bloom filter from the given offset in the given reader.
     pub(crate) fn read_from_column_chunk<R: ChunkReader>(
         column_metadata: &ColumnChunkMetaData,
-        reader: Arc<R>,
+        reader: &R,
     ) -> Result<Option<Self>, ParquetError> {
+        // Encrypted bloom filters are read with `Self::read_encrypted_from_column_chunk`
+        #[cfg(feature = "encryption")]
+        if column_metadata.crypto_metadata().is_some() {
+            return Ok(None);
+        }
+
         let offset: u64 = if let Some(offset) = column_metadata.bloom_filter_offset() {
             offset
                 .try_into()
@@ -356,6 +400,37 @@ impl Sbbf {
     }
 
     #[inline]
+    /// Read and decrypt the bloom filter of an encrypted column chunk from the given reader
+    #[cfg(feature = "encryption")]
+    pub(crate) fn read_encrypted_from_column_chunk<R: ChunkReader>(
+        column_metadata: &ColumnChunkMetaData,
+        reader: &R,
+        context: &CryptoContext,
+    ) -> Result<Option<Self>, ParquetError> {
+        match encrypted_bloom_filter_range(column_metadata)? {
+            Some(range) => {
+                let buffer = reader.get_bytes(range.start, (range.end - range.start) as usize)?;
+                Self::decrypt(&buffer, context).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Decrypt a bloom filter from `buffer`, containing its encrypted header and bitset
+    #[cfg(feature = "encryption")]
+    pub(crate) fn decrypt(buffer: &[u8], context: &CryptoContext) -> Result<Self, ParquetError> {
+        let (header, bitset) = context.decrypt_bloom_filter(buffer)?;
+        let (header, _) = read_bloom_filter_header_and_length(Bytes::from(header))?;
+        if usize::try_from(header.num_bytes).ok() != Some(bitset.len()) {
+            return Err(ParquetError::General(format!(
+                "Expected bloom filter bit set of {} bytes, got {}",
+                header.num_bytes,
+                bitset.len()
+            )));
+        }
+        Ok(Self::new(&bitset))
+    }
+
     fn hash_to_block_index(&self, hash: u64) -> usize {
         // unchecked_mul is unstable, but in reality this is safe, we'd just use saturating mul
         // but it will not saturate
@@ -92,6 +92,12 @@ impl ColumnWriter<'_> {
         downcast_writer!(self, typed, typed.get_estimated_total_bytes())
     }
 
+    /// Ends the current data page, see [`GenericColumnWriter::finish_data_page`]
+    #[cfg(feature = "arrow")]
+    pub(crate) fn finish_data_page(&mut self) -> Result<()> {
+        downcast_writer!(self, typed, typed.finish_data_page())
+    }
+
     /// Close this [`ColumnWriter`]
     pub fn close(self) -> Result<ColumnCloseResult> {
         downcast_writer!(self, typed, typed.close())
@@ -448,9 +454,10 @@ impl<'a, E: ColumnValueEncoder> GenericColumnWriter<'a, E> {
         // TODO: find out why we don't account for size of levels when we estimate page
         // size.
 
-        let num_levels = match def_levels {
-            Some(def_levels) => def_levels.len(),
-            None => values.len(),
+        let num_levels = match (def_levels, value_indices) {
+            (Some(def_levels), _) => def_levels.len(),
+            (None, Some(indices)) => indices.len(),
+            (None, None) => values.len(),
         };
 
         if let Some(min) = min {
@@ -573,6 +580,17 @@ impl<'a, E: ColumnValueEncoder> GenericColumnWriter<'a, E> {
             + self.encoder.estimated_dict_page_size().unwrap_or_default() as u64
     }
 
+    /// Ends the current data page, if any values have been buffered since the last one
+    ///
+    /// Subsequent values are written to a new data page
+    #[cfg(feature = "arrow")]
+    pub(crate) fn finish_data_page(&mut self) -> Result<()> {
+        if self.page_metrics.num_buffered_values > 0 {
+            self.add_data_page()?;
+        }
+        Ok(())
+    }
+
     /// Returns total number of rows written by this column writer so far.
     /// This value is also returned when column writer is closed.
     pub fn get_total_rows_written(&self) -> u64 {
@@ -699,7 +717,16 @@ impl<'a, E: ColumnValueEncoder> GenericColumnWriter<'a, E> {
 
         match value_indices {
             Some(indices) => {
-                let indices = &indices[values_offset..values_offset + values_to_write];
+                let indices = indices
+                    .get(values_offset..values_offset + values_to_write)
+                    .ok_or_else(|| {
+                        general_err!(
+                            "Expected {} value indices from offset {}, got {}",
+                            values_to_write,
+                            values_offset,
+                            indices.len()
+                        )
+                    })?;
                 self.encoder.write_gather(values, indices)?;
             }
             None => self.encoder.write(values, values_offset, values_to_write)?,
@@ -1137,7 +1164,6 @@ impl<'a, E: ColumnValueEncoder> GenericColumnWriter<'a, E> {
 
             builder = builder
                 .set_statistics(statistics)
-                .set_unencoded_byte_array_data_bytes(self.column_metrics.variable_length_bytes)
                 .set_repetition_level_histogram(
                     self.column_metrics.repetition_level_histogram.take(),
                 )
@@ -1146,6 +1172,10 @@ impl<'a, E: ColumnValueEncoder> GenericColumnWriter<'a, E> {
                 );
         }
 
+        // Unlike the level histograms, the unencoded size is recorded without statistics
+        builder =
+            builder.set_unencoded_byte_array_data_bytes(self.column_metrics.variable_length_bytes);
+
         let metadata = builder.build()?;
         Ok(metadata)
     }
@@ -2966,6 +2996,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_size_statistics_disabled() {
+        let page_writer = get_test_page_writer();
+        let props = WriterProperties::builder()
+            .set_statistics_enabled(EnabledStatistics::None)
+            .build();
+        let mut writer =
+            get_test_column_writer::<ByteArrayType>(page_writer, 1, 0, Arc::new(props));
+
+        let data: Vec<ByteArray> = vec!["a".into(), "bcd".into(), "".into()];
+        writer
+            .write_batch(&data, Some(&[1, 0, 1, 1]), None)
+            .unwrap();
+        writer.flush_data_pages().unwrap();
+        writer.write_batch(&data[..1], Some(&[1]), None).unwrap();
+
+        let r = writer.close().unwrap();
+        assert_eq!(r.rows_written, 5);
+        assert!(r.metadata.statistics().is_none());
+        assert_eq!(r.metadata.unencoded_byte_array_data_bytes(), Some(5));
+        assert!(r.metadata.repetition_level_histogram().is_none());
+        assert!(r.metadata.definition_level_histogram().is_none());
+
+        let offset_index = r.offset_index.unwrap();
+        assert_eq!(
+            offset_index.unencoded_byte_array_data_bytes,
+            Some(vec![4, 1])
+        );
+        assert!(r.column_index.is_none());
+    }
+
     #[test]
     fn test_statistics_truncating_byte_array() {
         let page_writer = get_test_page_writer();
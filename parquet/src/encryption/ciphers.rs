@@ -0,0 +1,327 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! AES-GCM and AES-CTR ciphers for Parquet modules
+//!
+//! An encrypted module is laid out as
+//!
+//! ```text
+//! length (4 bytes, little endian) | nonce (12 bytes) | ciphertext | tag (16 bytes, GCM only)
+//! ```
+//!
+//! where the length covers everything after itself.
+
+use crate::encryption::ParquetCipher;
+use crate::errors::{ParquetError, Result};
+use aes::{Aes128, Aes192, Aes256};
+use aes_gcm::aead::consts::U12;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, KeyInit, OsRng, Payload};
+use aes_gcm::{AesGcm, Nonce};
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use ctr::Ctr32BE;
+use std::fmt::{Debug, Formatter};
+
+pub(crate) const SIZE_LEN: usize = 4;
+pub(crate) const NONCE_LEN: usize = 12;
+pub(crate) const TAG_LEN: usize = 16;
+
+/// The length in bytes of the signature appended to a plaintext footer
+pub(crate) const FOOTER_SIGNATURE_LEN: usize = NONCE_LEN + TAG_LEN;
+
+/// Returns a new random nonce
+fn random_nonce() -> [u8; NONCE_LEN] {
+    let mut nonce = [0; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    nonce
+}
+
+/// Returns an error if `key` is not a valid AES key
+pub(crate) fn validate_key(key: &[u8]) -> Result<()> {
+    match key.len() {
+        16 | 24 | 32 => Ok(()),
+        n => Err(general_err!(
+            "Invalid AES key length {}, expected 16, 24 or 32 bytes",
+            n
+        )),
+    }
+}
+
+/// Splits an encrypted module into its nonce and ciphertext, validating its length
+fn split_module(module: &[u8], min_len: usize) -> Result<(&[u8], &[u8])> {
+    if module.len() < SIZE_LEN + min_len {
+        return Err(general_err!(
+            "Encrypted module too short: {} bytes",
+            module.len()
+        ));
+    }
+    let len = u32::from_le_bytes(module[..SIZE_LEN].try_into().unwrap()) as usize;
+    if len != module.len() - SIZE_LEN {
+        return Err(general_err!(
+            "Encrypted module length {} does not match buffer of {} bytes",
+            len,
+            module.len() - SIZE_LEN
+        ));
+    }
+    let body = &module[SIZE_LEN..];
+    Ok((&body[..NONCE_LEN], &body[NONCE_LEN..]))
+}
+
+/// Prepends the length to `nonce` and `ciphertext`
+fn join_module(nonce: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    let len = (nonce.len() + ciphertext.len()) as u32;
+    let mut module = Vec::with_capacity(SIZE_LEN + len as usize);
+    module.extend_from_slice(&len.to_le_bytes());
+    module.extend_from_slice(nonce);
+    module.extend_from_slice(ciphertext);
+    module
+}
+
+#[derive(Clone)]
+enum Gcm {
+    Aes128(AesGcm<Aes128, U12>),
+    Aes192(AesGcm<Aes192, U12>),
+    Aes256(AesGcm<Aes256, U12>),
+}
+
+macro_rules! gcm_dispatch {
+    ($gcm:expr, $cipher:ident => $e:expr) => {
+        match $gcm {
+            Gcm::Aes128($cipher) => $e,
+            Gcm::Aes192($cipher) => $e,
+            Gcm::Aes256($cipher) => $e,
+        }
+    };
+}
+
+impl Gcm {
+    fn try_new(key: &[u8]) -> Result<Self> {
+        validate_key(key)?;
+        let err = |_| general_err!("Invalid AES key");
+        Ok(match key.len() {
+            16 => Self::Aes128(AesGcm::new_from_slice(key).map_err(err)?),
+            24 => Self::Aes192(AesGcm::new_from_slice(key).map_err(err)?),
+            _ => Self::Aes256(AesGcm::new_from_slice(key).map_err(err)?),
+        })
+    }
+
+    /// Returns the ciphertext followed by the tag
+    fn encrypt(&self, nonce: &[u8], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        let nonce = Nonce::from_slice(nonce);
+        let payload = Payload {
+            msg: plaintext,
+            aad,
+        };
+        gcm_dispatch!(self, c => c.encrypt(nonce, payload))
+            .map_err(|_| general_err!("Failed to encrypt module"))
+    }
+
+    fn decrypt(&self, nonce: &[u8], ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        let nonce = Nonce::from_slice(nonce);
+        let payload = Payload {
+            msg: ciphertext,
+            aad,
+        };
+        gcm_dispatch!(self, c => c.decrypt(nonce, payload)).map_err(|_| {
+            general_err!("Failed to decrypt module, the key or additional authenticated data may be incorrect")
+        })
+    }
+}
+
+/// Applies the AES-CTR keystream for `nonce` to `data` in place
+fn apply_ctr(key: &[u8], nonce: &[u8], data: &mut [u8]) -> Result<()> {
+    // The counter occupies the last 4 bytes of the IV, starting at 1
+    let mut iv = [0; 16];
+    iv[..NONCE_LEN].copy_from_slice(nonce);
+    iv[15] = 1;
+    let err = |_| general_err!("Invalid AES key");
+    match key.len() {
+        16 => Ctr32BE::<Aes128>::new_from_slices(key, &iv)
+            .map_err(err)?
+            .apply_keystream(data),
+        24 => Ctr32BE::<Aes192>::new_from_slices(key, &iv)
+            .map_err(err)?
+            .apply_keystream(data),
+        _ => Ctr32BE::<Aes256>::new_from_slices(key, &iv)
+            .map_err(err)?
+            .apply_keystream(data),
+    }
+    Ok(())
+}
+
+/// The ciphers for all modules protected by a single key
+///
+/// Metadata and page headers always use AES-GCM, while page data uses
+/// AES-CTR for [`ParquetCipher::AesGcmCtrV1`]
+#[derive(Clone)]
+pub(crate) struct ModuleCipher {
+    gcm: Gcm,
+    /// The key to use for page data with AES-CTR
+    ctr_key: Option<Vec<u8>>,
+}
+
+impl Debug for ModuleCipher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModuleCipher")
+            .field("ctr", &self.ctr_key.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ModuleCipher {
+    pub(crate) fn try_new(key: &[u8], cipher: ParquetCipher) -> Result<Self> {
+        let ctr_key = match cipher {
+            ParquetCipher::AesGcmV1 => None,
+            ParquetCipher::AesGcmCtrV1 => Some(key.to_vec()),
+        };
+        Ok(Self {
+            gcm: Gcm::try_new(key)?,
+            ctr_key,
+        })
+    }
+
+    /// Encrypts a metadata module or page header with AES-GCM
+    pub(crate) fn encrypt(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        let nonce = random_nonce();
+        let ciphertext = self.gcm.encrypt(&nonce, plaintext, aad)?;
+        Ok(join_module(&nonce, &ciphertext))
+    }
+
+    /// Decrypts a module produced by [`Self::encrypt`]
+    pub(crate) fn decrypt(&self, module: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        let (nonce, ciphertext) = split_module(module, NONCE_LEN + TAG_LEN)?;
+        self.gcm.decrypt(nonce, ciphertext, aad)
+    }
+
+    /// Encrypts the data of a page
+    pub(crate) fn encrypt_page(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        match &self.ctr_key {
+            Some(key) => {
+                let nonce = random_nonce();
+                let mut data = plaintext.to_vec();
+                apply_ctr(key, &nonce, &mut data)?;
+                Ok(join_module(&nonce, &data))
+            }
+            None => self.encrypt(plaintext, aad),
+        }
+    }
+
+    /// Decrypts the data of a page produced by [`Self::encrypt_page`]
+    pub(crate) fn decrypt_page(&self, module: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        match &self.ctr_key {
+            Some(key) => {
+                let (nonce, ciphertext) = split_module(module, NONCE_LEN)?;
+                let mut data = ciphertext.to_vec();
+                apply_ctr(key, nonce, &mut data)?;
+                Ok(data)
+            }
+            None => self.decrypt(module, aad),
+        }
+    }
+
+    /// Computes the signature of a plaintext footer, the nonce followed by the GCM tag
+    pub(crate) fn sign(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        let nonce = random_nonce();
+        self.sign_with_nonce(&nonce, plaintext, aad)
+    }
+
+    fn sign_with_nonce(&self, nonce: &[u8], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        let ciphertext = self.gcm.encrypt(nonce, plaintext, aad)?;
+        let mut signature = Vec::with_capacity(FOOTER_SIGNATURE_LEN);
+        signature.extend_from_slice(nonce);
+        signature.extend_from_slice(&ciphertext[ciphertext.len() - TAG_LEN..]);
+        Ok(signature)
+    }
+
+    /// Verifies the signature of a plaintext footer produced by [`Self::sign`]
+    pub(crate) fn verify_signature(
+        &self,
+        plaintext: &[u8],
+        signature: &[u8],
+        aad: &[u8],
+    ) -> Result<()> {
+        if signature.len() != FOOTER_SIGNATURE_LEN {
+            return Err(general_err!(
+                "Invalid footer signature length {}, expected {}",
+                signature.len(),
+                FOOTER_SIGNATURE_LEN
+            ));
+        }
+        let expected = self.sign_with_nonce(&signature[..NONCE_LEN], plaintext, aad)?;
+        // The signature is not secret, so a constant time comparison is not required
+        match expected == signature {
+            true => Ok(()),
+            false => Err(general_err!("Footer signature verification failed")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for key_len in [16, 24, 32] {
+            let key = vec![7; key_len];
+            for cipher in [ParquetCipher::AesGcmV1, ParquetCipher::AesGcmCtrV1] {
+                let cipher = ModuleCipher::try_new(&key, cipher).unwrap();
+
+                let module = cipher.encrypt(b"metadata", b"aad").unwrap();
+                assert_eq!(module.len(), SIZE_LEN + NONCE_LEN + 8 + TAG_LEN);
+                assert_eq!(cipher.decrypt(&module, b"aad").unwrap(), b"metadata");
+                cipher.decrypt(&module, b"other").unwrap_err();
+
+                let page = cipher.encrypt_page(b"page data", b"aad").unwrap();
+                assert_eq!(cipher.decrypt_page(&page, b"aad").unwrap(), b"page data");
+            }
+        }
+    }
+
+    #[test]
+    fn test_ctr_layout() {
+        let cipher = ModuleCipher::try_new(&[1; 16], ParquetCipher::AesGcmCtrV1).unwrap();
+        let page = cipher.encrypt_page(b"page data", b"").unwrap();
+        assert_eq!(page.len(), SIZE_LEN + NONCE_LEN + 9);
+        assert_eq!(&page[..SIZE_LEN], &21_u32.to_le_bytes());
+    }
+
+    #[test]
+    fn test_signature() {
+        let cipher = ModuleCipher::try_new(&[1; 32], ParquetCipher::AesGcmV1).unwrap();
+        let signature = cipher.sign(b"footer", b"aad").unwrap();
+        assert_eq!(signature.len(), FOOTER_SIGNATURE_LEN);
+        cipher
+            .verify_signature(b"footer", &signature, b"aad")
+            .unwrap();
+        cipher
+            .verify_signature(b"footes", &signature, b"aad")
+            .unwrap_err();
+        cipher
+            .verify_signature(b"footer", &signature, b"aae")
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        let err = ModuleCipher::try_new(&[1; 10], ParquetCipher::AesGcmV1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parquet error: Invalid AES key length 10, expected 16, 24 or 32 bytes"
+        );
+    }
+}
@@ -0,0 +1,591 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Decryption of Parquet files, see [`FileDecryptionProperties`]
+
+use crate::encryption::ciphers::{validate_key, ModuleCipher, SIZE_LEN};
+use crate::encryption::modules::{create_footer_aad, create_module_aad, ModuleType};
+use crate::encryption::ParquetCipher;
+use crate::errors::{ParquetError, Result};
+use crate::file::metadata::{HeapSize, ParquetMetaData};
+use crate::format::{
+    ColumnChunk, ColumnCryptoMetaData, ColumnMetaData, EncryptionAlgorithm, PageHeader, PageType,
+};
+use crate::schema::types::SchemaDescriptor;
+use crate::thrift::{TCompactSliceInputProtocol, TSerializable};
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::io::Read;
+use std::sync::Arc;
+
+/// Retrieves the keys used to decrypt a file from their key metadata
+///
+/// Writers may store arbitrary key metadata in the file for the footer and for each
+/// column encrypted with its own key, for example the identifier of a key in a key
+/// management service, or a wrapped data key as produced by Spark and PyArrow's
+/// envelope encryption. A [`KeyRetriever`] resolves this metadata to the key itself.
+///
+/// ```
+/// # use parquet::encryption::decrypt::{FileDecryptionProperties, KeyRetriever};
+/// # use parquet::errors::{ParquetError, Result};
+/// # use std::collections::HashMap;
+/// # use std::sync::Arc;
+/// struct InMemoryKms(HashMap<Vec<u8>, Vec<u8>>);
+///
+/// impl KeyRetriever for InMemoryKms {
+///     fn retrieve_key(&self, key_metadata: &[u8]) -> Result<Vec<u8>> {
+///         self.0.get(key_metadata).cloned().ok_or_else(|| {
+///             ParquetError::General(format!("Unknown key {:?}", key_metadata))
+///         })
+///     }
+/// }
+///
+/// let kms = InMemoryKms(HashMap::from([(b"kf".to_vec(), b"0123456789012345".to_vec())]));
+/// let properties = FileDecryptionProperties::with_key_retriever(Arc::new(kms))
+///     .build()
+///     .unwrap();
+/// ```
+pub trait KeyRetriever: Send + Sync {
+    /// Returns the key identified by `key_metadata`, which is empty if
+    /// the writer did not store any key metadata
+    fn retrieve_key(&self, key_metadata: &[u8]) -> Result<Vec<u8>>;
+}
+
+#[derive(Clone)]
+enum DecryptionKeys {
+    Explicit {
+        footer_key: Vec<u8>,
+        column_keys: HashMap<String, Vec<u8>>,
+    },
+    ViaRetriever(Arc<dyn KeyRetriever>),
+}
+
+/// Properties used to decrypt a file written with Parquet modular encryption
+///
+/// Keys are either provided explicitly, a footer key and a key for each column
+/// encrypted with its own key, or retrieved from the key metadata stored in
+/// the file with a [`KeyRetriever`].
+///
+/// ```
+/// # use parquet::encryption::decrypt::FileDecryptionProperties;
+/// let properties = FileDecryptionProperties::builder(b"0123456789012345".to_vec())
+///     .with_column_key("ssn", b"1234567890123450".to_vec())
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct FileDecryptionProperties {
+    keys: DecryptionKeys,
+    aad_prefix: Option<Vec<u8>>,
+    footer_signature_verification: bool,
+}
+
+impl Debug for FileDecryptionProperties {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Never print keys
+        let retriever = matches!(self.keys, DecryptionKeys::ViaRetriever(_));
+        f.debug_struct("FileDecryptionProperties")
+            .field("key_retriever", &retriever)
+            .field("aad_prefix", &self.aad_prefix.is_some())
+            .field(
+                "footer_signature_verification",
+                &self.footer_signature_verification,
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+impl FileDecryptionProperties {
+    /// Returns a builder for properties with the given footer key
+    pub fn builder(footer_key: Vec<u8>) -> DecryptionPropertiesBuilder {
+        DecryptionPropertiesBuilder::new(Some(footer_key), None)
+    }
+
+    /// Returns a builder for properties that retrieve all keys with `key_retriever`
+    pub fn with_key_retriever(key_retriever: Arc<dyn KeyRetriever>) -> DecryptionPropertiesBuilder {
+        DecryptionPropertiesBuilder::new(None, Some(key_retriever))
+    }
+
+    /// Returns the AAD prefix to use if it is not stored in the file
+    pub fn aad_prefix(&self) -> Option<&[u8]> {
+        self.aad_prefix.as_deref()
+    }
+
+    /// Returns whether the signature of plaintext footers is verified
+    pub fn check_plaintext_footer_integrity(&self) -> bool {
+        self.footer_signature_verification
+    }
+
+    fn footer_key(&self, key_metadata: Option<&[u8]>) -> Result<Vec<u8>> {
+        match &self.keys {
+            DecryptionKeys::Explicit { footer_key, .. } => Ok(footer_key.clone()),
+            DecryptionKeys::ViaRetriever(retriever) => {
+                retriever.retrieve_key(key_metadata.unwrap_or_default())
+            }
+        }
+    }
+
+    fn column_key(&self, column_name: &str, key_metadata: Option<&[u8]>) -> Result<Vec<u8>> {
+        match &self.keys {
+            DecryptionKeys::Explicit { column_keys, .. } => {
+                column_keys.get(column_name).cloned().ok_or_else(|| {
+                    general_err!("No decryption key provided for column '{}'", column_name)
+                })
+            }
+            DecryptionKeys::ViaRetriever(retriever) => {
+                retriever.retrieve_key(key_metadata.unwrap_or_default())
+            }
+        }
+    }
+}
+
+/// Builder for [`FileDecryptionProperties`]
+pub struct DecryptionPropertiesBuilder {
+    footer_key: Option<Vec<u8>>,
+    key_retriever: Option<Arc<dyn KeyRetriever>>,
+    column_keys: HashMap<String, Vec<u8>>,
+    aad_prefix: Option<Vec<u8>>,
+    footer_signature_verification: bool,
+}
+
+impl DecryptionPropertiesBuilder {
+    fn new(footer_key: Option<Vec<u8>>, key_retriever: Option<Arc<dyn KeyRetriever>>) -> Self {
+        Self {
+            footer_key,
+            key_retriever,
+            column_keys: HashMap::new(),
+            aad_prefix: None,
+            footer_signature_verification: true,
+        }
+    }
+
+    /// Sets the key of the column at the dot separated path `column_name`
+    ///
+    /// Not supported with a [`KeyRetriever`]
+    pub fn with_column_key(mut self, column_name: &str, key: Vec<u8>) -> Self {
+        self.column_keys.insert(column_name.to_string(), key);
+        self
+    }
+
+    /// Sets the AAD prefix, required if the writer did not store it in the file
+    pub fn with_aad_prefix(mut self, aad_prefix: Vec<u8>) -> Self {
+        self.aad_prefix = Some(aad_prefix);
+        self
+    }
+
+    /// Disables verification of the signature of plaintext footers
+    pub fn disable_footer_signature_verification(mut self) -> Self {
+        self.footer_signature_verification = false;
+        self
+    }
+
+    /// Builds the [`FileDecryptionProperties`], validating the keys
+    pub fn build(self) -> Result<FileDecryptionProperties> {
+        let keys = match (self.footer_key, self.key_retriever) {
+            (Some(footer_key), None) => {
+                validate_key(&footer_key)?;
+                for key in self.column_keys.values() {
+                    validate_key(key)?;
+                }
+                DecryptionKeys::Explicit {
+                    footer_key,
+                    column_keys: self.column_keys,
+                }
+            }
+            (None, Some(retriever)) => {
+                if !self.column_keys.is_empty() {
+                    return Err(general_err!(
+                        "Column keys cannot be set when using a key retriever"
+                    ));
+                }
+                DecryptionKeys::ViaRetriever(retriever)
+            }
+            _ => unreachable!("constructed with either a footer key or a key retriever"),
+        };
+        Ok(FileDecryptionProperties {
+            keys,
+            aad_prefix: self.aad_prefix,
+            footer_signature_verification: self.footer_signature_verification,
+        })
+    }
+}
+
+/// Decrypts the modules of an encrypted file
+#[derive(Clone, Debug)]
+pub(crate) struct FileDecryptor {
+    properties: FileDecryptionProperties,
+    cipher: ParquetCipher,
+    footer_cipher: ModuleCipher,
+    file_aad: Vec<u8>,
+}
+
+// Need to implement HeapSize in this module as the fields are private
+impl HeapSize for FileDecryptor {
+    fn heap_size(&self) -> usize {
+        // The ciphers, any key retriever, and the overhead of the column key map are not counted
+        let keys = match &self.properties.keys {
+            DecryptionKeys::Explicit {
+                footer_key,
+                column_keys,
+            } => {
+                footer_key.capacity()
+                    + column_keys
+                        .iter()
+                        .map(|(name, key)| name.capacity() + key.capacity())
+                        .sum::<usize>()
+            }
+            DecryptionKeys::ViaRetriever(_) => 0,
+        };
+        let aad_prefix = self.properties.aad_prefix.as_ref().map_or(0, Vec::capacity);
+        keys + aad_prefix + self.file_aad.capacity()
+    }
+}
+
+impl PartialEq for FileDecryptor {
+    fn eq(&self, other: &Self) -> bool {
+        self.cipher == other.cipher && self.file_aad == other.file_aad
+    }
+}
+
+impl FileDecryptor {
+    /// Creates a decryptor for a file encrypted with `algorithm`
+    pub(crate) fn new(
+        properties: &FileDecryptionProperties,
+        algorithm: &EncryptionAlgorithm,
+        footer_key_metadata: Option<&[u8]>,
+    ) -> Result<Self> {
+        let (cipher, params) = match algorithm {
+            EncryptionAlgorithm::AESGCMV1(a) => (
+                ParquetCipher::AesGcmV1,
+                (&a.aad_prefix, &a.aad_file_unique, a.supply_aad_prefix),
+            ),
+            EncryptionAlgorithm::AESGCMCTRV1(a) => (
+                ParquetCipher::AesGcmCtrV1,
+                (&a.aad_prefix, &a.aad_file_unique, a.supply_aad_prefix),
+            ),
+        };
+        let (stored_prefix, aad_file_unique, supply_aad_prefix) = params;
+        let aad_file_unique = aad_file_unique
+            .as_ref()
+            .ok_or_else(|| general_err!("Encrypted file is missing its unique AAD identifier"))?;
+
+        let aad_prefix: &[u8] = match (properties.aad_prefix(), stored_prefix) {
+            (Some(supplied), Some(stored)) if supplied != stored.as_slice() => {
+                return Err(general_err!(
+                    "AAD prefix does not match the AAD prefix stored in the file"
+                ))
+            }
+            (Some(supplied), _) => supplied,
+            (None, Some(stored)) => stored,
+            (None, None) if supply_aad_prefix == Some(true) => {
+                return Err(general_err!(
+                    "File was encrypted with an AAD prefix that is not stored in the file, \
+                     it must be provided in the decryption properties"
+                ))
+            }
+            (None, None) => &[],
+        };
+        let mut file_aad = aad_prefix.to_vec();
+        file_aad.extend_from_slice(aad_file_unique);
+
+        let footer_key = properties.footer_key(footer_key_metadata)?;
+        Ok(Self {
+            properties: properties.clone(),
+            cipher,
+            footer_cipher: ModuleCipher::try_new(&footer_key, cipher)?,
+            file_aad,
+        })
+    }
+
+    /// Decrypts an encrypted footer
+    pub(crate) fn decrypt_footer(&self, module: &[u8]) -> Result<Vec<u8>> {
+        let aad = create_footer_aad(&self.file_aad);
+        self.footer_cipher.decrypt(module, &aad)
+    }
+
+    /// Verifies the `signature` of a plaintext `footer`, if enabled
+    pub(crate) fn verify_footer_signature(&self, footer: &[u8], signature: &[u8]) -> Result<()> {
+        if !self.properties.check_plaintext_footer_integrity() {
+            return Ok(());
+        }
+        let aad = create_footer_aad(&self.file_aad);
+        self.footer_cipher.verify_signature(footer, signature, &aad)
+    }
+
+    /// Returns the cipher for a column chunk, or an error if its key is not available
+    fn column_cipher(&self, column_chunk_crypto: &ColumnCryptoMetaData) -> Result<ModuleCipher> {
+        match column_chunk_crypto {
+            ColumnCryptoMetaData::ENCRYPTIONWITHFOOTERKEY(_) => Ok(self.footer_cipher.clone()),
+            ColumnCryptoMetaData::ENCRYPTIONWITHCOLUMNKEY(c) => {
+                let column_name = c.path_in_schema.join(".");
+                let key = self
+                    .properties
+                    .column_key(&column_name, c.key_metadata.as_deref())?;
+                ModuleCipher::try_new(&key, self.cipher)
+            }
+        }
+    }
+
+    /// Decrypts the `encrypted_column_metadata` of `column_chunk` if present
+    ///
+    /// Files with a plaintext footer also store column metadata without statistics
+    /// in plaintext, however this is only used for columns that are not encrypted
+    pub(crate) fn decrypt_column_metadata(
+        &self,
+        column_chunk: &mut ColumnChunk,
+        row_group_idx: usize,
+        column_ordinal: usize,
+    ) -> Result<()> {
+        let (crypto, encrypted) = match (
+            &column_chunk.crypto_metadata,
+            &column_chunk.encrypted_column_metadata,
+        ) {
+            (Some(crypto), Some(encrypted)) => (crypto, encrypted),
+            _ => return Ok(()),
+        };
+        let cipher = self.column_cipher(crypto)?;
+        let aad = create_module_aad(
+            &self.file_aad,
+            ModuleType::ColumnMetaData,
+            row_group_idx,
+            column_ordinal,
+            None,
+        )?;
+        let decrypted = cipher.decrypt(encrypted, &aad)?;
+        let mut prot = TCompactSliceInputProtocol::new(&decrypted);
+        let metadata = ColumnMetaData::read_from_in_protocol(&mut prot)
+            .map_err(|e| general_err!("Could not parse column metadata: {}", e))?;
+        column_chunk.meta_data = Some(metadata);
+        Ok(())
+    }
+
+    /// Returns an error if a column key was provided for a column not in `schema`
+    pub(crate) fn validate_column_keys(&self, schema: &SchemaDescriptor) -> Result<()> {
+        let column_keys = match &self.properties.keys {
+            DecryptionKeys::Explicit { column_keys, .. } => column_keys,
+            DecryptionKeys::ViaRetriever(_) => return Ok(()),
+        };
+        for column_name in column_keys.keys() {
+            if !schema
+                .columns()
+                .iter()
+                .any(|c| c.path().string() == *column_name)
+            {
+                return Err(general_err!(
+                    "Decryption key provided for column '{}' which is not in the file schema",
+                    column_name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the ordinal of a row group in the file, as stored by the writer if available
+pub(crate) fn row_group_ordinal(ordinal: Option<i16>, row_group_idx: usize) -> usize {
+    ordinal
+        .and_then(|o| usize::try_from(o).ok())
+        .unwrap_or(row_group_idx)
+}
+
+/// The state required to decrypt the modules of a column chunk
+#[derive(Clone, Debug)]
+pub(crate) struct CryptoContext {
+    row_group_idx: usize,
+    column_ordinal: usize,
+    cipher: ModuleCipher,
+    file_aad: Vec<u8>,
+}
+
+impl CryptoContext {
+    /// Returns the context for the column chunk at `column_ordinal` of the row group at
+    /// `row_group_idx` in `metadata`, or `None` if it is not encrypted
+    pub(crate) fn for_column(
+        metadata: &ParquetMetaData,
+        row_group_idx: usize,
+        column_ordinal: usize,
+    ) -> Result<Option<Self>> {
+        let row_group = metadata.row_group(row_group_idx);
+        let column = row_group.column(column_ordinal);
+        let crypto = match column.crypto_metadata() {
+            Some(crypto) => crypto,
+            None => return Ok(None),
+        };
+        let Some(decryptor) = metadata.file_decryptor() else {
+            return Err(general_err!(
+                "Column '{}' is encrypted but no decryption properties were provided",
+                column.column_path()
+            ));
+        };
+        Ok(Some(Self {
+            // Row groups may have been filtered out of `metadata`
+            row_group_idx: row_group_ordinal(row_group.ordinal(), row_group_idx),
+            column_ordinal,
+            cipher: decryptor.column_cipher(crypto)?,
+            file_aad: decryptor.file_aad.clone(),
+        }))
+    }
+
+    fn aad(&self, module_type: ModuleType, page_ordinal: Option<usize>) -> Result<Vec<u8>> {
+        create_module_aad(
+            &self.file_aad,
+            module_type,
+            self.row_group_idx,
+            self.column_ordinal,
+            page_ordinal,
+        )
+    }
+
+    /// Decrypts the column or offset index of this column chunk
+    pub(crate) fn decrypt_page_index(
+        &self,
+        module: &[u8],
+        module_type: ModuleType,
+    ) -> Result<Vec<u8>> {
+        self.cipher.decrypt(module, &self.aad(module_type, None)?)
+    }
+
+    /// Decrypts the bloom filter of this column chunk, stored as an encrypted header
+    /// followed by an encrypted bitset, returning the serialized header and bitset
+    pub(crate) fn decrypt_bloom_filter(&self, modules: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+        let header_len = modules
+            .get(..SIZE_LEN)
+            .map(|len| u32::from_le_bytes(len.try_into().unwrap()) as usize)
+            .ok_or_else(|| general_err!("Encrypted bloom filter is truncated"))?;
+        let split = SIZE_LEN
+            .checked_add(header_len)
+            .filter(|split| *split <= modules.len())
+            .ok_or_else(|| general_err!("Encrypted bloom filter is truncated"))?;
+        let (header, bitset) = modules.split_at(split);
+        let header = self
+            .cipher
+            .decrypt(header, &self.aad(ModuleType::BloomFilterHeader, None)?)?;
+        let bitset = self
+            .cipher
+            .decrypt(bitset, &self.aad(ModuleType::BloomFilterBitset, None)?)?;
+        Ok((header, bitset))
+    }
+}
+
+/// Decrypts the pages of a column chunk in order, tracking the page ordinal
+/// that forms part of the additional authenticated data of data pages
+#[derive(Debug)]
+pub(crate) struct PageDecryptor {
+    context: CryptoContext,
+    page_ordinal: usize,
+    /// Whether the next page is the first of the column chunk, which may be a dictionary page
+    first_page: bool,
+}
+
+impl PageDecryptor {
+    pub(crate) fn new(context: CryptoContext) -> Self {
+        Self {
+            context,
+            page_ordinal: 0,
+            first_page: true,
+        }
+    }
+
+    /// Reads an encrypted [`PageHeader`] from `input`, returning the number of bytes read
+    pub(crate) fn read_page_header<T: Read>(&self, input: &mut T) -> Result<(usize, PageHeader)> {
+        let mut len = [0; SIZE_LEN];
+        input.read_exact(&mut len)?;
+        let module_len = SIZE_LEN + u32::from_le_bytes(len) as usize;
+
+        let mut module = Vec::with_capacity(module_len);
+        module.extend_from_slice(&len);
+        input
+            .take((module_len - SIZE_LEN) as u64)
+            .read_to_end(&mut module)?;
+        if module.len() != module_len {
+            return Err(eof_err!(
+                "Expected to read {} bytes of page header, read only {}",
+                module_len,
+                module.len()
+            ));
+        }
+        Ok((module_len, self.decrypt_page_header(&module)?))
+    }
+
+    /// Decodes an encrypted [`PageHeader`] from the start of `buffer`, returning the
+    /// number of bytes it occupies
+    pub(crate) fn decode_page_header(&self, buffer: &[u8]) -> Result<(usize, PageHeader)> {
+        if buffer.len() < SIZE_LEN {
+            return Err(eof_err!("Encrypted page header too short"));
+        }
+        let len = u32::from_le_bytes(buffer[..SIZE_LEN].try_into().unwrap()) as usize;
+        let module_len = SIZE_LEN + len;
+        if buffer.len() < module_len {
+            return Err(eof_err!(
+                "Expected {} bytes of page header, found {}",
+                module_len,
+                buffer.len()
+            ));
+        }
+        Ok((module_len, self.decrypt_page_header(&buffer[..module_len])?))
+    }
+
+    fn decrypt_page_header(&self, module: &[u8]) -> Result<PageHeader> {
+        let cipher = &self.context.cipher;
+        // Only the first page may be a dictionary page, whose header is
+        // authenticated with a different module type
+        let dictionary = match self.first_page {
+            true => {
+                let aad = self.context.aad(ModuleType::DictionaryPageHeader, None)?;
+                cipher.decrypt(module, &aad).ok()
+            }
+            false => None,
+        };
+        let decrypted = match dictionary {
+            Some(decrypted) => decrypted,
+            None => {
+                let aad = self
+                    .context
+                    .aad(ModuleType::DataPageHeader, Some(self.page_ordinal))?;
+                cipher.decrypt(module, &aad)?
+            }
+        };
+        let mut prot = TCompactSliceInputProtocol::new(&decrypted);
+        Ok(PageHeader::read_from_in_protocol(&mut prot)?)
+    }
+
+    /// Decrypts the data of the page described by `header`
+    pub(crate) fn decrypt_page(&self, header: &PageHeader, buffer: &[u8]) -> Result<Bytes> {
+        let aad = match header.type_ {
+            PageType::DICTIONARY_PAGE => self.context.aad(ModuleType::DictionaryPage, None)?,
+            _ => self
+                .context
+                .aad(ModuleType::DataPage, Some(self.page_ordinal))?,
+        };
+        Ok(self.context.cipher.decrypt_page(buffer, &aad)?.into())
+    }
+
+    /// Advances past the page described by `header`
+    pub(crate) fn advance(&mut self, header: &PageHeader) {
+        self.first_page = false;
+        if header.type_ != PageType::DICTIONARY_PAGE {
+            self.page_ordinal += 1;
+        }
+    }
+
+    /// Advances past a data page without reading its header
+    pub(crate) fn skip_data_page(&mut self) {
+        self.first_page = false;
+        self.page_ordinal += 1;
+    }
+}
@@ -0,0 +1,502 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Encryption of Parquet files, see [`FileEncryptionProperties`]
+
+use crate::bloom_filter::Sbbf;
+use crate::column::page::CompressedPage;
+use crate::encryption::ciphers::{validate_key, ModuleCipher};
+use crate::encryption::modules::{create_footer_aad, create_module_aad, ModuleType};
+use crate::encryption::ParquetCipher;
+use crate::errors::{ParquetError, Result};
+use crate::format::{
+    AesGcmCtrV1, AesGcmV1, ColumnChunk, ColumnCryptoMetaData, EncryptionAlgorithm,
+    EncryptionWithColumnKey, EncryptionWithFooterKey, FileCryptoMetaData, PageHeader, PageType,
+};
+use crate::thrift::TSerializable;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use thrift::protocol::TCompactOutputProtocol;
+
+/// The length of the random part of the AAD that uniquely identifies a file
+const AAD_FILE_UNIQUE_LEN: usize = 8;
+
+#[derive(Clone, PartialEq)]
+struct EncryptionKey {
+    key: Vec<u8>,
+    key_metadata: Option<Vec<u8>>,
+}
+
+/// Properties used to write a file with Parquet modular encryption
+///
+/// By default all columns are encrypted with the footer key. If any column keys are
+/// set, only those columns are encrypted, each with its own key, and all other
+/// columns are written in plaintext. The footer is encrypted unless
+/// [`EncryptionPropertiesBuilder::with_plaintext_footer`] is set, in which case it is
+/// signed instead so that readers without the keys can still read the plaintext columns.
+///
+/// ```
+/// # use parquet::encryption::encrypt::FileEncryptionProperties;
+/// let properties = FileEncryptionProperties::builder(b"0123456789012345".to_vec())
+///     .with_footer_key_metadata(b"kf".to_vec())
+///     .with_column_key_and_metadata("ssn", b"1234567890123450".to_vec(), b"kc1".to_vec())
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct FileEncryptionProperties {
+    encrypt_footer: bool,
+    footer_key: EncryptionKey,
+    column_keys: HashMap<String, EncryptionKey>,
+    aad_prefix: Option<Vec<u8>>,
+    store_aad_prefix: bool,
+    cipher: ParquetCipher,
+}
+
+impl Debug for FileEncryptionProperties {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Never print keys
+        let mut columns: Vec<_> = self.column_keys.keys().collect();
+        columns.sort_unstable();
+        f.debug_struct("FileEncryptionProperties")
+            .field("encrypt_footer", &self.encrypt_footer)
+            .field("encrypted_columns", &columns)
+            .field("aad_prefix", &self.aad_prefix.is_some())
+            .field("store_aad_prefix", &self.store_aad_prefix)
+            .field("cipher", &self.cipher)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FileEncryptionProperties {
+    /// Returns a builder for properties with the given footer key
+    pub fn builder(footer_key: Vec<u8>) -> EncryptionPropertiesBuilder {
+        EncryptionPropertiesBuilder::new(footer_key)
+    }
+
+    /// Returns whether the footer is encrypted, otherwise it is signed
+    pub fn encrypt_footer(&self) -> bool {
+        self.encrypt_footer
+    }
+
+    /// Returns the cipher used to encrypt the file
+    pub fn cipher(&self) -> ParquetCipher {
+        self.cipher
+    }
+
+    /// Returns whether the column at the dot separated path `column_name` is encrypted
+    pub fn is_column_encrypted(&self, column_name: &str) -> bool {
+        self.column_keys.is_empty() || self.column_keys.contains_key(column_name)
+    }
+}
+
+/// Builder for [`FileEncryptionProperties`]
+pub struct EncryptionPropertiesBuilder {
+    encrypt_footer: bool,
+    footer_key: EncryptionKey,
+    column_keys: HashMap<String, EncryptionKey>,
+    aad_prefix: Option<Vec<u8>>,
+    store_aad_prefix: bool,
+    cipher: ParquetCipher,
+}
+
+impl EncryptionPropertiesBuilder {
+    fn new(footer_key: Vec<u8>) -> Self {
+        Self {
+            encrypt_footer: true,
+            footer_key: EncryptionKey {
+                key: footer_key,
+                key_metadata: None,
+            },
+            column_keys: HashMap::new(),
+            aad_prefix: None,
+            store_aad_prefix: true,
+            cipher: ParquetCipher::default(),
+        }
+    }
+
+    /// Writes the footer in plaintext with a signature, rather than encrypted
+    pub fn with_plaintext_footer(mut self, plaintext_footer: bool) -> Self {
+        self.encrypt_footer = !plaintext_footer;
+        self
+    }
+
+    /// Sets the key metadata stored in the file for the footer key
+    pub fn with_footer_key_metadata(mut self, key_metadata: Vec<u8>) -> Self {
+        self.footer_key.key_metadata = Some(key_metadata);
+        self
+    }
+
+    /// Encrypts the column at the dot separated path `column_name` with its own `key`
+    pub fn with_column_key(mut self, column_name: &str, key: Vec<u8>) -> Self {
+        let key = EncryptionKey {
+            key,
+            key_metadata: None,
+        };
+        self.column_keys.insert(column_name.to_string(), key);
+        self
+    }
+
+    /// Encrypts the column at the dot separated path `column_name` with its own `key`,
+    /// storing `key_metadata` in the file to identify the key
+    pub fn with_column_key_and_metadata(
+        mut self,
+        column_name: &str,
+        key: Vec<u8>,
+        key_metadata: Vec<u8>,
+    ) -> Self {
+        let key = EncryptionKey {
+            key,
+            key_metadata: Some(key_metadata),
+        };
+        self.column_keys.insert(column_name.to_string(), key);
+        self
+    }
+
+    /// Sets a prefix for the additional authenticated data of every module,
+    /// for example to bind the file to the table it belongs to
+    pub fn with_aad_prefix(mut self, aad_prefix: Vec<u8>) -> Self {
+        self.aad_prefix = Some(aad_prefix);
+        self
+    }
+
+    /// Sets whether the AAD prefix is stored in the file (defaults to `true`),
+    /// readers must otherwise supply it
+    pub fn with_aad_prefix_storage(mut self, store_aad_prefix: bool) -> Self {
+        self.store_aad_prefix = store_aad_prefix;
+        self
+    }
+
+    /// Sets the cipher (defaults to [`ParquetCipher::AesGcmV1`])
+    pub fn with_cipher(mut self, cipher: ParquetCipher) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
+    /// Builds the [`FileEncryptionProperties`], validating the keys
+    pub fn build(self) -> Result<FileEncryptionProperties> {
+        validate_key(&self.footer_key.key)?;
+        for key in self.column_keys.values() {
+            validate_key(&key.key)?;
+        }
+        Ok(FileEncryptionProperties {
+            encrypt_footer: self.encrypt_footer,
+            footer_key: self.footer_key,
+            column_keys: self.column_keys,
+            aad_prefix: self.aad_prefix,
+            store_aad_prefix: self.store_aad_prefix,
+            cipher: self.cipher,
+        })
+    }
+}
+
+/// Encrypts the modules of a file
+#[derive(Debug)]
+pub(crate) struct FileEncryptor {
+    properties: FileEncryptionProperties,
+    aad_file_unique: Vec<u8>,
+    file_aad: Vec<u8>,
+    footer_cipher: ModuleCipher,
+    column_ciphers: HashMap<String, ModuleCipher>,
+}
+
+impl FileEncryptor {
+    pub(crate) fn new(properties: FileEncryptionProperties) -> Result<Self> {
+        let mut aad_file_unique = vec![0; AAD_FILE_UNIQUE_LEN];
+        OsRng.fill_bytes(&mut aad_file_unique);
+
+        let mut file_aad = properties.aad_prefix.clone().unwrap_or_default();
+        file_aad.extend_from_slice(&aad_file_unique);
+
+        let footer_cipher = ModuleCipher::try_new(&properties.footer_key.key, properties.cipher)?;
+        let column_ciphers = properties
+            .column_keys
+            .iter()
+            .map(|(name, key)| {
+                let cipher = ModuleCipher::try_new(&key.key, properties.cipher)?;
+                Ok((name.clone(), cipher))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            properties,
+            aad_file_unique,
+            file_aad,
+            footer_cipher,
+            column_ciphers,
+        })
+    }
+
+    pub(crate) fn properties(&self) -> &FileEncryptionProperties {
+        &self.properties
+    }
+
+    /// Returns the cipher for the column at `column_name`, or `None` if it is not encrypted
+    fn column_cipher(&self, column_name: &str) -> Option<&ModuleCipher> {
+        match self.properties.column_keys.is_empty() {
+            true => Some(&self.footer_cipher),
+            false => self.column_ciphers.get(column_name),
+        }
+    }
+
+    /// Returns the encryption algorithm to store in the file
+    pub(crate) fn algorithm(&self) -> EncryptionAlgorithm {
+        let aad_prefix = match self.properties.store_aad_prefix {
+            true => self.properties.aad_prefix.clone(),
+            false => None,
+        };
+        let supply_aad_prefix = match self.properties.aad_prefix.is_some() {
+            true => Some(!self.properties.store_aad_prefix),
+            false => None,
+        };
+        let aad_file_unique = Some(self.aad_file_unique.clone());
+        match self.properties.cipher {
+            ParquetCipher::AesGcmV1 => EncryptionAlgorithm::AESGCMV1(AesGcmV1 {
+                aad_prefix,
+                aad_file_unique,
+                supply_aad_prefix,
+            }),
+            ParquetCipher::AesGcmCtrV1 => EncryptionAlgorithm::AESGCMCTRV1(AesGcmCtrV1 {
+                aad_prefix,
+                aad_file_unique,
+                supply_aad_prefix,
+            }),
+        }
+    }
+
+    /// Returns the [`FileCryptoMetaData`] written before an encrypted footer
+    pub(crate) fn file_crypto_metadata(&self) -> FileCryptoMetaData {
+        FileCryptoMetaData {
+            encryption_algorithm: self.algorithm(),
+            key_metadata: self.properties.footer_key.key_metadata.clone(),
+        }
+    }
+
+    /// Returns the footer key metadata stored in a plaintext footer
+    pub(crate) fn footer_key_metadata(&self) -> Option<Vec<u8>> {
+        self.properties.footer_key.key_metadata.clone()
+    }
+
+    /// Encrypts a serialized footer
+    pub(crate) fn encrypt_footer(&self, footer: &[u8]) -> Result<Vec<u8>> {
+        let aad = create_footer_aad(&self.file_aad);
+        self.footer_cipher.encrypt(footer, &aad)
+    }
+
+    /// Signs a serialized plaintext footer
+    pub(crate) fn sign_footer(&self, footer: &[u8]) -> Result<Vec<u8>> {
+        let aad = create_footer_aad(&self.file_aad);
+        self.footer_cipher.sign(footer, &aad)
+    }
+
+    /// Encrypts a column or offset index
+    pub(crate) fn encrypt_page_index<T: TSerializable>(
+        &self,
+        index: &T,
+        module_type: ModuleType,
+        column_chunk: &ColumnChunk,
+        row_group_idx: usize,
+        column_ordinal: usize,
+    ) -> Result<Option<Vec<u8>>> {
+        let Some(cipher) = self.column_chunk_cipher(column_chunk)? else {
+            return Ok(None);
+        };
+        let aad = create_module_aad(
+            &self.file_aad,
+            module_type,
+            row_group_idx,
+            column_ordinal,
+            None,
+        )?;
+        Ok(Some(cipher.encrypt(&serialize(index)?, &aad)?))
+    }
+
+    /// Encrypts the header and bitset of the bloom filter of column `column_name`,
+    /// returning `None` if the column is not encrypted
+    pub(crate) fn encrypt_bloom_filter(
+        &self,
+        bloom_filter: &Sbbf,
+        column_name: &str,
+        row_group_idx: usize,
+        column_ordinal: usize,
+    ) -> Result<Option<Vec<u8>>> {
+        let Some(cipher) = self.column_cipher(column_name) else {
+            return Ok(None);
+        };
+        let aad = |module_type| {
+            create_module_aad(
+                &self.file_aad,
+                module_type,
+                row_group_idx,
+                column_ordinal,
+                None,
+            )
+        };
+        let header = serialize(&bloom_filter.header())?;
+        let mut encrypted = cipher.encrypt(&header, &aad(ModuleType::BloomFilterHeader)?)?;
+        let bitset = bloom_filter.bitset();
+        encrypted.extend(cipher.encrypt(&bitset, &aad(ModuleType::BloomFilterBitset)?)?);
+        Ok(Some(encrypted))
+    }
+
+    fn column_chunk_cipher(&self, column_chunk: &ColumnChunk) -> Result<Option<&ModuleCipher>> {
+        let Some(meta_data) = column_chunk.meta_data.as_ref() else {
+            return Err(general_err!("Expected to have column metadata"));
+        };
+        Ok(self.column_cipher(&meta_data.path_in_schema.join(".")))
+    }
+
+    /// Encrypts the metadata of `column_chunk` if its column is encrypted
+    ///
+    /// With an encrypted footer, the metadata of columns encrypted with the footer
+    /// key is protected by the footer itself. Otherwise it is encrypted separately
+    /// and, with a plaintext footer, a copy without statistics is left in plaintext
+    /// for readers without the key.
+    pub(crate) fn encrypt_column_chunk(
+        &self,
+        column_chunk: &mut ColumnChunk,
+        row_group_idx: usize,
+        column_ordinal: usize,
+    ) -> Result<()> {
+        let Some(meta_data) = column_chunk.meta_data.as_mut() else {
+            return Err(general_err!("Expected to have column metadata"));
+        };
+        let column_name = meta_data.path_in_schema.join(".");
+        let Some(cipher) = self.column_cipher(&column_name) else {
+            return Ok(());
+        };
+        let column_key = self.properties.column_keys.get(&column_name);
+
+        column_chunk.crypto_metadata = Some(match column_key {
+            Some(key) => ColumnCryptoMetaData::ENCRYPTIONWITHCOLUMNKEY(EncryptionWithColumnKey {
+                path_in_schema: meta_data.path_in_schema.clone(),
+                key_metadata: key.key_metadata.clone(),
+            }),
+            None => ColumnCryptoMetaData::ENCRYPTIONWITHFOOTERKEY(EncryptionWithFooterKey {}),
+        });
+
+        if self.properties.encrypt_footer && column_key.is_none() {
+            return Ok(());
+        }
+
+        let aad = create_module_aad(
+            &self.file_aad,
+            ModuleType::ColumnMetaData,
+            row_group_idx,
+            column_ordinal,
+            None,
+        )?;
+        column_chunk.encrypted_column_metadata =
+            Some(cipher.encrypt(&serialize(&*meta_data)?, &aad)?);
+
+        if self.properties.encrypt_footer {
+            column_chunk.meta_data = None;
+        } else {
+            meta_data.statistics = None;
+            meta_data.encoding_stats = None;
+            meta_data.size_statistics = None;
+        }
+        Ok(())
+    }
+
+    /// Returns a [`PageEncryptor`] for a column chunk, or `None` if it is not encrypted
+    pub(crate) fn page_encryptor(
+        &self,
+        column_name: &str,
+        row_group_idx: usize,
+        column_ordinal: usize,
+    ) -> Option<PageEncryptor> {
+        let cipher = self.column_cipher(column_name)?;
+        Some(PageEncryptor {
+            cipher: cipher.clone(),
+            file_aad: self.file_aad.clone(),
+            row_group_idx,
+            column_ordinal,
+            page_ordinal: 0,
+        })
+    }
+}
+
+fn serialize<T: TSerializable>(value: &T) -> Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    let mut protocol = TCompactOutputProtocol::new(&mut buffer);
+    value.write_to_out_protocol(&mut protocol)?;
+    Ok(buffer)
+}
+
+/// Encrypts the pages of a column chunk in order, tracking the page ordinal
+/// that forms part of the additional authenticated data of data pages
+#[derive(Debug)]
+pub(crate) struct PageEncryptor {
+    cipher: ModuleCipher,
+    file_aad: Vec<u8>,
+    row_group_idx: usize,
+    column_ordinal: usize,
+    page_ordinal: usize,
+}
+
+impl PageEncryptor {
+    fn aad(&self, module_type: ModuleType) -> Result<Vec<u8>> {
+        create_module_aad(
+            &self.file_aad,
+            module_type,
+            self.row_group_idx,
+            self.column_ordinal,
+            Some(self.page_ordinal),
+        )
+    }
+
+    /// Encrypts the data of a page of type `page_type`
+    pub(crate) fn encrypt_page(&self, page_type: PageType, data: &[u8]) -> Result<Vec<u8>> {
+        let module_type = match page_type {
+            PageType::DICTIONARY_PAGE => ModuleType::DictionaryPage,
+            _ => ModuleType::DataPage,
+        };
+        self.cipher.encrypt_page(data, &self.aad(module_type)?)
+    }
+
+    /// Encrypts a page header, advancing to the next page if it is a data page
+    ///
+    /// The header must already describe the size of the encrypted page data
+    pub(crate) fn encrypt_page_header(&mut self, header: &PageHeader) -> Result<Vec<u8>> {
+        let module_type = match header.type_ {
+            PageType::DICTIONARY_PAGE => ModuleType::DictionaryPageHeader,
+            _ => ModuleType::DataPageHeader,
+        };
+        let encrypted = self
+            .cipher
+            .encrypt(&serialize(header)?, &self.aad(module_type)?)?;
+        if module_type == ModuleType::DataPageHeader {
+            self.page_ordinal += 1;
+        }
+        Ok(encrypted)
+    }
+
+    /// Encrypts `page`, returning its encrypted header and data
+    pub(crate) fn encrypt_compressed_page(
+        &mut self,
+        page: &CompressedPage,
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        let mut header = page.to_thrift_header();
+        let data = self.encrypt_page(header.type_, page.data())?;
+        header.compressed_page_size = data.len() as i32;
+        let header = self.encrypt_page_header(&header)?;
+        Ok((header, data))
+    }
+}
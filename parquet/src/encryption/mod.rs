@@ -0,0 +1,509 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Encryption implementation specific to Parquet, as described
+//! in the [spec][parquet-encryption-spec].
+//!
+//! Parquet modular encryption protects each module of a file (the footer, column metadata,
+//! page headers, pages and page indexes) separately with AES-GCM, or with AES-CTR for
+//! page data, binding every module to its location in the file through the additional
+//! authenticated data (AAD). Columns may be encrypted with the footer key, with their own
+//! column key, or left unencrypted, and the footer itself may be left in plaintext so that
+//! legacy readers can still read the unencrypted columns.
+//!
+//! Use [`FileEncryptionProperties`](encrypt::FileEncryptionProperties) with
+//! [`WriterPropertiesBuilder::with_file_encryption_properties`] to write encrypted files,
+//! and [`FileDecryptionProperties`](decrypt::FileDecryptionProperties) with
+//! [`ArrowReaderOptions::with_file_decryption_properties`] or
+//! [`ParquetMetaDataReader::with_decryption_properties`] to read them.
+//!
+//! [parquet-encryption-spec]: https://github.com/apache/parquet-format/blob/master/Encryption.md
+//! [`WriterPropertiesBuilder::with_file_encryption_properties`]: crate::file::properties::WriterPropertiesBuilder::with_file_encryption_properties
+//! [`ArrowReaderOptions::with_file_decryption_properties`]: crate::arrow::arrow_reader::ArrowReaderOptions::with_file_decryption_properties
+//! [`ParquetMetaDataReader::with_decryption_properties`]: crate::file::metadata::ParquetMetaDataReader::with_decryption_properties
+
+pub(crate) mod ciphers;
+pub mod decrypt;
+pub mod encrypt;
+pub(crate) mod modules;
+
+/// The algorithm used to encrypt the modules of a Parquet file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParquetCipher {
+    /// AES-GCM for all modules
+    #[default]
+    AesGcmV1,
+    /// AES-GCM for metadata and page headers, and the faster but unauthenticated
+    /// AES-CTR for page data
+    AesGcmCtrV1,
+}
+
+#[cfg(all(test, feature = "arrow"))]
+mod tests {
+    use super::decrypt::{FileDecryptionProperties, KeyRetriever};
+    use super::encrypt::FileEncryptionProperties;
+    use super::ParquetCipher;
+    use crate::arrow::arrow_reader::{ArrowReaderOptions, ParquetRecordBatchReaderBuilder};
+    use crate::arrow::{ArrowWriter, ProjectionMask};
+    use crate::errors::{ParquetError, Result};
+    use crate::file::metadata::ParquetMetaDataReader;
+    use crate::file::properties::{ReaderProperties, WriterProperties};
+    use crate::file::reader::{FileReader, SerializedFileReader};
+    use crate::file::serialized_reader::ReadOptionsBuilder;
+    use crate::util::test_common::file_util::get_test_path;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Float32Type, Float64Type};
+    use arrow_array::{ArrayRef, Int32Array, RecordBatch, StringArray};
+    use bytes::Bytes;
+    use std::sync::Arc;
+
+    const FOOTER_KEY: &[u8] = b"0123456789012345";
+    const COLUMN_KEY: &[u8] = b"1234567890123450";
+
+    fn test_batch() -> RecordBatch {
+        let a: ArrayRef = Arc::new(Int32Array::from_iter_values(0..1000));
+        let b: ArrayRef = Arc::new(StringArray::from_iter(
+            (0..1000).map(|i| (i % 3 != 0).then(|| format!("value {}", i % 10))),
+        ));
+        RecordBatch::try_from_iter([("a", a), ("b", b)]).unwrap()
+    }
+
+    fn write(properties: FileEncryptionProperties) -> Bytes {
+        let batch = test_batch();
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(300)
+            .set_data_page_row_count_limit(100)
+            .set_write_batch_size(50)
+            .with_file_encryption_properties(properties)
+            .build();
+        let mut buf = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        Bytes::from(buf)
+    }
+
+    fn read(data: Bytes, properties: Option<FileDecryptionProperties>) -> Result<Vec<RecordBatch>> {
+        let mut options = ArrowReaderOptions::new().with_page_index(true);
+        if let Some(properties) = properties {
+            options = options.with_file_decryption_properties(properties);
+        }
+        let reader = ParquetRecordBatchReaderBuilder::try_new_with_options(data, options)?
+            .with_batch_size(1000)
+            .build()?;
+        Ok(reader.collect::<std::result::Result<_, _>>()?)
+    }
+
+    fn assert_round_trip(data: Bytes, properties: FileDecryptionProperties) {
+        let batches = read(data, Some(properties)).unwrap();
+        let batch = arrow_select::concat::concat_batches(&batches[0].schema(), &batches).unwrap();
+        assert_eq!(batch, test_batch());
+    }
+
+    #[test]
+    fn test_encrypted_footer() {
+        let properties = FileEncryptionProperties::builder(FOOTER_KEY.to_vec())
+            .build()
+            .unwrap();
+        let data = write(properties);
+        assert_eq!(&data[..4], b"PARE");
+        assert_eq!(&data[data.len() - 4..], b"PARE");
+
+        let decryption = FileDecryptionProperties::builder(FOOTER_KEY.to_vec())
+            .build()
+            .unwrap();
+        assert_round_trip(data.clone(), decryption);
+
+        let err = read(data.clone(), None).unwrap_err().to_string();
+        assert!(err.contains("encrypted footer"), "{err}");
+
+        let wrong_key = FileDecryptionProperties::builder(COLUMN_KEY.to_vec())
+            .build()
+            .unwrap();
+        read(data, Some(wrong_key)).unwrap_err();
+    }
+
+    #[test]
+    fn test_plaintext_footer() {
+        let properties = FileEncryptionProperties::builder(FOOTER_KEY.to_vec())
+            .with_plaintext_footer(true)
+            .with_column_key("b", COLUMN_KEY.to_vec())
+            .build()
+            .unwrap();
+        let data = write(properties);
+        assert_eq!(&data[data.len() - 4..], b"PAR1");
+
+        let decryption = FileDecryptionProperties::builder(FOOTER_KEY.to_vec())
+            .with_column_key("b", COLUMN_KEY.to_vec())
+            .build()
+            .unwrap();
+        assert_round_trip(data.clone(), decryption);
+
+        // The unencrypted column can be read without any keys
+        let reader = SerializedFileReader::new(data.clone()).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.num_row_groups(), 4);
+        let row_group = metadata.row_group(0);
+        assert!(row_group.column(0).crypto_metadata().is_none());
+        assert!(row_group.column(1).crypto_metadata().is_some());
+        assert!(row_group.column(1).statistics().is_none());
+        let row_group = reader.get_row_group(0).unwrap();
+        let mut pages = row_group.get_column_page_reader(0).unwrap();
+        assert!(pages.get_next_page().unwrap().is_some());
+        assert!(row_group.get_column_page_reader(1).is_err());
+
+        // The footer signature is checked against the footer key
+        let wrong_key = FileDecryptionProperties::builder(COLUMN_KEY.to_vec())
+            .with_column_key("b", COLUMN_KEY.to_vec())
+            .build()
+            .unwrap();
+        let err = read(data.clone(), Some(wrong_key)).unwrap_err().to_string();
+        assert!(err.contains("signature"), "{err}");
+
+        let unverified = FileDecryptionProperties::builder(COLUMN_KEY.to_vec())
+            .with_column_key("b", COLUMN_KEY.to_vec())
+            .disable_footer_signature_verification()
+            .build()
+            .unwrap();
+        assert_round_trip(data.clone(), unverified);
+
+        // A missing or incorrect column key is an error, rather than falling back to the
+        // plaintext column metadata
+        let footer_only = FileDecryptionProperties::builder(FOOTER_KEY.to_vec())
+            .build()
+            .unwrap();
+        let err = ParquetMetaDataReader::new()
+            .with_decryption_properties(Some(&footer_only))
+            .parse_and_finish(&data)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parquet error: No decryption key provided for column 'b'"
+        );
+
+        let wrong_column_key = FileDecryptionProperties::builder(FOOTER_KEY.to_vec())
+            .with_column_key("b", FOOTER_KEY.to_vec())
+            .build()
+            .unwrap();
+        ParquetMetaDataReader::new()
+            .with_decryption_properties(Some(&wrong_column_key))
+            .parse_and_finish(&data)
+            .unwrap_err();
+
+        let unknown_column = FileDecryptionProperties::builder(FOOTER_KEY.to_vec())
+            .with_column_key("b", COLUMN_KEY.to_vec())
+            .with_column_key("c", COLUMN_KEY.to_vec())
+            .build()
+            .unwrap();
+        let err = read(data, Some(unknown_column)).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "Parquet error: Decryption key provided for column 'c' which is not in the file schema"
+        );
+    }
+
+    fn write_bloom_filters(properties: FileEncryptionProperties) -> Bytes {
+        let batch = test_batch();
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(300)
+            .set_bloom_filter_enabled(true)
+            .with_file_encryption_properties(properties)
+            .build();
+        let mut buf = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        Bytes::from(buf)
+    }
+
+    fn bloom_filter_properties() -> (FileEncryptionProperties, FileDecryptionProperties) {
+        let encryption = FileEncryptionProperties::builder(FOOTER_KEY.to_vec())
+            .with_plaintext_footer(true)
+            .with_column_key("b", COLUMN_KEY.to_vec())
+            .build()
+            .unwrap();
+        let decryption = FileDecryptionProperties::builder(FOOTER_KEY.to_vec())
+            .with_column_key("b", COLUMN_KEY.to_vec())
+            .build()
+            .unwrap();
+        (encryption, decryption)
+    }
+
+    #[test]
+    fn test_bloom_filter() {
+        let (encryption, decryption) = bloom_filter_properties();
+        let data = write_bloom_filters(encryption);
+
+        let props = || {
+            ReaderProperties::builder()
+                .set_read_bloom_filter(true)
+                .build()
+        };
+
+        // The bloom filter of the encrypted column cannot be read without its key
+        let options = ReadOptionsBuilder::new()
+            .with_reader_properties(props())
+            .build();
+        let reader = SerializedFileReader::new_with_options(data.clone(), options).unwrap();
+        let row_group = reader.get_row_group(0).unwrap();
+        assert!(row_group.get_column_bloom_filter(0).unwrap().check(&0_i32));
+        assert!(row_group.get_column_bloom_filter(1).is_none());
+
+        let options = ReadOptionsBuilder::new()
+            .with_reader_properties(props())
+            .with_file_decryption_properties(decryption)
+            .build();
+        let reader = SerializedFileReader::new_with_options(data, options).unwrap();
+        let row_group = reader.get_row_group(1).unwrap();
+        let a = row_group.get_column_bloom_filter(0).unwrap();
+        assert!(a.check(&300_i32));
+        assert!(!a.check(&1000_i32));
+        let b = row_group.get_column_bloom_filter(1).unwrap();
+        assert!(b.check(&"value 1"));
+        assert!(!b.check(&"value 10"));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_bloom_filter_async() {
+        use crate::arrow::async_reader::ParquetRecordBatchStreamBuilder;
+
+        let (encryption, decryption) = bloom_filter_properties();
+        let data = write_bloom_filters(encryption);
+
+        let options = ArrowReaderOptions::new().with_file_decryption_properties(decryption);
+        let mut builder =
+            ParquetRecordBatchStreamBuilder::new_with_options(std::io::Cursor::new(data), options)
+                .await
+                .unwrap();
+        let b = builder
+            .get_row_group_column_bloom_filter(2, 1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(b.check(&"value 1"));
+        assert!(!b.check(&"value 10"));
+    }
+
+    #[test]
+    fn test_column_keys_with_serialized_reader() {
+        let properties = FileEncryptionProperties::builder(FOOTER_KEY.to_vec())
+            .with_column_key("a", COLUMN_KEY.to_vec())
+            .build()
+            .unwrap();
+        let data = write(properties);
+
+        let decryption = FileDecryptionProperties::builder(FOOTER_KEY.to_vec())
+            .with_column_key("a", COLUMN_KEY.to_vec())
+            .build()
+            .unwrap();
+        let options = ReadOptionsBuilder::new()
+            .with_file_decryption_properties(decryption)
+            .with_predicate(Box::new(|_, idx| idx % 2 == 1))
+            .build();
+        let reader = SerializedFileReader::new_with_options(data, options).unwrap();
+        assert_eq!(reader.metadata().num_row_groups(), 2);
+        let rows = reader.get_row_iter(None).unwrap().count();
+        assert_eq!(rows, 400);
+
+        // Column `b` is not encrypted, but its metadata is protected by the footer
+        let row_group = reader.metadata().row_group(0);
+        assert!(row_group.column(0).crypto_metadata().is_some());
+        assert!(row_group.column(1).crypto_metadata().is_none());
+        assert!(row_group.column(0).statistics().is_some());
+    }
+
+    #[test]
+    fn test_key_retriever() {
+        struct TestRetriever;
+
+        impl KeyRetriever for TestRetriever {
+            fn retrieve_key(&self, key_metadata: &[u8]) -> Result<Vec<u8>> {
+                match key_metadata {
+                    b"footer" => Ok(FOOTER_KEY.to_vec()),
+                    b"column" => Ok(COLUMN_KEY.to_vec()),
+                    _ => Err(general_err!("unknown key")),
+                }
+            }
+        }
+
+        let properties = FileEncryptionProperties::builder(FOOTER_KEY.to_vec())
+            .with_footer_key_metadata(b"footer".to_vec())
+            .with_column_key_and_metadata("b", COLUMN_KEY.to_vec(), b"column".to_vec())
+            .build()
+            .unwrap();
+        let data = write(properties);
+
+        let decryption = FileDecryptionProperties::with_key_retriever(Arc::new(TestRetriever))
+            .build()
+            .unwrap();
+        assert_round_trip(data, decryption);
+    }
+
+    #[test]
+    fn test_ctr_cipher_and_aad_prefix() {
+        let properties = FileEncryptionProperties::builder(FOOTER_KEY.to_vec())
+            .with_cipher(ParquetCipher::AesGcmCtrV1)
+            .with_aad_prefix(b"file-a".to_vec())
+            .with_aad_prefix_storage(false)
+            .build()
+            .unwrap();
+        let data = write(properties);
+
+        let decryption = FileDecryptionProperties::builder(FOOTER_KEY.to_vec())
+            .with_aad_prefix(b"file-a".to_vec())
+            .build()
+            .unwrap();
+        assert_round_trip(data.clone(), decryption);
+
+        // The AAD prefix is not stored, so must be supplied
+        let decryption = FileDecryptionProperties::builder(FOOTER_KEY.to_vec())
+            .build()
+            .unwrap();
+        read(data.clone(), Some(decryption)).unwrap_err();
+
+        let decryption = FileDecryptionProperties::builder(FOOTER_KEY.to_vec())
+            .with_aad_prefix(b"file-b".to_vec())
+            .build()
+            .unwrap();
+        read(data, Some(decryption)).unwrap_err();
+    }
+
+    /// The keys used by the reference files in parquet-testing, which were written
+    /// by parquet-cpp, see `data/README.md`
+    const TESTING_COLUMN_KEY_1: &[u8] = b"1234567890123450";
+    const TESTING_COLUMN_KEY_2: &[u8] = b"1234567890123451";
+
+    fn read_testing_file(name: &str) -> Bytes {
+        Bytes::from(std::fs::read(get_test_path(name)).unwrap())
+    }
+
+    fn testing_column_keys() -> FileDecryptionProperties {
+        FileDecryptionProperties::builder(FOOTER_KEY.to_vec())
+            .with_column_key("double_field", TESTING_COLUMN_KEY_1.to_vec())
+            .with_column_key("float_field", TESTING_COLUMN_KEY_2.to_vec())
+            .build()
+            .unwrap()
+    }
+
+    /// Checks the contents of the parquet-testing encryption reference files
+    fn verify_testing_file(batches: Vec<RecordBatch>) {
+        let batch = arrow_select::concat::concat_batches(&batches[0].schema(), &batches).unwrap();
+        assert_eq!(batch.num_rows(), 50);
+        assert_eq!(batch.num_columns(), 8);
+
+        let bools = batch.column_by_name("boolean_field").unwrap().as_boolean();
+        let floats = batch.column_by_name("float_field").unwrap();
+        let doubles = batch.column_by_name("double_field").unwrap();
+        let floats = floats.as_primitive::<Float32Type>();
+        let doubles = doubles.as_primitive::<Float64Type>();
+        for i in 0..50 {
+            assert_eq!(bools.value(i), i % 2 == 0);
+            assert_eq!(floats.value(i), i as f32 * 1.1);
+            assert_eq!(doubles.value(i), i as f64 * 1.1111111);
+        }
+    }
+
+    #[test]
+    fn test_read_uniform_encryption() {
+        let data = read_testing_file("uniform_encryption.parquet.encrypted");
+        let decryption = FileDecryptionProperties::builder(FOOTER_KEY.to_vec())
+            .build()
+            .unwrap();
+        verify_testing_file(read(data.clone(), Some(decryption)).unwrap());
+
+        read(data, None).unwrap_err();
+    }
+
+    #[test]
+    fn test_read_encrypted_columns_and_footer() {
+        let data = read_testing_file("encrypt_columns_and_footer.parquet.encrypted");
+        verify_testing_file(read(data.clone(), Some(testing_column_keys())).unwrap());
+
+        let footer_only = FileDecryptionProperties::builder(FOOTER_KEY.to_vec())
+            .build()
+            .unwrap();
+        read(data, Some(footer_only)).unwrap_err();
+    }
+
+    #[test]
+    fn test_read_encrypted_columns_plaintext_footer() {
+        let data = read_testing_file("encrypt_columns_plaintext_footer.parquet.encrypted");
+        assert_eq!(&data[data.len() - 4..], b"PAR1");
+        verify_testing_file(read(data, Some(testing_column_keys())).unwrap());
+    }
+
+    #[test]
+    fn test_read_plaintext_footer_without_keys() {
+        let data = read_testing_file("encrypt_columns_plaintext_footer.parquet.encrypted");
+        let builder = ParquetRecordBatchReaderBuilder::try_new(data).unwrap();
+        let schema = builder.parquet_schema();
+        let encrypted = ["float_field", "double_field"];
+        let unencrypted: Vec<_> = (0..schema.num_columns())
+            .filter(|i| !encrypted.contains(&schema.column(*i).name()))
+            .collect();
+        assert_eq!(unencrypted.len(), 6);
+
+        let mask = ProjectionMask::leaves(schema, unencrypted);
+        let reader = builder.with_projection(mask).build().unwrap();
+        let batches = reader.collect::<std::result::Result<Vec<_>, _>>().unwrap();
+        let batch = arrow_select::concat::concat_batches(&batches[0].schema(), &batches).unwrap();
+        assert_eq!(batch.num_rows(), 50);
+        assert_eq!(batch.num_columns(), 6);
+        let bools = batch.column_by_name("boolean_field").unwrap().as_boolean();
+        assert!((0..50).all(|i| bools.value(i) == (i % 2 == 0)));
+
+        // The encrypted columns cannot be read without their keys
+        let data = read_testing_file("encrypt_columns_plaintext_footer.parquet.encrypted");
+        let builder = ParquetRecordBatchReaderBuilder::try_new(data).unwrap();
+        let idx = (0..builder.parquet_schema().num_columns())
+            .find(|i| builder.parquet_schema().column(*i).name() == "double_field")
+            .unwrap();
+        let mask = ProjectionMask::leaves(builder.parquet_schema(), [idx]);
+        let result = builder
+            .with_projection(mask)
+            .build()
+            .and_then(|reader| Ok(reader.collect::<std::result::Result<Vec<_>, _>>()?));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_read_encrypted_columns_and_footer_aad() {
+        let data = read_testing_file("encrypt_columns_and_footer_aad.parquet.encrypted");
+        let decryption = FileDecryptionProperties::builder(FOOTER_KEY.to_vec())
+            .with_column_key("double_field", TESTING_COLUMN_KEY_1.to_vec())
+            .with_column_key("float_field", TESTING_COLUMN_KEY_2.to_vec())
+            .with_aad_prefix(b"tester".to_vec())
+            .build()
+            .unwrap();
+        verify_testing_file(read(data.clone(), Some(decryption)).unwrap());
+
+        let decryption = FileDecryptionProperties::builder(FOOTER_KEY.to_vec())
+            .with_column_key("double_field", TESTING_COLUMN_KEY_1.to_vec())
+            .with_column_key("float_field", TESTING_COLUMN_KEY_2.to_vec())
+            .with_aad_prefix(b"wrong".to_vec())
+            .build()
+            .unwrap();
+        read(data, Some(decryption)).unwrap_err();
+    }
+
+    #[test]
+    fn test_read_encrypted_columns_and_footer_ctr() {
+        let data = read_testing_file("encrypt_columns_and_footer_ctr.parquet.encrypted");
+        verify_testing_file(read(data, Some(testing_column_keys())).unwrap());
+    }
+}
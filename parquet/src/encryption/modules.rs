@@ -0,0 +1,104 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::errors::{ParquetError, Result};
+
+/// The type of a module, which forms part of its additional authenticated data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ModuleType {
+    Footer = 0,
+    ColumnMetaData = 1,
+    DataPage = 2,
+    DictionaryPage = 3,
+    DataPageHeader = 4,
+    DictionaryPageHeader = 5,
+    ColumnIndex = 6,
+    OffsetIndex = 7,
+    BloomFilterHeader = 8,
+    BloomFilterBitset = 9,
+}
+
+/// Returns the additional authenticated data of the footer
+pub(crate) fn create_footer_aad(file_aad: &[u8]) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(file_aad.len() + 1);
+    aad.extend_from_slice(file_aad);
+    aad.push(ModuleType::Footer as u8);
+    aad
+}
+
+/// Returns the additional authenticated data of a module belonging to a column chunk
+///
+/// `page_ordinal` is only included for data pages and data page headers
+pub(crate) fn create_module_aad(
+    file_aad: &[u8],
+    module_type: ModuleType,
+    row_group_idx: usize,
+    column_ordinal: usize,
+    page_ordinal: Option<usize>,
+) -> Result<Vec<u8>> {
+    let mut aad = Vec::with_capacity(file_aad.len() + 7);
+    aad.extend_from_slice(file_aad);
+    aad.push(module_type as u8);
+    aad.extend_from_slice(&ordinal_bytes("row groups", row_group_idx)?);
+    aad.extend_from_slice(&ordinal_bytes("columns", column_ordinal)?);
+
+    if matches!(
+        module_type,
+        ModuleType::DataPage | ModuleType::DataPageHeader
+    ) {
+        let page_ordinal = page_ordinal
+            .ok_or_else(|| general_err!("Page ordinal is required for {:?}", module_type))?;
+        aad.extend_from_slice(&ordinal_bytes("pages per column chunk", page_ordinal)?);
+    }
+    Ok(aad)
+}
+
+fn ordinal_bytes(name: &str, ordinal: usize) -> Result<[u8; 2]> {
+    match i16::try_from(ordinal) {
+        Ok(ordinal) => Ok(ordinal.to_le_bytes()),
+        Err(_) => Err(general_err!(
+            "Encrypted parquet files can't have more than {} {}: {}",
+            i16::MAX,
+            name,
+            ordinal
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_aad() {
+        let file_aad = b"prefix";
+        assert_eq!(create_footer_aad(file_aad), b"prefix\x00");
+
+        let aad = create_module_aad(file_aad, ModuleType::DataPage, 1, 2, Some(3)).unwrap();
+        assert_eq!(aad, b"prefix\x02\x01\x00\x02\x00\x03\x00");
+
+        let aad = create_module_aad(file_aad, ModuleType::DictionaryPage, 1, 2, Some(3)).unwrap();
+        assert_eq!(aad, b"prefix\x03\x01\x00\x02\x00");
+
+        let err = create_module_aad(file_aad, ModuleType::ColumnIndex, 40_000, 0, None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("more than 32767 row groups"), "{err}");
+
+        create_module_aad(file_aad, ModuleType::DataPageHeader, 0, 0, None).unwrap_err();
+    }
+}
@@ -25,6 +25,8 @@ use crate::file::page_encoding_stats::PageEncodingStats;
 use crate::file::page_index::index::{Index, NativeIndex, PageIndex};
 use crate::file::page_index::offset_index::OffsetIndexMetaData;
 use crate::file::statistics::{Statistics, ValueStatistics};
+#[cfg(feature = "encryption")]
+use crate::format::ColumnCryptoMetaData;
 use crate::format::{BoundaryOrder, PageLocation, SortingColumn};
 use std::sync::Arc;
 
@@ -101,6 +103,31 @@ impl HeapSize for ColumnChunkMetaData {
             + self.unencoded_byte_array_data_bytes.heap_size()
             + self.repetition_level_histogram.heap_size()
             + self.definition_level_histogram.heap_size()
+            + self.crypto_metadata_heap_size()
+    }
+}
+
+impl ColumnChunkMetaData {
+    #[cfg(feature = "encryption")]
+    fn crypto_metadata_heap_size(&self) -> usize {
+        self.column_crypto_metadata.heap_size()
+    }
+
+    #[cfg(not(feature = "encryption"))]
+    fn crypto_metadata_heap_size(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(feature = "encryption")]
+impl HeapSize for ColumnCryptoMetaData {
+    fn heap_size(&self) -> usize {
+        match self {
+            Self::ENCRYPTIONWITHFOOTERKEY(_) => 0,
+            Self::ENCRYPTIONWITHCOLUMNKEY(k) => {
+                k.path_in_schema.heap_size() + k.key_metadata.as_ref().map_or(0, Vec::capacity)
+            }
+        }
     }
 }
 
@@ -98,12 +98,16 @@ mod writer;
 use std::ops::Range;
 use std::sync::Arc;
 
+#[cfg(feature = "encryption")]
+use crate::format::ColumnCryptoMetaData;
 use crate::format::{
     BoundaryOrder, ColumnChunk, ColumnIndex, ColumnMetaData, OffsetIndex, PageLocation, RowGroup,
     SizeStatistics, SortingColumn,
 };
 
 use crate::basic::{ColumnOrder, Compression, Encoding, Type};
+#[cfg(feature = "encryption")]
+use crate::encryption::decrypt::FileDecryptor;
 use crate::errors::{ParquetError, Result};
 pub(crate) use crate::file::metadata::memory::HeapSize;
 use crate::file::page_encoding_stats::{self, PageEncodingStats};
@@ -174,6 +178,9 @@ pub struct ParquetMetaData {
     column_index: Option<ParquetColumnIndex>,
     /// Offset index for each page in each column chunk
     offset_index: Option<ParquetOffsetIndex>,
+    /// Decryptor for the modules of an encrypted file
+    #[cfg(feature = "encryption")]
+    file_decryptor: Option<Arc<FileDecryptor>>,
 }
 
 impl ParquetMetaData {
@@ -185,6 +192,8 @@ impl ParquetMetaData {
             row_groups,
             column_index: None,
             offset_index: None,
+            #[cfg(feature = "encryption")]
+            file_decryptor: None,
         }
     }
 
@@ -282,6 +291,20 @@ impl ParquetMetaData {
             + self.row_groups.heap_size()
             + self.column_index.heap_size()
             + self.offset_index.heap_size()
+            + self.file_decryptor_size()
+    }
+
+    /// Returns the size of the file decryptor, which is allocated separately
+    #[cfg(feature = "encryption")]
+    fn file_decryptor_size(&self) -> usize {
+        self.file_decryptor
+            .as_ref()
+            .map_or(0, |d| std::mem::size_of::<FileDecryptor>() + d.heap_size())
+    }
+
+    #[cfg(not(feature = "encryption"))]
+    fn file_decryptor_size(&self) -> usize {
+        0
     }
 
     /// Override the column index
@@ -293,6 +316,18 @@ impl ParquetMetaData {
     pub(crate) fn set_offset_index(&mut self, index: Option<ParquetOffsetIndex>) {
         self.offset_index = index;
     }
+
+    /// Returns the decryptor for the modules of this file, if it is encrypted
+    #[cfg(feature = "encryption")]
+    pub(crate) fn file_decryptor(&self) -> Option<&Arc<FileDecryptor>> {
+        self.file_decryptor.as_ref()
+    }
+
+    /// Set the decryptor for the modules of this file
+    #[cfg(feature = "encryption")]
+    pub(crate) fn set_file_decryptor(&mut self, file_decryptor: Option<FileDecryptor>) {
+        self.file_decryptor = file_decryptor.map(Arc::new);
+    }
 }
 
 /// A builder for creating / manipulating [`ParquetMetaData`]
@@ -765,6 +800,8 @@ pub struct ColumnChunkMetaData {
     unencoded_byte_array_data_bytes: Option<i64>,
     repetition_level_histogram: Option<LevelHistogram>,
     definition_level_histogram: Option<LevelHistogram>,
+    #[cfg(feature = "encryption")]
+    column_crypto_metadata: Option<ColumnCryptoMetaData>,
 }
 
 /// Histograms for repetition and definition levels.
@@ -1054,6 +1091,12 @@ impl ColumnChunkMetaData {
         self.definition_level_histogram.as_ref()
     }
 
+    /// Returns how this column chunk is encrypted, if it is encrypted
+    #[cfg(feature = "encryption")]
+    pub fn crypto_metadata(&self) -> Option<&ColumnCryptoMetaData> {
+        self.column_crypto_metadata.as_ref()
+    }
+
     /// Method to convert from Thrift.
     pub fn from_thrift(column_descr: ColumnDescPtr, cc: ColumnChunk) -> Result<Self> {
         if cc.meta_data.is_none() {
@@ -1131,6 +1174,8 @@ impl ColumnChunkMetaData {
             unencoded_byte_array_data_bytes,
             repetition_level_histogram,
             definition_level_histogram,
+            #[cfg(feature = "encryption")]
+            column_crypto_metadata: cc.crypto_metadata,
         };
         Ok(result)
     }
@@ -1147,6 +1192,9 @@ impl ColumnChunkMetaData {
             offset_index_length: self.offset_index_length,
             column_index_offset: self.column_index_offset,
             column_index_length: self.column_index_length,
+            #[cfg(feature = "encryption")]
+            crypto_metadata: self.column_crypto_metadata.clone(),
+            #[cfg(not(feature = "encryption"))]
             crypto_metadata: None,
             encrypted_column_metadata: None,
         }
@@ -1254,6 +1302,8 @@ impl ColumnChunkMetaDataBuilder {
             unencoded_byte_array_data_bytes: None,
             repetition_level_histogram: None,
             definition_level_histogram: None,
+            #[cfg(feature = "encryption")]
+            column_crypto_metadata: None,
         })
     }
 
@@ -1403,6 +1453,13 @@ impl ColumnChunkMetaDataBuilder {
         self
     }
 
+    /// Sets how this column chunk is encrypted
+    #[cfg(feature = "encryption")]
+    pub fn set_column_crypto_metadata(mut self, value: Option<ColumnCryptoMetaData>) -> Self {
+        self.0.column_crypto_metadata = value;
+        self
+    }
+
     /// Builds column chunk metadata.
     pub fn build(self) -> Result<ColumnChunkMetaData> {
         Ok(self.0)
@@ -1861,7 +1918,10 @@ mod tests {
         let parquet_meta = ParquetMetaDataBuilder::new(file_metadata.clone())
             .set_row_groups(row_group_meta_with_stats)
             .build();
+        #[cfg(not(feature = "encryption"))]
         let base_expected_size = 2312;
+        #[cfg(feature = "encryption")]
+        let base_expected_size = 2512;
 
         assert_eq!(parquet_meta.memory_size(), base_expected_size);
 
@@ -1888,7 +1948,10 @@ mod tests {
             ]]))
             .build();
 
+        #[cfg(not(feature = "encryption"))]
         let bigger_expected_size = 2816;
+        #[cfg(feature = "encryption")]
+        let bigger_expected_size = 3016;
         // more set fields means more memory usage
         assert!(bigger_expected_size > base_expected_size);
         assert_eq!(parquet_meta.memory_size(), bigger_expected_size);
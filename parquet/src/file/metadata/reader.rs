@@ -15,17 +15,24 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::{io::Read, ops::Range, sync::Arc};
+use std::{borrow::Cow, io::Read, ops::Range, sync::Arc};
 
 use bytes::Bytes;
 
 use crate::basic::ColumnOrder;
+#[cfg(feature = "encryption")]
+use crate::encryption::{
+    decrypt::{row_group_ordinal, CryptoContext, FileDecryptionProperties, FileDecryptor},
+    modules::ModuleType,
+};
 use crate::errors::{ParquetError, Result};
 use crate::file::metadata::{FileMetaData, ParquetMetaData, RowGroupMetaData};
 use crate::file::page_index::index::Index;
 use crate::file::page_index::index_reader::{acc_range, decode_column_index, decode_offset_index};
 use crate::file::reader::ChunkReader;
-use crate::file::{FOOTER_SIZE, PARQUET_MAGIC};
+use crate::file::{FOOTER_SIZE, PARQUET_MAGIC, PARQUET_MAGIC_ENCR_FOOTER};
+#[cfg(feature = "encryption")]
+use crate::format::FileCryptoMetaData as TFileCryptoMetaData;
 use crate::format::{ColumnOrder as TColumnOrder, FileMetaData as TFileMetaData};
 use crate::schema::types;
 use crate::schema::types::SchemaDescriptor;
@@ -68,6 +75,28 @@ pub struct ParquetMetaDataReader {
     // Size of the serialized thrift metadata plus the 8 byte footer. Only set if
     // `self.parse_metadata` is called.
     metadata_size: Option<usize>,
+    #[cfg(feature = "encryption")]
+    file_decryption_properties: Option<FileDecryptionProperties>,
+}
+
+/// The decoded 8 byte footer at the end of a parquet file, see
+/// [`ParquetMetaDataReader::decode_footer_tail`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FooterTail {
+    metadata_length: usize,
+    encrypted_footer: bool,
+}
+
+impl FooterTail {
+    /// The length of the footer metadata in bytes
+    pub fn metadata_length(&self) -> usize {
+        self.metadata_length
+    }
+
+    /// Whether the footer metadata is encrypted
+    pub fn is_encrypted_footer(&self) -> bool {
+        self.encrypted_footer
+    }
 }
 
 impl ParquetMetaDataReader {
@@ -126,6 +155,20 @@ impl ParquetMetaDataReader {
         self
     }
 
+    /// Provide the [`FileDecryptionProperties`] used to decrypt the metadata and page
+    /// indexes of a file written with Parquet modular encryption.
+    ///
+    /// These are required for files with an encrypted footer, and used to verify the
+    /// footer signature of files with a plaintext footer.
+    #[cfg(feature = "encryption")]
+    pub fn with_decryption_properties(
+        mut self,
+        properties: Option<&FileDecryptionProperties>,
+    ) -> Self {
+        self.file_decryption_properties = properties.cloned();
+        self
+    }
+
     /// Indicates whether this reader has a [`ParquetMetaData`] internally.
     pub fn has_metadata(&self) -> bool {
         self.metadata.is_some()
@@ -342,8 +385,9 @@ impl ParquetMetaDataReader {
         mut fetch: F,
         file_size: usize,
     ) -> Result<()> {
-        let (metadata, remainder) =
-            Self::load_metadata(&mut fetch, file_size, self.get_prefetch_size()).await?;
+        let (metadata, remainder) = self
+            .load_metadata(&mut fetch, file_size, self.get_prefetch_size())
+            .await?;
 
         self.metadata = Some(metadata);
 
@@ -404,14 +448,22 @@ impl ParquetMetaDataReader {
             let index = metadata
                 .row_groups()
                 .iter()
-                .map(|x| {
+                .enumerate()
+                .map(|(rg_idx, x)| {
                     x.columns()
                         .iter()
-                        .map(|c| match c.column_index_range() {
-                            Some(r) => decode_column_index(
-                                &bytes[r.start - start_offset..r.end - start_offset],
-                                c.column_type(),
-                            ),
+                        .enumerate()
+                        .map(|(col_idx, c)| match c.column_index_range() {
+                            Some(r) => {
+                                let bytes = page_index_bytes(
+                                    metadata,
+                                    rg_idx,
+                                    col_idx,
+                                    true,
+                                    &bytes[r.start - start_offset..r.end - start_offset],
+                                )?;
+                                decode_column_index(&bytes, c.column_type())
+                            }
                             None => Ok(Index::NONE),
                         })
                         .collect::<Result<Vec<_>>>()
@@ -428,13 +480,19 @@ impl ParquetMetaDataReader {
             let index = metadata
                 .row_groups()
                 .iter()
-                .map(|x| {
+                .enumerate()
+                .map(|(rg_idx, x)| {
                     x.columns()
                         .iter()
-                        .map(|c| match c.offset_index_range() {
-                            Some(r) => decode_offset_index(
+                        .enumerate()
+                        .map(|(col_idx, c)| match c.offset_index_range() {
+                            Some(r) => decode_offset_index(&page_index_bytes(
+                                metadata,
+                                rg_idx,
+                                col_idx,
+                                false,
                                 &bytes[r.start - start_offset..r.end - start_offset],
-                            ),
+                            )?),
                             None => Err(general_err!("missing offset index")),
                         })
                         .collect::<Result<Vec<_>>>()
@@ -495,7 +553,8 @@ impl ParquetMetaDataReader {
             .get_read(file_size - 8)?
             .read_exact(&mut footer)?;
 
-        let metadata_len = Self::decode_footer(&footer)?;
+        let footer_tail = Self::decode_footer_tail(&footer)?;
+        let metadata_len = footer_tail.metadata_length();
         let footer_metadata_len = FOOTER_SIZE + metadata_len;
         self.metadata_size = Some(footer_metadata_len);
 
@@ -507,7 +566,10 @@ impl ParquetMetaDataReader {
         }
 
         let start = file_size - footer_metadata_len as u64;
-        Self::decode_metadata(chunk_reader.get_bytes(start, metadata_len)?.as_ref())
+        self.decode_footer_metadata(
+            chunk_reader.get_bytes(start, metadata_len)?.as_ref(),
+            &footer_tail,
+        )
     }
 
     /// Return the number of bytes to read in the initial pass. If `prefetch_size` has
@@ -525,6 +587,7 @@ impl ParquetMetaDataReader {
 
     #[cfg(all(feature = "async", feature = "arrow"))]
     async fn load_metadata<F: MetadataFetch>(
+        &self,
         fetch: &mut F,
         file_size: usize,
         prefetch: usize,
@@ -552,7 +615,8 @@ impl ParquetMetaDataReader {
         let mut footer = [0; FOOTER_SIZE];
         footer.copy_from_slice(&suffix[suffix_len - FOOTER_SIZE..suffix_len]);
 
-        let length = Self::decode_footer(&footer)?;
+        let footer_tail = Self::decode_footer_tail(&footer)?;
+        let length = footer_tail.metadata_length();
 
         if file_size < length + FOOTER_SIZE {
             return Err(eof_err!(
@@ -566,12 +630,12 @@ impl ParquetMetaDataReader {
         if length > suffix_len - FOOTER_SIZE {
             let metadata_start = file_size - length - FOOTER_SIZE;
             let meta = fetch.fetch(metadata_start..file_size - FOOTER_SIZE).await?;
-            Ok((Self::decode_metadata(&meta)?, None))
+            Ok((self.decode_footer_metadata(&meta, &footer_tail)?, None))
         } else {
             let metadata_start = file_size - length - FOOTER_SIZE - footer_start;
             let slice = &suffix[metadata_start..suffix_len - FOOTER_SIZE];
             Ok((
-                Self::decode_metadata(slice)?,
+                self.decode_footer_metadata(slice, &footer_tail)?,
                 Some((footer_start, suffix.slice(..metadata_start))),
             ))
         }
@@ -588,16 +652,137 @@ impl ParquetMetaDataReader {
     /// | len | 'PAR1' |
     /// +-----+--------+
     /// ```
+    ///
+    /// Returns an error for files with an encrypted footer, see [`Self::decode_footer_tail`]
     pub fn decode_footer(slice: &[u8; FOOTER_SIZE]) -> Result<usize> {
+        let footer_tail = Self::decode_footer_tail(slice)?;
+        if footer_tail.is_encrypted_footer() {
+            return Err(general_err!(
+                "Parquet file has an encrypted footer, use decode_footer_tail"
+            ));
+        }
+        Ok(footer_tail.metadata_length())
+    }
+
+    /// Decodes the Parquet footer returning a [`FooterTail`]
+    ///
+    /// Files with an encrypted footer end in the magic bytes 'PARE' rather than 'PAR1'
+    pub fn decode_footer_tail(slice: &[u8; FOOTER_SIZE]) -> Result<FooterTail> {
         // check this is indeed a parquet file
-        if slice[4..] != PARQUET_MAGIC {
+        let encrypted_footer = if slice[4..] == PARQUET_MAGIC {
+            false
+        } else if slice[4..] == PARQUET_MAGIC_ENCR_FOOTER {
+            true
+        } else {
             return Err(general_err!("Invalid Parquet file. Corrupt footer"));
-        }
+        };
 
         // get the metadata length from the footer
         let metadata_len = u32::from_le_bytes(slice[..4].try_into().unwrap());
-        // u32 won't be larger than usize in most cases
-        Ok(metadata_len as usize)
+        Ok(FooterTail {
+            // u32 won't be larger than usize in most cases
+            metadata_length: metadata_len as usize,
+            encrypted_footer,
+        })
+    }
+
+    /// Decodes the footer metadata of a file, decrypting it if required
+    #[cfg(feature = "encryption")]
+    fn decode_footer_metadata(
+        &self,
+        buf: &[u8],
+        footer_tail: &FooterTail,
+    ) -> Result<ParquetMetaData> {
+        Self::decrypt_metadata(
+            buf,
+            footer_tail.is_encrypted_footer(),
+            self.file_decryption_properties.as_ref(),
+        )
+    }
+
+    /// Decodes the footer metadata of a file
+    #[cfg(not(feature = "encryption"))]
+    fn decode_footer_metadata(
+        &self,
+        buf: &[u8],
+        footer_tail: &FooterTail,
+    ) -> Result<ParquetMetaData> {
+        if footer_tail.is_encrypted_footer() {
+            return Err(general_err!(
+                "Parquet file has an encrypted footer but the encryption feature is disabled"
+            ));
+        }
+        Self::decode_metadata(buf)
+    }
+
+    /// Decodes [`ParquetMetaData`] from the provided bytes, decrypting the footer
+    /// and column metadata of a file written with Parquet modular encryption.
+    ///
+    /// With a plaintext footer, the footer signature is verified if `properties`
+    /// are provided. Without them, only the metadata of unencrypted columns is complete.
+    #[cfg(feature = "encryption")]
+    fn decrypt_metadata(
+        buf: &[u8],
+        encrypted_footer: bool,
+        properties: Option<&FileDecryptionProperties>,
+    ) -> Result<ParquetMetaData> {
+        let decrypted_footer;
+        let mut file_decryptor = None;
+        let mut prot = TCompactSliceInputProtocol::new(buf);
+        if encrypted_footer {
+            let Some(properties) = properties else {
+                return Err(general_err!(
+                    "Parquet file has an encrypted footer but no decryption properties were provided"
+                ));
+            };
+            let t_crypto_metadata = TFileCryptoMetaData::read_from_in_protocol(&mut prot)
+                .map_err(|e| general_err!("Could not parse crypto metadata: {}", e))?;
+            let decryptor = FileDecryptor::new(
+                properties,
+                &t_crypto_metadata.encryption_algorithm,
+                t_crypto_metadata.key_metadata.as_deref(),
+            )?;
+            decrypted_footer = decryptor.decrypt_footer(prot.as_slice())?;
+            prot = TCompactSliceInputProtocol::new(&decrypted_footer);
+            file_decryptor = Some(decryptor);
+        }
+
+        let footer = prot.as_slice();
+        let mut t_file_metadata: TFileMetaData = TFileMetaData::read_from_in_protocol(&mut prot)
+            .map_err(|e| general_err!("Could not parse metadata: {}", e))?;
+
+        if let (false, Some(algorithm), Some(properties)) = (
+            encrypted_footer,
+            &t_file_metadata.encryption_algorithm,
+            properties,
+        ) {
+            let decryptor = FileDecryptor::new(
+                properties,
+                algorithm,
+                t_file_metadata.footer_signing_key_metadata.as_deref(),
+            )?;
+            // The signature follows the plaintext footer
+            let signature = prot.as_slice();
+            decryptor
+                .verify_footer_signature(&footer[..footer.len() - signature.len()], signature)?;
+            file_decryptor = Some(decryptor);
+        }
+
+        if let Some(decryptor) = &file_decryptor {
+            for (rg_idx, rg) in t_file_metadata.row_groups.iter_mut().enumerate() {
+                let rg_ordinal = row_group_ordinal(rg.ordinal, rg_idx);
+                for (col_idx, column) in rg.columns.iter_mut().enumerate() {
+                    decryptor.decrypt_column_metadata(column, rg_ordinal, col_idx)?;
+                }
+            }
+        }
+
+        let mut metadata = Self::decode_file_metadata(t_file_metadata)?;
+        if let Some(decryptor) = &file_decryptor {
+            decryptor.validate_column_keys(metadata.file_metadata().schema_descr())?;
+        }
+        metadata.set_file_decryptor(file_decryptor);
+        Ok(metadata)
     }
 
     /// Decodes [`ParquetMetaData`] from the provided bytes.
@@ -611,6 +796,10 @@ impl ParquetMetaDataReader {
         let mut prot = TCompactSliceInputProtocol::new(buf);
         let t_file_metadata: TFileMetaData = TFileMetaData::read_from_in_protocol(&mut prot)
             .map_err(|e| general_err!("Could not parse metadata: {}", e))?;
+        Self::decode_file_metadata(t_file_metadata)
+    }
+
+    fn decode_file_metadata(t_file_metadata: TFileMetaData) -> Result<ParquetMetaData> {
         let schema = types::from_thrift(&t_file_metadata.schema)?;
         let schema_descr = Arc::new(SchemaDescriptor::new(schema));
         let mut row_groups = Vec::new();
@@ -664,6 +853,39 @@ impl ParquetMetaDataReader {
     }
 }
 
+/// Returns the bytes of the page index of the column chunk at `col_idx` in row group
+/// `rg_idx`, decrypting them if the column chunk is encrypted. `column_index` selects
+/// between the column index and the offset index.
+#[cfg(feature = "encryption")]
+fn page_index_bytes<'a>(
+    metadata: &ParquetMetaData,
+    rg_idx: usize,
+    col_idx: usize,
+    column_index: bool,
+    bytes: &'a [u8],
+) -> Result<Cow<'a, [u8]>> {
+    let Some(context) = CryptoContext::for_column(metadata, rg_idx, col_idx)? else {
+        return Ok(Cow::Borrowed(bytes));
+    };
+    let module_type = match column_index {
+        true => ModuleType::ColumnIndex,
+        false => ModuleType::OffsetIndex,
+    };
+    Ok(Cow::Owned(context.decrypt_page_index(bytes, module_type)?))
+}
+
+/// Returns the bytes of the page index of a column chunk
+#[cfg(not(feature = "encryption"))]
+fn page_index_bytes<'a>(
+    _metadata: &ParquetMetaData,
+    _rg_idx: usize,
+    _col_idx: usize,
+    _column_index: bool,
+    bytes: &'a [u8],
+) -> Result<Cow<'a, [u8]>> {
+    Ok(Cow::Borrowed(bytes))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -15,11 +15,15 @@
 // specific language governing permissions and limitations
 // under the License.
 
+#[cfg(feature = "encryption")]
+use crate::encryption::{decrypt::row_group_ordinal, encrypt::FileEncryptor, modules::ModuleType};
 use crate::errors::Result;
 use crate::file::metadata::{KeyValue, ParquetMetaData};
 use crate::file::page_index::index::Index;
 use crate::file::writer::TrackedWrite;
 use crate::file::PARQUET_MAGIC;
+#[cfg(feature = "encryption")]
+use crate::file::PARQUET_MAGIC_ENCR_FOOTER;
 use crate::format::{ColumnIndex, OffsetIndex, RowGroup};
 use crate::schema::types;
 use crate::schema::types::{SchemaDescPtr, SchemaDescriptor, TypePtr};
@@ -41,6 +45,8 @@ pub(crate) struct ThriftMetadataWriter<'a, W: Write> {
     key_value_metadata: Option<Vec<KeyValue>>,
     created_by: Option<String>,
     writer_version: i32,
+    #[cfg(feature = "encryption")]
+    file_encryptor: Option<Arc<FileEncryptor>>,
 }
 
 impl<'a, W: Write> ThriftMetadataWriter<'a, W> {
@@ -54,11 +60,31 @@ impl<'a, W: Write> ThriftMetadataWriter<'a, W> {
         // iter each column
         // write offset index to the file
         for (row_group_idx, row_group) in self.row_groups.iter_mut().enumerate() {
+            #[cfg(feature = "encryption")]
+            let row_group_ordinal = row_group_ordinal(row_group.ordinal, row_group_idx);
             for (column_idx, column_metadata) in row_group.columns.iter_mut().enumerate() {
                 if let Some(offset_index) = &offset_indexes[row_group_idx][column_idx] {
                     let start_offset = self.buf.bytes_written();
-                    let mut protocol = TCompactOutputProtocol::new(&mut self.buf);
-                    offset_index.write_to_out_protocol(&mut protocol)?;
+                    #[cfg(feature = "encryption")]
+                    let encrypted = match self.file_encryptor.as_ref() {
+                        Some(file_encryptor) => file_encryptor.encrypt_page_index(
+                            offset_index,
+                            ModuleType::OffsetIndex,
+                            column_metadata,
+                            row_group_ordinal,
+                            column_idx,
+                        )?,
+                        None => None,
+                    };
+                    #[cfg(not(feature = "encryption"))]
+                    let encrypted: Option<Vec<u8>> = None;
+                    match encrypted {
+                        Some(encrypted) => self.buf.write_all(&encrypted)?,
+                        None => {
+                            let mut protocol = TCompactOutputProtocol::new(&mut self.buf);
+                            offset_index.write_to_out_protocol(&mut protocol)?;
+                        }
+                    }
                     let end_offset = self.buf.bytes_written();
                     // set offset and index for offset index
                     column_metadata.offset_index_offset = Some(start_offset as i64);
@@ -79,11 +105,31 @@ impl<'a, W: Write> ThriftMetadataWriter<'a, W> {
         // iter each column
         // write column index to the file
         for (row_group_idx, row_group) in self.row_groups.iter_mut().enumerate() {
+            #[cfg(feature = "encryption")]
+            let row_group_ordinal = row_group_ordinal(row_group.ordinal, row_group_idx);
             for (column_idx, column_metadata) in row_group.columns.iter_mut().enumerate() {
                 if let Some(column_index) = &column_indexes[row_group_idx][column_idx] {
                     let start_offset = self.buf.bytes_written();
-                    let mut protocol = TCompactOutputProtocol::new(&mut self.buf);
-                    column_index.write_to_out_protocol(&mut protocol)?;
+                    #[cfg(feature = "encryption")]
+                    let encrypted = match self.file_encryptor.as_ref() {
+                        Some(file_encryptor) => file_encryptor.encrypt_page_index(
+                            column_index,
+                            ModuleType::ColumnIndex,
+                            column_metadata,
+                            row_group_ordinal,
+                            column_idx,
+                        )?,
+                        None => None,
+                    };
+                    #[cfg(not(feature = "encryption"))]
+                    let encrypted: Option<Vec<u8>> = None;
+                    match encrypted {
+                        Some(encrypted) => self.buf.write_all(&encrypted)?,
+                        None => {
+                            let mut protocol = TCompactOutputProtocol::new(&mut self.buf);
+                            column_index.write_to_out_protocol(&mut protocol)?;
+                        }
+                    }
                     let end_offset = self.buf.bytes_written();
                     // set offset and index for offset index
                     column_metadata.column_index_offset = Some(start_offset as i64);
@@ -121,7 +167,7 @@ impl<'a, W: Write> ThriftMetadataWriter<'a, W> {
 
         let file_metadata = crate::format::FileMetaData {
             num_rows,
-            row_groups: self.row_groups,
+            row_groups: std::mem::take(&mut self.row_groups),
             key_value_metadata: self.key_value_metadata.clone(),
             version: self.writer_version,
             schema: types::to_thrift(self.schema.as_ref())?,
@@ -133,20 +179,76 @@ impl<'a, W: Write> ThriftMetadataWriter<'a, W> {
 
         // Write file metadata
         let start_pos = self.buf.bytes_written();
-        {
-            let mut protocol = TCompactOutputProtocol::new(&mut self.buf);
-            file_metadata.write_to_out_protocol(&mut protocol)?;
-        }
+        #[cfg(feature = "encryption")]
+        let magic = match self.file_encryptor.clone() {
+            Some(file_encryptor) => {
+                self.write_encrypted_metadata(&file_metadata, &file_encryptor)?
+            }
+            None => self.write_plaintext_metadata(&file_metadata)?,
+        };
+        #[cfg(not(feature = "encryption"))]
+        let magic = self.write_plaintext_metadata(&file_metadata)?;
         let end_pos = self.buf.bytes_written();
 
         // Write footer
         let metadata_len = (end_pos - start_pos) as u32;
 
         self.buf.write_all(&metadata_len.to_le_bytes())?;
-        self.buf.write_all(&PARQUET_MAGIC)?;
+        self.buf.write_all(&magic)?;
         Ok(file_metadata)
     }
 
+    /// Writes `file_metadata` unencrypted, returning the magic bytes that end the file
+    fn write_plaintext_metadata(
+        &mut self,
+        file_metadata: &crate::format::FileMetaData,
+    ) -> Result<[u8; 4]> {
+        let mut protocol = TCompactOutputProtocol::new(&mut self.buf);
+        file_metadata.write_to_out_protocol(&mut protocol)?;
+        Ok(PARQUET_MAGIC)
+    }
+
+    /// Writes `file_metadata` protected by `file_encryptor`, returning the magic
+    /// bytes that end the file
+    ///
+    /// The metadata of encrypted columns is encrypted, and the footer is then
+    /// either encrypted, preceded by the plaintext `FileCryptoMetaData`, or left
+    /// in plaintext and followed by its signature.
+    #[cfg(feature = "encryption")]
+    fn write_encrypted_metadata(
+        &mut self,
+        file_metadata: &crate::format::FileMetaData,
+        file_encryptor: &FileEncryptor,
+    ) -> Result<[u8; 4]> {
+        let mut file_metadata = file_metadata.clone();
+        for (row_group_idx, row_group) in file_metadata.row_groups.iter_mut().enumerate() {
+            let row_group_ordinal = row_group_ordinal(row_group.ordinal, row_group_idx);
+            for (column_idx, column_chunk) in row_group.columns.iter_mut().enumerate() {
+                file_encryptor.encrypt_column_chunk(column_chunk, row_group_ordinal, column_idx)?;
+            }
+        }
+
+        if file_encryptor.properties().encrypt_footer() {
+            let mut protocol = TCompactOutputProtocol::new(&mut self.buf);
+            file_encryptor
+                .file_crypto_metadata()
+                .write_to_out_protocol(&mut protocol)?;
+
+            let footer = serialize_file_metadata(&file_metadata)?;
+            self.buf
+                .write_all(&file_encryptor.encrypt_footer(&footer)?)?;
+            Ok(PARQUET_MAGIC_ENCR_FOOTER)
+        } else {
+            file_metadata.encryption_algorithm = Some(file_encryptor.algorithm());
+            file_metadata.footer_signing_key_metadata = file_encryptor.footer_key_metadata();
+
+            let footer = serialize_file_metadata(&file_metadata)?;
+            self.buf.write_all(&footer)?;
+            self.buf.write_all(&file_encryptor.sign_footer(&footer)?)?;
+            Ok(PARQUET_MAGIC)
+        }
+    }
+
     pub fn new(
         buf: &'a mut TrackedWrite<W>,
         schema: &'a TypePtr,
@@ -165,6 +267,8 @@ impl<'a, W: Write> ThriftMetadataWriter<'a, W> {
             key_value_metadata: None,
             created_by,
             writer_version,
+            #[cfg(feature = "encryption")]
+            file_encryptor: None,
         }
     }
 
@@ -182,6 +286,20 @@ impl<'a, W: Write> ThriftMetadataWriter<'a, W> {
         self.key_value_metadata = Some(key_value_metadata);
         self
     }
+
+    #[cfg(feature = "encryption")]
+    pub fn with_file_encryptor(mut self, file_encryptor: Option<Arc<FileEncryptor>>) -> Self {
+        self.file_encryptor = file_encryptor;
+        self
+    }
+}
+
+#[cfg(feature = "encryption")]
+fn serialize_file_metadata(file_metadata: &crate::format::FileMetaData) -> Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    let mut protocol = TCompactOutputProtocol::new(&mut buffer);
+    file_metadata.write_to_out_protocol(&mut protocol)?;
+    Ok(buffer)
 }
 
 /// Writes [`ParquetMetaData`] to a byte stream
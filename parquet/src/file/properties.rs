@@ -21,6 +21,8 @@ use std::{collections::HashMap, sync::Arc};
 
 use crate::basic::{Compression, Encoding};
 use crate::compression::{CodecOptions, CodecOptionsBuilder};
+#[cfg(feature = "encryption")]
+use crate::encryption::encrypt::FileEncryptionProperties;
 use crate::file::metadata::KeyValue;
 use crate::format::SortingColumn;
 use crate::schema::types::ColumnPath;
@@ -57,6 +59,12 @@ pub const DEFAULT_BLOOM_FILTER_FPP: f64 = 0.05;
 pub const DEFAULT_BLOOM_FILTER_NDV: u64 = 1_000_000_u64;
 /// Default values for [`WriterProperties::statistics_truncate_length`]
 pub const DEFAULT_STATISTICS_TRUNCATE_LENGTH: Option<usize> = None;
+/// Default value for [`CdcOptions::min_chunk_size`]
+pub const DEFAULT_CDC_MIN_CHUNK_SIZE: usize = 256 * 1024;
+/// Default value for [`CdcOptions::max_chunk_size`]
+pub const DEFAULT_CDC_MAX_CHUNK_SIZE: usize = 1024 * 1024;
+/// Default value for [`CdcOptions::norm_level`]
+pub const DEFAULT_CDC_NORM_LEVEL: i32 = 0;
 
 /// Parquet writer version.
 ///
@@ -163,6 +171,9 @@ pub struct WriterProperties {
     sorting_columns: Option<Vec<SortingColumn>>,
     column_index_truncate_length: Option<usize>,
     statistics_truncate_length: Option<usize>,
+    content_defined_chunking: Option<CdcOptions>,
+    #[cfg(feature = "encryption")]
+    file_encryption_properties: Option<FileEncryptionProperties>,
 }
 
 impl Default for WriterProperties {
@@ -228,6 +239,13 @@ impl WriterProperties {
         self.data_page_row_count_limit
     }
 
+    /// Returns the options for content-defined chunking of data pages, if enabled
+    ///
+    /// For more details see [`WriterPropertiesBuilder::set_content_defined_chunking`]
+    pub fn content_defined_chunking(&self) -> Option<&CdcOptions> {
+        self.content_defined_chunking.as_ref()
+    }
+
     /// Returns configured batch size for writes.
     ///
     /// When writing a batch of data, this setting allows to split it internally into
@@ -281,6 +299,14 @@ impl WriterProperties {
         self.statistics_truncate_length
     }
 
+    /// Returns the properties used to encrypt the file, if any
+    ///
+    /// For more details see [`WriterPropertiesBuilder::with_file_encryption_properties`]
+    #[cfg(feature = "encryption")]
+    pub fn file_encryption_properties(&self) -> Option<&FileEncryptionProperties> {
+        self.file_encryption_properties.as_ref()
+    }
+
     /// Returns encoding for a data page, when dictionary encoding is enabled.
     /// This is not configurable.
     #[inline]
@@ -377,6 +403,9 @@ pub struct WriterPropertiesBuilder {
     sorting_columns: Option<Vec<SortingColumn>>,
     column_index_truncate_length: Option<usize>,
     statistics_truncate_length: Option<usize>,
+    content_defined_chunking: Option<CdcOptions>,
+    #[cfg(feature = "encryption")]
+    file_encryption_properties: Option<FileEncryptionProperties>,
 }
 
 impl WriterPropertiesBuilder {
@@ -397,6 +426,9 @@ impl WriterPropertiesBuilder {
             sorting_columns: None,
             column_index_truncate_length: DEFAULT_COLUMN_INDEX_TRUNCATE_LENGTH,
             statistics_truncate_length: DEFAULT_STATISTICS_TRUNCATE_LENGTH,
+            content_defined_chunking: None,
+            #[cfg(feature = "encryption")]
+            file_encryption_properties: None,
         }
     }
 
@@ -417,6 +449,9 @@ impl WriterPropertiesBuilder {
             sorting_columns: self.sorting_columns,
             column_index_truncate_length: self.column_index_truncate_length,
             statistics_truncate_length: self.statistics_truncate_length,
+            content_defined_chunking: self.content_defined_chunking,
+            #[cfg(feature = "encryption")]
+            file_encryption_properties: self.file_encryption_properties,
         }
     }
 
@@ -471,6 +506,35 @@ impl WriterPropertiesBuilder {
         self
     }
 
+    /// Sets the options for content-defined chunking of data pages (defaults to `None`).
+    ///
+    /// When enabled, [`ArrowWriter`] additionally ends data pages at boundaries
+    /// determined by a rolling hash over the logical values of each column, instead of
+    /// only at fixed size and row count limits. Inserting or removing rows then only
+    /// changes the pages around the modification, with the pages of unchanged regions
+    /// being byte-identical between versions of a file, allowing them to be
+    /// deduplicated by content-addressable storage.
+    ///
+    /// Row groups are still ended by [`set_max_row_group_size`](Self::set_max_row_group_size)
+    /// and [`ArrowWriter::flush`], and the chunking state is reset at the start of each
+    /// row group.
+    ///
+    /// For best results:
+    ///
+    /// * [`set_data_page_size_limit`](Self::set_data_page_size_limit) and
+    ///   [`set_data_page_row_count_limit`](Self::set_data_page_row_count_limit) should
+    ///   be larger than [`CdcOptions::max_chunk_size`], so that pages are only ended at
+    ///   content-defined boundaries
+    /// * dictionary encoding should be disabled, as the dictionary indices stored in
+    ///   data pages depend on all previously written values of the column chunk
+    ///
+    /// [`ArrowWriter`]: crate::arrow::ArrowWriter
+    /// [`ArrowWriter::flush`]: crate::arrow::ArrowWriter::flush
+    pub fn set_content_defined_chunking(mut self, value: Option<CdcOptions>) -> Self {
+        self.content_defined_chunking = value;
+        self
+    }
+
     /// Sets best effort maximum dictionary page size, in bytes.
     ///
     /// Note: this is a best effort limit based on value of
@@ -767,6 +831,19 @@ impl WriterPropertiesBuilder {
         self.statistics_truncate_length = max_length;
         self
     }
+
+    /// Sets the [`FileEncryptionProperties`] used to write the file with
+    /// Parquet modular encryption (defaults to `None`, no encryption)
+    ///
+    /// Bloom filters of encrypted columns are written encrypted, like their pages
+    #[cfg(feature = "encryption")]
+    pub fn with_file_encryption_properties(
+        mut self,
+        file_encryption_properties: FileEncryptionProperties,
+    ) -> Self {
+        self.file_encryption_properties = Some(file_encryption_properties);
+        self
+    }
 }
 
 /// Controls the level of statistics to be computed by the writer and stored in
@@ -852,6 +929,33 @@ impl Default for BloomFilterProperties {
     }
 }
 
+/// Controls content-defined chunking of data pages, see
+/// [`WriterPropertiesBuilder::set_content_defined_chunking`]
+///
+/// Chunk sizes are measured in bytes of logical values, including repetition and
+/// definition levels, prior to encoding and compression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CdcOptions {
+    /// The minimum size of a chunk. Defaults to [`DEFAULT_CDC_MIN_CHUNK_SIZE`].
+    pub min_chunk_size: usize,
+    /// The maximum size of a chunk, a chunk is ended at the next possible row once
+    /// this is reached. Defaults to [`DEFAULT_CDC_MAX_CHUNK_SIZE`].
+    pub max_chunk_size: usize,
+    /// Adjusts the likelihood of a boundary, with positive values producing smaller
+    /// chunks and negative values larger chunks. Defaults to [`DEFAULT_CDC_NORM_LEVEL`].
+    pub norm_level: i32,
+}
+
+impl Default for CdcOptions {
+    fn default() -> Self {
+        Self {
+            min_chunk_size: DEFAULT_CDC_MIN_CHUNK_SIZE,
+            max_chunk_size: DEFAULT_CDC_MAX_CHUNK_SIZE,
+            norm_level: DEFAULT_CDC_NORM_LEVEL,
+        }
+    }
+}
+
 /// Container for column properties that can be changed as part of writer.
 ///
 /// If a field is `None`, it means that no specific value has been set for this column,
@@ -1095,6 +1199,7 @@ mod tests {
         assert_eq!(props.writer_version(), DEFAULT_WRITER_VERSION);
         assert_eq!(props.created_by(), DEFAULT_CREATED_BY);
         assert_eq!(props.key_value_metadata(), None);
+        assert_eq!(props.content_defined_chunking(), None);
         assert_eq!(props.encoding(&ColumnPath::from("col")), None);
         assert_eq!(
             props.compression(&ColumnPath::from("col")),
@@ -26,6 +26,8 @@ use crate::basic::{Encoding, Type};
 use crate::bloom_filter::Sbbf;
 use crate::column::page::{Page, PageMetadata, PageReader};
 use crate::compression::{create_codec, Codec};
+#[cfg(feature = "encryption")]
+use crate::encryption::decrypt::{CryptoContext, FileDecryptionProperties, PageDecryptor};
 use crate::errors::{ParquetError, Result};
 use crate::file::page_index::offset_index::OffsetIndexMetaData;
 use crate::file::{
@@ -109,6 +111,8 @@ pub struct ReadOptionsBuilder {
     predicates: Vec<ReadGroupPredicate>,
     enable_page_index: bool,
     props: Option<ReaderProperties>,
+    #[cfg(feature = "encryption")]
+    file_decryption_properties: Option<FileDecryptionProperties>,
 }
 
 impl ReadOptionsBuilder {
@@ -151,6 +155,13 @@ impl ReadOptionsBuilder {
         self
     }
 
+    /// Set the [`FileDecryptionProperties`] used to read an encrypted file
+    #[cfg(feature = "encryption")]
+    pub fn with_file_decryption_properties(mut self, properties: FileDecryptionProperties) -> Self {
+        self.file_decryption_properties = Some(properties);
+        self
+    }
+
     /// Seal the builder and return the read options
     pub fn build(self) -> ReadOptions {
         let props = self
@@ -160,6 +171,8 @@ impl ReadOptionsBuilder {
             predicates: self.predicates,
             enable_page_index: self.enable_page_index,
             props,
+            #[cfg(feature = "encryption")]
+            file_decryption_properties: self.file_decryption_properties,
         }
     }
 }
@@ -172,6 +185,8 @@ pub struct ReadOptions {
     predicates: Vec<ReadGroupPredicate>,
     enable_page_index: bool,
     props: ReaderProperties,
+    #[cfg(feature = "encryption")]
+    file_decryption_properties: Option<FileDecryptionProperties>,
 }
 
 impl<R: 'static + ChunkReader> SerializedFileReader<R> {
@@ -190,7 +205,11 @@ impl<R: 'static + ChunkReader> SerializedFileReader<R> {
     /// Creates file reader from a Parquet file with read options.
     /// Returns error if Parquet file does not exist or is corrupt.
     pub fn new_with_options(chunk_reader: R, options: ReadOptions) -> Result<Self> {
-        let mut metadata_builder = ParquetMetaDataReader::new()
+        let metadata_reader = ParquetMetaDataReader::new();
+        #[cfg(feature = "encryption")]
+        let metadata_reader =
+            metadata_reader.with_decryption_properties(options.file_decryption_properties.as_ref());
+        let mut metadata_builder = metadata_reader
             .parse_and_finish(&chunk_reader)?
             .into_builder();
         let mut predicates = options.predicates;
@@ -253,12 +272,15 @@ impl<R: 'static + ChunkReader> FileReader for SerializedFileReader<R> {
         // Row groups should be processed sequentially.
         let props = Arc::clone(&self.props);
         let f = Arc::clone(&self.chunk_reader);
-        Ok(Box::new(SerializedRowGroupReader::new(
+        let reader = SerializedRowGroupReader::new(
             f,
             row_group_metadata,
             self.metadata.offset_index().map(|x| x[i].as_slice()),
             props,
-        )?))
+        )?;
+        #[cfg(feature = "encryption")]
+        let reader = reader.with_parquet_metadata(&self.metadata, i)?;
+        Ok(Box::new(reader))
     }
 
     fn get_row_iter(&self, projection: Option<SchemaType>) -> Result<RowIter> {
@@ -273,6 +295,9 @@ pub struct SerializedRowGroupReader<'a, R: ChunkReader> {
     offset_index: Option<&'a [OffsetIndexMetaData]>,
     props: ReaderPropertiesPtr,
     bloom_filters: Vec<Option<Sbbf>>,
+    /// The metadata of the file and the index of this row group within it
+    #[cfg(feature = "encryption")]
+    parquet_metadata: Option<(&'a ParquetMetaData, usize)>,
 }
 
 impl<'a, R: ChunkReader> SerializedRowGroupReader<'a, R> {
@@ -287,7 +312,7 @@ impl<'a, R: ChunkReader> SerializedRowGroupReader<'a, R> {
             metadata
                 .columns()
                 .iter()
-                .map(|col| Sbbf::read_from_column_chunk(col, chunk_reader.clone()))
+                .map(|col| Sbbf::read_from_column_chunk(col, chunk_reader.as_ref()))
                 .collect::<Result<Vec<_>>>()?
         } else {
             iter::repeat(None).take(metadata.columns().len()).collect()
@@ -298,8 +323,33 @@ impl<'a, R: ChunkReader> SerializedRowGroupReader<'a, R> {
             offset_index,
             props,
             bloom_filters,
+            #[cfg(feature = "encryption")]
+            parquet_metadata: None,
         })
     }
+
+    /// Provide the metadata of the file, and the index of this row group within it,
+    /// used to decrypt the pages and bloom filters of encrypted columns
+    #[cfg(feature = "encryption")]
+    pub(crate) fn with_parquet_metadata(
+        mut self,
+        metadata: &'a ParquetMetaData,
+        row_group_idx: usize,
+    ) -> Result<Self> {
+        if self.props.read_bloom_filter() && metadata.file_decryptor().is_some() {
+            for (i, col) in self.metadata.columns().iter().enumerate() {
+                if let Some(context) = CryptoContext::for_column(metadata, row_group_idx, i)? {
+                    self.bloom_filters[i] = Sbbf::read_encrypted_from_column_chunk(
+                        col,
+                        self.chunk_reader.as_ref(),
+                        &context,
+                    )?;
+                }
+            }
+        }
+        self.parquet_metadata = Some((metadata, row_group_idx));
+        Ok(self)
+    }
 }
 
 impl<R: 'static + ChunkReader> RowGroupReader for SerializedRowGroupReader<'_, R> {
@@ -318,13 +368,21 @@ impl<R: 'static + ChunkReader> RowGroupReader for SerializedRowGroupReader<'_, R
         let page_locations = self.offset_index.map(|x| x[i].page_locations.clone());
 
         let props = Arc::clone(&self.props);
-        Ok(Box::new(SerializedPageReader::new_with_properties(
+        let reader = SerializedPageReader::new_with_properties(
             Arc::clone(&self.chunk_reader),
             col,
             self.metadata.num_rows() as usize,
             page_locations,
             props,
-        )?))
+        )?;
+        #[cfg(feature = "encryption")]
+        let reader = match self.parquet_metadata {
+            Some((metadata, rg_idx)) => {
+                reader.with_crypto_context(CryptoContext::for_column(metadata, rg_idx, i)?)
+            }
+            None => reader,
+        };
+        Ok(Box::new(reader))
     }
 
     /// get bloom filter for the `i`th column
@@ -512,6 +570,9 @@ pub struct SerializedPageReader<R: ChunkReader> {
     physical_type: Type,
 
     state: SerializedPageReaderState,
+
+    /// Decrypts the pages of an encrypted column chunk
+    page_decryptor: Option<PageDecryptor>,
 }
 
 impl<R: ChunkReader> SerializedPageReader<R> {
@@ -566,8 +627,44 @@ impl<R: ChunkReader> SerializedPageReader<R> {
             decompressor,
             state,
             physical_type: meta.column_type(),
+            page_decryptor: None,
         })
     }
+
+    /// Decrypt the pages of this column chunk with `context`, if it is encrypted
+    #[cfg(feature = "encryption")]
+    pub(crate) fn with_crypto_context(mut self, context: Option<CryptoContext>) -> Self {
+        self.page_decryptor = context.map(PageDecryptor::new);
+        self
+    }
+}
+
+/// Placeholder for the page decryptor when the `encryption` feature is disabled,
+/// no page reader is ever created with one
+#[cfg(not(feature = "encryption"))]
+enum PageDecryptor {}
+
+#[cfg(not(feature = "encryption"))]
+impl PageDecryptor {
+    fn read_page_header<T: Read>(&self, _input: &mut T) -> Result<(usize, PageHeader)> {
+        match *self {}
+    }
+
+    fn decode_page_header(&self, _buffer: &[u8]) -> Result<(usize, PageHeader)> {
+        match *self {}
+    }
+
+    fn decrypt_page(&self, _header: &PageHeader, _buffer: &[u8]) -> Result<Bytes> {
+        match *self {}
+    }
+
+    fn advance(&mut self, _header: &PageHeader) {
+        match *self {}
+    }
+
+    fn skip_data_page(&mut self) {
+        match *self {}
+    }
 }
 
 impl<R: ChunkReader> Iterator for SerializedPageReader<R> {
@@ -595,7 +692,10 @@ impl<R: ChunkReader> PageReader for SerializedPageReader<R> {
                     let header = if let Some(header) = next_page_header.take() {
                         *header
                     } else {
-                        let (header_len, header) = read_page_header_len(&mut read)?;
+                        let (header_len, header) = match &self.page_decryptor {
+                            Some(decryptor) => decryptor.read_page_header(&mut read)?,
+                            None => read_page_header_len(&mut read)?,
+                        };
                         *offset += header_len;
                         *remaining -= header_len;
                         header
@@ -619,9 +719,18 @@ impl<R: ChunkReader> PageReader for SerializedPageReader<R> {
                         ));
                     }
 
+                    let buffer = match &mut self.page_decryptor {
+                        Some(decryptor) => {
+                            let buffer = decryptor.decrypt_page(&header, &buffer)?;
+                            decryptor.advance(&header);
+                            buffer
+                        }
+                        None => Bytes::from(buffer),
+                    };
+
                     decode_page(
                         header,
-                        Bytes::from(buffer),
+                        buffer,
                         self.physical_type,
                         self.decompressor.as_mut(),
                     )?
@@ -643,11 +752,20 @@ impl<R: ChunkReader> PageReader for SerializedPageReader<R> {
 
                     let buffer = self.reader.get_bytes(front.offset as u64, page_len)?;
 
-                    let mut prot = TCompactSliceInputProtocol::new(buffer.as_ref());
-                    let header = PageHeader::read_from_in_protocol(&mut prot)?;
-                    let offset = buffer.len() - prot.as_slice().len();
-
-                    let bytes = buffer.slice(offset..);
+                    let (header, bytes) = match &mut self.page_decryptor {
+                        Some(decryptor) => {
+                            let (offset, header) = decryptor.decode_page_header(&buffer)?;
+                            let bytes = decryptor.decrypt_page(&header, &buffer[offset..])?;
+                            decryptor.advance(&header);
+                            (header, bytes)
+                        }
+                        None => {
+                            let mut prot = TCompactSliceInputProtocol::new(buffer.as_ref());
+                            let header = PageHeader::read_from_in_protocol(&mut prot)?;
+                            let offset = buffer.len() - prot.as_slice().len();
+                            (header, buffer.slice(offset..))
+                        }
+                    };
                     decode_page(
                         header,
                         bytes,
@@ -682,7 +800,10 @@ impl<R: ChunkReader> PageReader for SerializedPageReader<R> {
                         }
                     } else {
                         let mut read = self.reader.get_read(*offset as u64)?;
-                        let (header_len, header) = read_page_header_len(&mut read)?;
+                        let (header_len, header) = match &self.page_decryptor {
+                            Some(decryptor) => decryptor.read_page_header(&mut read)?,
+                            None => read_page_header_len(&mut read)?,
+                        };
                         *offset += header_len;
                         *remaining_bytes -= header_len;
                         let page_meta = if let Ok(page_meta) = (&header).try_into() {
@@ -736,9 +857,19 @@ impl<R: ChunkReader> PageReader for SerializedPageReader<R> {
                     // The next page header has already been peeked, so just advance the offset
                     *offset += buffered_header.compressed_page_size as usize;
                     *remaining_bytes -= buffered_header.compressed_page_size as usize;
+                    if let Some(decryptor) = &mut self.page_decryptor {
+                        decryptor.advance(&buffered_header);
+                    }
                 } else {
                     let mut read = self.reader.get_read(*offset as u64)?;
-                    let (header_len, header) = read_page_header_len(&mut read)?;
+                    let (header_len, header) = match &mut self.page_decryptor {
+                        Some(decryptor) => {
+                            let (header_len, header) = decryptor.read_page_header(&mut read)?;
+                            decryptor.advance(&header);
+                            (header_len, header)
+                        }
+                        None => read_page_header_len(&mut read)?,
+                    };
                     let data_page_size = header.compressed_page_size as usize;
                     *offset += header_len + data_page_size;
                     *remaining_bytes -= header_len + data_page_size;
@@ -746,7 +877,11 @@ impl<R: ChunkReader> PageReader for SerializedPageReader<R> {
                 Ok(())
             }
             SerializedPageReaderState::Pages { page_locations, .. } => {
-                page_locations.pop_front();
+                if page_locations.pop_front().is_some() {
+                    if let Some(decryptor) = &mut self.page_decryptor {
+                        decryptor.skip_data_page();
+                    }
+                }
 
                 Ok(())
             }
@@ -33,9 +33,13 @@ use crate::column::{
     writer::{get_column_writer, ColumnWriter},
 };
 use crate::data_type::DataType;
+#[cfg(feature = "encryption")]
+use crate::encryption::encrypt::{FileEncryptor, PageEncryptor};
 use crate::errors::{ParquetError, Result};
 use crate::file::properties::{BloomFilterPosition, WriterPropertiesPtr};
 use crate::file::reader::ChunkReader;
+#[cfg(feature = "encryption")]
+use crate::file::PARQUET_MAGIC_ENCR_FOOTER;
 use crate::file::{metadata::*, PARQUET_MAGIC};
 use crate::schema::types::{ColumnDescPtr, SchemaDescPtr, SchemaDescriptor, TypePtr};
 
@@ -153,6 +157,8 @@ pub struct SerializedFileWriter<W: Write> {
     // kv_metadatas will be appended to `props` when `write_metadata`
     kv_metadatas: Vec<KeyValue>,
     finished: bool,
+    #[cfg(feature = "encryption")]
+    file_encryptor: Option<Arc<FileEncryptor>>,
 }
 
 impl<W: Write> Debug for SerializedFileWriter<W> {
@@ -171,7 +177,14 @@ impl<W: Write + Send> SerializedFileWriter<W> {
     /// Creates new file writer.
     pub fn new(buf: W, schema: TypePtr, properties: WriterPropertiesPtr) -> Result<Self> {
         let mut buf = TrackedWrite::new(buf);
-        Self::start_file(&mut buf)?;
+        Self::start_file(&mut buf, &properties)?;
+        #[cfg(feature = "encryption")]
+        let file_encryptor = match properties.file_encryption_properties() {
+            Some(file_encryption_properties) => Some(Arc::new(FileEncryptor::new(
+                file_encryption_properties.clone(),
+            )?)),
+            None => None,
+        };
         Ok(Self {
             buf,
             schema: schema.clone(),
@@ -184,6 +197,8 @@ impl<W: Write + Send> SerializedFileWriter<W> {
             row_group_index: 0,
             kv_metadatas: Vec::new(),
             finished: false,
+            #[cfg(feature = "encryption")]
+            file_encryptor,
         })
     }
 
@@ -215,6 +230,8 @@ impl<W: Write + Send> SerializedFileWriter<W> {
         let row_bloom_filters = &mut self.bloom_filters;
         let row_column_indexes = &mut self.column_indexes;
         let row_offset_indexes = &mut self.offset_indexes;
+        #[cfg(feature = "encryption")]
+        let file_encryptor = self.file_encryptor.clone();
         let on_close = move |buf,
                              mut metadata,
                              row_group_bloom_filter,
@@ -225,9 +242,13 @@ impl<W: Write + Send> SerializedFileWriter<W> {
             row_offset_indexes.push(row_group_offset_index);
             // write bloom filters out immediately after the row group if requested
             match bloom_filter_position {
-                BloomFilterPosition::AfterRowGroup => {
-                    write_bloom_filters(buf, row_bloom_filters, &mut metadata)?
-                }
+                BloomFilterPosition::AfterRowGroup => write_bloom_filters(
+                    buf,
+                    row_bloom_filters,
+                    &mut metadata,
+                    #[cfg(feature = "encryption")]
+                    file_encryptor.as_deref(),
+                )?,
                 BloomFilterPosition::End => (),
             };
             row_groups.push(metadata);
@@ -241,6 +262,8 @@ impl<W: Write + Send> SerializedFileWriter<W> {
             ordinal,
             Some(Box::new(on_close)),
         );
+        #[cfg(feature = "encryption")]
+        let row_group_writer = row_group_writer.with_file_encryptor(self.file_encryptor.clone());
         Ok(row_group_writer)
     }
 
@@ -267,8 +290,8 @@ impl<W: Write + Send> SerializedFileWriter<W> {
     }
 
     /// Writes magic bytes at the beginning of the file.
-    fn start_file(buf: &mut TrackedWrite<W>) -> Result<()> {
-        buf.write_all(&PARQUET_MAGIC)?;
+    fn start_file(buf: &mut TrackedWrite<W>, properties: &WriterPropertiesPtr) -> Result<()> {
+        buf.write_all(&get_file_magic(properties))?;
         Ok(())
     }
 
@@ -278,7 +301,13 @@ impl<W: Write + Send> SerializedFileWriter<W> {
 
         // write out any remaining bloom filters after all row groups
         for row_group in &mut self.row_groups {
-            write_bloom_filters(&mut self.buf, &mut self.bloom_filters, row_group)?;
+            write_bloom_filters(
+                &mut self.buf,
+                &mut self.bloom_filters,
+                row_group,
+                #[cfg(feature = "encryption")]
+                self.file_encryptor.as_deref(),
+            )?;
         }
 
         let key_value_metadata = match self.props.key_value_metadata() {
@@ -306,6 +335,10 @@ impl<W: Write + Send> SerializedFileWriter<W> {
         }
         encoder = encoder.with_column_indexes(&self.column_indexes);
         encoder = encoder.with_offset_indexes(&self.offset_indexes);
+        #[cfg(feature = "encryption")]
+        {
+            encoder = encoder.with_file_encryptor(self.file_encryptor.clone());
+        }
         encoder.finish()
     }
 
@@ -361,14 +394,38 @@ impl<W: Write + Send> SerializedFileWriter<W> {
     pub fn bytes_written(&self) -> usize {
         self.buf.bytes_written()
     }
+
+    /// Returns the [`FileEncryptor`] of this file, if it is encrypted
+    #[cfg(feature = "encryption")]
+    pub(crate) fn file_encryptor(&self) -> Option<Arc<FileEncryptor>> {
+        self.file_encryptor.clone()
+    }
+}
+
+/// Returns the magic bytes that start a file written with `properties`
+#[cfg(feature = "encryption")]
+fn get_file_magic(properties: &WriterPropertiesPtr) -> [u8; 4] {
+    match properties.file_encryption_properties() {
+        Some(p) if p.encrypt_footer() => PARQUET_MAGIC_ENCR_FOOTER,
+        _ => PARQUET_MAGIC,
+    }
+}
+
+/// Returns the magic bytes that start a file written with `_properties`
+#[cfg(not(feature = "encryption"))]
+fn get_file_magic(_properties: &WriterPropertiesPtr) -> [u8; 4] {
+    PARQUET_MAGIC
 }
 
 /// Serialize all the bloom filters of the given row group to the given buffer,
 /// and returns the updated row group metadata.
+///
+/// The bloom filters of encrypted columns are encrypted with `file_encryptor`
 fn write_bloom_filters<W: Write + Send>(
     buf: &mut TrackedWrite<W>,
     bloom_filters: &mut [Vec<Option<Sbbf>>],
     row_group: &mut RowGroupMetaData,
+    #[cfg(feature = "encryption")] file_encryptor: Option<&FileEncryptor>,
 ) -> Result<()> {
     // iter row group
     // iter each column
@@ -388,7 +445,22 @@ fn write_bloom_filters<W: Write + Send>(
     for (column_idx, column_chunk) in row_group.columns_mut().iter_mut().enumerate() {
         if let Some(bloom_filter) = bloom_filters[row_group_idx][column_idx].take() {
             let start_offset = buf.bytes_written();
-            bloom_filter.write(&mut *buf)?;
+            #[cfg(feature = "encryption")]
+            let encrypted = match file_encryptor {
+                Some(file_encryptor) => file_encryptor.encrypt_bloom_filter(
+                    &bloom_filter,
+                    &column_chunk.column_path().string(),
+                    row_group_idx,
+                    column_idx,
+                )?,
+                None => None,
+            };
+            #[cfg(not(feature = "encryption"))]
+            let encrypted: Option<Vec<u8>> = None;
+            match encrypted {
+                Some(encrypted) => buf.write_all(&encrypted)?,
+                None => bloom_filter.write(&mut *buf)?,
+            }
             let end_offset = buf.bytes_written();
             // set offset and index for bloom filter
             *column_chunk = column_chunk
@@ -429,6 +501,8 @@ pub struct SerializedRowGroupWriter<'a, W: Write> {
     row_group_index: i16,
     file_offset: i64,
     on_close: Option<OnCloseRowGroup<'a, W>>,
+    #[cfg(feature = "encryption")]
+    file_encryptor: Option<Arc<FileEncryptor>>,
 }
 
 impl<'a, W: Write + Send> SerializedRowGroupWriter<'a, W> {
@@ -465,9 +539,44 @@ impl<'a, W: Write + Send> SerializedRowGroupWriter<'a, W> {
             offset_indexes: Vec::with_capacity(num_columns),
             total_bytes_written: 0,
             total_uncompressed_bytes: 0,
+            #[cfg(feature = "encryption")]
+            file_encryptor: None,
         }
     }
 
+    /// Encrypts the columns of this row group with `file_encryptor`
+    #[cfg(feature = "encryption")]
+    pub(crate) fn with_file_encryptor(
+        mut self,
+        file_encryptor: Option<Arc<FileEncryptor>>,
+    ) -> Self {
+        self.file_encryptor = file_encryptor;
+        self
+    }
+
+    /// Returns the [`PageEncryptor`] for the column at `column_ordinal`, if it is encrypted
+    #[cfg(feature = "encryption")]
+    fn page_encryptor(
+        &self,
+        column: &ColumnDescPtr,
+        column_ordinal: usize,
+    ) -> Option<PageEncryptor> {
+        self.file_encryptor.as_ref()?.page_encryptor(
+            &column.path().string(),
+            self.row_group_index as usize,
+            column_ordinal,
+        )
+    }
+
+    /// Returns true if the column with `column_path` is encrypted
+    #[cfg(feature = "encryption")]
+    fn is_column_encrypted(&self, column_path: &str) -> bool {
+        self.file_encryptor
+            .as_ref()
+            .map(|e| e.properties().is_column_encrypted(column_path))
+            .unwrap_or(false)
+    }
+
     /// Advance `self.column_index` returning the next [`ColumnDescPtr`] if any
     fn next_column_desc(&mut self) -> Option<ColumnDescPtr> {
         let ret = self.descr.columns().get(self.column_index)?.clone();
@@ -526,8 +635,13 @@ impl<'a, W: Write + Send> SerializedRowGroupWriter<'a, W> {
         Ok(match self.next_column_desc() {
             Some(column) => {
                 let props = self.props.clone();
+                #[cfg(feature = "encryption")]
+                let page_encryptor = self.page_encryptor(&column, self.column_index - 1);
                 let (buf, on_close) = self.get_on_close();
-                let page_writer = Box::new(SerializedPageWriter::new(buf));
+                let page_writer = SerializedPageWriter::new(buf);
+                #[cfg(feature = "encryption")]
+                let page_writer = page_writer.with_page_encryptor(page_encryptor);
+                let page_writer = Box::new(page_writer);
                 Some(factory(column, props, page_writer, Box::new(on_close))?)
             }
             None => None,
@@ -551,6 +665,27 @@ impl<'a, W: Write + Send> SerializedRowGroupWriter<'a, W> {
     ///
     /// See [`Self::next_column`] for writing data that isn't already encoded
     pub fn append_column<R: ChunkReader>(
+        &mut self,
+        reader: &R,
+        close: ColumnCloseResult,
+    ) -> Result<()> {
+        #[cfg(feature = "encryption")]
+        if let Some(desc) = self.descr.columns().get(self.column_index) {
+            if self.is_column_encrypted(&desc.path().string()) {
+                return Err(general_err!(
+                    "Appending column chunks is not supported for encrypted column {}",
+                    desc.path()
+                ));
+            }
+        }
+        self.splice_column(reader, close)
+    }
+
+    /// Append an encoded column chunk without checking its encryption
+    ///
+    /// If the column is encrypted, the chunk must have been encrypted for this
+    /// row group of this file
+    pub(crate) fn splice_column<R: ChunkReader>(
         &mut self,
         reader: &R,
         mut close: ColumnCloseResult,
@@ -699,12 +834,41 @@ impl<'a> SerializedColumnWriter<'a> {
 /// `SerializedPageWriter` should not be used after calling `close()`.
 pub struct SerializedPageWriter<'a, W: Write> {
     sink: &'a mut TrackedWrite<W>,
+    #[cfg(feature = "encryption")]
+    page_encryptor: Option<PageEncryptor>,
 }
 
 impl<'a, W: Write> SerializedPageWriter<'a, W> {
     /// Creates new page writer.
     pub fn new(sink: &'a mut TrackedWrite<W>) -> Self {
-        Self { sink }
+        Self {
+            sink,
+            #[cfg(feature = "encryption")]
+            page_encryptor: None,
+        }
+    }
+
+    /// Encrypts the written pages with `page_encryptor`
+    #[cfg(feature = "encryption")]
+    pub(crate) fn with_page_encryptor(mut self, page_encryptor: Option<PageEncryptor>) -> Self {
+        self.page_encryptor = page_encryptor;
+        self
+    }
+
+    /// Writes the header and data of `page`, encrypting them if configured.
+    /// Returns the number of bytes written for the header and the data.
+    fn write_page_parts(&mut self, page: &CompressedPage) -> Result<(usize, usize)> {
+        #[cfg(feature = "encryption")]
+        if let Some(page_encryptor) = self.page_encryptor.as_mut() {
+            let (header, data) = page_encryptor.encrypt_compressed_page(page)?;
+            self.sink.write_all(&header)?;
+            self.sink.write_all(&data)?;
+            return Ok((header.len(), data.len()));
+        }
+
+        let header_size = self.serialize_page_header(page.to_thrift_header())?;
+        self.sink.write_all(page.data())?;
+        Ok((header_size, page.compressed_size()))
     }
 
     /// Serializes page header into Thrift.
@@ -725,14 +889,12 @@ impl<W: Write + Send> PageWriter for SerializedPageWriter<'_, W> {
         let page_type = page.page_type();
         let start_pos = self.sink.bytes_written() as u64;
 
-        let page_header = page.to_thrift_header();
-        let header_size = self.serialize_page_header(page_header)?;
-        self.sink.write_all(page.data())?;
+        let (header_size, data_size) = self.write_page_parts(&page)?;
 
         let mut spec = PageWriteSpec::new();
         spec.page_type = page_type;
         spec.uncompressed_size = page.uncompressed_size() + header_size;
-        spec.compressed_size = page.compressed_size() + header_size;
+        spec.compressed_size = data_size + header_size;
         spec.offset = start_pos;
         spec.bytes_written = self.sink.bytes_written() as u64 - start_pos;
         spec.num_values = page.num_values();
@@ -137,6 +137,8 @@ pub mod column;
 experimental!(mod compression);
 experimental!(mod encodings);
 pub mod bloom_filter;
+#[cfg(feature = "encryption")]
+pub mod encryption;
 pub mod file;
 pub mod record;
 pub mod schema;